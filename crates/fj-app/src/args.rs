@@ -1,6 +1,7 @@
 use std::{path::PathBuf, str::FromStr as _};
 
 use anyhow::anyhow;
+use fj_export::{CoordinateSystem, ExportOptions, Format};
 use fj_host::Parameters;
 use fj_kernel::algorithms::approx::Tolerance;
 use fj_math::Scalar;
@@ -10,12 +11,76 @@ use fj_math::Scalar;
 #[command(version = fj::version::VERSION_FULL.to_string())]
 pub struct Args {
     /// The model to open
-    pub model: Option<PathBuf>,
+    ///
+    /// This is either the directory of a model crate, or (with the `script`
+    /// feature enabled) the path of a `.rhai` script. If several models are
+    /// given, they are opened together as the parts of an assembly, each of
+    /// which can be moved and hidden separately.
+    pub model: Vec<PathBuf>,
 
     /// Export model to this path
     #[arg(short, long, value_name = "PATH")]
     pub export: Option<PathBuf>,
 
+    /// The axis that points up in the exported file
+    ///
+    /// Defaults to the convention of the file format.
+    #[arg(long, value_enum, requires = "export")]
+    pub up_axis: Option<UpAxis>,
+
+    /// The handedness of the exported file's coordinate system
+    ///
+    /// Defaults to the convention of the file format.
+    #[arg(long, value_enum, requires = "export")]
+    pub handedness: Option<Handedness>,
+
+    /// Print the mass properties of the model and exit
+    ///
+    /// Can be combined with `--export`, to export the model too.
+    #[arg(long, conflicts_with_all = ["batch", "validate"])]
+    pub analyze: bool,
+
+    /// Print the dimensions of the model and its bodies, and exit
+    ///
+    /// Can be combined with `--export` and `--analyze`.
+    #[arg(long, conflicts_with_all = ["batch", "validate"])]
+    pub dimensions: bool,
+
+    /// Print the overhang area that needs support when 3D-printing, and exit
+    ///
+    /// Assumes that the model is printed along the z-axis. Can be combined
+    /// with `--export`, `--analyze`, and `--dimensions`.
+    #[arg(long, conflicts_with_all = ["batch", "validate"])]
+    pub overhangs: bool,
+
+    /// Compare the model to a mesh that was exported before, and exit
+    ///
+    /// Prints the difference in volume, the Hausdorff distance, and the regions
+    /// in which the surfaces are further apart than the tolerance allows. Exits
+    /// with a non-zero exit code, if there are any. Use this to verify that a
+    /// change to a model didn't change its geometry. Can be combined with
+    /// `--export`, `--analyze`, `--dimensions`, and `--overhangs`.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["batch", "validate"])]
+    pub diff: Option<PathBuf>,
+
+    /// Export all models listed in this batch manifest (TOML or JSON) and exit
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["export", "validate"])]
+    pub batch: Option<PathBuf>,
+
+    /// Process the jobs of the batch manifest in parallel
+    #[arg(long, requires = "batch")]
+    pub parallel: bool,
+
+    /// Validate the model, print a report, and exit
+    ///
+    /// Exits with a non-zero exit code, if validation fails.
+    #[arg(long, conflicts_with = "export")]
+    pub validate: bool,
+
+    /// Print the validation report as JSON
+    #[arg(long, requires = "validate")]
+    pub json: bool,
+
     /// Parameters for the model, each in the form `key=value`
     #[arg(short, long, value_parser = parse_parameters)]
     pub parameters: Option<Parameters>,
@@ -23,6 +88,39 @@ pub struct Args {
     /// Model deviation tolerance
     #[arg(short, long, value_parser = parse_tolerance)]
     pub tolerance: Option<Tolerance>,
+
+    /// The maximum angle, in degrees, that a segment of a circle may cover
+    #[arg(long, value_parser = parse_angle_tolerance)]
+    pub angle_tolerance: Option<Scalar>,
+
+    /// Simplify the triangle mesh to at most this number of triangles
+    #[arg(long)]
+    pub max_triangles: Option<usize>,
+
+    /// Always produce the same mesh for the same model, parameters, and
+    /// tolerance
+    ///
+    /// Exports are byte-identical then, which makes them easy to cache and
+    /// compare. This disables reusing the results of previous builds, which
+    /// makes reloading models in the GUI slower.
+    #[arg(long)]
+    pub deterministic: bool,
+
+    /// The mechanism that is used to load the model
+    #[arg(short, long, value_enum, default_value_t = Backend::Dylib)]
+    pub backend: Backend,
+
+    /// Also reload the model, when a crate that it depends on through a
+    /// `path` dependency changes
+    #[arg(long)]
+    pub watch_dependencies: bool,
+
+    /// Record how long each step of processing takes, and write it to a file
+    ///
+    /// The file is in the Chrome trace format. Open it in `chrome://tracing`
+    /// or https://ui.perfetto.dev/, to find out what makes a model slow.
+    #[arg(long, value_name = "PATH")]
+    pub trace: Option<PathBuf>,
 }
 
 impl Args {
@@ -33,6 +131,89 @@ impl Args {
     pub fn parse() -> Self {
         <Self as clap::Parser>::parse()
     }
+
+    /// Determine the options for exporting to the given format
+    ///
+    /// Anything that hasn't been specified on the command line defaults to the
+    /// convention of the format.
+    pub fn export_options(&self, format: Format) -> ExportOptions {
+        let default = format.default_coordinate_system();
+
+        ExportOptions {
+            coordinate_system: Some(CoordinateSystem {
+                up: self.up_axis.map(Into::into).unwrap_or(default.up),
+                handedness: self
+                    .handedness
+                    .map(Into::into)
+                    .unwrap_or(default.handedness),
+            }),
+        }
+    }
+}
+
+/// The mechanism that is used to load the model
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum Backend {
+    /// Load the model as a dynamic library
+    Dylib,
+
+    /// Compile the model to WebAssembly and run it in a sandbox
+    #[cfg(feature = "wasm")]
+    Wasm,
+
+    /// Load the model as a dynamic library into a child process
+    #[cfg(feature = "process")]
+    Process,
+}
+
+/// The axis that points up in an exported file
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum UpAxis {
+    /// The y-axis points up
+    Y,
+
+    /// The z-axis points up
+    Z,
+}
+
+impl From<UpAxis> for fj_export::UpAxis {
+    fn from(up_axis: UpAxis) -> Self {
+        match up_axis {
+            UpAxis::Y => Self::Y,
+            UpAxis::Z => Self::Z,
+        }
+    }
+}
+
+/// The handedness of an exported file's coordinate system
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Handedness {
+    /// A left-handed coordinate system
+    Left,
+
+    /// A right-handed coordinate system
+    Right,
+}
+
+impl From<Handedness> for fj_export::Handedness {
+    fn from(handedness: Handedness) -> Self {
+        match handedness {
+            Handedness::Left => Self::LeftHanded,
+            Handedness::Right => Self::RightHanded,
+        }
+    }
+}
+
+impl From<Backend> for fj_host::Backend {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Dylib => Self::Dylib,
+            #[cfg(feature = "wasm")]
+            Backend::Wasm => Self::Wasm,
+            #[cfg(feature = "process")]
+            Backend::Process => Self::Process,
+        }
+    }
 }
 
 fn parse_parameters(input: &str) -> anyhow::Result<Parameters> {
@@ -65,3 +246,12 @@ fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
 
     Ok(tolerance)
 }
+
+fn parse_angle_tolerance(input: &str) -> anyhow::Result<Scalar> {
+    let degrees = f64::from_str(input)?;
+    if degrees <= 0. {
+        return Err(anyhow!("Angle tolerance must be larger than zero"));
+    }
+
+    Ok(Scalar::from_f64(degrees.to_radians()))
+}
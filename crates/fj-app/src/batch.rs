@@ -0,0 +1,338 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use anyhow::{anyhow, Context as _};
+use figment::{
+    providers::{Format as _, Json, Toml},
+    Figment,
+};
+use fj_export::{export, Format};
+use fj_host::{Backend, Model, Parameters};
+use fj_operations::shape_processor::ShapeProcessor;
+use serde::Deserialize;
+
+/// A list of models to process, loaded from a TOML or JSON file
+///
+/// In TOML, a manifest looks like this:
+///
+/// ``` toml
+/// [[jobs]]
+/// model = "models/cuboid"
+/// output = "cuboid-small.3mf"
+/// parameters = { x = 1.0, y = 2.0, z = 3.0 }
+///
+/// [[jobs]]
+/// model = "models/cuboid"
+/// output = "cuboid-large.stl"
+/// parameters = { x = 10.0, y = 20.0, z = 30.0 }
+/// ```
+///
+/// Relative paths are relative to the directory that contains the manifest.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub jobs: Vec<Job>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let manifest = fs::read_to_string(path).with_context(|| {
+            format!("Failed to read batch manifest `{}`", path.display())
+        })?;
+
+        let figment = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Figment::from(Toml::string(&manifest)),
+            Some("json") => Figment::from(Json::string(&manifest)),
+            _ => {
+                return Err(anyhow!(
+                    "Batch manifest `{}` must be a `.toml` or `.json` file",
+                    path.display()
+                ))
+            }
+        };
+
+        let mut manifest: Self = figment.extract().with_context(|| {
+            format!("Error loading batch manifest `{}`", path.display())
+        })?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for job in &mut manifest.jobs {
+            job.model = base.join(&job.model);
+            job.output = base.join(&job.output);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Process all jobs and export their results
+    ///
+    /// Jobs are processed one after the other, unless `parallel` is set. Even
+    /// then, jobs for the same model are processed one after the other, as
+    /// each of them compiles the model. Compiling a model, while another job
+    /// loads the library that was compiled from it, would break the other job.
+    ///
+    /// A failing job doesn't stop the others from being processed, but makes
+    /// processing fail as a whole, once all jobs are done.
+    pub fn process(
+        &self,
+        backend: Backend,
+        shape_processor: &ShapeProcessor,
+        parallel: bool,
+    ) -> anyhow::Result<()> {
+        let results = if parallel {
+            let mut results =
+                self.jobs.iter().map(|_| None).collect::<Vec<_>>();
+
+            thread::scope(|scope| {
+                let handles = self
+                    .jobs_by_model()
+                    .into_iter()
+                    .map(|jobs| {
+                        scope.spawn(move || {
+                            jobs.into_iter()
+                                .map(|i| {
+                                    let job = &self.jobs[i];
+                                    (i, job.process(backend, shape_processor))
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                for handle in handles {
+                    let group = match handle.join() {
+                        Ok(group) => group,
+                        Err(payload) => std::panic::resume_unwind(payload),
+                    };
+                    for (i, result) in group {
+                        results[i] = Some(result);
+                    }
+                }
+            });
+
+            results
+                .into_iter()
+                .map(|result| result.expect("Every job has been processed"))
+                .collect()
+        } else {
+            self.jobs
+                .iter()
+                .map(|job| job.process(backend, shape_processor))
+                .collect::<Vec<_>>()
+        };
+
+        let mut failed = 0;
+        for (job, result) in self.jobs.iter().zip(results) {
+            match result {
+                Ok(()) => println!("Exported `{}`", job.output.display()),
+                Err(err) => {
+                    failed += 1;
+                    eprintln!(
+                        "Failed to export `{}`: {err:?}",
+                        job.output.display()
+                    );
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(anyhow!(
+                "{failed} of {} batch jobs failed",
+                self.jobs.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Group the jobs by the model they use
+    ///
+    /// Returns the indices of the jobs in each group, in the order in which
+    /// they appear in the manifest.
+    fn jobs_by_model(&self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<(PathBuf, Vec<usize>)> = Vec::new();
+
+        for (i, job) in self.jobs.iter().enumerate() {
+            // The same model might be referred to by different paths.
+            let model = fs::canonicalize(&job.model)
+                .unwrap_or_else(|_| job.model.clone());
+
+            match groups.iter_mut().find(|(path, _)| *path == model) {
+                Some((_, jobs)) => jobs.push(i),
+                None => groups.push((model, vec![i])),
+            }
+        }
+
+        groups.into_iter().map(|(_, jobs)| jobs).collect()
+    }
+}
+
+/// A single model, with a set of parameters, to be exported to a file
+#[derive(Debug, Deserialize)]
+pub struct Job {
+    /// The model crate or script
+    pub model: PathBuf,
+
+    /// The file to export the model to
+    pub output: PathBuf,
+
+    /// Parameters for the model
+    #[serde(default)]
+    pub parameters: HashMap<String, Parameter>,
+}
+
+impl Job {
+    fn process(
+        &self,
+        backend: Backend,
+        shape_processor: &ShapeProcessor,
+    ) -> anyhow::Result<()> {
+        Format::from_path(&self.output)?;
+
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|(key, value)| (key.clone(), value.to_string()))
+            .collect();
+
+        let model = Model::from_path(&self.model, Parameters(parameters))
+            .with_context(|| {
+                format!("Failed to load model `{}`", self.model.display())
+            })?
+            .with_backend(backend);
+
+        let evaluation = model.evaluate()?;
+        if let Some(warning) = evaluation.warning {
+            eprintln!("Warning (`{}`): {warning}", self.model.display());
+        }
+
+        let shape = shape_processor.process(&evaluation.shape)?;
+        if let Some(defect) = shape.mesh_defects.first() {
+            eprintln!(
+                "Warning (`{}`): Exported mesh is not watertight ({} defects, \
+                first: {defect})",
+                self.model.display(),
+                shape.mesh_defects.len()
+            );
+        }
+        export(&shape.mesh, &self.output)?;
+
+        Ok(())
+    }
+}
+
+/// The value of a model parameter
+///
+/// Models receive all parameters as strings. Allowing other types here saves
+/// users from having to quote numbers in the manifest.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Parameter {
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::Manifest;
+
+    #[test]
+    fn load_toml() {
+        let manifest = load(
+            "manifest.toml",
+            r#"
+                [[jobs]]
+                model = "models/cuboid"
+                output = "cuboid.3mf"
+                parameters = { x = 1, y = 2.5, name = "a", hollow = true }
+            "#,
+        );
+
+        let [job] = manifest.jobs.as_slice() else {
+            panic!("Expected one job");
+        };
+        let base = test_dir("manifest.toml");
+        assert_eq!(job.model, base.join("models/cuboid"));
+        assert_eq!(job.output, base.join("cuboid.3mf"));
+
+        let parameter = |name: &str| job.parameters[name].to_string();
+        assert_eq!(parameter("x"), "1");
+        assert_eq!(parameter("y"), "2.5");
+        assert_eq!(parameter("name"), "a");
+        assert_eq!(parameter("hollow"), "true");
+    }
+
+    #[test]
+    fn load_json() {
+        let manifest = load(
+            "manifest.json",
+            r#"{ "jobs": [{ "model": "a", "output": "a.stl" }] }"#,
+        );
+
+        assert_eq!(manifest.jobs.len(), 1);
+        assert!(manifest.jobs[0].parameters.is_empty());
+    }
+
+    #[test]
+    fn load_unknown_format() {
+        let path = test_dir("manifest.yaml").join("manifest.yaml");
+        fs::write(&path, "jobs: []").unwrap();
+
+        assert!(Manifest::load(&path).is_err());
+    }
+
+    #[test]
+    fn jobs_by_model() {
+        let manifest = load(
+            "jobs_by_model.toml",
+            r#"
+                [[jobs]]
+                model = "a"
+                output = "a-1.stl"
+
+                [[jobs]]
+                model = "b"
+                output = "b.stl"
+
+                [[jobs]]
+                model = "a"
+                output = "a-2.stl"
+            "#,
+        );
+
+        assert_eq!(manifest.jobs_by_model(), vec![vec![0, 2], vec![1]]);
+    }
+
+    fn load(name: &str, manifest: &str) -> Manifest {
+        let path = test_dir(name).join(name);
+        fs::write(&path, manifest).unwrap();
+
+        Manifest::load(&path).unwrap()
+    }
+
+    // Each test gets its own directory, as tests run in parallel.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("fj-app-batch-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+}
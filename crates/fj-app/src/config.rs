@@ -1,25 +1,84 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
 use anyhow::Context as _;
 use figment::{
-    providers::{Env, Format as _, Toml},
+    providers::{Env, Format as _, Json, Toml},
     Figment,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+/// The file that camera bookmarks are saved to
+///
+/// This is separate from `fj.toml`, so saving bookmarks from the viewer
+/// doesn't overwrite the hand-written configuration.
+const BOOKMARKS_FILE: &str = "fj-bookmarks.json";
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub default_path: Option<PathBuf>,
     pub default_model: Option<PathBuf>,
     pub invert_zoom: Option<bool>,
+    pub camera_damping: Option<f64>,
+
+    #[serde(default)]
+    pub camera_bookmarks: Vec<CameraBookmark>,
 }
 
 impl Config {
     pub fn load() -> Result<Self, anyhow::Error> {
         Figment::new()
             .merge(Toml::file("fj.toml"))
+            .merge(Json::file(BOOKMARKS_FILE))
             .merge(Env::prefixed("FJ_"))
             .extract()
             .context("Error loading configuration")
     }
+
+    pub fn save_bookmarks(
+        bookmarks: &[fj_viewer::CameraBookmark],
+    ) -> Result<(), anyhow::Error> {
+        let bookmarks = Bookmarks {
+            camera_bookmarks: bookmarks
+                .iter()
+                .cloned()
+                .map(CameraBookmark::from)
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&bookmarks)?;
+        fs::write(BOOKMARKS_FILE, json)
+            .with_context(|| format!("Error writing `{BOOKMARKS_FILE}`"))
+    }
+}
+
+#[derive(Serialize)]
+struct Bookmarks {
+    camera_bookmarks: Vec<CameraBookmark>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub rotation: [f64; 16],
+    pub translation: [f64; 16],
+}
+
+impl From<fj_viewer::CameraBookmark> for CameraBookmark {
+    fn from(bookmark: fj_viewer::CameraBookmark) -> Self {
+        Self {
+            name: bookmark.name,
+            rotation: bookmark.rotation,
+            translation: bookmark.translation,
+        }
+    }
+}
+
+impl From<CameraBookmark> for fj_viewer::CameraBookmark {
+    fn from(bookmark: CameraBookmark) -> Self {
+        Self {
+            name: bookmark.name,
+            rotation: bookmark.rotation,
+            translation: bookmark.translation,
+        }
+    }
 }
@@ -13,59 +13,206 @@
 //! [Fornjot repository]: https://github.com/hannobraun/Fornjot
 
 mod args;
+mod batch;
 mod config;
 mod path;
+mod validate;
 
 use std::{env, error::Error};
 
 use anyhow::{anyhow, Context};
-use fj_export::export;
+use fj_export::{export_with_options, import, Format};
 use fj_host::Parameters;
+use fj_kernel::algorithms::{analysis::diff, simplify::Target};
 use fj_operations::shape_processor::ShapeProcessor;
 use fj_window::run::run;
 use path::ModelPath;
-use tracing_subscriber::fmt::format;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{
+    fmt::format, layer::SubscriberExt as _, util::SubscriberInitExt as _,
+    EnvFilter, Layer as _,
+};
 
 use crate::{args::Args, config::Config};
 
 fn main() -> anyhow::Result<()> {
+    // If this process was started to evaluate a model, do that and exit.
+    #[cfg(feature = "process")]
+    fj_host::run_model_process();
+
+    let args = Args::parse();
+
     // Respect `RUST_LOG`. If that's not defined, log warnings and above. Fail if it's erroneous.
-    tracing_subscriber::fmt()
-        .with_env_filter(try_default_env_filter()?)
+    let log_layer = tracing_subscriber::fmt::layer()
         .event_format(format().pretty())
+        .with_filter(try_default_env_filter()?);
+
+    // The trace is written when the guard is dropped, at the end of `main`.
+    let (trace_layer, trace_guard) = match &args.trace {
+        Some(path) => {
+            let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(log_layer)
+        .with(trace_layer)
         .init();
 
-    let args = Args::parse();
     let config = Config::load()?;
     let model_path = ModelPath::from_args_and_config(&args, &config);
     let parameters = args.parameters.unwrap_or_else(Parameters::empty);
     let shape_processor = ShapeProcessor {
         tolerance: args.tolerance,
+        angle_tolerance: args.angle_tolerance,
+        simplification: args.max_triangles.map(Target::TriangleCount),
+        debug_algorithms: false,
+        deterministic: args.deterministic,
+        cache: Default::default(),
     };
 
-    let model = model_path.map(|m| m.load_model(parameters)).transpose()?;
+    if let Some(manifest_path) = args.batch {
+        // batch mode. export all models from the manifest and exit
+        let manifest = batch::Manifest::load(&manifest_path)?;
+        return manifest.process(
+            args.backend.into(),
+            &shape_processor,
+            args.parallel,
+        );
+    }
+
+    let model = model_path
+        .map(|m| m.load_model(parameters))
+        .transpose()?
+        .map(|model| {
+            model
+                .with_backend(args.backend.into())
+                .with_dependencies_watched(args.watch_dependencies)
+        });
 
-    if let Some(export_path) = args.export {
-        // export only mode. just load model, process, export and exit
+    if args.validate {
+        // validation only mode. just load model, validate, report and exit
 
         let evaluation = model.with_context(no_model_error)?.evaluate()?;
+        let report = validate::Report::new(&evaluation);
+
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print!("{report}");
+        }
+
+        if !report.is_valid() {
+            return Err(anyhow!("Model failed validation"));
+        }
+
+        return Ok(());
+    }
+
+    if args.export.is_some()
+        || args.analyze
+        || args.dimensions
+        || args.overhangs
+        || args.diff.is_some()
+    {
+        // export or analysis only mode. just load model, process, export or
+        // print analysis results, and exit
+
+        // Fail early, if the file format is not supported. No need to
+        // evaluate the model in that case.
+        if let Some(export_path) = &args.export {
+            Format::from_path(export_path)?;
+        }
+        let reference = args
+            .diff
+            .as_ref()
+            .map(|path| {
+                import(path).with_context(|| {
+                    format!("Failed to import `{}`", path.display())
+                })
+            })
+            .transpose()?;
+
+        let evaluation = model.with_context(no_model_error)?.evaluate()?;
+        if let Some(warning) = evaluation.warning {
+            eprintln!("Warning: {warning}");
+        }
+
+        // This fails, if the shape doesn't pass validation.
         let shape = shape_processor.process(&evaluation.shape)?;
 
-        export(&shape.mesh, &export_path)?;
+        if args.analyze {
+            print!("{}", shape.mass_properties);
+        }
+        if args.dimensions {
+            print!("{}", shape.dimensions);
+        }
+        if args.overhangs {
+            print!("{}", shape.overhangs);
+        }
+        let mut changed = false;
+        if let Some(reference) = &reference {
+            // The reference was presumably exported with the same tolerance,
+            // so both meshes can deviate from the shape by that much.
+            let tolerance = shape.tolerance * 2.;
+            let shape_diff =
+                diff::compare(reference, &shape.mesh, tolerance)
+                    .context("Model is empty, which can't be compared")?;
+
+            print!("{shape_diff}");
+            changed = !shape_diff.is_unchanged();
+        }
+        if let Some(export_path) = args.export {
+            if let Some(defect) = shape.mesh_defects.first() {
+                eprintln!(
+                    "Warning: Exported mesh is not watertight ({} defects, \
+                    first: {defect})",
+                    shape.mesh_defects.len()
+                );
+            }
+            let options = args.export_options(Format::from_path(&export_path)?);
+            export_with_options(&shape.mesh, &export_path, &options)?;
+        }
+
+        if changed {
+            return Err(anyhow!("Model differs from the reference mesh"));
+        }
 
         return Ok(());
     }
 
     let invert_zoom = config.invert_zoom.unwrap_or(false);
-    run(model, shape_processor, invert_zoom)?;
+    let bookmarks = config
+        .camera_bookmarks
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    run(
+        model,
+        shape_processor,
+        invert_zoom,
+        config.camera_damping,
+        bookmarks,
+        |bookmarks| {
+            if let Err(err) = Config::save_bookmarks(bookmarks) {
+                eprintln!("Warning: Failed to save camera bookmarks: {err:#}");
+            }
+        },
+        // The process exits without returning from `run`, so the trace needs
+        // to be written before that.
+        move || drop(trace_guard),
+    )?;
 
     Ok(())
 }
 
 fn no_model_error() -> anyhow::Error {
     anyhow!(
-        "You must specify a model to start Fornjot in export only mode.\n\
+        "You must specify a model to start Fornjot without a window.\n\
         - Pass a model as a command-line argument. See `fj-app --help`.\n\
         - Specify a default model in the configuration file."
     )
@@ -10,29 +10,37 @@ use crate::{args::Args, config::Config};
 
 pub struct ModelPath {
     default_path: Option<PathBuf>,
-    model_path: ModelPathSource,
+    model_paths: Vec<ModelPathSource>,
 }
 
 impl ModelPath {
     pub fn from_args_and_config(args: &Args, config: &Config) -> Option<Self> {
         let default_path = config.default_path.clone();
 
-        let model_path_from_args = args
+        let model_paths_from_args = args
             .model
-            .as_ref()
-            .map(|model| ModelPathSource::Args(model.clone()));
-        let model_path_from_config = config
-            .default_model
-            .as_ref()
-            .map(|model| ModelPathSource::Config(model.clone()));
-        let model_path = model_path_from_args.or(model_path_from_config)?;
+            .iter()
+            .map(|model| ModelPathSource::Args(model.clone()))
+            .collect::<Vec<_>>();
+        let model_paths = if model_paths_from_args.is_empty() {
+            let model_path_from_config = config
+                .default_model
+                .as_ref()
+                .map(|model| ModelPathSource::Config(model.clone()))?;
+            vec![model_path_from_config]
+        } else {
+            model_paths_from_args
+        };
 
         Some(Self {
             default_path,
-            model_path,
+            model_paths,
         })
     }
 
+    /// Load the model, or an assembly of all models, if there are several
+    ///
+    /// All models are passed the same parameters.
     pub fn load_model(&self, parameters: Parameters) -> anyhow::Result<Model> {
         let default_path = self
             .default_path
@@ -50,16 +58,26 @@ impl ModelPath {
             })
             .transpose()?;
 
-        let path = default_path
-            .clone()
-            .map(|(_, abs)| abs)
-            .unwrap_or_else(PathBuf::new)
-            .join(self.model_path.path());
+        let mut models = Vec::new();
+        for model_path in &self.model_paths {
+            let path = default_path
+                .clone()
+                .map(|(_, abs)| abs)
+                .unwrap_or_else(PathBuf::new)
+                .join(model_path.path());
+
+            let model = Model::from_path(&path, parameters.clone())
+                .with_context(|| {
+                    load_error_context(default_path.clone(), model_path, path)
+                })?;
+            models.push(model);
+        }
+
+        if models.len() == 1 {
+            return Ok(models.remove(0));
+        }
 
-        let model = Model::new(&path, parameters).with_context(|| {
-            load_error_context(default_path, &self.model_path, path)
-        })?;
-        Ok(model)
+        Ok(Model::assembly(models))
     }
 }
 
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use fj_host::Evaluation;
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    objects::Provenance,
+    services::{Services, ValidationFailed},
+    storage::ObjectId,
+};
+use fj_operations::{cache::BrepCache, Shape as _};
+
+/// The result of validating a model
+#[derive(serde::Serialize)]
+pub struct Report {
+    /// The errors found during validation
+    pub errors: Vec<ReportedError>,
+
+    /// The warnings emitted while evaluating the model
+    pub warnings: Vec<String>,
+}
+
+impl Report {
+    /// Compute the boundary representation of a model and validate it
+    pub fn new(evaluation: &Evaluation) -> Self {
+        let mut services = Services::new();
+        let mut debug_info = DebugInfo::new();
+        let _ = evaluation.shape.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut debug_info,
+            &CancellationToken::new(),
+        );
+
+        // The objects are gone, once the services have been dropped. Look up
+        // the provenance of the failed objects before that.
+        let provenances = services
+            .validation_errors()
+            .iter()
+            .map(|failed| {
+                let id = failed.object.id();
+                let operations = services
+                    .objects
+                    .provenance(id)
+                    .into_iter()
+                    .flat_map(Provenance::chain)
+                    .map(Provenance::operation)
+                    .collect();
+                (id, operations)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let errors = match services.drop_and_validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .iter()
+                .map(|failed| ReportedError::new(failed, &provenances))
+                .collect(),
+        };
+        let warnings = evaluation.warning.iter().cloned().collect();
+
+        Self { errors, warnings }
+    }
+
+    /// Indicate whether the model passed validation
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in &self.warnings {
+            writeln!(f, "Warning: {warning}")?;
+        }
+
+        if self.is_valid() {
+            return writeln!(f, "Model is valid.");
+        }
+
+        writeln!(f, "Found {} validation error(s):", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "- {} ({})", error.object, error.id)?;
+            if !error.provenance.is_empty() {
+                write!(f, " created by {}", error.provenance.join(" in "))?;
+            }
+            writeln!(f, ": {}", error.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A validation error, as it appears in a [`Report`]
+#[derive(serde::Serialize)]
+pub struct ReportedError {
+    /// The type of the object that failed validation
+    pub object: &'static str,
+
+    /// The ID of the object that failed validation
+    pub id: String,
+
+    /// The operations that created the object, innermost first
+    pub provenance: Vec<&'static str>,
+
+    /// The error message, including all of its sources
+    pub message: String,
+}
+
+impl ReportedError {
+    fn new(
+        failed: &ValidationFailed,
+        provenances: &HashMap<ObjectId, Vec<&'static str>>,
+    ) -> Self {
+        let mut message = failed.err.to_string();
+
+        let mut source = failed.err.source();
+        while let Some(err) = source {
+            message.push_str(&format!(": {err}"));
+            source = err.source();
+        }
+
+        Self {
+            object: failed.object.name(),
+            id: failed.object.id().to_string(),
+            provenance: provenances
+                .get(&failed.object.id())
+                .cloned()
+                .unwrap_or_default(),
+            message,
+        }
+    }
+}
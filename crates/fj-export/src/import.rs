@@ -0,0 +1,150 @@
+use std::{fs, io::Cursor, path::Path};
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Triangle};
+
+use crate::{Error, Format};
+
+/// Import a mesh from the file at the given path
+///
+/// Currently STL (binary and ASCII) & OBJ file types are supported. The format
+/// is determined from the file extension, like it is for [`crate::export`].
+///
+/// The mesh is converted from the default coordinate system of the format into
+/// that of Fornjot models. Degenerate triangles are skipped, but the mesh must
+/// contain at least one valid triangle.
+pub fn import(path: &Path) -> Result<Mesh<Point<3>>, Error> {
+    let format = Format::from_path(path)?;
+    let data = fs::read(path)?;
+
+    let triangles = match format {
+        Format::Stl => import_stl(&data)?,
+        Format::Obj => import_obj(&data)?,
+        Format::ThreeMF => return Err(Error::ImportNotSupported(format)),
+    };
+
+    let mut mesh = Mesh::new();
+    for points in triangles {
+        if let Ok(triangle) = Triangle::from_points(points) {
+            mesh.push_triangle(triangle, Color::default());
+        }
+    }
+    if mesh.triangles().next().is_none() {
+        return Err(Error::InvalidMeshData("File contains no triangles"));
+    }
+
+    Ok(format.default_coordinate_system().revert(&mesh))
+}
+
+fn import_stl(data: &[u8]) -> Result<Vec<[Point<3>; 3]>, Error> {
+    // Binary STL files can start with `solid` too, so checking the size is the
+    // only reliable way to tell them apart from ASCII STL files.
+    let is_binary = data
+        .get(80..84)
+        .map(|count| {
+            let count =
+                u32::from_le_bytes([count[0], count[1], count[2], count[3]]);
+            data.len() == 84 + 50 * count as usize
+        })
+        .unwrap_or(false);
+
+    if is_binary {
+        let file = stl::read_stl(&mut Cursor::new(data))?;
+        let triangles = file
+            .triangles
+            .into_iter()
+            .map(|triangle| {
+                [triangle.v1, triangle.v2, triangle.v3]
+                    .map(|vertex| Point::from(vertex.map(f64::from)))
+            })
+            .collect();
+
+        return Ok(triangles);
+    }
+
+    let text = std::str::from_utf8(data).map_err(|_| {
+        Error::InvalidMeshData("STL file is not binary or ASCII")
+    })?;
+
+    let mut vertices = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() != Some("vertex") {
+            continue;
+        }
+
+        vertices.push(parse_point(tokens).ok_or(Error::InvalidMeshData(
+            "Invalid vertex in ASCII STL file",
+        ))?);
+    }
+
+    if vertices.len() % 3 != 0 {
+        return Err(Error::InvalidMeshData(
+            "ASCII STL file contains incomplete facet",
+        ));
+    }
+
+    Ok(vertices
+        .chunks(3)
+        .map(|vertices| [vertices[0], vertices[1], vertices[2]])
+        .collect())
+}
+
+fn import_obj(data: &[u8]) -> Result<Vec<[Point<3>; 3]>, Error> {
+    let text = std::str::from_utf8(data)
+        .map_err(|_| Error::InvalidMeshData("OBJ file is not valid UTF-8"))?;
+
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                vertices.push(parse_point(tokens).ok_or(
+                    Error::InvalidMeshData("Invalid vertex in OBJ file"),
+                )?);
+            }
+            Some("f") => {
+                // Indices start at 1, and negative ones are relative to the
+                // end of the vertices defined so far. Texture coordinates and
+                // normals are ignored.
+                let face = tokens
+                    .map(|token| {
+                        let index =
+                            token.split('/').next()?.parse::<isize>().ok()?;
+                        let index = if index < 0 {
+                            vertices.len().checked_sub(index.unsigned_abs())?
+                        } else {
+                            usize::try_from(index).ok()?.checked_sub(1)?
+                        };
+                        vertices.get(index).copied()
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(Error::InvalidMeshData(
+                        "Invalid face in OBJ file",
+                    ))?;
+
+                // Faces with more than three vertices are assumed to be
+                // convex, and split into a fan of triangles.
+                for pair in face.windows(2).skip(1) {
+                    triangles.push([face[0], pair[0], pair[1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
+
+fn parse_point<'r>(
+    mut tokens: impl Iterator<Item = &'r str>,
+) -> Option<Point<3>> {
+    let mut coords = [0.; 3];
+    for coord in &mut coords {
+        *coord = tokens.next()?.parse().ok()?;
+    }
+
+    Some(Point::from(coords))
+}
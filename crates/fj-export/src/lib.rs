@@ -8,40 +8,215 @@
 //! users that just want to create CAD models.
 //!
 //! The purpose of this library is to export Fornjot models to external file
-//! formats.
+//! formats, and to import meshes from them.
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
 #![warn(missing_docs)]
 
+mod import;
+
 use std::{fs::File, io::Write, path::Path};
 
 use thiserror::Error;
 
-use fj_interop::mesh::Mesh;
-use fj_math::{Point, Triangle};
+use fj_interop::mesh::{Mesh, DEFAULT_CREASE_ANGLE};
+use fj_math::{Point, Scalar, Triangle};
+
+pub use self::import::import;
 
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
+/// Currently 3MF, STL & OBJ file types are supported. The case insensitive file extension of
+/// the provided path is used to switch between supported types. See [`Format::from_path`].
+///
+/// The mesh is exported in the default coordinate system of the format. See
+/// [`export_with_options`], to override that.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    match path.extension() {
-        Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            export_3mf(mesh, path)
+    export_with_options(mesh, path, &ExportOptions::default())
+}
+
+/// Export the provided mesh to the file at the given path, using the options
+///
+/// Works like [`export`], otherwise.
+pub fn export_with_options(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let format = Format::from_path(path)?;
+
+    let coordinate_system = options
+        .coordinate_system
+        .unwrap_or_else(|| format.default_coordinate_system());
+    let mesh = coordinate_system.convert(mesh);
+
+    match format {
+        Format::ThreeMF => export_3mf(&mesh, path),
+        Format::Stl => export_stl(&mesh, path),
+        Format::Obj => export_obj(&mesh, path),
+    }
+}
+
+/// Options that control how a mesh is exported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExportOptions {
+    /// The coordinate system that the exported file uses
+    ///
+    /// If this is `None`, the default coordinate system of the format is used.
+    /// See [`Format::default_coordinate_system`].
+    pub coordinate_system: Option<CoordinateSystem>,
+}
+
+/// A coordinate system that a mesh can be exported in
+///
+/// Fornjot models use a right-handed coordinate system, with the z-axis
+/// pointing up. The x-axis always stays the same, when converting to another
+/// coordinate system.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CoordinateSystem {
+    /// The axis that points up
+    pub up: UpAxis,
+
+    /// The handedness of the coordinate system
+    pub handedness: Handedness,
+}
+
+impl CoordinateSystem {
+    /// The coordinate system that Fornjot models are defined in
+    pub const FORNJOT: Self = Self {
+        up: UpAxis::Z,
+        handedness: Handedness::RightHanded,
+    };
+
+    /// Convert a mesh from the coordinate system of Fornjot models into this
+    ///
+    /// Conversion into a left-handed coordinate system mirrors the mesh. Its
+    /// triangles are wound the other way around, to keep their normals
+    /// pointing outwards.
+    pub fn convert(&self, mesh: &Mesh<Point<3>>) -> Mesh<Point<3>> {
+        self.map_mesh(mesh, |[x, y, z]| match (self.up, self.handedness) {
+            (UpAxis::Z, Handedness::RightHanded) => [x, y, z],
+            (UpAxis::Z, Handedness::LeftHanded) => [x, -y, z],
+            (UpAxis::Y, Handedness::RightHanded) => [x, z, -y],
+            (UpAxis::Y, Handedness::LeftHanded) => [x, z, y],
+        })
+    }
+
+    /// Convert a mesh from this coordinate system into that of Fornjot models
+    ///
+    /// This is the inverse of [`CoordinateSystem::convert`].
+    pub fn revert(&self, mesh: &Mesh<Point<3>>) -> Mesh<Point<3>> {
+        self.map_mesh(mesh, |[x, y, z]| match (self.up, self.handedness) {
+            (UpAxis::Z, Handedness::RightHanded) => [x, y, z],
+            (UpAxis::Z, Handedness::LeftHanded) => [x, -y, z],
+            (UpAxis::Y, Handedness::RightHanded) => [x, -z, y],
+            (UpAxis::Y, Handedness::LeftHanded) => [x, z, y],
+        })
+    }
+
+    fn map_mesh(
+        &self,
+        mesh: &Mesh<Point<3>>,
+        map_coords: impl Fn([Scalar; 3]) -> [Scalar; 3],
+    ) -> Mesh<Point<3>> {
+        let convert_point =
+            |point: Point<3>| Point::from(map_coords(point.coords.components));
+
+        let mut converted = Mesh::new();
+
+        for triangle in mesh.triangles() {
+            let mut points = triangle.inner.points().map(convert_point);
+            if self.handedness == Handedness::LeftHanded {
+                points.reverse();
+            }
+
+            match triangle.face {
+                Some(face) => converted.push_triangle_of_face(
+                    points,
+                    triangle.color,
+                    face,
+                ),
+                None => converted.push_triangle(points, triangle.color),
+            }
         }
-        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            export_stl(mesh, path)
+
+        converted
+    }
+}
+
+/// The axis that points up in a [`CoordinateSystem`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpAxis {
+    /// The y-axis points up, as is common for graphics applications
+    Y,
+
+    /// The z-axis points up, as is common for CAD applications
+    Z,
+}
+
+/// The handedness of a [`CoordinateSystem`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Handedness {
+    /// A left-handed coordinate system
+    LeftHanded,
+
+    /// A right-handed coordinate system
+    RightHanded,
+}
+
+/// A file format that can be exported to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// 3D Manufacturing Format
+    ThreeMF,
+
+    /// STL
+    Stl,
+
+    /// Wavefront OBJ
+    Obj,
+}
+
+impl Format {
+    /// Determine the format from the case insensitive extension of a path
+    ///
+    /// This can be used to check whether a path is supported, before doing
+    /// the work of creating a mesh to export.
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        match path.extension() {
+            Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
+                Ok(Self::ThreeMF)
+            }
+            Some(extension) if extension.to_ascii_uppercase() == "STL" => {
+                Ok(Self::Stl)
+            }
+            Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+                Ok(Self::Obj)
+            }
+            Some(extension) => Err(Error::InvalidExtension(
+                extension.to_string_lossy().into_owned(),
+            )),
+            None => Err(Error::NoExtension),
         }
-        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
-            export_obj(mesh, path)
+    }
+
+    /// The coordinate system that files of the format usually use
+    ///
+    /// 3MF specifies a right-handed coordinate system with the z-axis pointing
+    /// up. STL doesn't specify one, but is mostly used for 3D printing, where
+    /// the same is common. OBJ doesn't specify one either, but most
+    /// applications that read it assume the y-axis to point up.
+    pub fn default_coordinate_system(&self) -> CoordinateSystem {
+        match self {
+            Self::ThreeMF | Self::Stl => CoordinateSystem::FORNJOT,
+            Self::Obj => CoordinateSystem {
+                up: UpAxis::Y,
+                handedness: Handedness::RightHanded,
+            },
         }
-        Some(extension) => Err(Error::InvalidExtension(
-            extension.to_string_lossy().into_owned(),
-        )),
-        None => Err(Error::NoExtension),
     }
 }
 
@@ -125,7 +300,21 @@ fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
 fn export_obj(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     let mut f = File::create(path)?;
 
-    for (cnt, t) in mesh.triangles().enumerate() {
+    let mut face = None;
+    let mut num_groups = 0;
+    let normals = mesh.normals(DEFAULT_CREASE_ANGLE);
+
+    for (cnt, (t, normals)) in mesh.triangles().zip(normals).enumerate() {
+        // start a new group for each face, so they can be told apart. Groups
+        // are numbered in order, as the ids of faces differ between builds.
+        if t.face != face {
+            if t.face.is_some() {
+                writeln!(f, "g face_{num_groups}")?;
+                num_groups += 1;
+            }
+            face = t.face;
+        }
+
         // write each point of the triangle
         for v in t.inner.points() {
             wavefront_rs::obj::writer::Writer::write(
@@ -141,6 +330,20 @@ fn export_obj(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
             f.write_all(b"\n")?;
         }
 
+        // write the normal of each point
+        for n in normals {
+            wavefront_rs::obj::writer::Writer::write(
+                &mut f,
+                &wavefront_rs::obj::entity::Entity::VertexNormal {
+                    x: n.x.into_f64(),
+                    y: n.y.into_f64(),
+                    z: n.z.into_f64(),
+                },
+            )
+            .or(Err(Error::OBJ))?;
+            f.write_all(b"\n")?;
+        }
+
         // write the triangle
         wavefront_rs::obj::writer::Writer::write(
             &mut f,
@@ -149,17 +352,17 @@ fn export_obj(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
                     wavefront_rs::obj::entity::FaceVertex {
                         vertex: (cnt * 3 + 1) as i64,
                         texture: None,
-                        normal: None,
+                        normal: Some((cnt * 3 + 1) as i64),
                     },
                     wavefront_rs::obj::entity::FaceVertex {
                         vertex: (cnt * 3 + 2) as i64,
                         texture: None,
-                        normal: None,
+                        normal: Some((cnt * 3 + 2) as i64),
                     },
                     wavefront_rs::obj::entity::FaceVertex {
                         vertex: (cnt * 3 + 3) as i64,
                         texture: None,
-                        normal: None,
+                        normal: Some((cnt * 3 + 3) as i64),
                     },
                 ],
             },
@@ -171,7 +374,7 @@ fn export_obj(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     Ok(())
 }
 
-/// An error that can occur while exporting
+/// An error that can occur while exporting or importing
 #[derive(Debug, Error)]
 pub enum Error {
     /// No extension specified
@@ -197,4 +400,128 @@ pub enum Error {
     /// OBJ exporter error whilst exporting to OBJ file
     #[error("obj error whilst exporting to OBJ file")]
     OBJ,
+
+    /// Importing from the format is not supported
+    #[error("importing from {0:?} files is not supported")]
+    ImportNotSupported(Format),
+
+    /// The file to import from doesn't contain a valid mesh
+    #[error("invalid mesh data: {0}")]
+    InvalidMeshData(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Vector};
+
+    use crate::{CoordinateSystem, Format, Handedness, UpAxis};
+
+    #[test]
+    fn convert_maps_axes() {
+        let mesh = triangle();
+
+        // Compare the points regardless of their order, as conversion into a
+        // left-handed coordinate system changes it.
+        let convert = |up, handedness| {
+            let converted = CoordinateSystem { up, handedness }.convert(&mesh);
+            let mut points =
+                converted.triangles().next().unwrap().inner.points();
+            points.sort();
+            points
+        };
+        let expected = |points: [[f64; 3]; 3]| {
+            let mut points = points.map(Point::from);
+            points.sort();
+            points
+        };
+
+        assert_eq!(
+            convert(UpAxis::Z, Handedness::RightHanded),
+            expected([[1., 2., 3.], [4., 2., 3.], [1., 5., 3.]]),
+        );
+        assert_eq!(
+            convert(UpAxis::Z, Handedness::LeftHanded),
+            expected([[1., -2., 3.], [4., -2., 3.], [1., -5., 3.]]),
+        );
+        assert_eq!(
+            convert(UpAxis::Y, Handedness::RightHanded),
+            expected([[1., 3., -2.], [4., 3., -2.], [1., 3., -5.]]),
+        );
+        assert_eq!(
+            convert(UpAxis::Y, Handedness::LeftHanded),
+            expected([[1., 3., 2.], [4., 3., 2.], [1., 3., 5.]]),
+        );
+    }
+
+    #[test]
+    fn convert_keeps_normals_pointing_outwards() {
+        let mesh = triangle();
+
+        // The triangle's normal points along the z-axis, which is mapped to
+        // the up axis. If the winding weren't flipped for left-handed
+        // coordinate systems, the normal would point down instead.
+        for (up, handedness, normal) in [
+            (UpAxis::Z, Handedness::RightHanded, [0., 0., 1.]),
+            (UpAxis::Z, Handedness::LeftHanded, [0., 0., 1.]),
+            (UpAxis::Y, Handedness::RightHanded, [0., 1., 0.]),
+            (UpAxis::Y, Handedness::LeftHanded, [0., 1., 0.]),
+        ] {
+            let converted = CoordinateSystem { up, handedness }.convert(&mesh);
+            let triangle = converted.triangles().next().unwrap();
+
+            assert_eq!(
+                triangle.inner.normal(),
+                Vector::from(normal),
+                "{up:?}, {handedness:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn revert_is_inverse_of_convert() {
+        let mesh = triangle();
+
+        for up in [UpAxis::Y, UpAxis::Z] {
+            for handedness in [Handedness::LeftHanded, Handedness::RightHanded]
+            {
+                let coordinate_system = CoordinateSystem { up, handedness };
+                let reverted =
+                    coordinate_system.revert(&coordinate_system.convert(&mesh));
+
+                assert_eq!(
+                    reverted.triangles().next().unwrap().inner.points(),
+                    mesh.triangles().next().unwrap().inner.points(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn obj_defaults_to_y_up() {
+        assert_eq!(
+            Format::Obj.default_coordinate_system(),
+            CoordinateSystem {
+                up: UpAxis::Y,
+                handedness: Handedness::RightHanded,
+            },
+        );
+        assert_eq!(
+            Format::Stl.default_coordinate_system(),
+            CoordinateSystem::FORNJOT,
+        );
+        assert_eq!(
+            Format::ThreeMF.default_coordinate_system(),
+            CoordinateSystem::FORNJOT,
+        );
+    }
+
+    fn triangle() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[1., 2., 3.], [4., 2., 3.], [1., 5., 3.]],
+            Color::default(),
+        );
+        mesh
+    }
 }
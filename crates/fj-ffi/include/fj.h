@@ -0,0 +1,51 @@
+/*
+ * Fornjot C API
+ *
+ * See the documentation of the `fj-ffi` crate for details. Every shape that is
+ * returned must be freed using `fj_shape_free` or `fj_shape_2d_free`.
+ */
+
+#ifndef FJ_H
+#define FJ_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct FjShape FjShape;
+typedef struct FjShape2d FjShape2d;
+
+typedef enum FjStatus {
+    FJ_STATUS_OK = 0,
+    FJ_STATUS_INVALID_ARGUMENT = 1,
+    FJ_STATUS_PROCESSING = 2,
+    FJ_STATUS_EXPORT = 3,
+    FJ_STATUS_PANIC = 4,
+} FjStatus;
+
+const char *fj_last_error(void);
+
+FjShape2d *fj_sketch_from_points(const double *points, size_t num_points);
+FjShape2d *fj_sketch_from_circle(double radius);
+FjShape2d *fj_difference_2d(const FjShape2d *a, const FjShape2d *b);
+
+FjShape *fj_sweep(const FjShape2d *shape, double x, double y, double z);
+FjShape *fj_union(const FjShape *a, const FjShape *b);
+FjShape *fj_transform(
+    const FjShape *shape,
+    const double *axis,
+    double angle,
+    const double *offset);
+
+FjStatus fj_export(const FjShape *shape, const char *path, double tolerance);
+
+void fj_shape_free(FjShape *shape);
+void fj_shape_2d_free(FjShape2d *shape);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FJ_H */
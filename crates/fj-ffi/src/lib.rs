@@ -0,0 +1,460 @@
+//! # Fornjot C API
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library exposes the shape-building API of the [`fj`] crate, and the
+//! export pipeline of the [`fornjot`] crate, to C. It is meant as a foundation
+//! for bindings to other languages. The declarations are in `include/fj.h`.
+//!
+//! Shapes are passed around as opaque pointers. Functions that build a shape
+//! from other shapes copy them, so every shape that is returned must be freed
+//! by the caller, using [`fj_shape_free`] or [`fj_shape_2d_free`].
+//!
+//! Functions that fail return a null pointer or an [`FjStatus`] other than
+//! [`FjStatus::Ok`]. [`fj_last_error`] describes the most recent failure.
+//! Panics don't unwind into the caller. They are reported as failures, like any
+//! other error.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+#![warn(missing_docs)]
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    ptr, slice,
+};
+
+use fornjot::Tolerance;
+
+/// A 3-dimensional shape
+pub struct FjShape(fj::Shape);
+
+/// A 2-dimensional shape
+pub struct FjShape2d(fj::Shape2d);
+
+/// The result of a function that doesn't return a shape
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FjStatus {
+    /// The function has succeeded
+    Ok = 0,
+
+    /// An argument was a null pointer, or otherwise invalid
+    InvalidArgument = 1,
+
+    /// The shape could not be processed
+    Processing = 2,
+
+    /// The mesh could not be exported
+    Export = 3,
+
+    /// The library panicked
+    Panic = 4,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Access a description of the most recent error on this thread
+///
+/// Returns a null pointer, if no error has occurred. The string stays valid
+/// until the next call into this library on the same thread.
+#[no_mangle]
+pub extern "C" fn fj_last_error() -> *const c_char {
+    catch_panic(ptr::null(), || {
+        LAST_ERROR.with(|error| {
+            error
+                .borrow()
+                .as_ref()
+                .map_or(ptr::null(), |error| error.as_ptr())
+        })
+    })
+}
+
+/// Create a sketch from a closed polygon
+///
+/// `points` points to `num_points` pairs of x and y coordinates. Returns a
+/// null pointer, if there are fewer than three points.
+///
+/// # Safety
+///
+/// `points` must point to `2 * num_points` values of type `double`.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_from_points(
+    points: *const f64,
+    num_points: usize,
+) -> *mut FjShape2d {
+    catch_panic(ptr::null_mut(), || {
+        if points.is_null() {
+            return invalid_argument("`points` is null");
+        }
+        if num_points < 3 {
+            return invalid_argument("A sketch needs at least three points");
+        }
+
+        // Safe, as the caller guarantees that `points` is valid.
+        let coords = unsafe { slice::from_raw_parts(points, 2 * num_points) };
+        let points = coords
+            .chunks_exact(2)
+            .map(|point| [point[0], point[1]])
+            .collect();
+
+        let sketch = fj::Sketch::from_points(points)
+            .expect("Checked that there are points");
+        new_shape_2d(sketch)
+    })
+}
+
+/// Create a sketch from a circle around the origin
+///
+/// Returns a null pointer, if `radius` isn't positive.
+#[no_mangle]
+pub extern "C" fn fj_sketch_from_circle(radius: f64) -> *mut FjShape2d {
+    catch_panic(ptr::null_mut(), || {
+        if radius.is_nan() || radius <= 0. {
+            return invalid_argument("`radius` must be positive");
+        }
+
+        new_shape_2d(fj::Sketch::from_circle(fj::Circle::from_radius(radius)))
+    })
+}
+
+/// Create a 2D shape by subtracting `b` from `a`
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid 2D shapes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_difference_2d(
+    a: *const FjShape2d,
+    b: *const FjShape2d,
+) -> *mut FjShape2d {
+    catch_panic(ptr::null_mut(), || {
+        // Safe, as the caller guarantees that the shapes are valid.
+        let (Some(a), Some(b)) = (unsafe { a.as_ref() }, unsafe { b.as_ref() })
+        else {
+            return invalid_argument("Shape is null");
+        };
+
+        new_shape_2d(fj::Difference2d::from_shapes([a.0.clone(), b.0.clone()]))
+    })
+}
+
+/// Create a 3D shape by sweeping a 2D shape along a straight path
+///
+/// # Safety
+///
+/// `shape` must be null or a valid 2D shape.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sweep(
+    shape: *const FjShape2d,
+    x: f64,
+    y: f64,
+    z: f64,
+) -> *mut FjShape {
+    catch_panic(ptr::null_mut(), || {
+        // Safe, as the caller guarantees that the shape is valid.
+        let Some(shape) = (unsafe { shape.as_ref() }) else {
+            return invalid_argument("Shape is null");
+        };
+
+        new_shape(fj::Sweep::from_path(shape.0.clone(), [x, y, z]))
+    })
+}
+
+/// Create a 3D shape that consists of both `a` and `b`
+///
+/// The shapes must not overlap.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or valid 3D shapes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_union(
+    a: *const FjShape,
+    b: *const FjShape,
+) -> *mut FjShape {
+    catch_panic(ptr::null_mut(), || {
+        // Safe, as the caller guarantees that the shapes are valid.
+        let (Some(a), Some(b)) = (unsafe { a.as_ref() }, unsafe { b.as_ref() })
+        else {
+            return invalid_argument("Shape is null");
+        };
+
+        new_shape(fj::Group {
+            a: a.0.clone(),
+            b: b.0.clone(),
+        })
+    })
+}
+
+/// Create a 3D shape by rotating and then translating another
+///
+/// The shape is rotated by `angle` radians around `axis`, then moved by
+/// `offset`. Both `axis` and `offset` point to three values.
+///
+/// # Safety
+///
+/// `shape` must be null or a valid 3D shape. `axis` and `offset` must be null
+/// or point to three values of type `double`.
+#[no_mangle]
+pub unsafe extern "C" fn fj_transform(
+    shape: *const FjShape,
+    axis: *const f64,
+    angle: f64,
+    offset: *const f64,
+) -> *mut FjShape {
+    catch_panic(ptr::null_mut(), || {
+        // Safe, as the caller guarantees that the arguments are valid.
+        let (Some(shape), Some(axis), Some(offset)) = (
+            unsafe { shape.as_ref() },
+            unsafe { axis.cast::<[f64; 3]>().as_ref() },
+            unsafe { offset.cast::<[f64; 3]>().as_ref() },
+        ) else {
+            return invalid_argument("Argument is null");
+        };
+
+        new_shape(fj::Transform {
+            shape: shape.0.clone(),
+            axis: *axis,
+            angle: fj::Angle::from_rad(angle),
+            offset: *offset,
+        })
+    })
+}
+
+/// Process a 3D shape, and export the resulting mesh to a file
+///
+/// The format is determined from the file extension of `path`. The mesh
+/// approximates the shape within `tolerance`. If it isn't positive, a
+/// tolerance is derived from the size of the shape.
+///
+/// # Safety
+///
+/// `shape` must be null or a valid 3D shape. `path` must be null or a valid,
+/// null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fj_export(
+    shape: *const FjShape,
+    path: *const c_char,
+    tolerance: f64,
+) -> FjStatus {
+    catch_panic(FjStatus::Panic, || {
+        // Safe, as the caller guarantees that the shape is valid.
+        let Some(shape) = (unsafe { shape.as_ref() }) else {
+            set_last_error("Shape is null");
+            return FjStatus::InvalidArgument;
+        };
+        if path.is_null() {
+            set_last_error("`path` is null");
+            return FjStatus::InvalidArgument;
+        }
+
+        // Safe, as the caller guarantees that `path` is valid.
+        let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+            set_last_error("`path` is not valid UTF-8");
+            return FjStatus::InvalidArgument;
+        };
+
+        let tolerance = if tolerance > 0. {
+            match Tolerance::from_scalar(tolerance) {
+                Ok(tolerance) => Some(tolerance),
+                Err(err) => {
+                    set_last_error(err.to_string());
+                    return FjStatus::InvalidArgument;
+                }
+            }
+        } else {
+            None
+        };
+
+        match fornjot::process_and_export(
+            shape.0.clone(),
+            tolerance,
+            Path::new(path),
+        ) {
+            Ok(_) => FjStatus::Ok,
+            Err(err) => {
+                let status = match err {
+                    fornjot::Error::Processing(_) => FjStatus::Processing,
+                    fornjot::Error::Export(_) => FjStatus::Export,
+                };
+                set_last_error(error_chain(&err));
+                status
+            }
+        }
+    })
+}
+
+/// Free a 3D shape
+///
+/// # Safety
+///
+/// `shape` must be null or a 3D shape that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fj_shape_free(shape: *mut FjShape) {
+    catch_panic((), || {
+        if !shape.is_null() {
+            // Safe, as the caller guarantees that the shape is valid.
+            drop(unsafe { Box::from_raw(shape) });
+        }
+    })
+}
+
+/// Free a 2D shape
+///
+/// # Safety
+///
+/// `shape` must be null or a 2D shape that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fj_shape_2d_free(shape: *mut FjShape2d) {
+    catch_panic((), || {
+        if !shape.is_null() {
+            // Safe, as the caller guarantees that the shape is valid.
+            drop(unsafe { Box::from_raw(shape) });
+        }
+    })
+}
+
+fn new_shape(shape: impl Into<fj::Shape>) -> *mut FjShape {
+    Box::into_raw(Box::new(FjShape(shape.into())))
+}
+
+fn new_shape_2d(shape: impl Into<fj::Shape2d>) -> *mut FjShape2d {
+    Box::into_raw(Box::new(FjShape2d(shape.into())))
+}
+
+fn invalid_argument<T>(message: &str) -> *mut T {
+    set_last_error(message);
+    ptr::null_mut()
+}
+
+/// Run `f`, returning `on_panic` if it panics
+///
+/// Unwinding across the C boundary is undefined behavior, so this must wrap
+/// the body of every exported function.
+fn catch_panic<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        set_last_error(format!("Panic: {}", panic_message(&*payload)));
+        on_panic
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "No details were given"
+    }
+}
+
+fn set_last_error(message: impl Into<String>) {
+    // Interior null bytes can't be represented. There's no reason for them to
+    // show up in an error message, so just drop them.
+    let message = message.into().replace('\0', "");
+    let message = CString::new(message).expect("Removed all null bytes");
+
+    // This is called while handling panics, so it must not panic itself. If
+    // the thread-local has already been destroyed, there's no one left to
+    // read the error anyway.
+    let _ = LAST_ERROR.try_with(|error| {
+        if let Ok(mut error) = error.try_borrow_mut() {
+            *error = Some(message);
+        }
+    });
+}
+
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        ffi::{CStr, CString},
+        fs, ptr,
+    };
+
+    use super::{
+        catch_panic, fj_export, fj_last_error, fj_shape_2d_free, fj_shape_free,
+        fj_sketch_from_circle, fj_sketch_from_points, fj_sweep, FjShape,
+        FjStatus,
+    };
+
+    #[test]
+    fn sweep_and_export() {
+        let points = [0., 0., 1., 0., 1., 1., 0., 1.];
+        let path = env::temp_dir().join("fj-ffi-sweep-and-export.stl");
+        let path_c = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let sketch = fj_sketch_from_points(points.as_ptr(), 4);
+            assert!(!sketch.is_null());
+
+            let shape = fj_sweep(sketch, 0., 0., 1.);
+            assert!(!shape.is_null());
+
+            let status = fj_export(shape, path_c.as_ptr(), 0.01);
+            assert_eq!(status, FjStatus::Ok, "{:?}", last_error());
+
+            fj_shape_free(shape);
+            fj_shape_2d_free(sketch);
+        }
+
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(fj_sketch_from_circle(-1.).is_null());
+        assert_eq!(last_error().as_deref(), Some("`radius` must be positive"));
+
+        let status = unsafe { fj_export(ptr::null(), ptr::null(), 0.) };
+        assert_eq!(status, FjStatus::InvalidArgument);
+        assert_eq!(last_error().as_deref(), Some("Shape is null"));
+    }
+
+    #[test]
+    fn panics_are_reported_as_errors() {
+        let shape = catch_panic(ptr::null_mut(), || -> *mut FjShape {
+            panic!("Something went wrong")
+        });
+
+        assert!(shape.is_null());
+        assert_eq!(
+            last_error().as_deref(),
+            Some("Panic: Something went wrong")
+        );
+    }
+
+    fn last_error() -> Option<String> {
+        let error = fj_last_error();
+        if error.is_null() {
+            return None;
+        }
+
+        // Safe, as the library returns either null or a valid string.
+        let error = unsafe { CStr::from_ptr(error) };
+        Some(error.to_str().unwrap().to_owned())
+    }
+}
@@ -1,6 +1,9 @@
-use std::thread::JoinHandle;
+use std::{path::PathBuf, thread::JoinHandle};
 
 use crossbeam_channel::Sender;
+use fj_export::ExportOptions;
+use fj_interop::assembly::Part;
+use fj_math::Tolerance;
 use fj_operations::shape_processor::ShapeProcessor;
 
 use crate::{EventLoopClosed, HostThread, Model, ModelEvent};
@@ -37,7 +40,68 @@ impl Host {
         self.model_loaded = true;
     }
 
-    /// Whether a model has been sent to the host yet
+    /// Send the path of a mesh to the host for importing and processing
+    ///
+    /// The mesh replaces the current model, which is no longer watched for
+    /// changes.
+    pub fn load_mesh(&mut self, path: PathBuf) {
+        self.command_tx
+            .try_send(HostCommand::LoadMesh(path))
+            .expect("Host channel disconnected unexpectedly");
+        self.model_loaded = true;
+    }
+
+    /// Process the model again, using the given tolerance
+    ///
+    /// The tolerance is used for all further processing, replacing the one
+    /// the shape processor was created with.
+    pub fn set_tolerance(&mut self, tolerance: Tolerance) {
+        self.command_tx
+            .try_send(HostCommand::SetTolerance(tolerance))
+            .expect("Host channel disconnected unexpectedly");
+    }
+
+    /// Process the model again, with or without collecting the intermediate
+    /// results of the kernel's algorithms
+    ///
+    /// See [`ShapeProcessor::debug_algorithms`].
+    pub fn set_debug_algorithms(&mut self, enabled: bool) {
+        self.command_tx
+            .try_send(HostCommand::SetDebugAlgorithms(enabled))
+            .expect("Host channel disconnected unexpectedly");
+    }
+
+    /// Process the model again, with its parts placed as given
+    ///
+    /// Only has an effect on models that are assemblies. See
+    /// [`Model::evaluate_parts`].
+    pub fn set_parts(&mut self, parts: Vec<Part>) {
+        self.command_tx
+            .try_send(HostCommand::SetParts(parts))
+            .expect("Host channel disconnected unexpectedly");
+    }
+
+    /// Export the model to a file in the background
+    ///
+    /// The last evaluation of the model is processed again, using the given
+    /// tolerance, if any, or the tolerance that is used for displaying it
+    /// otherwise. Once it has been exported, [`ModelEvent::Exported`] is sent.
+    pub fn export(
+        &mut self,
+        path: PathBuf,
+        tolerance: Option<Tolerance>,
+        options: ExportOptions,
+    ) {
+        self.command_tx
+            .try_send(HostCommand::Export {
+                path,
+                tolerance,
+                options,
+            })
+            .expect("Host channel disconnected unexpectedly");
+    }
+
+    /// Whether a model or mesh has been sent to the host yet
     pub fn is_model_loaded(&self) -> bool {
         self.model_loaded
     }
@@ -82,6 +146,25 @@ impl Host {
 pub enum HostCommand {
     /// Load a model to be evaluated and processed
     LoadModel(Model),
+    /// Import a mesh from a file and process it, replacing the current model
+    LoadMesh(PathBuf),
     /// Used by a `Watcher` to trigger evaluation when a model is edited
     TriggerEvaluation,
+    /// Change the tolerance, and process the model again
+    SetTolerance(Tolerance),
+    /// Change whether algorithm debug info is collected, and process the model
+    /// again
+    SetDebugAlgorithms(bool),
+    /// Change the placement of the parts of an assembly, and process the model
+    /// again
+    SetParts(Vec<Part>),
+    /// Export the model to a file, optionally using a different tolerance
+    Export {
+        /// The path of the file that the model is exported to
+        path: PathBuf,
+        /// The tolerance that the model is approximated with for the export
+        tolerance: Option<Tolerance>,
+        /// The options that control how the model is exported
+        options: ExportOptions,
+    },
 }
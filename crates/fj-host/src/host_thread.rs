@@ -1,8 +1,21 @@
-use std::thread::{self, JoinHandle};
+use std::{
+    panic,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
 
 use crossbeam_channel::{self, Receiver, Sender};
-use fj_interop::processed_shape::ProcessedShape;
-use fj_operations::shape_processor::ShapeProcessor;
+use fj_export::ExportOptions;
+use fj_interop::{
+    assembly::Part,
+    cancellation::CancellationToken,
+    processed_shape::{PartialShape, ProcessedShape},
+    progress::Progress,
+    status::{Source, StatusMessage},
+};
+use fj_math::Tolerance;
+use fj_operations::shape_processor::{self, ShapeProcessor};
 
 use crate::{Error, HostCommand, Model, Watcher};
 
@@ -16,6 +29,10 @@ pub(crate) struct HostThread {
     model_event_tx: Sender<ModelEvent>,
     command_tx: Sender<HostCommand>,
     command_rx: Receiver<HostCommand>,
+    queued_command: Option<HostCommand>,
+    processing: Option<Processing>,
+    parts: Vec<Part>,
+    last_evaluation: Arc<Mutex<Option<fj::Shape>>>,
 }
 
 impl HostThread {
@@ -32,6 +49,10 @@ impl HostThread {
             model_event_tx: event_loop_proxy,
             command_tx,
             command_rx,
+            queued_command: None,
+            processing: None,
+            parts: Vec::new(),
+            last_evaluation: Arc::new(Mutex::new(None)),
         };
 
         let join_handle = host_thread.spawn_thread();
@@ -43,10 +64,10 @@ impl HostThread {
         thread::Builder::new()
             .name("host".to_string())
             .spawn(move || -> Result<(), EventLoopClosed> {
-                let mut model: Option<Model> = None;
+                let mut model: Option<Arc<Model>> = None;
                 let mut _watcher: Option<Watcher> = None;
 
-                while let Ok(command) = self.command_rx.recv() {
+                while let Some(command) = self.next_command() {
                     match command {
                         HostCommand::LoadModel(new_model) => {
                             // Right now, `fj-app` will only load a new model
@@ -56,7 +77,7 @@ impl HostThread {
                             // if the prior watcher sent `TriggerEvaluation`
                             // before it and the model were replaced.
                             match Watcher::watch_model(
-                                new_model.watch_path(),
+                                &new_model,
                                 self.command_tx.clone(),
                             ) {
                                 Ok(watcher) => {
@@ -69,59 +90,395 @@ impl HostThread {
                                     continue;
                                 }
                             }
-                            self.process_model(&new_model)?;
+                            self.parts = new_model.parts();
+                            self.clear_last_evaluation();
+
+                            let new_model = Arc::new(new_model);
+                            self.process_model(new_model.clone())?;
                             model = Some(new_model);
                         }
+                        HostCommand::LoadMesh(path) => {
+                            // The mesh replaces the model, so there's nothing
+                            // left to watch.
+                            model = None;
+                            _watcher = None;
+                            self.parts.clear();
+                            self.clear_last_evaluation();
+
+                            self.process_mesh(path)?;
+                        }
                         HostCommand::TriggerEvaluation => {
+                            // Any processing that is still going on is
+                            // outdated now. Cancel it, instead of waiting for
+                            // it to finish.
+                            self.cancel_processing()?;
+
+                            // Saving a file can trigger multiple evaluations
+                            // in a row. We only care about the latest change.
+                            self.skip_queued_evaluations();
+
                             self.send_event(ModelEvent::ChangeDetected)?;
                             if let Some(model) = &model {
-                                self.process_model(model)?;
+                                self.process_model(model.clone())?;
+                            }
+                        }
+                        HostCommand::SetTolerance(tolerance) => {
+                            self.shape_processor.tolerance = Some(tolerance);
+
+                            // The model hasn't changed, so processing reuses
+                            // the boundary representation of the last build.
+                            // Mostly, only the approximation is computed
+                            // again.
+                            if let Some(model) = &model {
+                                self.process_model(model.clone())?;
+                            }
+                        }
+                        HostCommand::SetDebugAlgorithms(enabled) => {
+                            let processor = &mut self.shape_processor;
+                            if processor.debug_algorithms == enabled {
+                                continue;
+                            }
+                            processor.debug_algorithms = enabled;
+
+                            if let Some(model) = &model {
+                                self.process_model(model.clone())?;
+                            }
+                        }
+                        HostCommand::SetParts(parts) => {
+                            if self.parts == parts {
+                                continue;
+                            }
+                            self.parts = parts;
+
+                            if let Some(model) = &model {
+                                self.process_model(model.clone())?;
                             }
                         }
+                        HostCommand::Export {
+                            path,
+                            tolerance,
+                            options,
+                        } => {
+                            self.export(path, tolerance, options);
+                        }
                     }
                 }
 
+                self.cancel_processing()?;
+
                 Ok(())
             })
             .expect("Cannot create OS thread for host")
     }
 
-    // Evaluate and process a model.
-    fn process_model(&mut self, model: &Model) -> Result<(), EventLoopClosed> {
-        let evaluation = match model.evaluate() {
-            Ok(evaluation) => evaluation,
+    // Evaluate and process a model in the background.
+    //
+    // Any processing that is still going on gets cancelled first.
+    fn process_model(
+        &mut self,
+        model: Arc<Model>,
+    ) -> Result<(), EventLoopClosed> {
+        let parts = self.parts.clone();
+        let last_evaluation = self.last_evaluation.clone();
+        self.spawn_processing(move |shape_processor, cancellation, tx| {
+            Processing::run(
+                &model,
+                &parts,
+                &last_evaluation,
+                shape_processor,
+                cancellation,
+                tx,
+            )
+        })
+    }
 
-            Err(err) => {
-                self.send_event(ModelEvent::Error(err))?;
-                return Ok(());
-            }
+    // Import and process a mesh in the background.
+    //
+    // Any processing that is still going on gets cancelled first.
+    fn process_mesh(&mut self, path: PathBuf) -> Result<(), EventLoopClosed> {
+        self.spawn_processing(move |shape_processor, cancellation, tx| {
+            Processing::run_mesh(&path, shape_processor, cancellation, tx)
+        })
+    }
+
+    // Process and export the last evaluation of the model in the background.
+    //
+    // That is the evaluation that is displayed, or is about to be. The model
+    // isn't evaluated again, so changes that are made to it after the export
+    // has been requested don't affect the export. Processing for the export
+    // runs alongside processing for display, which is fine, as it neither
+    // compiles nor loads the model, and clones of `ShapeProcessor` share their
+    // cache safely.
+    fn export(
+        &self,
+        path: PathBuf,
+        tolerance: Option<Tolerance>,
+        options: ExportOptions,
+    ) {
+        let mut shape_processor = self.shape_processor.clone();
+        if tolerance.is_some() {
+            shape_processor.tolerance = tolerance;
+        }
+        let shape = self
+            .last_evaluation
+            .lock()
+            .expect("Last evaluation is only stored while holding the lock")
+            .clone();
+        let model_event_tx = self.model_event_tx.clone();
+
+        thread::Builder::new()
+            .name("export".to_string())
+            .spawn(move || {
+                let result =
+                    shape.ok_or(Error::NothingToExport).and_then(|shape| {
+                        let shape = shape_processor.process(&shape)?;
+                        fj_export::export_with_options(
+                            &shape.mesh,
+                            &path,
+                            &options,
+                        )
+                        .map_err(Error::Export)?;
+
+                        Ok(shape.mesh_defects)
+                    });
+
+                let event = match result {
+                    Ok(defects) => {
+                        if let Some(defect) = defects.first() {
+                            let warning = format!(
+                                "Exported mesh is not watertight ({} \
+                                defects, first: {defect})",
+                                defects.len()
+                            );
+                            let _ = send_event(
+                                &model_event_tx,
+                                ModelEvent::Status(StatusMessage::warn(
+                                    Source::Export,
+                                    warning,
+                                )),
+                            );
+                        }
+
+                        ModelEvent::Exported(path)
+                    }
+                    Err(err) => ModelEvent::ExportFailed(err),
+                };
+
+                // If the event loop has closed, there's nobody left to tell.
+                let _ = send_event(&model_event_tx, event);
+            })
+            .expect("Cannot create OS thread for export");
+    }
+
+    // Run processing on a worker thread, cancelling any that is still going on.
+    fn spawn_processing(
+        &mut self,
+        run: impl FnOnce(
+                &ShapeProcessor,
+                &CancellationToken,
+                &Sender<ModelEvent>,
+            ) -> Result<(), EventLoopClosed>
+            + Send
+            + 'static,
+    ) -> Result<(), EventLoopClosed> {
+        self.cancel_processing()?;
+
+        let cancellation = CancellationToken::new();
+        let shape_processor = self.shape_processor.clone();
+        let model_event_tx = self.model_event_tx.clone();
+
+        let worker = {
+            let cancellation = cancellation.clone();
+
+            thread::Builder::new()
+                .name("processing".to_string())
+                .spawn(move || {
+                    run(&shape_processor, &cancellation, &model_event_tx)
+                })
+                .expect("Cannot create OS thread for processing")
         };
 
-        self.send_event(ModelEvent::Evaluated)?;
+        self.processing = Some(Processing {
+            cancellation,
+            worker,
+        });
 
-        if let Some(warn) = evaluation.warning {
-            self.send_event(ModelEvent::Warning(warn))?;
-        }
+        Ok(())
+    }
 
-        match self.shape_processor.process(&evaluation.shape) {
-            Ok(shape) => self.send_event(ModelEvent::ProcessedShape(shape))?,
+    // Cancel any processing that is still going on, and wait for it to stop.
+    fn cancel_processing(&mut self) -> Result<(), EventLoopClosed> {
+        if let Some(processing) = self.processing.take() {
+            processing.cancellation.cancel();
 
-            Err(err) => {
-                self.send_event(ModelEvent::Error(err.into()))?;
+            match processing.worker.join() {
+                Ok(result) => result?,
+                // Processing the shape panicked. Propagate the panic to the
+                // host thread, so the main thread can notice.
+                Err(payload) => panic::resume_unwind(payload),
             }
         }
 
         Ok(())
     }
 
+    // Forget the last evaluation, as it belongs to a model that was replaced.
+    fn clear_last_evaluation(&mut self) {
+        *self
+            .last_evaluation
+            .lock()
+            .expect("Last evaluation is only stored while holding the lock") =
+            None;
+    }
+
+    // Receive the next command, waiting for one if none is queued.
+    fn next_command(&mut self) -> Option<HostCommand> {
+        self.queued_command
+            .take()
+            .or_else(|| self.command_rx.recv().ok())
+    }
+
+    // Remove any `TriggerEvaluation` commands from the front of the queue.
+    fn skip_queued_evaluations(&mut self) {
+        for command in self.command_rx.try_iter() {
+            if let HostCommand::TriggerEvaluation = command {
+                continue;
+            }
+
+            self.queued_command = Some(command);
+            break;
+        }
+    }
+
     // Send a message to the event loop.
     fn send_event(&mut self, event: ModelEvent) -> Result<(), EventLoopClosed> {
-        self.model_event_tx
-            .send(event)
-            .map_err(|_| EventLoopClosed)?;
+        send_event(&self.model_event_tx, event)
+    }
+}
+
+// Processing of a model that is going on in the background
+struct Processing {
+    cancellation: CancellationToken,
+    worker: JoinHandle<Result<(), EventLoopClosed>>,
+}
+
+impl Processing {
+    // Evaluate and process a model, placing its parts as given.
+    //
+    // Checks `cancellation` between the steps, and stops sending events once
+    // it has been cancelled. The results of cancelled processing are outdated
+    // and must not reach the event loop. Otherwise, the evaluated shape is
+    // stored in `last_evaluation`, for exports.
+    fn run(
+        model: &Model,
+        parts: &[Part],
+        last_evaluation: &Mutex<Option<fj::Shape>>,
+        shape_processor: &ShapeProcessor,
+        cancellation: &CancellationToken,
+        model_event_tx: &Sender<ModelEvent>,
+    ) -> Result<(), EventLoopClosed> {
+        let send =
+            |event| send_unless_cancelled(cancellation, model_event_tx, event);
+
+        let evaluation =
+            match model.evaluate_parts_with_cancellation(parts, cancellation) {
+                Ok(evaluation) => evaluation,
+
+                Err(Error::Cancelled(_)) => return Ok(()),
+
+                Err(err) => {
+                    send(ModelEvent::Error(err))?;
+                    return Ok(());
+                }
+            };
+
+        if !cancellation.is_cancelled() {
+            *last_evaluation.lock().expect(
+                "Last evaluation is only stored while holding the lock",
+            ) = Some(evaluation.shape.clone());
+        }
+
+        send(ModelEvent::Evaluated)?;
+
+        if let Some(warn) = evaluation.warning {
+            send(ModelEvent::Status(StatusMessage::warn(Source::Host, warn)))?;
+        }
+
+        let progress = |progress| {
+            // If the event loop has closed, there's nobody left to report
+            // progress to. The error is going to be noticed when sending the
+            // result, so it's safe to ignore here.
+            let _ = send(ModelEvent::Progress(progress));
+        };
+
+        let partial = |shape| {
+            // Same as above. Partial shapes are only a preview of the result.
+            let _ = send(ModelEvent::PartialShape(shape));
+        };
+
+        match shape_processor.process_incrementally(
+            &evaluation.shape,
+            cancellation,
+            progress,
+            partial,
+        ) {
+            Ok(shape) => send(ModelEvent::ProcessedShape(shape))?,
+
+            Err(shape_processor::Error::Cancelled(_)) => {}
+
+            Err(err) => {
+                send(ModelEvent::Error(err.into()))?;
+            }
+        }
 
         Ok(())
     }
+
+    // Import a mesh and process it.
+    //
+    // Importing can't be cancelled, but the result is discarded, if processing
+    // has been cancelled in the meantime.
+    fn run_mesh(
+        path: &Path,
+        shape_processor: &ShapeProcessor,
+        cancellation: &CancellationToken,
+        model_event_tx: &Sender<ModelEvent>,
+    ) -> Result<(), EventLoopClosed> {
+        let event = match fj_export::import(path) {
+            Ok(mesh) => {
+                ModelEvent::ProcessedShape(shape_processor.process_mesh(mesh))
+            }
+            Err(err) => ModelEvent::Error(err.into()),
+        };
+
+        send_unless_cancelled(cancellation, model_event_tx, event)
+    }
+}
+
+// Send a message to the event loop, unless processing has been cancelled.
+//
+// The results of cancelled processing are outdated and must not reach the
+// event loop.
+fn send_unless_cancelled(
+    cancellation: &CancellationToken,
+    model_event_tx: &Sender<ModelEvent>,
+    event: ModelEvent,
+) -> Result<(), EventLoopClosed> {
+    if cancellation.is_cancelled() {
+        return Ok(());
+    }
+
+    send_event(model_event_tx, event)
+}
+
+// Send a message to the event loop.
+fn send_event(
+    model_event_tx: &Sender<ModelEvent>,
+    event: ModelEvent,
+) -> Result<(), EventLoopClosed> {
+    model_event_tx.send(event).map_err(|_| EventLoopClosed)?;
+
+    Ok(())
 }
 
 /// An event emitted by the host thread
@@ -136,12 +493,32 @@ pub enum ModelEvent {
     /// The model has been evaluated
     Evaluated,
 
+    /// Processing of the model has progressed
+    Progress(Progress),
+
+    /// Part of the model has been processed
+    ///
+    /// Sent while processing is still going on. Superseded by the next
+    /// [`ModelEvent::PartialShape`] or [`ModelEvent::ProcessedShape`].
+    PartialShape(PartialShape),
+
     /// The model has been processed
     ProcessedShape(ProcessedShape),
 
-    /// A warning
-    Warning(String),
+    /// A status message
+    ///
+    /// Errors are not sent as status messages, but as [`ModelEvent::Error`].
+    Status(StatusMessage),
 
     /// An error
     Error(Error),
+
+    /// The model has been exported to the file at the given path
+    Exported(PathBuf),
+
+    /// Exporting the model has failed
+    ///
+    /// Unlike [`ModelEvent::Error`], this doesn't affect the model that is
+    /// being displayed.
+    ExportFailed(Error),
 }
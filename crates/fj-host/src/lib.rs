@@ -20,6 +20,12 @@ mod host_thread;
 mod model;
 mod parameters;
 mod platform;
+#[cfg(feature = "process")]
+mod process;
+#[cfg(feature = "script")]
+pub mod script;
+#[cfg(feature = "wasm")]
+mod wasm;
 mod watcher;
 
 pub(crate) use self::host_thread::{EventLoopClosed, HostThread};
@@ -27,7 +33,10 @@ pub(crate) use self::host_thread::{EventLoopClosed, HostThread};
 pub use self::{
     host::{Host, HostCommand},
     host_thread::ModelEvent,
-    model::{Error, Evaluation, Model},
+    model::{Backend, Error, Evaluation, Model},
     parameters::Parameters,
     watcher::Watcher,
 };
+
+#[cfg(feature = "process")]
+pub use self::process::run_model_process;
@@ -1,11 +1,22 @@
 use std::{
-    io,
+    io::{self, Read},
     path::{Path, PathBuf},
-    process::Command,
+    process::{Child, Command, Output, Stdio},
     str,
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
-use fj::{abi, version::Version};
+use fj::{
+    abi,
+    syntax::{Group as _, Transform as _},
+    version::Version,
+};
+use fj_interop::{
+    assembly::Part,
+    cancellation::{CancellationToken, Cancelled},
+    units::Units,
+};
 use fj_operations::shape_processor;
 use tracing::debug;
 
@@ -13,10 +24,10 @@ use crate::{platform::HostPlatform, Parameters};
 
 /// Represents a Fornjot model
 pub struct Model {
-    src_path: PathBuf,
-    lib_path: PathBuf,
-    manifest_path: PathBuf,
+    name: String,
     parameters: Parameters,
+    units: Option<Units>,
+    source: Source,
 }
 
 impl Model {
@@ -24,6 +35,13 @@ impl Model {
     ///
     /// The path expected here is the root directory of the model's Cargo
     /// package, that is the folder containing `Cargo.toml`.
+    ///
+    /// A model can declare the units of its coordinates in its `Cargo.toml`:
+    ///
+    /// ``` toml
+    /// [package.metadata.fj]
+    /// units = "mm"
+    /// ```
     pub fn new(
         path: impl AsRef<Path>,
         parameters: Parameters,
@@ -37,141 +55,641 @@ impl Model {
             .exec()?;
 
         let pkg = package_associated_with_directory(&metadata, &crate_dir)?;
-        let src_path = crate_dir.join("src");
+
+        let units = pkg
+            .metadata
+            .get("fj")
+            .and_then(|fj| fj.get("units"))
+            .map(|units| {
+                units
+                    .as_str()
+                    .unwrap_or_default()
+                    .parse()
+                    .map_err(Error::Units)
+            })
+            .transpose()?;
+
+        let name = pkg.name.replace('-', "_");
+        let target_dir = metadata.target_directory.clone().into_std_path_buf();
+
+        let watch_paths = WatchPaths {
+            model: crate_dir,
+            dependencies: pkg
+                .dependencies
+                .iter()
+                .filter_map(|dependency| dependency.path.clone())
+                .map(|path| path.into_std_path_buf())
+                .collect(),
+            watch_dependencies: false,
+            ignored: vec![target_dir.clone()],
+        };
 
         let lib_path = {
-            let name = pkg.name.replace('-', "_");
             let file = HostPlatform::lib_file_name(&name);
-            let target_dir =
-                metadata.target_directory.clone().into_std_path_buf();
             target_dir.join("debug").join(file)
         };
+        #[cfg(feature = "wasm")]
+        let wasm_path = target_dir
+            .join(WASM_TARGET)
+            .join("debug")
+            .join(format!("{name}.wasm"));
 
         Ok(Self {
-            src_path,
-            lib_path,
-            manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+            name: pkg.name.clone(),
             parameters,
+            units,
+            source: Source::Crate {
+                lib_path,
+                #[cfg(feature = "wasm")]
+                wasm_path,
+                manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+                backend: Backend::default(),
+                watch_paths,
+            },
         })
     }
 
-    /// Access the path that needs to be watched for changes
-    pub fn watch_path(&self) -> PathBuf {
-        self.src_path.clone()
+    /// Initialize the model using the path to a script
+    ///
+    /// Scripts are written in [Rhai]. They don't need to be compiled, so no
+    /// Rust toolchain is required to evaluate them. See [`crate::script`] for
+    /// the functions that are available to scripts.
+    ///
+    /// [Rhai]: https://rhai.rs/
+    #[cfg(feature = "script")]
+    pub fn from_script(
+        path: impl AsRef<Path>,
+        parameters: Parameters,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref().canonicalize()?;
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name,
+            parameters,
+            units: None,
+            source: Source::Script { path },
+        })
     }
 
-    /// Evaluate the model
-    pub fn evaluate(&self) -> Result<Evaluation, Error> {
-        let manifest_path = self.manifest_path.display().to_string();
+    /// Initialize the model using a path that might be a crate or a script
+    ///
+    /// With the `script` feature enabled, a path with the `.rhai` extension is
+    /// loaded as a script. A path to a `Cargo.toml` is taken to mean the crate
+    /// it belongs to. Any other path is expected to be the root directory of a
+    /// model crate.
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        parameters: Parameters,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        #[cfg(feature = "script")]
+        if path.extension() == Some("rhai".as_ref()) {
+            return Self::from_script(path, parameters);
+        }
 
-        let cargo_output = Command::new("cargo")
-            .arg("rustc")
-            .args(["--manifest-path", &manifest_path])
-            .args(["--crate-type", "cdylib"])
-            .output()?;
+        let crate_dir = match path.parent() {
+            Some(parent) if path.ends_with("Cargo.toml") => parent,
+            _ => path,
+        };
 
-        if !cargo_output.status.success() {
-            let output =
-                String::from_utf8(cargo_output.stderr).unwrap_or_else(|_| {
-                    String::from("Failed to fetch command output")
-                });
+        Self::new(crate_dir, parameters)
+    }
 
-            return Err(Error::Compile { output });
+    /// Combine several models into an assembly
+    ///
+    /// Each model becomes a part of the assembly, that can be moved and hidden
+    /// independently of the others. See [`Model::evaluate_parts`]. The parts
+    /// are all watched for changes.
+    ///
+    /// The assembly uses the units of its first part.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `parts` is empty.
+    pub fn assembly(parts: Vec<Model>) -> Self {
+        assert!(!parts.is_empty(), "An assembly needs at least one part");
+
+        let name = parts
+            .iter()
+            .map(|part| part.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        Self {
+            name,
+            parameters: Parameters::empty(),
+            units: parts[0].units,
+            source: Source::Assembly { parts },
         }
+    }
 
-        let seconds_taken = str::from_utf8(&cargo_output.stderr)
-            .unwrap()
-            .rsplit_once(' ')
-            .unwrap()
-            .1
-            .trim();
-
-        let mut warnings = None;
-
-        // So, strictly speaking this is all unsound:
-        // - `Library::new` requires us to abide by the arbitrary requirements
-        //   of any library initialization or termination routines.
-        // - `Library::get` requires us to specify the correct type for the
-        //   model function.
-        // - The model function itself is `unsafe`, because it is a function
-        //   from across an FFI interface.
-        //
-        // Typical models won't have initialization or termination routines (I
-        // think), should abide by the `ModelFn` signature, and might not do
-        // anything unsafe. But we have no way to know that the library the user
-        // told us to load actually does (I think).
-        //
-        // I don't know of a way to fix this. We should take this as motivation
-        // to switch to a better technique:
-        // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
-            let lib = libloading::Library::new(&self.lib_path)
-                .map_err(Error::LoadingLibrary)?;
-
-            let version_pkg_host = fj::version::VERSION_PKG.to_string();
-
-            let version_pkg_model: libloading::Symbol<*const Version> =
-                lib.get(b"VERSION_PKG").map_err(Error::LoadingVersion)?;
-            let version_pkg_model = (**version_pkg_model).to_string();
-
-            debug!(
-                "Comparing package versions (host: {}, model: {})",
-                version_pkg_host, version_pkg_model
-            );
-            if version_pkg_host != version_pkg_model {
-                let host = String::from_utf8_lossy(version_pkg_host.as_bytes())
-                    .into_owned();
-                let model = version_pkg_model;
-
-                return Err(Error::VersionMismatch { host, model });
+    /// Select the backend that is used to load the model
+    ///
+    /// Uses [`Backend::Dylib`], unless specified otherwise. Has no effect on
+    /// models that are scripts, as those are always evaluated by the host. An
+    /// assembly uses the backend for all of its parts.
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        match &mut self.source {
+            Source::Crate { backend: b, .. } => *b = backend,
+            Source::Assembly { parts } => {
+                *parts = parts
+                    .drain(..)
+                    .map(|part| part.with_backend(backend))
+                    .collect();
             }
+            #[cfg(feature = "script")]
+            Source::Script { .. } => {}
+        }
+        self
+    }
 
-            let version_full_host = fj::version::VERSION_FULL.to_string();
+    /// Select whether the model's path dependencies are watched for changes
+    ///
+    /// By default, only the model crate itself is watched. With this enabled,
+    /// changes to crates that it depends on through a `path` dependency cause
+    /// the model to be reloaded too. This only applies to direct dependencies.
+    pub fn with_dependencies_watched(mut self, watch: bool) -> Self {
+        match &mut self.source {
+            Source::Crate { watch_paths, .. } => {
+                watch_paths.watch_dependencies = watch;
+            }
+            Source::Assembly { parts } => {
+                *parts = parts
+                    .drain(..)
+                    .map(|part| part.with_dependencies_watched(watch))
+                    .collect();
+            }
+            #[cfg(feature = "script")]
+            Source::Script { .. } => {}
+        }
+        self
+    }
+
+    /// Access the name of the model
+    ///
+    /// This is the name of the model's package, or the file name of a script.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-            let version_full_model: libloading::Symbol<*const Version> =
-                lib.get(b"VERSION_FULL").map_err(Error::LoadingVersion)?;
-            let version_full_model = (**version_full_model).to_string();
+    /// Access the units that the model's coordinates are given in
+    ///
+    /// Returns `None`, if the model doesn't declare any units.
+    pub fn units(&self) -> Option<Units> {
+        self.units
+    }
 
-            debug!(
-                "Comparing full versions (host: {}, model: {})",
-                version_full_host, version_full_model
-            );
-            if version_full_host != version_full_model {
-                let host =
-                    String::from_utf8_lossy(version_full_host.as_bytes())
-                        .into_owned();
-                let model = version_full_model;
+    /// Access the parts of the model, if it is an assembly
+    ///
+    /// The parts are returned in their initial placement. Returns an empty
+    /// list, if the model isn't an assembly.
+    pub fn parts(&self) -> Vec<Part> {
+        match &self.source {
+            Source::Assembly { parts } => {
+                parts.iter().map(|part| Part::new(part.name())).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Access the paths that need to be watched for changes
+    ///
+    /// For a model crate, this is the whole crate directory, including its
+    /// `Cargo.toml` and all modules, and optionally its path dependencies. For
+    /// a script, it's only the script itself. For an assembly, it's the paths
+    /// of all of its parts.
+    pub fn watch_paths(&self) -> Vec<PathBuf> {
+        match &self.source {
+            Source::Crate { watch_paths, .. } => {
+                let mut paths = vec![watch_paths.model.clone()];
+                if watch_paths.watch_dependencies {
+                    paths.extend(watch_paths.dependencies.iter().cloned());
+                }
+
+                paths
+            }
+            Source::Assembly { parts } => {
+                parts.iter().flat_map(Model::watch_paths).collect()
+            }
+            #[cfg(feature = "script")]
+            Source::Script { path } => vec![path.clone()],
+        }
+    }
 
-                warnings =
-                    Some(format!("{}", Error::VersionMismatch { host, model }));
+    /// Access the paths within the watched paths, whose changes are ignored
+    ///
+    /// This is the target directory of the model crate, as building the model
+    /// writes to it.
+    pub fn ignored_paths(&self) -> Vec<PathBuf> {
+        match &self.source {
+            Source::Crate { watch_paths, .. } => watch_paths.ignored.clone(),
+            Source::Assembly { parts } => {
+                parts.iter().flat_map(Model::ignored_paths).collect()
             }
+            #[cfg(feature = "script")]
+            Source::Script { .. } => Vec::new(),
+        }
+    }
 
-            let init: libloading::Symbol<abi::InitFunction> = lib
-                .get(abi::INIT_FUNCTION_NAME.as_bytes())
-                .map_err(Error::LoadingInit)?;
+    /// Evaluate the model
+    ///
+    /// The parts of an assembly are evaluated in their initial placement.
+    pub fn evaluate(&self) -> Result<Evaluation, Error> {
+        self.evaluate_parts(&self.parts())
+    }
 
-            let mut host = Host::new(&self.parameters);
+    /// Evaluate the model, placing the parts of an assembly as given
+    ///
+    /// The parts are matched with those of the assembly by their position in
+    /// the list. Parts that are hidden are left out, but at least one part
+    /// must be visible. The placement has no effect on models that aren't
+    /// assemblies.
+    pub fn evaluate_parts(
+        &self,
+        placement: &[Part],
+    ) -> Result<Evaluation, Error> {
+        self.evaluate_parts_with_cancellation(
+            placement,
+            &CancellationToken::new(),
+        )
+    }
 
-            match init(&mut abi::Host::from(&mut host)) {
-                abi::ffi_safe::Result::Ok(_metadata) => {}
-                abi::ffi_safe::Result::Err(e) => {
-                    return Err(Error::InitializeModel(e.into()));
+    /// Evaluate the model, placing the parts of an assembly as given, until
+    /// cancelled
+    ///
+    /// Works like [`Model::evaluate_parts`]. Compiling the model, evaluating it
+    /// in a child process, and evaluating a script stop as soon as
+    /// `cancellation` has been cancelled. Models that are loaded into this
+    /// process can't be interrupted. For those, cancellation takes effect once
+    /// they have been evaluated.
+    ///
+    /// Returns [`Error::Cancelled`], if evaluation has been cancelled.
+    pub fn evaluate_parts_with_cancellation(
+        &self,
+        placement: &[Part],
+        cancellation: &CancellationToken,
+    ) -> Result<Evaluation, Error> {
+        let evaluation = match &self.source {
+            Source::Crate {
+                lib_path,
+                #[cfg(feature = "wasm")]
+                wasm_path,
+                manifest_path,
+                backend,
+                ..
+            } => {
+                let compile_time =
+                    compile(manifest_path, *backend, cancellation)?;
+
+                let (shape, warning) = match backend {
+                    Backend::Dylib => load_dylib(lib_path, &self.parameters)?,
+
+                    #[cfg(feature = "wasm")]
+                    Backend::Wasm => (
+                        crate::wasm::evaluate(wasm_path, &self.parameters)?,
+                        None,
+                    ),
+
+                    #[cfg(feature = "process")]
+                    Backend::Process => crate::process::evaluate(
+                        lib_path,
+                        &self.parameters,
+                        cancellation,
+                    )?,
+                };
+
+                Evaluation {
+                    shape,
+                    compile_time,
+                    warning,
                 }
             }
 
-            let model = host.take_model().ok_or(Error::NoModelRegistered)?;
+            #[cfg(feature = "script")]
+            Source::Script { path } => {
+                let start = std::time::Instant::now();
+                let shape = crate::script::evaluate(
+                    path,
+                    &self.parameters,
+                    cancellation,
+                )?;
+
+                Evaluation {
+                    shape,
+                    compile_time: format!(
+                        "{:.2}s",
+                        start.elapsed().as_secs_f64()
+                    ),
+                    warning: None,
+                }
+            }
 
-            model.shape(&host).map_err(Error::Shape)?
+            Source::Assembly { parts } => {
+                let mut shape: Option<fj::Shape> = None;
+                let mut compile_times = Vec::new();
+                let mut warnings = Vec::new();
+
+                for (part, placement) in parts.iter().zip(placement) {
+                    if !placement.visible {
+                        continue;
+                    }
+
+                    let evaluation = part.evaluate_parts_with_cancellation(
+                        &part.parts(),
+                        cancellation,
+                    )?;
+                    let part_shape = fj::Shape::from(
+                        evaluation.shape.translate(placement.offset),
+                    );
+
+                    shape = Some(match shape {
+                        Some(shape) => shape.group(&part_shape).into(),
+                        None => part_shape,
+                    });
+                    compile_times.push(evaluation.compile_time);
+                    warnings.extend(evaluation.warning);
+                }
+
+                Evaluation {
+                    shape: shape.ok_or(Error::NoVisibleParts)?,
+                    compile_time: compile_times.join(", "),
+                    warning: (!warnings.is_empty())
+                        .then(|| warnings.join("\n")),
+                }
+            }
         };
 
-        Ok(Evaluation {
-            shape,
-            compile_time: seconds_taken.into(),
-            warning: warnings,
-        })
+        // Models that have been loaded into this process ignore cancellation.
+        // Don't return their outdated result.
+        cancellation.check()?;
+
+        Ok(evaluation)
     }
 }
 
+// The paths that are watched for changes to a model
+struct WatchPaths {
+    model: PathBuf,
+    dependencies: Vec<PathBuf>,
+    watch_dependencies: bool,
+    ignored: Vec<PathBuf>,
+}
+
+// Where a model comes from
+enum Source {
+    // The model is a Rust crate
+    Crate {
+        lib_path: PathBuf,
+        #[cfg(feature = "wasm")]
+        wasm_path: PathBuf,
+        manifest_path: PathBuf,
+        backend: Backend,
+        watch_paths: WatchPaths,
+    },
+
+    // The model is an assembly of other models
+    Assembly {
+        parts: Vec<Model>,
+    },
+
+    // The model is a script
+    #[cfg(feature = "script")]
+    Script {
+        path: PathBuf,
+    },
+}
+
+// Compile a model crate. Returns the time it took, from the Cargo output.
+fn compile(
+    manifest_path: &Path,
+    backend: Backend,
+    cancellation: &CancellationToken,
+) -> Result<String, Error> {
+    let manifest_path = manifest_path.display().to_string();
+
+    let mut command = Command::new("cargo");
+    command
+        .arg("rustc")
+        .args(["--manifest-path", &manifest_path])
+        .args(["--crate-type", "cdylib"])
+        // The output is shown in the GUI, if compiling fails. Keep the colors
+        // for that, even though it doesn't go to a terminal.
+        .args(["--color", "always"]);
+
+    #[cfg(feature = "wasm")]
+    if backend == Backend::Wasm {
+        command.args(["--target", WASM_TARGET]);
+    }
+    #[cfg(not(feature = "wasm"))]
+    let _ = backend;
+
+    let cargo = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let cargo_output = wait_with_output(cargo, cancellation)?;
+
+    if !cargo_output.status.success() {
+        let output = String::from_utf8(cargo_output.stderr)
+            .unwrap_or_else(|_| String::from("Failed to fetch command output"));
+
+        return Err(Error::Compile { output });
+    }
+
+    let seconds_taken = str::from_utf8(&cargo_output.stderr)
+        .unwrap()
+        .rsplit_once(' ')
+        .unwrap()
+        .1
+        .trim();
+
+    Ok(seconds_taken.into())
+}
+
+// Wait for a child process to exit, and collect its output. Kills the process,
+// if `cancellation` is cancelled before that.
+pub(crate) fn wait_with_output(
+    mut child: Child,
+    cancellation: &CancellationToken,
+) -> Result<Output, Error> {
+    // The child might fill up a pipe and block, before it exits. Read both
+    // pipes in the background, so that can't happen.
+    let stdout = read_to_end_in_background(child.stdout.take());
+    let stderr = read_to_end_in_background(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if cancellation.is_cancelled() {
+            // The child might have exited in the meantime, which makes killing
+            // it fail. Either way, it's gone after waiting for it.
+            let _ = child.kill();
+            child.wait()?;
+
+            return Err(Cancelled.into());
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let [stdout, stderr] = [stdout, stderr].map(|output| {
+        output
+            .join()
+            .expect("Thread reading output of child process panicked")
+    });
+
+    Ok(Output {
+        status,
+        stdout: stdout?,
+        stderr: stderr?,
+    })
+}
+
+fn read_to_end_in_background(
+    pipe: Option<impl Read + Send + 'static>,
+) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut output)?;
+        }
+
+        Ok(output)
+    })
+}
+
+// Load the model as a dynamic library, and compute its shape. Returns a
+// warning, if the model uses a slightly different version of Fornjot.
+pub(crate) fn load_dylib(
+    lib_path: &Path,
+    parameters: &Parameters,
+) -> Result<(fj::Shape, Option<String>), Error> {
+    let mut warnings = None;
+
+    // So, strictly speaking this is all unsound:
+    // - `Library::new` requires us to abide by the arbitrary requirements
+    //   of any library initialization or termination routines.
+    // - `Library::get` requires us to specify the correct type for the
+    //   model function.
+    // - The model function itself is `unsafe`, because it is a function
+    //   from across an FFI interface.
+    //
+    // Typical models won't have initialization or termination routines (I
+    // think), should abide by the `ModelFn` signature, and might not do
+    // anything unsafe. But we have no way to know that the library the user
+    // told us to load actually does (I think).
+    //
+    // I don't know of a way to fix this. We should take this as motivation
+    // to switch to a better technique:
+    // https://github.com/hannobraun/Fornjot/issues/71
+    let shape = unsafe {
+        let lib = libloading::Library::new(lib_path)
+            .map_err(Error::LoadingLibrary)?;
+
+        let version_pkg_host = fj::version::VERSION_PKG.to_string();
+
+        let version_pkg_model: libloading::Symbol<*const Version> =
+            lib.get(b"VERSION_PKG").map_err(Error::LoadingVersion)?;
+        let version_pkg_model = (**version_pkg_model).to_string();
+
+        debug!(
+            "Comparing package versions (host: {}, model: {})",
+            version_pkg_host, version_pkg_model
+        );
+        if version_pkg_host != version_pkg_model {
+            let host = String::from_utf8_lossy(version_pkg_host.as_bytes())
+                .into_owned();
+            let model = version_pkg_model;
+
+            return Err(Error::VersionMismatch { host, model });
+        }
+
+        let version_full_host = fj::version::VERSION_FULL.to_string();
+
+        let version_full_model: libloading::Symbol<*const Version> =
+            lib.get(b"VERSION_FULL").map_err(Error::LoadingVersion)?;
+        let version_full_model = (**version_full_model).to_string();
+
+        debug!(
+            "Comparing full versions (host: {}, model: {})",
+            version_full_host, version_full_model
+        );
+        if version_full_host != version_full_model {
+            let host = String::from_utf8_lossy(version_full_host.as_bytes())
+                .into_owned();
+            let model = version_full_model;
+
+            warnings =
+                Some(format!("{}", Error::VersionMismatch { host, model }));
+        }
+
+        let init: libloading::Symbol<abi::InitFunction> = lib
+            .get(abi::INIT_FUNCTION_NAME.as_bytes())
+            .map_err(Error::LoadingInit)?;
+
+        let mut host = Host::new(parameters);
+
+        match init(&mut abi::Host::from(&mut host)) {
+            abi::ffi_safe::Result::Ok(_metadata) => {}
+            abi::ffi_safe::Result::Err(e) => {
+                return Err(Error::InitializeModel(e.into()));
+            }
+        }
+
+        let model = host.take_model().ok_or(Error::NoModelRegistered)?;
+
+        model.shape(&host).map_err(Error::Shape)?
+    };
+
+    Ok((shape, warnings))
+}
+
+/// The mechanism that is used to load a model
+///
+/// See [`Model::with_backend`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Backend {
+    /// Compile the model to a dynamic library and load it into the host
+    ///
+    /// This is fast, but the model runs with the same privileges as the host.
+    /// Host and model must be compiled with the same version of Rust, as Rust
+    /// has no stable ABI.
+    #[default]
+    Dylib,
+
+    /// Compile the model to WebAssembly and run it in a sandbox
+    ///
+    /// This requires the `wasm32-wasi` target to be installed, and the model to
+    /// enable the `serde` feature of the `fj` crate.
+    #[cfg(feature = "wasm")]
+    Wasm,
+
+    /// Load the model as a dynamic library into a child process
+    ///
+    /// The shape is sent back to the host after it has been computed. A model
+    /// that crashes can't take down the host this way.
+    ///
+    /// The child process is started from the host's executable, which must
+    /// call [`run_model_process`] before doing anything else.
+    ///
+    /// [`run_model_process`]: crate::run_model_process
+    #[cfg(feature = "process")]
+    Process,
+}
+
+/// The target that models are compiled to, when using [`Backend::Wasm`]
+#[cfg(feature = "wasm")]
+const WASM_TARGET: &str = "wasm32-wasi";
+
 /// The result of evaluating a model
 ///
 /// See [`Model::evaluate`].
@@ -298,9 +816,11 @@ pub enum Error {
     },
 
     /// Model failed to compile
-    #[error("Error compiling model\n{output}")]
+    #[error("Error compiling model\n{}", without_ansi_escapes(output))]
     Compile {
         /// The compiler output
+        ///
+        /// The output is colored using ANSI escape codes.
         output: String,
     },
 
@@ -326,6 +846,54 @@ pub enum Error {
     #[error("Shape processing error")]
     ShapeProcessor(#[from] shape_processor::Error),
 
+    /// Error importing a mesh
+    #[error("Error importing mesh")]
+    Import(#[from] fj_export::Error),
+
+    /// Error exporting a model
+    #[error("Error exporting model")]
+    Export(#[source] fj_export::Error),
+
+    /// Evaluating the model has been cancelled
+    #[error("Evaluating the model has been cancelled")]
+    Cancelled(#[from] Cancelled),
+
+    /// All parts of an assembly have been hidden
+    #[error("All parts of the assembly are hidden")]
+    NoVisibleParts,
+
+    /// An export has been requested, but no model has been evaluated
+    ///
+    /// Meshes that have been imported can't be exported again.
+    #[error("No model has been evaluated that could be exported")]
+    NothingToExport,
+
+    /// Error loading or running a model compiled to WebAssembly
+    #[cfg(feature = "wasm")]
+    #[error("Error running WebAssembly model")]
+    Wasm(#[source] wasmtime::Error),
+
+    /// Evaluating the model in a child process failed
+    #[cfg(feature = "process")]
+    #[error("Error evaluating model in child process\n{0}")]
+    ModelProcess(String),
+
+    /// The child process that evaluated the model crashed
+    #[cfg(feature = "process")]
+    #[error("Model process crashed ({status})\n{output}")]
+    ModelProcessCrashed {
+        /// The exit status of the child process
+        status: String,
+
+        /// The output the child process printed to stderr
+        output: String,
+    },
+
+    /// Error evaluating a script
+    #[cfg(feature = "script")]
+    #[error("Error evaluating script")]
+    Script(#[source] Box<rhai::EvalAltResult>),
+
     /// Error while watching the model code for changes
     #[error("Error watching model for changes")]
     Notify(#[from] notify::Error),
@@ -335,6 +903,10 @@ pub enum Error {
     #[error("Unable to determine the crate's metadata")]
     CargoMetadata(#[from] cargo_metadata::Error),
 
+    /// The model declares units that are not known
+    #[error("Invalid units in model metadata")]
+    Units(#[source] fj_interop::units::ParseUnitsError),
+
     /// The user pointed us to a directory, but it doesn't look like that was
     /// a crate root (i.e. the folder containing `Cargo.toml`).
     #[error(
@@ -352,3 +924,22 @@ pub enum Error {
         possible_paths: Vec<PathBuf>,
     },
 }
+
+// Remove the ANSI escape codes that color the compiler output.
+fn without_ansi_escapes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let end = rest
+            .find(|c| ('@'..='~').contains(&c))
+            .map_or(rest.len(), |end| end + 1);
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+
+    result
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use fj_interop::cancellation::CancellationToken;
+
+use crate::{
+    model::{load_dylib, wait_with_output},
+    Error, Parameters,
+};
+
+/// The environment variable that tells a child process which model to load
+const LIB_PATH_VAR: &str = "FJ_HOST_MODEL_PROCESS";
+
+/// Run as a model process, if this process was started as one
+///
+/// Processes that use [`Backend::Process`] evaluate models in a child process
+/// that is started from their own executable. This function must be called at
+/// the start of `main`, before anything else happens. If the current process
+/// was started as a model process, this function evaluates the model, sends the
+/// result to the parent process, and exits. Otherwise, it returns immediately.
+///
+/// [`Backend::Process`]: crate::Backend::Process
+pub fn run_model_process() {
+    let Some(lib_path) = env::var_os(LIB_PATH_VAR) else {
+        return;
+    };
+
+    let parameters: HashMap<String, String> =
+        serde_json::from_reader(io::stdin())
+            .expect("Failed to read parameters from parent process");
+
+    let response =
+        match load_dylib(&PathBuf::from(lib_path), &Parameters(parameters)) {
+            Ok((shape, warning)) => Response::Evaluated { shape, warning },
+            Err(err) => Response::Error(error_with_sources(&err)),
+        };
+
+    // The model might print to stdout too. Put the response into its own line,
+    // so the parent process can find it.
+    let response = serde_json::to_string(&response)
+        .expect("Failed to serialize model process response");
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout)
+        .and_then(|()| writeln!(stdout, "{response}"))
+        .and_then(|()| stdout.flush())
+        .expect("Failed to send response to parent process");
+
+    process::exit(0);
+}
+
+/// Evaluate a model compiled to a dynamic library in a child process
+///
+/// The child process is killed, if `cancellation` is cancelled before it has
+/// finished.
+pub fn evaluate(
+    lib_path: &Path,
+    parameters: &Parameters,
+    cancellation: &CancellationToken,
+) -> Result<(fj::Shape, Option<String>), Error> {
+    let mut child = Command::new(env::current_exe()?)
+        .env(LIB_PATH_VAR, OsString::from(lib_path))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    {
+        // Dropping `stdin` at the end of this block closes it, which lets the
+        // child know that all parameters have been sent.
+        let stdin = child
+            .stdin
+            .take()
+            .expect("Expected `stdin` of child process to be piped");
+        serde_json::to_writer(stdin, &parameters.0)
+            .map_err(|err| Error::Io(err.into()))?;
+    }
+
+    let output = wait_with_output(child, cancellation)?;
+
+    if !output.status.success() {
+        return Err(Error::ModelProcessCrashed {
+            status: output.status.to_string(),
+            output: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines().collect::<Vec<_>>();
+    let response = lines.pop().unwrap_or_default();
+
+    // Anything but the last line was printed by the model itself.
+    for line in lines.into_iter().filter(|line| !line.is_empty()) {
+        println!("{line}");
+    }
+
+    let response = serde_json::from_str(response).map_err(|err| {
+        Error::ModelProcessCrashed {
+            status: format!("invalid response ({err})"),
+            output: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    })?;
+
+    match response {
+        Response::Evaluated { shape, warning } => Ok((shape, warning)),
+        Response::Error(err) => Err(Error::ModelProcess(err)),
+    }
+}
+
+fn error_with_sources(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(&format!("\nCaused by: {err}"));
+        source = err.source();
+    }
+
+    message
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum Response {
+    Evaluated {
+        shape: fj::Shape,
+        warning: Option<String>,
+    },
+    Error(String),
+}
@@ -0,0 +1,306 @@
+//! Models written in the [Rhai] scripting language
+//!
+//! Scripts create shapes by calling the functions listed below. The value of
+//! the last expression in the script is the shape of the model. It can either
+//! be a 2D or a 3D shape.
+//!
+//! Numbers can be written as integers or floating-point values. Points and
+//! vectors are written as arrays of numbers (`[x, y]` or `[x, y, z]`). Colors
+//! are written as arrays of four integers (`[r, g, b, a]`), or as hex strings
+//! (`"#rrggbb"` or `"#rrggbbaa"`).
+//!
+//! | Function                            | Result                            |
+//! |-------------------------------------|-----------------------------------|
+//! | `param(name, default)`              | The numeric model parameter       |
+//! | `sketch([[x, y], ...])`             | A polygon with the given points   |
+//! | `circle(radius)`                    | A circle                          |
+//! | `color(shape, [r, g, b, a])`        | The 2D shape, in the given color  |
+//! | `color(shape, "#rrggbb")`           | The 2D shape, in the given color  |
+//! | `difference(shape, other)`          | The 2D shape, minus the other one |
+//! | `sweep(shape, [x, y, z])`           | The 2D shape, swept along a path  |
+//! | `group(shape, other)`               | Both shapes together              |
+//! | `translate(shape, [x, y, z])`       | The translated shape              |
+//! | `rotate(shape, [x, y, z], degrees)` | The shape, rotated about an axis  |
+//!
+//! Here's an example of a script that creates a spacer:
+//!
+//! ```rhai
+//! let outer = circle(param("outer", 1.0));
+//! let inner = circle(param("inner", 0.5));
+//!
+//! difference(outer, inner).sweep([0, 0, param("height", 1.0)])
+//! ```
+//!
+//! [Rhai]: https://rhai.rs/
+
+use std::path::Path;
+
+use fj_interop::cancellation::{CancellationToken, Cancelled};
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+
+use crate::{Error, Parameters};
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Evaluate the script at the given path, returning its shape
+///
+/// The script is terminated, if `cancellation` is cancelled while it is
+/// running.
+pub(crate) fn evaluate(
+    path: &Path,
+    parameters: &Parameters,
+    cancellation: &CancellationToken,
+) -> Result<fj::Shape, Error> {
+    let mut engine = engine(parameters.clone());
+
+    let token = cancellation.clone();
+    engine.on_progress(move |_| token.is_cancelled().then_some(Dynamic::UNIT));
+
+    let shape =
+        engine
+            .eval_file::<Dynamic>(path.to_path_buf())
+            .map_err(|err| match *err {
+                EvalAltResult::ErrorTerminated(..) => Cancelled.into(),
+                _ => Error::Script(err),
+            })?;
+
+    into_shape(shape).map_err(Error::Script)
+}
+
+fn engine(parameters: Parameters) -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<fj::Shape2d>("Shape2d")
+        .register_type_with_name::<fj::Shape>("Shape");
+
+    engine.register_fn(
+        "param",
+        move |name: &str, default: Dynamic| -> ScriptResult<f64> {
+            match parameters.get(name) {
+                Some(value) => value.parse().map_err(|_| {
+                    format!("Parameter `{name}` is not a number: `{value}`")
+                        .into()
+                }),
+                None => number(&default),
+            }
+        },
+    );
+
+    engine.register_fn("sketch", |points: Array| -> ScriptResult<_> {
+        let sketch = fj::Sketch::from_points(points_2d(points)?)
+            .ok_or("A sketch needs at least one point")?;
+
+        Ok(fj::Shape2d::from(sketch))
+    });
+    engine.register_fn("circle", |radius: Dynamic| -> ScriptResult<_> {
+        let circle = fj::Circle::from_radius(number(&radius)?);
+        Ok(fj::Shape2d::from(fj::Sketch::from_circle(circle)))
+    });
+    engine.register_fn(
+        "color",
+        |shape: fj::Shape2d, color: Array| -> ScriptResult<_> {
+            let fj::Shape2d::Sketch(sketch) = shape else {
+                return Err("Only sketches can be colored".into());
+            };
+
+            let mut rgba = [0; 4];
+            for (channel, value) in rgba.iter_mut().zip(array::<4>(&color)?) {
+                *channel = color_channel(value)?;
+            }
+
+            Ok(fj::Shape2d::from(sketch.with_color(rgba)))
+        },
+    );
+    engine.register_fn(
+        "color",
+        |shape: fj::Shape2d, hex: &str| -> ScriptResult<_> {
+            let fj::Shape2d::Sketch(sketch) = shape else {
+                return Err("Only sketches can be colored".into());
+            };
+
+            let color =
+                hex.parse::<fj::Color>().map_err(|err| err.to_string())?;
+
+            Ok(fj::Shape2d::from(sketch.with_color(color)))
+        },
+    );
+    engine.register_fn("difference", |a: fj::Shape2d, b: fj::Shape2d| {
+        fj::Shape2d::from(fj::Difference2d::from_shapes([a, b]))
+    });
+    engine.register_fn(
+        "sweep",
+        |shape: fj::Shape2d, path: Array| -> ScriptResult<_> {
+            let path = array::<3>(&path)?;
+            Ok(fj::Shape::from(fj::Sweep::from_path(shape, path)))
+        },
+    );
+    engine.register_fn(
+        "sweep_symmetric",
+        |shape: fj::Shape2d, path: Array| -> ScriptResult<_> {
+            let path = array::<3>(&path)?;
+            Ok(fj::Shape::from(fj::Sweep::symmetric(shape, path)))
+        },
+    );
+    engine.register_fn(
+        "rib",
+        |profile: Array,
+         thickness: Dynamic,
+         height: Dynamic|
+         -> ScriptResult<_> {
+            let rib = fj::Rib::from_profile(
+                points_2d(profile)?,
+                number(&thickness)?,
+                number(&height)?,
+            )
+            .ok_or(
+                "A rib needs at least two points, and a positive thickness \
+                and height",
+            )?;
+            Ok(fj::Shape::from(rib))
+        },
+    );
+    engine.register_fn("group", |a: Dynamic, b: Dynamic| -> ScriptResult<_> {
+        let group = fj::Group {
+            a: into_shape(a)?,
+            b: into_shape(b)?,
+        };
+        Ok(fj::Shape::from(group))
+    });
+    engine.register_fn(
+        "translate",
+        |shape: Dynamic, offset: Array| -> ScriptResult<_> {
+            let transform = fj::Transform {
+                shape: into_shape(shape)?,
+                axis: [1., 0., 0.],
+                angle: fj::Angle::from_rad(0.),
+                offset: array::<3>(&offset)?,
+            };
+            Ok(fj::Shape::from(transform))
+        },
+    );
+    engine.register_fn(
+        "rotate",
+        |shape: Dynamic, axis: Array, angle: Dynamic| -> ScriptResult<_> {
+            let transform = fj::Transform {
+                shape: into_shape(shape)?,
+                axis: array::<3>(&axis)?,
+                angle: fj::Angle::from_deg(number(&angle)?),
+                offset: [0.; 3],
+            };
+            Ok(fj::Shape::from(transform))
+        },
+    );
+    engine.register_fn(
+        "scale",
+        |shape: Dynamic, factors: Array| -> ScriptResult<_> {
+            let scale = fj::Scale {
+                shape: into_shape(shape)?,
+                factors: array::<3>(&factors)?,
+            };
+            Ok(fj::Shape::from(scale))
+        },
+    );
+
+    engine
+}
+
+fn into_shape(value: Dynamic) -> ScriptResult<fj::Shape> {
+    if value.is::<fj::Shape>() {
+        return Ok(value.cast());
+    }
+    if value.is::<fj::Shape2d>() {
+        return Ok(value.cast::<fj::Shape2d>().into());
+    }
+
+    Err(format!("Expected a shape, got `{}`", value.type_name()).into())
+}
+
+fn number(value: &Dynamic) -> ScriptResult<f64> {
+    if let Ok(value) = value.as_float() {
+        return Ok(value);
+    }
+    if let Ok(value) = value.as_int() {
+        return Ok(value as f64);
+    }
+
+    Err(format!("Expected a number, got `{}`", value.type_name()).into())
+}
+
+fn color_channel(value: f64) -> ScriptResult<u8> {
+    // This also rejects `NaN`, which isn't within any range.
+    if !(0. ..=255.).contains(&value) || value.fract() != 0. {
+        return Err(format!(
+            "Expected a color channel, an integer from 0 to 255, got {value}"
+        )
+        .into());
+    }
+
+    Ok(value as u8)
+}
+
+fn points_2d(points: Array) -> ScriptResult<Vec<[f64; 2]>> {
+    points
+        .into_iter()
+        .map(|point| {
+            let point = point
+                .into_array()
+                .map_err(|_| "Expected a point, like `[x, y]`")?;
+            array::<2>(&point)
+        })
+        .collect()
+}
+
+fn array<const D: usize>(values: &[Dynamic]) -> ScriptResult<[f64; D]> {
+    if values.len() != D {
+        return Err(format!(
+            "Expected an array of {D} numbers, got {}",
+            values.len()
+        )
+        .into());
+    }
+
+    let mut array = [0.; D];
+    for (a, value) in array.iter_mut().zip(values) {
+        *a = number(value)?;
+    }
+
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use rhai::Dynamic;
+
+    use crate::Parameters;
+
+    #[test]
+    fn color() {
+        let color = |color: &str| {
+            let script =
+                format!("color(sketch([[0, 0], [1, 0], [0, 1]]), {color})");
+            super::engine(Parameters::empty())
+                .eval::<Dynamic>(&script)
+                .map(|shape| shape.cast::<fj::Shape2d>().color())
+        };
+
+        assert_eq!(color("[255, 128, 0, 255]").unwrap(), [255, 128, 0, 255]);
+        assert_eq!(color("[255.0, 0, 0, 0]").unwrap(), [255, 0, 0, 0]);
+        assert_eq!(color("\"#ff8000\"").unwrap(), [255, 128, 0, 255]);
+
+        for invalid in [
+            "[256, 0, 0, 255]",
+            "[-1, 0, 0, 255]",
+            "[0.5, 0, 0, 255]",
+            "[0, 0, 0, 1000.0]",
+            "[0, 0, 0]",
+        ] {
+            assert!(color(invalid).is_err(), "{invalid} is not a color");
+        }
+    }
+
+    #[test]
+    fn color_nan() {
+        assert!(super::color_channel(f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use fj::abi::wasm::{self, Response};
+use wasmtime::{Engine, Linker, Module, Store};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
+
+use crate::{Error, Parameters};
+
+/// Load a model compiled to WebAssembly and compute its shape
+///
+/// The model runs in a sandbox. Apart from standard I/O, it has no access to
+/// the host system.
+pub fn evaluate(
+    path: &Path,
+    parameters: &Parameters,
+) -> Result<fj::Shape, Error> {
+    let engine = Engine::default();
+    let module = Module::from_file(&engine, path).map_err(Error::Wasm)?;
+
+    let mut linker = Linker::<WasiCtx>::new(&engine);
+    wasmtime_wasi::add_to_linker(&mut linker, |ctx| ctx)
+        .map_err(Error::Wasm)?;
+
+    let wasi = WasiCtxBuilder::new().inherit_stdio().build();
+    let mut store = Store::new(&engine, wasi);
+
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .map_err(Error::Wasm)?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or_else(|| Error::Wasm(wasmtime::Error::msg("No memory export")))?;
+    let alloc = instance
+        .get_typed_func::<u32, u32>(&mut store, wasm::ALLOC_FUNCTION_NAME)
+        .map_err(Error::Wasm)?;
+    let shape = instance
+        .get_typed_func::<(u32, u32), u64>(
+            &mut store,
+            wasm::SHAPE_FUNCTION_NAME,
+        )
+        .map_err(Error::Wasm)?;
+
+    let arguments = serde_json::to_vec(&parameters.0)
+        .expect("Failed to serialize model parameters");
+    let arguments_len = arguments.len() as u32;
+
+    let arguments_ptr =
+        alloc.call(&mut store, arguments_len).map_err(Error::Wasm)?;
+    memory
+        .write(&mut store, arguments_ptr as usize, &arguments)
+        .map_err(|err| Error::Wasm(err.into()))?;
+
+    let response = shape
+        .call(&mut store, (arguments_ptr, arguments_len))
+        .map_err(Error::Wasm)?;
+    let (response_ptr, response_len) = wasm::unpack(response);
+
+    let mut response = vec![0; response_len as usize];
+    memory
+        .read(&store, response_ptr as usize, &mut response)
+        .map_err(|err| Error::Wasm(err.into()))?;
+
+    let response = serde_json::from_slice(&response)
+        .map_err(|err| Error::Wasm(err.into()))?;
+
+    match response {
+        Response::Shape(shape) => Ok(shape),
+        Response::InitializeModel(err) => {
+            Err(Error::InitializeModel(err.into()))
+        }
+        Response::NoModelRegistered => Err(Error::NoModelRegistered),
+        Response::ComputeShape(err) => Err(Error::Shape(err.into())),
+    }
+}
@@ -1,9 +1,13 @@
-use std::{collections::HashSet, ffi::OsStr, path::Path};
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 use crossbeam_channel::Sender;
 use notify::Watcher as _;
 
-use crate::{Error, HostCommand};
+use crate::{Error, HostCommand, Model};
 
 /// Watches a model for changes, reloading it continually
 pub struct Watcher {
@@ -12,11 +16,13 @@ pub struct Watcher {
 
 impl Watcher {
     /// Watch the provided model for changes
+    ///
+    /// See [`Model::watch_paths`] for which paths are watched.
     pub fn watch_model(
-        watch_path: impl AsRef<Path>,
+        model: &Model,
         host_tx: Sender<HostCommand>,
     ) -> Result<Self, Error> {
-        let watch_path = watch_path.as_ref();
+        let ignored_paths = model.ignored_paths();
 
         let mut watcher = notify::recommended_watcher(
             move |event: notify::Result<notify::Event>| {
@@ -25,31 +31,25 @@ impl Watcher {
                 let event = event.expect("Error handling watch event");
 
                 // Various acceptable ModifyKind kinds. Varies across platforms
-                // (e.g. MacOs vs. Windows10)
+                // (e.g. MacOs vs. Windows10). Files being created, removed, or
+                // renamed count too, as some editors save files that way.
                 if let notify::EventKind::Modify(
                     notify::event::ModifyKind::Any
+                    | notify::event::ModifyKind::Name(_)
                     | notify::event::ModifyKind::Data(
                         notify::event::DataChange::Any
                         | notify::event::DataChange::Content,
                     ),
-                ) = event.kind
+                )
+                | notify::EventKind::Create(_)
+                | notify::EventKind::Remove(_) = event.kind
                 {
-                    let file_ext = event
+                    if !event
                         .paths
-                        .get(0)
-                        .expect("File path missing in watch event")
-                        .extension();
-
-                    let black_list = HashSet::from([
-                        OsStr::new("swp"),
-                        OsStr::new("tmp"),
-                        OsStr::new("swx"),
-                    ]);
-
-                    if let Some(ext) = file_ext {
-                        if black_list.contains(ext) {
-                            return;
-                        }
+                        .iter()
+                        .any(|path| is_relevant(path, &ignored_paths))
+                    {
+                        return;
                     }
 
                     // This will panic, if the other end is disconnected, which
@@ -64,10 +64,41 @@ impl Watcher {
             },
         )?;
 
-        watcher.watch(watch_path, notify::RecursiveMode::Recursive)?;
+        for path in model.watch_paths() {
+            watcher.watch(&path, notify::RecursiveMode::Recursive)?;
+        }
 
         Ok(Self {
             _watcher: Box::new(watcher),
         })
     }
 }
+
+// Determine whether a change to a path can affect the model.
+fn is_relevant(path: &Path, ignored_paths: &[PathBuf]) -> bool {
+    if ignored_paths
+        .iter()
+        .any(|ignored| path.starts_with(ignored))
+    {
+        return false;
+    }
+
+    // Cargo updates the lock file while building the model.
+    if path.file_name() == Some(OsStr::new("Cargo.lock")) {
+        return false;
+    }
+
+    // Editors create temporary files next to the ones being edited.
+    let black_list = HashSet::from([
+        OsStr::new("swp"),
+        OsStr::new("tmp"),
+        OsStr::new("swx"),
+    ]);
+    if let Some(ext) = path.extension() {
+        if black_list.contains(ext) {
+            return false;
+        }
+    }
+
+    true
+}
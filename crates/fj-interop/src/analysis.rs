@@ -0,0 +1,286 @@
+//! Results of analyzing shapes
+
+use std::fmt;
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+/// The mass properties of a solid
+///
+/// All properties assume a uniform density of `1`, which makes the mass of the
+/// solid equal to its volume. To get the mass and inertia tensor for a
+/// different density, multiply them by that density.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    /// The volume of the solid
+    pub volume: Scalar,
+
+    /// The area of the solid's surface
+    pub surface_area: Scalar,
+
+    /// The center of mass of the solid
+    pub center_of_mass: Point<3>,
+
+    /// The inertia tensor of the solid, relative to its center of mass
+    pub inertia: [[Scalar; 3]; 3],
+
+    /// Indicates whether the properties are exact
+    ///
+    /// If this is `false`, the properties were computed from an approximation
+    /// of the solid, and are only as accurate as that approximation.
+    pub exact: bool,
+}
+
+impl fmt::Display for MassProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = f.precision().unwrap_or(3);
+        let [x, y, z] = self.center_of_mass.coords.components;
+
+        writeln!(f, "Volume: {:.p$}", self.volume.into_f64())?;
+        writeln!(f, "Surface area: {:.p$}", self.surface_area.into_f64())?;
+        writeln!(
+            f,
+            "Center of mass: {:.p$} {:.p$} {:.p$}",
+            x.into_f64(),
+            y.into_f64(),
+            z.into_f64(),
+        )?;
+        writeln!(f, "Inertia tensor:")?;
+        for [a, b, c] in self.inertia {
+            writeln!(
+                f,
+                "  {:.p$} {:.p$} {:.p$}",
+                a.into_f64(),
+                b.into_f64(),
+                c.into_f64(),
+            )?;
+        }
+
+        if !self.exact {
+            writeln!(f, "(approximated from triangle mesh)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The dimensions of a shape
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dimensions {
+    /// The bounding boxes of the whole shape
+    pub overall: BoundingBoxes,
+
+    /// The bounding boxes of each of the shape's bodies
+    ///
+    /// A body is a part of the shape that is not connected to any other part.
+    pub bodies: Vec<BoundingBoxes>,
+}
+
+impl fmt::Display for Dimensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = f.precision().unwrap_or(3);
+
+        writeln!(f, "Size: {:.p$}", self.overall)?;
+
+        if self.bodies.len() > 1 {
+            writeln!(f, "Bodies:")?;
+            for (i, body) in self.bodies.iter().enumerate() {
+                writeln!(f, "  {}: {body:.p$}", i + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The bounding boxes of a shape, or one of its bodies
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoundingBoxes {
+    /// The axis-aligned bounding box
+    pub aabb: Aabb<3>,
+
+    /// The oriented bounding box
+    pub obb: Obb,
+}
+
+impl fmt::Display for BoundingBoxes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = f.precision().unwrap_or(3);
+        let [x, y, z] = self.aabb.size().components.map(Scalar::into_f64);
+        let [a, b, c] = self.obb.size.map(Scalar::into_f64);
+
+        write!(
+            f,
+            "{x:.p$} {y:.p$} {z:.p$} (oriented: {a:.p$} {b:.p$} {c:.p$})"
+        )
+    }
+}
+
+/// An oriented bounding box (OBB)
+///
+/// Unlike an axis-aligned bounding box, an OBB can be rotated, which allows it
+/// to fit shapes more tightly, that are not aligned with the coordinate axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb {
+    /// The center point of the OBB
+    pub center: Point<3>,
+
+    /// The axes of the OBB
+    ///
+    /// The axes are normalized and orthogonal to each other.
+    pub axes: [Vector<3>; 3],
+
+    /// The size of the OBB along each of its axes
+    pub size: [Scalar; 3],
+}
+
+impl Default for Obb {
+    fn default() -> Self {
+        Self {
+            center: Point::origin(),
+            axes: [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()],
+            size: [Scalar::ZERO; 3],
+        }
+    }
+}
+
+/// The overhangs of a shape, relative to a build direction
+///
+/// Used to estimate the support that a shape needs, when it is 3D-printed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overhangs {
+    /// The direction in which the shape is built up, layer by layer
+    pub build_direction: Vector<3>,
+
+    /// The maximum overhang angle, in radians, that can be printed unsupported
+    pub threshold: Scalar,
+
+    /// The overhang of each triangle of the shape's mesh, in order
+    pub triangles: Vec<TriangleOverhang>,
+
+    /// The total area of all triangles that need support
+    pub area: Scalar,
+}
+
+impl fmt::Display for Overhangs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = f.precision().unwrap_or(3);
+        let num_triangles = self
+            .triangles
+            .iter()
+            .filter(|triangle| triangle.needs_support)
+            .count();
+
+        writeln!(
+            f,
+            "Overhang area needing support: {:.p$} ({num_triangles} triangles \
+            steeper than {:.0}°)",
+            self.area.into_f64(),
+            self.threshold.into_f64().to_degrees(),
+        )
+    }
+}
+
+/// The overhang of a single triangle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleOverhang {
+    /// The overhang angle of the triangle, in radians
+    ///
+    /// This is the angle between the triangle and the build direction. It is
+    /// `0` for vertical walls and `π/2` for triangles that face straight down.
+    /// Triangles that face upward have a negative angle.
+    pub angle: Scalar,
+
+    /// Indicates whether the triangle needs support
+    ///
+    /// This is the case, if its angle exceeds the threshold, and it doesn't
+    /// rest on the build plate.
+    pub needs_support: bool,
+}
+
+/// The differences between two shapes
+///
+/// Used to verify that a change to a model, like a refactoring, didn't change
+/// its geometry, or to find out where it did.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapeDiff {
+    /// The volume of the second shape, minus the volume of the first
+    pub volume_difference: Scalar,
+
+    /// The Hausdorff distance between the surfaces of the shapes
+    ///
+    /// This is the largest distance from any point on the surface of either
+    /// shape to the surface of the other.
+    pub hausdorff_distance: Scalar,
+
+    /// The maximum distance at which the shapes are considered to be the same
+    pub tolerance: Scalar,
+
+    /// The regions in which the shapes are further apart than the tolerance
+    pub changed_regions: Vec<Aabb<3>>,
+}
+
+impl ShapeDiff {
+    /// Indicate whether the shapes are the same, within the tolerance
+    pub fn is_unchanged(&self) -> bool {
+        self.changed_regions.is_empty()
+    }
+}
+
+impl fmt::Display for ShapeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = f.precision().unwrap_or(3);
+
+        writeln!(
+            f,
+            "Volume difference: {:+.p$}",
+            self.volume_difference.into_f64()
+        )?;
+        writeln!(
+            f,
+            "Hausdorff distance: {:.p$} (tolerance: {:.p$})",
+            self.hausdorff_distance.into_f64(),
+            self.tolerance.into_f64(),
+        )?;
+
+        if self.is_unchanged() {
+            return writeln!(f, "No changes");
+        }
+
+        writeln!(f, "Changed regions:")?;
+        for region in &self.changed_regions {
+            let [min, max] = [region.min, region.max]
+                .map(|point| point.coords.components.map(Scalar::into_f64));
+            writeln!(
+                f,
+                "- [{:.p$}, {:.p$}, {:.p$}] to [{:.p$}, {:.p$}, {:.p$}]",
+                min[0], min[1], min[2], max[0], max[1], max[2],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The curvature of a shape, estimated at each vertex of its mesh
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Curvature {
+    /// The curvature at each vertex of the shape's mesh, in order
+    pub vertices: Vec<VertexCurvature>,
+}
+
+/// The curvature at a single vertex
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VertexCurvature {
+    /// The Gaussian curvature
+    ///
+    /// This is the product of the principal curvatures. It is positive for
+    /// convex or concave regions, negative for saddle-shaped ones, and zero
+    /// for flat or developable ones, like planes or cylinders.
+    pub gaussian: Scalar,
+
+    /// The mean curvature
+    ///
+    /// This is the average of the principal curvatures. It is positive for
+    /// convex regions, given that the mesh is oriented outward.
+    pub mean: Scalar,
+}
@@ -0,0 +1,28 @@
+//! Assemblies of several models
+
+/// The placement of a model within an assembly
+///
+/// Each model of an assembly is shown as a separate part, that can be moved
+/// and hidden independently of the others.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Part {
+    /// The name of the part
+    pub name: String,
+
+    /// The offset of the part from its original position
+    pub offset: [f64; 3],
+
+    /// Whether the part is shown
+    pub visible: bool,
+}
+
+impl Part {
+    /// Create a part that is visible, at its original position
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            offset: [0.; 3],
+            visible: true,
+        }
+    }
+}
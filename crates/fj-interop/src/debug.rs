@@ -4,13 +4,35 @@
 //! ecosystem. The types in here aren't very useful in themselves, but they
 //! define an interface that other crates use to communicate between each other.
 
-use fj_math::{Point, Segment};
+use std::collections::BTreeMap;
 
-/// Debug info from the CAD kernel that can be visualized
+use fj_math::{Point, Segment, Triangle};
+
+use crate::mesh::{Color, FaceId};
+
+/// Debug info that can be visualized
+///
+/// Most of it comes from the CAD kernel. Points, lines, and labels come from
+/// the model.
 #[derive(Clone, Debug, Default)]
 pub struct DebugInfo {
     /// Rays being used during face triangulation
     pub triangle_edge_checks: Vec<TriangleEdgeCheck>,
+
+    /// Points defined by the model
+    pub points: Vec<(Point<3>, Color)>,
+
+    /// Line segments defined by the model
+    pub lines: Vec<(Segment<3>, Color)>,
+
+    /// Text labels defined by the model
+    pub labels: Vec<DebugLabel>,
+
+    /// Intermediate results of the kernel's algorithms, for each face
+    ///
+    /// Collecting these is expensive, so this is empty, unless it has been
+    /// requested explicitly.
+    pub faces: BTreeMap<FaceId, FaceDebugInfo>,
 }
 
 impl DebugInfo {
@@ -26,9 +48,43 @@ impl DebugInfo {
     /// allocations.
     pub fn clear(&mut self) {
         self.triangle_edge_checks.clear();
+        self.points.clear();
+        self.lines.clear();
+        self.labels.clear();
+        self.faces.clear();
     }
 }
 
+/// Intermediate results of the kernel's algorithms, for a single face
+#[derive(Clone, Debug, Default)]
+pub struct FaceDebugInfo {
+    /// The points that the boundary of the face was approximated with
+    pub approx_points: Vec<Point<3>>,
+
+    /// The triangles that the face was triangulated into
+    ///
+    /// These are the triangles before the mesh is welded or simplified.
+    pub triangles: Vec<Triangle<3>>,
+
+    /// The intersections of the face with other faces
+    ///
+    /// Intersection curves are approximated with line segments.
+    pub intersections: Vec<Segment<3>>,
+}
+
+/// A text label, attached to a point
+#[derive(Clone, Debug)]
+pub struct DebugLabel {
+    /// The point that the label is attached to
+    pub position: Point<3>,
+
+    /// The text of the label
+    pub text: String,
+
+    /// The color of the text
+    pub color: Color,
+}
+
 /// Record of a check to determine if a triangle edge is within a face
 #[derive(Clone, Debug)]
 pub struct TriangleEdgeCheck {
@@ -14,7 +14,13 @@
 
 #![warn(missing_docs)]
 
+pub mod analysis;
+pub mod assembly;
+pub mod cancellation;
 pub mod debug;
 pub mod ext;
 pub mod mesh;
 pub mod processed_shape;
+pub mod progress;
+pub mod status;
+pub mod units;
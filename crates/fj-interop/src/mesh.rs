@@ -1,8 +1,12 @@
 //! A triangle mesh
 
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar, Vector};
 
 /// A triangle mesh
 #[derive(Clone, Debug)]
@@ -78,8 +82,234 @@ impl Mesh<Point<3>> {
         triangle: impl Into<fj_math::Triangle<3>>,
         color: Color,
     ) {
-        let triangle = triangle.into();
+        self.push(triangle.into(), color, None);
+    }
+
+    /// Add a triangle to the mesh, that was created from a face
+    pub fn push_triangle_of_face(
+        &mut self,
+        triangle: impl Into<fj_math::Triangle<3>>,
+        color: Color,
+        face: FaceId,
+    ) {
+        self.push(triangle.into(), color, Some(face));
+    }
+
+    /// Add all triangles of another mesh to this one
+    pub fn append(&mut self, other: &Self) {
+        for triangle in other.triangles() {
+            self.push(triangle.inner, triangle.color, triangle.face);
+        }
+    }
+
+    /// Weld vertices that are closer to each other than `epsilon`
+    ///
+    /// Returns a new mesh, in which all vertices within `epsilon` of each
+    /// other are merged into one. This closes gaps between the triangles of
+    /// neighboring faces, that were approximated separately. Triangles that
+    /// degenerate, because multiple of their vertices were merged, are
+    /// removed.
+    ///
+    /// Merging is transitive: Vertices end up in the same group, if they are
+    /// connected by a chain of vertices within `epsilon` of each other. Each
+    /// group is merged into its smallest vertex. This makes the result
+    /// independent of the order of the triangles.
+    ///
+    /// `epsilon` must be positive.
+    pub fn weld(&self, epsilon: impl Into<Scalar>) -> Self {
+        let epsilon = epsilon.into();
+        let vertices = self.vertices().collect::<Vec<_>>();
+
+        // Vertices are sorted into a grid with a cell size of `epsilon`. Any
+        // vertex that it can be welded to, is in the same or a neighboring
+        // cell.
+        let cell = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / epsilon).floor().into_f64() as i64)
+        };
+        let mut grid = HashMap::<_, Vec<usize>>::new();
+        for (i, &vertex) in vertices.iter().enumerate() {
+            grid.entry(cell(vertex)).or_default().push(i);
+        }
+
+        // Union-find over the vertices. The root of each group is its smallest
+        // vertex.
+        let mut parents = (0..vertices.len()).collect::<Vec<_>>();
+        let find = |parents: &mut Vec<usize>, mut i: usize| {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        };
+
+        for (i, &vertex) in vertices.iter().enumerate() {
+            let [x, y, z] = cell(vertex);
+
+            let neighbors = (-1..=1).flat_map(|dx| {
+                (-1..=1).flat_map(move |dy| {
+                    (-1..=1).map(move |dz| [x + dx, y + dy, z + dz])
+                })
+            });
+            for key in neighbors {
+                for &j in grid.get(&key).into_iter().flatten() {
+                    if j <= i || (vertices[j] - vertex).magnitude() > epsilon {
+                        continue;
+                    }
+
+                    let [root_i, root_j] =
+                        [i, j].map(|index| find(&mut parents, index));
+                    if vertices[root_i] < vertices[root_j] {
+                        parents[root_j] = root_i;
+                    } else {
+                        parents[root_i] = root_j;
+                    }
+                }
+            }
+        }
+
+        let welded = (0..vertices.len())
+            .map(|i| (vertices[i], vertices[find(&mut parents, i)]))
+            .collect::<HashMap<_, _>>();
+
+        let mut mesh = Self::new();
+        for triangle in self.triangles() {
+            let points = triangle.inner.points().map(|point| welded[&point]);
+
+            // Welding can collapse triangles into points or lines.
+            let Ok(inner) = fj_math::Triangle::from_points(points) else {
+                continue;
+            };
+
+            mesh.push(inner, triangle.color, triangle.face);
+        }
+
+        mesh
+    }
+
+    /// Find defects that keep the mesh from being closed and manifold
+    ///
+    /// A closed, manifold mesh has every edge shared by exactly two triangles,
+    /// which traverse it in opposite directions. Returns an empty list, if
+    /// that is the case.
+    pub fn defects(&self) -> Vec<MeshDefect> {
+        let vertices = self.vertices().collect::<Vec<_>>();
+        let indices = self.indices().collect::<Vec<_>>();
+
+        // The number of times each edge is traversed in either direction,
+        // with the edges identified by their sorted vertex indices.
+        let mut edges = BTreeMap::<_, [u32; 2]>::new();
+        let mut defects = Vec::new();
+
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            if a == b || b == c || c == a {
+                defects.push(MeshDefect::DegenerateTriangle {
+                    points: [a, b, c].map(|i| vertices[i as usize]),
+                });
+                continue;
+            }
+
+            for [start, end] in [[a, b], [b, c], [c, a]] {
+                let (edge, direction) = if start < end {
+                    ([start, end], 0)
+                } else {
+                    ([end, start], 1)
+                };
+                edges.entry(edge).or_default()[direction] += 1;
+            }
+        }
+
+        for ([a, b], [forward, backward]) in edges {
+            let edge = [a, b].map(|i| vertices[i as usize]);
+
+            let defect = match forward + backward {
+                1 => MeshDefect::BoundaryEdge { edge },
+                2 if forward != backward => {
+                    MeshDefect::InconsistentOrientation { edge }
+                }
+                2 => continue,
+                num_triangles => MeshDefect::NonManifoldEdge {
+                    edge,
+                    num_triangles,
+                },
+            };
+            defects.push(defect);
+        }
+
+        defects
+    }
 
+    /// Compute normals for the corners of all triangles
+    ///
+    /// Returns the normals of each triangle's corners, in the order of
+    /// [`Mesh::triangles`]. A corner's normal is averaged from the normals of
+    /// all triangles that share its vertex, weighted by their area, but only
+    /// of those that are within `crease_angle` (in radians) of the corner's
+    /// triangle. Edges between triangles that meet at a sharper angle stay
+    /// sharp.
+    ///
+    /// A crease angle of zero results in flat normals. See
+    /// [`DEFAULT_CREASE_ANGLE`] for a reasonable default.
+    pub fn normals(
+        &self,
+        crease_angle: impl Into<Scalar>,
+    ) -> Vec<[Vector<3>; 3]> {
+        let min_cos = crease_angle.into().into_f64().cos();
+
+        // The magnitude of these normals is twice the area of the triangle,
+        // which is exactly the weight they need.
+        let weighted = self
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a))
+            })
+            .collect::<Vec<_>>();
+        let unit = weighted
+            .iter()
+            .map(|normal| normalize(*normal))
+            .collect::<Vec<_>>();
+
+        let indices = self.indices().collect::<Vec<_>>();
+        let mut triangles_by_vertex = vec![Vec::new(); self.vertices.len()];
+        for (i, triangle) in indices.chunks(3).enumerate() {
+            for &vertex in triangle {
+                triangles_by_vertex[vertex as usize].push(i);
+            }
+        }
+
+        indices
+            .chunks(3)
+            .enumerate()
+            .map(|(i, triangle)| {
+                [0, 1, 2].map(|corner| {
+                    let vertex = triangle[corner] as usize;
+
+                    let sum = triangles_by_vertex[vertex]
+                        .iter()
+                        .filter(|&&j| {
+                            j == i
+                                || unit[i].dot(&unit[j]).into_f64() >= min_cos
+                        })
+                        .fold(Vector::from([0., 0., 0.]), |sum, &j| {
+                            sum + weighted[j]
+                        });
+
+                    normalize(sum)
+                })
+            })
+            .collect()
+    }
+
+    fn push(
+        &mut self,
+        triangle: fj_math::Triangle<3>,
+        color: Color,
+        face: Option<FaceId>,
+    ) {
         for point in triangle.points() {
             self.push_vertex(point);
         }
@@ -87,10 +317,27 @@ impl Mesh<Point<3>> {
         self.triangles.push(Triangle {
             inner: triangle,
             color,
+            face,
         });
     }
 }
 
+/// A crease angle that suits most meshes
+///
+/// Triangles that meet at an angle of less than 30 degrees are shaded
+/// smoothly. See [`Mesh::normals`].
+pub const DEFAULT_CREASE_ANGLE: f64 = std::f64::consts::FRAC_PI_6;
+
+fn normalize(vector: Vector<3>) -> Vector<3> {
+    // Degenerate triangles have no normal. Leave it at zero, instead of
+    // spreading `NaN`s.
+    if vector.magnitude() == Scalar::ZERO {
+        return vector;
+    }
+
+    vector.normalize()
+}
+
 // This needs to be a manual implementation. Deriving `Default` would require
 // `V` to be `Default` as well, even though that is not necessary.
 impl<V> Default for Mesh<V> {
@@ -104,12 +351,76 @@ impl<V> Default for Mesh<V> {
     }
 }
 
+/// A defect of a triangle mesh
+///
+/// See [`Mesh::defects`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum MeshDefect {
+    /// An edge that is only part of one triangle
+    ///
+    /// The mesh has a hole there.
+    BoundaryEdge {
+        /// The points of the edge
+        edge: [Point<3>; 2],
+    },
+
+    /// An edge that is part of more than two triangles
+    NonManifoldEdge {
+        /// The points of the edge
+        edge: [Point<3>; 2],
+
+        /// The number of triangles that share the edge
+        num_triangles: u32,
+    },
+
+    /// An edge that both of its triangles traverse in the same direction
+    ///
+    /// One of the triangles is facing the wrong way.
+    InconsistentOrientation {
+        /// The points of the edge
+        edge: [Point<3>; 2],
+    },
+
+    /// A triangle, that has multiple identical points
+    DegenerateTriangle {
+        /// The points of the triangle
+        points: [Point<3>; 3],
+    },
+}
+
+impl fmt::Display for MeshDefect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BoundaryEdge { edge: [a, b] } => {
+                write!(f, "open edge from {a:?} to {b:?}")
+            }
+            Self::NonManifoldEdge {
+                edge: [a, b],
+                num_triangles,
+            } => write!(
+                f,
+                "edge from {a:?} to {b:?} is shared by {num_triangles} \
+                triangles"
+            ),
+            Self::InconsistentOrientation { edge: [a, b] } => write!(
+                f,
+                "triangles on both sides of edge from {a:?} to {b:?} face \
+                opposite directions"
+            ),
+            Self::DegenerateTriangle { points } => {
+                write!(f, "degenerate triangle {points:?}")
+            }
+        }
+    }
+}
+
 /// An index that refers to a vertex in a mesh
 pub type Index = u32;
 
 /// A triangle
 ///
-/// Extension of [`fj_math::Triangle`] that also includes a color.
+/// Extension of [`fj_math::Triangle`] that also includes a color, and the face
+/// it was created from.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Triangle {
     /// The points of the triangle
@@ -117,15 +428,357 @@ pub struct Triangle {
 
     /// The color of the triangle
     pub color: Color,
+
+    /// The face that the triangle was created from, if any
+    pub face: Option<FaceId>,
 }
 
+/// Identifies the face that a triangle was created from
+///
+/// This is the ID of the face object in the kernel, or for faces of instanced
+/// solids, derived from that ID and the instance. All triangles of a face share
+/// the same `FaceId`, which allows them to be grouped, or the face to be
+/// identified from one of its triangles.
+///
+/// Face IDs fit into 96 bits, which allows the viewer to render them into a
+/// texture for picking.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct FaceId(pub u128);
+
 /// RGBA color
+///
+/// An alpha of `255` is fully opaque, an alpha of `0` fully transparent.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Color(pub [u8; 4]);
 
+impl Color {
+    /// Return a copy of the color, with the given alpha
+    pub fn with_alpha(self, alpha: u8) -> Self {
+        let [r, g, b, _] = self.0;
+        Self([r, g, b, alpha])
+    }
+
+    /// Access the alpha channel of the color
+    pub fn alpha(&self) -> u8 {
+        self.0[3]
+    }
+
+    /// Indicate whether the color is fully opaque
+    pub fn is_opaque(&self) -> bool {
+        self.alpha() == 255
+    }
+}
+
+impl From<[u8; 4]> for Color {
+    fn from(rgba: [u8; 4]) -> Self {
+        Self(rgba)
+    }
+}
+
 impl Default for Color {
     fn default() -> Self {
         // The default color is red. This is an arbitrary choice.
         Self([255, 0, 0, 255])
     }
 }
+
+/// Meshes for use in tests
+///
+/// Available to the tests of other crates through the `test-support` feature.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support {
+    use fj_math::Point;
+
+    use super::{Color, Mesh};
+
+    /// The mesh of an axis-aligned box, with its triangles facing outwards
+    pub fn cuboid(min: [f64; 3], max: [f64; 3]) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        for triangle in cuboid_triangles(min, max) {
+            mesh.push_triangle(triangle, Color::default());
+        }
+        mesh
+    }
+
+    /// The triangles of an axis-aligned box, facing outwards
+    pub fn cuboid_triangles(
+        min: [f64; 3],
+        max: [f64; 3],
+    ) -> Vec<[Point<3>; 3]> {
+        let corner = |x: usize, y: usize, z: usize| {
+            Point::from([
+                [min[0], max[0]][x],
+                [min[1], max[1]][y],
+                [min[2], max[2]][z],
+            ])
+        };
+
+        let [a, b, c, d] =
+            [[0, 0], [1, 0], [1, 1], [0, 1]].map(|[x, y]| corner(x, y, 0));
+        let [e, f, g, h] =
+            [[0, 0], [1, 0], [1, 1], [0, 1]].map(|[x, y]| corner(x, y, 1));
+
+        [
+            [a, d, c, b],
+            [e, f, g, h],
+            [a, b, f, e],
+            [b, c, g, f],
+            [c, d, h, g],
+            [d, a, e, h],
+        ]
+        .into_iter()
+        .flat_map(|[p, q, r, s]| [[p, q, r], [p, r, s]])
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{PI, TAU};
+
+    use fj_math::{Point, Scalar, Vector};
+
+    use super::{
+        test_support::cuboid_triangles, Color, Mesh, MeshDefect,
+        DEFAULT_CREASE_ANGLE,
+    };
+
+    #[test]
+    fn closed() {
+        assert_eq!(mesh(cube()).defects(), Vec::new());
+    }
+
+    #[test]
+    fn open() {
+        let mut triangles = cube();
+        triangles.pop();
+
+        let defects = mesh(triangles).defects();
+
+        assert_eq!(defects.len(), 3);
+        assert!(defects
+            .iter()
+            .all(|defect| matches!(defect, MeshDefect::BoundaryEdge { .. })));
+    }
+
+    #[test]
+    fn non_manifold() {
+        let mut triangles = cube();
+        let [a, b, _] = triangles[0];
+        triangles.push([b, a, Point::from([0.5, 0.5, -1.])]);
+
+        let defects = defects(triangles);
+
+        assert!(defects.contains(&MeshDefect::NonManifoldEdge {
+            edge: sorted([a, b]),
+            num_triangles: 3,
+        }));
+    }
+
+    #[test]
+    fn inconsistent_orientation() {
+        let mut triangles = cube();
+        let [a, b, c] = triangles[0];
+        triangles[0] = [a, c, b];
+
+        let defects = defects(triangles);
+
+        assert_eq!(defects.len(), 3);
+        for edge in [[a, b], [b, c], [c, a]] {
+            assert!(defects.contains(&MeshDefect::InconsistentOrientation {
+                edge: sorted(edge),
+            }));
+        }
+    }
+
+    #[test]
+    fn degenerate() {
+        let [a, b] = [[0., 0., 0.], [1., 0., 0.]].map(Point::from);
+
+        let mut mesh = Mesh::new();
+        for point in [a, a, b] {
+            mesh.push_vertex(point);
+        }
+
+        assert_eq!(
+            mesh.defects(),
+            vec![MeshDefect::DegenerateTriangle { points: [a, a, b] }]
+        );
+    }
+
+    #[test]
+    fn weld_separately_approximated_faces() {
+        // Two faces that share an edge, whose points have been computed
+        // separately for each face, and differ slightly.
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+        let [a2, c2] = [[1e-12, 0., 0.], [1., 1. + 1e-12, 0.]].map(Point::from);
+
+        let mesh = mesh(vec![[a, b, c], [a2, c2, d]]);
+        assert_eq!(mesh.defects().len(), 6);
+
+        let welded = mesh.weld(1e-9);
+        assert_eq!(welded.vertices().count(), 4);
+        assert_eq!(welded.triangles().count(), 2);
+
+        // Only the outline of the square is open, not the edge between the
+        // faces.
+        let mut defects = welded
+            .defects()
+            .into_iter()
+            .map(normalize)
+            .collect::<Vec<_>>();
+        let mut expected = [[a, b], [b, c], [c, d], [d, a]]
+            .map(|edge| MeshDefect::BoundaryEdge { edge: sorted(edge) })
+            .to_vec();
+        defects.sort();
+        expected.sort();
+        assert_eq!(defects, expected);
+    }
+
+    #[test]
+    fn weld_is_independent_of_order() {
+        // A chain of vertices, in which only neighbors are within the
+        // tolerance of each other.
+        let [a, b, c] =
+            [[0., 0., 0.], [0.75, 0., 0.], [1.5, 0., 0.]].map(Point::from);
+        let triangle = |point: Point<3>| {
+            let x = point.x.into_f64();
+            // The other points are far away from those of the other triangles.
+            [
+                point,
+                Point::from([x * 10., 10., 0.]),
+                Point::from([x * 10., 0., 10.]),
+            ]
+        };
+        let [ta, tb, tc] = [a, b, c].map(triangle);
+
+        let welded = [vec![ta, tc, tb], vec![tb, ta, tc], vec![tc, tb, ta]]
+            .map(|triangles| {
+                let mut vertices =
+                    mesh(triangles).weld(1.).vertices().collect::<Vec<_>>();
+                vertices.sort();
+                vertices
+            });
+
+        assert_eq!(welded[0], welded[1]);
+        assert_eq!(welded[1], welded[2]);
+        assert!(welded[0].contains(&a));
+        assert!(!welded[0].contains(&b));
+        assert!(!welded[0].contains(&c));
+    }
+
+    #[test]
+    fn flat_normals() {
+        let mesh = mesh(cube());
+        let normals = mesh.normals(0.);
+
+        for (triangle, corners) in mesh.triangles().zip(normals) {
+            let normal = normal(triangle.inner.points());
+            for corner in corners {
+                assert!((corner - normal).magnitude() < Scalar::from(1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_normals() {
+        let segments = 32;
+        let mesh = mesh(cylinder(segments));
+        let normals = mesh.normals(DEFAULT_CREASE_ANGLE);
+
+        // At its corners, the normal of each side triangle deviates from the
+        // radial direction by half the angle between the segments. Smooth
+        // normals get much closer.
+        let max_deviation = PI / segments as f64 / 2.;
+
+        for (triangle, corners) in mesh.triangles().zip(normals) {
+            let points = triangle.inner.points();
+            let normal = normal(points);
+
+            for (point, corner) in points.into_iter().zip(corners) {
+                if normal.z.abs() > Scalar::from(0.5) {
+                    // The edges between the caps and the side are sharp.
+                    assert!(
+                        (corner - normal).magnitude() < Scalar::from(1e-12)
+                    );
+                    continue;
+                }
+
+                let radial = Vector::from([point.x, point.y, Scalar::ZERO]);
+                let cos = corner.dot(&radial.normalize()).into_f64();
+                assert!(cos.min(1.).acos() < max_deviation);
+            }
+        }
+    }
+
+    fn mesh(triangles: Vec<[Point<3>; 3]>) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        for triangle in triangles {
+            mesh.push_triangle(triangle, Color::default());
+        }
+        mesh
+    }
+
+    /// The triangles of a unit cube, facing outwards
+    fn cube() -> Vec<[Point<3>; 3]> {
+        cuboid_triangles([0., 0., 0.], [1., 1., 1.])
+    }
+
+    /// The triangles of a closed cylinder, facing outwards
+    fn cylinder(segments: usize) -> Vec<[Point<3>; 3]> {
+        let point = |i: usize, z: f64| {
+            // The last segment ends at the first point, which must be exactly
+            // the same.
+            let angle = TAU * (i % segments) as f64 / segments as f64;
+            Point::from([angle.cos(), angle.sin(), z])
+        };
+        let [bottom, top] = [0., 1.].map(|z| Point::from([0., 0., z]));
+
+        (0..segments)
+            .flat_map(|i| {
+                let [p, q] = [i, i + 1].map(|i| point(i, 0.));
+                let [r, s] = [i + 1, i].map(|i| point(i, 1.));
+
+                [[p, q, r], [p, r, s], [bottom, q, p], [top, s, r]]
+            })
+            .collect()
+    }
+
+    fn normal([a, b, c]: [Point<3>; 3]) -> Vector<3> {
+        (b - a).cross(&(c - a)).normalize()
+    }
+
+    // The defects of a mesh, with the points of each edge sorted
+    fn defects(triangles: Vec<[Point<3>; 3]>) -> Vec<MeshDefect> {
+        mesh(triangles)
+            .defects()
+            .into_iter()
+            .map(normalize)
+            .collect()
+    }
+
+    fn normalize(defect: MeshDefect) -> MeshDefect {
+        match defect {
+            MeshDefect::BoundaryEdge { edge } => {
+                MeshDefect::BoundaryEdge { edge: sorted(edge) }
+            }
+            MeshDefect::NonManifoldEdge {
+                edge,
+                num_triangles,
+            } => MeshDefect::NonManifoldEdge {
+                edge: sorted(edge),
+                num_triangles,
+            },
+            MeshDefect::InconsistentOrientation { edge } => {
+                MeshDefect::InconsistentOrientation { edge: sorted(edge) }
+            }
+            defect @ MeshDefect::DegenerateTriangle { .. } => defect,
+        }
+    }
+
+    fn sorted([a, b]: [Point<3>; 2]) -> [Point<3>; 2] {
+        [a.min(b), a.max(b)]
+    }
+}
@@ -1,8 +1,14 @@
 //! A processed shape
 
-use fj_math::{Aabb, Point};
+use std::time::Duration;
 
-use crate::{debug::DebugInfo, mesh::Mesh};
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{
+    analysis::{Curvature, Dimensions, MassProperties, Overhangs},
+    debug::DebugInfo,
+    mesh::{Mesh, MeshDefect},
+};
 
 /// A processed shape
 #[derive(Clone, Debug)]
@@ -13,6 +19,52 @@ pub struct ProcessedShape {
     /// The triangle mesh that approximates the original shape
     pub mesh: Mesh<Point<3>>,
 
+    /// The maximum distance between the mesh and the original shape
+    ///
+    /// This is zero for imported meshes, which are the original shape. It
+    /// doesn't account for simplification of the mesh.
+    pub tolerance: Scalar,
+
+    /// The defects that keep the mesh from being closed and manifold
+    ///
+    /// This is empty for a valid solid. A sketch, for example, is expected to
+    /// have open edges.
+    pub mesh_defects: Vec<MeshDefect>,
+
+    /// The mass properties of the shape
+    ///
+    /// These are only meaningful, if the shape is a closed solid.
+    pub mass_properties: MassProperties,
+
+    /// The dimensions of the shape, and of each of its bodies
+    pub dimensions: Dimensions,
+
+    /// The overhangs of the shape, if it were 3D-printed along the z-axis
+    pub overhangs: Overhangs,
+
+    /// The curvature of the shape, estimated at each vertex of its mesh
+    pub curvature: Curvature,
+
     /// The debug info generated while processing the shape
     pub debug_info: DebugInfo,
+
+    /// How long it took to process the shape
+    ///
+    /// This includes computing the boundary representation, approximating,
+    /// triangulating, and analyzing it. It doesn't include evaluating the
+    /// model.
+    pub processing_time: Duration,
+}
+
+/// A shape that is still being processed
+///
+/// Sent while the faces of a shape are being triangulated, so large shapes can
+/// be displayed incrementally.
+#[derive(Clone, Debug)]
+pub struct PartialShape {
+    /// The axis-aligned bounding box of the complete shape
+    pub aabb: Aabb<3>,
+
+    /// The triangles of the faces that have been triangulated so far
+    pub mesh: Mesh<Point<3>>,
 }
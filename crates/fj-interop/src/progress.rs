@@ -0,0 +1,59 @@
+//! Progress reporting for long-running operations
+//!
+//! See [`Progress`].
+
+/// The progress of processing a shape
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    /// The stage of processing that is currently going on
+    pub stage: Stage,
+
+    /// The number of steps of the current stage that have been completed
+    pub completed: usize,
+
+    /// The total number of steps of the current stage
+    pub total: usize,
+}
+
+impl Progress {
+    /// Construct an instance of `Progress`
+    pub fn new(stage: Stage, completed: usize, total: usize) -> Self {
+        Self {
+            stage,
+            completed,
+            total,
+        }
+    }
+
+    /// The fraction of the current stage that has been completed
+    ///
+    /// Returns a value between `0.0` and `1.0`. A stage that has no steps is
+    /// considered complete.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.;
+        }
+
+        (self.completed.min(self.total) as f32) / (self.total as f32)
+    }
+}
+
+/// A stage of processing a shape
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stage {
+    /// The faces of the shape are being approximated
+    Approximation,
+
+    /// The approximated faces are being triangulated
+    Triangulation,
+}
+
+impl Stage {
+    /// A human-readable description of the stage
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Approximation => "Approximating",
+            Self::Triangulation => "Triangulating",
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Status messages, for display to the user
+//!
+//! See [`StatusMessage`].
+
+use std::time::SystemTime;
+
+/// A status message
+///
+/// Status messages are sent by the components of Fornjot, to keep the user
+/// informed about what's going on. Each message has a level and a source, so
+/// messages can be filtered.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusMessage {
+    /// How important the message is
+    pub level: Level,
+
+    /// The component that sent the message
+    pub source: Source,
+
+    /// The text of the message
+    ///
+    /// Can span multiple lines.
+    pub text: String,
+
+    /// The time at which the message was created
+    pub time: SystemTime,
+}
+
+impl StatusMessage {
+    /// Create a new status message, with the current time
+    pub fn new(level: Level, source: Source, text: impl Into<String>) -> Self {
+        Self {
+            level,
+            source,
+            text: text.into(),
+            time: SystemTime::now(),
+        }
+    }
+
+    /// Create a new informational message
+    pub fn info(source: Source, text: impl Into<String>) -> Self {
+        Self::new(Level::Info, source, text)
+    }
+
+    /// Create a new warning
+    pub fn warn(source: Source, text: impl Into<String>) -> Self {
+        Self::new(Level::Warn, source, text)
+    }
+
+    /// Create a new error message
+    pub fn error(source: Source, text: impl Into<String>) -> Self {
+        Self::new(Level::Error, source, text)
+    }
+}
+
+/// How important a [`StatusMessage`] is
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Level {
+    /// Information about what's going on
+    Info,
+
+    /// Something might be wrong, but things can go on
+    Warn,
+
+    /// Something went wrong
+    Error,
+}
+
+impl Level {
+    /// All levels, from least to most important
+    pub const ALL: [Self; 3] = [Self::Info, Self::Warn, Self::Error];
+
+    /// Access a human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warn => "Warning",
+            Self::Error => "Error",
+        }
+    }
+}
+
+/// The component that sent a [`StatusMessage`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Source {
+    /// Loading, evaluating, and watching the model
+    Host,
+
+    /// The CAD kernel, for example its validation
+    Kernel,
+
+    /// Processing the shape returned by the model
+    Operations,
+
+    /// Exporting the model to a file
+    Export,
+}
+
+impl Source {
+    /// All sources
+    pub const ALL: [Self; 4] =
+        [Self::Host, Self::Kernel, Self::Operations, Self::Export];
+
+    /// Access a human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Host => "Host",
+            Self::Kernel => "Kernel",
+            Self::Operations => "Operations",
+            Self::Export => "Export",
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Units of length
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// The unit of length, that the coordinates of a model are given in
+///
+/// Models are not converted between units. This only affects how lengths are
+/// presented to the user.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Units {
+    /// Millimeters
+    Millimeters,
+
+    /// Centimeters
+    Centimeters,
+
+    /// Meters
+    Meters,
+
+    /// Inches
+    Inches,
+}
+
+impl Units {
+    /// All units
+    pub const ALL: [Self; 4] = [
+        Self::Millimeters,
+        Self::Centimeters,
+        Self::Meters,
+        Self::Inches,
+    ];
+
+    /// Access the symbol of the unit, as it's shown after a length
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Millimeters => "mm",
+            Self::Centimeters => "cm",
+            Self::Meters => "m",
+            Self::Inches => "in",
+        }
+    }
+
+    /// Access the name of the unit
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Millimeters => "millimeters",
+            Self::Centimeters => "centimeters",
+            Self::Meters => "meters",
+            Self::Inches => "inches",
+        }
+    }
+
+    /// Compute the spacing of a reference grid that covers the given extent
+    ///
+    /// The spacing is chosen such, that there are at least 10 grid lines along
+    /// the extent. Metric units are divided into powers of ten, inches into
+    /// powers of two.
+    pub fn grid_spacing(self, extent: f64) -> f64 {
+        let base: f64 = match self {
+            Self::Millimeters | Self::Centimeters | Self::Meters => 10.,
+            Self::Inches => 2.,
+        };
+
+        let target = extent / 10.;
+        if !target.is_normal() {
+            return 1.;
+        }
+
+        base.powf(target.log(base).floor())
+    }
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Units {
+    type Err = ParseUnitsError;
+
+    /// Parse units from either their symbol or their name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|units| s == units.symbol() || s == units.name())
+            .ok_or_else(|| ParseUnitsError(s.to_owned()))
+    }
+}
+
+/// Error parsing [`Units`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseUnitsError(pub String);
+
+impl fmt::Display for ParseUnitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown units `{}`; expected one of", self.0)?;
+        for (i, units) in Units::ALL.into_iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            write!(f, "{separator} `{}`", units.symbol())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseUnitsError {}
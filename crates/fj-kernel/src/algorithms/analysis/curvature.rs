@@ -0,0 +1,116 @@
+//! Curvature estimation
+//!
+//! See [`from_mesh`].
+
+use fj_interop::{
+    analysis::{Curvature, VertexCurvature},
+    mesh::Mesh,
+};
+use fj_math::{Point, Scalar, Vector};
+
+/// Estimate the curvature of a triangle mesh at each of its vertices
+///
+/// Uses the angle deficit at each vertex to estimate the Gaussian curvature,
+/// and the cotangent-weighted Laplacian to estimate the mean curvature. Both
+/// are normalized by the barycentric area around the vertex.
+///
+/// The estimates are only meaningful for vertices in the interior of a closed
+/// mesh. Since they are computed from the mesh, they reflect the curvature of
+/// the approximation, not the actual shape. This makes them useful for
+/// spotting tessellation artifacts.
+pub fn from_mesh(mesh: &Mesh<Point<3>>) -> Curvature {
+    let vertices = mesh.vertices().collect::<Vec<_>>();
+    let indices = mesh.indices().collect::<Vec<_>>();
+
+    let mut areas = vec![Scalar::ZERO; vertices.len()];
+    let mut angle_sums = vec![Scalar::ZERO; vertices.len()];
+    let mut normals = vec![Vector::from([0., 0., 0.]); vertices.len()];
+    let mut laplacians = vec![Vector::from([0., 0., 0.]); vertices.len()];
+
+    for triangle in indices.chunks(3) {
+        let triangle =
+            [triangle[0], triangle[1], triangle[2]].map(|index| index as usize);
+        let [p0, p1, p2] = triangle.map(|i| vertices[i]);
+
+        let cross = (p1 - p0).cross(&(p2 - p0));
+        let area = cross.magnitude() / 2.;
+        if area == Scalar::ZERO {
+            continue;
+        }
+
+        for k in 0..3 {
+            let [i, j, l] = [k, (k + 1) % 3, (k + 2) % 3].map(|k| triangle[k]);
+            let [p, q, r] = [i, j, l].map(|i| vertices[i]);
+
+            areas[i] += area / 3.;
+            normals[i] = normals[i] + cross;
+
+            let u = q - p;
+            let v = r - p;
+            let sin = u.cross(&v).magnitude();
+            let cos = u.dot(&v);
+
+            angle_sums[i] +=
+                Scalar::from_f64(sin.into_f64().atan2(cos.into_f64()));
+
+            // The angle at `p` determines the weight of the opposite edge.
+            let cot = cos / sin;
+            laplacians[j] = laplacians[j] + (r - q) * cot;
+            laplacians[l] = laplacians[l] + (q - r) * cot;
+        }
+    }
+
+    let vertices = areas
+        .into_iter()
+        .zip(angle_sums)
+        .zip(normals.into_iter().zip(laplacians))
+        .map(|((area, angle_sum), (normal, laplacian))| {
+            if area == Scalar::ZERO {
+                return VertexCurvature::default();
+            }
+
+            let gaussian = (Scalar::TAU - angle_sum) / area;
+
+            // The Laplacian points against the normal in convex regions.
+            let laplacian = laplacian / (area * 2.);
+            let mean = -laplacian.dot(&normal.normalize()) / 2.;
+
+            VertexCurvature { gaussian, mean }
+        })
+        .collect();
+
+    Curvature { vertices }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    #[test]
+    fn from_mesh() {
+        let mut octahedron = Mesh::new();
+        for x in [-1., 1.] {
+            for y in [-1., 1.] {
+                for z in [-1., 1.] {
+                    let [a, b, c] = [[x, 0., 0.], [0., y, 0.], [0., 0., z]]
+                        .map(Point::from);
+
+                    // Make sure all triangles face outward.
+                    let triangle =
+                        if x * y * z > 0. { [a, b, c] } else { [a, c, b] };
+                    octahedron.push_triangle(triangle, Color::default());
+                }
+            }
+        }
+
+        let curvature = super::from_mesh(&octahedron);
+
+        assert_eq!(curvature.vertices.len(), 6);
+        for vertex in curvature.vertices {
+            let expected = Scalar::PI / 3_f64.sqrt();
+            assert!((vertex.gaussian - expected).abs() < Scalar::from(1e-9));
+            assert!(vertex.mean > Scalar::ZERO);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Comparison of shapes
+//!
+//! See [`compare`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::{analysis::ShapeDiff, mesh::Mesh};
+use fj_math::{Aabb, Point, Scalar};
+use parry3d_f64::query::PointQuery as _;
+
+use super::{interference::to_parry, mass};
+
+/// Compare the shapes bounded by two triangle meshes
+///
+/// Triangles of either mesh that are further from the other mesh than
+/// `tolerance` make up the changed regions. If both meshes approximate a shape
+/// within some tolerance, pass twice that, as they can deviate from each other
+/// by that much.
+///
+/// The Hausdorff distance is estimated from the distances of the vertices,
+/// edge midpoints, and triangle centers of each mesh to the other.
+///
+/// Returns `None`, if either mesh is empty.
+pub fn compare(
+    a: &Mesh<Point<3>>,
+    b: &Mesh<Point<3>>,
+    tolerance: impl Into<Scalar>,
+) -> Option<ShapeDiff> {
+    if a.triangles().next().is_none() || b.triangles().next().is_none() {
+        return None;
+    }
+
+    let tolerance = tolerance.into();
+
+    let (distance_a, regions_a) = deviation(a, b, tolerance);
+    let (distance_b, regions_b) = deviation(b, a, tolerance);
+
+    let volume_difference =
+        mass::from_mesh(b).volume - mass::from_mesh(a).volume;
+
+    Some(ShapeDiff {
+        volume_difference,
+        hausdorff_distance: distance_a.max(distance_b),
+        tolerance,
+        changed_regions: merge_overlapping(
+            regions_a.into_iter().chain(regions_b),
+        ),
+    })
+}
+
+/// Compute how far `from` deviates from `to`
+///
+/// Returns the largest distance of any sample of `from` to `to`, and the
+/// regions of `from` that are further away than `tolerance`.
+fn deviation(
+    from: &Mesh<Point<3>>,
+    to: &Mesh<Point<3>>,
+    tolerance: Scalar,
+) -> (Scalar, Vec<Aabb<3>>) {
+    let to = to_parry(to);
+    let distance = |point: Point<3>| {
+        Scalar::from(to.distance_to_local_point(&point.to_na(), false))
+    };
+
+    let vertices = from.vertices().collect::<Vec<_>>();
+    let vertex_distances = vertices
+        .iter()
+        .map(|&vertex| distance(vertex))
+        .collect::<Vec<_>>();
+
+    let mut max_distance = Scalar::ZERO;
+    let mut changed = Vec::new();
+
+    for triangle in from.indices().collect::<Vec<_>>().chunks(3) {
+        let triangle =
+            [triangle[0], triangle[1], triangle[2]].map(|index| index as usize);
+        let [p0, p1, p2] = triangle.map(|index| vertices[index]);
+
+        let samples = [
+            p0 + (p1 - p0) * 0.5,
+            p1 + (p2 - p1) * 0.5,
+            p2 + (p0 - p2) * 0.5,
+            p0 + ((p1 - p0) + (p2 - p0)) * (1. / 3.),
+        ];
+        let triangle_distance = triangle
+            .map(|index| vertex_distances[index])
+            .into_iter()
+            .chain(samples.map(distance))
+            .max()
+            .unwrap_or(Scalar::ZERO);
+
+        max_distance = max_distance.max(triangle_distance);
+        if triangle_distance > tolerance {
+            changed.push(triangle);
+        }
+    }
+
+    (max_distance, regions(&vertices, &changed))
+}
+
+/// Group triangles that share vertices, and compute the bounds of each group
+fn regions(vertices: &[Point<3>], triangles: &[[usize; 3]]) -> Vec<Aabb<3>> {
+    // Union-find over the triangles. Each group is represented by the index of
+    // one of its triangles.
+    let mut parents = (0..triangles.len()).collect::<Vec<_>>();
+    let find = |parents: &mut Vec<usize>, mut i: usize| {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    };
+
+    let mut triangle_by_vertex = BTreeMap::new();
+    for (i, triangle) in triangles.iter().enumerate() {
+        for vertex in triangle {
+            let j = *triangle_by_vertex.entry(*vertex).or_insert(i);
+
+            let [root_i, root_j] =
+                [i, j].map(|index| find(&mut parents, index));
+            parents[root_i] = root_j;
+        }
+    }
+
+    let mut regions = BTreeMap::new();
+    for (i, triangle) in triangles.iter().enumerate() {
+        let root = find(&mut parents, i);
+        regions
+            .entry(root)
+            .or_insert_with(Vec::new)
+            .extend(triangle.map(|index| vertices[index]));
+    }
+
+    regions.into_values().map(Aabb::<3>::from_points).collect()
+}
+
+/// Merge regions that overlap, until none of them do
+fn merge_overlapping(
+    regions: impl IntoIterator<Item = Aabb<3>>,
+) -> Vec<Aabb<3>> {
+    let mut merged: Vec<Aabb<3>> = Vec::new();
+
+    for mut region in regions {
+        // Merging a region can make it overlap with regions that it didn't
+        // overlap before, so keep going until nothing changes.
+        while let Some(i) =
+            merged.iter().position(|other| other.intersects(&region))
+        {
+            region = region.merged(&merged.swap_remove(i));
+        }
+
+        merged.push(region);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{test_support::cuboid, Mesh};
+    use fj_math::{Point, Scalar};
+
+    #[test]
+    fn identical() {
+        let a = cube(1.);
+        let b = cube(1.);
+
+        let diff = super::compare(&a, &b, 0.001).unwrap();
+
+        assert!(diff.is_unchanged());
+        assert_eq!(diff.hausdorff_distance, Scalar::ZERO);
+        assert_eq!(diff.volume_difference, Scalar::ZERO);
+    }
+
+    #[test]
+    fn changed() {
+        let a = cube(1.);
+        let b = cube(1.5);
+
+        let diff = super::compare(&a, &b, 0.001).unwrap();
+
+        assert!(!diff.is_unchanged());
+        assert!(
+            (diff.hausdorff_distance - Scalar::from(0.5)).abs()
+                < Scalar::from(1e-9)
+        );
+        assert!(
+            (diff.volume_difference - Scalar::from(0.5)).abs()
+                < Scalar::from(1e-9)
+        );
+    }
+
+    fn cube(height: f64) -> Mesh<Point<3>> {
+        cuboid([0., 0., 0.], [1., 1., height])
+    }
+}
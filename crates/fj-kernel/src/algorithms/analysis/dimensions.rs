@@ -0,0 +1,337 @@
+//! Dimensions of shapes
+//!
+//! See [`from_mesh`] and [`footprint`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::{
+    analysis::{BoundingBoxes, Dimensions, Obb},
+    mesh::Mesh,
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+use nalgebra::{Matrix3, SymmetricEigen, Vector3};
+
+/// Compute the dimensions of the shape represented by a triangle mesh
+///
+/// Triangles that share a vertex are considered to be part of the same body.
+pub fn from_mesh(mesh: &Mesh<Point<3>>) -> Dimensions {
+    let vertices = mesh.vertices().collect::<Vec<_>>();
+    let overall = bounding_boxes(&vertices);
+
+    let mut parents = (0..vertices.len()).collect::<Vec<_>>();
+    let indices = mesh.indices().collect::<Vec<_>>();
+    for triangle in indices.chunks(3) {
+        for pair in triangle.windows(2) {
+            let a = find(&mut parents, pair[0] as usize);
+            let b = find(&mut parents, pair[1] as usize);
+            parents[a] = b;
+        }
+    }
+
+    let mut bodies = BTreeMap::<_, Vec<_>>::new();
+    for (i, vertex) in vertices.into_iter().enumerate() {
+        bodies
+            .entry(find(&mut parents, i))
+            .or_default()
+            .push(vertex);
+    }
+    let bodies = bodies
+        .values()
+        .map(Vec::as_slice)
+        .map(bounding_boxes)
+        .collect();
+
+    Dimensions { overall, bodies }
+}
+
+/// Compute the bounding boxes of a set of points
+pub fn bounding_boxes(points: &[Point<3>]) -> BoundingBoxes {
+    if points.is_empty() {
+        return BoundingBoxes::default();
+    }
+
+    let aabb = Aabb::<3>::from_points(points.iter().copied());
+    let obb = oriented_bounding_box(points, &aabb);
+
+    BoundingBoxes { aabb, obb }
+}
+
+/// Compute the smallest box around a set of points, that is upright
+///
+/// One axis of the box is `up`, which must not be zero. The box is rotated
+/// around that axis, to fit the points as tightly as possible. This is useful
+/// for packing parts on a print bed, where they can only be rotated around
+/// the build direction.
+///
+/// The axes of the returned box are sorted such, that the two horizontal ones
+/// come first, longer one first, followed by `up`.
+pub fn footprint(points: &[Point<3>], up: Vector<3>) -> Obb {
+    if points.is_empty() {
+        return Obb::default();
+    }
+
+    let points = points
+        .iter()
+        .map(|point| point.coords.to_na())
+        .collect::<Vec<_>>();
+
+    let mut obb = fit(&points, around_axis(&points, up.normalize().to_na()));
+    if obb.size[0] < obb.size[1] {
+        obb.axes.swap(0, 1);
+        obb.size.swap(0, 1);
+    }
+
+    obb
+}
+
+fn oriented_bounding_box(points: &[Point<3>], aabb: &Aabb<3>) -> Obb {
+    let points = points
+        .iter()
+        .map(|point| point.coords.to_na())
+        .collect::<Vec<_>>();
+    let num_points = points.len() as f64;
+
+    // The principal axes of the points are good candidates for the axes of
+    // the OBB.
+    let mean = points.iter().sum::<Vector3<f64>>() / num_points;
+    let covariance = points
+        .iter()
+        .map(|point| {
+            let d = point - mean;
+            d * d.transpose()
+        })
+        .sum::<Matrix3<f64>>()
+        / num_points;
+    let eigenvectors = SymmetricEigen::new(covariance).eigenvectors;
+
+    // Principal axes are not guaranteed to result in the tightest box. Most
+    // notably, they are ambiguous for shapes that extend equally in multiple
+    // directions, like a cube. Each of them, as well as each coordinate axis,
+    // is only used as one axis of the OBB. The box is then rotated around it,
+    // to find the tightest fit.
+    let candidate_axes = [0, 1, 2]
+        .map(|i| eigenvectors.column(i).into_owned())
+        .into_iter()
+        .chain([Vector3::x(), Vector3::y(), Vector3::z()]);
+    let mut obb = candidate_axes
+        .map(|axis| fit(&points, around_axis(&points, axis)))
+        .min_by_key(|obb| surface_area(obb.size))
+        .expect("List of candidate axes is not empty");
+
+    // Sort the axes by size, largest first.
+    let mut order = [0, 1, 2];
+    order.sort_by_key(|&i| -obb.size[i]);
+    obb.axes = order.map(|i| obb.axes[i]);
+    obb.size = order.map(|i| obb.size[i]);
+
+    // Fall back to the AABB, if that is tighter.
+    let aabb_size = aabb.size().components;
+    if surface_area(aabb_size) <= surface_area(obb.size) {
+        return Obb {
+            center: aabb.center(),
+            size: aabb_size,
+            ..Obb::default()
+        };
+    }
+
+    obb
+}
+
+/// Find the axes of the tightest box, that has `axis` as one of its axes
+///
+/// Uses rotating calipers: One side of the tightest rectangle around a convex
+/// polygon is collinear with one of its edges. `axis` must be normalized.
+fn around_axis(
+    points: &[Vector3<f64>],
+    axis: Vector3<f64>,
+) -> [Vector3<f64>; 3] {
+    let helper = if axis.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let e1 = axis.cross(&helper).normalize();
+    let e2 = axis.cross(&e1);
+
+    let hull = convex_hull(
+        points.iter().map(|point| [point.dot(&e1), point.dot(&e2)]),
+    );
+
+    let mut best = (f64::INFINITY, [1., 0.]);
+    for (i, a) in hull.iter().enumerate() {
+        let b = hull[(i + 1) % hull.len()];
+
+        let [dx, dy] = [b[0] - a[0], b[1] - a[1]];
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < f64::EPSILON {
+            continue;
+        }
+        let direction = [dx / length, dy / length];
+
+        let extent = |[x, y]: [f64; 2]| {
+            let (min, max) =
+                hull.iter().map(|p| p[0] * x + p[1] * y).fold(
+                    (f64::INFINITY, f64::NEG_INFINITY),
+                    |(min, max), d| (min.min(d), max.max(d)),
+                );
+            max - min
+        };
+        let [x, y] = direction;
+        let area = extent([x, y]) * extent([-y, x]);
+
+        if area < best.0 {
+            best = (area, direction);
+        }
+    }
+
+    let (_, [x, y]) = best;
+    [e1 * x + e2 * y, e1 * -y + e2 * x, axis]
+}
+
+/// Compute the convex hull of a set of 2D points, counter-clockwise
+///
+/// Uses Andrew's monotone chain algorithm.
+fn convex_hull(points: impl IntoIterator<Item = [f64; 2]>) -> Vec<[f64; 2]> {
+    let mut points = points.into_iter().collect::<Vec<_>>();
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+
+    let reversed = points.iter().rev().copied().collect::<Vec<_>>();
+
+    let mut hull: Vec<[f64; 2]> = Vec::with_capacity(points.len() * 2);
+    for pass in [points.as_slice(), reversed.as_slice()] {
+        let start = hull.len();
+        for &point in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], point)
+                    <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+
+        // The last point of each pass is the first point of the next one.
+        hull.pop();
+    }
+
+    hull
+}
+
+/// Fit a box with the given axes around the points
+fn fit(points: &[Vector3<f64>], axes: [Vector3<f64>; 3]) -> Obb {
+    let extents = axes.map(|axis| {
+        points
+            .iter()
+            .map(|point| point.dot(&axis))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    });
+
+    let center = axes
+        .iter()
+        .zip(extents)
+        .map(|(axis, (min, max))| axis * (min + max) / 2.)
+        .sum::<Vector3<f64>>();
+
+    Obb {
+        center: Point {
+            coords: Vector::from_na(center),
+        },
+        axes: axes.map(Vector::from_na),
+        size: extents.map(|(min, max)| Scalar::from_f64(max - min)),
+    }
+}
+
+fn surface_area([a, b, c]: [Scalar; 3]) -> Scalar {
+    a * b + b * c + c * a
+}
+
+fn find(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    // Compress the path, to speed up future lookups.
+    let mut i = i;
+    while parents[i] != root {
+        let next = parents[i];
+        parents[i] = root;
+        i = next;
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar, Vector};
+
+    #[test]
+    fn from_mesh() {
+        let mut mesh = Mesh::new();
+        for offset in [0., 3.] {
+            let triangle = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]]
+                .map(|[x, y, z]| Point::from([x + offset, y, z]));
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let dimensions = super::from_mesh(&mesh);
+
+        assert_eq!(dimensions.overall.aabb.size(), [4., 1., 0.].into());
+        assert_eq!(dimensions.bodies.len(), 2);
+        for body in dimensions.bodies {
+            assert_eq!(body.aabb.size(), [1., 1., 0.].into());
+        }
+    }
+
+    #[test]
+    fn bounding_boxes_of_rotated_rectangle() {
+        let [u, v] = [[1., 1.], [-1., 1.]]
+            .map(|[x, y]| [x / 2_f64.sqrt(), y / 2_f64.sqrt()]);
+        let points =
+            [[2., 0.5], [-2., 0.5], [-2., -0.5], [2., -0.5]].map(|[a, b]| {
+                Point::from([a * u[0] + b * v[0], a * u[1] + b * v[1], 0.])
+            });
+
+        let bounding_boxes = super::bounding_boxes(&points);
+
+        let expected = [4., 1., 0.];
+        for (size, expected) in
+            bounding_boxes.obb.size.into_iter().zip(expected)
+        {
+            assert!((size - Scalar::from(expected)).abs() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn footprint_of_rotated_box() {
+        let [u, v] = [[1., 1.], [-1., 1.]]
+            .map(|[x, y]| [x / 2_f64.sqrt(), y / 2_f64.sqrt()]);
+        let points = [0., 2.].into_iter().flat_map(|z| {
+            [[2., 0.5], [-2., 0.5], [-2., -0.5], [2., -0.5]].map(|[a, b]| {
+                Point::from([a * u[0] + b * v[0], a * u[1] + b * v[1], z])
+            })
+        });
+
+        let footprint =
+            super::footprint(&points.collect::<Vec<_>>(), Vector::unit_z());
+
+        let expected = [4., 1., 2.];
+        for (size, expected) in footprint.size.into_iter().zip(expected) {
+            assert!((size - Scalar::from(expected)).abs() < Scalar::from(1e-9));
+        }
+        assert_eq!(footprint.axes[2], Vector::unit_z());
+    }
+}
@@ -0,0 +1,290 @@
+//! Interference and clearance between bodies
+//!
+//! See [`check`].
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Aabb, Point, Scalar, Vector};
+use parry3d_f64::{
+    math::Isometry,
+    query::{self, ClosestPoints, PointQuery as _},
+    shape::TriMesh,
+};
+
+use crate::{
+    algorithms::{approx::Tolerance, triangulate::Triangulate},
+    objects::Solid,
+};
+
+/// The result of checking two bodies for interference
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interference {
+    /// The bodies overlap
+    Overlap {
+        /// The axis-aligned bounding box of the region in which they overlap
+        region: Aabb<3>,
+    },
+
+    /// The bodies touch, but don't overlap
+    Contact,
+
+    /// The bodies are separated
+    Clearance {
+        /// The minimum distance between the bodies
+        distance: Scalar,
+
+        /// The points on both bodies that are closest to each other
+        points: [Point<3>; 2],
+    },
+}
+
+/// Check two solids for interference
+pub trait CheckInterference {
+    /// Check this solid and another for interference
+    ///
+    /// Both solids are triangulated using the provided tolerance. The result
+    /// is only as accurate as that triangulation.
+    ///
+    /// Returns `None`, if either solid is empty.
+    fn interference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+    ) -> Option<Interference>;
+}
+
+impl CheckInterference for Solid {
+    fn interference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+    ) -> Option<Interference> {
+        let tolerance = tolerance.into();
+        check(
+            &(self, tolerance).triangulate(),
+            &(other, tolerance).triangulate(),
+        )
+    }
+}
+
+/// Check the solids bounded by two triangle meshes for interference
+///
+/// The region of overlap is bounded by the vertices of each mesh that are
+/// inside of the other, and by the points where the edges of each mesh
+/// intersect the triangles of the other.
+///
+/// If the meshes only touch, all of those points are on the surface of both.
+/// The bodies overlap, if there is a point strictly inside of both, among the
+/// bounding points, their center, and the points halfway between those. This
+/// is exact, if the region of overlap is convex.
+///
+/// Returns `None`, if either mesh is empty.
+pub fn check(a: &Mesh<Point<3>>, b: &Mesh<Point<3>>) -> Option<Interference> {
+    if a.triangles().next().is_none() || b.triangles().next().is_none() {
+        return None;
+    }
+
+    let [parry_a, parry_b] = [a, b].map(to_parry);
+
+    let overlap = overlap_points(a, b)
+        .chain(overlap_points(b, a))
+        .collect::<Vec<_>>();
+    if has_volume(&overlap, [(a, &parry_a), (b, &parry_b)]) {
+        return Some(Interference::Overlap {
+            region: Aabb::<3>::from_points(overlap),
+        });
+    }
+
+    let closest_points = query::closest_points(
+        &Isometry::identity(),
+        &parry_a,
+        &Isometry::identity(),
+        &parry_b,
+        f64::MAX,
+    )
+    .expect("Closest points between triangle meshes are supported");
+
+    let interference = match closest_points {
+        ClosestPoints::WithinMargin(a, b) if a != b => {
+            let points = [a, b].map(Point::from);
+            Interference::Clearance {
+                distance: points[0].distance_to(&points[1]),
+                points,
+            }
+        }
+        // Bodies that touch might also be reported as being within the margin,
+        // at a distance of zero.
+        ClosestPoints::WithinMargin(..)
+        | ClosestPoints::Intersecting
+        | ClosestPoints::Disjoint => Interference::Contact,
+    };
+
+    Some(interference)
+}
+
+/// Determine whether the region bounded by the given points has a volume
+///
+/// That is the case, if any of the sample points is strictly inside of both
+/// meshes, as opposed to on their surface.
+fn has_volume(
+    bounds: &[Point<3>],
+    meshes: [(&Mesh<Point<3>>, &TriMesh); 2],
+) -> bool {
+    if bounds.is_empty() {
+        return false;
+    }
+
+    // Points that are closer to the surface than this, are considered to be on
+    // it. It needs to scale with the size of the meshes, to allow for the
+    // accumulated error in their coordinates.
+    let size = meshes
+        .iter()
+        .map(|(mesh, _)| Aabb::<3>::from_points(mesh.vertices()))
+        .reduce(|a, b| a.merged(&b))
+        .expect("Expected two meshes")
+        .size()
+        .magnitude();
+    let epsilon = size * 1e-9;
+
+    let center = Point::origin()
+        + bounds
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords)
+            / bounds.len() as f64;
+    let mut samples = bounds
+        .iter()
+        .map(|&point| point + (center - point) * 0.5)
+        .chain(bounds.iter().copied())
+        .chain([center]);
+
+    samples.any(|sample| {
+        meshes.iter().all(|(mesh, parry)| {
+            let distance =
+                parry.distance_to_local_point(&sample.to_na(), false);
+            contains(mesh, sample) && Scalar::from(distance) > epsilon
+        })
+    })
+}
+
+/// Find the points of `a` that bound its overlap with `b`
+fn overlap_points<'r>(
+    a: &'r Mesh<Point<3>>,
+    b: &'r Mesh<Point<3>>,
+) -> impl Iterator<Item = Point<3>> + 'r {
+    let vertices_inside = a.vertices().filter(|vertex| contains(b, *vertex));
+
+    let edge_intersections = a.triangles().flat_map(move |triangle| {
+        let [p0, p1, p2] = triangle.inner.points();
+
+        [[p0, p1], [p1, p2], [p2, p0]].into_iter().flat_map(
+            move |[start, end]| {
+                let dir = end - start;
+                b.triangles().filter_map(move |triangle| {
+                    let t =
+                        triangle.inner.cast_local_ray(start, dir, 1., true)?;
+                    Some(start + dir * t)
+                })
+            },
+        )
+    });
+
+    vertices_inside.chain(edge_intersections)
+}
+
+/// Determine whether a point is inside of the solid bounded by a mesh
+///
+/// Counts how often a ray from the point crosses the mesh. The direction of the
+/// ray is chosen to be unlikely to hit any edges or vertices exactly.
+pub(crate) fn contains(mesh: &Mesh<Point<3>>, point: Point<3>) -> bool {
+    let dir = Vector::from([1., 0.3718, 0.1593]);
+
+    let crossings = mesh
+        .triangles()
+        .filter(|triangle| {
+            triangle
+                .inner
+                .cast_local_ray(point, dir, f64::INFINITY, true)
+                .is_some()
+        })
+        .count();
+
+    crossings % 2 == 1
+}
+
+pub(crate) fn to_parry(mesh: &Mesh<Point<3>>) -> TriMesh {
+    let vertices = mesh.vertices().map(|vertex| vertex.to_na()).collect();
+    let indices = mesh
+        .indices()
+        .collect::<Vec<_>>()
+        .chunks(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    TriMesh::new(vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{test_support::cuboid, Mesh};
+    use fj_math::{Aabb, Point, Scalar};
+
+    use super::Interference;
+
+    #[test]
+    fn overlap() {
+        let a = cube([0., 0., 0.]);
+        let b = cube([0.5, 0.5, 0.5]);
+
+        assert_eq!(
+            super::check(&a, &b),
+            Some(Interference::Overlap {
+                region: Aabb {
+                    min: Point::from([0.5, 0.5, 0.5]),
+                    max: Point::from([1., 1., 1.]),
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn overlap_without_vertices_inside() {
+        // Two bars that cross each other. None of their vertices are strictly
+        // inside of the other bar.
+        let a = cuboid([0., 1., 0.], [3., 2., 1.]);
+        let b = cuboid([1., 0., 0.], [2., 3., 1.]);
+
+        assert!(matches!(
+            super::check(&a, &b),
+            Some(Interference::Overlap { .. })
+        ));
+    }
+
+    #[test]
+    fn contact() {
+        let a = cube([0., 0., 0.]);
+
+        // Touching along a face, an edge, and a vertex.
+        for offset in [[1., 0., 0.], [1., 1., 0.], [1., 1., 1.], [1., 0.5, 0.5]]
+        {
+            let b = cube(offset);
+            assert_eq!(super::check(&a, &b), Some(Interference::Contact));
+        }
+    }
+
+    #[test]
+    fn clearance() {
+        let a = cube([0., 0., 0.]);
+        let b = cube([2., 0., 0.]);
+
+        let Some(Interference::Clearance { distance, .. }) =
+            super::check(&a, &b)
+        else {
+            panic!("Expected clearance between cubes");
+        };
+        assert!((distance - Scalar::ONE).abs() < Scalar::from(1e-9));
+    }
+
+    fn cube(offset: [f64; 3]) -> Mesh<Point<3>> {
+        let [x, y, z] = offset;
+        cuboid(offset, [x + 1., y + 1., z + 1.])
+    }
+}
@@ -0,0 +1,216 @@
+//! Mass properties of solids
+//!
+//! See [`ComputeMassProperties`].
+
+use std::array;
+
+use fj_interop::{analysis::MassProperties, mesh::Mesh};
+use fj_math::{Point, Scalar};
+
+use crate::{
+    algorithms::{approx::Tolerance, triangulate::Triangulate},
+    geometry::curve::{Curve, GlobalPath},
+    objects::{Face, FaceSet, Shell, Solid},
+    storage::Handle,
+};
+
+/// Compute the mass properties of a shape
+///
+/// The properties are computed from a triangle mesh of the shape. If all faces
+/// of the shape are planar and bounded by straight edges, that mesh represents
+/// the shape exactly, and so do the resulting properties. Otherwise, the
+/// properties are approximated, using the provided tolerance.
+///
+/// The shape must be closed, or the computed volume will be meaningless.
+pub trait ComputeMassProperties {
+    /// Compute the mass properties of the shape
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> MassProperties;
+}
+
+impl ComputeMassProperties for FaceSet {
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> MassProperties {
+        let mesh = (self, tolerance.into()).triangulate();
+
+        MassProperties {
+            exact: is_polyhedral(self),
+            ..from_mesh(&mesh)
+        }
+    }
+}
+
+impl ComputeMassProperties for Shell {
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> MassProperties {
+        self.faces().mass_properties(tolerance)
+    }
+}
+
+impl ComputeMassProperties for Solid {
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> MassProperties {
+        let mesh = (self, tolerance.into()).triangulate();
+        let exact = self.shells().all(|shell| is_polyhedral(shell.faces()));
+
+        MassProperties {
+            exact,
+            ..from_mesh(&mesh)
+        }
+    }
+}
+
+/// Compute the mass properties of the solid bounded by a triangle mesh
+///
+/// The mesh is assumed to be an approximation of the actual solid, so the
+/// result is never marked as exact. Callers that know better can override
+/// [`MassProperties::exact`].
+pub fn from_mesh(mesh: &Mesh<Point<3>>) -> MassProperties {
+    let mut volume_6 = 0.;
+    let mut surface_area = 0.;
+    let mut center_of_mass = [0.; 3];
+    let mut covariance = [[0.; 3]; 3];
+
+    // Every triangle forms a tetrahedron with the origin. The signed volumes of
+    // these tetrahedra add up to the volume of the solid, and so do their other
+    // properties.
+    for triangle in mesh.triangles() {
+        let points = triangle.inner.points().map(<[f64; 3]>::from);
+        let [a, b, c] = points;
+
+        let det = dot(a, cross(b, c));
+        let sum = add(add(a, b), c);
+
+        volume_6 += det;
+        surface_area += magnitude(cross(sub(b, a), sub(c, a))) / 2.;
+        center_of_mass = add(center_of_mass, scale(sum, det));
+        covariance = array::from_fn(|i| {
+            array::from_fn(|j| {
+                let products = points.iter().map(|p| p[i] * p[j]).sum::<f64>();
+                covariance[i][j] + det / 120. * (products + sum[i] * sum[j])
+            })
+        });
+    }
+
+    // The sign of the volume depends on the orientation of the triangles.
+    let sign = if volume_6 < 0. { -1. } else { 1. };
+    let volume = sign * volume_6 / 6.;
+
+    let center_of_mass = if volume_6 == 0. {
+        [0.; 3]
+    } else {
+        scale(center_of_mass, 1. / (4. * volume_6))
+    };
+
+    // Move the covariance from the origin to the center of mass, then convert
+    // it into the inertia tensor.
+    let covariance: [[f64; 3]; 3] = array::from_fn(|i| {
+        array::from_fn(|j| {
+            sign * covariance[i][j]
+                - volume * center_of_mass[i] * center_of_mass[j]
+        })
+    });
+    let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+    let inertia = array::from_fn(|i| {
+        array::from_fn(|j| {
+            let diagonal = if i == j { trace } else { 0. };
+            Scalar::from_f64(diagonal - covariance[i][j])
+        })
+    });
+
+    MassProperties {
+        volume: Scalar::from_f64(volume),
+        surface_area: Scalar::from_f64(surface_area),
+        center_of_mass: Point::from(center_of_mass),
+        inertia,
+        exact: false,
+    }
+}
+
+/// Indicate whether all faces are planar and bounded by straight edges
+///
+/// Faces like that are represented exactly by their triangulation.
+pub fn is_polyhedral<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+) -> bool {
+    faces.into_iter().all(|face| {
+        let surface_is_planar =
+            matches!(face.surface().geometry().u, GlobalPath::Line(_));
+        let edges_are_straight = face
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .all(|half_edge| matches!(half_edge.curve(), Curve::Line(_)));
+
+        surface_is_planar && edges_are_straight
+    })
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f64; 3], s: f64) -> [f64; 3] {
+    a.map(|a| a * s)
+}
+
+fn magnitude(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    #[test]
+    fn from_mesh() {
+        let o = Point::from([0., 0., 0.]);
+        let x = Point::from([1., 0., 0.]);
+        let y = Point::from([0., 1., 0.]);
+        let z = Point::from([0., 0., 1.]);
+
+        let mut tetrahedron = Mesh::new();
+        for triangle in [[o, y, x], [o, x, z], [o, z, y], [x, y, z]] {
+            tetrahedron.push_triangle(triangle, Color::default());
+        }
+
+        let properties = super::from_mesh(&tetrahedron);
+
+        assert_approx_eq(properties.volume, 1. / 6.);
+        assert_approx_eq(properties.surface_area, 1.5 + 3_f64.sqrt() / 2.);
+        for c in properties.center_of_mass.coords.components {
+            assert_approx_eq(c, 0.25);
+        }
+        assert_approx_eq(properties.inertia[0][0], 1. / 80.);
+        assert_approx_eq(properties.inertia[0][1], 1. / 480.);
+        assert!(!properties.exact);
+    }
+
+    fn assert_approx_eq(a: Scalar, b: f64) {
+        assert!((a.into_f64() - b).abs() < 1e-12, "{a} != {b}");
+    }
+}
@@ -0,0 +1,11 @@
+//! Analysis of shapes
+//!
+//! The algorithms in this module compute properties of shapes, that users are
+//! interested in, but that aren't needed to define the shapes themselves.
+
+pub mod curvature;
+pub mod diff;
+pub mod dimensions;
+pub mod interference;
+pub mod mass;
+pub mod overhang;
@@ -0,0 +1,104 @@
+//! Overhang analysis for 3D printing
+//!
+//! See [`from_mesh`].
+
+use fj_interop::{
+    analysis::{Overhangs, TriangleOverhang},
+    mesh::Mesh,
+};
+use fj_math::{Point, Scalar, Vector};
+
+/// The default maximum overhang angle, in radians, that needs no support
+///
+/// 45° is a common rule of thumb for FDM printers.
+pub const DEFAULT_THRESHOLD: f64 = std::f64::consts::FRAC_PI_4;
+
+/// Compute the overhangs of the shape represented by a triangle mesh
+///
+/// Triangles whose overhang angle exceeds `threshold` (in radians) need
+/// support, unless they rest on the build plate. The build plate is assumed to
+/// be at the lowest point of the mesh, along `build_direction`.
+pub fn from_mesh(
+    mesh: &Mesh<Point<3>>,
+    build_direction: impl Into<Vector<3>>,
+    threshold: impl Into<Scalar>,
+) -> Overhangs {
+    let build_direction = build_direction.into().normalize();
+    let threshold = threshold.into();
+
+    let heights = mesh
+        .vertices()
+        .map(|vertex| vertex.coords.dot(&build_direction))
+        .collect::<Vec<_>>();
+    let build_plate = heights.iter().copied().min().unwrap_or_default();
+    let height =
+        heights.iter().copied().max().unwrap_or_default() - build_plate;
+
+    // Points of triangles that rest on the build plate might not be at exactly
+    // the same height, due to floating-point inaccuracies.
+    let epsilon = height * 1e-9;
+
+    let mut area = Scalar::ZERO;
+    let triangles = mesh
+        .triangles()
+        .map(|triangle| {
+            let points = triangle.inner.points();
+            let [a, b, c] = points;
+
+            let cross = (b - a).cross(&(c - a));
+            let downward = -cross.normalize().dot(&build_direction);
+            let angle =
+                Scalar::from_f64(downward.into_f64().clamp(-1., 1.).asin());
+
+            let rests_on_build_plate = points.iter().all(|point| {
+                (point.coords.dot(&build_direction) - build_plate).abs()
+                    <= epsilon
+            });
+
+            let needs_support = angle > threshold && !rests_on_build_plate;
+            if needs_support {
+                area += cross.magnitude() / 2.;
+            }
+
+            TriangleOverhang {
+                angle,
+                needs_support,
+            }
+        })
+        .collect();
+
+    Overhangs {
+        build_direction,
+        threshold,
+        triangles,
+        area,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar, Vector};
+
+    #[test]
+    fn from_mesh() {
+        let mut mesh = Mesh::new();
+        for z in [0., 1.] {
+            // Both triangles face straight down.
+            let triangle = [[0., 0.], [0., 1.], [1., 0.]]
+                .map(|[x, y]| Point::from([x, y, z]));
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let overhangs =
+            super::from_mesh(&mesh, Vector::unit_z(), super::DEFAULT_THRESHOLD);
+
+        let needs_support = overhangs
+            .triangles
+            .iter()
+            .map(|triangle| triangle.needs_support)
+            .collect::<Vec<_>>();
+        assert_eq!(needs_support, [false, true]);
+        assert_eq!(overhangs.area, Scalar::from(0.5));
+    }
+}
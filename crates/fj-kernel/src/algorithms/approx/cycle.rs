@@ -37,7 +37,7 @@ impl Approx for (&Cycle, &Surface) {
 }
 
 /// An approximation of a [`Cycle`]
-#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct CycleApprox {
     /// The approximated edges that make up the approximated cycle
     pub half_edges: Vec<HalfEdgeApprox>,
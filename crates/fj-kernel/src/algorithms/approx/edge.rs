@@ -5,13 +5,19 @@
 //! approximations are usually used to build cycle approximations, and this way,
 //! the caller doesn't have to call with duplicate vertices.
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 
 use crate::{
-    geometry::curve::{Curve, GlobalPath},
-    objects::{GlobalEdge, HalfEdge, Surface, Vertex},
+    geometry::{
+        curve::{Curve, GlobalPath},
+        surface::SurfaceGeometry,
+    },
+    objects::{Face, GlobalEdge, HalfEdge, Surface, Vertex},
     storage::{Handle, ObjectId},
 };
 
@@ -27,6 +33,7 @@ impl Approx for (&HalfEdge, &Surface) {
         cache: &mut Self::Cache,
     ) -> Self::Approximation {
         let (half_edge, surface) = self;
+        let tolerance = tolerance.into();
 
         let boundary = half_edge.boundary();
         let range = RangeOnPath { boundary };
@@ -56,6 +63,21 @@ impl Approx for (&HalfEdge, &Surface) {
                             range,
                             tolerance,
                         );
+                        let boundary = boundary.map(|point_curve| {
+                            let point_global =
+                                surface.geometry().point_from_surface_coords(
+                                    half_edge
+                                        .curve()
+                                        .point_from_path_coords(point_curve),
+                                );
+                            (point_curve, point_global)
+                        });
+                        let approx = cache.share_points(
+                            half_edge.global_form(),
+                            approx,
+                            boundary,
+                            tolerance,
+                        );
                         cache.insert_edge(
                             half_edge.global_form().clone(),
                             range,
@@ -82,7 +104,7 @@ impl Approx for (&HalfEdge, &Surface) {
 }
 
 /// An approximation of an [`HalfEdge`]
-#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct HalfEdgeApprox {
     /// The point that approximates the first vertex of the edge
     pub first: ApproxPoint<2>,
@@ -116,12 +138,18 @@ fn approx_edge(
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
     let points = match (curve, surface.geometry().u) {
-        (Curve::Circle(_), GlobalPath::Circle(_)) => {
-            todo!(
-                "Approximating a circle on a curved surface not supported yet."
-            )
-        }
-        (Curve::Circle(_), GlobalPath::Line(_)) => {
+        (
+            Curve::Bezier(_) | Curve::Circle(_),
+            GlobalPath::Bezier(_)
+            | GlobalPath::Circle(_)
+            | GlobalPath::Ellipse(_),
+        ) => approx_curve_on_curved_surface(
+            curve,
+            &surface.geometry(),
+            range,
+            tolerance.into(),
+        ),
+        (Curve::Bezier(_) | Curve::Circle(_), GlobalPath::Line(_)) => {
             (curve, range)
                 .approx_with_cache(tolerance, &mut ())
                 .into_iter()
@@ -179,10 +207,89 @@ fn approx_edge(
     GlobalEdgeApprox { points }
 }
 
+/// Approximate a curved edge on a curved surface
+///
+/// Neither the approximation of the curve, nor that of the surface bound how
+/// far the edge deviates from the approximation in 3D. The approximation of the
+/// curve is refined instead, by bisecting each of its segments until the edge
+/// passes within the tolerance of the segment's midpoint.
+fn approx_curve_on_curved_surface(
+    curve: &Curve,
+    surface: &SurfaceGeometry,
+    range: RangeOnPath,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let point_global = |point_curve: Point<1>| {
+        surface.point_from_surface_coords(
+            curve.point_from_path_coords(point_curve),
+        )
+    };
+
+    let [start, end] = range.boundary;
+    let samples = iter::once(start)
+        .chain(
+            (curve, range)
+                .approx_with_cache(tolerance, &mut ())
+                .into_iter()
+                .map(|(point_curve, _)| point_curve),
+        )
+        .chain(iter::once(end))
+        .collect::<Vec<_>>();
+
+    let mut points = Vec::new();
+    for segment in samples.windows(2) {
+        bisect(
+            [segment[0], segment[1]],
+            &point_global,
+            tolerance,
+            MAX_BISECTIONS,
+            &mut points,
+        );
+        points.push((segment[1], point_global(segment[1])));
+    }
+
+    // Like the other approximations, this one doesn't include the boundary.
+    points.pop();
+
+    points
+}
+
+/// Add points within a segment, until it is within the tolerance of the edge
+fn bisect(
+    [a, b]: [Point<1>; 2],
+    point_global: &impl Fn(Point<1>) -> Point<3>,
+    tolerance: Tolerance,
+    depth: usize,
+    points: &mut Vec<(Point<1>, Point<3>)>,
+) {
+    let mid = Point::from([a.t + (b.t - a.t) / 2.]);
+    let [a_global, b_global, mid_global] = [a, b, mid].map(point_global);
+
+    let chord_mid = a_global + (b_global - a_global) / 2.;
+    if depth == 0 || (mid_global - chord_mid).magnitude() <= tolerance.length()
+    {
+        return;
+    }
+
+    bisect([a, mid], point_global, tolerance, depth - 1, points);
+    points.push((mid, mid_global));
+    bisect([mid, b], point_global, tolerance, depth - 1, points);
+}
+
+/// Limit the bisections per segment, in case the tolerance can't be reached
+/// due to floating-point precision
+const MAX_BISECTIONS: usize = 16;
+
 /// A cache for results of an approximation
+///
+/// Besides speeding up the approximation, the cache makes sure that all
+/// half-edges that share a [`GlobalEdge`] are approximated by the same points,
+/// down to the last bit. Otherwise, the approximations of neighboring faces
+/// wouldn't meet exactly, leaving cracks in the resulting mesh.
 #[derive(Default)]
 pub struct EdgeCache {
     edge_approx: BTreeMap<(ObjectId, RangeOnPath), GlobalEdgeApprox>,
+    edge_points: BTreeMap<ObjectId, Vec<Point<3>>>,
     vertex_approx: BTreeMap<ObjectId, Point<3>>,
 }
 
@@ -224,6 +331,112 @@ impl EdgeCache {
             .unwrap_or(approx)
     }
 
+    /// Remove all approximations that are not used by the given faces
+    ///
+    /// A cache that outlives the objects it was filled with would otherwise
+    /// keep growing, holding on to approximations that can never be used
+    /// again.
+    pub fn retain<'r>(
+        &mut self,
+        faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    ) {
+        let mut edges = BTreeSet::new();
+        let mut vertices = BTreeSet::new();
+
+        for face in faces {
+            for cycle in face.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    edges.insert(half_edge.global_form().id());
+                    vertices.insert(half_edge.start_vertex().id());
+                }
+            }
+        }
+
+        self.edge_approx.retain(|(id, _), _| edges.contains(id));
+        self.edge_points.retain(|id, _| edges.contains(id));
+        self.vertex_approx.retain(|id, _| vertices.contains(id));
+    }
+
+    /// Make an approximation use the points of its global edge
+    ///
+    /// Every half-edge that refers to a global edge is approximated in its own
+    /// curve coordinates, so their approximations can differ by floating-point
+    /// noise, or even in the number of points, if the edge is curved in one
+    /// surface, but not in the other. The first approximation of a global edge
+    /// defines its points. Later ones are replaced by those points, in the
+    /// order in which they run along the half-edge.
+    ///
+    /// The curve coordinates of each point are interpolated from those of the
+    /// closest segment of the replaced approximation, which also needs the
+    /// boundary of the half-edge for that.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the points is further than the tolerance from the
+    /// replaced approximation. Validation makes sure that all half-edges that
+    /// refer to a global edge coincide, so this can't happen for valid shapes.
+    fn share_points(
+        &mut self,
+        handle: &Handle<GlobalEdge>,
+        approx: GlobalEdgeApprox,
+        boundary: [(Point<1>, Point<3>); 2],
+        tolerance: Tolerance,
+    ) -> GlobalEdgeApprox {
+        let Some(shared) = self.edge_points.get(&handle.id()) else {
+            let points = approx
+                .points
+                .iter()
+                .map(|point| point.global_form)
+                .collect();
+            self.edge_points.insert(handle.id(), points);
+            return approx;
+        };
+
+        let [start, end] = boundary;
+        let segments = iter::once(start)
+            .chain(
+                approx
+                    .points
+                    .iter()
+                    .map(|point| (point.local_form, point.global_form)),
+            )
+            .chain(iter::once(end))
+            .collect::<Vec<_>>();
+
+        let mut points = shared
+            .iter()
+            .map(|&point_global| {
+                let (point_curve, distance) = segments
+                    .windows(2)
+                    .map(|segment| {
+                        project_onto_segment(
+                            point_global,
+                            [segment[0], segment[1]],
+                        )
+                    })
+                    .min_by_key(|(_, distance)| *distance)
+                    .expect("Approximation has at least one segment");
+
+                assert!(
+                    tolerance.contains_distance(distance),
+                    "Half-edges that refer to the same global edge don't \
+                    coincide"
+                );
+
+                ApproxPoint::new(point_curve, point_global)
+            })
+            .collect::<Vec<_>>();
+
+        // The points of the global edge might have been defined by a half-edge
+        // that runs in the other direction.
+        points.sort_by_key(|point| point.local_form.t);
+        if start.0 > end.0 {
+            points.reverse();
+        }
+
+        GlobalEdgeApprox { points }
+    }
+
     fn get_position(&self, handle: &Handle<Vertex>) -> Option<Point<3>> {
         self.vertex_approx.get(&handle.id()).cloned()
     }
@@ -239,6 +452,29 @@ impl EdgeCache {
     }
 }
 
+/// Project a point onto a segment of an edge approximation
+///
+/// Returns the curve coordinates of the projected point, interpolated between
+/// those of the segment's end points, and its distance to the given point.
+fn project_onto_segment(
+    point: Point<3>,
+    [(a_curve, a), (b_curve, b)]: [(Point<1>, Point<3>); 2],
+) -> (Point<1>, Scalar) {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    let s = if length_squared == Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        ((point - a).dot(&ab) / length_squared).clamp(Scalar::ZERO, Scalar::ONE)
+    };
+
+    let point_curve = Point::from([a_curve.t + (b_curve.t - a_curve.t) * s]);
+    let distance = (a + ab * s).distance_to(&point);
+
+    (point_curve, distance)
+}
+
 /// An approximation of a [`GlobalEdge`]
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct GlobalEdgeApprox {
@@ -256,18 +492,30 @@ impl GlobalEdgeApprox {
 
 #[cfg(test)]
 mod tests {
-    use std::{f64::consts::TAU, ops::Deref};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        f64::consts::{PI, TAU},
+        ops::Deref,
+    };
 
+    use fj_math::Point;
     use pretty_assertions::assert_eq;
 
     use crate::{
-        algorithms::approx::{path::RangeOnPath, Approx, ApproxPoint},
-        geometry::{curve::GlobalPath, surface::SurfaceGeometry},
-        objects::{HalfEdge, Surface},
-        operations::{BuildHalfEdge, Insert},
+        algorithms::approx::{
+            path::RangeOnPath, Approx, ApproxPoint, Tolerance,
+        },
+        geometry::{
+            curve::{Curve, GlobalPath},
+            surface::SurfaceGeometry,
+        },
+        objects::{GlobalEdge, HalfEdge, Shell, Surface},
+        operations::{BuildHalfEdge, BuildShell, Insert},
         services::Services,
     };
 
+    use super::{EdgeCache, GlobalEdgeApprox};
+
     #[test]
     fn approx_line_on_flat_surface() {
         let mut services = Services::new();
@@ -364,4 +612,107 @@ mod tests {
                 .collect::<Vec<_>>();
         assert_eq!(approx.points, expected_approx);
     }
+
+    #[test]
+    fn approx_circle_on_curved_surface() {
+        let surface = SurfaceGeometry {
+            u: GlobalPath::circle_from_radius(1.),
+            v: [0., 0., 1.].into(),
+        };
+        let curve = Curve::circle_from_center_and_radius([PI, 0.], 1.);
+        let range = RangeOnPath::from([[0.], [TAU]]);
+
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+        let approx = super::approx_curve_on_curved_surface(
+            &curve, &surface, range, tolerance,
+        );
+
+        // Every segment of the approximation, including the ones to the
+        // boundary, must be within the tolerance of the edge at its middle.
+        let point_global = |t: f64| {
+            surface.point_from_surface_coords(curve.point_from_path_coords([t]))
+        };
+        let points = [(Point::from([0.]), point_global(0.))]
+            .into_iter()
+            .chain(approx)
+            .chain([(Point::from([TAU]), point_global(TAU))])
+            .collect::<Vec<_>>();
+        for segment in points.windows(2) {
+            let [(t_a, a), (t_b, b)] = [segment[0], segment[1]];
+
+            let mid = point_global((t_a.t + t_b.t).into_f64() / 2.);
+            let chord_mid = a + (b - a) / 2.;
+
+            assert!((mid - chord_mid).magnitude() <= tolerance.length());
+        }
+    }
+
+    #[test]
+    fn neighboring_faces_share_edge_points() {
+        let mut services = Services::new();
+
+        // The edges of the cylinder's caps are approximated as circles on the
+        // caps, but as lines along the curved side wall. The approximations
+        // must still end up with the same points.
+        let cylinder = Shell::cylinder(1., 2., &mut services.objects);
+
+        let tolerance = 0.01;
+        let approx = (&cylinder).approx(tolerance);
+
+        let mut faces_by_point = BTreeMap::new();
+        for (i, face) in approx.iter().enumerate() {
+            for point in face.points() {
+                faces_by_point
+                    .entry(point.global_form)
+                    .or_insert_with(BTreeSet::new)
+                    .insert(i);
+            }
+        }
+
+        for (point, faces) in faces_by_point {
+            assert!(faces.len() >= 2, "{point:?} only used by one face");
+        }
+    }
+
+    #[test]
+    fn share_points_with_different_number_of_points() {
+        let mut services = Services::new();
+
+        let global_edge = GlobalEdge::new().insert(&mut services.objects);
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+        let approx = |points: &[(f64, f64)]| GlobalEdgeApprox {
+            points: points
+                .iter()
+                .map(|&(t, x)| {
+                    ApproxPoint::new(Point::from([t]), Point::from([x, 0., 0.]))
+                })
+                .collect(),
+        };
+
+        let mut cache = EdgeCache::new();
+        let first = approx(&[(0.25, 0.25), (0.5, 0.5), (0.75, 0.75)]);
+        cache.share_points(
+            &global_edge,
+            first,
+            [([0.], [0., 0., 0.]), ([1.], [1., 0., 0.])].map(point),
+            tolerance,
+        );
+
+        // A half-edge that runs in the other direction, and has been
+        // approximated by fewer points.
+        let second = cache.share_points(
+            &global_edge,
+            approx(&[(1., 0.5)]),
+            [([0.], [1., 0., 0.]), ([2.], [0., 0., 0.])].map(point),
+            tolerance,
+        );
+
+        assert_eq!(second, approx(&[(0.5, 0.75), (1., 0.5), (1.5, 0.25)]));
+    }
+
+    fn point(
+        (point_curve, point_global): ([f64; 1], [f64; 3]),
+    ) -> (Point<1>, Point<3>) {
+        (Point::from(point_curve), Point::from(point_global))
+    }
 }
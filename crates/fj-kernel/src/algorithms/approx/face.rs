@@ -4,10 +4,13 @@
 
 use std::{collections::BTreeSet, ops::Deref};
 
-use fj_interop::mesh::Color;
+use fj_interop::mesh::{Color, FaceId};
+use fj_math::{Scalar, Transform};
 
 use crate::{
+    algorithms::texture::TextureApprox,
     objects::{Face, FaceSet, Handedness},
+    storage::Handle,
     validate::ValidationConfig,
 };
 
@@ -31,37 +34,13 @@ impl Approx for &FaceSet {
             .map(|face| face.approx_with_cache(tolerance, cache))
             .collect();
 
-        let min_distance = ValidationConfig::default().distinct_min_distance;
-        let mut all_points: BTreeSet<ApproxPoint<2>> = BTreeSet::new();
-
-        // Run some validation code on the approximation.
-        for approx in &approx {
-            let approx: &FaceApprox = approx;
-
-            for a in &approx.points() {
-                for b in &all_points {
-                    let distance = (b.global_form - a.global_form).magnitude();
-
-                    if b.global_form != a.global_form && distance < min_distance
-                    {
-                        panic!(
-                            "Invalid approximation: \
-                            Distinct points are too close \
-                            (a: {:?}, b: {:?}, distance: {distance})",
-                            a.global_form, b.global_form,
-                        );
-                    }
-                }
-
-                all_points.insert(a.clone());
-            }
-        }
+        check_distinct_points(&approx);
 
         approx
     }
 }
 
-impl Approx for &Face {
+impl Approx for &Handle<Face> {
     type Approximation = FaceApprox;
     type Cache = EdgeCache;
 
@@ -70,6 +49,8 @@ impl Approx for &Face {
         tolerance: impl Into<Tolerance>,
         cache: &mut Self::Cache,
     ) -> Self::Approximation {
+        let _span = tracing::debug_span!("approximate_face").entered();
+
         let tolerance = tolerance.into();
 
         // Curved faces whose curvature is not fully defined by their edges
@@ -99,13 +80,21 @@ impl Approx for &Face {
             exterior,
             interiors,
             color: self.color(),
+            texture: self.texture().map(|texture| {
+                TextureApprox::new(
+                    texture,
+                    self.surface().geometry(),
+                    tolerance,
+                )
+            }),
             coord_handedness: self.coord_handedness(),
+            face: self.id().into(),
         }
     }
 }
 
 /// An approximation of a [`Face`]
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct FaceApprox {
     /// Approximation of the exterior cycle
     pub exterior: CycleApprox,
@@ -116,8 +105,18 @@ pub struct FaceApprox {
     /// The color of the approximated face
     pub color: Option<Color>,
 
+    /// The texture of the approximated face, if it has one
+    pub texture: Option<TextureApprox>,
+
     /// The handedness of the approximated face's front-side coordinate system
     pub coord_handedness: Handedness,
+
+    /// The ID of the approximated face
+    ///
+    /// This is the same as the ID of the face's [`Handle`], unless the face
+    /// belongs to an instance of a solid. Then it is derived from that ID and
+    /// the instance, so the faces of every instance have their own IDs.
+    pub face: FaceId,
 }
 
 impl FaceApprox {
@@ -133,4 +132,85 @@ impl FaceApprox {
 
         points
     }
+
+    /// Transform the approximation
+    ///
+    /// Only the global forms of the points are transformed. The surface
+    /// coordinates stay the same, as they are relative to the transformed
+    /// surface.
+    pub fn transform(self, transform: &Transform) -> Self {
+        let transform_cycle = |mut cycle: CycleApprox| {
+            for half_edge in &mut cycle.half_edges {
+                for point in [&mut half_edge.first]
+                    .into_iter()
+                    .chain(&mut half_edge.points)
+                {
+                    point.global_form =
+                        transform.transform_point(&point.global_form);
+                }
+            }
+            cycle
+        };
+
+        // A mirroring transform turns the face around. Its triangles need to
+        // be wound the other way, to keep facing the same side.
+        let (_, _, scale) = transform.decompose();
+        let coord_handedness = if scale.x < Scalar::ZERO {
+            match self.coord_handedness {
+                Handedness::LeftHanded => Handedness::RightHanded,
+                Handedness::RightHanded => Handedness::LeftHanded,
+            }
+        } else {
+            self.coord_handedness
+        };
+
+        Self {
+            exterior: transform_cycle(self.exterior),
+            interiors: self
+                .interiors
+                .into_iter()
+                .map(transform_cycle)
+                .collect(),
+            color: self.color,
+            texture: self.texture.map(|texture| texture.transform(transform)),
+            coord_handedness,
+            face: self.face,
+        }
+    }
+}
+
+/// Check that distinct points of a face set's approximation are not too close
+///
+/// This is done automatically when approximating a [`FaceSet`]. Callers that
+/// approximate faces individually can use it to run the same check.
+///
+/// # Panics
+///
+/// Panics, if two distinct points are closer to each other than
+/// [`ValidationConfig::distinct_min_distance`].
+pub fn check_distinct_points<'r>(
+    approx: impl IntoIterator<Item = &'r FaceApprox>,
+) {
+    let min_distance =
+        ValidationConfig::default().distinct_min_distance.length();
+    let mut all_points: BTreeSet<ApproxPoint<2>> = BTreeSet::new();
+
+    for approx in approx {
+        for a in &approx.points() {
+            for b in &all_points {
+                let distance = (b.global_form - a.global_form).magnitude();
+
+                if b.global_form != a.global_form && distance < min_distance {
+                    panic!(
+                        "Invalid approximation: \
+                        Distinct points are too close \
+                        (a: {:?}, b: {:?}, distance: {distance})",
+                        a.global_form, b.global_form,
+                    );
+                }
+            }
+
+            all_points.insert(a.clone());
+        }
+    }
 }
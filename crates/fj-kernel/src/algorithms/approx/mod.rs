@@ -4,10 +4,10 @@ pub mod cycle;
 pub mod edge;
 pub mod face;
 pub mod path;
+pub mod shape;
 pub mod shell;
 pub mod sketch;
 pub mod solid;
-pub mod tolerance;
 
 use std::{
     cmp::Ordering,
@@ -17,7 +17,7 @@ use std::{
 
 use fj_math::Point;
 
-pub use self::tolerance::{InvalidTolerance, Tolerance};
+pub use fj_math::{InvalidTolerance, Tolerance};
 
 /// Approximate an object
 pub trait Approx: Sized {
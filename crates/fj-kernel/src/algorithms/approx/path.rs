@@ -30,7 +30,7 @@
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_math::{Bezier, Circle, Ellipse, Point, Scalar, Sign};
 
 use crate::geometry::curve::{Curve, GlobalPath};
 
@@ -48,6 +48,9 @@ impl Approx for (&Curve, RangeOnPath) {
         let (path, range) = self;
 
         match path {
+            Curve::Bezier(bezier) => {
+                approx_bezier(bezier, range, tolerance.into())
+            }
             Curve::Circle(circle) => {
                 approx_circle(circle, range, tolerance.into())
             }
@@ -68,9 +71,15 @@ impl Approx for (GlobalPath, RangeOnPath) {
         let (path, range) = self;
 
         match path {
+            GlobalPath::Bezier(bezier) => {
+                approx_bezier(&bezier, range, tolerance.into())
+            }
             GlobalPath::Circle(circle) => {
                 approx_circle(&circle, range, tolerance.into())
             }
+            GlobalPath::Ellipse(ellipse) => {
+                approx_ellipse(&ellipse, range, tolerance.into())
+            }
             GlobalPath::Line(_) => vec![],
         }
     }
@@ -123,6 +132,51 @@ fn approx_circle<const D: usize>(
     points
 }
 
+/// Approximate an ellipse
+///
+/// Works like [`approx_circle`], but bases the approximation on the ellipse's
+/// semi-major axis. This is conservative: The approximation never deviates
+/// from the ellipse more than it would from a circle of that radius.
+fn approx_ellipse<const D: usize>(
+    ellipse: &Ellipse<D>,
+    range: impl Into<RangeOnPath>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let range = range.into();
+
+    let params = PathApproxParams::for_ellipse(ellipse, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(range) {
+        let point_global = ellipse.point_from_ellipse_coords(point_curve);
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
+/// Approximate a cubic Bézier curve
+///
+/// The curve is sampled at uniform increments of its Bézier coordinates, like
+/// a circle is sampled at uniform increments of its angle.
+fn approx_bezier<const D: usize>(
+    bezier: &Bezier<D>,
+    range: impl Into<RangeOnPath>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let range = range.into();
+
+    let params = PathApproxParams::for_bezier(bezier, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(range) {
+        let point_global = bezier.point_from_bezier_coords(point_curve);
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
 struct PathApproxParams {
     increment: Scalar,
 }
@@ -132,14 +186,59 @@ impl PathApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
-        let radius = circle.a().magnitude();
+        Self::for_radius(circle.a().magnitude(), tolerance)
+    }
 
-        let num_vertices_to_approx_full_circle = Scalar::max(
-            Scalar::PI
-                / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
-            3.,
-        )
-        .ceil();
+    pub fn for_ellipse<const D: usize>(
+        ellipse: &Ellipse<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        Self::for_radius(ellipse.max_radius(), tolerance)
+    }
+
+    pub fn for_bezier<const D: usize>(
+        bezier: &Bezier<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        // A chord with a parameter increment of `h` deviates from the curve
+        // by at most `h^2 * M / 8`, where `M` bounds the second derivative.
+        let max_second_derivative = bezier.max_second_derivative();
+        let tolerance = tolerance.into().length();
+
+        let num_segments = if max_second_derivative == Scalar::ZERO {
+            1.
+        } else {
+            let increment =
+                (tolerance * 8. / max_second_derivative).into_f64().sqrt();
+            (1. / increment).ceil().max(1.)
+        };
+
+        Self {
+            increment: Scalar::ONE / num_segments,
+        }
+    }
+
+    fn for_radius(radius: Scalar, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
+
+        // A tolerance that is larger than the circle's diameter doesn't
+        // constrain anything. Without clamping, it would produce `NaN` here.
+        let cos_half_angle = Scalar::max(
+            Scalar::ONE - (tolerance.length() / radius),
+            -Scalar::ONE,
+        );
+        let num_vertices_for_length = Scalar::PI / cos_half_angle.acos();
+
+        // Each segment covers the angle of one increment. Limiting that angle
+        // gives small circles, like holes, a predictable number of segments,
+        // where the length tolerance alone would approximate them very
+        // coarsely.
+        let num_vertices_for_angle = Scalar::TAU / tolerance.angle();
+
+        let num_vertices_to_approx_full_circle =
+            num_vertices_for_length.max(num_vertices_for_angle).max(3.);
+        let num_vertices_to_approx_full_circle =
+            num_vertices_to_approx_full_circle.ceil();
 
         let increment = Scalar::TAU / num_vertices_to_approx_full_circle;
 
@@ -205,6 +304,13 @@ mod tests {
         test_increment(1., 0.1, 7.);
         test_increment(1., 0.01, 23.);
 
+        // Larger than the circle.
+        test_increment(1., 10., 3.);
+
+        // The angle limits the number of vertices, if it's more restrictive.
+        test_increment(1., Tolerance::new(0.5, TAU / 12.).unwrap(), 12.);
+        test_increment(1., Tolerance::new(0.01, TAU / 12.).unwrap(), 23.);
+
         fn test_increment(
             radius: impl Into<Scalar>,
             tolerance: impl Into<Tolerance>,
@@ -0,0 +1,165 @@
+//! Shape approximation
+//!
+//! See [`ShapeApprox`].
+
+use std::{collections::BTreeMap, ops::Deref};
+
+use fj_interop::mesh::Mesh;
+use fj_math::Point;
+
+use crate::{
+    algorithms::triangulate::Triangulate,
+    objects::{Face, HalfEdge},
+    storage::{Handle, ObjectId},
+};
+
+use super::{
+    edge::{EdgeCache, HalfEdgeApprox},
+    face::FaceApprox,
+    Approx, Tolerance,
+};
+
+/// The approximation of a whole shape, with lookups by object
+///
+/// Approximating a shape is expensive, and many consumers need it: Rendering,
+/// exporting, slicing, analysis. An instance of `ShapeApprox` can be computed
+/// once and shared between them, making sure that they all work with the same
+/// tessellation.
+#[derive(Clone, Debug)]
+pub struct ShapeApprox {
+    tolerance: Tolerance,
+    faces: BTreeMap<ObjectId, FaceApprox>,
+    half_edges: BTreeMap<ObjectId, HalfEdgeApprox>,
+}
+
+impl ShapeApprox {
+    /// Construct an empty approximation with the given tolerance
+    pub fn new(tolerance: impl Into<Tolerance>) -> Self {
+        Self {
+            tolerance: tolerance.into(),
+            faces: BTreeMap::new(),
+            half_edges: BTreeMap::new(),
+        }
+    }
+
+    /// Approximate the given faces
+    pub fn from_faces<'r>(
+        faces: impl IntoIterator<Item = &'r Handle<Face>>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        let mut approx = Self::new(tolerance);
+        let mut cache = EdgeCache::new();
+
+        for face in faces {
+            approx.insert(face, &mut cache);
+        }
+
+        approx
+    }
+
+    /// Approximate a face and add it to the approximation
+    ///
+    /// Returns the approximation of the face. If the face has been added
+    /// before, it is not approximated again.
+    pub fn insert(
+        &mut self,
+        face: &Handle<Face>,
+        cache: &mut EdgeCache,
+    ) -> &FaceApprox {
+        let tolerance = self.tolerance;
+
+        // Half-edges are approximated a second time here, as the approximation
+        // of the face doesn't keep track of which half-edge its parts came
+        // from. The cache makes this cheap.
+        for cycle in face.all_cycles() {
+            for half_edge in cycle.half_edges() {
+                self.half_edges.entry(half_edge.id()).or_insert_with(|| {
+                    (half_edge.deref(), face.surface().deref())
+                        .approx_with_cache(tolerance, cache)
+                });
+            }
+        }
+
+        self.faces
+            .entry(face.id())
+            .or_insert_with(|| face.approx_with_cache(tolerance, cache))
+    }
+
+    /// Access the tolerance that the approximation was computed with
+    pub fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+
+    /// Access the approximation of a face
+    ///
+    /// Returns `None`, if the face is not part of the approximated shape.
+    pub fn face(&self, face: &Handle<Face>) -> Option<&FaceApprox> {
+        self.faces.get(&face.id())
+    }
+
+    /// Access the approximation of a half-edge
+    ///
+    /// Returns `None`, if the half-edge is not part of the approximated shape.
+    pub fn half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Option<&HalfEdgeApprox> {
+        self.half_edges.get(&half_edge.id())
+    }
+
+    /// Iterate over the approximations of all faces
+    pub fn faces(&self) -> impl Iterator<Item = &FaceApprox> + '_ {
+        self.faces.values()
+    }
+
+    /// Return the number of approximated faces
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+}
+
+impl Triangulate for &ShapeApprox {
+    fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>) {
+        for face in self.faces() {
+            face.clone().triangulate_into_mesh(mesh);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{approx::Approx, sweep::Sweep},
+        builder::{CycleBuilder, FaceBuilder},
+        operations::Insert,
+        services::Services,
+    };
+
+    use super::ShapeApprox;
+
+    #[test]
+    fn lookup_by_object() {
+        let mut services = Services::new();
+
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [1., 0.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+        let shell = face.sweep([0., 0., 1.], &mut services.objects).unwrap();
+
+        let tolerance = 0.1;
+        let approx = ShapeApprox::from_faces(shell.faces(), tolerance);
+
+        assert_eq!(approx.num_faces(), shell.faces().into_iter().count());
+        for face in shell.faces() {
+            assert_eq!(approx.face(face), Some(&face.approx(tolerance)));
+
+            for half_edge in face.exterior().half_edges() {
+                assert!(approx.half_edge(half_edge).is_some());
+            }
+        }
+    }
+}
@@ -1,8 +1,14 @@
 //! Solid approximation
 
-use std::collections::BTreeSet;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+};
 
-use crate::objects::Solid;
+use fj_interop::mesh::FaceId;
+use fj_math::Scalar;
+
+use crate::objects::{Instance, Solid};
 
 use super::{edge::EdgeCache, face::FaceApprox, Approx, Tolerance};
 
@@ -17,8 +23,155 @@ impl Approx for &Solid {
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
 
-        self.shells()
+        let mut approx = self
+            .shells()
             .flat_map(|shell| shell.approx_with_cache(tolerance, cache))
-            .collect()
+            .collect::<BTreeSet<_>>();
+
+        // A solid that is instanced multiple times with the same scale, is only
+        // approximated once. Only transforming the approximation is repeated.
+        let mut instanced = BTreeMap::new();
+
+        for instance in self.instances() {
+            // If the instance is scaled up, so would be the approximation
+            // error. The referenced solid needs to be approximated with a
+            // tighter tolerance to make up for that.
+            let (_, _, scale) = instance.transform().decompose();
+            let max_scale = scale
+                .components
+                .into_iter()
+                .map(|component| component.abs())
+                .fold(Scalar::ONE, Scalar::max);
+            let tolerance = Tolerance::new(
+                tolerance.length() / max_scale,
+                tolerance.angle(),
+            )
+            .expect("Dividing positive tolerance by positive scale");
+
+            let solid_approx = instanced
+                .entry((instance.solid().id(), tolerance))
+                .or_insert_with(|| {
+                    // The edge cache only knows edges, not the tolerance they
+                    // were approximated with. Every tolerance needs its own,
+                    // or instances with different scales would share the
+                    // approximation of whichever was approximated first.
+                    instance
+                        .solid()
+                        .approx_with_cache(tolerance, &mut EdgeCache::new())
+                });
+
+            approx.extend(solid_approx.iter().cloned().map(|face| {
+                let mut face = face.transform(instance.transform());
+                face.face = instance_face_id(face.face, instance);
+                face
+            }));
+        }
+
+        approx
+    }
+}
+
+/// Derive the ID of a face of an instance from the ID of the original face
+///
+/// Every instance gets its own face IDs, so the faces of different instances
+/// can be told apart, when picking them, for example.
+fn instance_face_id(face: FaceId, instance: &Instance) -> FaceId {
+    let mut hasher = DefaultHasher::new();
+
+    face.hash(&mut hasher);
+    instance.solid().id().hash(&mut hasher);
+    for value in instance.transform().data() {
+        value.to_bits().hash(&mut hasher);
+    }
+
+    FaceId(u128::from(hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fj_math::{Point, Scalar, Transform};
+
+    use crate::{
+        algorithms::{approx::Approx, sweep::Sweep},
+        builder::{CycleBuilder, FaceBuilder},
+        objects::{Instance, Solid},
+        operations::Insert,
+        services::Services,
+    };
+
+    #[test]
+    fn instances_with_different_scales() {
+        let mut services = Services::new();
+
+        // A half-cylinder with a radius of 1, its axis on the z-axis.
+        let exterior = CycleBuilder::new()
+            .move_to([1., 0.])
+            .add_arc_to([0., 1.], 1., &mut services.objects)
+            .add_arc_to([-1., 0.], 1., &mut services.objects)
+            .close(&mut services.objects);
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(exterior)
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+        let shell = face.sweep([0., 0., 1.], &mut services.objects).unwrap();
+        let part = Solid::new([shell]).insert(&mut services.objects);
+
+        // The small instance comes first, so its approximation would be the
+        // one that is reused, if scales weren't told apart.
+        let small = Instance::new(part.clone(), Transform::identity());
+        let large = Instance::new(
+            part,
+            Transform::translation([100., 0., 0.]) * Transform::scale(10.),
+        );
+        let assembly = Solid::new([]).with_instances([small, large]);
+
+        let tolerance = 0.1;
+        let approx = assembly.approx(tolerance);
+
+        // Each instance has its own faces.
+        let num_faces = approx
+            .iter()
+            .map(|face| face.face)
+            .collect::<BTreeSet<_>>()
+            .len();
+        assert_eq!(num_faces, approx.len());
+
+        let axis = Point::from([100., 0.]);
+        let radius = Scalar::from(10.);
+
+        let mut num_segments = 0;
+        for face in &approx {
+            let points = face
+                .exterior
+                .points()
+                .into_iter()
+                .map(|point| point.global_form)
+                .collect::<Vec<_>>();
+
+            // Only check the large instance.
+            if points[0].x < Scalar::from(50.) {
+                continue;
+            }
+
+            for (i, a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+
+                // Skip the flat side, which is not on the cylinder.
+                if a.y.abs() < Scalar::from(1e-9)
+                    && b.y.abs() < Scalar::from(1e-9)
+                {
+                    continue;
+                }
+
+                let middle = Point::from([(a.x + b.x) / 2., (a.y + b.y) / 2.]);
+                let deviation = radius - (middle - axis).magnitude();
+                assert!(deviation <= Scalar::from(tolerance), "{deviation}");
+
+                num_segments += 1;
+            }
+        }
+        assert!(num_segments > 0);
     }
 }
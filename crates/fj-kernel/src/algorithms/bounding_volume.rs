@@ -0,0 +1,452 @@
+//! Compute bounding volumes of objects
+//!
+//! See [`BoundingVolume`].
+
+use fj_math::{Aabb, Bezier, Ellipse, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{
+        curve::{Curve, GlobalPath},
+        surface::SurfaceGeometry,
+    },
+    objects::{Cycle, Face, FaceSet, HalfEdge, Shell, Sketch, Solid},
+};
+
+/// Compute a bounding volume of an object
+///
+/// The bounding volume is computed from the exact geometry of the object's
+/// edges. It does not require an approximation, which means it does not depend
+/// on a tolerance value.
+pub trait BoundingVolume<const D: usize> {
+    /// Compute an axis-aligned bounding box (AABB)
+    ///
+    /// Returns `None`, if the object is empty.
+    fn aabb(&self) -> Option<Aabb<D>>;
+}
+
+/// The part of a curve within the given boundary, in path coordinates
+///
+/// The result is exact for lines, circular arcs, and Bézier curves.
+impl BoundingVolume<2> for (Curve, [Point<1>; 2]) {
+    fn aabb(&self) -> Option<Aabb<2>> {
+        let (curve, boundary) = *self;
+
+        let points =
+            path_extrema(boundary, |range| curve_extrema(&curve, range))
+                .map(|t| curve.point_from_path_coords([t]));
+
+        aabb_from_points(points)
+    }
+}
+
+/// The part of a global path within the given boundary, in path coordinates
+///
+/// The result is exact for all paths.
+impl BoundingVolume<3> for (GlobalPath, [Point<1>; 2]) {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        let (path, boundary) = self;
+
+        let points =
+            path_extrema(*boundary, |range| global_path_extrema(path, range))
+                .map(|t| path.point_from_path_coords([t]));
+
+        aabb_from_points(points)
+    }
+}
+
+impl BoundingVolume<2> for HalfEdge {
+    fn aabb(&self) -> Option<Aabb<2>> {
+        (self.curve(), self.boundary()).aabb()
+    }
+}
+
+/// A half-edge in global space, given the surface it is defined on
+///
+/// See [`global_extrema`] for when the result is exact.
+impl BoundingVolume<3> for (&HalfEdge, &SurfaceGeometry) {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        let (half_edge, surface) = *self;
+        aabb_from_points(global_extrema(half_edge, surface))
+    }
+}
+
+impl BoundingVolume<2> for Cycle {
+    fn aabb(&self) -> Option<Aabb<2>> {
+        merge(self.half_edges().map(|half_edge| half_edge.aabb()))
+    }
+}
+
+impl BoundingVolume<3> for Face {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        // All surfaces are swept along a straight line, so no coordinate can
+        // have an extremum within the face that isn't also reached on its
+        // boundary. The interiors are within the exterior, which makes the
+        // exterior sufficient.
+        let surface = self.surface().geometry();
+
+        merge(
+            self.exterior()
+                .half_edges()
+                .map(|half_edge| (&**half_edge, &surface).aabb()),
+        )
+    }
+}
+
+impl BoundingVolume<3> for FaceSet {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        merge(self.into_iter().map(|face| face.aabb()))
+    }
+}
+
+impl BoundingVolume<3> for Shell {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        self.faces().aabb()
+    }
+}
+
+impl BoundingVolume<3> for Solid {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        let shells = self.shells().map(|shell| shell.aabb());
+        let instances =
+            self.instances().map(|instance| {
+                // Transform all corners, as the box might be rotated.
+                let aabb = instance.solid().aabb()?;
+                aabb_from_points(aabb.vertices().map(|vertex| {
+                    instance.transform().transform_point(&vertex)
+                }))
+            });
+
+        merge(shells.chain(instances))
+    }
+}
+
+impl BoundingVolume<3> for Sketch {
+    fn aabb(&self) -> Option<Aabb<3>> {
+        self.faces().aabb()
+    }
+}
+
+/// Compute the points of a half-edge that could be extreme in global space
+///
+/// On a plane, the result is exact. On other surfaces, it is exact for lines
+/// along either of the surface's axes. For other curves, the points bound the
+/// area of the surface that the curve covers, which is conservative.
+fn global_extrema(
+    half_edge: &HalfEdge,
+    surface: &SurfaceGeometry,
+) -> Vec<Point<3>> {
+    let boundary = half_edge.boundary();
+
+    if let GlobalPath::Line(_) = surface.u {
+        let path = path_on_plane(half_edge.curve(), surface);
+        return path_extrema(boundary, |range| {
+            global_path_extrema(&path, range)
+        })
+        .map(|t| path.point_from_path_coords([t]))
+        .collect();
+    }
+
+    let Some(aabb) = half_edge.aabb() else {
+        return Vec::new();
+    };
+    let [u, v] = [0, 1]
+        .map(|i| [aabb.min, aabb.max].map(|point| point.coords.components[i]));
+    let u = u.map(|u| Point::from([u]));
+
+    path_extrema(u, |range| global_path_extrema(&surface.u, range))
+        .map(|t| surface.u.point_from_path_coords([t]))
+        .flat_map(|point| v.map(|v| point + surface.v * v))
+        .collect()
+}
+
+/// Map a curve on a plane into global space
+///
+/// Planes map surface coordinates to global coordinates affinely, so the
+/// resulting path is exact.
+fn path_on_plane(curve: Curve, surface: &SurfaceGeometry) -> GlobalPath {
+    let point = |point: Point<2>| surface.point_from_surface_coords(point);
+    let vector = |vector: Vector<2>| surface.vector_from_surface_coords(vector);
+
+    match curve {
+        Curve::Bezier(bezier) => {
+            GlobalPath::Bezier(Bezier::new(bezier.control_points().map(point)))
+        }
+        Curve::Circle(circle) => GlobalPath::from_ellipse(Ellipse::new(
+            point(circle.center()),
+            vector(circle.a()),
+            vector(circle.b()),
+        )),
+        Curve::Line(line) => GlobalPath::Line(Line::from_origin_and_direction(
+            point(line.origin()),
+            vector(line.direction()),
+        )),
+    }
+}
+
+/// Find the path coordinates within `range`, at which a curve reaches an
+/// extremum along either axis
+pub(crate) fn curve_extrema(curve: &Curve, range: [Scalar; 2]) -> Vec<Scalar> {
+    match curve {
+        Curve::Bezier(bezier) => bezier_extrema(bezier, range),
+        Curve::Circle(circle) => {
+            sinusoid_extrema(circle.a(), circle.b(), range)
+        }
+        Curve::Line(_) => Vec::new(),
+    }
+}
+
+fn global_path_extrema(path: &GlobalPath, range: [Scalar; 2]) -> Vec<Scalar> {
+    match path {
+        GlobalPath::Bezier(bezier) => bezier_extrema(bezier, range),
+        GlobalPath::Circle(circle) => {
+            sinusoid_extrema(circle.a(), circle.b(), range)
+        }
+        GlobalPath::Ellipse(ellipse) => {
+            sinusoid_extrema(ellipse.a(), ellipse.b(), range)
+        }
+        GlobalPath::Line(_) => Vec::new(),
+    }
+}
+
+/// Compute the path coordinates at which a path can reach an extreme position
+///
+/// This includes the boundary, as well as all extrema within it, as computed
+/// by `extrema`. The range passed to `extrema` is sorted.
+fn path_extrema(
+    boundary: [Point<1>; 2],
+    extrema: impl FnOnce([Scalar; 2]) -> Vec<Scalar>,
+) -> impl Iterator<Item = Scalar> {
+    let [start, end] = boundary.map(|point| point.t);
+    let range = if start < end {
+        [start, end]
+    } else {
+        [end, start]
+    };
+
+    [start, end].into_iter().chain(extrema(range))
+}
+
+/// Find the extrema of `a * cos(t) + b * sin(t)` along each axis
+///
+/// This is the form of circles and ellipses, relative to their center.
+pub(crate) fn sinusoid_extrema<const D: usize>(
+    a: Vector<D>,
+    b: Vector<D>,
+    [min, max]: [Scalar; 2],
+) -> Vec<Scalar> {
+    let mut extrema = Vec::new();
+
+    for i in 0..D {
+        // The derivative along the axis, `b_i * cos(t) - a_i * sin(t)`, is
+        // zero every half turn from here.
+        let t = b.components[i].atan2(a.components[i]);
+
+        let mut t = t + ((min - t) / Scalar::PI).ceil() * Scalar::PI;
+        while t <= max {
+            extrema.push(t);
+            t += Scalar::PI;
+        }
+    }
+
+    extrema
+}
+
+/// Find the extrema of a Bézier curve along each axis
+pub(crate) fn bezier_extrema<const D: usize>(
+    bezier: &Bezier<D>,
+    [min, max]: [Scalar; 2],
+) -> Vec<Scalar> {
+    let [p0, p1, p2, p3] = bezier.control_points();
+    let [d0, d1, d2] = [p1 - p0, p2 - p1, p3 - p2];
+
+    let mut extrema = Vec::new();
+
+    for i in 0..D {
+        let [d0, d1, d2] = [d0, d1, d2].map(|d| d.components[i]);
+
+        // Up to a constant factor, the derivative along the axis is the
+        // quadratic `a * t^2 + b * t + c`.
+        let a = d0 - d1 * 2. + d2;
+        let b = (d1 - d0) * 2.;
+        let c = d0;
+
+        let roots = if a.abs() < Scalar::from(1e-12) {
+            if b == Scalar::ZERO {
+                Vec::new()
+            } else {
+                vec![-c / b]
+            }
+        } else {
+            let discriminant = b * b - a * c * 4.;
+            if discriminant < Scalar::ZERO {
+                Vec::new()
+            } else {
+                let root = Scalar::from(discriminant.into_f64().sqrt());
+                vec![(-b - root) / (a * 2.), (-b + root) / (a * 2.)]
+            }
+        };
+
+        extrema.extend(roots.into_iter().filter(|t| *t >= min && *t <= max));
+    }
+
+    extrema
+}
+
+fn aabb_from_points<const D: usize>(
+    points: impl IntoIterator<Item = Point<D>>,
+) -> Option<Aabb<D>> {
+    let mut points = points.into_iter();
+    let first = points.next()?;
+
+    let aabb = points.fold(
+        Aabb {
+            min: first,
+            max: first,
+        },
+        |mut aabb, point| {
+            for i in 0..D {
+                let c = point.coords.components[i];
+
+                aabb.min.coords.components[i] =
+                    aabb.min.coords.components[i].min(c);
+                aabb.max.coords.components[i] =
+                    aabb.max.coords.components[i].max(c);
+            }
+
+            aabb
+        },
+    );
+
+    Some(aabb)
+}
+
+fn merge<const D: usize>(
+    aabbs: impl IntoIterator<Item = Option<Aabb<D>>>,
+) -> Option<Aabb<D>> {
+    aabb_from_points(
+        aabbs
+            .into_iter()
+            .flatten()
+            .flat_map(|aabb| [aabb.min, aabb.max]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use fj_math::{Aabb, Point, Transform};
+
+    use crate::{
+        algorithms::sweep::Sweep,
+        geometry::curve::Curve,
+        objects::{
+            Cycle, Face, GlobalEdge, HalfEdge, Instance, Shell, Solid, Vertex,
+        },
+        operations::{BuildHalfEdge, BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::BoundingVolume;
+
+    #[test]
+    fn cylinder() {
+        let mut services = Services::new();
+
+        let half_edge = HalfEdge::circle(1., &mut services.objects)
+            .insert(&mut services.objects);
+        let exterior = Cycle::new([half_edge]).insert(&mut services.objects);
+        let face =
+            Face::new(services.objects.surfaces.xy_plane(), exterior, [], None)
+                .insert(&mut services.objects);
+
+        let expected = Aabb {
+            min: Point::from([-1., -1., 0.]),
+            max: Point::from([1., 1., 0.]),
+        };
+        assert_aabb_eq(face.aabb(), expected);
+
+        let shell = face.sweep([0., 0., 2.], &mut services.objects).unwrap();
+
+        let expected = Aabb {
+            min: Point::from([-1., -1., 0.]),
+            max: Point::from([1., 1., 2.]),
+        };
+        assert_aabb_eq(shell.aabb(), expected);
+    }
+
+    #[test]
+    fn arc() {
+        let curve = Curve::circle_from_radius(1.);
+        let boundary = [0., FRAC_PI_2 * 3.].map(|t| Point::from([t]));
+
+        let expected = Aabb {
+            min: Point::from([-1., -1.]),
+            max: Point::from([1., 1.]),
+        };
+        assert_aabb_eq((curve, boundary).aabb(), expected);
+
+        let boundary = [0., FRAC_PI_2].map(|t| Point::from([t]));
+        let expected = Aabb {
+            min: Point::from([0., 0.]),
+            max: Point::from([1., 1.]),
+        };
+        assert_aabb_eq((curve, boundary).aabb(), expected);
+    }
+
+    #[test]
+    fn edge_in_global_space() {
+        let mut services = Services::new();
+
+        let (curve, boundary) = Curve::line_from_points([[0., 1.], [2., 3.]]);
+        let half_edge = HalfEdge::new(
+            curve,
+            boundary,
+            Vertex::new().insert(&mut services.objects),
+            GlobalEdge::new().insert(&mut services.objects),
+        );
+        let surface = services.objects.surfaces.xz_plane().geometry();
+
+        let expected = Aabb {
+            min: Point::from([0., 0., 1.]),
+            max: Point::from([2., 0., 3.]),
+        };
+        assert_aabb_eq((&half_edge, &surface).aabb(), expected);
+    }
+
+    #[test]
+    fn instances() {
+        let mut services = Services::new();
+
+        let shell = Shell::box_from_corners(
+            [[0., 0., 0.], [1., 2., 1.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let part = Solid::new([shell]).insert(&mut services.objects);
+
+        let instances = [
+            Transform::translation([3., 0., 0.]),
+            Transform::rotation([0., 0., std::f64::consts::FRAC_PI_2]),
+        ]
+        .map(|transform| Instance::new(part.clone(), transform));
+        let assembly = Solid::new([]).with_instances(instances);
+
+        let expected = Aabb {
+            min: Point::from([-2., 0., 0.]),
+            max: Point::from([4., 2., 1.]),
+        };
+        assert_aabb_eq(assembly.aabb(), expected);
+    }
+
+    fn assert_aabb_eq<const D: usize>(
+        aabb: Option<Aabb<D>>,
+        expected: Aabb<D>,
+    ) {
+        let aabb = aabb.expect("Expected non-empty shape");
+
+        for (a, b) in [(aabb.min, expected.min), (aabb.max, expected.max)] {
+            assert!((a - b).magnitude().into_f64() < 1e-12, "{a:?} != {b:?}");
+        }
+    }
+}
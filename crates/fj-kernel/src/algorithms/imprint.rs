@@ -0,0 +1,544 @@
+//! Imprint the edges of one shape onto the faces of another
+//!
+//! See [`Imprint`].
+
+use fj_math::{Bezier, Ellipse, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{
+        curve::{Curve, GlobalPath},
+        surface::SurfaceGeometry,
+    },
+    objects::{
+        BehindHandle, Cycle, Face, HalfEdge, Object, Objects, Provenance,
+        Shell, Solid,
+    },
+    operations::{BuildHalfEdge, Insert},
+    services::Service,
+    storage::Handle,
+};
+
+use super::{
+    intersect::{
+        face_point::FacePointIntersection, FaceFaceIntersection, Intersect,
+    },
+    reverse::Reverse,
+    transform::TransformObject,
+};
+
+/// Imprint the edges of a tool onto a shape
+///
+/// The tool is expected to touch the shape, like two parts of an assembly
+/// that rest on each other. Each face of the tool that lies within the plane
+/// of a face of the shape is imprinted onto it: Every cycle of the tool face
+/// that the face of the shape fully contains, splits it into a face within the
+/// cycle and a face with a new hole. No material is removed, and the shape
+/// doesn't change geometrically.
+///
+/// Projected cycles that would intersect the boundary of a face are ignored.
+/// If a face of the tool crosses a face of the shape, instead of lying within
+/// its plane, the tool overlaps the shape, and an error is returned. See
+/// [`ImprintError`].
+///
+/// # Implementation Note
+///
+/// Only planar faces of the shape are imprinted onto, and checked for contact
+/// with the tool. Circles are imprinted only where their projection is still
+/// a circle.
+///
+/// Whether a projected cycle is contained within a face, and whether a curved
+/// face of the tool crosses a face of the shape, is checked by sampling points
+/// along their edges. This may not detect an edge that barely leaves a face.
+pub trait Imprint: Sized {
+    /// Imprint the edges of `tool` onto the shape
+    fn imprint(
+        self,
+        tool: &impl ImprintTool,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self, ImprintError>;
+}
+
+impl Imprint for Handle<Shell> {
+    fn imprint(
+        self,
+        tool: &impl ImprintTool,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self, ImprintError> {
+        let provenance =
+            Provenance::new("imprint", [self.clone().into(), tool.object()]);
+
+        objects.with_provenance(provenance, |objects| {
+            let tool_faces = tool.tool_faces(objects);
+
+            let mut faces = Vec::new();
+            for face in self.faces() {
+                faces.extend(imprint_face(face, &tool_faces, objects)?);
+            }
+
+            Ok(Shell::new(faces).insert(objects))
+        })
+    }
+}
+
+impl Imprint for Handle<Solid> {
+    fn imprint(
+        self,
+        tool: &impl ImprintTool,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self, ImprintError> {
+        let shells = self
+            .shells()
+            .map(|shell| shell.clone().imprint(tool, objects))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Instances refer to solids that are shared with other places, so they
+        // are left as they are.
+        Ok(Solid::new(shells)
+            .with_instances(self.instances().cloned())
+            .insert(objects))
+    }
+}
+
+/// A shape whose edges can be imprinted onto another
+///
+/// See [`Imprint`].
+pub trait ImprintTool {
+    /// Access the tool as an object, to record it in the provenance
+    fn object(&self) -> Object<BehindHandle>;
+
+    /// Access the faces of the tool
+    fn tool_faces(&self, objects: &mut Service<Objects>) -> Vec<Handle<Face>>;
+}
+
+impl ImprintTool for Handle<Face> {
+    fn object(&self) -> Object<BehindHandle> {
+        self.clone().into()
+    }
+
+    fn tool_faces(&self, _: &mut Service<Objects>) -> Vec<Handle<Face>> {
+        vec![self.clone()]
+    }
+}
+
+impl ImprintTool for Handle<Shell> {
+    fn object(&self) -> Object<BehindHandle> {
+        self.clone().into()
+    }
+
+    fn tool_faces(&self, _: &mut Service<Objects>) -> Vec<Handle<Face>> {
+        self.faces().into_iter().cloned().collect()
+    }
+}
+
+impl ImprintTool for Handle<Solid> {
+    fn object(&self) -> Object<BehindHandle> {
+        self.clone().into()
+    }
+
+    fn tool_faces(&self, objects: &mut Service<Objects>) -> Vec<Handle<Face>> {
+        let mut faces = self
+            .shells()
+            .flat_map(|shell| shell.faces())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // The faces of instances are only available in the coordinates of the
+        // referenced solid.
+        for instance in self.instances() {
+            let solid = instance
+                .solid()
+                .clone()
+                .transform(instance.transform(), objects);
+            faces.extend(solid.tool_faces(objects));
+        }
+
+        faces
+    }
+}
+
+/// Error imprinting a tool onto a shape
+///
+/// Returned by [`Imprint::imprint`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ImprintError {
+    /// A face of the tool crosses a face of the shape
+    ///
+    /// Only faces of the tool that lie within the plane of a face of the shape
+    /// can be imprinted onto it. A face that crosses it means that the tool
+    /// overlaps the shape.
+    #[error(
+        "Face of tool crosses face of shape, instead of lying within its \
+        plane: {point:?}"
+    )]
+    NonCoplanarContact {
+        /// A point at which the faces cross
+        point: Point<3>,
+    },
+}
+
+fn imprint_face(
+    face: &Handle<Face>,
+    tool_faces: &[Handle<Face>],
+    objects: &mut Service<Objects>,
+) -> Result<Vec<Handle<Face>>, ImprintError> {
+    let target = face.surface().geometry();
+
+    let Some(target_normal) = normal(&target) else {
+        return Ok(vec![face.clone()]);
+    };
+
+    let mut faces = vec![face.clone()];
+
+    for tool in tool_faces {
+        let tool_surface = tool.surface().geometry();
+
+        if !is_coplanar(&target, target_normal, tool) {
+            if let Some(point) = crossing(face, target_normal, tool) {
+                return Err(ImprintError::NonCoplanarContact { point });
+            }
+
+            continue;
+        }
+
+        for cycle in tool.all_cycles() {
+            let Some(curves) = project_cycle(cycle, &tool_surface, &target)
+            else {
+                continue;
+            };
+
+            // Later cycles might be nested within earlier ones, so they need
+            // to be imprinted onto the faces that result from that.
+            faces = faces
+                .into_iter()
+                .flat_map(|face| match split(&face, &curves, objects) {
+                    Some(split) => split.to_vec(),
+                    None => vec![face],
+                })
+                .collect();
+        }
+    }
+
+    Ok(faces)
+}
+
+/// Determine whether a tool face lies within the plane of a surface
+fn is_coplanar(
+    target: &SurfaceGeometry,
+    target_normal: Vector<3>,
+    tool: &Face,
+) -> bool {
+    let Some(tool_normal) = normal(&tool.surface().geometry()) else {
+        return false;
+    };
+
+    let distance = (tool.surface().geometry().u.origin() - target.u.origin())
+        .dot(&target_normal);
+
+    let epsilon = Scalar::from(EPSILON);
+    tool_normal.cross(&target_normal).magnitude() < epsilon
+        && distance.abs() < epsilon
+}
+
+/// Find a point at which a tool face crosses a planar face
+///
+/// Faces that only touch the plane of `face` from one side don't cross it.
+fn crossing(face: &Face, normal: Vector<3>, tool: &Face) -> Option<Point<3>> {
+    let target = face.surface().geometry();
+    let tool_surface = tool.surface().geometry();
+
+    let distance =
+        |point: Point<3>| (point - target.u.origin()).dot(&normal).into_f64();
+
+    // Points along the boundary of the tool face, per cycle.
+    let cycles = tool
+        .all_cycles()
+        .map(|cycle| {
+            cycle
+                .half_edges()
+                .flat_map(|half_edge| {
+                    let curve = half_edge.curve();
+                    let [start, end] = half_edge.boundary();
+
+                    (0..SAMPLES_PER_EDGE).map(move |i| {
+                        let t = start.t
+                            + (end.t - start.t)
+                                * (i as f64 / SAMPLES_PER_EDGE as f64);
+                        curve.point_from_path_coords([t])
+                    })
+                })
+                .map(|point| tool_surface.point_from_surface_coords(point))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let distances = cycles
+        .iter()
+        .flatten()
+        .map(|&point| distance(point))
+        .collect::<Vec<_>>();
+    let is_above = distances.iter().any(|&distance| distance > EPSILON);
+    let is_below = distances.iter().any(|&distance| distance < -EPSILON);
+    if !(is_above && is_below) {
+        return None;
+    }
+
+    if normal(&tool_surface).is_some() {
+        // Both faces are planar, and the intersection between them is known
+        // exactly.
+        let intersection = FaceFaceIntersection::compute([face, tool])?;
+        let [curve, _] = intersection.intersection_curves;
+        let interval = intersection.intersection_intervals.intervals.first()?;
+
+        let t = (interval.start.t + interval.end.t) / 2.;
+        let point = curve.point_from_path_coords([t]);
+        return Some(target.point_from_surface_coords(point));
+    }
+
+    // Where the boundary of a curved tool face crosses the plane, it might
+    // cross the face.
+    cycles.iter().find_map(|points| {
+        let mut segments = points.iter().zip(points.iter().cycle().skip(1));
+        segments.find_map(|(&a, &b)| {
+            let [da, db] = [a, b].map(distance);
+            let is_crossing = (da > EPSILON && db < -EPSILON)
+                || (da < -EPSILON && db > EPSILON);
+            if !is_crossing {
+                return None;
+            }
+
+            let point = a + (b - a) * (da / (da - db));
+            let local = target.project_global_point(point);
+
+            (face, &local).intersect().map(|_| point)
+        })
+    })
+}
+
+/// Project the edges of a cycle from one planar surface onto another
+fn project_cycle(
+    cycle: &Cycle,
+    from: &SurfaceGeometry,
+    to: &SurfaceGeometry,
+) -> Option<Vec<(Curve, [Point<1>; 2])>> {
+    let point = |point: Point<2>| {
+        to.project_global_point(from.point_from_surface_coords(point))
+    };
+    let vector = |vector: Vector<2>| {
+        point(Point::origin() + vector) - point(Point::origin())
+    };
+
+    cycle
+        .half_edges()
+        .map(|half_edge| {
+            let curve = match half_edge.curve() {
+                Curve::Bezier(bezier) => Curve::Bezier(Bezier::new(
+                    bezier.control_points().map(point),
+                )),
+                Curve::Line(line) => {
+                    Curve::Line(Line::from_origin_and_direction(
+                        point(line.origin()),
+                        vector(line.direction()),
+                    ))
+                }
+                Curve::Circle(circle) => {
+                    let ellipse = Ellipse::new(
+                        point(circle.center()),
+                        vector(circle.a()),
+                        vector(circle.b()),
+                    );
+                    Curve::Circle(ellipse.to_circle()?)
+                }
+            };
+
+            Some((curve, half_edge.boundary()))
+        })
+        .collect()
+}
+
+/// Split a face along a cycle that lies within it
+///
+/// Returns `None`, if the cycle is not fully contained within the face.
+fn split(
+    face: &Handle<Face>,
+    curves: &[(Curve, [Point<1>; 2])],
+    objects: &mut Service<Objects>,
+) -> Option<[Handle<Face>; 2]> {
+    for &(curve, [start, end]) in curves {
+        for i in 0..SAMPLES_PER_EDGE {
+            let t = start.t
+                + (end.t - start.t) * (i as f64 / SAMPLES_PER_EDGE as f64);
+            let point = curve.point_from_path_coords([t]);
+
+            let intersection = (&**face, &point).intersect();
+            if intersection != Some(FacePointIntersection::PointIsInsideFace) {
+                return None;
+            }
+        }
+    }
+
+    let half_edges = curves
+        .iter()
+        .map(|&(curve, boundary)| {
+            HalfEdge::unjoined(curve, boundary, objects).insert(objects)
+        })
+        .collect::<Vec<_>>();
+    let cycle = Cycle::new(half_edges).insert(objects);
+
+    // The face within the cycle must have the same orientation as the
+    // original face. The face around it gets the cycle as a hole.
+    let (exterior, hole) = if cycle.winding() == face.exterior().winding() {
+        let hole = cycle.clone().reverse(objects);
+        (cycle, hole)
+    } else {
+        let exterior = cycle.clone().reverse(objects);
+        (exterior, cycle)
+    };
+
+    // Holes of the original face that are within the cycle end up in the face
+    // within the cycle.
+    let within = Face::new(face.surface().clone(), exterior.clone(), [], None);
+    let (interiors_within, interiors_around): (Vec<_>, Vec<_>) =
+        face.interiors().cloned().partition(|interior| {
+            let point = interior
+                .half_edges()
+                .next()
+                .expect("Invalid cycle: expected at least one half-edge")
+                .start_position();
+
+            (&within, &point).intersect()
+                == Some(FacePointIntersection::PointIsInsideFace)
+        });
+
+    let within = Face::new(
+        face.surface().clone(),
+        exterior,
+        interiors_within,
+        face.color(),
+    )
+    .insert(objects);
+    let around = Face::new(
+        face.surface().clone(),
+        face.exterior().clone(),
+        interiors_around.into_iter().chain([hole]),
+        face.color(),
+    )
+    .insert(objects);
+
+    Some([around, within])
+}
+
+/// Compute the normal of a surface, if it is a plane
+fn normal(surface: &SurfaceGeometry) -> Option<Vector<3>> {
+    let GlobalPath::Line(line) = surface.u else {
+        return None;
+    };
+
+    Some(line.direction().cross(&surface.v).normalize())
+}
+
+const SAMPLES_PER_EDGE: usize = 16;
+
+const EPSILON: f64 = 1e-9;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        builder::{CycleBuilder, FaceBuilder},
+        objects::Shell,
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::{Imprint, ImprintError};
+
+    #[test]
+    fn imprint_onto_coplanar_face() {
+        let mut services = Services::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let face_abc = tetrahedron.face_abc.clone();
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+
+        let tool = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[0.1, 0.1], [0.3, 0.1], [0.1, 0.3]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+
+        let shell = shell.imprint(&tool, &mut services.objects).unwrap();
+
+        let faces_abc = shell
+            .faces()
+            .into_iter()
+            .filter(|face| face.surface().id() == face_abc.surface().id())
+            .collect::<Vec<_>>();
+        assert_eq!(faces_abc.len(), 2);
+
+        let [with_hole, without_hole] = [1, 0].map(|num_interiors| {
+            faces_abc
+                .iter()
+                .filter(|face| face.interiors().count() == num_interiors)
+                .count()
+        });
+        assert_eq!(with_hole, 1);
+        assert_eq!(without_hole, 1);
+
+        services.drop_and_validate().unwrap();
+    }
+
+    #[test]
+    fn imprint_shell_resting_on_shell() {
+        let mut services = Services::new();
+
+        let base = Shell::box_from_corners(
+            [[0., 0., 0.], [4., 4., 1.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let tool = Shell::box_from_corners(
+            [[1., 1., 1.], [2., 2., 2.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+
+        let base = base.imprint(&tool, &mut services.objects).unwrap();
+
+        // Only the top face is split, into the contact area and the rest.
+        assert_eq!(base.faces().into_iter().count(), 7);
+        let with_hole = base
+            .faces()
+            .into_iter()
+            .filter(|face| face.interiors().count() == 1)
+            .count();
+        assert_eq!(with_hole, 1);
+
+        services.drop_and_validate().unwrap();
+    }
+
+    #[test]
+    fn imprint_overlapping_shell() {
+        let mut services = Services::new();
+
+        let base = Shell::box_from_corners(
+            [[0., 0., 0.], [4., 4., 1.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let tool = Shell::box_from_corners(
+            [[1., 1., 0.5], [2., 2., 2.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+
+        let result = base.imprint(&tool, &mut services.objects);
+        assert!(matches!(
+            result,
+            Err(ImprintError::NonCoplanarContact { .. })
+        ));
+    }
+}
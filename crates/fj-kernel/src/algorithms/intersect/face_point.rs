@@ -23,90 +23,66 @@ impl Intersect for (&Face, &Point<2>) {
         let mut num_hits = 0;
 
         for cycle in face.all_cycles() {
-            // We need to properly detect the ray passing the boundary at the
-            // "seam" of the polygon, i.e. the vertex between the last and the
-            // first segment. The logic in the loop properly takes care of that,
-            // as long as we initialize the `previous_hit` variable with the
-            // result of the last segment.
-            let mut previous_hit = cycle
-                .half_edges()
-                .last()
-                .cloned()
-                .and_then(|edge| (&ray, &edge).intersect());
-
             for (half_edge, next_half_edge) in
                 cycle.half_edges().circular_tuple_windows::<(_, _)>()
             {
                 let hit = (&ray, half_edge).intersect();
 
-                let count_hit = match (hit, previous_hit) {
-                    (
-                        Some(RaySegmentIntersection::RayStartsOnSegment),
-                        _,
-                    ) => {
+                let count_hit = match hit {
+                    Some(RaySegmentIntersection::RayStartsOnSegment) => {
                         // If the ray starts on the boundary of the face,
                         // there's nothing to else check.
                         return Some(FacePointIntersection::PointIsOnEdge(
-                            half_edge.clone()
+                            half_edge.clone(),
                         ));
                     }
-                    (Some(RaySegmentIntersection::RayStartsOnOnFirstVertex), _) => {
+                    Some(RaySegmentIntersection::RayStartsOnOnFirstVertex) => {
                         let vertex = half_edge.start_position();
-                        return Some(
-                            FacePointIntersection::PointIsOnVertex(vertex)
-                        );
+                        return Some(FacePointIntersection::PointIsOnVertex(
+                            vertex,
+                        ));
                     }
-                    (Some(RaySegmentIntersection::RayStartsOnSecondVertex), _) => {
+                    Some(RaySegmentIntersection::RayStartsOnSecondVertex) => {
                         let vertex = next_half_edge.start_position();
-                        return Some(
-                            FacePointIntersection::PointIsOnVertex(vertex)
-                        );
+                        return Some(FacePointIntersection::PointIsOnVertex(
+                            vertex,
+                        ));
                     }
-                    (Some(RaySegmentIntersection::RayHitsSegment), _) => {
+                    Some(RaySegmentIntersection::RayHitsSegment) => {
                         // We're hitting a segment right-on. Clear case.
                         true
                     }
-                    (
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                    )
-                    | (
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                    ) => {
-                        // If we're hitting a vertex, only count it if we've hit
-                        // the other kind of vertex right before.
+                    Some(RaySegmentIntersection::RayHitsUpperVertex) => {
+                        // A vertex that the ray passes through counts as being
+                        // above the ray. The edge then goes from below the ray
+                        // to above it, which is a crossing.
                         //
-                        // That means, we're passing through the polygon
-                        // boundary at where two edges touch. Depending on the
-                        // order in which edges are checked, we're seeing this
-                        // as a hit to one edge's lower/upper vertex, then the
-                        // other edge's opposite vertex.
-                        //
-                        // If we're seeing two of the same vertices in a row,
-                        // we're not actually passing through the polygon
-                        // boundary. Then we're just touching a vertex without
-                        // passing through anything.
+                        // The other edge that meets in the vertex counts it
+                        // too. If that edge goes up from the vertex, it
+                        // reports a hit to its lower vertex, which doesn't
+                        // count. Then the boundary passes through the ray
+                        // once. If it goes down again, it reports another hit
+                        // to its upper vertex. Then the two hits cancel out,
+                        // and the boundary just touches the ray.
                         true
                     }
-                    (Some(RaySegmentIntersection::RayHitsSegmentAndAreParallel), _) => {
-                        // A parallel edge must be completely ignored. Its
-                        // presence won't change anything, so we can treat it as
-                        // if it wasn't there, and its neighbors were connected
-                        // to each other.
-                        continue;
+                    Some(RaySegmentIntersection::RayHitsLowerVertex) => {
+                        // See above. The edge stays above the ray.
+                        false
                     }
-                    _ => {
-                        // Any other case is not a valid hit.
+                    Some(
+                        RaySegmentIntersection::RayHitsSegmentAndAreParallel,
+                    ) => {
+                        // Both vertices of a parallel edge count as being
+                        // above the ray, so it doesn't pass through it.
                         false
                     }
+                    None => false,
                 };
 
                 if count_hit {
                     num_hits += 1;
                 }
-
-                previous_hit = hit;
             }
         }
 
@@ -139,6 +115,8 @@ mod tests {
     use crate::{
         algorithms::intersect::{face_point::FacePointIntersection, Intersect},
         builder::{CycleBuilder, FaceBuilder},
+        objects::{Cycle, Face, HalfEdge},
+        operations::{BuildHalfEdge, Insert},
         services::Services,
     };
 
@@ -305,4 +283,70 @@ mod tests {
             Some(FacePointIntersection::PointIsOnVertex(vertex))
         );
     }
+
+    #[test]
+    fn point_in_face_bounded_by_bezier_curve() {
+        let mut services = Services::new();
+
+        let line = HalfEdge::line_segment(
+            [[0., 0.], [3., 0.]],
+            None,
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let bezier = HalfEdge::bezier(
+            [[3., 0.], [2., 2.], [1., 2.], [0., 0.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let face = Face::new(
+            services.objects.surfaces.xy_plane(),
+            Cycle::new([line, bezier]).insert(&mut services.objects),
+            [],
+            None,
+        );
+
+        // The curve reaches a height of 1.5 at its middle.
+        let inside = Point::from([1.5, 1.]);
+        let above = Point::from([1.5, 1.6]);
+
+        // A ray from here passes the curve twice.
+        let left = Point::from([0.2, 1.]);
+
+        assert_eq!(
+            (&face, &inside).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+        assert_eq!((&face, &above).intersect(), None);
+        assert_eq!((&face, &left).intersect(), None);
+    }
+
+    #[test]
+    fn point_in_face_bounded_by_circle() {
+        let mut services = Services::new();
+
+        let circle = HalfEdge::circle(1., &mut services.objects)
+            .insert(&mut services.objects);
+        let face = Face::new(
+            services.objects.surfaces.xy_plane(),
+            Cycle::new([circle]).insert(&mut services.objects),
+            [],
+            None,
+        );
+
+        // The ray from the center passes the circle where it starts and ends.
+        let center = Point::from([0., 0.]);
+        let inside = Point::from([0.5, 0.5]);
+        let outside = Point::from([-2., 0.5]);
+
+        assert_eq!(
+            (&face, &center).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+        assert_eq!(
+            (&face, &inside).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+        assert_eq!((&face, &outside).intersect(), None);
+    }
 }
@@ -11,7 +11,7 @@ mod face_face;
 mod line_segment;
 mod surface_surface;
 
-use fj_math::{Point, Vector};
+use fj_math::{Point, Scalar, Vector};
 
 pub use self::{
     curve_edge::CurveEdgeIntersection,
@@ -64,3 +64,25 @@ where
         }
     }
 }
+
+/// Find where a predicate changes its result, by bisection
+///
+/// The predicate must return different results for `a` and `b`, and change its
+/// result only once between them. Returns the argument closest to the change,
+/// for which the predicate returns `true`.
+fn bisect(a: Scalar, b: Scalar, predicate: impl Fn(Scalar) -> bool) -> Scalar {
+    let [mut outside, mut inside] = if predicate(a) { [b, a] } else { [a, b] };
+
+    loop {
+        let mid = outside + (inside - outside) / 2.;
+        if mid == outside || mid == inside {
+            break inside;
+        }
+
+        if predicate(mid) {
+            inside = mid;
+        } else {
+            outside = mid;
+        }
+    }
+}
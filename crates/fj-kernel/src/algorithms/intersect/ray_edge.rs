@@ -1,9 +1,13 @@
 //! Intersection between a ray and an edge in 2D
 
-use fj_math::Segment;
+use fj_math::{Point, Scalar, Segment};
+use itertools::Itertools;
 
 use crate::{
-    algorithms::intersect::{HorizontalRayToTheRight, Intersect},
+    algorithms::{
+        bounding_volume::{curve_extrema, BoundingVolume},
+        intersect::{bisect, HorizontalRayToTheRight, Intersect},
+    },
     geometry::curve::Curve,
     objects::HalfEdge,
     storage::Handle,
@@ -17,10 +21,22 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Handle<HalfEdge>) {
     fn intersect(self) -> Option<Self::Intersection> {
         let (ray, edge) = self;
 
+        // The ray can only hit the edge, if it passes through the edge's
+        // bounding box. This is cheap to check, and also rules out curves
+        // that the checks below don't support, if they are out of reach.
+        let aabb = edge.aabb()?;
+        let origin = ray.origin;
+        if origin.u > aabb.max.u
+            || origin.v < aabb.min.v
+            || origin.v > aabb.max.v
+        {
+            return None;
+        }
+
         let line = match edge.curve() {
             Curve::Line(line) => line,
-            Curve::Circle(_) => {
-                todo!("Casting rays against circles is not supported yet")
+            curve @ (Curve::Bezier(_) | Curve::Circle(_)) => {
+                return intersect_curve(ray, curve, edge.boundary());
             }
         };
 
@@ -32,3 +48,75 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Handle<HalfEdge>) {
         (ray, &segment).intersect()
     }
 }
+
+/// Intersect a ray with an edge along a curve
+///
+/// Unlike a line segment, a curved edge can pass the ray multiple times, so
+/// the result can't refer to a single crossing. Points at the same height as
+/// the ray count as being above it. If the edge then passes the ray an odd
+/// number of times, the result is [`RaySegmentIntersection::RayHitsSegment`].
+/// Otherwise, the ray is not considered to hit the edge.
+///
+/// Applying the same rule to the vertices of line segments, by counting hits
+/// of their upper vertex but not their lower one, leads to a consistent count.
+fn intersect_curve(
+    ray: &HorizontalRayToTheRight<2>,
+    curve: Curve,
+    boundary: [Point<1>; 2],
+) -> Option<RaySegmentIntersection> {
+    let origin = ray.origin;
+
+    let point = |t: Scalar| {
+        // Reducing angles to a single turn makes the end of a full circle
+        // coincide exactly with its start.
+        let t = match curve {
+            Curve::Circle(_) => t - (t / Scalar::TAU).floor() * Scalar::TAU,
+            Curve::Bezier(_) | Curve::Line(_) => t,
+        };
+        curve.point_from_path_coords([t])
+    };
+    let is_above = |t: Scalar| point(t).v >= origin.v;
+
+    // Between its extrema, the height of the curve is monotonic, so each of
+    // those pieces passes the ray at most once.
+    let [start, end] = boundary.map(|point| point.t);
+    let mut pieces = curve_extrema(&curve, [start.min(end), start.max(end)]);
+    pieces.extend([start, end]);
+    pieces.sort();
+    pieces.dedup();
+    if start > end {
+        pieces.reverse();
+    }
+
+    for t in [start, end] {
+        if point(t) == origin {
+            return Some(if t == start {
+                RaySegmentIntersection::RayStartsOnOnFirstVertex
+            } else {
+                RaySegmentIntersection::RayStartsOnSecondVertex
+            });
+        }
+    }
+
+    let mut num_hits = 0;
+
+    for (a, b) in pieces.into_iter().tuple_windows() {
+        if is_above(a) == is_above(b) {
+            continue;
+        }
+
+        let hit = point(bisect(a, b, is_above));
+        if hit == origin {
+            return Some(RaySegmentIntersection::RayStartsOnSegment);
+        }
+        if hit.u > origin.u {
+            num_hits += 1;
+        }
+    }
+
+    if num_hits % 2 == 1 {
+        Some(RaySegmentIntersection::RayHitsSegment)
+    } else {
+        None
+    }
+}
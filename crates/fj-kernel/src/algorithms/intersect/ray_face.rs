@@ -1,15 +1,19 @@
 //! Intersection between a ray and a face, in 3D
 
-use fj_math::{Plane, Point, Scalar};
+use fj_math::{predicates, Bezier, Plane, Point, Scalar, Sign, Vector};
+use itertools::Itertools;
 
 use crate::{
-    algorithms::intersect::face_point::FacePointIntersection,
+    algorithms::{
+        bounding_volume::{bezier_extrema, sinusoid_extrema, BoundingVolume},
+        intersect::face_point::FacePointIntersection,
+    },
     geometry::curve::GlobalPath,
     objects::{Face, HalfEdge},
     storage::Handle,
 };
 
-use super::{HorizontalRayToTheRight, Intersect};
+use super::{bisect, HorizontalRayToTheRight, Intersect};
 
 impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
     type Intersection = RayFaceIntersection;
@@ -18,9 +22,14 @@ impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
         let (ray, face) = self;
 
         let plane = match face.surface().geometry().u {
-            GlobalPath::Circle(_) => todo!(
-                "Casting a ray against a swept circle is not supported yet"
-            ),
+            GlobalPath::Bezier(_)
+            | GlobalPath::Circle(_)
+            | GlobalPath::Ellipse(_) => {
+                return curved_face_hits(ray, face)
+                    .into_iter()
+                    .min_by_key(|(t, _)| *t)
+                    .map(|(_, intersection)| intersection);
+            }
             GlobalPath::Line(line) => Plane::from_parametric(
                 line.origin(),
                 line.direction(),
@@ -34,12 +43,7 @@ impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
             let c = plane.origin() + plane.v();
             let d = ray.origin;
 
-            let [a, b, c, d] = [a, b, c, d]
-                .map(|point| [point.x, point.y, point.z])
-                .map(|point| point.map(Scalar::into_f64))
-                .map(|[x, y, z]| robust::Coord3D { x, y, z });
-
-            if robust::orient3d(a, b, c, d) == 0. {
+            if predicates::orient3d(a, b, c, d) == Sign::Zero {
                 return Some(RayFaceIntersection::RayHitsFaceAndAreParallel);
             } else {
                 return None;
@@ -107,23 +111,165 @@ impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
             return None;
         }
 
-        let point = Point::from([u, v]);
-        let intersection = match (face, &point).intersect()? {
-            FacePointIntersection::PointIsInsideFace => {
-                RayFaceIntersection::RayHitsFace
-            }
-            FacePointIntersection::PointIsOnEdge(edge) => {
-                RayFaceIntersection::RayHitsEdge(edge)
-            }
-            FacePointIntersection::PointIsOnVertex(vertex) => {
-                RayFaceIntersection::RayHitsVertex(vertex)
-            }
+        intersect_face_at(face, [u, v])
+    }
+}
+
+/// Compute all hits between a ray and a face
+///
+/// The [`Intersect`] implementation only returns the hit that is closest to the
+/// origin of the ray. A ray can hit a face on a curved surface more than once,
+/// and this returns all of those hits. A face on a planar surface is hit once
+/// at most.
+pub fn ray_face_hits(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+) -> Vec<RayFaceIntersection> {
+    match face.surface().geometry().u {
+        GlobalPath::Bezier(_)
+        | GlobalPath::Circle(_)
+        | GlobalPath::Ellipse(_) => curved_face_hits(ray, face)
+            .into_iter()
+            .map(|(_, intersection)| intersection)
+            .collect(),
+        GlobalPath::Line(_) => (ray, face).intersect().into_iter().collect(),
+    }
+}
+
+/// Intersect a ray with a face on a curved surface
+///
+/// A ray can hit a curved surface more than once. Returns all hits, each with
+/// its distance from the origin of the ray.
+fn curved_face_hits(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+) -> Vec<(Scalar, RayFaceIntersection)> {
+    let surface = face.surface().geometry();
+    let origin = ray.origin;
+    let direction = surface.v;
+
+    // The surface is made up of straight lines along `direction`, through each
+    // point of its u-axis. Seen along the ray, so in the yz-plane, the ray
+    // becomes a point, and each of those lines becomes a line, or a point too,
+    // if it's parallel to the ray.
+    if direction.y == Scalar::ZERO && direction.z == Scalar::ZERO {
+        let (_, distance) = surface.project_point(origin);
+        return if distance <= Scalar::from(PARALLEL_DISTANCE) {
+            vec![(Scalar::ZERO, RayFaceIntersection::RayHitsFaceAndAreParallel)]
+        } else {
+            Vec::new()
         };
+    }
+
+    // Otherwise, the ray hits the lines that pass through its origin in the
+    // yz-plane. This measures how far a line is from that, up to a factor.
+    let across =
+        |vector: Vector<3>| vector.y * direction.z - vector.z * direction.y;
+    let offset =
+        |u: Scalar| across(surface.u.point_from_path_coords([u]) - origin);
+
+    // Only the part of the surface that the face covers is relevant.
+    let Some(aabb) = face.exterior().aabb() else {
+        return Vec::new();
+    };
+    let [mut min, mut max] = [aabb.min.u, aabb.max.u];
+    let extrema = match surface.u {
+        GlobalPath::Bezier(bezier) => {
+            [min, max] = [min.max(Scalar::ZERO), max.min(Scalar::ONE)];
+            if min > max {
+                return Vec::new();
+            }
 
-        Some(intersection)
+            let bezier = Bezier::new(
+                bezier.control_points().map(|point| [across(point.coords)]),
+            );
+            bezier_extrema(&bezier, [min, max])
+        }
+        GlobalPath::Circle(circle) => sinusoid_extrema(
+            Vector::from([across(circle.a())]),
+            Vector::from([across(circle.b())]),
+            [min, max],
+        ),
+        GlobalPath::Ellipse(ellipse) => sinusoid_extrema(
+            Vector::from([across(ellipse.a())]),
+            Vector::from([across(ellipse.b())]),
+            [min, max],
+        ),
+        GlobalPath::Line(_) => {
+            unreachable!("Only called for curved surfaces")
+        }
+    };
+
+    // Between its extrema, the offset is monotonic, so each of those pieces
+    // contains at most one hit.
+    let mut pieces = extrema;
+    pieces.extend([min, max]);
+    pieces.sort();
+    pieces.dedup();
+
+    let is_positive = |u: Scalar| offset(u) >= Scalar::ZERO;
+    let mut hits = pieces
+        .iter()
+        .copied()
+        .filter(|&u| offset(u) == Scalar::ZERO)
+        .collect::<Vec<_>>();
+    for (&a, &b) in pieces.iter().tuple_windows() {
+        // Pieces that start or end at a hit have been covered above.
+        if offset(a) != Scalar::ZERO
+            && offset(b) != Scalar::ZERO
+            && is_positive(a) != is_positive(b)
+        {
+            hits.push(bisect(a, b, is_positive));
+        }
     }
+
+    hits.into_iter()
+        .filter_map(|u| {
+            let point = surface.u.point_from_path_coords([u]);
+            let v = if direction.z.abs() > direction.y.abs() {
+                (origin.z - point.z) / direction.z
+            } else {
+                (origin.y - point.y) / direction.y
+            };
+            let t = point.x + direction.x * v - origin.x;
+
+            if t < Scalar::ZERO {
+                // Ray points away from the surface here.
+                return None;
+            }
+
+            let intersection = intersect_face_at(face, [u, v])?;
+            Some((t, intersection))
+        })
+        .collect()
 }
 
+/// Classify a point on the surface of a face that the ray hits
+fn intersect_face_at(
+    face: &Face,
+    point: impl Into<Point<2>>,
+) -> Option<RayFaceIntersection> {
+    let point = point.into();
+
+    let intersection = match (face, &point).intersect()? {
+        FacePointIntersection::PointIsInsideFace => {
+            RayFaceIntersection::RayHitsFace
+        }
+        FacePointIntersection::PointIsOnEdge(edge) => {
+            RayFaceIntersection::RayHitsEdge(edge)
+        }
+        FacePointIntersection::PointIsOnVertex(vertex) => {
+            RayFaceIntersection::RayHitsVertex(vertex)
+        }
+    };
+
+    Some(intersection)
+}
+
+/// The distance below which a ray that is parallel to the lines of a curved
+/// surface is considered to lie on it
+const PARALLEL_DISTANCE: f64 = 1e-12;
+
 /// A hit between a ray and a face
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RayFaceIntersection {
@@ -142,17 +288,22 @@ pub enum RayFaceIntersection {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Point;
+    use std::f64::consts::PI;
+
+    use fj_math::{Ellipse, Point};
 
     use crate::{
         algorithms::{
             intersect::{
-                ray_face::RayFaceIntersection, HorizontalRayToTheRight,
-                Intersect,
+                ray_face::{ray_face_hits, RayFaceIntersection},
+                HorizontalRayToTheRight, Intersect,
             },
             transform::TransformObject,
         },
         builder::{CycleBuilder, FaceBuilder},
+        geometry::{curve::GlobalPath, surface::SurfaceGeometry},
+        objects::Surface,
+        operations::Insert,
         services::Services,
     };
 
@@ -298,4 +449,99 @@ mod tests {
 
         assert_eq!((&ray, &face).intersect(), None);
     }
+
+    #[test]
+    fn ray_hits_curved_face() {
+        let mut services = Services::new();
+
+        // Half of a cylinder, on the side of positive y.
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::circle_from_radius(1.),
+            v: [0., 0., 1.].into(),
+        })
+        .insert(&mut services.objects);
+        let face = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [[0., -1.], [PI, -1.], [PI, 1.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects);
+
+        // Rays from outside and from inside of the cylinder hit the face.
+        let outside = HorizontalRayToTheRight::from([-2., 0.5, 0.]);
+        let inside = HorizontalRayToTheRight::from([0., 0.5, 0.]);
+
+        // This ray passes the cylinder, where the face doesn't cover it.
+        let other_side = HorizontalRayToTheRight::from([-2., -0.5, 0.]);
+
+        assert_eq!(
+            (&outside, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+        assert_eq!(
+            (&inside, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+        assert_eq!((&other_side, &face).intersect(), None);
+    }
+
+    #[test]
+    fn ray_hits_face_on_elliptic_cylinder() {
+        let mut services = Services::new();
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::Ellipse(Ellipse::new(
+                [0., 0., 0.],
+                [2., 0., 0.],
+                [0., 1., 0.],
+            )),
+            v: [0., 0., 1.].into(),
+        })
+        .insert(&mut services.objects);
+        let face = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [[0., -1.], [PI, -1.], [PI, 1.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects);
+
+        let hits = HorizontalRayToTheRight::from([-3., 0.5, 0.]);
+        let misses = HorizontalRayToTheRight::from([-3., 0.5, 2.]);
+
+        assert_eq!(
+            (&hits, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+        assert_eq!((&misses, &face).intersect(), None);
+    }
+
+    #[test]
+    fn ray_hits_curved_face_twice() {
+        let mut services = Services::new();
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::circle_from_radius(1.),
+            v: [0., 0., 1.].into(),
+        })
+        .insert(&mut services.objects);
+        let face = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [[0., -1.], [PI, -1.], [PI, 1.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects);
+
+        // From outside, the ray enters and leaves the half-cylinder.
+        let outside = HorizontalRayToTheRight::from([-2., 0.5, 0.]);
+        let inside = HorizontalRayToTheRight::from([0., 0.5, 0.]);
+
+        assert_eq!(
+            ray_face_hits(&outside, &face),
+            vec![RayFaceIntersection::RayHitsFace; 2]
+        );
+        assert_eq!(
+            ray_face_hits(&inside, &face),
+            vec![RayFaceIntersection::RayHitsFace]
+        );
+    }
 }
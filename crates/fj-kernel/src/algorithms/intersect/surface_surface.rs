@@ -1,4 +1,4 @@
-use fj_math::{Line, Plane, Point, Scalar};
+use fj_math::{Interval, Line, Plane, Point, Scalar, Sign};
 
 use crate::{
     geometry::curve::{Curve, GlobalPath},
@@ -27,20 +27,28 @@ impl SurfaceSurfaceIntersection {
         let [(a_distance, a_normal), (b_distance, b_normal)] =
             planes.map(|plane| plane.constant_normal_form());
 
-        let direction = a_normal.cross(&b_normal);
-
-        let denom = direction.dot(&direction);
-        if denom == Scalar::ZERO {
-            // Comparing `denom` against zero looks fishy. It's probably better
-            // to compare it against an epsilon value, but I don't know how
-            // large that epsilon should be.
-            //
-            // I'll just leave it like that, until we had the opportunity to
-            // collect some experience with this code.
-            // - @hannobraun
+        // The planes are parallel, if the cross product of their normals is
+        // zero. Check that conservatively, so we don't compute a meaningless
+        // intersection for planes that might only be non-parallel due to
+        // floating-point error.
+        let [a, b] = [a_normal, b_normal]
+            .map(|normal| normal.components.map(Interval::from_scalar));
+        let cross = [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ];
+        let length_squared = cross
+            .into_iter()
+            .map(Interval::squared)
+            .fold(Interval::from_scalar(Scalar::ZERO), |sum, c| sum + c);
+        if length_squared.sign() != Some(Sign::Positive) {
             return None;
         }
 
+        let direction = a_normal.cross(&b_normal);
+        let denom = direction.dot(&direction);
+
         let origin = (b_normal * a_distance - a_normal * b_distance)
             .cross(&direction)
             / denom;
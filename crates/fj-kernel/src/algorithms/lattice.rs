@@ -0,0 +1,390 @@
+//! Lattice infill
+//!
+//! See [`infill`].
+
+use std::f64::consts::TAU;
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Aabb, Point, Scalar, Tolerance, Triangle, Vector};
+use parry3d_f64::query::PointQuery as _;
+
+use crate::objects::FaceSet;
+
+use super::{
+    analysis::interference::to_parry,
+    intersect::{
+        ray_face::{ray_face_hits, RayFaceIntersection},
+        HorizontalRayToTheRight,
+    },
+    triangulate::Triangulate,
+};
+
+/// A lattice that can fill the interior of a solid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lattice {
+    /// A grid of struts along the x, y, and z axes
+    Grid {
+        /// The distance between neighboring struts
+        cell_size: Scalar,
+
+        /// The thickness of the struts
+        thickness: Scalar,
+    },
+
+    /// A gyroid, a triply periodic minimal surface, thickened into a sheet
+    ///
+    /// Gyroids are popular as infill for 3D printing, as they are equally
+    /// strong in all directions and consist of only one connected region.
+    Gyroid {
+        /// The size of the cell after which the gyroid repeats
+        cell_size: Scalar,
+
+        /// The thickness of the sheet
+        thickness: Scalar,
+    },
+}
+
+impl Lattice {
+    /// Evaluate the implicit function that defines the lattice
+    ///
+    /// Returns a negative value for points within the lattice's material, and
+    /// a positive value outside of it. The magnitude approximates the distance
+    /// to the lattice's surface.
+    pub fn evaluate(&self, point: Point<3>) -> Scalar {
+        match *self {
+            Self::Grid {
+                cell_size,
+                thickness,
+            } => {
+                // Distance from the nearest grid line along each axis.
+                let [x, y, z] = point.coords.components.map(|c| {
+                    let c = c / cell_size;
+                    (c - c.round()).abs() * cell_size
+                });
+
+                let distance_to_strut = [(y, z), (x, z), (x, y)]
+                    .map(|(a, b)| Vector::from([a, b]).magnitude())
+                    .into_iter()
+                    .min()
+                    .expect("Array is not empty");
+
+                distance_to_strut - thickness / 2.
+            }
+            Self::Gyroid {
+                cell_size,
+                thickness,
+            } => {
+                let [x, y, z] =
+                    point.coords.components.map(|c| c / cell_size * TAU);
+
+                let [(sin_x, cos_x), (sin_y, cos_y), (sin_z, cos_z)] =
+                    [x, y, z].map(Scalar::sin_cos);
+
+                let gyroid = sin_x * cos_y + sin_y * cos_z + sin_z * cos_x;
+
+                // The gradient of the gyroid function is at most about 1.5
+                // per radian. Scale the value, so it's roughly a distance.
+                let distance = gyroid.abs() * cell_size / TAU / 1.5;
+                distance - thickness / 2.
+            }
+        }
+    }
+
+    fn cell_size(&self) -> Scalar {
+        match *self {
+            Self::Grid { cell_size, .. } | Self::Gyroid { cell_size, .. } => {
+                cell_size
+            }
+        }
+    }
+}
+
+/// Fill the solid bounded by the provided faces with a lattice
+///
+/// Returns a mesh of the lattice, clipped by the boundary of the solid. The
+/// lattice is sampled on a grid, with `resolution` samples along each axis of
+/// a lattice cell. The result is only as accurate as that sampling.
+///
+/// The returned mesh only contains the lattice. Combine it with a hollowed-out
+/// version of the solid, to create an infilled part.
+pub fn infill(
+    faces: &FaceSet,
+    lattice: &Lattice,
+    resolution: u32,
+    color: Color,
+) -> Mesh<Point<3>> {
+    let mut infill = Mesh::new();
+
+    let step = lattice.cell_size() / f64::from(resolution.max(1));
+
+    // The distance to the boundary is computed from a triangulation, which
+    // only needs to be accurate to a fraction of the sampling grid.
+    let tolerance = Tolerance::from_scalar(step / 4.)
+        .expect("Lattice cell size must be positive");
+    let mesh = (faces, tolerance).triangulate();
+
+    let Some(aabb) = bounding_box(&mesh) else {
+        return infill;
+    };
+    let parry = to_parry(&mesh);
+
+    // The lattice is clipped by intersecting it with the solid. Both are
+    // represented by a function that is negative inside, so the intersection
+    // is their maximum.
+    let field = |point: Point<3>| {
+        let distance =
+            Scalar::from(parry.distance_to_local_point(&point.to_na(), false));
+        let solid = if contains(faces, point, step) {
+            -distance
+        } else {
+            distance
+        };
+
+        Scalar::max(lattice.evaluate(point), solid)
+    };
+
+    let size = aabb.size();
+    let [nx, ny, nz] = size.components.map(|c| {
+        // One extra sample on each side, so the boundary of the solid is
+        // always between samples.
+        (c / step).ceil().into_f64() as usize + 2
+    });
+    let origin = aabb.min - Vector::from([step, step, step]) / 2.;
+
+    let position = |[i, j, k]: [usize; 3]| {
+        origin + Vector::from([i as f64, j as f64, k as f64]) * step
+    };
+
+    let mut values = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+    for k in 0..=nz {
+        for j in 0..=ny {
+            for i in 0..=nx {
+                values.push(field(position([i, j, k])));
+            }
+        }
+    }
+    let value = |[i, j, k]: [usize; 3]| {
+        values[i + j * (nx + 1) + k * (nx + 1) * (ny + 1)]
+    };
+
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                let corners = CUBE_CORNERS.map(|[di, dj, dk]| {
+                    let index = [i + di, j + dj, k + dk];
+                    (position(index), value(index))
+                });
+
+                for tetrahedron in CUBE_TETRAHEDRA {
+                    let tetrahedron = tetrahedron.map(|corner| corners[corner]);
+                    polygonize_tetrahedron(tetrahedron, color, &mut infill);
+                }
+            }
+        }
+    }
+
+    infill
+}
+
+fn bounding_box(mesh: &Mesh<Point<3>>) -> Option<Aabb<3>> {
+    mesh.vertices().next()?;
+    Some(Aabb::<3>::from_points(mesh.vertices()))
+}
+
+/// Determine whether a point is inside of the solid bounded by faces
+///
+/// Counts how often a ray from the point crosses the faces. If the ray hits an
+/// edge or a vertex, or lies within a face, that count is ambiguous. The ray is
+/// then moved by a small fraction of `step`, and the count is repeated.
+fn contains(faces: &FaceSet, point: Point<3>, step: Scalar) -> bool {
+    const ATTEMPTS: u32 = 8;
+
+    let nudge = Vector::from([0., 0.3718, 0.1593]) * step * 1e-3;
+
+    let mut crossings = 0;
+    for attempt in 0..ATTEMPTS {
+        let ray = HorizontalRayToTheRight {
+            origin: point + nudge * f64::from(attempt),
+        };
+
+        crossings = 0;
+        let mut is_ambiguous = false;
+
+        for face in faces {
+            for hit in ray_face_hits(&ray, face) {
+                match hit {
+                    RayFaceIntersection::RayHitsFace => crossings += 1,
+                    RayFaceIntersection::RayHitsFaceAndAreParallel
+                    | RayFaceIntersection::RayHitsEdge(_)
+                    | RayFaceIntersection::RayHitsVertex(_) => {
+                        is_ambiguous = true;
+                    }
+                }
+            }
+        }
+
+        if !is_ambiguous {
+            break;
+        }
+    }
+
+    crossings % 2 == 1
+}
+
+/// Add the part of the surface at the zero level that lies in a tetrahedron
+fn polygonize_tetrahedron(
+    corners: [(Point<3>, Scalar); 4],
+    color: Color,
+    mesh: &mut Mesh<Point<3>>,
+) {
+    let (inside, outside): (Vec<_>, Vec<_>) = corners
+        .into_iter()
+        .partition(|(_, value)| *value < Scalar::ZERO);
+
+    let crossing = |a: (Point<3>, Scalar), b: (Point<3>, Scalar)| {
+        let t = a.1 / (a.1 - b.1);
+        a.0 + (b.0 - a.0) * t
+    };
+
+    let polygon = match (inside.as_slice(), outside.as_slice()) {
+        ([a], [b, c, d]) | ([b, c, d], [a]) => {
+            vec![crossing(*a, *b), crossing(*a, *c), crossing(*a, *d)]
+        }
+        ([a, b], [c, d]) => vec![
+            crossing(*a, *c),
+            crossing(*a, *d),
+            crossing(*b, *d),
+            crossing(*b, *c),
+        ],
+        _ => return,
+    };
+
+    // Triangles must face away from the material.
+    let centroid = |points: &[(Point<3>, Scalar)]| {
+        points
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, (point, _)| {
+                sum + point.coords
+            })
+            / points.len() as f64
+    };
+    let outward = centroid(&outside) - centroid(&inside);
+
+    for i in 1..polygon.len() - 1 {
+        let mut points = [polygon[0], polygon[i], polygon[i + 1]];
+
+        let Ok(triangle) = Triangle::<3>::from_points(points) else {
+            // A degenerate triangle, where the surface goes through a corner.
+            continue;
+        };
+        if triangle.normal().dot(&outward) < Scalar::ZERO {
+            points.swap(1, 2);
+        }
+
+        mesh.push_triangle(points, color);
+    }
+}
+
+/// The corners of a cube, as offsets from its lowest corner
+///
+/// Indexed such, that the bits of the index correspond to the x, y, and z
+/// offsets.
+const CUBE_CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
+/// A decomposition of a cube into tetrahedra that share its main diagonal
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 3, 2, 7],
+    [0, 2, 6, 7],
+    [0, 6, 4, 7],
+    [0, 4, 5, 7],
+    [0, 5, 1, 7],
+];
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{objects::Shell, operations::BuildShell, services::Services};
+
+    use super::Lattice;
+
+    #[test]
+    fn infill_is_clipped_by_box() {
+        let mut services = Services::new();
+
+        let shell = Shell::box_from_corners(
+            [[0., 0., 0.], [1., 1., 1.]],
+            &mut services.objects,
+        );
+
+        let lattice = Lattice::Grid {
+            cell_size: Scalar::from(0.5),
+            thickness: Scalar::from(0.1),
+        };
+
+        let infill =
+            super::infill(shell.faces(), &lattice, 4, Color::default());
+        assert!(infill.triangles().next().is_some());
+
+        let epsilon = 1e-9;
+        for vertex in infill.vertices() {
+            for c in vertex.coords.components {
+                let c = c.into_f64();
+                assert!(c > -epsilon && c < 1. + epsilon);
+            }
+        }
+    }
+
+    #[test]
+    fn infill_is_clipped_by_cylinder() {
+        let mut services = Services::new();
+
+        let radius = 1.;
+        let height = 1.;
+        let shell = Shell::cylinder(radius, height, &mut services.objects);
+
+        let lattice = Lattice::Gyroid {
+            cell_size: Scalar::from(0.5),
+            thickness: Scalar::from(0.1),
+        };
+
+        let infill =
+            super::infill(shell.faces(), &lattice, 4, Color::default());
+        assert!(infill.triangles().next().is_some());
+
+        // The boundary of the cylinder is only sampled on a grid, so the
+        // lattice can end slightly outside of it. Not by more than a sample
+        // step, though.
+        let step = 0.5 / 4.;
+        for vertex in infill.vertices() {
+            let [x, y, z] = vertex.coords.components.map(Scalar::into_f64);
+
+            let distance_to_axis = Vector::from([x, y]).magnitude().into_f64();
+            assert!(distance_to_axis < radius + step);
+            assert!(z > -step && z < height + step);
+        }
+    }
+
+    #[test]
+    fn gyroid() {
+        let lattice = Lattice::Gyroid {
+            cell_size: Scalar::from(1.),
+            thickness: Scalar::from(0.1),
+        };
+
+        // The origin is on the gyroid surface, so it's within the sheet.
+        assert!(lattice.evaluate(Point::origin()) < Scalar::ZERO);
+        assert!(lattice.evaluate(Point::from([0.25, 0., 0.])) > Scalar::ZERO);
+    }
+}
@@ -3,9 +3,16 @@
 //! Algorithmic code is collected in this module, to keep other modules focused
 //! on their respective purpose.
 
+pub mod analysis;
 pub mod approx;
+pub mod bounding_volume;
+pub mod imprint;
 pub mod intersect;
+pub mod lattice;
+pub mod remove_faces;
 pub mod reverse;
+pub mod simplify;
 pub mod sweep;
+pub mod texture;
 pub mod transform;
 pub mod triangulate;
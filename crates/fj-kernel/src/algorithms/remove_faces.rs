@@ -0,0 +1,336 @@
+//! Remove faces from a shell, and heal the resulting opening
+//!
+//! See [`RemoveFaces`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar, Triangle};
+
+use crate::{
+    geometry::curve::Curve,
+    objects::{
+        Cycle, Face, GlobalEdge, HalfEdge, Objects, Provenance, Shell, Surface,
+    },
+    operations::{BuildHalfEdge, BuildSurface, Insert, UpdateHalfEdge},
+    services::Service,
+    storage::Handle,
+};
+
+/// Remove faces from a shape, and heal the opening that this leaves
+///
+/// This can be used to defeature a model, for example by removing the wall of
+/// a hole. The opening is healed in two ways:
+///
+/// - Holes in the remaining faces, that were only bordered by removed faces,
+///   are closed. The face is extended over the hole.
+/// - Any remaining openings are closed with a planar face, if they are bounded
+///   by coplanar line segments.
+///
+/// # Implementation Note
+///
+/// Healing by extending the neighboring faces and re-intersecting them is not
+/// supported yet. If an opening can't be healed in one of the ways above, an
+/// error is returned. See [`RemoveFacesError`].
+pub trait RemoveFaces: Sized {
+    /// Remove the provided faces, and heal the opening
+    fn remove_faces(
+        self,
+        faces: &[Handle<Face>],
+        objects: &mut Service<Objects>,
+    ) -> Result<Self, RemoveFacesError>;
+}
+
+impl RemoveFaces for Handle<Shell> {
+    fn remove_faces(
+        self,
+        faces: &[Handle<Face>],
+        objects: &mut Service<Objects>,
+    ) -> Result<Self, RemoveFacesError> {
+        let inputs = [self.clone().into()]
+            .into_iter()
+            .chain(faces.iter().map(|face| face.clone().into()));
+        let provenance = Provenance::new("remove faces", inputs);
+
+        objects.with_provenance(provenance, |objects| {
+            let is_removed = |face: &Handle<Face>| {
+                faces.iter().any(|removed| removed.id() == face.id())
+            };
+
+            let removed_edges = self
+                .faces()
+                .into_iter()
+                .filter(|face| is_removed(face))
+                .flat_map(|face| face.all_cycles())
+                .flat_map(|cycle| cycle.half_edges())
+                .map(|half_edge| half_edge.global_form().id())
+                .collect::<BTreeSet<_>>();
+
+            let mut remaining = Vec::new();
+            for face in self.faces() {
+                if is_removed(face) {
+                    continue;
+                }
+
+                let (interiors, closed): (Vec<_>, Vec<_>) =
+                    face.interiors().cloned().partition(|interior| {
+                        !interior.half_edges().all(|half_edge| {
+                            removed_edges
+                                .contains(&half_edge.global_form().id())
+                        })
+                    });
+
+                if closed.is_empty() {
+                    remaining.push(face.clone());
+                    continue;
+                }
+
+                let face = Face::new(
+                    face.surface().clone(),
+                    face.exterior().clone(),
+                    interiors,
+                    face.color(),
+                )
+                .insert(objects);
+                remaining.push(face);
+            }
+
+            let caps = cap_openings(&remaining, objects)?;
+
+            Ok(Shell::new(remaining.into_iter().chain(caps)).insert(objects))
+        })
+    }
+}
+
+/// Error removing faces from a shell
+///
+/// Returned by [`RemoveFaces::remove_faces`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum RemoveFacesError {
+    /// An opening is not bounded by coplanar line segments
+    ///
+    /// Healing it would require extending the neighboring faces and
+    /// re-intersecting them.
+    #[error(
+        "Opening is not bounded by coplanar line segments, and can't be \
+        healed: {points:?}"
+    )]
+    NonPlanarOpening {
+        /// The points at which the edges that bound the opening start
+        points: Vec<Point<3>>,
+    },
+
+    /// The edges that bound an opening don't form a closed loop
+    #[error(
+        "Edges that bound opening don't form a closed loop, and it can't be \
+        healed: {points:?}"
+    )]
+    UnclosedOpening {
+        /// The points at which the edges that bound the opening start
+        points: Vec<Point<3>>,
+    },
+}
+
+/// Close all openings with a planar face
+fn cap_openings(
+    faces: &[Handle<Face>],
+    objects: &mut Service<Objects>,
+) -> Result<Vec<Handle<Face>>, RemoveFacesError> {
+    let half_edges = || {
+        faces.iter().flat_map(|face| {
+            face.all_cycles()
+                .flat_map(|cycle| cycle.half_edges())
+                .map(move |half_edge| (face, half_edge))
+        })
+    };
+
+    let mut uses = BTreeMap::new();
+    for (_, half_edge) in half_edges() {
+        *uses.entry(half_edge.global_form().id()).or_insert(0) += 1;
+    }
+
+    // In a closed shell, every global edge is referred to by two half-edges.
+    // Those that are left with only one border the opening.
+    let mut boundary = half_edges()
+        .filter(|(_, half_edge)| uses[&half_edge.global_form().id()] == 1)
+        .map(|(face, half_edge)| {
+            let surface = face.surface().geometry();
+            let points = half_edge.boundary().map(|point| {
+                surface.point_from_surface_coords(
+                    half_edge.curve().point_from_path_coords(point),
+                )
+            });
+
+            OpeningEdge {
+                points,
+                is_line: matches!(half_edge.curve(), Curve::Line(_)),
+                global_form: half_edge.global_form().clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let epsilon = Scalar::from(1e-9);
+    let mut caps = Vec::new();
+
+    while let Some(first) = boundary.pop() {
+        let mut opening = vec![first];
+
+        let is_closed = loop {
+            let [start, _] = opening[0].points;
+            let [_, end] = opening[opening.len() - 1].points;
+
+            if (end - start).magnitude() < epsilon {
+                break true;
+            }
+
+            let next = boundary.iter().position(|edge| {
+                let [next_start, _] = edge.points;
+                (next_start - end).magnitude() < epsilon
+            });
+            match next {
+                Some(i) => opening.push(boundary.swap_remove(i)),
+                None => break false,
+            }
+        };
+
+        let points = opening
+            .iter()
+            .map(|edge| {
+                let [start, _] = edge.points;
+                start
+            })
+            .collect::<Vec<_>>();
+
+        if !is_closed {
+            return Err(RemoveFacesError::UnclosedOpening { points });
+        }
+
+        let cap = cap(&opening, &points, epsilon, objects)
+            .ok_or(RemoveFacesError::NonPlanarOpening { points })?;
+        caps.push(cap);
+    }
+
+    Ok(caps)
+}
+
+/// Close an opening bounded by coplanar line segments with a planar face
+fn cap(
+    opening: &[OpeningEdge],
+    points: &[Point<3>],
+    epsilon: Scalar,
+    objects: &mut Service<Objects>,
+) -> Option<Handle<Face>> {
+    if !opening.iter().all(|edge| edge.is_line) {
+        return None;
+    }
+
+    let [a, b] = [points.first()?, points.get(1)?].map(|point| *point);
+    let (c, plane) = points[2..].iter().find_map(|&c| {
+        let triangle = Triangle::<3>::from_points([a, b, c]).ok()?;
+        Some((c, triangle))
+    })?;
+
+    let normal = plane.normal();
+    let is_planar = points
+        .iter()
+        .all(|point| (*point - a).dot(&normal).abs() < epsilon);
+    if !is_planar {
+        return None;
+    }
+
+    let surface = Surface::plane_from_points([a, b, c]).insert(objects);
+
+    // The cap's half-edges run in the opposite direction of the half-edges
+    // that bound the opening.
+    let half_edges = opening
+        .iter()
+        .rev()
+        .map(|edge| {
+            let [start, end] = edge.points;
+            HalfEdge::line_segment_from_global_points(
+                [end, start],
+                &surface,
+                None,
+                objects,
+            )
+            .update_global_form(edge.global_form.clone())
+            .insert(objects)
+        })
+        .collect::<Vec<_>>();
+    let exterior = Cycle::new(half_edges).insert(objects);
+
+    Some(Face::new(surface, exterior, [], None).insert(objects))
+}
+
+struct OpeningEdge {
+    points: [Point<3>; 2],
+    is_line: bool,
+    global_form: Handle<GlobalEdge>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        objects::{Face, Shell},
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::{RemoveFaces, RemoveFacesError};
+
+    #[test]
+    fn remove_face_and_cap_opening() {
+        let mut services = Services::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let face_abc = tetrahedron.face_abc.clone();
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+
+        let shell = shell
+            .remove_faces(&[face_abc.clone()], &mut services.objects)
+            .unwrap();
+
+        assert_eq!(shell.faces().into_iter().count(), 4);
+        assert!(shell.find_face(&face_abc).is_none());
+
+        // The cap must be connected to the same edges as the removed face.
+        let global_edges = |face: &Face| {
+            face.exterior()
+                .half_edges()
+                .map(|half_edge| half_edge.global_form().id())
+                .collect::<BTreeSet<_>>()
+        };
+        let cap = shell
+            .faces()
+            .into_iter()
+            .find(|face| global_edges(face) == global_edges(&face_abc));
+        assert!(cap.is_some());
+
+        services.drop_and_validate().unwrap();
+    }
+
+    #[test]
+    fn remove_faces_and_fail_to_cap_non_planar_opening() {
+        let mut services = Services::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let faces =
+            [tetrahedron.face_abc.clone(), tetrahedron.face_abd.clone()];
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+
+        // Without two of its faces, the tetrahedron has an opening that is
+        // bounded by four edges that don't lie in a plane.
+        let result = shell.remove_faces(&faces, &mut services.objects);
+        let Err(RemoveFacesError::NonPlanarOpening { points }) = result else {
+            panic!("Expected opening to be non-planar");
+        };
+        assert_eq!(points.len(), 4);
+    }
+}
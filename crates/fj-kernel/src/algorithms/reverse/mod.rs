@@ -4,6 +4,8 @@ use crate::{objects::Objects, services::Service};
 
 mod cycle;
 mod face;
+mod shell;
+mod solid;
 
 /// Reverse the direction/orientation of an object
 pub trait Reverse: Sized {
@@ -0,0 +1,22 @@
+use crate::{
+    objects::{Objects, Shell},
+    operations::Insert,
+    services::Service,
+    storage::Handle,
+};
+
+use super::Reverse;
+
+impl Reverse for Handle<Shell> {
+    fn reverse(self, objects: &mut Service<Objects>) -> Self {
+        // Reversed faces keep referring to the same global edges, so the
+        // reversed shell is still connected the same way.
+        let faces = self
+            .faces()
+            .into_iter()
+            .map(|face| face.clone().reverse(objects))
+            .collect::<Vec<_>>();
+
+        Shell::new(faces).insert(objects)
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    objects::{Instance, Objects, Solid},
+    operations::Insert,
+    services::Service,
+    storage::Handle,
+};
+
+use super::Reverse;
+
+impl Reverse for Handle<Solid> {
+    fn reverse(self, objects: &mut Service<Objects>) -> Self {
+        let shells = self
+            .shells()
+            .map(|shell| shell.clone().reverse(objects))
+            .collect::<Vec<_>>();
+
+        // Instances of the same solid keep sharing it, after it's reversed.
+        let mut reversed = BTreeMap::new();
+        let instances = self
+            .instances()
+            .map(|instance| {
+                let solid = reversed
+                    .entry(instance.solid().id())
+                    .or_insert_with(|| {
+                        instance.solid().clone().reverse(objects)
+                    })
+                    .clone();
+
+                Instance::new(solid, *instance.transform())
+            })
+            .collect::<Vec<_>>();
+
+        Solid::new(shells).with_instances(instances).insert(objects)
+    }
+}
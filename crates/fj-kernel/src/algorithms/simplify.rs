@@ -0,0 +1,415 @@
+//! Mesh simplification
+//!
+//! See [`simplify`].
+
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+use fj_interop::mesh::{Color, FaceId, Mesh};
+use fj_math::{Point, Scalar, Vector};
+use nalgebra::{Matrix3, Matrix4, Vector3, Vector4};
+
+/// The target of a mesh simplification
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    /// Simplify until the mesh has at most this many triangles
+    ///
+    /// This might not be reached, if the mesh can't be simplified further
+    /// without breaking it.
+    TriangleCount(usize),
+
+    /// Simplify as long as the error stays below this distance
+    ///
+    /// The error is an estimate of how far the simplified mesh deviates from
+    /// the original one.
+    MaxError(Scalar),
+}
+
+/// Simplify a triangle mesh, using quadric error metrics
+///
+/// Edges are collapsed one by one, cheapest first. The cost of a collapse is
+/// the squared distance of the resulting vertex from the planes of all
+/// original triangles that were merged into it [^1]. This preserves flat areas
+/// and sharp edges, while reducing finely tessellated curved areas.
+///
+/// Open edges of the mesh are preserved as well as possible, and collapses
+/// that would flip a triangle over are skipped. Triangles keep the color and
+/// face of the triangle they originated from.
+///
+/// [^1]: Michael Garland and Paul S. Heckbert, "Surface Simplification Using
+///     Quadric Error Metrics", SIGGRAPH 1997
+pub fn simplify(mesh: &Mesh<Point<3>>, target: Target) -> Mesh<Point<3>> {
+    let mut simplifier = Simplifier::new(mesh);
+
+    let max_cost = match target {
+        Target::TriangleCount(_) => f64::INFINITY,
+        Target::MaxError(error) => error.into_f64() * error.into_f64(),
+    };
+    let is_done = |num_triangles: usize| match target {
+        Target::TriangleCount(count) => num_triangles <= count,
+        Target::MaxError(_) => false,
+    };
+
+    while !is_done(simplifier.num_triangles) {
+        let Some(Collapse {
+            cost,
+            edge,
+            position,
+            versions,
+        }) = simplifier.candidates.pop()
+        else {
+            break;
+        };
+
+        if cost.into_f64() > max_cost {
+            break;
+        }
+        if edge.map(|v| simplifier.versions[v]) != versions {
+            // One of the vertices has changed since this candidate was
+            // computed.
+            continue;
+        }
+
+        simplifier.collapse(edge, position);
+    }
+
+    simplifier.into_mesh()
+}
+
+struct Simplifier {
+    positions: Vec<Vector3<f64>>,
+    quadrics: Vec<Matrix4<f64>>,
+    versions: Vec<u64>,
+
+    triangles: Vec<Option<([usize; 3], Color, Option<FaceId>)>>,
+    triangles_by_vertex: Vec<BTreeSet<usize>>,
+    num_triangles: usize,
+
+    candidates: BinaryHeap<Collapse>,
+}
+
+impl Simplifier {
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let positions = mesh
+            .vertices()
+            .map(|vertex| vertex.coords.to_na())
+            .collect::<Vec<_>>();
+        let indices = mesh.indices().collect::<Vec<_>>();
+
+        let triangles = indices
+            .chunks(3)
+            .zip(mesh.triangles())
+            .map(|(indices, triangle)| {
+                let indices = [0, 1, 2].map(|i| indices[i] as usize);
+                Some((indices, triangle.color, triangle.face))
+            })
+            .collect::<Vec<_>>();
+
+        let mut triangles_by_vertex = vec![BTreeSet::new(); positions.len()];
+        let mut quadrics = vec![Matrix4::zeros(); positions.len()];
+        let mut edges = BTreeMap::<_, Vec<usize>>::new();
+
+        for (i, triangle) in triangles.iter().enumerate() {
+            let Some(([a, b, c], _, _)) = *triangle else {
+                continue;
+            };
+
+            for vertex in [a, b, c] {
+                triangles_by_vertex[vertex].insert(i);
+            }
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                edges.entry(sorted([p, q])).or_default().push(i);
+            }
+
+            let [pa, pb, pc] = [a, b, c].map(|vertex| positions[vertex]);
+            let Some(normal) = (pb - pa).cross(&(pc - pa)).try_normalize(0.)
+            else {
+                continue;
+            };
+            let quadric = plane_quadric(normal, pa);
+            for vertex in [a, b, c] {
+                quadrics[vertex] += quadric;
+            }
+        }
+
+        // Open edges are constrained by a plane that is perpendicular to
+        // their triangle. Moving the edge away from that plane is expensive.
+        for (&[a, b], adjacent) in &edges {
+            let &[triangle] = adjacent.as_slice() else {
+                continue;
+            };
+            let Some(([p, q, r], _, _)) = triangles[triangle] else {
+                continue;
+            };
+
+            let [pp, pq, pr] = [p, q, r].map(|vertex| positions[vertex]);
+            let normal = (pq - pp).cross(&(pr - pp));
+            let Some(normal) =
+                (positions[b] - positions[a]).cross(&normal).try_normalize(0.)
+            else {
+                continue;
+            };
+
+            let quadric = plane_quadric(normal, positions[a]) * BOUNDARY_WEIGHT;
+            quadrics[a] += quadric;
+            quadrics[b] += quadric;
+        }
+
+        let num_triangles = triangles.len();
+        let mut simplifier = Self {
+            versions: vec![0; positions.len()],
+            positions,
+            quadrics,
+            triangles,
+            triangles_by_vertex,
+            num_triangles,
+            candidates: BinaryHeap::new(),
+        };
+
+        for edge in edges.into_keys() {
+            simplifier.push_candidate(edge);
+        }
+
+        simplifier
+    }
+
+    fn push_candidate(&mut self, edge: [usize; 2]) {
+        let [a, b] = edge;
+        let quadric = self.quadrics[a] + self.quadrics[b];
+
+        let (cost, position) =
+            optimal_position(&quadric, [self.positions[a], self.positions[b]]);
+
+        self.candidates.push(Collapse {
+            // Costs can end up slightly negative, due to floating point
+            // inaccuracy.
+            cost: Scalar::from(cost.max(0.)),
+            edge,
+            position,
+            versions: edge.map(|vertex| self.versions[vertex]),
+        });
+    }
+
+    fn collapse(&mut self, [a, b]: [usize; 2], position: Point<3>) {
+        let position = position.coords.to_na();
+
+        let shared = self.triangles_by_vertex[a]
+            .intersection(&self.triangles_by_vertex[b])
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let moved = self.triangles_by_vertex[a]
+            .union(&self.triangles_by_vertex[b])
+            .copied()
+            .filter(|triangle| !shared.contains(triangle))
+            .collect::<Vec<_>>();
+
+        // Don't collapse, if it would flip or collapse a remaining triangle.
+        for &triangle in &moved {
+            let Some((vertices, _, _)) = self.triangles[triangle] else {
+                continue;
+            };
+
+            let before = vertices.map(|vertex| self.positions[vertex]);
+            let after = vertices.map(|vertex| {
+                if vertex == a || vertex == b {
+                    position
+                } else {
+                    self.positions[vertex]
+                }
+            });
+
+            let [n_before, n_after] =
+                [before, after].map(|[p, q, r]| (q - p).cross(&(r - p)));
+            if n_before.dot(&n_after) <= 0. {
+                return;
+            }
+        }
+
+        for triangle in shared {
+            if let Some((vertices, _, _)) = self.triangles[triangle].take() {
+                for vertex in vertices {
+                    self.triangles_by_vertex[vertex].remove(&triangle);
+                }
+                self.num_triangles -= 1;
+            }
+        }
+
+        for triangle in self.triangles_by_vertex[b].clone() {
+            if let Some((vertices, _, _)) = &mut self.triangles[triangle] {
+                for vertex in vertices {
+                    if *vertex == b {
+                        *vertex = a;
+                    }
+                }
+            }
+            self.triangles_by_vertex[a].insert(triangle);
+        }
+        self.triangles_by_vertex[b].clear();
+
+        self.positions[a] = position;
+        self.quadrics[a] += self.quadrics[b];
+        self.versions[a] += 1;
+        self.versions[b] += 1;
+
+        let neighbors = self.triangles_by_vertex[a]
+            .iter()
+            .filter_map(|&triangle| self.triangles[triangle])
+            .flat_map(|(vertices, _, _)| vertices)
+            .filter(|&vertex| vertex != a)
+            .collect::<BTreeSet<_>>();
+        for neighbor in neighbors {
+            self.push_candidate(sorted([a, neighbor]));
+        }
+    }
+
+    fn into_mesh(self) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        for (vertices, color, face) in self.triangles.into_iter().flatten() {
+            let points = vertices.map(|vertex| Point {
+                coords: Vector::from_na(self.positions[vertex]),
+            });
+
+            match face {
+                Some(face) => mesh.push_triangle_of_face(points, color, face),
+                None => mesh.push_triangle(points, color),
+            }
+        }
+
+        mesh
+    }
+}
+
+/// A candidate for an edge collapse
+///
+/// Ordered such, that the cheapest collapse is the greatest, to make
+/// [`BinaryHeap`] a min-heap.
+#[derive(Eq, PartialEq)]
+struct Collapse {
+    cost: Scalar,
+    edge: [usize; 2],
+    position: Point<3>,
+    versions: [u64; 2],
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| other.edge.cmp(&self.edge))
+    }
+}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compute the quadric, that measures the squared distance from a plane
+fn plane_quadric(normal: Vector3<f64>, point: Vector3<f64>) -> Matrix4<f64> {
+    let plane = Vector4::new(normal.x, normal.y, normal.z, -normal.dot(&point));
+    plane * plane.transpose()
+}
+
+/// Find the position with the lowest cost, when collapsing an edge
+///
+/// Returns the cost and the position.
+fn optimal_position(
+    quadric: &Matrix4<f64>,
+    [a, b]: [Vector3<f64>; 2],
+) -> (f64, Point<3>) {
+    let cost = |position: Vector3<f64>| {
+        let position = position.push(1.);
+        (position.transpose() * quadric * position)[0]
+    };
+
+    let system: Matrix3<f64> = quadric.fixed_view::<3, 3>(0, 0).into_owned();
+    let rhs = -quadric.fixed_view::<3, 1>(0, 3).into_owned();
+
+    // The optimal position is the minimum of the quadric. If it's not well
+    // defined, which is the case in flat areas, fall back to one of the
+    // edge's points.
+    let optimal = system
+        .try_inverse()
+        .filter(|_| system.determinant().abs() > 1e-12)
+        .map(|inverse| inverse * rhs);
+
+    optimal
+        .into_iter()
+        .chain([a, b, (a + b) / 2.])
+        .map(|position| (cost(position), position))
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(cost, position)| {
+            (
+                cost,
+                Point {
+                    coords: Vector::from_na(position),
+                },
+            )
+        })
+        .expect("List of candidate positions is not empty")
+}
+
+fn sorted([a, b]: [usize; 2]) -> [usize; 2] {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+/// The weight of the planes that keep open edges in place
+const BOUNDARY_WEIGHT: f64 = 1000.;
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use super::Target;
+
+    #[test]
+    fn simplify_flat_grid() {
+        // A 2x2 square made up of 32 triangles.
+        let mut mesh = Mesh::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                let point = |di: usize, dj: usize| {
+                    Point::from([
+                        (i + di) as f64 * 0.5,
+                        (j + dj) as f64 * 0.5,
+                        0.,
+                    ])
+                };
+
+                let [a, b, c, d] =
+                    [point(0, 0), point(1, 0), point(1, 1), point(0, 1)];
+                mesh.push_triangle([a, b, c], Color::default());
+                mesh.push_triangle([a, c, d], Color::default());
+            }
+        }
+
+        let simplified =
+            super::simplify(&mesh, Target::MaxError(Scalar::from(1e-9)));
+
+        let num_triangles = simplified.triangles().count();
+        assert!(num_triangles < 32);
+
+        // The square must still cover the same area, facing the same way.
+        let mut area = Scalar::ZERO;
+        for triangle in simplified.triangles() {
+            let [a, b, c] = triangle.inner.points();
+            let normal = (b - a).cross(&(c - a));
+            assert!(normal.z > Scalar::ZERO);
+            area += normal.z / 2.;
+        }
+        assert!((area - Scalar::from(4.)).abs() < Scalar::from(1e-9));
+
+        for vertex in simplified.vertices() {
+            for c in vertex.coords.components {
+                assert!(c > Scalar::from(-1e-9) && c < Scalar::from(2. + 1e-9));
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Vector};
+use fj_math::{Bezier, Ellipse, Line, Vector};
 
 use crate::{
     geometry::{
@@ -11,10 +11,10 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for (Curve, &Surface) {
-    type Swept = Handle<Surface>;
+    type Swept = Result<Handle<Surface>, SweepError>;
 
     fn sweep_with_cache(
         self,
@@ -25,22 +25,20 @@ impl Sweep for (Curve, &Surface) {
         let (curve, surface) = self;
 
         match surface.geometry().u {
-            GlobalPath::Circle(_) => {
+            GlobalPath::Bezier(_)
+            | GlobalPath::Circle(_)
+            | GlobalPath::Ellipse(_) => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
                 // `Surface` is a `GlobalPath`, which we are computing below.
-                // That computation might or might not work with an arbitrary
-                // surface. Probably not, but I'm not sure.
+                // The image of a curve on a curved surface is not one of the
+                // paths that a `GlobalPath` can represent.
                 //
-                // What definitely won't work, is computing the bottom edge of
-                // the sweep. The edge sweeping code currently assumes that the
-                // bottom edge is a line (which is true when sweeping from a
-                // flat surface). But is the surface we're sweeping from is
-                // curved, there's simply no way to represent the curve of the
-                // resulting bottom edge.
-                todo!(
-                    "Sweeping a curve that is defined on a curved surface is \
-                    not supported yet."
-                )
+                // The bottom edge of the sweep couldn't be represented either.
+                // The edge sweeping code assumes that the bottom edge is a line
+                // (which is true when sweeping from a flat surface). But if the
+                // surface we're sweeping from is curved, there's simply no way
+                // to represent the curve of the resulting bottom edge.
+                return Err(SweepError::CurvedSurface(surface.geometry()));
             }
             GlobalPath::Line(_) => {
                 // We're sweeping from a curve on a flat surface, which is
@@ -49,6 +47,16 @@ impl Sweep for (Curve, &Surface) {
         }
 
         let u = match curve {
+            Curve::Bezier(bezier) => {
+                let points = bezier.control_points().map(|point| {
+                    surface.geometry().point_from_surface_coords(point)
+                });
+
+                // The surface is a plane, which makes the mapping from surface
+                // to global coordinates affine. Bézier curves are invariant
+                // under affine maps, so this is exact.
+                GlobalPath::Bezier(Bezier::new(points))
+            }
             Curve::Circle(circle) => {
                 let center = surface
                     .geometry()
@@ -58,9 +66,9 @@ impl Sweep for (Curve, &Surface) {
                 let b =
                     surface.geometry().vector_from_surface_coords(circle.b());
 
-                let circle = Circle::new(center, a, b);
-
-                GlobalPath::Circle(circle)
+                // The surface might not preserve angles, if it has been
+                // transformed, so the result isn't necessarily a circle.
+                GlobalPath::from_ellipse(Ellipse::new(center, a, b))
             }
             Curve::Line(line) => {
                 let origin =
@@ -75,6 +83,34 @@ impl Sweep for (Curve, &Surface) {
             }
         };
 
-        Surface::new(SurfaceGeometry { u, v: path.into() }).insert(objects)
+        Ok(Surface::new(SurfaceGeometry { u, v: path.into() }).insert(objects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::sweep::{Sweep, SweepError},
+        geometry::{
+            curve::{Curve, GlobalPath},
+            surface::SurfaceGeometry,
+        },
+        objects::Surface,
+        services::Services,
+    };
+
+    #[test]
+    fn sweep_curve_on_curved_surface() {
+        let mut services = Services::new();
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::circle_from_radius(1.),
+            v: [0., 0., 1.].into(),
+        });
+        let curve = Curve::circle_from_radius(0.5);
+
+        let result =
+            (curve, &surface).sweep([1., 0., 0.], &mut services.objects);
+        assert!(matches!(result, Err(SweepError::CurvedSurface(_))));
     }
 }
@@ -8,10 +8,10 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for (&HalfEdge, &Handle<Vertex>, &Surface, Option<Color>) {
-    type Swept = (Handle<Face>, Handle<HalfEdge>);
+    type Swept = Result<(Handle<Face>, Handle<HalfEdge>), SweepError>;
 
     fn sweep_with_cache(
         self,
@@ -22,6 +22,11 @@ impl Sweep for (&HalfEdge, &Handle<Vertex>, &Surface, Option<Color>) {
         let (edge, next_vertex, surface, color) = self;
         let path = path.into();
 
+        // Sweeping the curve fails on some surfaces. Doing it first means that
+        // nothing else has been swept by then.
+        let swept_surface =
+            (edge.curve(), surface).sweep_with_cache(path, cache, objects)?;
+
         // Next, we need to define the boundaries of the face. Let's start with
         // the global vertices and edges.
         let (vertices, global_edges) = {
@@ -105,7 +110,7 @@ impl Sweep for (&HalfEdge, &Handle<Vertex>, &Surface, Option<Color>) {
             });
 
         let face = Face::new(
-            (edge.curve(), surface).sweep_with_cache(path, cache, objects),
+            swept_surface,
             exterior.unwrap().insert(objects),
             Vec::new(),
             color,
@@ -114,6 +119,6 @@ impl Sweep for (&HalfEdge, &Handle<Vertex>, &Surface, Option<Color>) {
         // And we're done creating the face! All that's left to do is build our
         // return values.
         let face = face.insert(objects);
-        (face, edge_top)
+        Ok((face, edge_top))
     }
 }
@@ -13,10 +13,10 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for Handle<Face> {
-    type Swept = Handle<Shell>;
+    type Swept = Result<Handle<Shell>, SweepError>;
 
     fn sweep_with_cache(
         self,
@@ -24,16 +24,24 @@ impl Sweep for Handle<Face> {
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
     ) -> Self::Swept {
+        let _span = tracing::debug_span!("sweep_face").entered();
+
         let path = path.into();
 
         let mut faces = Vec::new();
 
         let is_negative_sweep = {
             let u = match self.surface().geometry().u {
-                GlobalPath::Circle(_) => todo!(
-                    "Sweeping from faces defined in round surfaces is not \
-                    supported"
-                ),
+                // The edges of a face on a curved surface can't be swept (see
+                // `SweepError::CurvedSurface`), regardless of the kind of
+                // curve that the surface is swept from.
+                GlobalPath::Bezier(_)
+                | GlobalPath::Circle(_)
+                | GlobalPath::Ellipse(_) => {
+                    return Err(SweepError::CurvedSurface(
+                        self.surface().geometry(),
+                    ));
+                }
                 GlobalPath::Line(line) => line.direction(),
             };
             let v = self.surface().geometry().v;
@@ -71,7 +79,7 @@ impl Sweep for Handle<Face> {
                     self.surface().deref(),
                     self.color(),
                 )
-                    .sweep_with_cache(path, cache, objects);
+                    .sweep_with_cache(path, cache, objects)?;
 
                 faces.push(face);
 
@@ -98,6 +106,80 @@ impl Sweep for Handle<Face> {
         let top_face = top_face.insert(objects);
         faces.push(top_face);
 
-        Shell::new(faces).insert(objects)
+        Ok(Shell::new(faces).insert(objects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Ellipse;
+
+    use crate::{
+        algorithms::sweep::{Sweep, SweepError},
+        builder::{CycleBuilder, FaceBuilder},
+        geometry::{curve::GlobalPath, surface::SurfaceGeometry},
+        objects::Surface,
+        operations::Insert,
+        services::Services,
+    };
+
+    #[test]
+    fn sweep_face_with_hole() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let face = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+                &mut services.objects,
+            ))
+            .with_interior(CycleBuilder::polygon(
+                [[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+
+        let shell = face.sweep([0., 0., 1.], &mut services.objects).unwrap();
+
+        // Both caps, plus one side face for each edge of both cycles.
+        let faces = shell.faces();
+        assert_eq!(faces.into_iter().count(), 2 + 4 + 4);
+
+        let caps = faces
+            .into_iter()
+            .filter(|face| face.interiors().count() > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(caps.len(), 2);
+        for cap in caps {
+            assert_eq!(cap.interiors().count(), 1);
+        }
+
+        services.drop_and_validate().unwrap();
+    }
+
+    #[test]
+    fn sweep_face_on_curved_surface() {
+        let mut services = Services::new();
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::Ellipse(Ellipse::new(
+                [0., 0., 0.],
+                [2., 0., 0.],
+                [0., 1., 0.],
+            )),
+            v: [0., 0., 1.].into(),
+        })
+        .insert(&mut services.objects);
+        let face = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+
+        let result = face.sweep([1., 0., 0.], &mut services.objects);
+        assert!(matches!(result, Err(SweepError::CurvedSurface(_))));
     }
 }
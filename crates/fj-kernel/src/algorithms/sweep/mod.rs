@@ -11,6 +11,7 @@ use std::collections::BTreeMap;
 use fj_math::Vector;
 
 use crate::{
+    geometry::surface::SurfaceGeometry,
     objects::{GlobalEdge, Objects, Vertex},
     services::Service,
     storage::{Handle, ObjectId},
@@ -40,6 +41,18 @@ pub trait Sweep: Sized {
     ) -> Self::Swept;
 }
 
+/// An error that can occur while sweeping
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum SweepError {
+    /// The object is defined on a curved surface
+    ///
+    /// Sweeping an edge creates a face, whose surface is swept from the edge's
+    /// curve. There's no way to represent the path of a curve on a curved
+    /// surface in 3D, which the u-axis of that new surface would need to be.
+    #[error("Sweeping from a curved surface is not supported: {0:#?}")]
+    CurvedSurface(SurfaceGeometry),
+}
+
 /// A cache used for sweeping
 ///
 /// See [`Sweep`].
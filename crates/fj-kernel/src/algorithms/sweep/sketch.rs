@@ -1,16 +1,16 @@
 use fj_math::Vector;
 
 use crate::{
-    objects::{Objects, Sketch, Solid},
+    objects::{Objects, Provenance, Sketch, Solid},
     operations::Insert,
     services::Service,
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for Handle<Sketch> {
-    type Swept = Handle<Solid>;
+    type Swept = Result<Handle<Solid>, SweepError>;
 
     fn sweep_with_cache(
         self,
@@ -18,14 +18,19 @@ impl Sweep for Handle<Sketch> {
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
     ) -> Self::Swept {
+        let _span = tracing::debug_span!("sweep_sketch").entered();
+
         let path = path.into();
+        let provenance = Provenance::new("sweep", [self.clone().into()]);
 
-        let mut shells = Vec::new();
-        for face in self.faces().clone() {
-            let shell = face.sweep_with_cache(path, cache, objects);
-            shells.push(shell);
-        }
+        objects.with_provenance(provenance, |objects| {
+            let mut shells = Vec::new();
+            for face in self.faces().clone() {
+                let shell = face.sweep_with_cache(path, cache, objects)?;
+                shells.push(shell);
+            }
 
-        Solid::new(shells).insert(objects)
+            Ok(Solid::new(shells).insert(objects))
+        })
     }
 }
@@ -0,0 +1,409 @@
+//! Surface textures, like knurling
+//!
+//! See [`Texture`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar, Transform};
+
+use crate::geometry::surface::SurfaceGeometry;
+
+use super::{
+    approx::{face::FaceApprox, Tolerance},
+    triangulate::TriangulationPoint,
+};
+
+/// A repeating displacement pattern, applied to a face when triangulating it
+///
+/// Textures are a purely cosmetic feature of the triangle mesh. They don't
+/// change the face itself, which means they don't affect any other operations.
+/// This makes it practical to add grip surfaces to a model that would
+/// otherwise require hundreds of features.
+///
+/// The pattern is laid out in the coordinates of the face's surface, scaled to
+/// model units. It works best on planes and cylinders, where those coordinates
+/// are not distorted.
+///
+/// Apply a texture to a face using [`Face::with_texture`].
+///
+/// [`Face::with_texture`]: crate::objects::Face::with_texture
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Texture {
+    /// The pattern of the texture
+    pub pattern: Pattern,
+
+    /// The distance after which the pattern repeats
+    pub pitch: Scalar,
+
+    /// The depth of the pattern
+    ///
+    /// The pattern is cut into the face. The face's original surface is the
+    /// top of the pattern.
+    pub depth: Scalar,
+}
+
+impl Texture {
+    /// Compute the height of the pattern at the given pattern coordinates
+    ///
+    /// `s` and `t` are the coordinates along the u- and v-axis of the surface,
+    /// in model units. Returns a value between zero (the top of the pattern)
+    /// and one (its deepest point).
+    pub fn height(&self, s: Scalar, t: Scalar) -> Scalar {
+        let [s, t] = [s, t].map(|c| c / self.pitch);
+
+        match self.pattern {
+            Pattern::Ribs => groove(s),
+            Pattern::Knurl => Scalar::max(groove(s), groove(t)),
+            Pattern::Diamond => Scalar::max(groove(s + t), groove(s - t)),
+        }
+    }
+
+    /// Compute the step size required to sample the texture
+    ///
+    /// The pattern is made up of straight slopes, so a piecewise linear
+    /// approximation deviates from it by at most the change in depth over
+    /// one step.
+    fn step(&self, tolerance: Tolerance) -> Scalar {
+        let step = self.pitch / 4.;
+
+        if self.depth <= Scalar::ZERO {
+            return step;
+        }
+
+        let slope = self.depth * 2. / self.pitch;
+        Scalar::min(step, tolerance.length() / slope)
+    }
+}
+
+/// The pattern of a [`Texture`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pattern {
+    /// Grooves that run along the v-axis of the surface
+    ///
+    /// On a cylinder that was created by sweeping a circle, this is a straight
+    /// knurl.
+    Ribs,
+
+    /// Grooves that run along both axes of the surface
+    ///
+    /// Results in a grid of square pyramids.
+    Knurl,
+
+    /// Grooves that run diagonally to the axes of the surface
+    ///
+    /// Results in a grid of diamond-shaped pyramids, like a classic diamond
+    /// knurl.
+    Diamond,
+}
+
+/// A [`Texture`], prepared for triangulating the approximation of a face
+///
+/// Created when approximating a face that has a texture. See
+/// [`FaceApprox::texture`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TextureApprox {
+    /// The texture of the approximated face
+    pub texture: Texture,
+
+    /// The geometry of the approximated face's surface
+    ///
+    /// The pattern is laid out in its coordinates, and cut along its normal.
+    pub surface: SurfaceGeometry,
+
+    /// The maximum distance between the points that sample the pattern
+    pub step: Scalar,
+}
+
+impl TextureApprox {
+    /// Prepare a texture for triangulating a face on the given surface
+    pub fn new(
+        texture: Texture,
+        surface: SurfaceGeometry,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        Self {
+            texture,
+            surface,
+            step: texture.step(tolerance.into()),
+        }
+    }
+
+    /// Transform the prepared texture, along with the face's approximation
+    pub fn transform(self, transform: &Transform) -> Self {
+        Self {
+            surface: self.surface.transform(transform),
+            ..self
+        }
+    }
+}
+
+/// Triangulate the approximation of a face, applying a texture to it
+///
+/// The face is triangulated like it would be without the texture. Its
+/// triangles are then subdivided, finely enough to follow the pattern within
+/// the tolerance of the approximation, and displaced towards the back of the
+/// face.
+///
+/// All triangles are subdivided into the same number of steps, and the points
+/// on the edges between them are computed the same way from either side. This
+/// keeps the mesh free of T-junctions. The boundary of the face is left
+/// untouched, so the result still fits the triangles of the face's neighbors.
+pub(crate) fn triangulate_into_mesh(
+    approx: FaceApprox,
+    texture: &TextureApprox,
+    mesh: &mut Mesh<Point<3>>,
+) {
+    let color = approx.color.unwrap_or_default();
+    let face_id = approx.face;
+    let triangles = approx.triangles();
+
+    // Edges that are not shared by two triangles are on the boundary of the
+    // face.
+    let mut edges = BTreeMap::new();
+    for triangle in &triangles {
+        let [a, b, c] = triangle.map(|point| point.point_surface);
+        for [p, q] in [[a, b], [b, c], [c, a]] {
+            let edge = if p < q { [p, q] } else { [q, p] };
+            *edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+    let is_boundary = |p: Point<2>, q: Point<2>| {
+        let edge = if p < q { [p, q] } else { [q, p] };
+        edges.get(&edge) == Some(&1)
+    };
+
+    let n = num_steps(&triangles, texture.step);
+
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(|point| point.point_surface);
+
+        // The texture is cut in the direction opposite to the triangle's
+        // normal, which depends on its winding in surface coordinates.
+        let winding = if (b - a).cross2d(&(c - a)) < Scalar::ZERO {
+            -Scalar::ONE
+        } else {
+            Scalar::ONE
+        };
+        let displaced = |point| displace(texture, point, winding);
+
+        // The point `(i, j)` has the barycentric weights `n - i - j`, `i`, and
+        // `j` for the corners `a`, `b`, and `c`.
+        let vertex = |i: usize, j: usize| {
+            let weights = [n - i - j, i, j];
+
+            let num_nonzero = weights.iter().filter(|&&w| w > 0).count();
+            if num_nonzero == 1 {
+                let corner = weights
+                    .iter()
+                    .position(|&w| w > 0)
+                    .expect("One weight is positive");
+                return triangle[corner].point_global;
+            }
+
+            if num_nonzero == 2 {
+                let zero = weights
+                    .iter()
+                    .position(|&w| w == 0)
+                    .expect("One weight is zero");
+                let [x, y] = [(zero + 1) % 3, (zero + 2) % 3];
+                let [p, q] = [triangle[x], triangle[y]];
+
+                // Points on the boundary would be out of place, as the
+                // neighboring face doesn't have them. Move them to the nearest
+                // end of the edge. The resulting degenerate triangles are
+                // skipped below.
+                if is_boundary(p.point_surface, q.point_surface) {
+                    let nearest = if weights[x] * 2 >= n { p } else { q };
+                    return nearest.point_global;
+                }
+
+                // The triangle on the other side of the edge computes the
+                // same point, from the same ordered endpoints.
+                let (start, end, w) = if p.point_surface < q.point_surface {
+                    (p.point_surface, q.point_surface, weights[y])
+                } else {
+                    (q.point_surface, p.point_surface, weights[x])
+                };
+                return displaced(
+                    start + (end - start) * (w as f64 / n as f64),
+                );
+            }
+
+            let point = a
+                + (b - a) * (i as f64 / n as f64)
+                + (c - a) * (j as f64 / n as f64);
+            displaced(point)
+        };
+
+        let mut push = |[p, q, r]: [(usize, usize); 3]| {
+            let [p, q, r] = [p, q, r].map(|(i, j)| vertex(i, j));
+            if p == q || q == r || r == p {
+                return;
+            }
+            mesh.push_triangle_of_face([p, q, r], color, face_id);
+        };
+
+        for j in 0..n {
+            for i in 0..n - j {
+                push([(i, j), (i + 1, j), (i, j + 1)]);
+
+                if i + j + 1 < n {
+                    push([(i + 1, j), (i + 1, j + 1), (i, j + 1)]);
+                }
+            }
+        }
+    }
+}
+
+/// Compute the number of steps that all triangles of a face are divided into
+fn num_steps(triangles: &[[TriangulationPoint; 3]], step: Scalar) -> usize {
+    let longest_edge = triangles
+        .iter()
+        .flat_map(|triangle| {
+            let [p, q, r] = triangle.map(|point| point.point_global);
+            [(p, q), (q, r), (r, p)]
+        })
+        .map(|(start, end)| (end - start).magnitude())
+        .max()
+        .unwrap_or(Scalar::ZERO);
+    let n = (longest_edge / step).ceil().into_f64().max(1.) as usize;
+
+    // With two steps, moving the midpoints of two boundary edges to their
+    // ends leaves a triangle that lies flat on the third edge.
+    if n == 2 {
+        3
+    } else {
+        n
+    }
+}
+
+fn displace(
+    texture: &TextureApprox,
+    point: Point<2>,
+    winding: Scalar,
+) -> Point<3> {
+    let TextureApprox {
+        texture, surface, ..
+    } = texture;
+
+    let [du, dv] = surface.derivatives_at(point);
+    let normal = surface.normal_at(point) * winding;
+
+    let s = point.u * du.magnitude();
+    let t = point.v * dv.magnitude();
+    let depth = texture.depth * texture.height(s, t);
+
+    surface.point_from_surface_coords(point) - normal * depth
+}
+
+/// A triangle wave that is zero at integers and one halfway between them
+fn groove(x: Scalar) -> Scalar {
+    let x = x - x.floor();
+    Scalar::ONE - (x * 2. - 1.).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{
+            approx::{Approx, Tolerance},
+            sweep::Sweep,
+            triangulate::Triangulate,
+        },
+        builder::{CycleBuilder, FaceBuilder},
+        objects::FaceSet,
+        operations::Insert,
+        services::Services,
+    };
+
+    use super::{Pattern, Texture};
+
+    #[test]
+    fn knurl_plane() {
+        let mut services = Services::new();
+
+        let texture = Texture {
+            pattern: Pattern::Knurl,
+            pitch: Scalar::from(0.5),
+            depth: Scalar::from(0.1),
+        };
+
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .with_texture(Some(texture))
+            .insert(&mut services.objects);
+
+        let mesh = face.approx(0.01).triangulate();
+
+        let mut deepest = Scalar::ZERO;
+        for vertex in mesh.vertices() {
+            let [x, y, z] = vertex.coords.components;
+
+            // The texture is cut into the face, along its negative normal.
+            assert!(z <= Scalar::ZERO);
+            assert!(z >= -texture.depth);
+            deepest = deepest.min(z);
+
+            // The boundary of the face is not displaced.
+            let on_boundary = [x, y]
+                .into_iter()
+                .any(|c| c == Scalar::ZERO || c == Scalar::from(2.));
+            if on_boundary {
+                assert_eq!(z, Scalar::ZERO);
+            }
+        }
+
+        assert!(deepest < -texture.depth / 2.);
+    }
+
+    #[test]
+    fn textured_solid_is_watertight() {
+        let mut services = Services::new();
+
+        // A half-cylinder, so the texture is applied to curved and flat
+        // faces, and to faces that are triangulated into long slivers.
+        let exterior = CycleBuilder::new()
+            .move_to([1., 0.])
+            .add_arc_to([0., 1.], 1., &mut services.objects)
+            .add_arc_to([-1., 0.], 1., &mut services.objects)
+            .close(&mut services.objects);
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(exterior)
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+        let shell = face.sweep([0., 0., 1.], &mut services.objects).unwrap();
+
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+        let untextured = (shell.faces(), tolerance).triangulate();
+
+        for pattern in [Pattern::Ribs, Pattern::Knurl, Pattern::Diamond] {
+            let texture = Texture {
+                pattern,
+                pitch: Scalar::from(0.25),
+                depth: Scalar::from(0.05),
+            };
+
+            let faces = shell
+                .faces()
+                .into_iter()
+                .map(|face| {
+                    face.clone_object()
+                        .with_texture(Some(texture))
+                        .insert(&mut services.objects)
+                })
+                .collect::<FaceSet>();
+            let mesh = (&faces, tolerance).triangulate();
+
+            assert!(mesh.triangles().count() > untextured.triangles().count());
+            assert_eq!(mesh.defects(), Vec::new());
+        }
+    }
+}
@@ -14,8 +14,9 @@ impl TransformObject for Face {
         objects: &mut Service<Objects>,
         cache: &mut TransformCache,
     ) -> Self {
-        // Color does not need to be transformed.
+        // Color and texture do not need to be transformed.
         let color = self.color();
+        let texture = self.texture();
 
         let surface = self
             .surface()
@@ -29,7 +30,7 @@ impl TransformObject for Face {
             interior.transform_with_cache(transform, objects, cache)
         });
 
-        Self::new(surface, exterior, interiors, color)
+        Self::new(surface, exterior, interiors, color).with_texture(texture)
     }
 }
 
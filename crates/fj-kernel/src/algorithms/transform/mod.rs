@@ -23,6 +23,12 @@ use crate::{
 
 /// Transform an object
 ///
+/// This is implemented for every object type, as well as for handles to them.
+/// Transforming a handle uses the [`TransformCache`], so an object that is
+/// referenced from multiple places, like a vertex that is shared by several
+/// half-edges, is only transformed once, and the transformed object is shared
+/// in the same way.
+///
 /// # Implementation Note
 ///
 /// So far, a general `transform` method is available, along some convenience
@@ -70,6 +76,13 @@ pub trait TransformObject: Sized {
     ) -> Self {
         self.transform(&Transform::rotation(axis_angle), objects)
     }
+
+    /// Scale the object uniformly, relative to the origin
+    ///
+    /// Convenience wrapper around [`TransformObject::transform`].
+    fn scale(self, factor: f64, objects: &mut Service<Objects>) -> Self {
+        self.transform(&Transform::scale(factor), objects)
+    }
 }
 
 impl<T> TransformObject for Handle<T>
@@ -122,3 +135,28 @@ impl TransformCache {
         map.insert(key.id(), value);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{algorithms::transform::TransformObject, services::Services};
+
+    #[test]
+    fn scale() {
+        let mut services = Services::new();
+
+        let surface = services
+            .objects
+            .surfaces
+            .xy_plane()
+            .translate([0., 0., 1.], &mut services.objects);
+        let scaled = surface.clone().scale(2., &mut services.objects);
+
+        // Scaling is relative to the origin, so the plane moves away from it.
+        for point in [[0., 0.], [1., 0.], [0., 1.], [2., 3.]] {
+            assert_eq!(
+                scaled.geometry().point_from_surface_coords(point),
+                surface.geometry().point_from_surface_coords(point) * 2.,
+            );
+        }
+    }
+}
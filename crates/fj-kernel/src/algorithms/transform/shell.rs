@@ -22,3 +22,58 @@ impl TransformObject for Shell {
         Self::new(faces)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use fj_math::Transform;
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        objects::{GlobalEdge, HalfEdge, ObjectIters, Shell, Vertex},
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    #[test]
+    fn transform_rebuilds_topology() {
+        let mut services = Services::new();
+
+        let shell = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        )
+        .shell
+        .insert(&mut services.objects);
+        let transformed = shell.clone().transform(
+            &(Transform::translation([1., 2., 3.])
+                * Transform::from_axis_angle([0., 0., 1.], 1.)),
+            &mut services.objects,
+        );
+
+        let ids = |shell: &Shell| -> HashSet<_> {
+            shell.iter_objects().map(|object| object.id()).collect()
+        };
+        let original = ids(&shell);
+        let transformed_ids = ids(&transformed);
+
+        // All objects are new, but shared objects are still shared.
+        assert!(original.is_disjoint(&transformed_ids));
+        assert_eq!(
+            shell.all::<GlobalEdge>().count(),
+            transformed.all::<GlobalEdge>().count(),
+        );
+        assert_eq!(
+            shell.all::<HalfEdge>().count(),
+            transformed.all::<HalfEdge>().count(),
+        );
+        assert_eq!(
+            shell.all::<Vertex>().count(),
+            transformed.all::<Vertex>().count(),
+        );
+
+        // The transformed shell is validated like any other.
+        services.drop_and_validate().unwrap();
+    }
+}
@@ -1,7 +1,7 @@
 use fj_math::Transform;
 
 use crate::{
-    objects::{Objects, Solid},
+    objects::{Instance, Objects, Solid},
     services::Service,
 };
 
@@ -19,6 +19,15 @@ impl TransformObject for Solid {
             .cloned()
             .map(|shell| shell.transform_with_cache(transform, objects, cache));
 
-        Self::new(shells)
+        // Instances keep referring to the same solid. Only their transform
+        // changes, so the referenced solid is never copied.
+        let instances = self.instances().map(|instance| {
+            Instance::new(
+                instance.solid().clone(),
+                *transform * *instance.transform(),
+            )
+        });
+
+        Self::new(shells).with_instances(instances)
     }
 }
@@ -6,9 +6,14 @@ mod polygon;
 use fj_interop::mesh::Mesh;
 use fj_math::Point;
 
+pub(crate) use self::delaunay::TriangulationPoint;
+
 use self::polygon::Polygon;
 
-use super::approx::{face::FaceApprox, Approx, Tolerance};
+use super::{
+    approx::{face::FaceApprox, Approx, Tolerance},
+    texture,
+};
 
 /// Triangulate a shape
 pub trait Triangulate: Sized {
@@ -44,6 +49,26 @@ where
 
 impl Triangulate for FaceApprox {
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>) {
+        let _span = tracing::debug_span!("triangulate_face").entered();
+
+        if let Some(texture) = self.texture {
+            texture::triangulate_into_mesh(self, &texture, mesh);
+            return;
+        }
+
+        let color = self.color.unwrap_or_default();
+        let face = self.face;
+
+        for triangle in self.triangles() {
+            let points = triangle.map(|point| point.point_global);
+            mesh.push_triangle_of_face(points, color, face);
+        }
+    }
+}
+
+impl FaceApprox {
+    /// Triangulate the face, keeping the surface coordinates of all points
+    pub(crate) fn triangles(self) -> Vec<[TriangulationPoint; 3]> {
         let face_as_polygon = Polygon::new()
             .with_exterior(
                 self.exterior
@@ -63,25 +88,22 @@ impl Triangulate for FaceApprox {
                 .contains_triangle(triangle.map(|point| point.point_surface))
         });
 
-        let color = self.color.unwrap_or_default();
-
-        for triangle in triangles {
-            let points = triangle.map(|point| point.point_global);
-            mesh.push_triangle(points, color);
-        }
+        triangles
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_interop::mesh::Mesh;
+    use fj_interop::mesh::{FaceId, Mesh};
     use fj_math::{Point, Scalar};
 
     use crate::{
         algorithms::approx::{Approx, Tolerance},
         builder::{CycleBuilder, FaceBuilder},
         objects::Face,
+        operations::Insert,
         services::Services,
+        storage::Handle,
     };
 
     use super::Triangulate;
@@ -100,7 +122,8 @@ mod tests {
                 [a, b, c, d],
                 &mut services.objects,
             ))
-            .build(&mut services.objects);
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
 
         let a = Point::from(a).to_xyz();
         let b = Point::from(b).to_xyz();
@@ -142,7 +165,8 @@ mod tests {
                 [e, f, g, h],
                 &mut services.objects,
             ))
-            .build(&mut services.objects);
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
 
         let triangles = triangulate(face)?;
 
@@ -202,7 +226,8 @@ mod tests {
                 [a, b, c, d, e],
                 &mut services.objects,
             ))
-            .build(&mut services.objects);
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
 
         let triangles = triangulate(face)?;
 
@@ -219,7 +244,30 @@ mod tests {
         Ok(())
     }
 
-    fn triangulate(face: Face) -> anyhow::Result<Mesh<Point<3>>> {
+    #[test]
+    fn triangles_record_their_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let mesh = face.approx(tolerance).triangulate();
+
+        assert_eq!(mesh.triangles().count(), 2);
+        for triangle in mesh.triangles() {
+            assert_eq!(triangle.face, Some(FaceId::from(face.id())));
+        }
+
+        Ok(())
+    }
+
+    fn triangulate(face: Handle<Face>) -> anyhow::Result<Mesh<Point<3>>> {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
         Ok(face.approx(tolerance).triangulate())
     }
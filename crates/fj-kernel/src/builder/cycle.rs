@@ -1,4 +1,4 @@
-use fj_math::Point;
+use fj_math::{predicates, Point, Scalar, Sign, Winding};
 use itertools::Itertools;
 
 use crate::{
@@ -7,12 +7,25 @@ use crate::{
     operations::{BuildHalfEdge, Insert, UpdateHalfEdge},
     services::Service,
     storage::Handle,
+    validate::Validate,
 };
 
+use super::BuildError;
+
 /// Builder API for [`Cycle`]
+///
+/// Besides creating a whole cycle at once, a cycle can be constructed step by
+/// step, like a path: Start with [`CycleBuilder::move_to`], add half-edges with
+/// [`CycleBuilder::add_line_to`] and [`CycleBuilder::add_arc_to`], then finish
+/// with [`CycleBuilder::close`].
+///
+/// Misuse of these methods is recorded, and reported once the cycle is built.
+/// See [`CycleBuilder::try_build`].
 #[derive(Default)]
 pub struct CycleBuilder {
     half_edges: Vec<HalfEdge>,
+    start: Option<Point<2>>,
+    error: Option<BuildError>,
 }
 
 impl CycleBuilder {
@@ -46,7 +59,10 @@ impl CycleBuilder {
             })
             .collect();
 
-        Self { half_edges }
+        Self {
+            half_edges,
+            ..Self::default()
+        }
     }
 
     /// Create a polygon
@@ -65,15 +81,305 @@ impl CycleBuilder {
             })
             .collect();
 
-        Self { half_edges }
+        Self {
+            half_edges,
+            ..Self::default()
+        }
+    }
+
+    /// Create a polygon with the given winding
+    ///
+    /// Works like [`CycleBuilder::polygon`], except that the order of the
+    /// points is reversed, if they don't already have the given winding.
+    pub fn polygon_with_winding<P>(
+        points: impl IntoIterator<Item = P>,
+        winding: Winding,
+        objects: &mut Service<Objects>,
+    ) -> Self
+    where
+        P: Into<Point<2>>,
+    {
+        let mut points = points.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        let reverse = match predicates::polygon_orientation(&points) {
+            Sign::Positive => winding == Winding::Cw,
+            Sign::Negative => winding == Winding::Ccw,
+            Sign::Zero => false,
+        };
+        if reverse {
+            points.reverse();
+        }
+
+        Self::polygon(points, objects)
+    }
+
+    /// Define the point where the cycle starts
+    ///
+    /// This is an error, if half-edges have already been added.
+    pub fn move_to(mut self, point: impl Into<Point<2>>) -> Self {
+        if !self.half_edges.is_empty() {
+            return self.fail(BuildError::CycleStartMovedAfterHalfEdges);
+        }
+
+        self.start = Some(point.into());
+        self
+    }
+
+    /// Add a line segment from the end of the cycle to the given point
+    ///
+    /// This is an error, if [`CycleBuilder::move_to`] hasn't been called first.
+    pub fn add_line_to(
+        mut self,
+        point: impl Into<Point<2>>,
+        objects: &mut Service<Objects>,
+    ) -> Self {
+        let Some(start) = self.current_position() else {
+            return self.fail(BuildError::CycleStartNotDefined);
+        };
+        self.half_edges.push(HalfEdge::line_segment(
+            [start, point.into()],
+            None,
+            objects,
+        ));
+        self
+    }
+
+    /// Add an arc from the end of the cycle to the given point
+    ///
+    /// The arc is counterclockwise, if `radius` is positive, clockwise if it
+    /// is negative. It goes the shorter way around its circle, covering less
+    /// than half a turn.
+    ///
+    /// This is an error, if [`CycleBuilder::move_to`] hasn't been called
+    /// first, or if the given point is too far away to be reached with the
+    /// given radius.
+    pub fn add_arc_to(
+        mut self,
+        point: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        objects: &mut Service<Objects>,
+    ) -> Self {
+        let Some(start) = self.current_position() else {
+            return self.fail(BuildError::CycleStartNotDefined);
+        };
+        let end = point.into();
+        let radius = radius.into();
+
+        let half_chord = (end - start).magnitude() / 2.;
+        if half_chord > radius.abs() {
+            return self.fail(BuildError::ArcRadiusTooSmall {
+                radius,
+                start,
+                end,
+            });
+        }
+
+        let angle = (half_chord / radius.abs()).into_f64().asin() * 2.;
+        let angle = if radius < Scalar::ZERO { -angle } else { angle };
+
+        self.half_edges
+            .push(HalfEdge::arc(start, end, angle, objects));
+        self
+    }
+
+    /// Close the cycle, by adding a line segment back to its start
+    ///
+    /// No line segment is added, if the cycle already ends where it starts.
+    ///
+    /// This is an error, if [`CycleBuilder::move_to`] hasn't been called first.
+    pub fn close(self, objects: &mut Service<Objects>) -> Self {
+        let Some(start) = self.start_position() else {
+            return self.fail(BuildError::CycleStartNotDefined);
+        };
+        if self.current_position() == Some(start) {
+            return self;
+        }
+
+        self.add_line_to(start, objects)
+    }
+
+    /// Round the corners between line segments of the cycle
+    ///
+    /// Every corner where two line segments meet is replaced with an arc of
+    /// the given radius, which is tangent to both line segments. Corners that
+    /// involve other kinds of half-edges are left as they are.
+    ///
+    /// This is an error, if a line segment is too short to fit the arcs at
+    /// both of its ends.
+    pub fn fillet_corners(
+        self,
+        radius: impl Into<Scalar>,
+        objects: &mut Service<Objects>,
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let radius = radius.into();
+
+        let lines = self
+            .half_edges
+            .iter()
+            .map(|half_edge| match half_edge.curve() {
+                Curve::Line(_) => {
+                    Some([half_edge.start_position(), end_position(half_edge)])
+                }
+                Curve::Bezier(_) | Curve::Circle(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut trimmed = lines.clone();
+        let mut fillets = vec![None; lines.len()];
+
+        for (i, &line) in lines.iter().enumerate() {
+            let j = (i + 1) % lines.len();
+            let (Some([a, corner]), Some([_, b])) = (line, lines[j]) else {
+                continue;
+            };
+
+            let incoming = (corner - a).normalize();
+            let outgoing = (b - corner).normalize();
+
+            // The angle by which the cycle turns at the corner. Positive, if
+            // it turns counterclockwise.
+            let angle =
+                incoming.cross2d(&outgoing).atan2(incoming.dot(&outgoing));
+            if angle == Scalar::ZERO {
+                continue;
+            }
+
+            let distance = radius * (angle.abs() / 2.).into_f64().tan();
+            let start = corner - incoming * distance;
+            let end = corner + outgoing * distance;
+
+            if let Some([_, line_end]) = &mut trimmed[i] {
+                *line_end = start;
+            }
+            if let Some([line_start, _]) = &mut trimmed[j] {
+                *line_start = end;
+            }
+            fillets[i] = Some((start, end, angle));
+        }
+
+        let mut half_edges = Vec::new();
+        for ((half_edge, line), (trimmed_line, fillet)) in self
+            .half_edges
+            .into_iter()
+            .zip(lines)
+            .zip(trimmed.into_iter().zip(fillets))
+        {
+            match (line, trimmed_line) {
+                (Some([a, b]), Some([start, end])) => {
+                    // Fillets can use up a line segment completely, which
+                    // leaves a bit of floating-point noise.
+                    let length = (b - a).magnitude();
+                    let remaining = (end - start).dot(&(b - a)) / length;
+                    if remaining <= -length * 1e-9 {
+                        return Self {
+                            half_edges: Vec::new(),
+                            start: self.start,
+                            error: None,
+                        }
+                        .fail(
+                            BuildError::FilletRadiusTooLarge {
+                                radius,
+                                start: a,
+                                end: b,
+                            },
+                        );
+                    }
+
+                    if remaining > length * 1e-9 {
+                        half_edges.push(HalfEdge::line_segment(
+                            [start, end],
+                            None,
+                            objects,
+                        ));
+                    }
+                }
+                _ => half_edges.push(half_edge),
+            }
+
+            if let Some((start, end, angle)) = fillet {
+                half_edges.push(HalfEdge::arc(start, end, angle, objects));
+            }
+        }
+
+        Self {
+            half_edges,
+            start: self.start,
+            error: None,
+        }
     }
 
     /// Build the cycle
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the builder's methods have been misused. Use
+    /// [`CycleBuilder::try_build`] to handle that case instead.
     pub fn build(self, objects: &mut Service<Objects>) -> Cycle {
+        if let Some(err) = self.error {
+            panic!("Failed to build cycle: {err}");
+        }
+
         let half_edges = self
             .half_edges
             .into_iter()
             .map(|half_edge| half_edge.insert(objects));
         Cycle::new(half_edges)
     }
+
+    /// Build the cycle, returning an error if it can't be built
+    ///
+    /// Besides misuse of the builder's methods, this reports the cycle being
+    /// invalid. Its half-edges are validated before they are inserted, so no
+    /// invalid objects are left behind in `objects`.
+    pub fn try_build(
+        self,
+        objects: &mut Service<Objects>,
+    ) -> Result<Cycle, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        for half_edge in &self.half_edges {
+            half_edge
+                .validate_and_return_first_error()
+                .map_err(Box::new)?;
+        }
+
+        let cycle = self.build(objects);
+        cycle.validate_and_return_first_error().map_err(Box::new)?;
+
+        Ok(cycle)
+    }
+
+    fn fail(mut self, err: BuildError) -> Self {
+        // Only the first error is kept. Any later ones are likely to be
+        // consequences of it.
+        if self.error.is_none() {
+            self.error = Some(err);
+        }
+        self
+    }
+
+    fn start_position(&self) -> Option<Point<2>> {
+        match self.half_edges.first() {
+            Some(half_edge) => Some(half_edge.start_position()),
+            None => self.start,
+        }
+    }
+
+    fn current_position(&self) -> Option<Point<2>> {
+        match self.half_edges.last() {
+            Some(half_edge) => Some(end_position(half_edge)),
+            None => self.start_position(),
+        }
+    }
+}
+
+fn end_position(half_edge: &HalfEdge) -> Point<2> {
+    let [_, end] = half_edge.boundary();
+    half_edge.curve().point_from_path_coords(end)
 }
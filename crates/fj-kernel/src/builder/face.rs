@@ -1,13 +1,15 @@
 use fj_interop::mesh::Color;
+use fj_math::{predicates, Point, Sign, Winding};
 
 use crate::{
     objects::{Face, Objects, Surface},
     operations::Insert,
     services::Service,
     storage::Handle,
+    validate::Validate,
 };
 
-use super::CycleBuilder;
+use super::{BuildError, CycleBuilder};
 
 /// Builder API for [`Face`]
 pub struct FaceBuilder {
@@ -27,6 +29,41 @@ impl FaceBuilder {
         }
     }
 
+    /// Create a polygonal face with holes
+    ///
+    /// The exterior keeps the winding that its points are given in. The
+    /// interiors get the opposite winding, as required for a valid face,
+    /// regardless of the order of their points.
+    pub fn polygon_with_holes<P, I>(
+        surface: Handle<Surface>,
+        exterior: impl IntoIterator<Item = P>,
+        interiors: impl IntoIterator<Item = I>,
+        objects: &mut Service<Objects>,
+    ) -> Self
+    where
+        P: Into<Point<2>>,
+        I: IntoIterator<Item = P>,
+    {
+        let exterior = exterior.into_iter().map(Into::into).collect::<Vec<_>>();
+        let interior_winding = match predicates::polygon_orientation(&exterior)
+        {
+            Sign::Negative => Winding::Ccw,
+            Sign::Positive | Sign::Zero => Winding::Cw,
+        };
+
+        let mut face = Self::new(surface)
+            .with_exterior(CycleBuilder::polygon(exterior, objects));
+        for interior in interiors {
+            face = face.with_interior(CycleBuilder::polygon_with_winding(
+                interior,
+                interior_winding,
+                objects,
+            ));
+        }
+
+        face
+    }
+
     /// Replace the face's exterior cycle
     pub fn with_exterior(mut self, exterior: CycleBuilder) -> Self {
         self.exterior = exterior;
@@ -46,6 +83,11 @@ impl FaceBuilder {
     }
 
     /// Build the face
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the methods of any of the cycle builders have been misused.
+    /// Use [`FaceBuilder::try_build`] to handle that case instead.
     pub fn build(self, objects: &mut Service<Objects>) -> Face {
         let exterior = self.exterior.build(objects).insert(objects);
         let interiors = self
@@ -55,4 +97,24 @@ impl FaceBuilder {
 
         Face::new(self.surface, exterior, interiors, self.color)
     }
+
+    /// Build the face, returning an error if it can't be built
+    ///
+    /// See [`CycleBuilder::try_build`].
+    pub fn try_build(
+        self,
+        objects: &mut Service<Objects>,
+    ) -> Result<Face, BuildError> {
+        let exterior = self.exterior.try_build(objects)?.insert(objects);
+        let interiors = self
+            .interiors
+            .into_iter()
+            .map(|cycle| Ok(cycle.try_build(objects)?.insert(objects)))
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
+        let face = Face::new(self.surface, exterior, interiors, self.color);
+        face.validate_and_return_first_error().map_err(Box::new)?;
+
+        Ok(face)
+    }
 }
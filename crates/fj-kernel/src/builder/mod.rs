@@ -4,3 +4,58 @@ mod cycle;
 mod face;
 
 pub use self::{cycle::CycleBuilder, face::FaceBuilder};
+
+use fj_math::{Point, Scalar};
+
+use crate::validate::ValidationError;
+
+/// An error that can occur while building an object
+///
+/// Builder methods that are misused don't panic right away. They record the
+/// error, which is returned by the builder's `try_build` method. Their `build`
+/// method panics instead.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BuildError {
+    /// Half-edges of a cycle were added, without defining where it starts
+    #[error("Start of cycle must be defined using `move_to`")]
+    CycleStartNotDefined,
+
+    /// The start of a cycle was moved, after half-edges had been added
+    #[error("Can't move start of cycle after adding half-edges")]
+    CycleStartMovedAfterHalfEdges,
+
+    /// The radius of an arc is too small to reach its end point
+    #[error(
+        "Arc radius {radius} is too small to reach from {start:?} to {end:?}"
+    )]
+    ArcRadiusTooSmall {
+        /// The radius of the arc
+        radius: Scalar,
+
+        /// The start point of the arc
+        start: Point<2>,
+
+        /// The end point of the arc
+        end: Point<2>,
+    },
+
+    /// The radius of a fillet is too large for an adjacent line segment
+    #[error(
+        "Fillet radius {radius} is too large for line segment from {start:?} \
+        to {end:?}"
+    )]
+    FilletRadiusTooLarge {
+        /// The radius of the fillet
+        radius: Scalar,
+
+        /// The start point of the line segment
+        start: Point<2>,
+
+        /// The end point of the line segment
+        end: Point<2>,
+    },
+
+    /// The object that would have been built is invalid
+    #[error("Built object would be invalid")]
+    Invalid(#[from] Box<ValidationError>),
+}
@@ -0,0 +1,215 @@
+//! Canonical keys for geometry
+//!
+//! See [`CanonicalKey`].
+//!
+//! The object stores use these keys to deduplicate surfaces. Curves and
+//! vertices are not deduplicated, as they are not stored as geometry of their
+//! own. Curves are part of half-edges, and vertices are positioned by the
+//! half-edges they bound. Keys for curves and points are available to code
+//! that builds those objects.
+
+use fj_math::{Point, Scalar, Tolerance, Vector};
+
+use super::{
+    curve::{Curve, GlobalPath},
+    surface::SurfaceGeometry,
+};
+
+/// A key that identifies geometry, up to a tolerance
+///
+/// All numbers that make up the geometry are quantized to multiples of the
+/// tolerance. If two keys are equal, the geometry they were computed from is
+/// identical within that tolerance.
+///
+/// The reverse is not always true. Two numbers that are very close to each
+/// other can still be quantized differently, if a multiple of the tolerance
+/// lies between them. This makes keys suitable for finding duplicates, as long
+/// as a missed duplicate is not a problem.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CanonicalKey {
+    kind: Kind,
+    components: Vec<i64>,
+}
+
+impl CanonicalKey {
+    fn new(
+        kind: Kind,
+        components: impl IntoIterator<Item = Scalar>,
+        tolerance: Tolerance,
+    ) -> Option<Self> {
+        let quantum = tolerance.length();
+
+        let components = components
+            .into_iter()
+            .map(|component| {
+                let quantized = (component / quantum).round().into_f64();
+
+                // Above 2^53, quantized values are not exact anymore, but
+                // still deterministic. Values that don't fit into the key at
+                // all can't be distinguished from each other.
+                (quantized.abs() < MAX_QUANTIZED).then_some(quantized as i64)
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self { kind, components })
+    }
+}
+
+/// Geometry that a [`CanonicalKey`] can be computed for
+pub trait Canonicalize {
+    /// Compute the canonical key of the geometry
+    ///
+    /// Returns `None`, if the geometry is too large to be quantized using the
+    /// given tolerance.
+    fn canonical_key(&self, tolerance: Tolerance) -> Option<CanonicalKey>;
+}
+
+impl<const D: usize> Canonicalize for Point<D> {
+    fn canonical_key(&self, tolerance: Tolerance) -> Option<CanonicalKey> {
+        CanonicalKey::new(Kind::Point, self.coords.components, tolerance)
+    }
+}
+
+impl Canonicalize for Curve {
+    fn canonical_key(&self, tolerance: Tolerance) -> Option<CanonicalKey> {
+        let (kind, components) = match self {
+            Self::Bezier(bezier) => (
+                Kind::Bezier,
+                points(bezier.control_points()).collect::<Vec<_>>(),
+            ),
+            Self::Circle(circle) => (
+                Kind::Circle,
+                points([circle.center()])
+                    .chain(vectors([circle.a(), circle.b()]))
+                    .collect(),
+            ),
+            Self::Line(line) => (
+                Kind::Line,
+                points([line.origin()])
+                    .chain(vectors([line.direction()]))
+                    .collect(),
+            ),
+        };
+
+        CanonicalKey::new(kind, components, tolerance)
+    }
+}
+
+impl Canonicalize for GlobalPath {
+    fn canonical_key(&self, tolerance: Tolerance) -> Option<CanonicalKey> {
+        let (kind, components) = global_path(self);
+        CanonicalKey::new(kind, components, tolerance)
+    }
+}
+
+impl Canonicalize for SurfaceGeometry {
+    fn canonical_key(&self, tolerance: Tolerance) -> Option<CanonicalKey> {
+        let (kind, mut components) = global_path(&self.u);
+        components.extend(self.v.components);
+
+        CanonicalKey::new(Kind::Surface(Box::new(kind)), components, tolerance)
+    }
+}
+
+fn global_path(path: &GlobalPath) -> (Kind, Vec<Scalar>) {
+    match path {
+        GlobalPath::Bezier(bezier) => {
+            (Kind::Bezier, points(bezier.control_points()).collect())
+        }
+        GlobalPath::Circle(circle) => (
+            Kind::Circle,
+            points([circle.center()])
+                .chain(vectors([circle.a(), circle.b()]))
+                .collect(),
+        ),
+        GlobalPath::Ellipse(ellipse) => (
+            Kind::Ellipse,
+            points([ellipse.center()])
+                .chain(vectors([ellipse.a(), ellipse.b()]))
+                .collect(),
+        ),
+        GlobalPath::Line(line) => (
+            Kind::Line,
+            points([line.origin()])
+                .chain(vectors([line.direction()]))
+                .collect(),
+        ),
+    }
+}
+
+fn points<const D: usize, const N: usize>(
+    points: [Point<D>; N],
+) -> impl Iterator<Item = Scalar> {
+    points.into_iter().flat_map(|point| point.coords.components)
+}
+
+fn vectors<const D: usize, const N: usize>(
+    vectors: [Vector<D>; N],
+) -> impl Iterator<Item = Scalar> {
+    vectors.into_iter().flat_map(|vector| vector.components)
+}
+
+/// The kind of geometry a key was computed from
+///
+/// Geometry of different kinds can consist of the same numbers, but must never
+/// have the same key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+enum Kind {
+    Bezier,
+    Circle,
+    Ellipse,
+    Line,
+    Point,
+    Surface(Box<Kind>),
+}
+
+/// The limit for quantized values that can be represented in a key
+const MAX_QUANTIZED: f64 = (1u64 << 63) as f64;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Tolerance};
+
+    use crate::geometry::{curve::GlobalPath, surface::SurfaceGeometry};
+
+    use super::Canonicalize;
+
+    #[test]
+    fn points_within_tolerance() {
+        let tolerance = Tolerance::from(0.1);
+
+        let a = Point::from([1., 2., 3.]);
+        let b = Point::from([1.01, 2., 3.]);
+        let c = Point::from([1.2, 2., 3.]);
+
+        assert_eq!(a.canonical_key(tolerance), b.canonical_key(tolerance));
+        assert_ne!(a.canonical_key(tolerance), c.canonical_key(tolerance));
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let tolerance = Tolerance::from(5e-14);
+
+        let a = Point::from([1000., 2000., 3000.]);
+        let b = Point::from([1e6, 0., 0.]);
+
+        assert!(a.canonical_key(tolerance).is_some());
+        assert!(b.canonical_key(tolerance).is_none());
+    }
+
+    #[test]
+    fn different_kinds_of_geometry() {
+        let tolerance = Tolerance::from(0.1);
+
+        let line = GlobalPath::x_axis();
+        let surface = SurfaceGeometry {
+            u: line,
+            v: [0., 0., 0.].into(),
+        };
+
+        assert_ne!(
+            line.canonical_key(tolerance),
+            surface.canonical_key(tolerance)
+        );
+    }
+}
@@ -2,11 +2,19 @@
 //!
 //! See [`Curve`] and [`GlobalPath`].
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{
+    Bezier, Circle, Ellipse, Line, Point, Scalar, Transform, Vector,
+};
 
 /// A path through surface (2D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Curve {
+    /// A cubic Bézier curve
+    ///
+    /// Its path coordinates are its Bézier coordinates. More complex splines
+    /// can be made up of multiple half-edges.
+    Bezier(Bezier<2>),
+
     /// A circle
     Circle(Circle<2>),
 
@@ -69,6 +77,7 @@ impl Curve {
         point: impl Into<Point<1>>,
     ) -> Point<2> {
         match self {
+            Self::Bezier(bezier) => bezier.point_from_bezier_coords(point),
             Self::Circle(circle) => circle.point_from_circle_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
@@ -78,9 +87,22 @@ impl Curve {
 /// A path through global (3D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum GlobalPath {
+    /// A cubic Bézier curve
+    ///
+    /// Results from sweeping a [`Curve::Bezier`]. Splines through 3D space can
+    /// be made up of multiple Bézier curves.
+    Bezier(Bezier<3>),
+
     /// A circle
     Circle(Circle<3>),
 
+    /// An ellipse
+    ///
+    /// Results from transforming a circle in a way that doesn't preserve
+    /// angles, like a non-uniform scaling. Use [`GlobalPath::from_ellipse`] to
+    /// construct this, to make sure that circles are represented as such.
+    Ellipse(Ellipse<3>),
+
     /// A line
     Line(Line<3>),
 }
@@ -117,6 +139,16 @@ impl GlobalPath {
         Self::Circle(Circle::from_center_and_radius(Point::origin(), radius))
     }
 
+    /// Build a path from the given ellipse
+    ///
+    /// Returns a [`GlobalPath::Circle`], if the ellipse is a circle.
+    pub fn from_ellipse(ellipse: Ellipse<3>) -> Self {
+        match ellipse.to_circle() {
+            Some(circle) => Self::Circle(circle),
+            None => Self::Ellipse(ellipse),
+        }
+    }
+
     /// Construct a line from two points
     ///
     /// Also returns the coordinates of the points on the path.
@@ -130,7 +162,9 @@ impl GlobalPath {
     /// Access the origin of the path's coordinate system
     pub fn origin(&self) -> Point<3> {
         match self {
+            Self::Bezier(bezier) => bezier.point_from_bezier_coords([0.]),
             Self::Circle(circle) => circle.center() + circle.a(),
+            Self::Ellipse(ellipse) => ellipse.center() + ellipse.a(),
             Self::Line(line) => line.origin(),
         }
     }
@@ -141,30 +175,266 @@ impl GlobalPath {
         point: impl Into<Point<1>>,
     ) -> Point<3> {
         match self {
+            Self::Bezier(bezier) => bezier.point_from_bezier_coords(point),
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
     }
 
     /// Convert a vector on the path into global coordinates
+    ///
+    /// For lines, this is exact. For circles and ellipses, the result is the
+    /// vector from the center to the point at the given angle.
+    ///
+    /// Bézier curves are not linear, so no vector in global coordinates
+    /// corresponds to a vector along them. The result is the vector along the
+    /// derivative at the origin of the path's coordinate system, which
+    /// approximates the curve close to that origin. Use
+    /// [`GlobalPath::point_from_path_coords`] to get points on the curve.
     pub fn vector_from_path_coords(
         &self,
         vector: impl Into<Vector<1>>,
     ) -> Vector<3> {
         match self {
+            Self::Bezier(bezier) => {
+                bezier.derivative(Point::from([0.])) * vector.into().t
+            }
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords(vector)
+            }
             Self::Line(line) => line.vector_from_line_coords(vector),
         }
     }
 
+    /// Compute the derivative of the path at the given point
+    ///
+    /// The derivative is relative to the path's coordinates. It is tangent to
+    /// the path, but its magnitude is not necessarily one.
+    pub fn derivative(&self, point: impl Into<Point<1>>) -> Vector<3> {
+        let point = point.into();
+
+        match self {
+            Self::Bezier(bezier) => bezier.derivative(point),
+            Self::Circle(circle) => {
+                // The derivative of `a * cos(t) + b * sin(t)` is that same
+                // expression, a quarter turn later.
+                circle.vector_from_circle_coords([point.t + Scalar::PI / 2.])
+            }
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords([point.t + Scalar::PI / 2.])
+            }
+            Self::Line(line) => line.direction(),
+        }
+    }
+
     /// Transform the path
+    ///
+    /// A circle becomes an ellipse, if the transform doesn't preserve angles.
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
         match self {
-            Self::Circle(curve) => {
-                Self::Circle(transform.transform_circle(&curve))
+            Self::Bezier(curve) => {
+                Self::Bezier(transform.transform_bezier(&curve))
+            }
+            Self::Circle(curve) => Self::from_ellipse(
+                transform.transform_ellipse(&Ellipse::from(curve)),
+            ),
+            Self::Ellipse(curve) => {
+                Self::from_ellipse(transform.transform_ellipse(&curve))
             }
             Self::Line(curve) => Self::Line(transform.transform_line(&curve)),
         }
     }
 }
+
+/// Arc-length parameterization of a path
+///
+/// The coordinates of a path are not necessarily proportional to the distance
+/// traveled along it. A circle's coordinates are angles, for example. This
+/// trait provides conversions between path coordinates and arc length, which
+/// is the distance along the path.
+///
+/// Only [`ArcLength::speed`] needs to be implemented. Everything else is
+/// computed from it numerically, which is exact for paths with constant speed,
+/// like lines and circles.
+pub trait ArcLength {
+    /// Compute the magnitude of the path's derivative at the given point
+    ///
+    /// This is the arc length per unit of path coordinates, at that point.
+    fn speed(&self, point: Point<1>) -> Scalar;
+
+    /// Compute the arc length between two points on the path
+    ///
+    /// The result is negative, if the second point comes before the first.
+    fn arc_length(&self, range: [Point<1>; 2]) -> Scalar {
+        let [start, end] = range.map(|point| point.t);
+
+        // Composite Gauss-Legendre quadrature, over segments of at most an
+        // eighth of a turn, for paths whose coordinates are angles.
+        let num_segments = ((end - start).abs() / (Scalar::PI / 4.))
+            .ceil()
+            .into_f64()
+            .max(1.);
+        let segment = (end - start) / num_segments;
+
+        let mut length = Scalar::ZERO;
+        for i in 0..num_segments as usize {
+            let center = start + segment * (i as f64 + 0.5);
+
+            for (node, weight) in GAUSS_LEGENDRE {
+                let t = center + segment / 2. * node;
+                length += self.speed(Point::from([t])) * weight * segment / 2.;
+            }
+        }
+
+        length
+    }
+
+    /// Compute the point that is the given arc length away from `start`
+    ///
+    /// A negative `length` results in a point before `start`.
+    fn point_at_arc_length(
+        &self,
+        start: Point<1>,
+        length: impl Into<Scalar>,
+    ) -> Point<1> {
+        let length = length.into();
+
+        let mut point = start;
+        for _ in 0..MAX_ITERATIONS {
+            let speed = self.speed(point);
+            if speed == Scalar::ZERO {
+                break;
+            }
+
+            let error = self.arc_length([start, point]) - length;
+            if error.abs() <= Scalar::from(1e-12) * length.abs().max(1.) {
+                break;
+            }
+
+            point = Point::from([point.t - error / speed]);
+        }
+
+        point
+    }
+
+    /// Sample the path between two points, evenly spaced by arc length
+    ///
+    /// Returns the path coordinates of the samples, including both points of
+    /// the range. The samples are at most `max_spacing` apart, which must be
+    /// larger than zero.
+    fn sample_by_arc_length(
+        &self,
+        range: [Point<1>; 2],
+        max_spacing: impl Into<Scalar>,
+    ) -> Vec<Point<1>> {
+        let max_spacing = max_spacing.into();
+        assert!(
+            max_spacing > Scalar::ZERO,
+            "Spacing of samples must be larger than zero"
+        );
+
+        let [start, end] = range;
+        let length = self.arc_length(range);
+        let num_intervals =
+            (length.abs() / max_spacing).ceil().into_f64().max(1.) as usize;
+        let spacing = length / num_intervals as f64;
+
+        let mut samples = vec![start];
+        let mut previous = start;
+        for _ in 1..num_intervals {
+            // Measuring from the previous sample keeps the quadrature cheap.
+            previous = self.point_at_arc_length(previous, spacing);
+            samples.push(previous);
+        }
+        samples.push(end);
+
+        samples
+    }
+}
+
+impl ArcLength for Curve {
+    fn speed(&self, point: Point<1>) -> Scalar {
+        match self {
+            Self::Bezier(bezier) => bezier.derivative(point).magnitude(),
+            Self::Circle(circle) => circle.radius(),
+            Self::Line(line) => line.direction().magnitude(),
+        }
+    }
+}
+
+impl ArcLength for GlobalPath {
+    fn speed(&self, point: Point<1>) -> Scalar {
+        self.derivative(point).magnitude()
+    }
+}
+
+/// Nodes and weights of 5-point Gauss-Legendre quadrature on `[-1, 1]`
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0., 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_47),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_47),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+];
+
+const MAX_ITERATIONS: usize = 32;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Bezier, Circle, Ellipse, Point, Scalar, Vector};
+
+    use super::{ArcLength, GlobalPath};
+
+    #[test]
+    fn arc_length() {
+        let circle = GlobalPath::Circle(Circle::from_center_and_radius(
+            Point::from([0., 0., 0.]),
+            2.,
+        ));
+        let range = [Scalar::ZERO, Scalar::PI].map(|t| Point::from([t]));
+        let half_circumference = circle.arc_length(range);
+        assert!((half_circumference - Scalar::TAU).abs() < Scalar::from(1e-12));
+
+        // An ellipse with semi-axes 2 and 1.
+        let ellipse = GlobalPath::Ellipse(Ellipse::new(
+            Point::from([0., 0., 0.]),
+            Vector::from([2., 0., 0.]),
+            Vector::from([0., 1., 0.]),
+        ));
+        let range = [Scalar::ZERO, Scalar::TAU].map(|t| Point::from([t]));
+        let circumference = ellipse.arc_length(range);
+        assert!((circumference - 9.688_448_220_5).abs() < Scalar::from(1e-9));
+
+        let samples = ellipse.sample_by_arc_length(range, 1.);
+        assert_eq!(samples.len(), 11);
+        for pair in samples.windows(2) {
+            let length = ellipse.arc_length([pair[0], pair[1]]);
+            assert!((length - circumference / 10.).abs() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn vector_from_bezier_coords() {
+        let bezier = GlobalPath::Bezier(Bezier::new(
+            [[0., 0., 0.], [1., 2., 0.], [2., -2., 0.], [3., 0., 1.]]
+                .map(Point::from),
+        ));
+        let derivative = bezier.derivative([0.]);
+
+        // The conversion is linear, even though the curve is not.
+        for t in [0.25, 0.5, 1.] {
+            let vector = bezier.vector_from_path_coords([t]);
+            assert_eq!(vector, derivative * t);
+            assert_eq!(bezier.vector_from_path_coords([-t]), -vector);
+        }
+
+        // Close to the origin, the vector approximates the curve.
+        let t = 1e-6;
+        let offset = bezier.point_from_path_coords([t]) - bezier.origin();
+        let error = (offset - bezier.vector_from_path_coords([t])).magnitude();
+        assert!(error < Scalar::from(t * 1e-3));
+    }
+}
@@ -1,4 +1,5 @@
 //! Types that are tied to objects, but aren't objects themselves
 
+pub mod canonical;
 pub mod curve;
 pub mod surface;
@@ -1,6 +1,6 @@
 //! The geometry that defines a surface
 
-use fj_math::{Line, Plane, Point, Transform, Vector};
+use fj_math::{Line, Plane, Point, Scalar, Transform, Vector};
 
 use super::curve::GlobalPath;
 
@@ -26,6 +26,9 @@ impl SurfaceGeometry {
     }
 
     /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// This is only exact for planes. On curved surfaces, the u-component is
+    /// converted as described in [`GlobalPath::vector_from_path_coords`].
     pub fn vector_from_surface_coords(
         &self,
         vector: impl Into<Vector<2>>,
@@ -35,19 +38,129 @@ impl SurfaceGeometry {
             + self.path_to_line().vector_from_line_coords([vector.v])
     }
 
+    /// Compute the partial derivatives of the surface at the given point
+    ///
+    /// Returns the derivatives along the u- and v-axis, relative to surface
+    /// coordinates. Their magnitude is not necessarily one.
+    pub fn derivatives_at(&self, point: impl Into<Point<2>>) -> [Vector<3>; 2] {
+        let point = point.into();
+        [self.u.derivative([point.u]), self.v]
+    }
+
+    /// Compute the normal of the surface at the given point
+    ///
+    /// The normal points towards the side of the surface, from which its
+    /// coordinate system appears right-handed. That is the front side of a
+    /// face with [`Handedness::RightHanded`].
+    ///
+    /// [`Handedness::RightHanded`]: crate::objects::Handedness::RightHanded
+    pub fn normal_at(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        let [du, dv] = self.derivatives_at(point);
+        du.cross(&dv).normalize()
+    }
+
     fn path_to_line(&self) -> Line<3> {
         Line::from_origin_and_direction(self.u.origin(), self.v)
     }
 
     /// Project the global point into the surface
+    ///
+    /// Returns the surface coordinates of the point on the surface, that is
+    /// closest to the given point. See [`SurfaceGeometry::project_point`].
     pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
-        let GlobalPath::Line(line) = self.u else {
-            todo!("Projecting point into non-plane surface is not supported")
+        let (surface_coords, _) = self.project_point(point);
+        surface_coords
+    }
+
+    /// Find the point on the surface that is closest to the given point
+    ///
+    /// Returns the surface coordinates of that point, and its distance to the
+    /// given point. Planes are handled exactly. For curved surfaces, the result
+    /// is found iteratively, starting from the closest of a number of samples
+    /// along the u-axis. This finds the global minimum for cylinders, but only
+    /// a local one for surfaces with a more complex u-axis.
+    ///
+    /// The u-coordinate of the result is within the range that the u-axis is
+    /// defined for: `[0, 1]` for Bézier curves, `[0, τ)` for circles and
+    /// ellipses.
+    pub fn project_point(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> (Point<2>, Scalar) {
+        let point = point.into();
+
+        let surface_coords = match self.u {
+            GlobalPath::Line(line) => {
+                let plane = Plane::from_parametric(
+                    line.origin(),
+                    line.direction(),
+                    self.v,
+                );
+                plane.project_point(point)
+            }
+            GlobalPath::Bezier(_) => {
+                self.project_point_iteratively(point, UDomain::Bounded)
+            }
+            GlobalPath::Circle(_) | GlobalPath::Ellipse(_) => {
+                self.project_point_iteratively(point, UDomain::Periodic)
+            }
+        };
+
+        let distance = (self.point_from_surface_coords(surface_coords) - point)
+            .magnitude();
+
+        (surface_coords, distance)
+    }
+
+    fn project_point_iteratively(
+        &self,
+        point: Point<3>,
+        domain: UDomain,
+    ) -> Point<2> {
+        // The v-axis is straight, so for any `u`, the closest `v` can be
+        // computed directly. That leaves a one-dimensional problem.
+        let closest = |u: Scalar| {
+            let offset = point - self.u.point_from_path_coords([u]);
+            let v = offset.dot(&self.v) / self.v.dot(&self.v);
+            Point::from([u, v])
+        };
+        let distance = |u: Scalar| {
+            (self.point_from_surface_coords(closest(u)) - point).magnitude()
         };
 
-        let plane =
-            Plane::from_parametric(line.origin(), line.direction(), self.v);
-        plane.project_point(point)
+        let end = domain.end();
+        let mut u = (0..=NUM_SAMPLES)
+            .map(|i| end * (i as f64 / NUM_SAMPLES as f64))
+            .min_by_key(|&u| distance(u))
+            .expect("Range is not empty");
+
+        // Gauss-Newton iteration, which minimizes the distance by solving the
+        // normal equations of the linearized problem.
+        for _ in 0..MAX_ITERATIONS {
+            let coords = closest(u);
+            let [du, dv] = self.derivatives_at(coords);
+            let residual = self.point_from_surface_coords(coords) - point;
+
+            let [a, b, c] = [du.dot(&du), du.dot(&dv), dv.dot(&dv)];
+            let determinant = a * c - b * b;
+            if determinant == Scalar::ZERO {
+                break;
+            }
+
+            let step =
+                (dv.dot(&residual) * b - du.dot(&residual) * c) / determinant;
+            let previous = u;
+            u = domain.restrict(u + step);
+
+            // Steps that would leave a bounded domain are cut short, so the
+            // actual change is what matters for convergence.
+            let change = (u - previous).abs();
+            if change <= Scalar::from(1e-15) * u.abs().max(Scalar::ONE) {
+                break;
+            }
+        }
+
+        closest(domain.restrict(u))
     }
 
     /// Transform the surface geometry
@@ -59,9 +172,39 @@ impl SurfaceGeometry {
     }
 }
 
+const NUM_SAMPLES: usize = 16;
+const MAX_ITERATIONS: usize = 16;
+
+/// The range of coordinates that the u-axis of a curved surface is defined for
+#[derive(Clone, Copy)]
+enum UDomain {
+    /// The coordinates range from 0 to 1, as is the case for Bézier curves
+    Bounded,
+
+    /// The coordinates repeat every full turn, as is the case for circles and
+    /// ellipses
+    Periodic,
+}
+
+impl UDomain {
+    fn end(self) -> Scalar {
+        match self {
+            Self::Bounded => Scalar::ONE,
+            Self::Periodic => Scalar::TAU,
+        }
+    }
+
+    fn restrict(self, u: Scalar) -> Scalar {
+        match self {
+            Self::Bounded => u.clamp(Scalar::ZERO, Scalar::ONE),
+            Self::Periodic => u - (u / Scalar::TAU).floor() * Scalar::TAU,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use fj_math::{Bezier, Circle, Ellipse, Line, Point, Scalar, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::geometry::{curve::GlobalPath, surface::SurfaceGeometry};
@@ -97,4 +240,109 @@ mod tests {
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn normal_at() {
+        let surface = SurfaceGeometry {
+            u: GlobalPath::Circle(Circle::from_center_and_radius(
+                Point::from([0., 0., 0.]),
+                2.,
+            )),
+            v: Vector::from([0., 0., 3.]),
+        };
+
+        let [du, dv] = surface.derivatives_at([0., 0.]);
+        assert!(
+            (du - Vector::from([0., 2., 0.])).magnitude() < Scalar::from(1e-12)
+        );
+        assert_eq!(dv, Vector::from([0., 0., 3.]));
+
+        // The normal of a cylinder points outward, if the circle's coordinates
+        // go counter-clockwise around the sweep direction.
+        let normal = surface.normal_at([Scalar::PI / 2., 0.]);
+        assert!(
+            (normal - Vector::from([0., 1., 0.])).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn project_point() {
+        let plane = SurfaceGeometry {
+            u: GlobalPath::x_axis(),
+            v: Vector::from([0., 2., 0.]),
+        };
+        let (coords, distance) = plane.project_point([1., 2., 3.]);
+        assert_eq!(coords, Point::from([1., 1.]));
+        assert_eq!(distance, Scalar::from(3.));
+
+        let cylinder = SurfaceGeometry {
+            u: GlobalPath::Circle(Circle::from_center_and_radius(
+                Point::from([0., 0., 0.]),
+                2.,
+            )),
+            v: Vector::from([0., 0., 3.]),
+        };
+        let (coords, distance) = cylinder.project_point([0., 3., 1.5]);
+        assert!(
+            (coords - Point::from([Scalar::PI / 2., Scalar::from(0.5)]))
+                .magnitude()
+                < Scalar::from(1e-9)
+        );
+        assert!((distance - Scalar::ONE).abs() < Scalar::from(1e-9));
+    }
+
+    #[test]
+    fn project_point_onto_bezier_surface() {
+        let surface = SurfaceGeometry {
+            u: GlobalPath::Bezier(Bezier::new([
+                [0., 0., 0.],
+                [1., 1., 0.],
+                [2., 1., 0.],
+                [3., 0., 0.],
+            ])),
+            v: Vector::from([0., 0., 1.]),
+        };
+
+        let point = surface.point_from_surface_coords([0.3, 2.]);
+        let coords = surface.project_global_point(point);
+        assert!(
+            (coords - Point::from([0.3, 2.])).magnitude() < Scalar::from(1e-9)
+        );
+
+        // Points beyond the end of the curve are projected onto its end, not
+        // onto the curve's extension.
+        let (coords, distance) = surface.project_point([10., 0., 0.5]);
+        assert!(
+            (coords - Point::from([1., 0.5])).magnitude() < Scalar::from(1e-9)
+        );
+        assert!((distance - Scalar::from(7.)).abs() < Scalar::from(1e-9));
+    }
+
+    #[test]
+    fn project_point_onto_ellipse_surface() {
+        let surface = SurfaceGeometry {
+            u: GlobalPath::Ellipse(Ellipse::new(
+                [0., 0., 0.],
+                [2., 0., 0.],
+                [0., 1., 0.],
+            )),
+            v: Vector::from([0., 0., 1.]),
+        };
+
+        let point = surface.point_from_surface_coords([1., 0.5]);
+        let coords = surface.project_global_point(point);
+        assert!(
+            (coords - Point::from([1., 0.5])).magnitude() < Scalar::from(1e-9)
+        );
+
+        // The result is normalized to a full turn.
+        let point = surface.point_from_surface_coords([-0.1, 0.]);
+        let coords = surface.project_global_point(point);
+        assert!(
+            (coords - Point::from([Scalar::TAU - 0.1, Scalar::ZERO]))
+                .magnitude()
+                < Scalar::from(1e-9)
+        );
+    }
 }
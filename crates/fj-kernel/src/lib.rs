@@ -92,6 +92,8 @@ pub mod builder;
 pub mod geometry;
 pub mod objects;
 pub mod operations;
+#[cfg(feature = "serde")]
+pub mod serialize;
 pub mod services;
 pub mod storage;
 pub mod validate;
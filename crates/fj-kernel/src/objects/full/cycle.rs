@@ -1,7 +1,6 @@
 use std::slice;
 
-use fj_math::{Scalar, Winding};
-use itertools::Itertools;
+use fj_math::{predicates, Point, Scalar, Sign, Winding};
 
 use crate::{geometry::curve::Curve, objects::HalfEdge, storage::Handle};
 
@@ -28,54 +27,84 @@ impl Cycle {
     /// Please note that this is not *the* winding of the cycle, only one of the
     /// two possible windings, depending on the direction you look at the
     /// surface that the cycle is defined on from.
+    ///
+    /// A cycle that doesn't enclose any area, like an empty cycle or one that
+    /// goes back and forth along a line, has no winding. Such a cycle is
+    /// considered to wind counterclockwise.
     pub fn winding(&self) -> Winding {
-        // The cycle could be made up of one or two circles. If that is the
-        // case, the winding of the cycle is determined by the winding of the
-        // first circle.
-        if self.half_edges.len() < 3 {
-            let first = self
-                .half_edges()
-                .next()
-                .expect("Invalid cycle: expected at least one half-edge");
-
-            let [a, b] = first.boundary();
-            let edge_direction_positive = a < b;
-
-            let circle = match first.curve() {
-                Curve::Circle(circle) => circle,
-                Curve::Line(_) => unreachable!(
-                    "Invalid cycle: less than 3 edges, but not all are circles"
-                ),
-            };
-            let cross_positive = circle.a().cross2d(&circle.b()) > Scalar::ZERO;
-
-            if edge_direction_positive == cross_positive {
-                return Winding::Ccw;
-            } else {
-                return Winding::Cw;
+        let is_polygon = self
+            .half_edges()
+            .all(|half_edge| matches!(half_edge.curve(), Curve::Line(_)));
+
+        if is_polygon {
+            let points = self
+                .half_edges
+                .iter()
+                .map(|half_edge| half_edge.start_position())
+                .collect::<Vec<_>>();
+
+            // This is exact, but only considers a single corner. If that is
+            // degenerate, the area below can still tell.
+            match predicates::polygon_orientation(&points) {
+                Sign::Positive => return Winding::Ccw,
+                Sign::Negative => return Winding::Cw,
+                Sign::Zero => {}
             }
         }
 
-        // Now that we got the special case out of the way, we can treat the
-        // cycle as a polygon:
-        // https://stackoverflow.com/a/1165943
-
-        let mut sum = Scalar::ZERO;
+        // By Green's theorem, the integral of `p × p'` along the cycle is twice
+        // the area that it encloses, signed according to its winding.
+        let area = self
+            .half_edges()
+            .map(|half_edge| {
+                integrate_cross_product(half_edge.curve(), half_edge.boundary())
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+
+        if area < Scalar::ZERO {
+            Winding::Cw
+        } else {
+            Winding::Ccw
+        }
+    }
+}
 
-        for (a, b) in self.half_edges.iter().circular_tuple_windows() {
-            let [a, b] = [a, b].map(|half_edge| half_edge.start_position());
+/// Integrate `p × p'` along a curve, within the given boundary
+fn integrate_cross_product(curve: Curve, boundary: [Point<1>; 2]) -> Scalar {
+    let [t0, t1] = boundary.map(|point| point.t);
 
-            sum += (b.u - a.u) * (b.v + a.v);
+    match curve {
+        Curve::Line(line) => {
+            line.origin().coords.cross2d(&line.direction()) * (t1 - t0)
         }
-
-        if sum > Scalar::ZERO {
-            return Winding::Cw;
+        Curve::Circle(circle) => {
+            let [p0, p1] =
+                boundary.map(|point| circle.point_from_circle_coords(point));
+            circle.center().coords.cross2d(&(p1 - p0))
+                + circle.a().cross2d(&circle.b()) * (t1 - t0)
         }
-        if sum < Scalar::ZERO {
-            return Winding::Ccw;
+        Curve::Bezier(bezier) => {
+            // The integrand is a polynomial of degree 5, which Gauss-Legendre
+            // quadrature with three nodes integrates exactly.
+            let nodes = [-(0.6_f64.sqrt()), 0., 0.6_f64.sqrt()];
+            let weights = [5. / 9., 8. / 9., 5. / 9.];
+
+            let half_length = (t1 - t0) / 2.;
+            let mid = t0 + half_length;
+
+            nodes
+                .into_iter()
+                .zip(weights)
+                .map(|(node, weight)| {
+                    let t = Point::from([mid + half_length * node]);
+                    let p = bezier.point_from_bezier_coords(t);
+                    let d = bezier.derivative(t);
+
+                    p.coords.cross2d(&d) * weight
+                })
+                .fold(Scalar::ZERO, |sum, value| sum + value)
+                * half_length
         }
-
-        unreachable!("Encountered invalid cycle: {self:#?}");
     }
 }
 
@@ -83,3 +112,66 @@ impl Cycle {
 ///
 /// Returned by [`Cycle::half_edges`].
 pub type HalfEdgesOfCycle<'a> = slice::Iter<'a, Handle<HalfEdge>>;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Winding;
+
+    use crate::{
+        objects::{Cycle, HalfEdge},
+        operations::{BuildHalfEdge, Insert},
+        services::Services,
+    };
+
+    #[test]
+    fn winding_of_cycle_with_curved_edge() {
+        let mut services = Services::new();
+
+        // The vertices alone don't form a polygon. The curve decides.
+        let line = HalfEdge::line_segment(
+            [[0., 0.], [3., 0.]],
+            None,
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let bezier = HalfEdge::bezier(
+            [[3., 0.], [2., 2.], [1., 2.], [0., 0.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let cycle = Cycle::new([line, bezier]);
+        assert_eq!(cycle.winding(), Winding::Ccw);
+
+        let line = HalfEdge::line_segment(
+            [[0., 0.], [3., 0.]],
+            None,
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let bezier = HalfEdge::bezier(
+            [[3., 0.], [2., -2.], [1., -2.], [0., 0.]],
+            &mut services.objects,
+        )
+        .insert(&mut services.objects);
+        let cycle = Cycle::new([line, bezier]);
+        assert_eq!(cycle.winding(), Winding::Cw);
+    }
+
+    #[test]
+    fn winding_of_degenerate_cycle() {
+        let mut services = Services::new();
+
+        let points = [[0., 0.], [1., 0.], [2., 0.]];
+        let half_edges = [[0, 1], [1, 2], [2, 0]].map(|[a, b]| {
+            HalfEdge::line_segment(
+                [points[a], points[b]],
+                None,
+                &mut services.objects,
+            )
+            .insert(&mut services.objects)
+        });
+
+        assert_eq!(Cycle::new(half_edges).winding(), Winding::Ccw);
+        assert_eq!(Cycle::new([]).winding(), Winding::Ccw);
+    }
+}
@@ -4,6 +4,7 @@ use fj_interop::mesh::Color;
 use fj_math::Winding;
 
 use crate::{
+    algorithms::texture::Texture,
     objects::{Cycle, Surface},
     storage::Handle,
 };
@@ -38,6 +39,7 @@ pub struct Face {
     exterior: Handle<Cycle>,
     interiors: Vec<Handle<Cycle>>,
     color: Option<Color>,
+    texture: Option<Texture>,
 }
 
 impl Face {
@@ -55,9 +57,18 @@ impl Face {
             exterior,
             interiors,
             color,
+            texture: None,
         }
     }
 
+    /// Apply a texture to the face
+    ///
+    /// See [`Texture`].
+    pub fn with_texture(mut self, texture: Option<Texture>) -> Self {
+        self.texture = texture;
+        self
+    }
+
     /// Access the surface of the face
     pub fn surface(&self) -> &Handle<Surface> {
         &self.surface
@@ -85,6 +96,11 @@ impl Face {
         self.color
     }
 
+    /// Access the texture of the face
+    pub fn texture(&self) -> Option<Texture> {
+        self.texture
+    }
+
     /// Determine handed-ness of the face's front-side coordinate system
     ///
     /// A face is defined on a surface, which has a coordinate system. Since
@@ -1,4 +1,10 @@
-use std::collections::BTreeSet;
+use std::{
+    cmp::Ordering,
+    collections::BTreeSet,
+    hash::{Hash, Hasher},
+};
+
+use fj_math::{Scalar, Transform};
 
 use crate::{
     objects::{Face, Shell},
@@ -9,13 +15,23 @@ use crate::{
 /// one shell, but if the Solid contains cavities they will be represented by a
 /// shell each, as well as a shell for the outside.
 ///
+/// Besides its own shells, a solid can contain [`Instance`]s of other solids.
+/// This allows a part that is repeated many times, as in a pattern or an
+/// assembly, to be stored only once.
+///
 /// # Implementation Note
 ///
 /// The shells that form the boundaries of the solid must not intersect. This is
 /// not currently validated.
+///
+/// Instances are resolved by approximation, bounding volumes, transformation,
+/// reversal, and serialization. Other algorithms, like mass properties,
+/// interference checks, and validation, only look at the solid's own shells so
+/// far.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Solid {
     shells: BTreeSet<Handle<Shell>>,
+    instances: BTreeSet<Instance>,
 }
 
 impl Solid {
@@ -23,14 +39,31 @@ impl Solid {
     pub fn new(shells: impl IntoIterator<Item = Handle<Shell>>) -> Self {
         Self {
             shells: shells.into_iter().collect(),
+            instances: BTreeSet::new(),
         }
     }
 
+    /// Add instances of other solids to the solid
+    pub fn with_instances(
+        mut self,
+        instances: impl IntoIterator<Item = Instance>,
+    ) -> Self {
+        self.instances.extend(instances);
+        self
+    }
+
     /// Access the solid's shells
+    ///
+    /// This doesn't include the shells of any instances.
     pub fn shells(&self) -> impl Iterator<Item = &Handle<Shell>> {
         self.shells.iter()
     }
 
+    /// Access the instances of other solids within the solid
+    pub fn instances(&self) -> impl Iterator<Item = &Instance> {
+        self.instances.iter()
+    }
+
     /// Find the given face in the solid
     pub fn find_face(&self, face: &Handle<Face>) -> Option<Handle<Face>> {
         for shell in self.shells() {
@@ -42,3 +75,69 @@ impl Solid {
         None
     }
 }
+
+/// A transformed reference to a [`Solid`]
+///
+/// Instead of copying the referenced solid, and all of its objects, an
+/// instance is resolved lazily, by the algorithms that need its transformed
+/// geometry.
+#[derive(Clone, Debug)]
+pub struct Instance {
+    solid: Handle<Solid>,
+    transform: Transform,
+}
+
+impl Instance {
+    /// Construct an instance of `Instance`
+    pub fn new(solid: Handle<Solid>, transform: Transform) -> Self {
+        Self { solid, transform }
+    }
+
+    /// Access the referenced solid
+    pub fn solid(&self) -> &Handle<Solid> {
+        &self.solid
+    }
+
+    /// Access the transform that is applied to the referenced solid
+    pub fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    fn transform_data(&self) -> Vec<Scalar> {
+        self.transform
+            .data()
+            .iter()
+            .copied()
+            .map(Scalar::from)
+            .collect()
+    }
+}
+
+impl Eq for Instance {}
+
+impl PartialEq for Instance {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Hash for Instance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.solid.hash(state);
+        self.transform_data().hash(state);
+    }
+}
+
+impl Ord for Instance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.solid
+            .cmp(&other.solid)
+            .then_with(|| self.transform_data().cmp(&other.transform_data()))
+    }
+}
+
+impl PartialOrd for Instance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,239 @@
+use std::{collections::HashSet, marker::PhantomData};
+
+use crate::storage::{Handle, ObjectId};
+
+use super::{
+    BehindHandle, Cycle, Face, GlobalEdge, HalfEdge, Object, Shell, Sketch,
+    Solid, Surface, Vertex,
+};
+
+/// Access the objects that an object refers to, directly or indirectly
+///
+/// This is implemented for all objects. Use [`ObjectIters::iter_objects`] to
+/// walk the whole graph below an object, or [`ObjectIters::all`] to only get
+/// the objects of a specific type.
+pub trait ObjectIters {
+    /// Access the objects that this object refers to directly
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>>;
+
+    /// Iterate over all objects that this object refers to
+    ///
+    /// This includes objects that are referred to indirectly, like the
+    /// vertices of a face. Every object is only yielded once, even if it's
+    /// referred to multiple times, as is usual for shared edges.
+    fn iter_objects(&self) -> ObjectIter {
+        ObjectIter::from_objects(self.referenced_objects())
+    }
+
+    /// Iterate over all objects of type `T` that this object refers to
+    ///
+    /// See [`ObjectIters::iter_objects`].
+    fn all<T>(&self) -> OfType<T>
+    where
+        Handle<T>: TryFrom<Object<BehindHandle>>,
+    {
+        self.iter_objects().of_type()
+    }
+}
+
+impl ObjectIters for Cycle {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        self.half_edges().cloned().map(Into::into).collect()
+    }
+}
+
+impl ObjectIters for Face {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        let mut objects = vec![self.surface().clone().into()];
+        objects.extend(self.all_cycles().cloned().map(Into::into));
+        objects
+    }
+}
+
+impl ObjectIters for GlobalEdge {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        Vec::new()
+    }
+}
+
+impl ObjectIters for HalfEdge {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        vec![
+            self.start_vertex().clone().into(),
+            self.global_form().clone().into(),
+        ]
+    }
+}
+
+impl ObjectIters for Shell {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        self.faces().into_iter().cloned().map(Into::into).collect()
+    }
+}
+
+impl ObjectIters for Sketch {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        self.faces().into_iter().cloned().map(Into::into).collect()
+    }
+}
+
+impl ObjectIters for Solid {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        let shells = self.shells().cloned().map(Into::into);
+        let instances = self
+            .instances()
+            .map(|instance| instance.solid().clone().into());
+
+        shells.chain(instances).collect()
+    }
+}
+
+impl ObjectIters for Surface {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        Vec::new()
+    }
+}
+
+impl ObjectIters for Vertex {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        Vec::new()
+    }
+}
+
+impl ObjectIters for Object<BehindHandle> {
+    fn referenced_objects(&self) -> Vec<Object<BehindHandle>> {
+        match self {
+            Self::Cycle(cycle) => cycle.referenced_objects(),
+            Self::Face(face) => face.referenced_objects(),
+            Self::GlobalEdge(global_edge) => global_edge.referenced_objects(),
+            Self::HalfEdge(half_edge) => half_edge.referenced_objects(),
+            Self::Shell(shell) => shell.referenced_objects(),
+            Self::Sketch(sketch) => sketch.referenced_objects(),
+            Self::Solid(solid) => solid.referenced_objects(),
+            Self::Surface(surface) => surface.referenced_objects(),
+            Self::Vertex(vertex) => vertex.referenced_objects(),
+        }
+    }
+}
+
+/// Iterator over the objects that an object refers to
+///
+/// Returned by [`ObjectIters::iter_objects`]. Walks the object graph depth
+/// first, yielding each object before the objects it refers to.
+pub struct ObjectIter {
+    stack: Vec<Object<BehindHandle>>,
+    visited: HashSet<ObjectId>,
+}
+
+impl ObjectIter {
+    /// Construct an iterator that starts at the given object
+    ///
+    /// Unlike [`ObjectIters::iter_objects`], this yields the provided object
+    /// too.
+    pub fn new(object: impl Into<Object<BehindHandle>>) -> Self {
+        Self::from_objects(vec![object.into()])
+    }
+
+    /// Only yield objects of type `T`
+    pub fn of_type<T>(self) -> OfType<T>
+    where
+        Handle<T>: TryFrom<Object<BehindHandle>>,
+    {
+        OfType {
+            inner: self,
+            _t: PhantomData,
+        }
+    }
+
+    fn from_objects(mut objects: Vec<Object<BehindHandle>>) -> Self {
+        // The stack is processed from the back. Reverse it, so objects are
+        // yielded in the order in which they're referred to.
+        objects.reverse();
+
+        Self {
+            stack: objects,
+            visited: HashSet::new(),
+        }
+    }
+}
+
+impl Iterator for ObjectIter {
+    type Item = Object<BehindHandle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let object = self.stack.pop()?;
+
+            if !self.visited.insert(object.id()) {
+                continue;
+            }
+
+            let mut referenced = object.referenced_objects();
+            referenced.reverse();
+            self.stack.extend(referenced);
+
+            return Some(object);
+        }
+    }
+}
+
+/// Iterator over the objects of a specific type
+///
+/// Returned by [`ObjectIter::of_type`] and [`ObjectIters::all`].
+pub struct OfType<T> {
+    inner: ObjectIter,
+    _t: PhantomData<T>,
+}
+
+impl<T> Iterator for OfType<T>
+where
+    Handle<T>: TryFrom<Object<BehindHandle>>,
+{
+    type Item = Handle<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|object| object.try_into().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Face, HalfEdge, Object, Shell, Solid, Vertex},
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::{ObjectIter, ObjectIters};
+
+    #[test]
+    fn iter_objects() {
+        let mut services = Services::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+        let solid = Solid::new([shell]).insert(&mut services.objects);
+
+        assert_eq!(solid.all::<Face>().count(), 4);
+        assert_eq!(solid.all::<HalfEdge>().count(), 12);
+
+        // The vertices of a tetrahedron are shared between its half-edges, but
+        // every vertex must only be yielded once.
+        let mut vertices =
+            solid.all::<Vertex>().map(|v| v.id()).collect::<Vec<_>>();
+        let num_vertices = vertices.len();
+        vertices.sort();
+        vertices.dedup();
+        assert_eq!(vertices.len(), num_vertices);
+
+        let mut objects = ObjectIter::new(solid.clone());
+        let Some(Object::Solid(first)) = objects.next() else {
+            panic!("Expected iterator to start with solid");
+        };
+        assert_eq!(first.id(), solid.id());
+        assert!(matches!(objects.next(), Some(Object::Shell(_))));
+    }
+}
@@ -74,7 +74,10 @@
 //! [#1021]: https://github.com/hannobraun/Fornjot/issues/1021
 
 mod full;
+mod iter;
 mod object;
+mod provenance;
+mod references;
 mod stores;
 
 pub use self::{
@@ -84,10 +87,13 @@ pub use self::{
         face::{Face, FaceSet, Handedness},
         shell::Shell,
         sketch::Sketch,
-        solid::Solid,
+        solid::{Instance, Solid},
         surface::Surface,
         vertex::Vertex,
     },
+    iter::{ObjectIter, ObjectIters, OfType},
     object::{Bare, BehindHandle, Form, Object, WithHandle},
+    provenance::{OperationScope, Provenance, StableId},
+    references::References,
     stores::{Objects, Surfaces},
 };
@@ -22,6 +22,17 @@ macro_rules! object {
             )*
         }
 
+        impl<F: Form> Object<F> {
+            /// Access the name of the object's type
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$ty(_) => $name,
+                    )*
+                }
+            }
+        }
+
         impl Object<BehindHandle> {
             /// Access the ID of the object
             pub fn id(&self) -> ObjectId {
@@ -36,13 +47,37 @@ macro_rules! object {
         impl Object<WithHandle> {
             /// Insert the object into its respective store
             pub fn insert(self, objects: &mut Objects) -> Object<BehindHandle> {
-                match self {
+                objects.references.insert(&self);
+
+                let object: Object<BehindHandle> = match self {
                     $(
                         Self::$ty((handle, object)) => {
                             objects.$store.insert(handle.clone(), object);
                             handle.into()
                         }
                     )*
+                };
+
+                objects.provenances.insert(object.id(), object.name());
+
+                object
+            }
+
+            /// Discard the object in its respective store
+            ///
+            /// This is the inverse of [`Object::insert`].
+            pub fn discard(&self, objects: &mut Objects) {
+                objects.references.remove(self);
+
+                match self {
+                    $(
+                        Self::$ty((handle, _)) => {
+                            objects
+                                .provenances
+                                .remove(handle.id(), self.name());
+                            objects.$store.discard(handle);
+                        }
+                    )*
                 }
             }
 
@@ -79,6 +114,19 @@ macro_rules! object {
                 }
             }
 
+            impl TryFrom<Object<BehindHandle>> for Handle<$ty> {
+                type Error = Object<BehindHandle>;
+
+                fn try_from(
+                    object: Object<BehindHandle>,
+                ) -> Result<Self, Self::Error> {
+                    match object {
+                        Object::$ty(handle) => Ok(handle),
+                        object => Err(object),
+                    }
+                }
+            }
+
             impl From<(Handle<$ty>, $ty)> for Object<WithHandle> {
                 fn from((handle, object): (Handle<$ty>, $ty)) -> Self {
                     Self::$ty((handle, object))
@@ -0,0 +1,308 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::storage::ObjectId;
+
+use super::{BehindHandle, Object};
+
+/// The operation that created an object
+///
+/// Operations can be nested. A sweep might be part of a larger operation, for
+/// example. The enclosing operation is available via [`Provenance::parent`].
+#[derive(Clone, Debug)]
+pub struct Provenance {
+    operation: &'static str,
+    inputs: Vec<Object<BehindHandle>>,
+    parent: Option<Arc<Provenance>>,
+}
+
+impl Provenance {
+    /// Construct an instance of `Provenance`
+    ///
+    /// `operation` is the name of the operation, like "sweep". `inputs` are
+    /// the objects that the operation was applied to.
+    pub fn new(
+        operation: &'static str,
+        inputs: impl IntoIterator<Item = Object<BehindHandle>>,
+    ) -> Self {
+        Self {
+            operation,
+            inputs: inputs.into_iter().collect(),
+            parent: None,
+        }
+    }
+
+    /// Access the name of the operation
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Access the objects that the operation was applied to
+    pub fn inputs(&self) -> &[Object<BehindHandle>] {
+        &self.inputs
+    }
+
+    /// Access the operation that this operation was a part of
+    pub fn parent(&self) -> Option<&Provenance> {
+        self.parent.as_deref()
+    }
+
+    /// Iterate over this operation and all operations it was a part of
+    ///
+    /// Starts with this operation, followed by its parent, and so forth.
+    pub fn chain(&self) -> impl Iterator<Item = &Provenance> {
+        let mut next = Some(self);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = current.parent();
+            Some(current)
+        })
+    }
+}
+
+/// An identifier for an object that persists across rebuilds
+///
+/// [`ObjectId`] identifies an object in memory, which means any object that is
+/// created by re-evaluating a model has a new one, even if nothing about that
+/// object has changed. A `StableId` is instead derived from how an object was
+/// created: the operations it was created within (see [`Provenance`]), its
+/// type, and the number of objects of the same type that were created before
+/// it, within the same operation.
+///
+/// As a result, corresponding objects from two evaluations of the same model
+/// get the same `StableId`, as long as the model creates its objects in the
+/// same way. A parameter change that only changes the geometry of a model
+/// doesn't change its stable IDs.
+///
+/// Stable IDs are computed using the standard library's default hasher. They
+/// are not guaranteed to be stable across versions of Rust, and shouldn't be
+/// persisted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct StableId(u64);
+
+impl StableId {
+    fn child(&self, kind: &'static str, index: u64) -> Self {
+        let mut hasher = DefaultHasher::new();
+        (self.0, kind, index).hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Index of the operations that created objects
+///
+/// Also computes the [`StableId`]s of objects and operations.
+#[derive(Debug, Default)]
+pub(crate) struct Provenances {
+    scopes: Vec<OperationScope>,
+    by_object: HashMap<ObjectId, ObjectInfo>,
+    counters: HashMap<(StableId, &'static str), u64>,
+}
+
+impl Provenances {
+    pub fn get(&self, id: ObjectId) -> Option<&Provenance> {
+        self.by_object.get(&id)?.provenance.as_deref()
+    }
+
+    pub fn stable_id(&self, id: ObjectId) -> Option<StableId> {
+        self.by_object.get(&id).map(|info| info.stable_id)
+    }
+
+    pub fn current(&self) -> Option<&OperationScope> {
+        self.scopes.last()
+    }
+
+    pub fn nest(&self, mut provenance: Provenance) -> OperationScope {
+        let stable_id = self.next_stable_id(provenance.operation);
+        provenance.parent =
+            self.current().map(|scope| scope.provenance.clone());
+
+        OperationScope {
+            provenance: Arc::new(provenance),
+            stable_id,
+        }
+    }
+
+    pub fn enter(&mut self, scope: OperationScope) {
+        self.count(scope.provenance.operation, 1);
+        self.scopes.push(scope);
+    }
+
+    pub fn exit(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Undo [`Provenances::enter`]
+    pub fn unenter(&mut self) {
+        if let Some(scope) = self.scopes.pop() {
+            self.count(scope.provenance.operation, -1);
+        }
+    }
+
+    /// Undo [`Provenances::exit`]
+    pub fn unexit(&mut self, scope: OperationScope) {
+        self.scopes.push(scope);
+    }
+
+    pub fn insert(&mut self, id: ObjectId, kind: &'static str) {
+        let info = ObjectInfo {
+            provenance: self.current().map(|scope| scope.provenance.clone()),
+            stable_id: self.next_stable_id(kind),
+        };
+
+        self.count(kind, 1);
+        self.by_object.insert(id, info);
+    }
+
+    /// Count an object that has been reused, instead of inserted
+    ///
+    /// Doesn't assign a stable ID, as the reused object already has one, but
+    /// makes sure that the next object of the same kind gets the same stable
+    /// ID, as if one had been inserted.
+    pub fn skip(&mut self, kind: &'static str) {
+        self.count(kind, 1);
+    }
+
+    /// Undo [`Provenances::skip`]
+    pub fn unskip(&mut self, kind: &'static str) {
+        self.count(kind, -1);
+    }
+
+    pub fn remove(&mut self, id: ObjectId, kind: &'static str) {
+        if self.by_object.remove(&id).is_some() {
+            self.count(kind, -1);
+        }
+    }
+
+    fn current_stable_id(&self) -> StableId {
+        self.current()
+            .map(|scope| scope.stable_id)
+            .unwrap_or_default()
+    }
+
+    fn next_stable_id(&self, kind: &'static str) -> StableId {
+        let parent = self.current_stable_id();
+        let index = self.counters.get(&(parent, kind)).copied().unwrap_or(0);
+
+        parent.child(kind, index)
+    }
+
+    fn count(&mut self, kind: &'static str, change: i64) {
+        let parent = self.current_stable_id();
+        let counter = self.counters.entry((parent, kind)).or_default();
+        *counter = counter.saturating_add_signed(change);
+    }
+}
+
+/// An operation that objects are being inserted within
+///
+/// See [`Service::with_provenance`].
+///
+/// [`Service::with_provenance`]: crate::services::Service::with_provenance
+#[derive(Clone, Debug)]
+pub struct OperationScope {
+    provenance: Arc<Provenance>,
+    stable_id: StableId,
+}
+
+impl OperationScope {
+    /// Access the operation, including the operations it is nested in
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    /// Access the stable ID of the operation
+    pub fn stable_id(&self) -> StableId {
+        self.stable_id
+    }
+}
+
+#[derive(Debug)]
+struct ObjectInfo {
+    provenance: Option<Arc<Provenance>>,
+    stable_id: StableId,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Face, Object, Shell, Vertex},
+        operations::{BuildShell, Insert},
+        services::Services,
+        storage::Handle,
+    };
+
+    use super::Provenance;
+
+    #[test]
+    fn provenance() {
+        let mut services = Services::new();
+
+        let input = Vertex::new().insert(&mut services.objects);
+        let outer = Provenance::new("outer", [input.clone().into()]);
+        let inner = Provenance::new("inner", []);
+
+        let (a, b) = services.objects.with_provenance(outer, |objects| {
+            let a = objects.with_provenance(inner, |objects| {
+                Vertex::new().insert(objects)
+            });
+            let b = Vertex::new().insert(objects);
+            (a, b)
+        });
+
+        let objects = &services.objects;
+        assert!(objects.provenance(input.id()).is_none());
+
+        let a = objects.provenance(a.id()).unwrap();
+        assert_eq!(
+            a.chain().map(Provenance::operation).collect::<Vec<_>>(),
+            ["inner", "outer"],
+        );
+
+        let b = objects.provenance(b.id()).unwrap();
+        assert_eq!(b.operation(), "outer");
+        assert!(matches!(
+            b.inputs(),
+            [Object::Vertex(vertex)] if vertex.id() == input.id()
+        ));
+    }
+
+    #[test]
+    fn stable_id() {
+        fn tetrahedron(offset: f64) -> (Services, [Handle<Face>; 2]) {
+            let mut services = Services::new();
+
+            let faces = services.objects.with_provenance(
+                Provenance::new("tetrahedron", []),
+                |objects| {
+                    let tetrahedron = Shell::tetrahedron(
+                        [
+                            [0., 0., 0.],
+                            [offset, 0., 0.],
+                            [0., offset, 0.],
+                            [0., 0., offset],
+                        ],
+                        objects,
+                    );
+                    [tetrahedron.face_abc, tetrahedron.face_bcd]
+                },
+            );
+
+            (services, faces)
+        }
+
+        let (a, [a_abc, a_bcd]) = tetrahedron(1.);
+        let (b, [b_abc, b_bcd]) = tetrahedron(2.);
+
+        let [a_abc, a_bcd] =
+            [a_abc, a_bcd].map(|face| a.objects.stable_id(face.id()).unwrap());
+        let [b_abc, b_bcd] =
+            [b_abc, b_bcd].map(|face| b.objects.stable_id(face.id()).unwrap());
+
+        assert_eq!(a_abc, b_abc);
+        assert_eq!(a_bcd, b_bcd);
+        assert_ne!(a_abc, a_bcd);
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use crate::storage::{Handle, ObjectId};
+
+use super::{
+    Cycle, Face, GlobalEdge, HalfEdge, Object, Shell, Sketch, Solid, Surface,
+    Vertex, WithHandle,
+};
+
+/// Index of the objects that refer to an object
+///
+/// Objects only know about the objects they refer to. This index provides the
+/// reverse: Given an object, find all objects that refer to it. It is updated
+/// whenever an object is inserted into [`Objects`], so queries don't need to
+/// walk all objects.
+///
+/// [`Objects`]: super::Objects
+#[derive(Debug, Default)]
+pub struct References {
+    half_edges_by_vertex: Referrers<HalfEdge>,
+    half_edges_by_global_edge: Referrers<HalfEdge>,
+    cycles_by_half_edge: Referrers<Cycle>,
+    faces_by_cycle: Referrers<Face>,
+    faces_by_surface: Referrers<Face>,
+    shells_by_face: Referrers<Shell>,
+    sketches_by_face: Referrers<Sketch>,
+    solids_by_shell: Referrers<Solid>,
+    solids_by_instanced_solid: Referrers<Solid>,
+}
+
+impl References {
+    /// Find all half-edges that start at the given vertex
+    pub fn half_edges_starting_at(
+        &self,
+        vertex: &Handle<Vertex>,
+    ) -> &[Handle<HalfEdge>] {
+        self.half_edges_by_vertex.get(vertex)
+    }
+
+    /// Find all half-edges that have the given global form
+    pub fn half_edges_with_global_form(
+        &self,
+        global_edge: &Handle<GlobalEdge>,
+    ) -> &[Handle<HalfEdge>] {
+        self.half_edges_by_global_edge.get(global_edge)
+    }
+
+    /// Find all cycles that contain the given half-edge
+    pub fn cycles_containing(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> &[Handle<Cycle>] {
+        self.cycles_by_half_edge.get(half_edge)
+    }
+
+    /// Find all faces that are bounded by the given cycle
+    pub fn faces_bounded_by(&self, cycle: &Handle<Cycle>) -> &[Handle<Face>] {
+        self.faces_by_cycle.get(cycle)
+    }
+
+    /// Find all faces that are defined on the given surface
+    pub fn faces_on(&self, surface: &Handle<Surface>) -> &[Handle<Face>] {
+        self.faces_by_surface.get(surface)
+    }
+
+    /// Find all faces that contain the given half-edge, in any of their cycles
+    pub fn faces_containing(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Vec<Handle<Face>> {
+        let mut faces = Vec::new();
+
+        for cycle in self.cycles_containing(half_edge) {
+            for face in self.faces_bounded_by(cycle) {
+                if !faces.iter().any(|f: &Handle<Face>| f.id() == face.id()) {
+                    faces.push(face.clone());
+                }
+            }
+        }
+
+        faces
+    }
+
+    /// Find all shells that contain the given face
+    pub fn shells_containing(&self, face: &Handle<Face>) -> &[Handle<Shell>] {
+        self.shells_by_face.get(face)
+    }
+
+    /// Find all sketches that contain the given face
+    pub fn sketches_containing(
+        &self,
+        face: &Handle<Face>,
+    ) -> &[Handle<Sketch>] {
+        self.sketches_by_face.get(face)
+    }
+
+    /// Find all solids that contain the given shell
+    pub fn solids_containing(&self, shell: &Handle<Shell>) -> &[Handle<Solid>] {
+        self.solids_by_shell.get(shell)
+    }
+
+    /// Find all solids that contain an instance of the given solid
+    pub fn solids_instancing(&self, solid: &Handle<Solid>) -> &[Handle<Solid>] {
+        self.solids_by_instanced_solid.get(solid)
+    }
+
+    /// Record the references of an object that is being inserted
+    pub(super) fn insert(&mut self, object: &Object<WithHandle>) {
+        self.update(object, Change::Insert);
+    }
+
+    /// Forget the references of an object that is being discarded
+    pub(super) fn remove(&mut self, object: &Object<WithHandle>) {
+        self.update(object, Change::Remove);
+    }
+
+    fn update(&mut self, object: &Object<WithHandle>, change: Change) {
+        match object {
+            Object::Cycle((handle, cycle)) => {
+                for half_edge in cycle.half_edges() {
+                    self.cycles_by_half_edge.update(half_edge, handle, change);
+                }
+            }
+            Object::Face((handle, face)) => {
+                self.faces_by_surface.update(face.surface(), handle, change);
+                for cycle in face.all_cycles() {
+                    self.faces_by_cycle.update(cycle, handle, change);
+                }
+            }
+            Object::HalfEdge((handle, half_edge)) => {
+                self.half_edges_by_vertex.update(
+                    half_edge.start_vertex(),
+                    handle,
+                    change,
+                );
+                self.half_edges_by_global_edge.update(
+                    half_edge.global_form(),
+                    handle,
+                    change,
+                );
+            }
+            Object::Shell((handle, shell)) => {
+                for face in shell.faces() {
+                    self.shells_by_face.update(face, handle, change);
+                }
+            }
+            Object::Sketch((handle, sketch)) => {
+                for face in sketch.faces() {
+                    self.sketches_by_face.update(face, handle, change);
+                }
+            }
+            Object::Solid((handle, solid)) => {
+                for shell in solid.shells() {
+                    self.solids_by_shell.update(shell, handle, change);
+                }
+                for instance in solid.instances() {
+                    self.solids_by_instanced_solid.update(
+                        instance.solid(),
+                        handle,
+                        change,
+                    );
+                }
+            }
+            Object::GlobalEdge(_) | Object::Surface(_) | Object::Vertex(_) => {
+                // These objects don't refer to other objects.
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Change {
+    Insert,
+    Remove,
+}
+
+/// The objects of one type that refer to other objects, by the referenced ID
+#[derive(Debug)]
+struct Referrers<T> {
+    inner: HashMap<ObjectId, Vec<Handle<T>>>,
+}
+
+impl<T> Referrers<T> {
+    fn get<R>(&self, referenced: &Handle<R>) -> &[Handle<T>] {
+        self.inner
+            .get(&referenced.id())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn update<R>(
+        &mut self,
+        referenced: &Handle<R>,
+        referrer: &Handle<T>,
+        change: Change,
+    ) {
+        let referrers = self.inner.entry(referenced.id()).or_default();
+
+        match change {
+            Change::Insert => {
+                // An object might refer to another one more than once, like a
+                // face that uses the same cycle as its exterior and an
+                // interior.
+                if !referrers.iter().any(|r| r.id() == referrer.id()) {
+                    referrers.push(referrer.clone());
+                }
+            }
+            Change::Remove => {
+                referrers.retain(|r| r.id() != referrer.id());
+            }
+        }
+    }
+}
+
+impl<T> Default for Referrers<T> {
+    fn default() -> Self {
+        Self {
+            inner: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    #[test]
+    fn find_referencing_objects() {
+        let mut services = Services::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+        let solid = Solid::new([shell.clone()]).insert(&mut services.objects);
+
+        let references = services.objects.references();
+        let face = tetrahedron.face_abc;
+        let half_edge = face.exterior().half_edges().next().unwrap();
+
+        let faces = references.faces_containing(half_edge);
+        assert!(faces.iter().any(|f| f.id() == face.id()));
+
+        let shells = references.shells_containing(&face);
+        assert_eq!(shells.len(), 1);
+        assert_eq!(shells[0].id(), shell.id());
+
+        let solids = references.solids_containing(&shell);
+        assert_eq!(solids.len(), 1);
+        assert_eq!(solids[0].id(), solid.id());
+
+        assert!(references.sketches_containing(&face).is_empty());
+    }
+}
@@ -1,12 +1,20 @@
-use fj_math::Vector;
+use std::collections::BTreeMap;
+
+use fj_math::{Scalar, Tolerance, Vector};
 
 use crate::{
-    geometry::{curve::GlobalPath, surface::SurfaceGeometry},
-    storage::{Handle, Store},
+    geometry::{
+        canonical::{CanonicalKey, Canonicalize},
+        curve::GlobalPath,
+        surface::SurfaceGeometry,
+    },
+    storage::{Handle, Iter, ObjectId, Store},
+    validate::ValidationConfig,
 };
 
 use super::{
-    Cycle, Face, GlobalEdge, HalfEdge, Shell, Sketch, Solid, Surface, Vertex,
+    provenance::Provenances, Cycle, Face, GlobalEdge, HalfEdge, Provenance,
+    References, Shell, Sketch, Solid, StableId, Surface, Vertex,
 };
 
 /// The available object stores
@@ -38,6 +46,9 @@ pub struct Objects {
 
     /// Store for [`Vertex`] objects
     pub vertices: Store<Vertex>,
+
+    pub(super) references: References,
+    pub(crate) provenances: Provenances,
 }
 
 impl Objects {
@@ -45,12 +56,64 @@ impl Objects {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Construct a new instance of `Stores` for a model of the given size
+    ///
+    /// Surfaces whose geometry is identical within a tolerance are
+    /// deduplicated. By default, that tolerance is
+    /// [`ValidationConfig::identical_max_distance`], which is too small to
+    /// identify surfaces of large models reliably. Passing the size of the
+    /// model (like the largest extent of its bounding box) scales the
+    /// tolerance accordingly.
+    pub fn with_model_size(model_size: impl Into<Scalar>) -> Self {
+        let min = ValidationConfig::default().identical_max_distance;
+        let quantum = model_size.into() * RELATIVE_QUANTUM;
+
+        let quantum = if quantum > min.length() {
+            Tolerance::from(quantum)
+        } else {
+            min
+        };
+
+        Self {
+            surfaces: Surfaces::with_quantum(quantum),
+            ..Self::default()
+        }
+    }
+
+    /// Access the index of objects that refer to other objects
+    pub fn references(&self) -> &References {
+        &self.references
+    }
+
+    /// Access the operation that created the object with the given ID
+    ///
+    /// Returns `None`, if the object was not inserted within an operation. See
+    /// [`Service::with_provenance`].
+    ///
+    /// [`Service::with_provenance`]: crate::services::Service::with_provenance
+    pub fn provenance(&self, id: ObjectId) -> Option<&Provenance> {
+        self.provenances.get(id)
+    }
+
+    /// Access the stable ID of the object with the given ID
+    ///
+    /// Returns `None`, if no object with the given ID has been inserted. See
+    /// [`StableId`].
+    pub fn stable_id(&self, id: ObjectId) -> Option<StableId> {
+        self.provenances.stable_id(id)
+    }
 }
 
 /// Store for [`Surface`]s
+///
+/// Keeps track of the geometry of all surfaces, which allows looking up a
+/// surface by its geometry. See [`Surfaces::find`].
 #[derive(Debug)]
 pub struct Surfaces {
     store: Store<Surface>,
+    by_key: BTreeMap<CanonicalKey, Handle<Surface>>,
+    quantum: Tolerance,
 
     xy_plane: Handle<Surface>,
     xz_plane: Handle<Surface>,
@@ -58,34 +121,7 @@ pub struct Surfaces {
 }
 
 impl Surfaces {
-    /// Reserve a slot for an object in the store
-    pub fn reserve(&self) -> Handle<Surface> {
-        self.store.reserve()
-    }
-
-    /// Insert an object into the store
-    pub fn insert(&mut self, handle: Handle<Surface>, surface: Surface) {
-        self.store.insert(handle, surface);
-    }
-
-    /// Access the xy-plane
-    pub fn xy_plane(&self) -> Handle<Surface> {
-        self.xy_plane.clone()
-    }
-
-    /// Access the xz-plane
-    pub fn xz_plane(&self) -> Handle<Surface> {
-        self.xz_plane.clone()
-    }
-
-    /// Access the yz-plane
-    pub fn yz_plane(&self) -> Handle<Surface> {
-        self.yz_plane.clone()
-    }
-}
-
-impl Default for Surfaces {
-    fn default() -> Self {
+    fn with_quantum(quantum: Tolerance) -> Self {
         let mut store: Store<Surface> = Store::new();
 
         let xy_plane = store.reserve();
@@ -114,11 +150,85 @@ impl Default for Surfaces {
             }),
         );
 
+        let by_key = [&xy_plane, &xz_plane, &yz_plane]
+            .into_iter()
+            .filter_map(|plane| {
+                let key = plane.geometry().canonical_key(quantum)?;
+                Some((key, plane.clone()))
+            })
+            .collect();
+
         Self {
             store,
+            by_key,
+            quantum,
             xy_plane,
             xz_plane,
             yz_plane,
         }
     }
+
+    /// Reserve a slot for an object in the store
+    pub fn reserve(&self) -> Handle<Surface> {
+        self.store.reserve()
+    }
+
+    /// Insert an object into the store
+    pub fn insert(&mut self, handle: Handle<Surface>, surface: Surface) {
+        if let Some(key) = surface.geometry().canonical_key(self.quantum) {
+            self.by_key.entry(key).or_insert_with(|| handle.clone());
+        }
+
+        self.store.insert(handle, surface);
+    }
+
+    /// Discard an object in the store
+    pub fn discard(&mut self, handle: &Handle<Surface>) {
+        self.by_key.retain(|_, surface| surface.id() != handle.id());
+        self.store.discard(handle);
+    }
+
+    /// Find a surface with the given geometry
+    ///
+    /// Returns a surface whose geometry is identical to the given one, within
+    /// a tolerance that depends on the size of the model. See
+    /// [`Objects::with_model_size`]. Returns `None`, if there is no such
+    /// surface, or if it can't be found reliably. See [`CanonicalKey`].
+    pub fn find(&self, geometry: &SurfaceGeometry) -> Option<Handle<Surface>> {
+        let key = geometry.canonical_key(self.quantum)?;
+        self.by_key.get(&key).cloned()
+    }
+
+    /// Iterate over all surfaces in the store
+    pub fn iter(&self) -> Iter<Surface> {
+        self.store.iter()
+    }
+
+    /// Access the xy-plane
+    pub fn xy_plane(&self) -> Handle<Surface> {
+        self.xy_plane.clone()
+    }
+
+    /// Access the xz-plane
+    pub fn xz_plane(&self) -> Handle<Surface> {
+        self.xz_plane.clone()
+    }
+
+    /// Access the yz-plane
+    pub fn yz_plane(&self) -> Handle<Surface> {
+        self.yz_plane.clone()
+    }
+}
+
+impl Default for Surfaces {
+    fn default() -> Self {
+        Self::with_quantum(ValidationConfig::default().identical_max_distance)
+    }
 }
+
+/// The tolerance for deduplicating surfaces, relative to the size of the model
+///
+/// Large enough to cover the inaccuracies of a few floating-point operations,
+/// and small enough that canonical keys can be computed for coordinates up to
+/// a multiple of the model size. See [`Objects::with_model_size`].
+const RELATIVE_QUANTUM: f64 = 1e-13;
@@ -1,5 +1,5 @@
 use fj_interop::ext::ArrayExt;
-use fj_math::{Arc, Point, Scalar};
+use fj_math::{Arc, Bezier, Point, Scalar};
 
 use crate::{
     geometry::curve::Curve,
@@ -48,6 +48,17 @@ pub trait BuildHalfEdge {
         HalfEdge::unjoined(curve, boundary, objects)
     }
 
+    /// Create a cubic Bézier curve from its control points
+    fn bezier(
+        points: [impl Into<Point<2>>; 4],
+        objects: &mut Service<Objects>,
+    ) -> HalfEdge {
+        let curve = Curve::Bezier(Bezier::new(points));
+        let boundary = [[0.], [1.]].map(Point::from);
+
+        HalfEdge::unjoined(curve, boundary, objects)
+    }
+
     /// Create a circle
     fn circle(
         radius: impl Into<Scalar>,
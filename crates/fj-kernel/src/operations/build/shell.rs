@@ -1,13 +1,17 @@
-use fj_math::Point;
+use std::collections::BTreeMap;
+
+use fj_math::{Aabb, Point, Scalar, Vector, Winding};
 
 use crate::{
-    objects::{Face, Objects, Shell},
+    algorithms::sweep::Sweep,
+    builder::{CycleBuilder, FaceBuilder},
+    objects::{Cycle, Face, HalfEdge, Objects, Shell, Surface},
     operations::Insert,
     services::Service,
     storage::Handle,
 };
 
-use super::{BuildFace, Triangle};
+use super::{BuildFace, BuildHalfEdge, BuildSurface, Triangle};
 
 /// Build a [`Shell`]
 pub trait BuildShell {
@@ -47,10 +51,142 @@ pub trait BuildShell {
             face_bcd,
         }
     }
+
+    /// Build a shell from a mesh of triangles
+    ///
+    /// The triangles refer to `points` by index. Triangles that have an edge in
+    /// common share the [`GlobalEdge`] of that edge. To result in a valid shell,
+    /// the mesh must be closed, and the triangles must wind consistently.
+    ///
+    /// [`GlobalEdge`]: crate::objects::GlobalEdge
+    fn from_triangles(
+        points: impl IntoIterator<Item = impl Into<Point<3>>>,
+        triangles: impl IntoIterator<Item = [usize; 3]>,
+        objects: &mut Service<Objects>,
+    ) -> Shell {
+        let points: Vec<Point<3>> =
+            points.into_iter().map(Into::into).collect();
+        let mut edges = BTreeMap::new();
+
+        let faces = triangles
+            .into_iter()
+            .map(|[a, b, c]| {
+                let keys = [[a, b], [b, c], [c, a]].map(|[a, b]| {
+                    if a < b {
+                        [a, b]
+                    } else {
+                        [b, a]
+                    }
+                });
+
+                let Triangle {
+                    face,
+                    edges: global_edges,
+                } = Face::triangle(
+                    [points[a], points[b], points[c]],
+                    keys.map(|key| edges.get(&key).cloned()),
+                    objects,
+                );
+
+                for (key, edge) in keys.into_iter().zip(global_edges) {
+                    edges.entry(key).or_insert(edge);
+                }
+
+                face.insert(objects)
+            })
+            .collect::<Vec<_>>();
+
+        Shell::new(faces)
+    }
+
+    /// Build an axis-aligned box from two of its opposite corners
+    ///
+    /// The box must have a non-zero extent along each axis.
+    fn box_from_corners(
+        corners: [impl Into<Point<3>>; 2],
+        objects: &mut Service<Objects>,
+    ) -> Shell {
+        let corners: [Point<3>; 2] = corners.map(Into::into);
+        let aabb = Aabb::<3>::from_points(corners);
+        let min = aabb.min;
+        let [x, y, z] = aabb.size().components;
+
+        let surface = Surface::plane_from_points([
+            min,
+            min + Vector::unit_x(),
+            min + Vector::unit_y(),
+        ])
+        .insert(objects);
+        let bottom = FaceBuilder::new(surface)
+            .with_exterior(CycleBuilder::polygon(
+                [
+                    [Scalar::ZERO, Scalar::ZERO],
+                    [x, Scalar::ZERO],
+                    [x, y],
+                    [Scalar::ZERO, y],
+                ],
+                objects,
+            ))
+            .build(objects)
+            .insert(objects);
+
+        sweep(bottom, Vector::unit_z() * z, objects)
+    }
+
+    /// Build a cylinder, standing on the xy-plane, centered on the z-axis
+    ///
+    /// If `height` is negative, the cylinder extends below the xy-plane.
+    fn cylinder(
+        radius: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        objects: &mut Service<Objects>,
+    ) -> Shell {
+        let exterior = HalfEdge::circle(radius, objects).insert(objects);
+        let exterior = Cycle::new([exterior]).insert(objects);
+        let bottom =
+            Face::new(objects.surfaces.xy_plane(), exterior, Vec::new(), None)
+                .insert(objects);
+
+        sweep(bottom, Vector::unit_z() * height.into(), objects)
+    }
+
+    /// Build a prism, by extruding a polygon in the xy-plane along the z-axis
+    ///
+    /// The polygon's points can be given in either winding. If `height` is
+    /// negative, the prism extends below the xy-plane.
+    fn prism(
+        polygon: impl IntoIterator<Item = impl Into<Point<2>>>,
+        height: impl Into<Scalar>,
+        objects: &mut Service<Objects>,
+    ) -> Shell {
+        let bottom = FaceBuilder::new(objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon_with_winding(
+                polygon,
+                Winding::Ccw,
+                objects,
+            ))
+            .build(objects)
+            .insert(objects);
+
+        sweep(bottom, Vector::unit_z() * height.into(), objects)
+    }
 }
 
 impl BuildShell for Shell {}
 
+fn sweep(
+    face: Handle<Face>,
+    path: Vector<3>,
+    objects: &mut Service<Objects>,
+) -> Shell {
+    // Sweeping takes care of connecting all faces of the shell, by sharing
+    // their edges and vertices.
+    let shell = face
+        .sweep(path, objects)
+        .expect("Shells are built by sweeping faces on planes");
+    Shell::new(shell.faces().into_iter().cloned())
+}
+
 /// A tetrahedron
 ///
 /// A tetrahedron is constructed from 4 points and has 4 faces. For the purpose
@@ -0,0 +1,444 @@
+use std::fmt;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar};
+
+use crate::{
+    algorithms::{
+        analysis::interference, approx::Tolerance,
+        bounding_volume::BoundingVolume, reverse::Reverse,
+        triangulate::Triangulate,
+    },
+    objects::{Cycle, Face, HalfEdge, Objects, Shell, Solid},
+    operations::{Insert, UpdateHalfEdge},
+    services::Service,
+    storage::Handle,
+    validate::{Validate, ValidationConfig, ValidationError},
+};
+
+/// Build a [`Solid`]
+pub trait BuildSolid {
+    /// Build a solid from a closed set of faces
+    ///
+    /// The faces don't need to be connected. Half-edges of different faces
+    /// that coincide are sewn together, by making them refer to the same
+    /// global edge and vertices. Every connected group of faces becomes a
+    /// shell of the solid.
+    ///
+    /// Shells that are within an odd number of other shells are voids, and
+    /// are oriented to face inwards. All other shells are oriented to face
+    /// outwards. Faces of a shell that is oriented the wrong way are reversed.
+    ///
+    /// Returns an error, if any half-edge has no counterpart to be sewn to, or
+    /// if the resulting solid is invalid.
+    fn from_faces(
+        faces: impl IntoIterator<Item = Handle<Face>>,
+        objects: &mut Service<Objects>,
+    ) -> Result<Solid, SewError> {
+        let faces = faces.into_iter().collect::<Vec<_>>();
+        let edges = EdgesOfFaces::new(&faces);
+        let partners = edges.find_partners()?;
+
+        // Sewn half-edges share their vertices. Each half-edge is identified
+        // with its start vertex here.
+        let mut vertices = UnionFind::new(edges.half_edges.len());
+        let mut shells = UnionFind::new(faces.len());
+        for (i, &j) in partners.iter().enumerate() {
+            if edges.same_direction(i, j) {
+                vertices.union(i, j);
+                vertices.union(edges.next(i), edges.next(j));
+            } else {
+                vertices.union(i, edges.next(j));
+                vertices.union(j, edges.next(i));
+            }
+
+            shells.union(edges.half_edges[i].face, edges.half_edges[j].face);
+        }
+
+        let mut faces_of_shells = vec![Vec::new(); faces.len()];
+        for (i, face) in faces.iter().enumerate() {
+            let mut exterior = None;
+            let mut interiors = Vec::new();
+
+            for (cycle, indices) in edges.cycles_of_face(i) {
+                let mut half_edges = Vec::new();
+                for k in indices {
+                    // Of each pair of sewn half-edges, the one that comes
+                    // first provides the global edge.
+                    let global_form = edges.half_edges[k.min(partners[k])]
+                        .half_edge
+                        .global_form()
+                        .clone();
+                    let start_vertex = edges.half_edges[vertices.find(k)]
+                        .half_edge
+                        .start_vertex()
+                        .clone();
+
+                    let half_edge = edges.half_edges[k]
+                        .half_edge
+                        .update_start_vertex(start_vertex)
+                        .update_global_form(global_form)
+                        .insert(objects);
+                    half_edges.push(half_edge);
+                }
+
+                let cycle_handle = Cycle::new(half_edges).insert(objects);
+                if cycle == 0 {
+                    exterior = Some(cycle_handle);
+                } else {
+                    interiors.push(cycle_handle);
+                }
+            }
+
+            let exterior = exterior.expect("Every face has an exterior cycle");
+            let face = Face::new(
+                face.surface().clone(),
+                exterior,
+                interiors,
+                face.color(),
+            )
+            .insert(objects);
+            faces_of_shells[shells.find(i)].push(face);
+        }
+
+        let shells = faces_of_shells
+            .into_iter()
+            .filter(|faces| !faces.is_empty())
+            .collect::<Vec<_>>();
+        let shells = orient_shells(shells, objects);
+
+        let solid = Solid::new(shells);
+        solid.validate_and_return_first_error().map_err(Box::new)?;
+
+        Ok(solid)
+    }
+}
+
+impl BuildSolid for Solid {}
+
+/// Error building a [`Solid`] from faces
+///
+/// Returned by [`BuildSolid::from_faces`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum SewError {
+    /// Some half-edges have no counterpart that they could be sewn to
+    #[error(
+        "Faces don't form closed shells. Half-edges that could not be sewn:\n\
+        {}",
+        .0.iter().map(|edge| format!("- {edge}\n")).collect::<String>()
+    )]
+    UnsewnEdges(Vec<UnsewnEdge>),
+
+    /// The faces have been sewn, but the resulting solid is not valid
+    #[error("Solid built from faces is not valid")]
+    Invalid(#[from] Box<ValidationError>),
+}
+
+/// A half-edge that could not be sewn to another
+///
+/// See [`SewError::UnsewnEdges`].
+#[derive(Clone, Debug)]
+pub struct UnsewnEdge {
+    /// The half-edge that could not be sewn
+    pub half_edge: Handle<HalfEdge>,
+
+    /// The face that the half-edge is part of
+    pub face: Handle<Face>,
+
+    /// The start and end points of the half-edge
+    pub points: [Point<3>; 2],
+}
+
+impl fmt::Display for UnsewnEdge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b] = self.points;
+        write!(f, "from {a:?} to {b:?}")
+    }
+}
+
+struct EdgesOfFaces {
+    half_edges: Vec<EdgeOfFace>,
+}
+
+impl EdgesOfFaces {
+    fn new(faces: &[Handle<Face>]) -> Self {
+        let mut half_edges = Vec::new();
+
+        for (i, face) in faces.iter().enumerate() {
+            let surface = face.surface().geometry();
+
+            for (j, cycle) in face.all_cycles().enumerate() {
+                let first = half_edges.len();
+                let len = cycle.half_edges().len();
+
+                for half_edge in cycle.half_edges() {
+                    let [start, end] = half_edge.boundary();
+                    let samples = [start, start + (end - start) * 0.5, end]
+                        .map(|point| {
+                            surface.point_from_surface_coords(
+                                half_edge.curve().point_from_path_coords(point),
+                            )
+                        });
+
+                    half_edges.push(EdgeOfFace {
+                        half_edge: half_edge.clone(),
+                        face: i,
+                        face_handle: face.clone(),
+                        cycle: j,
+                        first_of_cycle: first,
+                        len_of_cycle: len,
+                        samples,
+                    });
+                }
+            }
+        }
+
+        Self { half_edges }
+    }
+
+    /// Find the half-edge that each half-edge is sewn to
+    fn find_partners(&self) -> Result<Vec<usize>, SewError> {
+        let max_distance =
+            ValidationConfig::default().distinct_min_distance.length();
+        let mut partners = vec![None; self.half_edges.len()];
+
+        for i in 0..self.half_edges.len() {
+            if partners[i].is_some() {
+                continue;
+            }
+
+            let partner = (i + 1..self.half_edges.len()).find(|&j| {
+                partners[j].is_none() && self.coincide(i, j, max_distance)
+            });
+            if let Some(j) = partner {
+                partners[i] = Some(j);
+                partners[j] = Some(i);
+            }
+        }
+
+        let unsewn = partners
+            .iter()
+            .zip(&self.half_edges)
+            .filter(|(partner, _)| partner.is_none())
+            .map(|(_, edge)| UnsewnEdge {
+                half_edge: edge.half_edge.clone(),
+                face: edge.face_handle.clone(),
+                points: [edge.samples[0], edge.samples[2]],
+            })
+            .collect::<Vec<_>>();
+        if !unsewn.is_empty() {
+            return Err(SewError::UnsewnEdges(unsewn));
+        }
+
+        Ok(partners.into_iter().flatten().collect())
+    }
+
+    /// Determine whether two half-edges coincide, in any direction
+    fn coincide(&self, i: usize, j: usize, max_distance: Scalar) -> bool {
+        let [a, b] = [i, j].map(|k| self.half_edges[k].samples);
+        let b_reversed = [b[2], b[1], b[0]];
+
+        [b, b_reversed].into_iter().any(|b| {
+            a.into_iter()
+                .zip(b)
+                .all(|(a, b)| a.distance_to(&b) < max_distance)
+        })
+    }
+
+    /// Determine whether two coincident half-edges point in the same direction
+    fn same_direction(&self, i: usize, j: usize) -> bool {
+        let [a, b] = [i, j].map(|k| self.half_edges[k].samples);
+        a[0].distance_to(&b[0]) < a[0].distance_to(&b[2])
+    }
+
+    /// Access the index of the half-edge that follows the given one
+    fn next(&self, i: usize) -> usize {
+        let edge = &self.half_edges[i];
+        let index_in_cycle = i - edge.first_of_cycle;
+        edge.first_of_cycle + (index_in_cycle + 1) % edge.len_of_cycle
+    }
+
+    /// Iterate over the cycles of a face, and the indices of their half-edges
+    ///
+    /// The exterior cycle has index `0`.
+    fn cycles_of_face(
+        &self,
+        face: usize,
+    ) -> impl Iterator<Item = (usize, impl Iterator<Item = usize>)> + '_ {
+        self.half_edges
+            .iter()
+            .enumerate()
+            .filter(move |(i, edge)| {
+                edge.face == face && *i == edge.first_of_cycle
+            })
+            .map(|(i, edge)| (edge.cycle, i..i + edge.len_of_cycle))
+    }
+}
+
+struct EdgeOfFace {
+    half_edge: Handle<HalfEdge>,
+    face: usize,
+    face_handle: Handle<Face>,
+    cycle: usize,
+    first_of_cycle: usize,
+    len_of_cycle: usize,
+
+    /// The start, middle, and end point of the half-edge
+    samples: [Point<3>; 3],
+}
+
+/// Orient shells to face out of the solid, except for voids
+fn orient_shells(
+    shells: Vec<Vec<Handle<Face>>>,
+    objects: &mut Service<Objects>,
+) -> Vec<Handle<Shell>> {
+    let shells = shells
+        .into_iter()
+        .map(|faces| Shell::new(faces).insert(objects))
+        .collect::<Vec<_>>();
+    let meshes = shells.iter().map(triangulate).collect::<Vec<_>>();
+
+    let mut oriented = Vec::new();
+    for (i, (shell, mesh)) in shells.into_iter().zip(&meshes).enumerate() {
+        let Some(point) = mesh.vertices().next() else {
+            oriented.push(shell);
+            continue;
+        };
+
+        let num_containing = meshes
+            .iter()
+            .enumerate()
+            .filter(|&(j, other)| {
+                j != i && interference::contains(other, point)
+            })
+            .count();
+        let is_void = num_containing % 2 == 1;
+        let faces_outwards = signed_volume(mesh) > 0.;
+
+        if is_void == faces_outwards {
+            oriented.push(shell.reverse(objects));
+        } else {
+            oriented.push(shell);
+        }
+    }
+
+    oriented
+}
+
+fn triangulate(shell: &Handle<Shell>) -> Mesh<Point<3>> {
+    // The mesh is only used to determine the relative position and the
+    // orientation of shells, so the tolerance doesn't need to be tight.
+    let size = shell
+        .aabb()
+        .map(|aabb| aabb.size().magnitude())
+        .unwrap_or(Scalar::ONE);
+    let tolerance = Tolerance::from_scalar(size / 1000.)
+        .expect("Tolerance derived from non-empty shell is positive");
+
+    (&**shell, tolerance).triangulate()
+}
+
+fn signed_volume(mesh: &Mesh<Point<3>>) -> f64 {
+    mesh.triangles()
+        .map(|triangle| {
+            let [a, b, c] = triangle.inner.points().map(|point| point.coords);
+            a.dot(&b.cross(&c)).into_f64()
+        })
+        .sum()
+}
+
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Merge the sets of `a` and `b`
+    ///
+    /// The smaller of the two representatives becomes the representative of
+    /// the merged set.
+    fn union(&mut self, a: usize, b: usize) {
+        let [a, b] = [a, b].map(|i| self.find(i));
+        let [min, max] = if a < b { [a, b] } else { [b, a] };
+        self.parents[max] = min;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Face, Shell, Solid},
+        operations::{BuildFace, BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::{BuildSolid, SewError};
+
+    #[test]
+    fn sew_unconnected_faces() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let faces =
+            [[a, b, c], [a, b, d], [c, a, d], [b, c, d]].map(|points| {
+                Face::triangle(
+                    points,
+                    [None, None, None],
+                    &mut services.objects,
+                )
+                .face
+                .insert(&mut services.objects)
+            });
+
+        let solid = Solid::from_faces(faces.clone(), &mut services.objects)?;
+        assert_eq!(solid.shells().count(), 1);
+
+        let [_, faces @ ..] = faces;
+        let err = Solid::from_faces(faces, &mut services.objects).unwrap_err();
+        assert!(
+            matches!(err, SewError::UnsewnEdges(edges) if edges.len() == 3)
+        );
+
+        services.drop_and_validate().unwrap();
+
+        Ok(())
+    }
+
+    #[test]
+    fn sew_solid_with_void() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let outer = Shell::box_from_corners(
+            [[0., 0., 0.], [3., 3., 3.]],
+            &mut services.objects,
+        );
+        let inner = Shell::box_from_corners(
+            [[1., 1., 1.], [2., 2., 2.]],
+            &mut services.objects,
+        );
+        let faces = [outer, inner]
+            .iter()
+            .flat_map(|shell| shell.faces().into_iter().cloned())
+            .collect::<Vec<_>>();
+
+        let solid = Solid::from_faces(faces, &mut services.objects)?;
+        assert_eq!(solid.shells().count(), 2);
+
+        services.drop_and_validate().unwrap();
+
+        Ok(())
+    }
+}
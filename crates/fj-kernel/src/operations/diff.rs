@@ -0,0 +1,146 @@
+use crate::storage::Handle;
+
+/// The result of an operation that updates an object
+///
+/// Besides the updated object, this reports which of its children have been
+/// changed, so callers can react to exactly what changed, instead of having to
+/// compare the old and the new object themselves.
+#[derive(Clone, Debug)]
+pub struct Updated<T, C> {
+    /// The updated object
+    pub object: T,
+
+    /// The changes to the children of the object
+    pub diff: Diff<C>,
+}
+
+impl<T, C> Updated<T, C> {
+    /// Discard the diff and return the updated object
+    pub fn into_object(self) -> T {
+        self.object
+    }
+}
+
+/// The changes that an operation made to a collection of objects
+///
+/// Objects are identified by their handles. An object that has been replaced
+/// is not also listed as added or removed.
+#[derive(Clone, Debug)]
+pub struct Diff<T> {
+    /// The objects that have been added
+    pub added: Vec<Handle<T>>,
+
+    /// The objects that have been removed
+    pub removed: Vec<Handle<T>>,
+
+    /// The objects that have been replaced, as pairs of old and new object
+    pub replaced: Vec<(Handle<T>, Handle<T>)>,
+}
+
+impl<T> Diff<T> {
+    /// Create an empty diff
+    pub fn new() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            replaced: Vec::new(),
+        }
+    }
+
+    /// Indicate whether the diff contains no changes
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.replaced.is_empty()
+    }
+
+    /// Combine this diff with one from a subsequent operation
+    ///
+    /// Objects that were added or replaced by this diff, and are then changed
+    /// again by `next`, are only reported in their final state.
+    pub fn then(mut self, next: Self) -> Self {
+        for (old, new) in next.replaced {
+            if let Some(added) =
+                self.added.iter_mut().find(|added| added.id() == old.id())
+            {
+                *added = new;
+            } else if let Some((_, replacement)) = self
+                .replaced
+                .iter_mut()
+                .find(|(_, replacement)| replacement.id() == old.id())
+            {
+                *replacement = new;
+            } else {
+                self.replaced.push((old, new));
+            }
+        }
+
+        for removed in next.removed {
+            if let Some(i) = self
+                .added
+                .iter()
+                .position(|added| added.id() == removed.id())
+            {
+                self.added.remove(i);
+            } else if let Some(i) = self
+                .replaced
+                .iter()
+                .position(|(_, replacement)| replacement.id() == removed.id())
+            {
+                let (old, _) = self.replaced.remove(i);
+                self.removed.push(old);
+            } else {
+                self.removed.push(removed);
+            }
+        }
+
+        self.added.extend(next.added);
+
+        self
+    }
+}
+
+impl<T> Default for Diff<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::GlobalEdge, operations::Insert, services::Services,
+        storage::Handle,
+    };
+
+    use super::Diff;
+
+    #[test]
+    fn then() {
+        let mut services = Services::new();
+
+        let [a, b, c, d] =
+            [(); 4].map(|()| GlobalEdge::new().insert(&mut services.objects));
+
+        let first = Diff {
+            added: vec![a.clone()],
+            removed: Vec::new(),
+            replaced: vec![(b.clone(), c.clone())],
+        };
+        let second = Diff {
+            added: Vec::new(),
+            removed: vec![c],
+            replaced: vec![(a, d.clone())],
+        };
+
+        let diff = first.then(second);
+
+        // Handles compare equal by value, so compare their IDs instead.
+        let ids = |handles: &[Handle<GlobalEdge>]| {
+            handles.iter().map(|handle| handle.id()).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&diff.added), vec![d.id()]);
+        assert_eq!(ids(&diff.removed), vec![b.id()]);
+        assert!(diff.replaced.is_empty());
+    }
+}
@@ -40,6 +40,101 @@ impl_insert!(
     Shell, shells;
     Sketch, sketches;
     Solid, solids;
-    Surface, surfaces;
     Vertex, vertices;
 );
+
+impl Insert for Surface {
+    fn insert(self, objects: &mut Service<Objects>) -> Handle<Self> {
+        // Surfaces that are created independently of each other, for example
+        // by the faces of a pattern, often have the same geometry. Reusing an
+        // existing surface keeps the object graph small.
+        if let Some(surface) = objects.surfaces.find(&self.geometry()) {
+            objects.execute(Operation::ReuseObject {
+                object: surface.clone().into(),
+            });
+            return surface;
+        }
+
+        let handle = objects.surfaces.reserve();
+        let object = (handle.clone(), self).into();
+        objects.execute(Operation::InsertObject { object });
+        handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Vector;
+
+    use crate::{
+        geometry::{curve::GlobalPath, surface::SurfaceGeometry},
+        objects::{Objects, StableId, Surface},
+        services::{Services, Validation},
+    };
+
+    use super::Insert;
+
+    #[test]
+    fn insert_identical_surface() {
+        let mut services = Services::new();
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::x_axis(),
+            v: Vector::unit_y(),
+        })
+        .insert(&mut services.objects);
+        assert_eq!(surface.id(), services.objects.surfaces.xy_plane().id());
+
+        let surface = Surface::new(SurfaceGeometry {
+            u: GlobalPath::x_axis(),
+            v: Vector::from([0., 1., 1.]),
+        })
+        .insert(&mut services.objects);
+        assert_ne!(surface.id(), services.objects.surfaces.xy_plane().id());
+    }
+
+    #[test]
+    fn stable_ids_dont_depend_on_deduplication() {
+        fn stable_id_after(second: [f64; 3]) -> Option<StableId> {
+            let mut services = Services::new();
+
+            let [_, _, surface] =
+                [[0., 1., 1.], second, [0., 1., 2.]].map(|v| {
+                    Surface::new(SurfaceGeometry {
+                        u: GlobalPath::x_axis(),
+                        v: Vector::from(v),
+                    })
+                    .insert(&mut services.objects)
+                });
+
+            services.objects.stable_id(surface.id())
+        }
+
+        let deduplicated = stable_id_after([0., 1., 1.]);
+        let inserted = stable_id_after([0., 1., 3.]);
+
+        assert!(deduplicated.is_some());
+        assert_eq!(deduplicated, inserted);
+    }
+
+    #[test]
+    fn insert_identical_surface_in_large_model() {
+        let geometry = SurfaceGeometry {
+            u: GlobalPath::x_axis(),
+            v: Vector::from([0., 1000., 1000.]),
+        };
+        let inaccurate = SurfaceGeometry {
+            v: geometry.v + Vector::from([0., 1e-12, 0.]),
+            ..geometry
+        };
+
+        let mut services = Services::from_states(
+            Objects::with_model_size(1000.),
+            Validation::default(),
+        );
+
+        let a = Surface::new(geometry).insert(&mut services.objects);
+        let b = Surface::new(inaccurate).insert(&mut services.objects);
+        assert_eq!(a.id(), b.id());
+    }
+}
@@ -1,14 +1,18 @@
 //! Operations to update shapes
 
 mod build;
+mod diff;
 mod insert;
+mod select;
 mod update;
 
 pub use self::{
     build::{
-        BuildCycle, BuildFace, BuildHalfEdge, BuildShell, BuildSurface,
-        Tetrahedron, Triangle,
+        BuildCycle, BuildFace, BuildHalfEdge, BuildShell, BuildSolid,
+        BuildSurface, SewError, Tetrahedron, Triangle, UnsewnEdge,
     },
+    diff::{Diff, Updated},
     insert::Insert,
+    select::Select,
     update::{UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateShell},
 };
@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+
+use fj_math::{Aabb, Scalar, Vector};
+
+use crate::{
+    algorithms::bounding_volume::BoundingVolume,
+    geometry::curve::GlobalPath,
+    objects::{Face, FaceSet, HalfEdge, Shell, Solid},
+    storage::Handle,
+};
+
+/// Select faces and edges of a shape, by their geometric properties
+///
+/// The selected objects are returned as handles, which can be passed on to
+/// other operations. Unlike indices, handles keep referring to the same
+/// objects, regardless of how the shape has been constructed.
+pub trait Select {
+    /// Access all faces of the shape
+    fn all_faces(&self) -> Vec<Handle<Face>>;
+
+    /// Access all edges of the shape
+    ///
+    /// Every edge of a closed shape is represented by two half-edges, one for
+    /// each of the faces that it connects. Only one half-edge per edge is
+    /// returned.
+    fn all_edges(&self) -> Vec<Handle<HalfEdge>> {
+        edges_with_faces(&self.all_faces())
+            .into_iter()
+            .map(|(half_edge, _)| half_edge)
+            .collect()
+    }
+
+    /// Select the planar faces that are parallel to the given vector
+    ///
+    /// These are the faces that the vector lies within. For a shape that has
+    /// been swept along the vector, these are the side walls.
+    fn faces_parallel_to(
+        &self,
+        vector: impl Into<Vector<3>>,
+    ) -> Vec<Handle<Face>> {
+        let vector = vector.into().normalize();
+
+        self.all_faces()
+            .into_iter()
+            .filter(|face| {
+                plane_normal(face)
+                    .map(|normal| {
+                        normal.dot(&vector).abs() < Scalar::from(MAX_DEVIATION)
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Select the planar faces that are perpendicular to the given vector
+    ///
+    /// These are the faces whose normal points along or against the vector.
+    /// For a shape that has been swept along the vector, these are the caps.
+    fn faces_perpendicular_to(
+        &self,
+        vector: impl Into<Vector<3>>,
+    ) -> Vec<Handle<Face>> {
+        let vector = vector.into().normalize();
+
+        self.all_faces()
+            .into_iter()
+            .filter(|face| {
+                plane_normal(face)
+                    .map(|normal| {
+                        normal.dot(&vector).abs() > Scalar::ONE - MAX_DEVIATION
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Select the faces whose bounding volume intersects the given AABB
+    fn faces_touching_aabb(&self, aabb: &Aabb<3>) -> Vec<Handle<Face>> {
+        self.all_faces()
+            .into_iter()
+            .filter(|face| {
+                face.aabb()
+                    .map(|face_aabb| face_aabb.intersects(aabb))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Select the edges that are longer than the given length
+    ///
+    /// The length of curved edges is approximated.
+    fn edges_longer_than(
+        &self,
+        length: impl Into<Scalar>,
+    ) -> Vec<Handle<HalfEdge>> {
+        let length = length.into();
+
+        edges_with_faces(&self.all_faces())
+            .into_iter()
+            .filter(|(half_edge, face)| edge_length(half_edge, face) > length)
+            .map(|(half_edge, _)| half_edge)
+            .collect()
+    }
+}
+
+impl Select for FaceSet {
+    fn all_faces(&self) -> Vec<Handle<Face>> {
+        self.into_iter().cloned().collect()
+    }
+}
+
+impl Select for Shell {
+    fn all_faces(&self) -> Vec<Handle<Face>> {
+        self.faces().all_faces()
+    }
+}
+
+impl Select for Solid {
+    fn all_faces(&self) -> Vec<Handle<Face>> {
+        self.shells().flat_map(|shell| shell.all_faces()).collect()
+    }
+}
+
+/// The maximum deviation of normalized vectors, when comparing directions
+const MAX_DEVIATION: f64 = 1e-9;
+
+fn plane_normal(face: &Face) -> Option<Vector<3>> {
+    let geometry = face.surface().geometry();
+
+    match geometry.u {
+        GlobalPath::Line(line) => {
+            Some(line.direction().cross(&geometry.v).normalize())
+        }
+        GlobalPath::Bezier(_)
+        | GlobalPath::Circle(_)
+        | GlobalPath::Ellipse(_) => None,
+    }
+}
+
+fn edges_with_faces(
+    faces: &[Handle<Face>],
+) -> Vec<(Handle<HalfEdge>, Handle<Face>)> {
+    let mut global_edges = BTreeSet::new();
+    let mut edges = Vec::new();
+
+    for face in faces {
+        for cycle in face.all_cycles() {
+            for half_edge in cycle.half_edges() {
+                if global_edges.insert(half_edge.global_form().id()) {
+                    edges.push((half_edge.clone(), face.clone()));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+fn edge_length(half_edge: &HalfEdge, face: &Face) -> Scalar {
+    // Straight lines on planes are measured exactly. Everything else is
+    // approximated by a polyline, which is slightly shorter than the actual
+    // edge.
+    const NUM_SEGMENTS: u32 = 64;
+
+    let surface = face.surface().geometry();
+    let [start, end] = half_edge.boundary();
+
+    let points = (0..=NUM_SEGMENTS)
+        .map(|i| {
+            let t = f64::from(i) / f64::from(NUM_SEGMENTS);
+            let point = start + (end - start) * t;
+            surface.point_from_surface_coords(
+                half_edge.curve().point_from_path_coords(point),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    points
+        .windows(2)
+        .map(|points| points[0].distance_to(&points[1]))
+        .fold(Scalar::ZERO, |length, segment| length + segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Vector};
+
+    use crate::{objects::Shell, operations::BuildShell, services::Services};
+
+    use super::Select;
+
+    #[test]
+    fn select_faces_and_edges_of_box() {
+        let mut services = Services::new();
+
+        let shell = Shell::box_from_corners(
+            [[0., 0., 0.], [1., 2., 3.]],
+            &mut services.objects,
+        );
+
+        assert_eq!(shell.all_faces().len(), 6);
+        assert_eq!(shell.all_edges().len(), 12);
+
+        assert_eq!(shell.faces_parallel_to(Vector::unit_z()).len(), 4);
+        assert_eq!(shell.faces_perpendicular_to(Vector::unit_z()).len(), 2);
+
+        let corner = Aabb::<3>::from_points([[-0.1; 3], [0.1; 3]]);
+        assert_eq!(shell.faces_touching_aabb(&corner).len(), 3);
+
+        // The edges along the y-axis and the z-axis.
+        assert_eq!(shell.edges_longer_than(1.5).len(), 8);
+    }
+}
@@ -1,5 +1,10 @@
+use fj_math::{Point, Winding};
+
 use crate::{
-    objects::{Cycle, Face},
+    builder::CycleBuilder,
+    objects::{Cycle, Face, Objects},
+    operations::Insert,
+    services::Service,
     storage::Handle,
 };
 
@@ -10,6 +15,16 @@ pub trait UpdateFace {
         &self,
         f: impl FnOnce(&Handle<Cycle>) -> Handle<Cycle>,
     ) -> Face;
+
+    /// Add a polygonal interior cycle to the face
+    ///
+    /// The interior gets the winding opposite to that of the exterior, as
+    /// required for a valid face, regardless of the order of its points.
+    fn add_interior_polygon(
+        &self,
+        points: impl IntoIterator<Item = impl Into<Point<2>>>,
+        objects: &mut Service<Objects>,
+    ) -> Face;
 }
 
 impl UpdateFace for Face {
@@ -25,5 +40,29 @@ impl UpdateFace for Face {
             self.interiors().cloned(),
             self.color(),
         )
+        .with_texture(self.texture())
+    }
+
+    fn add_interior_polygon(
+        &self,
+        points: impl IntoIterator<Item = impl Into<Point<2>>>,
+        objects: &mut Service<Objects>,
+    ) -> Face {
+        let winding = match self.exterior().winding() {
+            Winding::Ccw => Winding::Cw,
+            Winding::Cw => Winding::Ccw,
+        };
+        let interior =
+            CycleBuilder::polygon_with_winding(points, winding, objects)
+                .build(objects)
+                .insert(objects);
+
+        Face::new(
+            self.surface().clone(),
+            self.exterior().clone(),
+            self.interiors().cloned().chain([interior]),
+            self.color(),
+        )
+        .with_texture(self.texture())
     }
 }
@@ -1,19 +1,29 @@
 use crate::{
     objects::{Face, Shell},
+    operations::{Diff, Updated},
     storage::Handle,
 };
 
 /// Update a [`Shell`]
 pub trait UpdateShell {
     /// Update a face of the shell
+    ///
+    /// The diff reports the face as replaced, unless `f` returns the face it
+    /// was passed.
     fn update_face(
         &self,
         handle: &Handle<Face>,
         f: impl FnMut(&Handle<Face>) -> Handle<Face>,
-    ) -> Shell;
+    ) -> Updated<Shell, Face>;
+
+    /// Add faces to the shell
+    fn add_faces(
+        &self,
+        faces: impl IntoIterator<Item = Handle<Face>>,
+    ) -> Updated<Shell, Face>;
 
     /// Remove a face from the shell
-    fn remove_face(&self, handle: &Handle<Face>) -> Shell;
+    fn remove_face(&self, handle: &Handle<Face>) -> Updated<Shell, Face>;
 }
 
 impl UpdateShell for Shell {
@@ -21,25 +31,66 @@ impl UpdateShell for Shell {
         &self,
         handle: &Handle<Face>,
         mut f: impl FnMut(&Handle<Face>) -> Handle<Face>,
-    ) -> Shell {
-        let faces = self.faces().into_iter().map(|face| {
-            if face.id() == handle.id() {
-                f(face)
-            } else {
-                face.clone()
-            }
-        });
-
-        Shell::new(faces)
+    ) -> Updated<Shell, Face> {
+        let mut diff = Diff::new();
+
+        let faces = self
+            .faces()
+            .into_iter()
+            .map(|face| {
+                if face.id() == handle.id() {
+                    let updated = f(face);
+                    if updated.id() != face.id() {
+                        diff.replaced.push((face.clone(), updated.clone()));
+                    }
+                    updated
+                } else {
+                    face.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Updated {
+            object: Shell::new(faces),
+            diff,
+        }
+    }
+
+    fn add_faces(
+        &self,
+        faces: impl IntoIterator<Item = Handle<Face>>,
+    ) -> Updated<Shell, Face> {
+        let added = faces.into_iter().collect::<Vec<_>>();
+        let faces = self.faces().into_iter().cloned().chain(added.clone());
+
+        Updated {
+            object: Shell::new(faces),
+            diff: Diff {
+                added,
+                ..Diff::new()
+            },
+        }
     }
 
-    fn remove_face(&self, handle: &Handle<Face>) -> Shell {
+    fn remove_face(&self, handle: &Handle<Face>) -> Updated<Shell, Face> {
+        let mut diff = Diff::new();
+
         let faces = self
             .faces()
             .into_iter()
-            .filter(|face| face.id() == handle.id())
-            .cloned();
+            .filter(|face| {
+                let remove = face.id() == handle.id();
+                if remove {
+                    diff.removed.push((*face).clone());
+                }
+                !remove
+            })
+            .cloned()
+            .collect::<Vec<_>>();
 
-        Shell::new(faces)
+        Updated {
+            object: Shell::new(faces),
+            diff,
+        }
     }
 }
@@ -0,0 +1,769 @@
+//! Serialization of object graphs
+//!
+//! See [`ObjectGraph`].
+
+use std::collections::HashMap;
+
+use fj_interop::mesh::Color;
+use fj_math::{
+    Bezier, Circle, Ellipse, Line, Point, Scalar, Transform, Vector,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithms::texture::{Pattern, Texture},
+    geometry::{
+        curve::{Curve, GlobalPath},
+        surface::SurfaceGeometry,
+    },
+    objects::{
+        Cycle, Face, GlobalEdge, HalfEdge, Instance, Objects, Shell, Sketch,
+        Solid, Surface, Vertex,
+    },
+    operations::Insert,
+    services::Service,
+    storage::{Handle, ObjectId},
+};
+
+/// A serializable snapshot of all objects in an [`Objects`] store
+///
+/// Objects refer to each other by their index in the snapshot. This preserves
+/// shared objects: a half-edge that is referenced by two cycles is stored
+/// once, and is still shared, after the snapshot has been loaded.
+///
+/// The built-in planes of [`Objects::surfaces`] are always stored first, in the
+/// order xy, xz, yz. They are mapped to the built-in planes of the store that
+/// the snapshot is loaded into, instead of being duplicated.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObjectGraph {
+    surfaces: Vec<SurfaceData>,
+    vertices: usize,
+    global_edges: usize,
+    half_edges: Vec<HalfEdgeData>,
+    cycles: Vec<Vec<usize>>,
+    faces: Vec<FaceData>,
+    shells: Vec<Vec<usize>>,
+    solids: Vec<Vec<usize>>,
+    sketches: Vec<Vec<usize>>,
+
+    // Snapshots from before solids could contain instances don't have this.
+    #[serde(default)]
+    instances: Vec<InstanceData>,
+}
+
+impl ObjectGraph {
+    /// Take a snapshot of all objects in the provided store
+    pub fn from_objects(objects: &Objects) -> Self {
+        let built_in_planes = [
+            objects.surfaces.xy_plane(),
+            objects.surfaces.xz_plane(),
+            objects.surfaces.yz_plane(),
+        ];
+        let other_surfaces = objects
+            .surfaces
+            .iter()
+            .filter(|surface| !built_in_planes.contains(surface));
+        let surfaces =
+            Indices::new(built_in_planes.into_iter().chain(other_surfaces));
+        let vertices = Indices::new(&objects.vertices);
+        let global_edges = Indices::new(&objects.global_edges);
+        let half_edges = Indices::new(&objects.half_edges);
+        let cycles = Indices::new(&objects.cycles);
+        let faces = Indices::new(&objects.faces);
+        let shells = Indices::new(&objects.shells);
+        let solids = Indices::new(&objects.solids);
+
+        let instances = {
+            let solids = &solids;
+
+            solids
+                .handles
+                .iter()
+                .enumerate()
+                .flat_map(|(i, solid)| {
+                    solid.instances().map(move |instance| InstanceData {
+                        solid: i,
+                        instance_of: solids.get(instance.solid()),
+                        transform: instance
+                            .transform()
+                            .data()
+                            .try_into()
+                            .expect("Transform data has 16 elements"),
+                    })
+                })
+                .collect()
+        };
+
+        Self {
+            surfaces: surfaces
+                .handles
+                .iter()
+                .map(|surface| SurfaceData::new(&surface.geometry()))
+                .collect(),
+            vertices: vertices.handles.len(),
+            global_edges: global_edges.handles.len(),
+            half_edges: half_edges
+                .handles
+                .iter()
+                .map(|half_edge| {
+                    let [start, end] = half_edge.boundary();
+
+                    HalfEdgeData {
+                        curve: CurveData::new(&half_edge.curve()),
+                        boundary: [start.t.into_f64(), end.t.into_f64()],
+                        start_vertex: vertices.get(half_edge.start_vertex()),
+                        global_form: global_edges.get(half_edge.global_form()),
+                    }
+                })
+                .collect(),
+            cycles: cycles
+                .handles
+                .iter()
+                .map(|cycle| half_edges.all(cycle.half_edges(), false))
+                .collect(),
+            faces: faces
+                .handles
+                .iter()
+                .map(|face| FaceData {
+                    surface: surfaces.get(face.surface()),
+                    exterior: cycles.get(face.exterior()),
+                    interiors: cycles.all(face.interiors(), false),
+                    color: face.color().map(|Color(color)| color),
+                    texture: face.texture().map(TextureData::new),
+                })
+                .collect(),
+            // Shells, solids, and sketches refer to sets of objects. Sort
+            // their indices, to make the snapshot deterministic.
+            shells: shells
+                .handles
+                .iter()
+                .map(|shell| faces.all(shell.faces(), true))
+                .collect(),
+            solids: solids
+                .handles
+                .iter()
+                .map(|solid| shells.all(solid.shells(), true))
+                .collect(),
+            sketches: objects
+                .sketches
+                .iter()
+                .map(|sketch| faces.all(sketch.faces(), true))
+                .collect(),
+            instances,
+        }
+    }
+
+    /// Insert all objects in the snapshot into a store
+    ///
+    /// Returns the handles of the top-level objects that have been inserted.
+    /// All inserted objects are validated, as usual.
+    ///
+    /// Returns an error, if the snapshot refers to objects that it doesn't
+    /// contain, or if it contains geometry that is invalid, like a circle with
+    /// a radius of zero. The snapshot is checked before any objects are
+    /// inserted.
+    pub fn insert_into(
+        &self,
+        objects: &mut Service<Objects>,
+    ) -> Result<InsertedObjects, InsertError> {
+        let surface_geometry = self
+            .surfaces
+            .iter()
+            .map(SurfaceData::to_geometry)
+            .collect::<Result<Vec<_>, _>>()?;
+        let curves = self
+            .half_edges
+            .iter()
+            .map(|half_edge| half_edge.curve.to_curve())
+            .collect::<Result<Vec<_>, _>>()?;
+        for number in self
+            .half_edges
+            .iter()
+            .flat_map(|half_edge| half_edge.boundary)
+            .chain(self.instances.iter().flat_map(|i| i.transform))
+        {
+            finite(number)?;
+        }
+
+        let built_in_planes = [
+            objects.surfaces.xy_plane(),
+            objects.surfaces.xz_plane(),
+            objects.surfaces.yz_plane(),
+        ];
+        let other_surfaces = surface_geometry
+            .into_iter()
+            .skip(built_in_planes.len())
+            .map(|geometry| Surface::new(geometry).insert(objects))
+            .collect::<Vec<_>>();
+        let surfaces = built_in_planes
+            .into_iter()
+            .chain(other_surfaces)
+            .collect::<Vec<_>>();
+
+        let vertices = (0..self.vertices)
+            .map(|_| Vertex::new().insert(objects))
+            .collect::<Vec<_>>();
+        let global_edges = (0..self.global_edges)
+            .map(|_| GlobalEdge::new().insert(objects))
+            .collect::<Vec<_>>();
+
+        let mut half_edges = Vec::new();
+        for (half_edge, curve) in self.half_edges.iter().zip(curves) {
+            let half_edge = HalfEdge::new(
+                curve,
+                half_edge.boundary.map(|t| Point::from([t])),
+                resolve(&vertices, half_edge.start_vertex, "vertex")?,
+                resolve(&global_edges, half_edge.global_form, "global edge")?,
+            );
+            half_edges.push(half_edge.insert(objects));
+        }
+
+        let mut cycles = Vec::new();
+        for cycle in &self.cycles {
+            let cycle =
+                Cycle::new(resolve_all(&half_edges, cycle, "half-edge")?);
+            cycles.push(cycle.insert(objects));
+        }
+
+        let mut faces = Vec::new();
+        for face in &self.faces {
+            let face = Face::new(
+                resolve(&surfaces, face.surface, "surface")?,
+                resolve(&cycles, face.exterior, "cycle")?,
+                resolve_all(&cycles, &face.interiors, "cycle")?,
+                face.color.map(Color),
+            )
+            .with_texture(face.texture.map(|t| t.to_texture()).transpose()?);
+            faces.push(face.insert(objects));
+        }
+
+        let mut shells = Vec::new();
+        for shell in &self.shells {
+            let shell = Shell::new(resolve_all(&faces, shell, "face")?);
+            shells.push(shell.insert(objects));
+        }
+
+        // A solid can only refer to solids that were inserted before it, so
+        // instances always refer to solids with a lower index.
+        let mut solids = Vec::new();
+        for (i, solid) in self.solids.iter().enumerate() {
+            let instances = self
+                .instances
+                .iter()
+                .filter(|instance| instance.solid == i)
+                .map(|instance| {
+                    Ok(Instance::new(
+                        resolve(&solids, instance.instance_of, "solid")?,
+                        Transform::from_data(instance.transform),
+                    ))
+                })
+                .collect::<Result<Vec<_>, InvalidReference>>()?;
+
+            let solid = Solid::new(resolve_all(&shells, solid, "shell")?)
+                .with_instances(instances);
+            solids.push(solid.insert(objects));
+        }
+
+        let mut sketches = Vec::new();
+        for sketch in &self.sketches {
+            let sketch = Sketch::new(resolve_all(&faces, sketch, "face")?);
+            sketches.push(sketch.insert(objects));
+        }
+
+        Ok(InsertedObjects {
+            faces,
+            shells,
+            solids,
+            sketches,
+        })
+    }
+}
+
+/// The objects that have been inserted by [`ObjectGraph::insert_into`]
+///
+/// Lower-level objects are reachable through these.
+#[derive(Clone, Debug)]
+pub struct InsertedObjects {
+    /// The inserted faces
+    pub faces: Vec<Handle<Face>>,
+
+    /// The inserted shells
+    pub shells: Vec<Handle<Shell>>,
+
+    /// The inserted solids
+    pub solids: Vec<Handle<Solid>>,
+
+    /// The inserted sketches
+    pub sketches: Vec<Handle<Sketch>>,
+}
+
+/// Error returned by [`ObjectGraph::insert_into`]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum InsertError {
+    /// The object graph refers to an object that it doesn't contain
+    #[error(transparent)]
+    InvalidReference(#[from] InvalidReference),
+
+    /// The object graph contains invalid geometry
+    #[error(transparent)]
+    InvalidGeometry(#[from] InvalidGeometry),
+}
+
+/// An [`ObjectGraph`] refers to an object that it doesn't contain
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Object graph refers to {kind} {index}, which doesn't exist")]
+pub struct InvalidReference {
+    /// The kind of object that is referred to
+    pub kind: &'static str,
+
+    /// The index that refers to the object
+    pub index: usize,
+}
+
+/// An [`ObjectGraph`] contains geometry that is invalid
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Object graph contains invalid geometry: {reason}")]
+pub struct InvalidGeometry {
+    /// Why the geometry is invalid
+    pub reason: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SurfaceData {
+    u: GlobalPathData,
+    v: [f64; 3],
+}
+
+impl SurfaceData {
+    fn new(geometry: &SurfaceGeometry) -> Self {
+        Self {
+            u: GlobalPathData::new(&geometry.u),
+            v: geometry.v.components.map(|c| c.into_f64()),
+        }
+    }
+
+    fn to_geometry(&self) -> Result<SurfaceGeometry, InvalidGeometry> {
+        let v = vector(self.v)?;
+        if v.magnitude() == Scalar::ZERO {
+            return Err(invalid("Surface has a zero `v` vector"));
+        }
+
+        Ok(SurfaceGeometry {
+            u: self.u.to_path()?,
+            v,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum GlobalPathData {
+    Bezier {
+        points: [[f64; 3]; 4],
+    },
+    Circle {
+        center: [f64; 3],
+        a: [f64; 3],
+        b: [f64; 3],
+    },
+    Ellipse {
+        center: [f64; 3],
+        a: [f64; 3],
+        b: [f64; 3],
+    },
+    Line {
+        origin: [f64; 3],
+        direction: [f64; 3],
+    },
+}
+
+impl GlobalPathData {
+    fn new(path: &GlobalPath) -> Self {
+        match path {
+            GlobalPath::Bezier(bezier) => Self::Bezier {
+                points: bezier
+                    .control_points()
+                    .map(|point| point.coords.components.map(|c| c.into_f64())),
+            },
+            GlobalPath::Circle(circle) => Self::Circle {
+                center: circle.center().coords.components.map(|c| c.into_f64()),
+                a: circle.a().components.map(|c| c.into_f64()),
+                b: circle.b().components.map(|c| c.into_f64()),
+            },
+            GlobalPath::Ellipse(ellipse) => Self::Ellipse {
+                center: ellipse
+                    .center()
+                    .coords
+                    .components
+                    .map(|c| c.into_f64()),
+                a: ellipse.a().components.map(|c| c.into_f64()),
+                b: ellipse.b().components.map(|c| c.into_f64()),
+            },
+            GlobalPath::Line(line) => Self::Line {
+                origin: line.origin().coords.components.map(|c| c.into_f64()),
+                direction: line.direction().components.map(|c| c.into_f64()),
+            },
+        }
+    }
+
+    fn to_path(&self) -> Result<GlobalPath, InvalidGeometry> {
+        let path = match *self {
+            Self::Bezier { points } => {
+                GlobalPath::Bezier(Bezier::new(bezier_points(points)?))
+            }
+            Self::Circle { center, a, b } => {
+                let (center, a, b) = (point(center)?, vector(a)?, vector(b)?);
+                check_circle(a, b)?;
+                GlobalPath::Circle(Circle::new(center, a, b))
+            }
+            Self::Ellipse { center, a, b } => {
+                let (center, a, b) = (point(center)?, vector(a)?, vector(b)?);
+                check_ellipse(a, b)?;
+                GlobalPath::Ellipse(Ellipse::new(center, a, b))
+            }
+            Self::Line { origin, direction } => {
+                let (origin, direction) = (point(origin)?, vector(direction)?);
+                check_line(direction)?;
+                GlobalPath::Line(Line::from_origin_and_direction(
+                    origin, direction,
+                ))
+            }
+        };
+
+        Ok(path)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum CurveData {
+    Bezier {
+        points: [[f64; 2]; 4],
+    },
+    Circle {
+        center: [f64; 2],
+        a: [f64; 2],
+        b: [f64; 2],
+    },
+    Line {
+        origin: [f64; 2],
+        direction: [f64; 2],
+    },
+}
+
+impl CurveData {
+    fn new(curve: &Curve) -> Self {
+        match curve {
+            Curve::Bezier(bezier) => Self::Bezier {
+                points: bezier
+                    .control_points()
+                    .map(|point| point.coords.components.map(|c| c.into_f64())),
+            },
+            Curve::Circle(circle) => Self::Circle {
+                center: circle.center().coords.components.map(|c| c.into_f64()),
+                a: circle.a().components.map(|c| c.into_f64()),
+                b: circle.b().components.map(|c| c.into_f64()),
+            },
+            Curve::Line(line) => Self::Line {
+                origin: line.origin().coords.components.map(|c| c.into_f64()),
+                direction: line.direction().components.map(|c| c.into_f64()),
+            },
+        }
+    }
+
+    fn to_curve(&self) -> Result<Curve, InvalidGeometry> {
+        let curve = match *self {
+            Self::Bezier { points } => {
+                Curve::Bezier(Bezier::new(bezier_points(points)?))
+            }
+            Self::Circle { center, a, b } => {
+                let (center, a, b) = (point(center)?, vector(a)?, vector(b)?);
+                check_circle(a, b)?;
+                Curve::Circle(Circle::new(center, a, b))
+            }
+            Self::Line { origin, direction } => {
+                let (origin, direction) = (point(origin)?, vector(direction)?);
+                check_line(direction)?;
+                Curve::Line(Line::from_origin_and_direction(origin, direction))
+            }
+        };
+
+        Ok(curve)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct HalfEdgeData {
+    curve: CurveData,
+    boundary: [f64; 2],
+    start_vertex: usize,
+    global_form: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct InstanceData {
+    solid: usize,
+    instance_of: usize,
+    transform: [f64; 16],
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FaceData {
+    surface: usize,
+    exterior: usize,
+    interiors: Vec<usize>,
+    color: Option<[u8; 4]>,
+
+    // Snapshots from before faces could have textures don't have this.
+    #[serde(default)]
+    texture: Option<TextureData>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct TextureData {
+    pattern: Pattern,
+    pitch: f64,
+    depth: f64,
+}
+
+impl TextureData {
+    fn new(texture: Texture) -> Self {
+        Self {
+            pattern: texture.pattern,
+            pitch: texture.pitch.into_f64(),
+            depth: texture.depth.into_f64(),
+        }
+    }
+
+    fn to_texture(&self) -> Result<Texture, InvalidGeometry> {
+        finite(self.pitch)?;
+        finite(self.depth)?;
+        if self.pitch <= 0. || self.depth < 0. {
+            return Err(invalid(
+                "Texture has a non-positive pitch or a negative depth",
+            ));
+        }
+
+        Ok(Texture {
+            pattern: self.pattern,
+            pitch: Scalar::from(self.pitch),
+            depth: Scalar::from(self.depth),
+        })
+    }
+}
+
+/// The objects of one kind, and a lookup from their IDs to their indices
+struct Indices<T> {
+    handles: Vec<Handle<T>>,
+    indices: HashMap<ObjectId, usize>,
+}
+
+impl<T> Indices<T> {
+    fn new(handles: impl IntoIterator<Item = Handle<T>>) -> Self {
+        let handles = handles.into_iter().collect::<Vec<_>>();
+        let indices = handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| (handle.id(), i))
+            .collect();
+
+        Self { handles, indices }
+    }
+
+    fn get(&self, handle: &Handle<T>) -> usize {
+        *self
+            .indices
+            .get(&handle.id())
+            .expect("Object refers to object that is not in the store")
+    }
+
+    fn all<'r>(
+        &self,
+        handles: impl IntoIterator<Item = &'r Handle<T>>,
+        sort: bool,
+    ) -> Vec<usize>
+    where
+        T: 'r,
+    {
+        let mut indices = handles
+            .into_iter()
+            .map(|handle| self.get(handle))
+            .collect::<Vec<_>>();
+        if sort {
+            indices.sort();
+        }
+        indices
+    }
+}
+
+// The following checks mirror the requirements of the geometry constructors
+// in `fj-math`, which panic, if those aren't met.
+
+fn finite(number: f64) -> Result<(), InvalidGeometry> {
+    if !number.is_finite() {
+        return Err(invalid("Geometry contains a value that is not finite"));
+    }
+
+    Ok(())
+}
+
+fn point<const D: usize>(
+    coords: [f64; D],
+) -> Result<Point<D>, InvalidGeometry> {
+    Ok(Point {
+        coords: vector(coords)?,
+    })
+}
+
+fn vector<const D: usize>(
+    components: [f64; D],
+) -> Result<Vector<D>, InvalidGeometry> {
+    for number in components {
+        finite(number)?;
+    }
+
+    Ok(Vector::from(components))
+}
+
+fn bezier_points<const D: usize>(
+    points: [[f64; D]; 4],
+) -> Result<[Point<D>; 4], InvalidGeometry> {
+    let [a, b, c, d] = points;
+    let points = [point(a)?, point(b)?, point(c)?, point(d)?];
+
+    if points.iter().all(|point| *point == points[0]) {
+        return Err(invalid("All control points of Bézier curve coincide"));
+    }
+
+    Ok(points)
+}
+
+fn check_circle<const D: usize>(
+    a: Vector<D>,
+    b: Vector<D>,
+) -> Result<(), InvalidGeometry> {
+    if a.magnitude() != b.magnitude() {
+        return Err(invalid("Circle vectors are not of equal length"));
+    }
+    if a.magnitude() == Scalar::ZERO {
+        return Err(invalid("Circle has a radius of zero"));
+    }
+    if a.dot(&b) >= Scalar::from(f64::EPSILON) {
+        return Err(invalid("Circle vectors are not perpendicular"));
+    }
+
+    Ok(())
+}
+
+fn check_ellipse<const D: usize>(
+    a: Vector<D>,
+    b: Vector<D>,
+) -> Result<(), InvalidGeometry> {
+    if a.magnitude() == Scalar::ZERO || b.magnitude() == Scalar::ZERO {
+        return Err(invalid("Ellipse has a zero vector"));
+    }
+
+    let gram = a.dot(&a) * b.dot(&b) - a.dot(&b) * a.dot(&b);
+    if gram <= Scalar::from(f64::EPSILON) * a.dot(&a) * b.dot(&b) {
+        return Err(invalid("Ellipse vectors are parallel"));
+    }
+
+    Ok(())
+}
+
+fn check_line<const D: usize>(
+    direction: Vector<D>,
+) -> Result<(), InvalidGeometry> {
+    if direction.magnitude() == Scalar::ZERO {
+        return Err(invalid("Line has a zero direction"));
+    }
+
+    Ok(())
+}
+
+fn invalid(reason: &'static str) -> InvalidGeometry {
+    InvalidGeometry { reason }
+}
+
+fn resolve<T>(
+    handles: &[Handle<T>],
+    index: usize,
+    kind: &'static str,
+) -> Result<Handle<T>, InvalidReference> {
+    handles
+        .get(index)
+        .cloned()
+        .ok_or(InvalidReference { kind, index })
+}
+
+fn resolve_all<T>(
+    handles: &[Handle<T>],
+    indices: &[usize],
+    kind: &'static str,
+) -> Result<Vec<Handle<T>>, InvalidReference> {
+    indices
+        .iter()
+        .map(|&index| resolve(handles, index, kind))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Transform;
+
+    use crate::{
+        objects::{Instance, Shell, Solid},
+        operations::{BuildShell, Insert},
+        services::Services,
+    };
+
+    use super::{CurveData, InsertError, ObjectGraph};
+
+    #[test]
+    fn round_trip() {
+        let mut services = Services::new();
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let shell = tetrahedron.shell.insert(&mut services.objects);
+        let solid = Solid::new([shell]).insert(&mut services.objects);
+        Solid::new([])
+            .with_instances([Instance::new(
+                solid,
+                Transform::translation([2., 0., 0.]),
+            )])
+            .insert(&mut services.objects);
+
+        let graph = ObjectGraph::from_objects(&services.objects);
+
+        let mut loaded = Services::new();
+        let inserted = graph.insert_into(&mut loaded.objects).unwrap();
+
+        assert_eq!(inserted.solids.len(), 2);
+        assert_eq!(inserted.solids[1].instances().count(), 1);
+        // Objects refer to each other by index, so this also checks that
+        // shared objects are still shared after loading.
+        assert_eq!(ObjectGraph::from_objects(&loaded.objects), graph);
+    }
+
+    #[test]
+    fn invalid_geometry() {
+        let mut services = Services::new();
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        tetrahedron.shell.insert(&mut services.objects);
+
+        let mut graph = ObjectGraph::from_objects(&services.objects);
+        graph.half_edges[0].curve = CurveData::Circle {
+            center: [0., 0.],
+            a: [0., 0.],
+            b: [0., 0.],
+        };
+
+        let mut loaded = Services::new();
+        let result = graph.insert_into(&mut loaded.objects);
+
+        assert!(matches!(result, Err(InsertError::InvalidGeometry(_))));
+    }
+}
@@ -6,16 +6,17 @@ mod objects;
 mod service;
 mod validation;
 
-use std::sync::Arc;
+use std::{mem, sync::Arc};
 
+use fj_interop::cancellation::CancellationToken;
 use parking_lot::Mutex;
 
 use crate::objects::Objects;
 
 pub use self::{
-    objects::{InsertObject, Operation},
-    service::{Service, State},
-    validation::{Validation, ValidationFailed},
+    objects::{InsertObject, ObjectsEvent, Operation},
+    service::{Service, Snapshot, State, Undo},
+    validation::{Validation, ValidationFailed, ValidationMode},
 };
 
 /// The kernel services
@@ -27,15 +28,114 @@ pub struct Services {
 
     /// The validation service
     ///
-    /// Validates objects that are inserted using the objects service.
-    pub validation: Arc<Mutex<Service<Validation>>>,
+    /// Validates objects that are inserted using the objects service. Only
+    /// `Services` and the objects service refer to it, which makes sure that
+    /// [`Services::drop_and_validate`] can take ownership of it.
+    validation: Arc<Mutex<Service<Validation>>>,
 }
 
 impl Services {
     /// Construct an instance of `Services`
     pub fn new() -> Self {
-        let mut objects = Service::<Objects>::default();
-        let validation = Arc::new(Mutex::new(Service::default()));
+        Self::with_validation(Validation::default())
+    }
+
+    /// Construct an instance of `Services` whose validation can be cancelled
+    ///
+    /// See [`Validation::with_cancellation`].
+    pub fn with_cancellation(cancellation: CancellationToken) -> Self {
+        Self::with_validation(Validation::with_cancellation(cancellation))
+    }
+
+    /// Construct an instance of `Services` that handles validation errors in
+    /// the given way
+    ///
+    /// See [`ValidationMode`].
+    pub fn with_validation_mode(mode: ValidationMode) -> Self {
+        Self::with_validation(Validation::default().with_mode(mode))
+    }
+
+    /// Access the validation errors that have occurred so far
+    pub fn validation_errors(&self) -> Vec<ValidationFailed> {
+        self.validation
+            .lock()
+            .errors
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Drop the services, returning all validation errors
+    ///
+    /// The validation service panics on drop, if any validation errors have
+    /// occurred. Use this method to handle those errors instead.
+    pub fn drop_and_validate(self) -> Result<(), Vec<ValidationFailed>> {
+        let Self {
+            objects,
+            validation,
+        } = self;
+
+        // The objects service holds a reference to the validation service, as
+        // its subscriber. Drop it, so we can take ownership.
+        drop(objects);
+
+        let Ok(validation) = Arc::try_unwrap(validation) else {
+            unreachable!(
+                "Validation service is private to `Services`, and no longer \
+                referenced by the objects service"
+            )
+        };
+        let mut validation = validation.into_inner().into_state();
+
+        let errors = mem::take(&mut validation.errors);
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(errors.into_values().flatten().collect())
+    }
+
+    /// Take a snapshot of the services
+    ///
+    /// See [`Services::rollback`].
+    pub fn snapshot(&self) -> ServicesSnapshot {
+        ServicesSnapshot {
+            objects: self.objects.snapshot(),
+            validation: self.validation.lock().snapshot(),
+        }
+    }
+
+    /// Roll back the services to the provided snapshot
+    ///
+    /// Discards all objects that have been inserted since the snapshot was
+    /// taken, as well as their validation errors. Handles to discarded objects
+    /// remain valid, but the objects are no longer part of the stores.
+    ///
+    /// Returns the events that have been rolled back.
+    pub fn rollback(
+        &mut self,
+        snapshot: ServicesSnapshot,
+    ) -> Vec<ObjectsEvent> {
+        self.validation.lock().rollback(snapshot.validation);
+        self.objects.rollback(snapshot.objects)
+    }
+
+    /// Construct an instance of `Services` from a validation service state
+    ///
+    /// Use this to combine the options of [`Validation`], like a cancellation
+    /// token and a [`ValidationMode`].
+    pub fn with_validation(validation: Validation) -> Self {
+        Self::from_states(Objects::new(), validation)
+    }
+
+    /// Construct an instance of `Services` from the states of both services
+    ///
+    /// Use this to configure the objects service too, for example using
+    /// [`Objects::with_model_size`].
+    pub fn from_states(objects: Objects, validation: Validation) -> Self {
+        let mut objects = Service::new(objects);
+        let validation = Arc::new(Mutex::new(Service::new(validation)));
 
         objects.subscribe(validation.clone());
 
@@ -51,3 +151,80 @@ impl Default for Services {
         Self::new()
     }
 }
+
+/// A snapshot of the kernel services
+///
+/// Returned by [`Services::snapshot`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ServicesSnapshot {
+    objects: Snapshot,
+    validation: Snapshot,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Cycle, GlobalEdge, HalfEdge, Vertex},
+        operations::{BuildHalfEdge, Insert},
+    };
+
+    use super::{Services, ValidationMode};
+
+    #[test]
+    fn rollback() {
+        let mut services = Services::new();
+
+        Vertex::new().insert(&mut services.objects);
+        let snapshot = services.snapshot();
+        let global_edge = GlobalEdge::new().insert(&mut services.objects);
+
+        let rolled_back = services.rollback(snapshot);
+
+        assert_eq!(rolled_back.len(), 1);
+        assert_eq!(services.objects.global_edges.iter().count(), 0);
+        assert_eq!(services.objects.vertices.iter().count(), 1);
+
+        // Handles to rolled back objects must remain valid.
+        let _ = global_edge.clone_object();
+    }
+
+    #[test]
+    fn drop_and_validate_returns_all_errors() {
+        let mut services = Services::new();
+
+        // Neither half-edge connects to the other. That's one error for each.
+        let half_edges =
+            [[[0., 0.], [1., 0.]], [[2., 0.], [3., 0.]]].map(|points| {
+                HalfEdge::line_segment(points, None, &mut services.objects)
+                    .insert(&mut services.objects)
+            });
+        Cycle::new(half_edges).insert(&mut services.objects);
+
+        assert_eq!(services.validation_errors().len(), 2);
+        assert_eq!(services.drop_and_validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Inserted invalid object")]
+    fn validation_mode_panic() {
+        let mut services =
+            Services::with_validation_mode(ValidationMode::Panic);
+        Cycle::new([]).insert(&mut services.objects);
+    }
+
+    #[test]
+    fn validation_mode_warn() {
+        let mut services = Services::with_validation_mode(ValidationMode::Warn);
+        Cycle::new([]).insert(&mut services.objects);
+
+        assert!(services.drop_and_validate().is_ok());
+    }
+
+    #[test]
+    fn validation_mode_collect() {
+        let mut services = Services::new();
+        Cycle::new([]).insert(&mut services.objects);
+
+        assert!(services.drop_and_validate().is_err());
+    }
+}
@@ -1,18 +1,91 @@
-use crate::objects::{Object, Objects, WithHandle};
+use crate::objects::{
+    BehindHandle, Object, Objects, OperationScope, Provenance, WithHandle,
+};
 
-use super::State;
+use super::{Service, State, Undo};
 
 impl State for Objects {
     type Command = Operation;
-    type Event = InsertObject;
+    type Event = ObjectsEvent;
 
     fn decide(&self, command: Self::Command, events: &mut Vec<Self::Event>) {
-        let Operation::InsertObject { object } = command;
-        events.push(InsertObject { object });
+        let event = match command {
+            Operation::InsertObject { object } => {
+                ObjectsEvent::InsertObject(InsertObject { object })
+            }
+            Operation::ReuseObject { object } => {
+                ObjectsEvent::ReuseObject { object }
+            }
+            Operation::EnterScope { provenance } => ObjectsEvent::EnterScope {
+                scope: self.provenances.nest(provenance),
+            },
+            Operation::ExitScope => {
+                let Some(scope) = self.provenances.current() else {
+                    return;
+                };
+
+                ObjectsEvent::ExitScope {
+                    scope: scope.clone(),
+                }
+            }
+        };
+
+        events.push(event);
     }
 
     fn evolve(&mut self, event: &Self::Event) {
-        event.object.clone().insert(self);
+        match event {
+            ObjectsEvent::InsertObject(event) => {
+                event.object.clone().insert(self);
+            }
+            ObjectsEvent::ReuseObject { object } => {
+                self.provenances.skip(object.name());
+            }
+            ObjectsEvent::EnterScope { scope } => {
+                self.provenances.enter(scope.clone());
+            }
+            ObjectsEvent::ExitScope { .. } => {
+                self.provenances.exit();
+            }
+        }
+    }
+}
+
+impl Undo for Objects {
+    fn undo(&mut self, event: &Self::Event) {
+        match event {
+            ObjectsEvent::InsertObject(event) => {
+                event.object.discard(self);
+            }
+            ObjectsEvent::ReuseObject { object } => {
+                self.provenances.unskip(object.name());
+            }
+            ObjectsEvent::EnterScope { .. } => {
+                self.provenances.unenter();
+            }
+            ObjectsEvent::ExitScope { scope } => {
+                self.provenances.unexit(scope.clone());
+            }
+        }
+    }
+}
+
+impl Service<Objects> {
+    /// Run the provided closure as part of an operation
+    ///
+    /// All objects that are inserted by the closure will have the provided
+    /// provenance, which can be queried using [`Objects::provenance`].
+    /// Operations can be nested.
+    pub fn with_provenance<R>(
+        &mut self,
+        provenance: Provenance,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.execute(Operation::EnterScope { provenance });
+        let result = f(self);
+        self.execute(Operation::ExitScope);
+
+        result
     }
 }
 
@@ -27,10 +100,60 @@ pub enum Operation {
         /// The object to insert
         object: Object<WithHandle>,
     },
+
+    /// Reuse an existing object, instead of inserting an identical one
+    ///
+    /// The object is counted as if it had been inserted. This keeps the
+    /// [`StableId`]s of the objects that are inserted after it independent of
+    /// whether it was deduplicated.
+    ///
+    /// [`StableId`]: crate::objects::StableId
+    ReuseObject {
+        /// The object to reuse
+        object: Object<BehindHandle>,
+    },
+
+    /// Attribute all objects that are inserted from now on to an operation
+    ///
+    /// Lasts until the matching [`Operation::ExitScope`]. See
+    /// [`Service::with_provenance`].
+    EnterScope {
+        /// The operation that inserts the objects
+        provenance: Provenance,
+    },
+
+    /// End the operation that was started by the last [`Operation::EnterScope`]
+    ExitScope,
 }
 
 /// Event produced by `Service<Objects>`
 #[derive(Clone, Debug)]
+pub enum ObjectsEvent {
+    /// An object has been inserted
+    InsertObject(InsertObject),
+
+    /// An existing object has been reused, instead of inserting an identical
+    /// one
+    ReuseObject {
+        /// The object that has been reused
+        object: Object<BehindHandle>,
+    },
+
+    /// An operation has been started
+    EnterScope {
+        /// The operation, nested within the previously active one
+        scope: OperationScope,
+    },
+
+    /// An operation has been ended
+    ExitScope {
+        /// The operation that has been ended
+        scope: OperationScope,
+    },
+}
+
+/// Event produced by `Service<Objects>`, when an object is inserted
+#[derive(Clone, Debug)]
 pub struct InsertObject {
     /// The object to insert
     pub object: Object<WithHandle>,
@@ -65,11 +65,57 @@ impl<S: State> Service<S> {
         self.events.extend(events);
     }
 
+    /// Consume the service, returning the state it wraps
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
     /// Access the events
     pub fn events(&self) -> impl Iterator<Item = &S::Event> {
         self.events.iter()
     }
 
+    /// Take a snapshot of the current state
+    ///
+    /// The snapshot can be passed to [`Service::rollback`], to undo all
+    /// changes that happened since, or to [`Service::events_since`], to find
+    /// out what those changes are.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            num_events: self.events.len(),
+        }
+    }
+
+    /// Access the events that have been logged since the provided snapshot
+    ///
+    /// Returns no events, if the snapshot is newer than the event log, because
+    /// the events it was taken after have been rolled back.
+    pub fn events_since(&self, snapshot: Snapshot) -> &[S::Event] {
+        self.events.get(snapshot.num_events..).unwrap_or_default()
+    }
+
+    /// Roll back the state to the provided snapshot
+    ///
+    /// Undoes all events that have been logged since the snapshot was taken,
+    /// most recent first, and removes them from the event log. Returns the
+    /// events that have been rolled back.
+    ///
+    /// Subscribers are not notified. If they hold state that is derived from
+    /// the rolled back events, it's up to the caller to update that.
+    pub fn rollback(&mut self, snapshot: Snapshot) -> Vec<S::Event>
+    where
+        S: Undo,
+    {
+        let num_events = snapshot.num_events.min(self.events.len());
+        let events = self.events.split_off(num_events);
+
+        for event in events.iter().rev() {
+            self.state.undo(event);
+        }
+
+        events
+    }
+
     /// Replay the provided events on the given state
     pub fn replay<'event>(
         state: &mut S,
@@ -141,6 +187,25 @@ pub trait State {
     fn evolve(&mut self, event: &Self::Event);
 }
 
+/// Implemented for state that supports undoing events
+///
+/// Required by [`Service::rollback`].
+pub trait Undo: State {
+    /// Undo the changes that the provided event made to the state
+    ///
+    /// Events are undone in the reverse order in which they were evolved. The
+    /// provided event is always the most recent one that hasn't been undone.
+    fn undo(&mut self, event: &Self::Event);
+}
+
+/// A snapshot of the state of a [`Service`]
+///
+/// Returned by [`Service::snapshot`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    num_events: usize,
+}
+
 pub trait Subscriber<T> {
     fn handle_event(&mut self, event: &T);
 }
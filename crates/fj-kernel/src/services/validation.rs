@@ -1,27 +1,84 @@
 use std::{collections::BTreeMap, thread};
 
+use fj_interop::cancellation::CancellationToken;
+
 use crate::{
     objects::{BehindHandle, Object},
     storage::ObjectId,
     validate::ValidationError,
 };
 
-use super::{objects::InsertObject, State};
+use super::{objects::ObjectsEvent, State, Undo};
 
 /// Errors that occurred while validating the objects inserted into the stores
 #[derive(Default)]
-pub struct Validation(pub BTreeMap<ObjectId, ValidationFailed>);
+pub struct Validation {
+    /// The validation errors, by the object they occurred in
+    pub errors: BTreeMap<ObjectId, Vec<ValidationFailed>>,
+
+    cancellation: CancellationToken,
+    mode: ValidationMode,
+}
+
+impl Validation {
+    /// Construct an instance of `Validation` that can be cancelled
+    ///
+    /// Once `cancellation` has been cancelled, no more objects are validated.
+    /// Since the results of a cancelled operation are going to be discarded,
+    /// any unhandled validation errors are also ignored on drop.
+    pub fn with_cancellation(cancellation: CancellationToken) -> Self {
+        Self {
+            errors: BTreeMap::new(),
+            cancellation,
+            mode: ValidationMode::default(),
+        }
+    }
+
+    /// Set what happens, if an object fails validation
+    ///
+    /// See [`ValidationMode`].
+    pub fn with_mode(mut self, mode: ValidationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// What happens, if an object fails validation
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ValidationMode {
+    /// Panic as soon as an invalid object is inserted
+    ///
+    /// Useful for debugging, as the backtrace points to the code that created
+    /// the invalid object.
+    Panic,
+
+    /// Collect the validation errors
+    ///
+    /// They can be handled using [`Services::drop_and_validate`]. Unhandled
+    /// errors cause a panic, once the validation service is dropped.
+    ///
+    /// [`Services::drop_and_validate`]: super::Services::drop_and_validate
+    #[default]
+    Collect,
+
+    /// Log the validation errors as warnings, and otherwise ignore them
+    Warn,
+}
 
 impl Drop for Validation {
     fn drop(&mut self) {
-        let num_errors = self.0.len();
+        if self.cancellation.is_cancelled() {
+            return;
+        }
+
+        let num_errors = self.errors.values().map(Vec::len).sum::<usize>();
         if num_errors > 0 {
             println!(
                 "Dropping `Validation` with {num_errors} unhandled validation \
                 errors:"
             );
 
-            for event in self.0.values() {
+            for event in self.errors.values().flatten() {
                 println!("{}", event.err);
             }
 
@@ -33,13 +90,43 @@ impl Drop for Validation {
 }
 
 impl State for Validation {
-    type Command = InsertObject;
+    type Command = ObjectsEvent;
     type Event = ValidationFailed;
 
     fn decide(&self, command: Self::Command, events: &mut Vec<Self::Event>) {
+        if self.cancellation.is_cancelled() {
+            return;
+        }
+
+        let ObjectsEvent::InsertObject(command) = command else {
+            return;
+        };
+
+        // Objects are validated one by one, as they are inserted. That makes
+        // for a lot of spans, so they are only enabled at the trace level.
+        let _span = tracing::trace_span!("validate").entered();
+
         let mut errors = Vec::new();
         command.object.validate(&mut errors);
 
+        match self.mode {
+            ValidationMode::Panic => {
+                if let Some(err) = errors.first() {
+                    panic!(
+                        "Inserted invalid object: {:#?}\n{err}",
+                        command.object
+                    );
+                }
+            }
+            ValidationMode::Collect => {}
+            ValidationMode::Warn => {
+                for err in errors {
+                    tracing::warn!("Inserted invalid object: {err}");
+                }
+                return;
+            }
+        }
+
         for err in errors {
             events.push(ValidationFailed {
                 object: command.object.clone().into(),
@@ -49,12 +136,29 @@ impl State for Validation {
     }
 
     fn evolve(&mut self, event: &Self::Event) {
-        self.0.insert(event.object.id(), event.clone());
+        self.errors
+            .entry(event.object.id())
+            .or_default()
+            .push(event.clone());
+    }
+}
+
+impl Undo for Validation {
+    fn undo(&mut self, event: &Self::Event) {
+        // Events are undone in reverse order, so this is the last error that
+        // was recorded for the object.
+        let id = event.object.id();
+        if let Some(errors) = self.errors.get_mut(&id) {
+            errors.pop();
+            if errors.is_empty() {
+                self.errors.remove(&id);
+            }
+        }
     }
 }
 
 /// An event produced by the validation service
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ValidationFailed {
     /// The object for which validation failed
     pub object: Object<BehindHandle>,
@@ -14,7 +14,7 @@ impl<T> Blocks<T> {
         }
     }
 
-    pub fn reserve(&mut self) -> (Index, *const Option<T>) {
+    pub fn reserve(&mut self) -> (usize, *const Option<T>) {
         let mut current_block = match self.inner.pop() {
             Some(block) => block,
             None => Block::new(self.block_size),
@@ -24,13 +24,11 @@ impl<T> Blocks<T> {
             match current_block.reserve() {
                 Ok((object_index, ptr)) => {
                     let block_index = BlockIndex(self.inner.len());
-                    break (
-                        Index {
-                            block_index,
-                            object_index,
-                        },
-                        ptr,
-                    );
+                    let index = Index {
+                        block_index,
+                        object_index,
+                    };
+                    break (self.slot(index), ptr);
                 }
                 Err(()) => {
                     // Block is full. Need to create a new one and retry.
@@ -45,18 +43,28 @@ impl<T> Blocks<T> {
         ret
     }
 
-    pub fn insert(&mut self, index: Index, object: T) {
-        let block = &mut self.inner[index.block_index.0];
-        block.insert(index.object_index, object);
+    pub fn insert(&mut self, slot: usize, object: T) {
+        let block = &mut self.inner[slot / self.block_size];
+        block.insert(ObjectIndex(slot % self.block_size), object);
     }
 
-    pub fn get_and_inc(&self, index: &mut Index) -> Option<&Option<T>> {
+    pub fn get_and_inc(
+        &self,
+        index: &mut Index,
+    ) -> Option<(usize, &Option<T>)> {
         let block = self.inner.get(index.block_index.0)?;
+        let slot = self.slot(*index);
         let object = block.get(index.object_index);
 
         index.inc(block);
 
-        Some(object)
+        Some((slot, object))
+    }
+
+    // Every slot in the blocks has a unique number. Blocks are filled one
+    // after the other, so this is just the number of slots before it.
+    fn slot(&self, index: Index) -> usize {
+        index.block_index.0 * self.block_size + index.object_index.0
     }
 }
 
@@ -1,6 +1,8 @@
 use std::{any::type_name, cmp::Ordering, fmt, hash::Hash, ops::Deref};
 
-use super::{blocks::Index, store::StoreInner};
+use fj_interop::mesh::FaceId;
+
+use super::store::StoreInner;
 
 /// A handle for an object
 ///
@@ -23,14 +25,14 @@ use super::{blocks::Index, store::StoreInner};
 /// comparing the values returned by [`Handle::id`].
 pub struct Handle<T> {
     pub(super) store: StoreInner<T>,
-    pub(super) index: Index,
+    pub(super) id: ObjectId,
     pub(super) ptr: *const Option<T>,
 }
 
 impl<T> Handle<T> {
     /// Access this pointer's unique id
     pub fn id(&self) -> ObjectId {
-        ObjectId::from_ptr(self.ptr)
+        self.id
     }
 
     /// Return a clone of the object this handle refers to
@@ -86,7 +88,7 @@ impl<T> Clone for Handle<T> {
     fn clone(&self) -> Self {
         Self {
             store: self.store.clone(),
-            index: self.index,
+            id: self.id,
             ptr: self.ptr,
         }
     }
@@ -166,13 +168,30 @@ unsafe impl<T> Sync for Handle<T> {}
 
 /// Represents the ID of an object
 ///
+/// The ID is made up of the generation of the store that the object lives in,
+/// and the object's slot within that store. It is unique for the lifetime of
+/// the process, even after the object has been dropped.
+///
 /// See [`Handle::id`].
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct ObjectId(pub(crate) u64);
+pub struct ObjectId(pub(crate) u128);
 
 impl ObjectId {
-    pub(crate) fn from_ptr<T>(ptr: *const T) -> ObjectId {
-        Self(ptr as u64)
+    pub(super) fn new(generation: u64, slot: usize) -> Self {
+        let slot = u32::try_from(slot)
+            .expect("Store can't hold more than `u32::MAX` objects");
+
+        Self((u128::from(generation) << 32) | u128::from(slot))
+    }
+
+    pub(super) fn slot(&self) -> usize {
+        (self.0 & u128::from(u32::MAX)) as usize
+    }
+}
+
+impl From<ObjectId> for FaceId {
+    fn from(id: ObjectId) -> Self {
+        Self(id.0)
     }
 }
 
@@ -183,6 +202,13 @@ impl fmt::Debug for ObjectId {
     }
 }
 
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = self.0;
+        write!(f, "{id:#x}")
+    }
+}
+
 /// A wrapper around [`Handle`] to define equality based on identity
 ///
 /// This is a utility type that implements [`Eq`]/[`PartialEq`] and other common
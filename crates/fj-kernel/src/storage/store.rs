@@ -20,14 +20,26 @@
 //! a custom development seemed justified.
 //!
 //! But in any case, this was fun to write, and not that much work.
-
-use std::{marker::PhantomData, sync::Arc};
+//!
+//! Please note that this is not a generational arena. Objects are identified by
+//! the generation of their store and their slot within it, but the generation
+//! belongs to the whole store. Slots are never reused, not even for discarded
+//! objects, so there is no need for a generation per slot.
+
+use std::{
+    collections::HashSet,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use parking_lot::RwLock;
 
 use super::{
     blocks::{Blocks, Index},
-    Handle,
+    Handle, ObjectId,
 };
 
 /// Append-only object storage
@@ -47,8 +59,23 @@ impl<T> Store<T> {
 
     /// Construct a new instance of `Store` using the provided block size
     pub fn with_block_size(block_size: usize) -> Self {
+        // Object IDs consist of the generation of their store, and their slot
+        // within it. Since every store gets a new generation, no ID is ever
+        // used twice, not even after the store it came from has been dropped.
+        //
+        // Wrapping around would break that, so running out of generations is
+        // an error. With 64 bits, that won't happen in practice.
+        static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+        let generation = NEXT_GENERATION
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |generation| {
+                generation.checked_add(1)
+            })
+            .expect("Ran out of store generations");
+
         let inner = Arc::new(RwLock::new(StoreInnerInner {
             blocks: Blocks::new(block_size),
+            discarded: HashSet::new(),
+            generation,
         }));
 
         Self { inner }
@@ -67,11 +94,11 @@ impl<T> Store<T> {
     pub fn reserve(&self) -> Handle<T> {
         let mut inner = self.inner.write();
 
-        let (index, ptr) = inner.blocks.reserve();
+        let (slot, ptr) = inner.blocks.reserve();
 
         Handle {
             store: self.inner.clone(),
-            index,
+            id: ObjectId::new(inner.generation, slot),
             ptr,
         }
     }
@@ -85,7 +112,17 @@ impl<T> Store<T> {
     /// before.
     pub fn insert(&mut self, handle: Handle<T>, object: T) {
         let mut inner = self.inner.write();
-        inner.blocks.insert(handle.index, object);
+        inner.blocks.insert(handle.id.slot(), object);
+    }
+
+    /// Discard an object in the store
+    ///
+    /// Iterating over the store no longer yields the discarded object. Since
+    /// the store is append-only, the object itself stays in memory, and all
+    /// existing handles to it remain valid.
+    pub fn discard(&mut self, handle: &Handle<T>) {
+        let mut inner = self.inner.write();
+        inner.discarded.insert(handle.id());
     }
 
     /// Iterate over all objects in this store
@@ -127,17 +164,20 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
         let inner = self.store.read();
 
         loop {
-            let index = self.next_index;
-            let ptr = inner.blocks.get_and_inc(&mut self.next_index)?;
+            let (slot, ptr) = inner.blocks.get_and_inc(&mut self.next_index)?;
+            let id = ObjectId::new(inner.generation, slot);
 
             if ptr.is_none() {
                 // This is a reserved slot.
                 continue;
             }
+            if inner.discarded.contains(&id) {
+                continue;
+            }
 
             return Some(Handle {
                 store: self.store.clone(),
-                index,
+                id,
                 ptr,
             });
         }
@@ -149,6 +189,8 @@ pub type StoreInner<T> = Arc<RwLock<StoreInnerInner<T>>>;
 #[derive(Debug)]
 pub struct StoreInnerInner<T> {
     blocks: Blocks<T>,
+    discarded: HashSet<ObjectId>,
+    generation: u64,
 }
 
 #[cfg(test)]
@@ -181,4 +223,39 @@ mod tests {
         let objects = store.iter().collect::<Vec<_>>();
         assert_eq!(objects, [a, b]);
     }
+
+    #[test]
+    fn discard() {
+        let mut store = Store::with_block_size(1);
+
+        let a: Handle<i32> = store.reserve();
+        let b = store.reserve();
+        store.insert(a.clone(), 0);
+        store.insert(b.clone(), 1);
+
+        store.discard(&a);
+
+        let objects = store.iter().collect::<Vec<_>>();
+        assert_eq!(objects, [b]);
+        assert_eq!(*a, 0);
+    }
+
+    #[test]
+    fn ids_are_not_reused() {
+        let id = {
+            let mut store = Store::with_block_size(1);
+
+            let a: Handle<i32> = store.reserve();
+            store.insert(a.clone(), 0);
+
+            a.id()
+        };
+
+        let mut store = Store::with_block_size(1);
+
+        let b: Handle<i32> = store.reserve();
+        store.insert(b.clone(), 0);
+
+        assert_ne!(b.id(), id);
+    }
 }
@@ -1,5 +1,8 @@
+use std::cmp::Ordering;
+
 use crate::objects::Cycle;
 use crate::objects::HalfEdge;
+use fj_math::Interval;
 use fj_math::Point;
 use fj_math::Scalar;
 use itertools::Itertools;
@@ -73,7 +76,15 @@ impl CycleValidationError {
 
             let distance = (end_of_first - start_of_second).magnitude();
 
-            if distance > config.identical_max_distance {
+            // Only report half-edges that are definitely disconnected. If the
+            // distance is too close to the limit to call, floating-point error
+            // could have pushed it either way.
+            let is_disconnected =
+                Interval::distance(&end_of_first, &start_of_second)
+                    .compare(config.identical_max_distance.length())
+                    == Some(Ordering::Greater);
+
+            if is_disconnected {
                 errors.push(
                     Self::HalfEdgesDisconnected {
                         end_of_first,
@@ -96,7 +107,7 @@ mod tests {
 
     use crate::{
         assert_contains_err,
-        builder::CycleBuilder,
+        builder::{BuildError, CycleBuilder},
         objects::{Cycle, HalfEdge},
         operations::{BuildCycle, BuildHalfEdge, Insert, UpdateCycle},
         services::Services,
@@ -148,4 +159,72 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn half_edges_connected_after_building_path() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // The short sides are used up completely by the fillets.
+        let rounded = CycleBuilder::polygon(
+            [[0., 0.], [2., 0.], [2., 1.], [0., 1.]],
+            &mut services.objects,
+        )
+        .fillet_corners(0.5, &mut services.objects)
+        .build(&mut services.objects);
+
+        assert_eq!(rounded.half_edges().count(), 2 + 4);
+        rounded.validate_and_return_first_error()?;
+
+        let path = CycleBuilder::new()
+            .move_to([0., 0.])
+            .add_line_to([2., 0.], &mut services.objects)
+            .add_arc_to([2., 2.], 1., &mut services.objects)
+            .close(&mut services.objects)
+            .build(&mut services.objects);
+
+        assert_eq!(path.half_edges().count(), 3);
+        path.validate_and_return_first_error()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_build_reports_errors() {
+        let mut services = Services::new();
+
+        let no_start = CycleBuilder::new()
+            .add_line_to([1., 0.], &mut services.objects)
+            .close(&mut services.objects)
+            .try_build(&mut services.objects);
+        assert!(matches!(no_start, Err(BuildError::CycleStartNotDefined)));
+
+        let arc_too_small = CycleBuilder::new()
+            .move_to([0., 0.])
+            .add_arc_to([4., 0.], 1., &mut services.objects)
+            .try_build(&mut services.objects);
+        assert!(matches!(
+            arc_too_small,
+            Err(BuildError::ArcRadiusTooSmall { .. })
+        ));
+
+        let fillet_too_large = CycleBuilder::polygon(
+            [[0., 0.], [2., 0.], [2., 1.], [0., 1.]],
+            &mut services.objects,
+        )
+        .fillet_corners(1., &mut services.objects)
+        .try_build(&mut services.objects);
+        assert!(matches!(
+            fillet_too_large,
+            Err(BuildError::FilletRadiusTooLarge { .. })
+        ));
+
+        // The invalid cycle must not have been inserted, or dropping the
+        // services would panic.
+        let not_closed = CycleBuilder::new()
+            .move_to([0., 0.])
+            .add_line_to([1., 0.], &mut services.objects)
+            .add_line_to([1., 1.], &mut services.objects)
+            .try_build(&mut services.objects);
+        assert!(matches!(not_closed, Err(BuildError::Invalid(_))));
+    }
 }
@@ -57,7 +57,7 @@ impl HalfEdgeValidationError {
         let [back_position, front_position] = half_edge.boundary();
         let distance = (back_position - front_position).magnitude();
 
-        if distance < config.distinct_min_distance {
+        if distance < config.distinct_min_distance.length() {
             errors.push(
                 Self::VerticesAreCoincident {
                     back_position,
@@ -75,6 +75,7 @@ mod tests {
         assert_contains_err,
         builder::{CycleBuilder, FaceBuilder},
         objects::Face,
+        operations::UpdateFace,
         services::Services,
         validate::{FaceValidationError, Validate, ValidationError},
     };
@@ -118,4 +119,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn face_polygon_with_holes() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // The interiors are given in both windings. Both must be corrected.
+        let exterior = [[0., 0.], [6., 0.], [0., 6.]];
+        let interiors = [
+            [[1., 1.], [2., 1.], [1., 2.]],
+            [[3., 1.], [3., 2.], [4., 1.]],
+        ];
+
+        for exterior in [exterior, [exterior[2], exterior[1], exterior[0]]] {
+            let face = FaceBuilder::polygon_with_holes(
+                services.objects.surfaces.xy_plane(),
+                exterior,
+                interiors,
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+
+            face.validate_and_return_first_error()?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_add_interior_polygon() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let face = FaceBuilder::new(services.objects.surfaces.xy_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[0., 0.], [6., 0.], [0., 6.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects)
+            .add_interior_polygon(
+                [[1., 1.], [2., 1.], [1., 2.]],
+                &mut services.objects,
+            )
+            .add_interior_polygon(
+                [[3., 1.], [3., 2.], [4., 1.]],
+                &mut services.objects,
+            );
+
+        assert_eq!(face.interiors().count(), 2);
+        face.validate_and_return_first_error()?;
+
+        Ok(())
+    }
 }
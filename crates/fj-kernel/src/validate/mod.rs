@@ -17,7 +17,7 @@ pub use self::{
 
 use std::convert::Infallible;
 
-use fj_math::Scalar;
+use fj_math::Tolerance;
 
 /// Assert that some object has a validation error which matches a specifc pattern.
 /// This is preferred to matching on [`Validate::validate_and_return_first_error`], since usually we don't care about the order.
@@ -67,29 +67,29 @@ pub trait Validate: Sized {
 pub struct ValidationConfig {
     /// The minimum distance between distinct objects
     ///
-    /// Objects whose distance is less than the value defined in this field, are
-    /// considered identical.
-    pub distinct_min_distance: Scalar,
+    /// Objects whose distance is less than the length of the tolerance defined
+    /// in this field, are considered identical.
+    pub distinct_min_distance: Tolerance,
 
     /// The maximum distance between identical objects
     ///
     /// Objects that are considered identical might still have a distance
     /// between them, due to inaccuracies of the numerical representation. If
-    /// that distance is less than the one defined in this field, can not be
-    /// considered identical.
-    pub identical_max_distance: Scalar,
+    /// that distance is less than the length of the tolerance defined in this
+    /// field, can not be considered identical.
+    pub identical_max_distance: Tolerance,
 }
 
 impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
-            distinct_min_distance: Scalar::from_f64(5e-7), // 0.5 µm,
+            distinct_min_distance: Tolerance::from(5e-7), // 0.5 µm,
 
             // This value was chosen pretty arbitrarily. Seems small enough to
             // catch errors. If it turns out it's too small (because it produces
             // false positives due to floating-point accuracy issues), we can
             // adjust it.
-            identical_max_distance: Scalar::from_f64(5e-14),
+            identical_max_distance: Tolerance::from(5e-14),
         }
     }
 }
@@ -1,10 +1,18 @@
-use std::{collections::HashMap, iter::repeat};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    iter::repeat,
+};
 
-use fj_math::{Point, Scalar};
+use fj_math::{Interval, Point, Scalar};
+use itertools::Itertools;
 
 use crate::{
-    geometry::surface::SurfaceGeometry,
-    objects::{HalfEdge, Shell, Surface},
+    geometry::{
+        curve::{Curve, GlobalPath},
+        surface::SurfaceGeometry,
+    },
+    objects::{HalfEdge, Shell, Surface, Vertex},
     storage::{Handle, ObjectId},
 };
 
@@ -17,6 +25,13 @@ impl Validate for Shell {
         errors: &mut Vec<ValidationError>,
     ) {
         ShellValidationError::validate_edges_coincident(self, config, errors);
+        ShellValidationError::validate_edge_vertices(self, config, errors);
+        ShellValidationError::validate_vertices_coincident(
+            self, config, errors,
+        );
+        ShellValidationError::validate_edges_on_neighboring_surfaces(
+            self, config, errors,
+        );
         ShellValidationError::validate_watertight(self, config, errors);
     }
 }
@@ -58,6 +73,72 @@ pub enum ShellValidationError {
         /// The surface that the second edge is on
         surface_2: Handle<Surface>,
     },
+
+    /// [`Shell`] contains half-edges that are identical, but that are bounded
+    /// by different vertices
+    #[error(
+        "Shell contains HalfEdges that are identical but are bounded by \
+        different Vertices\n\
+        Edge 1: {edge_1:#?}\n\
+        Vertices of edge 1: {vertices_1:#?}\n\
+        Edge 2: {edge_2:#?}\n\
+        Vertices of edge 2: {vertices_2:#?}"
+    )]
+    IdenticalEdgesHaveDifferentVertices {
+        /// The first edge
+        edge_1: Handle<HalfEdge>,
+
+        /// The start and end vertex of the first edge
+        vertices_1: [Handle<Vertex>; 2],
+
+        /// The second edge
+        edge_2: Handle<HalfEdge>,
+
+        /// The start and end vertex of the second edge
+        vertices_2: [Handle<Vertex>; 2],
+    },
+
+    /// [`Shell`] contains a vertex that is used in different positions
+    #[error(
+        "Shell contains a Vertex that is used in different positions\n\
+        Vertex: {vertex:#?}\n\
+        Position 1: {position_1:?}\n\
+        Position 2: {position_2:?}"
+    )]
+    IdenticalVerticesNotCoincident {
+        /// The vertex
+        vertex: Handle<Vertex>,
+
+        /// The first position that the vertex is used in
+        position_1: Point<3>,
+
+        /// The second position that the vertex is used in
+        position_2: Point<3>,
+    },
+
+    /// [`Shell`] contains a half-edge that does not lie on the surface of a
+    /// neighboring face that shares its edge
+    #[error(
+        "Shell contains a HalfEdge that does not lie on the Surface of a \
+        neighboring Face\n\
+        Edge: {edge:#?}\n\
+        Surface of the edge: {surface:#?}\n\
+        Surface of the neighboring face: {neighbor_surface:#?}\n\
+        Distance: {distance}"
+    )]
+    HalfEdgeNotOnNeighboringSurface {
+        /// The half-edge
+        edge: Handle<HalfEdge>,
+
+        /// The surface that the half-edge is defined on
+        surface: Handle<Surface>,
+
+        /// The surface of the neighboring face
+        neighbor_surface: Handle<Surface>,
+
+        /// The largest distance of a sample of the half-edge to the surface
+        distance: Scalar,
+    },
 }
 
 /// Sample two edges at various (currently 3) points in 3D along them.
@@ -68,25 +149,15 @@ fn distances(
     (edge1, surface1): (Handle<HalfEdge>, Handle<Surface>),
     (edge2, surface2): (Handle<HalfEdge>, Handle<Surface>),
 ) -> impl Iterator<Item = Scalar> {
-    fn sample(
-        percent: f64,
-        (edge, surface): (&Handle<HalfEdge>, SurfaceGeometry),
-    ) -> Point<3> {
-        let boundary = edge.boundary();
-        let path_coords = boundary[0] + (boundary[1] - boundary[0]) * percent;
-        let surface_coords = edge.curve().point_from_path_coords(path_coords);
-        surface.point_from_surface_coords(surface_coords)
-    }
-
     // Check whether start positions do not match. If they don't treat second edge as flipped
-    let flip = sample(0.0, (&edge1, surface1.geometry()))
-        .distance_to(&sample(0.0, (&edge2, surface2.geometry())))
-        > config.identical_max_distance;
-
-    // Three samples (start, middle, end), are enough to detect weather lines
-    // and circles match. If we were to add more complicated curves, this might
-    // need to change.
-    let sample_count = 3;
+    let start_distance = sample(0.0, (&edge1, surface1.geometry()))
+        .distance_to(&sample(0.0, (&edge2, surface2.geometry())));
+    let flip = !config
+        .identical_max_distance
+        .contains_distance(start_distance);
+
+    let sample_count = sample_count(&edge1, &surface1.geometry())
+        .max(sample_count(&edge2, &surface2.geometry()));
     let step = 1.0 / (sample_count as f64 - 1.0);
 
     let mut distances = Vec::new();
@@ -102,6 +173,36 @@ fn distances(
     distances.into_iter()
 }
 
+/// Compute how many samples are needed to tell, if an edge is misplaced
+///
+/// Three samples (start, middle, end), are enough to detect whether lines and
+/// circles match. Bézier curves are cubic. Put on a surface that is swept from
+/// another Bézier curve, an edge can follow a polynomial of degree 9 in 3D
+/// space, which takes ten samples to pin down.
+///
+/// The count is always odd, so the middle of the edge is among the samples.
+fn sample_count(edge: &HalfEdge, surface: &SurfaceGeometry) -> usize {
+    let curve_is_bezier = matches!(edge.curve(), Curve::Bezier(_));
+    let surface_is_bezier = matches!(surface.u, GlobalPath::Bezier(_));
+
+    if curve_is_bezier || surface_is_bezier {
+        11
+    } else {
+        3
+    }
+}
+
+/// Sample an edge at the given fraction of its length, in 3D
+fn sample(
+    percent: f64,
+    (edge, surface): (&Handle<HalfEdge>, SurfaceGeometry),
+) -> Point<3> {
+    let boundary = edge.boundary();
+    let path_coords = boundary[0] + (boundary[1] - boundary[0]) * percent;
+    let surface_coords = edge.curve().point_from_path_coords(path_coords);
+    surface.point_from_surface_coords(surface_coords)
+}
+
 impl ShellValidationError {
     fn validate_edges_coincident(
         shell: &Shell,
@@ -118,47 +219,252 @@ impl ShellValidationError {
             })
             .collect();
 
-        // This is O(N^2) which isn't great, but we can't use a HashMap since we
-        // need to deal with float inaccuracies. Maybe we could use some smarter
-        // data-structure like an octree.
+        // Identical edges refer to the same global edge. Only those need to be
+        // compared to each other.
+        let mut edges_by_global_form: BTreeMap<_, Vec<_>> = BTreeMap::new();
         for edge in &edges_and_surfaces {
-            for other_edge in &edges_and_surfaces {
-                let id = edge.0.global_form().id();
-                let other_id = other_edge.0.global_form().id();
-                let identical = id == other_id;
-                match identical {
-                    true => {
-                        // All points on identical curves should be within
-                        // identical_max_distance, so we shouldn't have any
-                        // greater than the max
-                        if distances(config, edge.clone(), other_edge.clone())
-                            .any(|d| d > config.identical_max_distance)
-                        {
-                            errors.push(
-                                Self::IdenticalEdgesNotCoincident {
-                                    edge_1: edge.0.clone(),
-                                    surface_1: edge.1.clone(),
-                                    edge_2: other_edge.0.clone(),
-                                    surface_2: other_edge.1.clone(),
-                                }
-                                .into(),
-                            )
+            edges_by_global_form
+                .entry(edge.0.global_form().id())
+                .or_default()
+                .push(edge);
+        }
+
+        for edges in edges_by_global_form.values() {
+            for (edge, other_edge) in edges.iter().tuple_combinations() {
+                // All points on identical curves should be within
+                // identical_max_distance, so we shouldn't have any greater
+                // than the max
+                if distances(config, (*edge).clone(), (*other_edge).clone())
+                    .any(|d| {
+                        !config.identical_max_distance.contains_distance(d)
+                    })
+                {
+                    errors.push(
+                        Self::IdenticalEdgesNotCoincident {
+                            edge_1: edge.0.clone(),
+                            surface_1: edge.1.clone(),
+                            edge_2: other_edge.0.clone(),
+                            surface_2: other_edge.1.clone(),
                         }
+                        .into(),
+                    )
+                }
+            }
+        }
+
+        // Coincident edges are sampled along their length, always including
+        // their start, middle, and end. All of those samples must be within
+        // `distinct_min_distance` of each other, regardless of the edges'
+        // direction, which includes their middles.
+        //
+        // Sorting the middles into a grid whose cells have that size means that
+        // only edges in neighboring cells can be coincident.
+        let cell_size = config.distinct_min_distance.length();
+        let cell = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / cell_size).floor().into_f64() as i64)
+        };
+
+        let cells = edges_and_surfaces
+            .iter()
+            .map(|(edge, surface)| {
+                cell(sample(0.5, (edge, surface.geometry())))
+            })
+            .collect::<Vec<_>>();
+
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        for (i, cell) in cells.iter().enumerate() {
+            grid.entry(*cell).or_default().push(i);
+        }
+
+        for (i, [x, y, z]) in cells.into_iter().enumerate() {
+            let edge = &edges_and_surfaces[i];
+
+            let neighbors = (-1..=1)
+                .cartesian_product(-1..=1)
+                .cartesian_product(-1..=1)
+                .filter_map(|((dx, dy), dz)| {
+                    grid.get(&[x + dx, y + dy, z + dz])
+                })
+                .flatten();
+
+            for &j in neighbors {
+                // Every pair of edges only needs to be checked once.
+                if j <= i {
+                    continue;
+                }
+
+                let other_edge = &edges_and_surfaces[j];
+                if edge.0.global_form().id() == other_edge.0.global_form().id()
+                {
+                    continue;
+                }
+
+                // If all points on distinct curves are within
+                // distinct_min_distance, that's a problem.
+                if distances(config, edge.clone(), other_edge.clone())
+                    .all(|d| d < config.distinct_min_distance.length())
+                {
+                    errors.push(
+                        Self::CoincidentEdgesNotIdentical(
+                            edge.0.clone(),
+                            other_edge.0.clone(),
+                        )
+                        .into(),
+                    )
+                }
+            }
+        }
+    }
+
+    fn validate_edge_vertices(
+        shell: &Shell,
+        _: &ValidationConfig,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        // The end vertex of a half-edge is the start vertex of the next one in
+        // its cycle.
+        let edges_and_vertices = shell.faces().into_iter().flat_map(|face| {
+            face.all_cycles().flat_map(|cycle| {
+                cycle
+                    .half_edges()
+                    .circular_tuple_windows()
+                    .map(|(half_edge, next)| {
+                        let vertices = [
+                            half_edge.start_vertex().clone(),
+                            next.start_vertex().clone(),
+                        ];
+                        (half_edge.clone(), vertices)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        // Identical half-edges run in opposite directions, so their vertices
+        // are compared regardless of order.
+        let mut edges_by_global_form = BTreeMap::new();
+        for (edge, vertices) in edges_and_vertices {
+            let mut ids = vertices.clone().map(|vertex| vertex.id());
+            ids.sort();
+
+            let (other_edge, other_vertices, other_ids) = edges_by_global_form
+                .entry(edge.global_form().id())
+                .or_insert_with(|| (edge.clone(), vertices.clone(), ids));
+
+            if ids != *other_ids {
+                errors.push(
+                    Self::IdenticalEdgesHaveDifferentVertices {
+                        edge_1: other_edge.clone(),
+                        vertices_1: other_vertices.clone(),
+                        edge_2: edge,
+                        vertices_2: vertices,
                     }
-                    false => {
-                        // If all points on distinct curves are within
-                        // distinct_min_distance, that's a problem.
-                        if distances(config, edge.clone(), other_edge.clone())
-                            .all(|d| d < config.distinct_min_distance)
-                        {
-                            errors.push(
-                                Self::CoincidentEdgesNotIdentical(
-                                    edge.0.clone(),
-                                    other_edge.0.clone(),
-                                )
-                                .into(),
-                            )
+                    .into(),
+                );
+            }
+        }
+    }
+
+    fn validate_vertices_coincident(
+        shell: &Shell,
+        config: &ValidationConfig,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mut positions_by_vertex: BTreeMap<ObjectId, Point<3>> =
+            BTreeMap::new();
+
+        for face in shell.faces() {
+            let surface = face.surface().geometry();
+
+            for half_edge in
+                face.all_cycles().flat_map(|cycle| cycle.half_edges())
+            {
+                let vertex = half_edge.start_vertex();
+                let position = surface
+                    .point_from_surface_coords(half_edge.start_position());
+
+                let first =
+                    *positions_by_vertex.entry(vertex.id()).or_insert(position);
+
+                // Like in the validation of solids, only distances that
+                // definitely exceed the limit are reported.
+                let distance = Interval::distance(&first, &position);
+                if distance.compare(config.identical_max_distance.length())
+                    == Some(Ordering::Greater)
+                {
+                    errors.push(
+                        Self::IdenticalVerticesNotCoincident {
+                            vertex: vertex.clone(),
+                            position_1: first,
+                            position_2: position,
                         }
+                        .into(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_edges_on_neighboring_surfaces(
+        shell: &Shell,
+        config: &ValidationConfig,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        // Half-edges are defined in the coordinates of their face's surface,
+        // so they lie on that by construction. Faces that share an edge meet
+        // along it, so each half-edge must also lie on the surfaces of the
+        // neighboring faces.
+        let mut edges_by_global_form: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for face in shell.faces() {
+            for half_edge in
+                face.all_cycles().flat_map(|cycle| cycle.half_edges())
+            {
+                edges_by_global_form
+                    .entry(half_edge.global_form().id())
+                    .or_default()
+                    .push((half_edge.clone(), face.surface().clone()));
+            }
+        }
+
+        for edges in edges_by_global_form.values() {
+            for (edge, surface) in edges {
+                for (_, neighbor_surface) in edges {
+                    if surface.id() == neighbor_surface.id() {
+                        continue;
+                    }
+
+                    let neighbor = neighbor_surface.geometry();
+                    let sample_count = sample_count(edge, &surface.geometry())
+                        .max(sample_count(edge, &neighbor));
+                    let step = 1.0 / (sample_count as f64 - 1.0);
+
+                    let distance = (0..sample_count)
+                        .map(|i| {
+                            let point = sample(
+                                i as f64 * step,
+                                (edge, surface.geometry()),
+                            );
+                            let (_, distance) = neighbor.project_point(point);
+                            distance
+                        })
+                        .max()
+                        .expect("Sample count is not zero");
+
+                    if !config
+                        .identical_max_distance
+                        .contains_distance(distance)
+                    {
+                        errors.push(
+                            Self::HalfEdgeNotOnNeighboringSurface {
+                                edge: edge.clone(),
+                                surface: surface.clone(),
+                                neighbor_surface: neighbor_surface.clone(),
+                                distance,
+                            }
+                            .into(),
+                        );
                     }
                 }
             }
@@ -191,9 +497,11 @@ impl ShellValidationError {
 
 #[cfg(test)]
 mod tests {
+    use fj_math::Transform;
+
     use crate::{
         assert_contains_err,
-        objects::{GlobalEdge, Shell},
+        objects::{Face, GlobalEdge, Shell, Surface, Vertex},
         operations::{
             BuildShell, Insert, UpdateCycle, UpdateFace, UpdateHalfEdge,
             UpdateShell,
@@ -225,6 +533,9 @@ mod tests {
             .insert(&mut services.objects)
         });
 
+        assert_eq!(invalid.diff.replaced.len(), 1);
+        let invalid = invalid.object;
+
         valid.shell.validate_and_return_first_error()?;
         assert_contains_err!(
             invalid,
@@ -235,6 +546,121 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn identical_edges_with_different_vertices() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let invalid = valid.shell.update_face(&valid.face_abc, |face| {
+            face.update_exterior(|cycle| {
+                cycle
+                    .update_half_edge(0, |half_edge| {
+                        let start_vertex =
+                            Vertex::new().insert(&mut services.objects);
+                        half_edge
+                            .update_start_vertex(start_vertex)
+                            .insert(&mut services.objects)
+                    })
+                    .insert(&mut services.objects)
+            })
+            .insert(&mut services.objects)
+        });
+        let invalid = invalid.object;
+
+        valid.shell.validate_and_return_first_error()?;
+        assert_contains_err!(
+            invalid,
+            ValidationError::Shell(
+                ShellValidationError::IdenticalEdgesHaveDifferentVertices { .. }
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn identical_vertices_not_coincident() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let invalid = valid.shell.update_face(&valid.face_abc, |face| {
+            face.update_exterior(|cycle| {
+                let other_vertex = cycle
+                    .half_edges()
+                    .nth(1)
+                    .expect("Triangle has three half-edges")
+                    .start_vertex()
+                    .clone();
+
+                cycle
+                    .update_half_edge(0, |half_edge| {
+                        half_edge
+                            .update_start_vertex(other_vertex.clone())
+                            .insert(&mut services.objects)
+                    })
+                    .insert(&mut services.objects)
+            })
+            .insert(&mut services.objects)
+        });
+        let invalid = invalid.object;
+
+        valid.shell.validate_and_return_first_error()?;
+        assert_contains_err!(
+            invalid,
+            ValidationError::Shell(
+                ShellValidationError::IdenticalVerticesNotCoincident { .. }
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn half_edge_not_on_neighboring_surface() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut services.objects,
+        );
+        let invalid = valid.shell.update_face(&valid.face_abc, |face| {
+            // Moving the surface moves the half-edges defined on it, away from
+            // the neighboring faces.
+            let surface = Surface::new(
+                face.surface()
+                    .geometry()
+                    .transform(&Transform::translation([0., 0., 1.])),
+            )
+            .insert(&mut services.objects);
+
+            Face::new(
+                surface,
+                face.exterior().clone(),
+                face.interiors().cloned(),
+                face.color(),
+            )
+            .insert(&mut services.objects)
+        });
+        let invalid = invalid.object;
+
+        valid.shell.validate_and_return_first_error()?;
+        assert_contains_err!(
+            invalid,
+            ValidationError::Shell(
+                ShellValidationError::HalfEdgeNotOnNeighboringSurface { .. }
+            )
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn shell_not_watertight() -> anyhow::Result<()> {
         let mut services = Services::new();
@@ -243,7 +669,7 @@ mod tests {
             [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
             &mut services.objects,
         );
-        let invalid = valid.shell.remove_face(&valid.face_abc);
+        let invalid = valid.shell.remove_face(&valid.face_abc).object;
 
         valid.shell.validate_and_return_first_error()?;
         assert_contains_err!(
@@ -253,4 +679,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn constructors_build_valid_shells() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let box_ = Shell::box_from_corners(
+            [[1., 2., 3.], [-1., 0., 0.]],
+            &mut services.objects,
+        );
+        let cylinder = Shell::cylinder(1., -2., &mut services.objects);
+        let prism = Shell::prism(
+            [[0., 0.], [0., 2.], [1., 1.], [2., 2.], [2., 0.]],
+            3.,
+            &mut services.objects,
+        );
+        let pyramid = Shell::from_triangles(
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0.5; 3],
+            ],
+            [
+                [0, 2, 1],
+                [0, 3, 2],
+                [0, 1, 4],
+                [1, 2, 4],
+                [2, 3, 4],
+                [3, 0, 4],
+            ],
+            &mut services.objects,
+        );
+
+        for (shell, num_faces) in
+            [(box_, 6), (cylinder, 3), (prism, 7), (pyramid, 6)]
+        {
+            assert_eq!(shell.faces().into_iter().count(), num_faces);
+            shell.validate_and_return_first_error()?;
+        }
+
+        services.drop_and_validate().unwrap();
+
+        Ok(())
+    }
 }
@@ -1,10 +1,10 @@
-use std::iter::repeat;
+use std::{cmp::Ordering, iter::repeat};
 
 use crate::{
     objects::{Solid, Vertex},
     storage::Handle,
 };
-use fj_math::Point;
+use fj_math::{Interval, Point};
 
 use super::{Validate, ValidationConfig, ValidationError};
 
@@ -65,11 +65,19 @@ impl SolidValidationError {
         // This is O(N^2) which isn't great, but we can't use a HashMap since we
         // need to deal with float inaccuracies. Maybe we could use some smarter
         // data-structure like an octree.
+        //
+        // Only vertices that definitely violate the limits are reported. If a
+        // distance is too close to a limit to call, floating-point error could
+        // have pushed it either way.
         for a in &vertices {
             for b in &vertices {
+                let distance = Interval::distance(&a.0, &b.0);
+
                 match a.1.id() == b.1.id() {
                     true => {
-                        if a.0.distance_to(&b.0) > config.identical_max_distance
+                        if distance
+                            .compare(config.identical_max_distance.length())
+                            == Some(Ordering::Greater)
                         {
                             errors.push(
                                 Self::IdenticalVerticesNotCoincident([
@@ -81,7 +89,9 @@ impl SolidValidationError {
                         }
                     }
                     false => {
-                        if a.0.distance_to(&b.0) < config.distinct_min_distance
+                        if distance
+                            .compare(config.distinct_min_distance.length())
+                            == Some(Ordering::Less)
                         {
                             errors.push(
                                 Self::DistinctVerticesCoincide([
@@ -126,6 +126,13 @@ impl Aabb<3> {
     pub fn merged(&self, other: &Self) -> Self {
         self.to_parry().merged(&other.to_parry()).into()
     }
+
+    /// Determine whether this AABB intersects another
+    ///
+    /// AABBs that only touch are considered to intersect.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.to_parry().intersects(&other.to_parry())
+    }
 }
 
 impl From<parry2d_f64::bounding_volume::Aabb> for Aabb<2> {
@@ -160,4 +167,17 @@ mod tests {
         assert!(!aabb.contains([0., 2.]));
         assert!(!aabb.contains([4., 2.]));
     }
+
+    #[test]
+    fn intersects() {
+        let aabb = Aabb::<3>::from_points([[0., 0., 0.], [2., 2., 2.]]);
+
+        let overlapping = Aabb::<3>::from_points([[1., 1., 1.], [3., 3., 3.]]);
+        let touching = Aabb::<3>::from_points([[2., 0., 0.], [3., 2., 2.]]);
+        let separate = Aabb::<3>::from_points([[3., 0., 0.], [4., 2., 2.]]);
+
+        assert!(aabb.intersects(&overlapping));
+        assert!(aabb.intersects(&touching));
+        assert!(!aabb.intersects(&separate));
+    }
 }
@@ -1,8 +1,12 @@
 use num_traits::Float;
 
-use crate::{Point, Scalar, Vector};
+use crate::{Aabb, Point, Scalar, Vector, Winding};
 
 /// Calculated geometry that is useful when dealing with an arc
+///
+/// The arc goes from `start_angle` to `end_angle`. It is counterclockwise, if
+/// `end_angle` is larger than `start_angle`, clockwise otherwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Arc {
     /// Center of the circle the arc is constructed on
     pub center: Point<2>,
@@ -81,11 +85,81 @@ impl Arc {
             end_angle,
         }
     }
+
+    /// Access the angle that the arc covers, in radians
+    ///
+    /// The angle is negative, if the arc is clockwise.
+    pub fn angle(&self) -> Scalar {
+        self.end_angle - self.start_angle
+    }
+
+    /// Access the winding of the arc
+    pub fn winding(&self) -> Winding {
+        if self.end_angle >= self.start_angle {
+            Winding::Ccw
+        } else {
+            Winding::Cw
+        }
+    }
+
+    /// Compute the length of the arc
+    pub fn length(&self) -> Scalar {
+        self.radius * self.angle().abs()
+    }
+
+    /// Access the point at which the arc starts
+    pub fn start(&self) -> Point<2> {
+        self.point_at_angle(self.start_angle)
+    }
+
+    /// Access the point at which the arc ends
+    pub fn end(&self) -> Point<2> {
+        self.point_at_angle(self.end_angle)
+    }
+
+    /// Compute the point on the arc at the given parameter
+    ///
+    /// The parameter is `0` at the start of the arc and `1` at its end.
+    pub fn point_at(&self, t: impl Into<Scalar>) -> Point<2> {
+        self.point_at_angle(self.start_angle + self.angle() * t.into())
+    }
+
+    /// Compute the point on the arc's circle at the given angle, in radians
+    pub fn point_at_angle(&self, angle: impl Into<Scalar>) -> Point<2> {
+        let angle = angle.into();
+        self.center + Vector::from([angle.cos(), angle.sin()]) * self.radius
+    }
+
+    /// Compute the axis-aligned bounding box of the arc
+    ///
+    /// Takes the points into account, at which the arc is the furthest from
+    /// its center in each direction, if the arc passes them.
+    pub fn aabb(&self) -> Aabb<2> {
+        let quarter_turn = Scalar::PI / 2.;
+
+        let [min, max] = if self.start_angle <= self.end_angle {
+            [self.start_angle, self.end_angle]
+        } else {
+            [self.end_angle, self.start_angle]
+        };
+        let [first, last] = [
+            (min / quarter_turn).ceil().into_f64(),
+            (max / quarter_turn).floor().into_f64(),
+        ]
+        .map(|i| i as i64);
+
+        let extremes = (first..=last)
+            .map(|i| self.point_at_angle(quarter_turn * i as f64));
+
+        Aabb::<2>::from_points(
+            [self.start(), self.end()].into_iter().chain(extremes),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Scalar, Vector};
+    use crate::{Point, Scalar, Vector, Winding};
 
     use super::Arc;
 
@@ -125,6 +199,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arc_geometry() {
+        let arc = Arc {
+            center: Point::from([1., 1.]),
+            radius: Scalar::TWO,
+            start_angle: Scalar::PI / 4.,
+            end_angle: -Scalar::PI / 4.,
+        };
+
+        let epsilon = Scalar::default_epsilon() * 10.;
+
+        assert_eq!(arc.winding(), Winding::Cw);
+        assert_abs_diff_eq!(arc.length(), Scalar::PI, epsilon = epsilon);
+        assert_abs_diff_eq!(
+            arc.point_at(0.5),
+            Point::from([3., 1.]),
+            epsilon = epsilon
+        );
+
+        let offset = 2_f64.sqrt();
+        let aabb = arc.aabb();
+        assert_abs_diff_eq!(
+            aabb.min,
+            Point::from([1. + offset, 1. - offset]),
+            epsilon = epsilon
+        );
+        assert_abs_diff_eq!(
+            aabb.max,
+            Point::from([3., 1. + offset]),
+            epsilon = epsilon
+        );
+    }
+
     fn check_arc_calculation(
         center: impl Into<Point<2>>,
         radius: f64,
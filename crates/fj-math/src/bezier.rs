@@ -0,0 +1,233 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An n-dimensional cubic Bézier curve
+///
+/// The curve is defined by four control points. It starts at the first and
+/// ends at the last one, and is tangent to the control polygon at both ends.
+/// More complex splines can be made up of multiple Bézier curves.
+///
+/// Bézier coordinates are the parameter of the curve, which is zero at the
+/// start and one at the end of the curve. Outside of that range, the curve's
+/// polynomial is extrapolated.
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Bezier<const D: usize> {
+    points: [Point<D>; 4],
+}
+
+impl<const D: usize> Bezier<D> {
+    /// Construct a Bézier curve from its control points
+    ///
+    /// # Panics
+    ///
+    /// Panics, if all control points are coincident.
+    pub fn new(points: [impl Into<Point<D>>; 4]) -> Self {
+        let points = points.map(Into::into);
+
+        let [first, ..] = points;
+        assert!(
+            points.iter().any(|point| *point != first),
+            "Can't construct `Bezier`. All control points are coincident: \
+            {points:?}"
+        );
+
+        Self { points }
+    }
+
+    /// Access the control points of the curve
+    pub fn control_points(&self) -> [Point<D>; 4] {
+        self.points
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.points.reverse();
+        self
+    }
+
+    /// Convert a point in Bézier coordinates into a `D`-dimensional point
+    pub fn point_from_bezier_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let t = point.into().t;
+        let s = Scalar::ONE - t;
+
+        let [a, b, c, d] = self.points.map(|point| point.coords);
+        let weights = [s * s * s, s * s * t * 3., s * t * t * 3., t * t * t];
+
+        Point {
+            coords: a * weights[0]
+                + b * weights[1]
+                + c * weights[2]
+                + d * weights[3],
+        }
+    }
+
+    /// Compute the derivative of the curve at the given point
+    ///
+    /// The derivative is relative to Bézier coordinates. Its magnitude is not
+    /// necessarily one.
+    pub fn derivative(&self, point: impl Into<Point<1>>) -> Vector<D> {
+        let t = point.into().t;
+        let s = Scalar::ONE - t;
+
+        let [a, b, c, d] = self.points;
+        ((b - a) * (s * s) + (c - b) * (s * t * 2.) + (d - c) * (t * t)) * 3.
+    }
+
+    /// Compute the second derivative of the curve at the given point
+    pub fn second_derivative(&self, point: impl Into<Point<1>>) -> Vector<D> {
+        let t = point.into().t;
+        let s = Scalar::ONE - t;
+
+        let [start, end] = self.second_differences();
+        (start * s + end * t) * 6.
+    }
+
+    /// Compute an upper bound for the magnitude of the second derivative
+    ///
+    /// The bound is valid for the range of Bézier coordinates from zero to
+    /// one.
+    pub fn max_second_derivative(&self) -> Scalar {
+        // The second derivative is linear in the parameter, so its magnitude
+        // is largest at one of the ends.
+        self.second_differences()
+            .map(|difference| difference.magnitude() * 6.)
+            .into_iter()
+            .max()
+            .expect("Array is not empty")
+    }
+
+    /// Find the point on the curve that is closest to the given point
+    ///
+    /// Returns the Bézier coordinates of that point, within the range of zero
+    /// to one.
+    pub fn project_point(&self, point: impl Into<Point<D>>) -> Point<1> {
+        let point = point.into();
+
+        let distance = |t: Scalar| {
+            (self.point_from_bezier_coords([t]) - point).magnitude()
+        };
+
+        // A cubic curve can have multiple local minima. Find the start point
+        // for the refinement, by sampling the curve.
+        let mut t = (0..=NUM_SAMPLES)
+            .map(|i| Scalar::from(i as f64 / NUM_SAMPLES as f64))
+            .min_by_key(|&t| distance(t))
+            .expect("Range is not empty");
+
+        // Refine using Newton's method, to find a zero of the derivative of
+        // the squared distance.
+        for _ in 0..MAX_ITERATIONS {
+            let offset = self.point_from_bezier_coords([t]) - point;
+            let derivative = self.derivative([t]);
+
+            let f = offset.dot(&derivative);
+            let df = derivative.dot(&derivative)
+                + offset.dot(&self.second_derivative([t]));
+            if df <= Scalar::ZERO {
+                break;
+            }
+
+            let next = (t - f / df).max(Scalar::ZERO).min(Scalar::ONE);
+            if next == t {
+                break;
+            }
+            t = next;
+        }
+
+        Point::from([t])
+    }
+
+    /// Compute a bounding box of the curve
+    ///
+    /// The curve is contained within the convex hull of its control points, so
+    /// the bounding box of the control points is returned. It is not
+    /// necessarily tight.
+    pub fn aabb(&self) -> Aabb<D> {
+        let [first, ..] = self.points;
+
+        let mut min = first;
+        let mut max = first;
+        for point in self.points {
+            for i in 0..D {
+                min.coords.components[i] =
+                    min.coords.components[i].min(point.coords.components[i]);
+                max.coords.components[i] =
+                    max.coords.components[i].max(point.coords.components[i]);
+            }
+        }
+
+        Aabb { min, max }
+    }
+
+    fn second_differences(&self) -> [Vector<D>; 2] {
+        let [a, b, c, d] = self.points;
+        [a - b + (c - b), b - c + (d - c)]
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Bezier<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.points
+            .iter()
+            .zip(&other.points)
+            .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+}
+
+const NUM_SAMPLES: usize = 16;
+const MAX_ITERATIONS: usize = 16;
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::Bezier;
+
+    #[test]
+    fn bezier() {
+        let bezier = Bezier::<2>::new([[0., 0.], [1., 2.], [3., 2.], [4., 0.]]);
+
+        assert_eq!(
+            bezier.point_from_bezier_coords([0.]),
+            Point::from([0., 0.])
+        );
+        assert_eq!(
+            bezier.point_from_bezier_coords([1.]),
+            Point::from([4., 0.])
+        );
+        assert_eq!(
+            bezier.point_from_bezier_coords([0.5]),
+            Point::from([2., 1.5])
+        );
+
+        assert_eq!(bezier.derivative([0.]), Vector::from([3., 6.]));
+        assert_eq!(bezier.derivative([1.]), Vector::from([3., -6.]));
+
+        let t = bezier.project_point([2., 3.]);
+        assert_abs_diff_eq!(
+            t,
+            Point::from([0.5]),
+            epsilon = Scalar::from(1e-9)
+        );
+        let t = bezier.project_point([-1., -1.]);
+        assert_eq!(t, Point::from([0.]));
+
+        let aabb = bezier.aabb();
+        assert_eq!(aabb.min, Point::from([0., 0.]));
+        assert_eq!(aabb.max, Point::from([4., 2.]));
+    }
+}
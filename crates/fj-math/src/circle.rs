@@ -54,6 +54,20 @@ impl<const D: usize> Circle<D> {
         Self { center, a, b }
     }
 
+    /// Construct a circle without checking the requirements of [`Self::new`]
+    ///
+    /// Used by [`Ellipse::to_circle`], which checks them within a relative
+    /// epsilon.
+    ///
+    /// [`Ellipse::to_circle`]: crate::Ellipse::to_circle
+    pub(crate) fn new_unchecked(
+        center: Point<D>,
+        a: Vector<D>,
+        b: Vector<D>,
+    ) -> Self {
+        Self { center, a, b }
+    }
+
     /// Construct a `Circle` from a center point and a radius
     pub fn from_center_and_radius(
         center: impl Into<Point<D>>,
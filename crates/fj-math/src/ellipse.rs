@@ -0,0 +1,217 @@
+use approx::AbsDiffEq;
+use num_traits::Float;
+
+use crate::{Circle, Point, Scalar, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The ellipse is defined by its center and two conjugate semi-diameters, `a`
+/// and `b`. Unlike the ones of a [`Circle`], those don't need to be of equal
+/// length, nor perpendicular to each other. This makes it possible to
+/// represent the result of an arbitrary affine transformation (like a
+/// non-uniform scaling) of a circle.
+///
+/// Ellipse coordinates are defined just like circle coordinates, which means
+/// the ellipse coordinates of a point don't change, when a circle is converted
+/// into an ellipse, and then transformed.
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` or `b` are zero, or if they are parallel to each other.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        assert_ne!(a.magnitude(), Scalar::ZERO, "`a` must not be zero");
+        assert_ne!(b.magnitude(), Scalar::ZERO, "`b` must not be zero");
+
+        // The Gram determinant is zero, if and only if `a` and `b` are
+        // parallel. Scale the epsilon, as the determinant scales with the
+        // fourth power of the ellipse's size.
+        let gram = a.dot(&a) * b.dot(&b) - a.dot(&b) * a.dot(&b);
+        assert!(
+            gram > Scalar::default_epsilon() * a.dot(&a) * b.dot(&b),
+            "`a` and `b` must not be parallel to each other"
+        );
+
+        Self { center, a, b }
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the vector that defines the starting point of the ellipse
+    ///
+    /// The point where this vector points from the ellipse center, is the zero
+    /// coordinate of the ellipse's coordinate system.
+    ///
+    /// Please also refer to [`Self::b`].
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines the plane of the ellipse
+    ///
+    /// Also defines the direction of the ellipse's coordinate system. The
+    /// ellipse passes through the point this vector points to from the center,
+    /// at a quarter turn.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Compute the length of the ellipse's semi-major axis
+    ///
+    /// This is the largest distance between the center and any point on the
+    /// ellipse.
+    pub fn max_radius(&self) -> Scalar {
+        // The semi-major axis is the largest singular value of the matrix that
+        // has `a` and `b` as columns. Its square is the largest eigenvalue of
+        // that matrix's Gram matrix.
+        let p = self.a.dot(&self.a);
+        let q = self.b.dot(&self.b);
+        let r = self.a.dot(&self.b);
+
+        let half_sum = (p + q) / 2.;
+        let half_diff = (p - q) / 2.;
+        let eigenvalue = half_sum + (half_diff * half_diff + r * r).sqrt();
+
+        eigenvalue.sqrt()
+    }
+
+    /// Convert the ellipse into a circle, if it is one
+    ///
+    /// Returns `None`, if `a` and `b` are not of equal length or not
+    /// perpendicular to each other, within a small relative epsilon.
+    pub fn to_circle(&self) -> Option<Circle<D>> {
+        let radius = self.a.magnitude();
+        let epsilon = Scalar::from(1e-12);
+
+        let equal_length =
+            (radius - self.b.magnitude()).abs() <= epsilon * radius;
+        let perpendicular =
+            self.a.dot(&self.b).abs() <= epsilon * radius * radius;
+
+        if !(equal_length && perpendicular) {
+            return None;
+        }
+
+        // `Circle::new` expects `a` and `b` to be *precisely* of equal length
+        // and perpendicular, which the result of a transformation rarely is.
+        // We've checked that they're close enough.
+        Some(Circle::new_unchecked(self.center, self.a, self.b))
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional vector
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+}
+
+impl<const D: usize> From<Circle<D>> for Ellipse<D> {
+    fn from(circle: Circle<D>) -> Self {
+        Self {
+            center: circle.center(),
+            a: circle.a(),
+            b: circle.b(),
+        }
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Ellipse<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.center.abs_diff_eq(&other.center, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use num_traits::Float;
+
+    use crate::{Circle, Point, Scalar, Vector};
+
+    use super::Ellipse;
+
+    #[test]
+    fn ellipse() {
+        let ellipse = Ellipse::new(
+            Point::from([0., 0., 0.]),
+            Vector::from([2., 0., 0.]),
+            Vector::from([1., 1., 0.]),
+        );
+        assert!(ellipse.to_circle().is_none());
+
+        // The squared semi-axes are the eigenvalues of [[4, 2], [2, 2]].
+        assert_abs_diff_eq!(
+            ellipse.max_radius(),
+            (Scalar::from(3.) + Scalar::from(5.).sqrt()).sqrt(),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        assert_abs_diff_eq!(
+            ellipse.point_from_ellipse_coords([Scalar::PI / 2.]),
+            Point::from([1., 1., 0.]),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        let circle = Circle::from_center_and_radius(Point::from([1., 2.]), 3.);
+        let ellipse = Ellipse::from(circle);
+        assert_eq!(ellipse.max_radius(), Scalar::from(3.));
+        assert_eq!(ellipse.to_circle(), Some(circle));
+
+        let ellipse = Ellipse::new(
+            Point::from([0., 0.]),
+            Vector::from([1., 1.]),
+            Vector::from([-1., 1.]) * (1. + 1e-15),
+        );
+        assert!(ellipse.to_circle().is_some());
+    }
+}
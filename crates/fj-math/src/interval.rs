@@ -0,0 +1,342 @@
+use std::{cmp::Ordering, ops};
+
+use crate::{Point, Scalar, Sign};
+
+/// A closed interval of scalars, for conservative computations
+///
+/// Arithmetic on intervals is rounded outward, so the result of a computation
+/// is guaranteed to contain the exact result of the same computation on real
+/// numbers. This makes it possible to decide questions like "is this value
+/// positive?" with certainty, or to detect that a value is too close to call.
+///
+/// Those decisions are represented as `Option`s, where `None` means "maybe".
+/// See [`Interval::sign`] and [`Interval::compare`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Interval {
+    /// The lower bound of the interval
+    pub min: Scalar,
+
+    /// The upper bound of the interval
+    pub max: Scalar,
+}
+
+impl Interval {
+    /// Construct an interval from two bounds, in any order
+    pub fn new(a: impl Into<Scalar>, b: impl Into<Scalar>) -> Self {
+        let [a, b] = [a.into(), b.into()];
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Construct an interval that contains only the given value
+    pub fn from_scalar(value: impl Into<Scalar>) -> Self {
+        let value = value.into();
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Construct an interval around a value, that allows for an error
+    pub fn around(value: impl Into<Scalar>, error: impl Into<Scalar>) -> Self {
+        let [value, error] = [value.into(), error.into()].map(Scalar::into_f64);
+        let error = error.abs();
+
+        Self::from_f64(add_down(value, -error), add_up(value, error))
+    }
+
+    /// Compute an interval that contains the distance between two points
+    pub fn distance<const D: usize>(a: &Point<D>, b: &Point<D>) -> Self {
+        a.coords
+            .components
+            .into_iter()
+            .zip(b.coords.components)
+            .map(|(a, b)| {
+                (Self::from_scalar(a) - Self::from_scalar(b)).squared()
+            })
+            .fold(Self::from_scalar(Scalar::ZERO), |sum, d| sum + d)
+            .sqrt()
+    }
+
+    /// Compute the width of the interval
+    pub fn width(&self) -> Scalar {
+        self.max - self.min
+    }
+
+    /// Determine whether the interval contains the given value
+    pub fn contains(&self, value: impl Into<Scalar>) -> bool {
+        let value = value.into();
+        self.min <= value && value <= self.max
+    }
+
+    /// Determine the sign of all values in the interval
+    ///
+    /// Returns `None`, if the interval contains values of different signs.
+    pub fn sign(&self) -> Option<Sign> {
+        if self.min > Scalar::ZERO {
+            return Some(Sign::Positive);
+        }
+        if self.max < Scalar::ZERO {
+            return Some(Sign::Negative);
+        }
+        if self.min == Scalar::ZERO && self.max == Scalar::ZERO {
+            return Some(Sign::Zero);
+        }
+
+        None
+    }
+
+    /// Compare all values in the interval to all values in another
+    ///
+    /// Returns `None`, if the intervals overlap, meaning the result of the
+    /// comparison depends on the exact values.
+    pub fn compare(&self, other: impl Into<Self>) -> Option<Ordering> {
+        (*self - other.into()).sign().map(|sign| match sign {
+            Sign::Negative => Ordering::Less,
+            Sign::Positive => Ordering::Greater,
+            Sign::Zero => Ordering::Equal,
+        })
+    }
+
+    /// Compute the square of all values in the interval
+    ///
+    /// Unlike multiplying the interval with itself, this takes into account
+    /// that a square can't be negative.
+    pub fn squared(self) -> Self {
+        let abs = if self.min >= Scalar::ZERO {
+            self
+        } else if self.max <= Scalar::ZERO {
+            -self
+        } else {
+            Self::new(Scalar::ZERO, self.min.abs().max(self.max.abs()))
+        };
+
+        let [min, max] = [abs.min, abs.max].map(Scalar::into_f64);
+        Self::from_f64(mul_down(min, min), mul_up(max, max))
+    }
+
+    /// Compute the square root of all values in the interval
+    ///
+    /// Negative values are ignored.
+    pub fn sqrt(self) -> Self {
+        let [min, max] = [self.min, self.max]
+            .map(|value| value.max(Scalar::ZERO))
+            .map(Scalar::into_f64);
+        Self::from_f64(sqrt_down(min), sqrt_up(max))
+    }
+
+    fn from_f64(min: f64, max: f64) -> Self {
+        Self {
+            min: Scalar::from_f64(min),
+            max: Scalar::from_f64(max),
+        }
+    }
+}
+
+impl From<Scalar> for Interval {
+    fn from(value: Scalar) -> Self {
+        Self::from_scalar(value)
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(value: f64) -> Self {
+        Self::from_scalar(value)
+    }
+}
+
+impl ops::Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            min: -self.max,
+            max: -self.min,
+        }
+    }
+}
+
+impl<T: Into<Self>> ops::Add<T> for Interval {
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Self::from_f64(
+            add_down(self.min.into_f64(), rhs.min.into_f64()),
+            add_up(self.max.into_f64(), rhs.max.into_f64()),
+        )
+    }
+}
+
+impl<T: Into<Self>> ops::Sub<T> for Interval {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self + -rhs.into()
+    }
+}
+
+impl<T: Into<Self>> ops::Mul<T> for Interval {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+
+        let [a, b] = [self.min, self.max].map(Scalar::into_f64);
+        let [c, d] = [rhs.min, rhs.max].map(Scalar::into_f64);
+        let products = [[a, c], [a, d], [b, c], [b, d]];
+
+        let min = products
+            .map(|[x, y]| mul_down(x, y))
+            .into_iter()
+            .fold(f64::INFINITY, f64::min);
+        let max = products
+            .map(|[x, y]| mul_up(x, y))
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        Self::from_f64(min, max)
+    }
+}
+
+// The following functions compute the result of an operation, rounded down or
+// up. The rounding error of a single operation is computed exactly, using
+// error-free transformations. That way, the result is only widened, if the
+// operation was actually inexact.
+
+fn add_down(a: f64, b: f64) -> f64 {
+    let (sum, error) = two_sum(a, b);
+    if error < 0. {
+        next_down(sum)
+    } else {
+        sum
+    }
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let (sum, error) = two_sum(a, b);
+    if error > 0. {
+        next_up(sum)
+    } else {
+        sum
+    }
+}
+
+fn mul_down(a: f64, b: f64) -> f64 {
+    let product = a * b;
+    if a.mul_add(b, -product) < 0. {
+        next_down(product)
+    } else {
+        product
+    }
+}
+
+fn mul_up(a: f64, b: f64) -> f64 {
+    let product = a * b;
+    if a.mul_add(b, -product) > 0. {
+        next_up(product)
+    } else {
+        product
+    }
+}
+
+fn sqrt_down(a: f64) -> f64 {
+    let root = a.sqrt();
+    if root.mul_add(root, -a) > 0. {
+        next_down(root)
+    } else {
+        root
+    }
+}
+
+fn sqrt_up(a: f64) -> f64 {
+    let root = a.sqrt();
+    if root.mul_add(root, -a) < 0. {
+        next_up(root)
+    } else {
+        root
+    }
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    if !sum.is_finite() {
+        return (sum, 0.);
+    }
+
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    let error = (a - a_virtual) + (b - b_virtual);
+
+    (sum, error)
+}
+
+fn next_up(a: f64) -> f64 {
+    if a.is_nan() || a == f64::INFINITY {
+        return a;
+    }
+    if a == 0. {
+        return f64::from_bits(1);
+    }
+
+    let bits = a.to_bits();
+    if a > 0. {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
+
+fn next_down(a: f64) -> f64 {
+    -next_up(-a)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{Point, Scalar, Sign};
+
+    use super::Interval;
+
+    #[test]
+    fn arithmetic_is_conservative() {
+        let a = Interval::from_scalar(0.1);
+        let b = Interval::from_scalar(0.2);
+
+        let sum = a + b;
+
+        // The exact sum of the `f64` values is not representable, so the
+        // resulting interval can't be a single value.
+        assert!(sum.min < sum.max);
+        assert!(sum.contains(0.1 + 0.2));
+        assert_eq!(sum.compare(0.3), None);
+    }
+
+    #[test]
+    fn exact_results_are_not_widened() {
+        let a = Interval::new(1., 2.);
+
+        assert_eq!(a + 1., Interval::new(2., 3.));
+        assert_eq!(a * -2., Interval::new(-4., -2.));
+        assert_eq!((a - a).sign(), None);
+        assert_eq!(
+            Interval::from_scalar(Scalar::ZERO).sign(),
+            Some(Sign::Zero)
+        );
+    }
+
+    #[test]
+    fn distance() {
+        let a = Point::from([0., 0.]);
+        let b = Point::from([3., 4.]);
+
+        let distance = Interval::distance(&a, &b);
+
+        assert_eq!(distance, Interval::from_scalar(5.));
+        assert_eq!(distance.compare(4.9), Some(Ordering::Greater));
+        assert_eq!(distance.compare(5.1), Some(Ordering::Less));
+    }
+}
@@ -37,14 +37,20 @@
 
 mod aabb;
 mod arc;
+mod bezier;
 mod circle;
 mod coordinates;
+mod ellipse;
+mod interval;
 mod line;
 mod plane;
 mod point;
 mod poly_chain;
+pub mod predicates;
+mod quaternion;
 mod scalar;
 mod segment;
+mod tolerance;
 mod transform;
 mod triangle;
 mod vector;
@@ -52,14 +58,19 @@ mod vector;
 pub use self::{
     aabb::Aabb,
     arc::Arc,
+    bezier::Bezier,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    ellipse::Ellipse,
+    interval::Interval,
     line::Line,
     plane::Plane,
     point::Point,
     poly_chain::PolyChain,
+    quaternion::Quaternion,
     scalar::{Scalar, Sign},
     segment::Segment,
+    tolerance::{InvalidTolerance, Tolerance},
     transform::Transform,
     triangle::{Triangle, Winding},
     vector::Vector,
@@ -0,0 +1,203 @@
+//! Robust geometric predicates
+//!
+//! The predicates in this module are exact. They use adaptive-precision
+//! arithmetic, as described by Jonathan Richard Shewchuk, to compute the
+//! correct sign of their result, regardless of floating-point error. This is
+//! fast for the common case, and only falls back to more expensive computations
+//! if the inputs are (nearly) degenerate.
+//!
+//! Use these, instead of comparing computed areas or volumes against a
+//! tolerance, whenever a decision depends on the relative position of points.
+
+use crate::{Point, Sign};
+
+/// Determine the orientation of three points in the plane
+///
+/// Returns [`Sign::Positive`], if `a`, `b`, and `c` are in counterclockwise
+/// order, [`Sign::Negative`], if they are in clockwise order, and
+/// [`Sign::Zero`], if they are collinear.
+pub fn orient2d(a: Point<2>, b: Point<2>, c: Point<2>) -> Sign {
+    let [a, b, c] = [a, b, c].map(to_coord);
+    sign(robust::orient2d(a, b, c))
+}
+
+/// Determine the orientation of a simple polygon in the plane
+///
+/// Returns [`Sign::Positive`], if the vertices of the polygon are in
+/// counterclockwise order, [`Sign::Negative`], if they are in clockwise order,
+/// and [`Sign::Zero`], if the polygon is degenerate.
+///
+/// The vertex with the lowest coordinates is on the polygon's convex hull, so
+/// the polygon's orientation is the same as that of the corner at that vertex.
+/// Determining it like this, instead of summing up the polygon's signed area,
+/// gives the correct result even for nearly degenerate polygons.
+pub fn polygon_orientation(points: &[Point<2>]) -> Sign {
+    let Some((i, &lowest)) =
+        points.iter().enumerate().min_by_key(|(_, point)| **point)
+    else {
+        return Sign::Zero;
+    };
+
+    let prev = points[(i + points.len() - 1) % points.len()];
+    let next = points[(i + 1) % points.len()];
+
+    orient2d(prev, lowest, next)
+}
+
+/// Determine the orientation of four points in space
+///
+/// Returns [`Sign::Positive`], if `d` is below the plane through `a`, `b`, and
+/// `c`, where "below" is defined such that `a`, `b`, and `c` appear in
+/// counterclockwise order when viewed from above. Returns [`Sign::Negative`],
+/// if `d` is above that plane, and [`Sign::Zero`], if the points are coplanar.
+pub fn orient3d(a: Point<3>, b: Point<3>, c: Point<3>, d: Point<3>) -> Sign {
+    let [a, b, c, d] = [a, b, c, d].map(to_coord3d);
+    sign(robust::orient3d(a, b, c, d))
+}
+
+/// Determine whether a point is inside the circle through three other points
+///
+/// `a`, `b`, and `c` must be in counterclockwise order, or the sign of the
+/// result is reversed. Returns [`Sign::Positive`], if `d` is inside the circle,
+/// [`Sign::Negative`], if it is outside, and [`Sign::Zero`], if it is on the
+/// circle.
+pub fn incircle(a: Point<2>, b: Point<2>, c: Point<2>, d: Point<2>) -> Sign {
+    let [a, b, c, d] = [a, b, c, d].map(to_coord);
+    sign(robust::incircle(a, b, c, d))
+}
+
+/// Determine whether a point is inside the sphere through four other points
+///
+/// `a`, `b`, `c`, and `d` must be oriented such that [`orient3d`] returns
+/// [`Sign::Positive`] for them, or the sign of the result is reversed. Returns
+/// [`Sign::Positive`], if `e` is inside the sphere, [`Sign::Negative`], if it
+/// is outside, and [`Sign::Zero`], if it is on the sphere.
+pub fn insphere(
+    a: Point<3>,
+    b: Point<3>,
+    c: Point<3>,
+    d: Point<3>,
+    e: Point<3>,
+) -> Sign {
+    let [a, b, c, d, e] = [a, b, c, d, e].map(to_coord3d);
+    sign(robust::insphere(a, b, c, d, e))
+}
+
+fn to_coord(point: Point<2>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: point.u.into_f64(),
+        y: point.v.into_f64(),
+    }
+}
+
+fn to_coord3d(point: Point<3>) -> robust::Coord3D<f64> {
+    robust::Coord3D {
+        x: point.x.into_f64(),
+        y: point.y.into_f64(),
+        z: point.z.into_f64(),
+    }
+}
+
+fn sign(value: f64) -> Sign {
+    if value > 0. {
+        Sign::Positive
+    } else if value < 0. {
+        Sign::Negative
+    } else {
+        Sign::Zero
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Sign};
+
+    #[test]
+    fn orient2d() {
+        let a = Point::from([0., 0.]);
+        let b = Point::from([1., 1.]);
+
+        // The offset of `c` from the line through `a` and `b` is far below the
+        // error of a naive floating-point computation.
+        let c = Point::from([2., 2. + f64::EPSILON * 2.]);
+        let d = Point::from([2., 2.]);
+
+        assert_eq!(super::orient2d(a, b, c), Sign::Positive);
+        assert_eq!(super::orient2d(a, c, b), Sign::Negative);
+        assert_eq!(super::orient2d(a, b, d), Sign::Zero);
+    }
+
+    #[test]
+    fn polygon_orientation() {
+        let ccw =
+            [[0., 0.], [2., 0.], [1., 1.], [2., 2.], [0., 2.]].map(Point::from);
+        let mut cw = ccw;
+        cw.reverse();
+
+        assert_eq!(super::polygon_orientation(&ccw), Sign::Positive);
+        assert_eq!(super::polygon_orientation(&cw), Sign::Negative);
+        assert_eq!(super::polygon_orientation(&ccw[..2]), Sign::Zero);
+        assert_eq!(super::polygon_orientation(&[]), Sign::Zero);
+    }
+
+    #[test]
+    fn orient3d() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+
+        assert_eq!(
+            super::orient3d(a, b, c, Point::from([0., 0., -1.])),
+            Sign::Positive
+        );
+        assert_eq!(
+            super::orient3d(a, b, c, Point::from([0., 0., 1.])),
+            Sign::Negative
+        );
+        assert_eq!(
+            super::orient3d(a, b, c, Point::from([1., 1., 0.])),
+            Sign::Zero
+        );
+    }
+
+    #[test]
+    fn incircle() {
+        let a = Point::from([1., 0.]);
+        let b = Point::from([0., 1.]);
+        let c = Point::from([-1., 0.]);
+
+        assert_eq!(
+            super::incircle(a, b, c, Point::from([0., 0.])),
+            Sign::Positive
+        );
+        assert_eq!(
+            super::incircle(a, b, c, Point::from([0., 2.])),
+            Sign::Negative
+        );
+        assert_eq!(
+            super::incircle(a, b, c, Point::from([0., -1.])),
+            Sign::Zero
+        );
+    }
+
+    #[test]
+    fn insphere() {
+        let a = Point::from([1., 0., 0.]);
+        let b = Point::from([0., 1., 0.]);
+        let c = Point::from([-1., 0., 0.]);
+        let d = Point::from([0., 0., -1.]);
+
+        assert_eq!(
+            super::insphere(a, b, c, d, Point::from([0., 0., 0.])),
+            Sign::Positive
+        );
+        assert_eq!(
+            super::insphere(a, b, c, d, Point::from([0., 0., 2.])),
+            Sign::Negative
+        );
+        assert_eq!(
+            super::insphere(a, b, c, d, Point::from([0., 0., 1.])),
+            Sign::Zero
+        );
+    }
+}
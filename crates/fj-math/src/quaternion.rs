@@ -0,0 +1,231 @@
+use std::ops;
+
+use crate::{Point, Scalar, Vector};
+
+/// A rotation in 3D space, represented as a unit quaternion
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion(nalgebra::UnitQuaternion<f64>);
+
+impl Quaternion {
+    /// Construct the identity rotation
+    pub fn identity() -> Self {
+        Self(nalgebra::UnitQuaternion::identity())
+    }
+
+    /// Construct a rotation by the given angle around the given axis
+    ///
+    /// The axis doesn't need to be normalized. Returns the identity rotation,
+    /// if the axis has zero length.
+    pub fn from_axis_angle(
+        axis: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+    ) -> Self {
+        let axis = axis.into();
+        if axis.magnitude() == Scalar::ZERO {
+            return Self::identity();
+        }
+
+        Self::from_scaled_axis(axis.normalize() * angle.into())
+    }
+
+    /// Construct a rotation from a scaled axis
+    ///
+    /// The direction of the vector defines the rotation axis. Its length
+    /// defines the angle of the rotation.
+    pub fn from_scaled_axis(axis_angle: impl Into<Vector<3>>) -> Self {
+        Self(nalgebra::UnitQuaternion::from_scaled_axis(
+            axis_angle.into().to_na(),
+        ))
+    }
+
+    /// Construct a rotation from Euler angles
+    ///
+    /// The rotation is applied around the x axis (`roll`) first, then the y
+    /// axis (`pitch`), then the z axis (`yaw`).
+    pub fn from_euler_angles(
+        roll: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+        yaw: impl Into<Scalar>,
+    ) -> Self {
+        Self(nalgebra::UnitQuaternion::from_euler_angles(
+            roll.into().into_f64(),
+            pitch.into().into_f64(),
+            yaw.into().into_f64(),
+        ))
+    }
+
+    /// Construct a rotation from the axes of a rotated frame
+    ///
+    /// The resulting rotation maps the x, y, and z axes to the provided ones.
+    /// The axes must be normalized and orthogonal to each other, and must form
+    /// a right-handed coordinate system.
+    pub fn from_basis(axes: [Vector<3>; 3]) -> Self {
+        let rotation =
+            nalgebra::Rotation3::from_basis_unchecked(&axes.map(Vector::to_na));
+        Self(nalgebra::UnitQuaternion::from_rotation_matrix(&rotation))
+    }
+
+    /// Construct the shortest rotation that maps `a` onto `b`
+    ///
+    /// Returns `None`, if either vector has zero length, or if the vectors
+    /// point in opposite directions, which makes the rotation axis ambiguous.
+    pub fn rotation_between(
+        a: impl Into<Vector<3>>,
+        b: impl Into<Vector<3>>,
+    ) -> Option<Self> {
+        nalgebra::UnitQuaternion::rotation_between(
+            &a.into().to_na(),
+            &b.into().to_na(),
+        )
+        .map(Self)
+    }
+
+    /// Construct a `Quaternion` from an nalgebra unit quaternion
+    pub fn from_na(quaternion: nalgebra::UnitQuaternion<f64>) -> Self {
+        Self(quaternion)
+    }
+
+    /// Convert the `Quaternion` into an nalgebra unit quaternion
+    pub fn to_na(self) -> nalgebra::UnitQuaternion<f64> {
+        self.0
+    }
+
+    /// Access the angle of the rotation, in the range `[0, π]`
+    pub fn angle(&self) -> Scalar {
+        Scalar::from_f64(self.0.angle())
+    }
+
+    /// Access the axis of the rotation
+    ///
+    /// Returns `None`, if this is the identity rotation.
+    pub fn axis(&self) -> Option<Vector<3>> {
+        self.0.axis().map(|axis| Vector::from_na(axis.into_inner()))
+    }
+
+    /// Access the rotation as a scaled axis
+    ///
+    /// This is the inverse of [`Quaternion::from_scaled_axis`].
+    pub fn scaled_axis(&self) -> Vector<3> {
+        Vector::from_na(self.0.scaled_axis())
+    }
+
+    /// Access the rotation as Euler angles
+    ///
+    /// Returns `[roll, pitch, yaw]`. This is the inverse of
+    /// [`Quaternion::from_euler_angles`].
+    pub fn euler_angles(&self) -> [Scalar; 3] {
+        let (roll, pitch, yaw) = self.0.euler_angles();
+        [roll, pitch, yaw].map(Scalar::from_f64)
+    }
+
+    /// Access the rotated axes
+    ///
+    /// This is the inverse of [`Quaternion::from_basis`].
+    pub fn basis(&self) -> [Vector<3>; 3] {
+        [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .map(|axis| self.rotate_vector(&axis))
+    }
+
+    /// Compute the inverse rotation
+    pub fn inverse(&self) -> Self {
+        Self(self.0.inverse())
+    }
+
+    /// Interpolate between this rotation and another
+    ///
+    /// Returns this rotation for `t == 0`, and `other` for `t == 1`. Takes the
+    /// shortest path in between.
+    pub fn slerp(&self, other: &Self, t: impl Into<Scalar>) -> Self {
+        Self(self.0.slerp(&other.0, t.into().into_f64()))
+    }
+
+    /// Rotate the given vector
+    pub fn rotate_vector(&self, vector: &Vector<3>) -> Vector<3> {
+        Vector::from_na(self.0.transform_vector(&vector.to_na()))
+    }
+
+    /// Rotate the given point around the origin
+    pub fn rotate_point(&self, point: &Point<3>) -> Point<3> {
+        Point::from(self.0.transform_point(&point.to_na()))
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl ops::Mul<Self> for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl approx::AbsDiffEq for Quaternion {
+    type Epsilon = Scalar;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        // `q` and `-q` represent the same rotation.
+        self.0.angle_to(&other.0) <= epsilon.into_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Scalar, Vector};
+
+    use super::Quaternion;
+
+    #[test]
+    fn from_axis_angle() {
+        let rotation =
+            Quaternion::from_axis_angle([0., 0., 2.], Scalar::PI / 2.);
+
+        assert_abs_diff_eq!(
+            rotation.rotate_vector(&Vector::unit_x()),
+            Vector::unit_y(),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            rotation,
+            Quaternion::from_euler_angles(0., 0., Scalar::PI / 2.),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn basis() {
+        let rotation = Quaternion::from_euler_angles(0.1, 0.2, 0.3);
+
+        assert_abs_diff_eq!(
+            Quaternion::from_basis(rotation.basis()),
+            rotation,
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn rotation_between() {
+        let a = Vector::from([1., 2., 3.]);
+        let b = Vector::from([-3., 1., 0.5]);
+
+        let rotation = Quaternion::rotation_between(a, b).unwrap();
+
+        assert_abs_diff_eq!(
+            rotation.rotate_vector(&a).normalize(),
+            b.normalize(),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert!(Quaternion::rotation_between(a, -a).is_none());
+    }
+}
@@ -592,7 +592,7 @@ impl approx::AbsDiffEq for Scalar {
 /// The sign of a [`Scalar`]
 ///
 /// See [`Scalar::sign`]
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Sign {
     /// The scalar is negative
     Negative,
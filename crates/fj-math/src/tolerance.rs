@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::Scalar;
+
+/// A tolerance value
+///
+/// A tolerance defines the maximum allowed deviation of an approximation from
+/// the actual shape, or of two values that are considered to be identical.
+///
+/// A tolerance has two components:
+///
+/// - A length, which limits the distance between points.
+/// - An angle, which limits the angle between directions.
+///
+/// The `Tolerance` type enforces that both components are always larger than
+/// zero, which is an attribute that code using tolerances relies on.
+///
+/// # Failing [`From`]/[`Into`] implementation
+///
+/// The [`From`]/[`Into`] implementations of tolerance are fallible, which goes
+/// against the explicit mandate of those traits, as stated in their
+/// documentation.
+///
+/// A fallible [`Into`] provides a lot of convenience in test code. Since said
+/// documentation doesn't provide any actual reasoning for this requirement, I'm
+/// feeling free to just ignore it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Tolerance {
+    length: Scalar,
+    angle: Scalar,
+}
+
+impl Tolerance {
+    /// The default angular tolerance, in radians
+    ///
+    /// No two directions can deviate by more than this, so this angle doesn't
+    /// constrain anything. Only the length tolerance applies. This is
+    /// deliberate: a tolerance that is created from a length alone, like the
+    /// ones in the validation configuration, must behave the same as it did
+    /// before tolerances had an angle.
+    ///
+    /// Code that needs an angular tolerance must ask for one explicitly, using
+    /// [`Tolerance::new`] or [`Tolerance::with_angle`].
+    pub const DEFAULT_ANGLE: Scalar = Scalar::PI;
+
+    /// Construct a `Tolerance` from a [`Scalar`] that defines its length
+    ///
+    /// The angular tolerance is set to [`Tolerance::DEFAULT_ANGLE`].
+    ///
+    /// Returns an error, if the passed scalar is not larger than zero.
+    pub fn from_scalar(
+        length: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        Self::new(length, Self::DEFAULT_ANGLE)
+    }
+
+    /// Construct a `Tolerance` from a length and an angle, in radians
+    ///
+    /// Returns an error, if either of the passed values is not larger than
+    /// zero.
+    pub fn new(
+        length: impl Into<Scalar>,
+        angle: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        let length = length.into();
+        let angle = angle.into();
+
+        if length <= Scalar::ZERO {
+            return Err(InvalidTolerance::Length(length));
+        }
+        if angle <= Scalar::ZERO {
+            return Err(InvalidTolerance::Angle(angle));
+        }
+
+        Ok(Self { length, angle })
+    }
+
+    /// Return a copy of this tolerance, with a different angle, in radians
+    ///
+    /// Returns an error, if the passed angle is not larger than zero.
+    pub fn with_angle(
+        self,
+        angle: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        Self::new(self.length, angle)
+    }
+
+    /// Return the length that defines the tolerance
+    pub fn length(&self) -> Scalar {
+        self.length
+    }
+
+    /// Return the angle, in radians, that defines the tolerance
+    pub fn angle(&self) -> Scalar {
+        self.angle
+    }
+
+    /// Determine whether a distance is within the length tolerance
+    pub fn contains_distance(&self, distance: impl Into<Scalar>) -> bool {
+        distance.into() <= self.length
+    }
+
+    /// Determine whether an angle, in radians, is within the angle tolerance
+    pub fn contains_angle(&self, angle: impl Into<Scalar>) -> bool {
+        angle.into().abs() <= self.angle
+    }
+}
+
+impl<S> From<S> for Tolerance
+where
+    S: Into<Scalar>,
+{
+    fn from(length: S) -> Self {
+        Self::from_scalar(length)
+            .expect("Tried to create `Tolerance` from invalid value")
+    }
+}
+
+/// Error constructing a [`Tolerance`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvalidTolerance {
+    /// The length is not above zero
+    Length(Scalar),
+
+    /// The angle is not above zero
+    Angle(Scalar),
+}
+
+impl fmt::Display for InvalidTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(length) => {
+                write!(f, "Invalid tolerance ({length}); must be above zero")
+            }
+            Self::Angle(angle) => write!(
+                f,
+                "Invalid angular tolerance ({angle}); must be above zero"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidTolerance {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scalar;
+
+    use super::{InvalidTolerance, Tolerance};
+
+    #[test]
+    fn new() {
+        assert!(Tolerance::new(0.1, 0.1).is_ok());
+        assert_eq!(
+            Tolerance::new(0., 0.1),
+            Err(InvalidTolerance::Length(Scalar::ZERO))
+        );
+        assert_eq!(
+            Tolerance::new(0.1, -0.1),
+            Err(InvalidTolerance::Angle(Scalar::from(-0.1)))
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let tolerance = Tolerance::new(0.1, 0.2).unwrap();
+
+        assert!(tolerance.contains_distance(0.1));
+        assert!(!tolerance.contains_distance(0.2));
+        assert!(tolerance.contains_angle(-0.2));
+        assert!(!tolerance.contains_angle(0.3));
+    }
+
+    #[test]
+    fn default_angle() {
+        let tolerance = Tolerance::from(0.1);
+
+        assert_eq!(tolerance.angle(), Tolerance::DEFAULT_ANGLE);
+        assert!(tolerance.contains_angle(Scalar::PI));
+        assert!(tolerance.contains_angle(-Scalar::PI));
+    }
+}
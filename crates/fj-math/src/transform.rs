@@ -2,7 +2,7 @@ use std::ops;
 
 use nalgebra::Perspective3;
 
-use crate::{Circle, Line, Scalar};
+use crate::{Bezier, Circle, Ellipse, Line, Quaternion, Scalar};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -47,6 +47,103 @@ impl Transform {
         ))
     }
 
+    /// Construct a scaling with a separate factor for each axis
+    ///
+    /// Unlike [`Transform::scale`], this doesn't preserve angles, meaning it
+    /// can turn circles into ellipses. See [`Transform::transform_ellipse`].
+    pub fn non_uniform_scale(factors: impl Into<Vector<3>>) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::new_nonuniform_scaling(&factors.into().to_na()),
+        ))
+    }
+
+    /// Construct a rotation from a quaternion
+    pub fn from_rotation(rotation: Quaternion) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            rotation.to_na().to_homogeneous(),
+        ))
+    }
+
+    /// Construct a rotation by the given angle around the given axis
+    ///
+    /// See [`Quaternion::from_axis_angle`].
+    pub fn from_axis_angle(
+        axis: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+    ) -> Self {
+        Self::from_rotation(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    /// Construct a rotation from Euler angles
+    ///
+    /// See [`Quaternion::from_euler_angles`].
+    pub fn from_euler_angles(
+        roll: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+        yaw: impl Into<Scalar>,
+    ) -> Self {
+        Self::from_rotation(Quaternion::from_euler_angles(roll, pitch, yaw))
+    }
+
+    /// Construct a view transform for a camera
+    ///
+    /// The transform maps `eye` to the origin, and the direction towards
+    /// `target` to the negative z axis. `up` is mapped into the y-z plane, with
+    /// a positive y component. This is the right-handed convention used by
+    /// most graphics code.
+    pub fn look_at(
+        eye: impl Into<Point<3>>,
+        target: impl Into<Point<3>>,
+        up: impl Into<Vector<3>>,
+    ) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::look_at_rh(
+                &eye.into().to_na(),
+                &target.into().to_na(),
+                &up.into().to_na(),
+            ),
+        ))
+    }
+
+    /// Construct the transform that maps the global frame to the given one
+    ///
+    /// The transform maps the origin to `origin`, and the x, y, and z axes
+    /// to the provided ones. The axes don't need to be normalized or
+    /// orthogonal, as long as they are linearly independent.
+    pub fn from_frame(
+        origin: impl Into<Point<3>>,
+        axes: [Vector<3>; 3],
+    ) -> Self {
+        let origin = origin.into();
+        let [x, y, z] = axes.map(Vector::to_na);
+
+        let mut matrix = nalgebra::Matrix4::identity();
+        matrix
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&nalgebra::Matrix3::from_columns(&[x, y, z]));
+        matrix
+            .fixed_view_mut::<3, 1>(0, 3)
+            .copy_from(&origin.coords.to_na());
+
+        Self(nalgebra::Transform::from_matrix_unchecked(matrix))
+    }
+
+    /// Construct the transform that maps one frame to another
+    ///
+    /// Each frame is defined by an origin and three axes, as described in
+    /// [`Transform::from_frame`]. Points that are expressed relative to the
+    /// `from` frame end up in the same place relative to the `to` frame.
+    pub fn from_frames(
+        from: (Point<3>, [Vector<3>; 3]),
+        to: (Point<3>, [Vector<3>; 3]),
+    ) -> Self {
+        let (from_origin, from_axes) = from;
+        let (to_origin, to_axes) = to;
+
+        Self::from_frame(to_origin, to_axes)
+            * Self::from_frame(from_origin, from_axes).inverse()
+    }
+
     /// Transform the given point
     pub fn transform_point(&self, point: &Point<3>) -> Point<3> {
         Point::from(self.0.transform_point(&point.to_na()))
@@ -95,6 +192,30 @@ impl Transform {
         )
     }
 
+    /// Transform the given ellipse
+    ///
+    /// As opposed to [`Transform::transform_circle`], this works with any
+    /// transform. Points keep their ellipse coordinates.
+    pub fn transform_ellipse(&self, ellipse: &Ellipse<3>) -> Ellipse<3> {
+        Ellipse::new(
+            self.transform_point(&ellipse.center()),
+            self.transform_vector(&ellipse.a()),
+            self.transform_vector(&ellipse.b()),
+        )
+    }
+
+    /// Transform the given Bézier curve
+    ///
+    /// Bézier curves are invariant under affine transforms, so this works with
+    /// any transform. Points keep their Bézier coordinates.
+    pub fn transform_bezier(&self, bezier: &Bezier<3>) -> Bezier<3> {
+        Bezier::new(
+            bezier
+                .control_points()
+                .map(|point| self.transform_point(&point)),
+        )
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
@@ -144,6 +265,15 @@ impl Transform {
         self.0.matrix().data.as_slice()
     }
 
+    /// Construct a transform from the data returned by [`Transform::data`]
+    ///
+    /// The data is the transform's matrix, in column-major order.
+    pub fn from_data(data: [f64; 16]) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::from_column_slice(&data),
+        ))
+    }
+
     /// Extract the rotation component of this transform
     pub fn extract_rotation(&self) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -155,6 +285,42 @@ impl Transform {
     pub fn extract_translation(&self) -> Self {
         *self * self.extract_rotation().inverse()
     }
+
+    /// Decompose this transform into translation, rotation, and scale
+    ///
+    /// Applying the scale first, then the rotation, then the translation,
+    /// results in this transform again, as long as it doesn't contain any
+    /// shear. A mirroring is represented by a negative scale along the x
+    /// axis.
+    pub fn decompose(&self) -> (Vector<3>, Quaternion, Vector<3>) {
+        let matrix = self.0.matrix();
+
+        let translation =
+            Vector::from_na(matrix.fixed_view::<3, 1>(0, 3).into_owned());
+
+        let mut linear = matrix.fixed_view::<3, 3>(0, 0).into_owned();
+        let mut scale = [0, 1, 2].map(|i| linear.column(i).norm());
+        if linear.determinant() < 0. {
+            scale[0] = -scale[0];
+        }
+        for (i, scale) in scale.into_iter().enumerate() {
+            if scale != 0. {
+                linear.column_mut(i).unscale_mut(scale);
+            }
+        }
+
+        let rotation = nalgebra::Rotation3::from_matrix_eps(
+            &linear,
+            1e-12,
+            0,
+            nalgebra::Rotation3::identity(),
+        );
+        let rotation = Quaternion::from_na(
+            nalgebra::UnitQuaternion::from_rotation_matrix(&rotation),
+        );
+
+        (translation, rotation, Vector::from(scale))
+    }
 }
 
 impl ops::Mul<Self> for Transform {
@@ -169,7 +335,7 @@ impl ops::Mul<Self> for Transform {
 mod tests {
     use approx::assert_abs_diff_eq;
 
-    use crate::{Line, Point, Scalar, Vector};
+    use crate::{Circle, Ellipse, Line, Point, Quaternion, Scalar, Vector};
 
     use super::Transform;
 
@@ -194,6 +360,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_uniform_scale() {
+        let circle =
+            Circle::from_center_and_radius(Point::from([1., 1., 0.]), 1.);
+
+        let transform = Transform::non_uniform_scale([2., 3., 1.]);
+        let ellipse = transform.transform_ellipse(&Ellipse::from(circle));
+
+        assert_abs_diff_eq!(
+            ellipse,
+            Ellipse::new(
+                Point::from([2., 3., 0.]),
+                Vector::from([2., 0., 0.]),
+                Vector::from([0., 3., 0.]),
+            ),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert!(ellipse.to_circle().is_none());
+        assert_eq!(ellipse.max_radius(), Scalar::from(3.));
+    }
+
+    #[test]
+    fn from_frames() {
+        let from = (
+            Point::from([1., 0., 0.]),
+            [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()],
+        );
+        let to = (
+            Point::from([0., 2., 0.]),
+            [Vector::unit_y(), -Vector::unit_x(), Vector::unit_z()],
+        );
+
+        let transform = Transform::from_frames(from, to);
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([2., 0., 3.])),
+            Point::from([0., 3., 3.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn look_at() {
+        let transform =
+            Transform::look_at([0., 0., 5.], [0., 0., 0.], Vector::unit_y());
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::origin()),
+            Point::from([0., 0., -5.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn decompose() {
+        let translation = Vector::from([1., 2., 3.]);
+        let rotation = Quaternion::from_euler_angles(0.1, 0.2, 0.3);
+
+        let transform = Transform::translation(translation)
+            * Transform::from_rotation(rotation)
+            * Transform::scale(2.);
+        let (t, r, s) = transform.decompose();
+
+        let epsilon = Scalar::from(1e-12);
+        assert_abs_diff_eq!(t, translation, epsilon = epsilon);
+        assert_abs_diff_eq!(r, rotation, epsilon = epsilon);
+        assert_abs_diff_eq!(s, Vector::from([2., 2., 2.]), epsilon = epsilon);
+    }
+
     #[test]
     fn extract_rotation_translation() {
         let rotation =
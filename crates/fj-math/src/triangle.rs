@@ -1,6 +1,6 @@
 use parry3d_f64::query::{Ray, RayCast as _};
 
-use crate::Vector;
+use crate::{predicates, Sign, Vector};
 
 use super::{Point, Scalar};
 
@@ -58,23 +58,16 @@ impl<const D: usize> Triangle<D> {
 impl Triangle<2> {
     /// Returns the direction of the line through the points of the triangle.
     pub fn winding(&self) -> Winding {
-        let [pa, pb, pc] = self.points.map(|point| robust::Coord {
-            x: point.u,
-            y: point.v,
-        });
-        let orient2d = robust::orient2d(pa, pb, pc);
-
-        if orient2d < 0. {
-            return Winding::Cw;
+        let [a, b, c] = self.points;
+
+        match predicates::orient2d(a, b, c) {
+            Sign::Positive => Winding::Ccw,
+            Sign::Negative => Winding::Cw,
+            Sign::Zero => unreachable!(
+                "Points don't form a triangle, but this was verified in the \
+                constructor."
+            ),
         }
-        if orient2d > 0. {
-            return Winding::Ccw;
-        }
-
-        unreachable!(
-            "Points don't form a triangle, but this was verified in the \
-            constructor."
-        )
     }
 }
 
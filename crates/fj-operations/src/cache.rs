@@ -0,0 +1,496 @@
+//! Reuse of kernel objects across rebuilds of a model
+//!
+//! See [`BrepCache`].
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use fj_kernel::{
+    algorithms::approx::{edge::EdgeCache, Tolerance},
+    objects::{FaceSet, Sketch, Solid},
+};
+
+use crate::{rib::make_sweep, thread::make_cylinder};
+
+/// Caches the boundary representations of shapes between rebuilds
+///
+/// Shapes are compared by value. If a model is rebuilt, and one of its shapes
+/// is unchanged (including all the shapes it is made of), the boundary
+/// representation from the previous build is reused, instead of being computed
+/// again. As a result, the kernel objects of that shape stay the same, which
+/// also allows their approximations to be reused.
+///
+/// Approximations are kept for a few different tolerance values, so switching
+/// between levels of detail doesn't require approximating everything again.
+///
+/// Entries that have not been used during a build are evicted at its end. See
+/// [`BrepCache::finish_build`].
+///
+/// Within a build, the cache also keeps track of the sweeps that have been
+/// computed, so identical sweeps (as they are common in patterns) don't need
+/// to be computed again. See [`BrepCache::get_sweep`].
+#[derive(Default)]
+pub struct BrepCache {
+    shapes: Entries<fj::Shape, FaceSet>,
+    shapes_2d: Entries<fj::Shape2d, Sketch>,
+    sweeps: HashMap<u64, Vec<(fj::Sweep, Solid)>>,
+    approx: Vec<(Tolerance, EdgeCache)>,
+}
+
+impl BrepCache {
+    /// Construct an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepare the cache for a new build
+    ///
+    /// Every cache entry can be used once per build. This makes sure that
+    /// the same kernel objects don't end up in a shape twice, if it contains
+    /// multiple identical shapes.
+    pub fn begin_build(&mut self) {
+        self.shapes.begin_build();
+        self.shapes_2d.begin_build();
+        self.sweeps.clear();
+    }
+
+    /// Finish a build, evicting all entries that haven't been used
+    ///
+    /// `brep` is the boundary representation that resulted from the build.
+    /// Only the approximations of its edges are kept.
+    pub fn finish_build(&mut self, brep: &FaceSet) {
+        self.shapes.finish_build();
+        self.shapes_2d.finish_build();
+        self.sweeps.clear();
+
+        // The objects of `brep` are the only ones that can show up in the
+        // next build, so the approximations of all others are useless.
+        for (_, edges) in &mut self.approx {
+            edges.retain(brep);
+        }
+    }
+
+    /// Abort a build, evicting all entries that have been inserted during it
+    ///
+    /// Call this, if the build failed. The objects inserted during a failed
+    /// build might be invalid, and would not be validated again, if they were
+    /// reused.
+    pub fn abort_build(&mut self) {
+        self.shapes.abort_build();
+        self.shapes_2d.abort_build();
+        self.sweeps.clear();
+    }
+
+    /// Take the cached boundary representation of a shape, if available
+    pub fn get_shape(&mut self, shape: &fj::Shape) -> Option<FaceSet> {
+        let brep = self.shapes.get(shape)?;
+        self.reuse_children(shape);
+        Some(brep)
+    }
+
+    /// Insert the boundary representation of a shape
+    pub fn insert_shape(&mut self, shape: &fj::Shape, brep: FaceSet) {
+        self.shapes.insert(shape, brep);
+    }
+
+    /// Take the cached boundary representation of a 2D shape, if available
+    pub fn get_shape_2d(&mut self, shape: &fj::Shape2d) -> Option<Sketch> {
+        let brep = self.shapes_2d.get(shape)?;
+        self.reuse_children_2d(shape);
+        Some(brep)
+    }
+
+    /// Insert the boundary representation of a 2D shape
+    pub fn insert_shape_2d(&mut self, shape: &fj::Shape2d, brep: Sketch) {
+        self.shapes_2d.insert(shape, brep);
+    }
+
+    /// Access the solid of an identical sweep, computed earlier in this build
+    ///
+    /// Unlike the other cache entries, this can be used any number of times.
+    /// The solid is part of the shape already, so the caller must not use its
+    /// objects directly, but create new ones from them.
+    pub fn get_sweep(&self, sweep: &fj::Sweep) -> Option<Solid> {
+        self.sweeps
+            .get(&fingerprint(sweep))?
+            .iter()
+            .find(|(cached, _)| cached == sweep)
+            .map(|(_, solid)| solid.clone())
+    }
+
+    /// Insert the solid that resulted from a sweep
+    pub fn insert_sweep(&mut self, sweep: &fj::Sweep, solid: Solid) {
+        self.sweeps
+            .entry(fingerprint(sweep))
+            .or_default()
+            .push((sweep.clone(), solid));
+    }
+
+    /// Take the cache for edge approximations with the given tolerance
+    ///
+    /// The cache is empty, if the tolerance hasn't been used during any of the
+    /// last few builds. Return it using [`BrepCache::return_edge_cache`].
+    pub fn take_edge_cache(&mut self, tolerance: Tolerance) -> EdgeCache {
+        let cached = self
+            .approx
+            .iter()
+            .position(|(cached, _)| *cached == tolerance);
+
+        match cached {
+            Some(i) => self.approx.remove(i).1,
+            None => EdgeCache::new(),
+        }
+    }
+
+    /// Return the cache for edge approximations after using it
+    pub fn return_edge_cache(
+        &mut self,
+        tolerance: Tolerance,
+        edges: EdgeCache,
+    ) {
+        self.approx.push((tolerance, edges));
+
+        // The most recently used approximations are at the end.
+        if self.approx.len() > MAX_TOLERANCES {
+            self.approx.remove(0);
+        }
+    }
+
+    // If a shape is reused, the shapes it is made of are not computed, and
+    // their cache entries would be evicted at the end of the build. They are
+    // still needed, once the shape changes again.
+    fn reuse_children(&mut self, shape: &fj::Shape) {
+        match shape {
+            fj::Shape::Debug(shape) => self.reuse(&shape.shape),
+            fj::Shape::Group(group) => {
+                self.reuse(&group.a);
+                self.reuse(&group.b);
+            }
+            fj::Shape::Heightmap(_) => {}
+            fj::Shape::Imprint(imprint) => {
+                self.reuse(&imprint.shape);
+                self.reuse(&imprint.tool);
+            }
+            fj::Shape::Infill(infill) => self.reuse(infill.shape()),
+            fj::Shape::RemoveFaces(shape) => self.reuse(&shape.shape),
+            fj::Shape::Rib(rib) => match rib.target() {
+                // The height of a rib that extends up to a target depends on
+                // the target's boundary representation, so its sweep can't be
+                // reconstructed here. It's computed again when needed.
+                Some(target) => self.reuse(target),
+                None => self.reuse(&make_sweep(rib, rib.height()).into()),
+            },
+            fj::Shape::Scale(scale) => self.reuse(&scale.shape),
+            fj::Shape::Shape2d(shape) => self.reuse_2d(shape),
+            fj::Shape::Sweep(sweep) => self.reuse_2d(sweep.shape()),
+            fj::Shape::Texture(texture) => self.reuse(texture.shape()),
+            fj::Shape::Thread(thread) => match thread.representation {
+                fj::ThreadRepresentation::Cosmetic => {
+                    self.reuse(&make_cylinder(thread).into());
+                }
+                fj::ThreadRepresentation::Modeled => {}
+            },
+            fj::Shape::Transform(transform) => self.reuse(&transform.shape),
+        }
+    }
+
+    fn reuse_children_2d(&mut self, shape: &fj::Shape2d) {
+        match shape {
+            fj::Shape2d::Difference(difference) => {
+                for shape in difference.shapes() {
+                    self.reuse_2d(shape);
+                }
+            }
+            fj::Shape2d::Sketch(_) => {}
+        }
+    }
+
+    fn reuse(&mut self, shape: &fj::Shape) {
+        if self.shapes.mark_used(shape).is_some() {
+            self.reuse_children(shape);
+        }
+    }
+
+    fn reuse_2d(&mut self, shape: &fj::Shape2d) {
+        if self.shapes_2d.mark_used(shape).is_some() {
+            self.reuse_children_2d(shape);
+        }
+    }
+}
+
+// Sweeps can't be hashed, as they contain floating-point numbers. Hashing the
+// bits of those numbers instead is consistent with comparing them, as long as
+// `-0.0` is treated like `0.0`.
+fn fingerprint(sweep: &fj::Sweep) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    hash_floats(sweep.path(), &mut hasher);
+    hash_floats(sweep.start(), &mut hasher);
+    hash_shape_2d(sweep.shape(), &mut hasher);
+
+    hasher.finish()
+}
+
+fn hash_shape_2d(shape: &fj::Shape2d, hasher: &mut impl Hasher) {
+    match shape {
+        fj::Shape2d::Difference(difference) => {
+            0u8.hash(hasher);
+            for shape in difference.shapes() {
+                hash_shape_2d(shape, hasher);
+            }
+        }
+        fj::Shape2d::Sketch(sketch) => {
+            1u8.hash(hasher);
+            sketch.color().hash(hasher);
+
+            match sketch.chain() {
+                fj::Chain::Circle(circle) => {
+                    hash_floats([circle.radius()], hasher);
+                }
+                fj::Chain::PolyChain(poly_chain) => {
+                    for segment in poly_chain.to_segments() {
+                        hash_floats(segment.endpoint, hasher);
+                        if let fj::SketchSegmentRoute::Arc { angle } =
+                            segment.route
+                        {
+                            hash_floats([angle.rad()], hasher);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn hash_floats<const N: usize>(floats: [f64; N], hasher: &mut impl Hasher) {
+    for float in floats {
+        // Adding zero turns `-0.0` into `0.0`, and leaves everything else
+        // unchanged.
+        (float + 0.).to_bits().hash(hasher);
+    }
+}
+
+/// The number of tolerance values for which approximations are kept
+const MAX_TOLERANCES: usize = 4;
+
+struct Entries<K, V> {
+    entries: Vec<Entry<K, V>>,
+}
+
+impl<K, V> Entries<K, V>
+where
+    K: Clone + PartialEq,
+    V: Clone,
+{
+    fn begin_build(&mut self) {
+        for entry in &mut self.entries {
+            entry.used = false;
+            entry.inserted = false;
+        }
+    }
+
+    fn abort_build(&mut self) {
+        self.entries.retain(|entry| !entry.inserted);
+    }
+
+    fn finish_build(&mut self) {
+        self.entries.retain(|entry| entry.used);
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.mark_used(key).map(|entry| entry.value.clone())
+    }
+
+    fn mark_used(&mut self, key: &K) -> Option<&Entry<K, V>> {
+        // Shapes can't be hashed, as they contain floating point numbers. A
+        // linear search is fast enough for models of a realistic size.
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| !entry.used && entry.key == *key)?;
+
+        entry.used = true;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: &K, value: V) {
+        self.entries.push(Entry {
+            key: key.clone(),
+            value,
+            used: true,
+            inserted: true,
+        });
+    }
+}
+
+// This needs to be a manual implementation. Deriving `Default` would require
+// `K` and `V` to be `Default` as well, even though that is not necessary.
+impl<K, V> Default for Entries<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    used: bool,
+    inserted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::{
+        algorithms::bounding_volume::BoundingVolume,
+        objects::{FaceSet, Provenance},
+        services::Services,
+        storage::ObjectId,
+    };
+
+    use crate::Shape;
+
+    use super::BrepCache;
+
+    #[test]
+    fn unchanged_shapes_are_reused() {
+        let shape = fj::Shape::from(square().sweep([0., 0., 1.]));
+        let mut cache = BrepCache::new();
+
+        let first = build(&shape, &mut cache);
+        cache.finish_build(&first);
+        let second = build(&shape, &mut cache);
+
+        assert_eq!(ids(&first), ids(&second));
+    }
+
+    #[test]
+    fn unused_shapes_are_evicted() {
+        let [a, b] =
+            [1., 2.].map(|z| fj::Shape::from(square().sweep([0., 0., z])));
+        let mut cache = BrepCache::new();
+
+        let first = build(&a, &mut cache);
+        cache.finish_build(&first);
+        let other = build(&b, &mut cache);
+        cache.finish_build(&other);
+        let second = build(&a, &mut cache);
+
+        assert!(ids(&first).is_disjoint(&ids(&second)));
+    }
+
+    #[test]
+    fn aborted_builds_are_discarded() {
+        let shape = fj::Shape::from(square().sweep([0., 0., 1.]));
+        let mut cache = BrepCache::new();
+
+        let first = build(&shape, &mut cache);
+        cache.abort_build();
+        let second = build(&shape, &mut cache);
+
+        assert!(ids(&first).is_disjoint(&ids(&second)));
+    }
+
+    #[test]
+    fn identical_sweeps_are_reused() {
+        let sweep = square().sweep([0., 0., 1.]);
+        let shape = fj::Shape::from(
+            sweep
+                .translate([2., 0., 0.])
+                .group(&sweep.translate([4., 0., 0.])),
+        );
+
+        let mut services = Services::new();
+        let mut cache = BrepCache::new();
+        cache.begin_build();
+        let faces = shape.compute_brep(
+            &mut services.objects,
+            &mut cache,
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+
+        // Both copies are made of their own faces.
+        assert_eq!(faces.into_iter().count(), 12);
+
+        // Sweeps are compared by value. `-0.0` is equal to `0.0`.
+        assert!(cache.get_sweep(&square().sweep([-0., 0., 1.])).is_some());
+        assert!(cache.get_sweep(&square().sweep([0., 0., 2.])).is_none());
+    }
+
+    #[test]
+    fn reused_sweeps_match_computed_sweeps() {
+        let sweep = square().sweep([0., 0., 1.]);
+        let pattern =
+            [2., 4., 6.].map(|x| fj::Shape::from(sweep.translate([x, 0., 0.])));
+
+        let describe = |reuse: bool| {
+            let mut services = Services::new();
+            let mut cache = BrepCache::new();
+            cache.begin_build();
+
+            let mut faces = Vec::new();
+            for shape in &pattern {
+                if !reuse {
+                    cache = BrepCache::new();
+                }
+
+                let brep = shape.compute_brep(
+                    &mut services.objects,
+                    &mut cache,
+                    &mut DebugInfo::new(),
+                    &CancellationToken::new(),
+                );
+                faces.extend(brep);
+            }
+
+            // Faces are described by their geometry, and by the operations
+            // they were created in.
+            let mut descriptions = faces
+                .into_iter()
+                .map(|face| {
+                    let operations = services
+                        .objects
+                        .provenance(face.id())
+                        .unwrap()
+                        .chain()
+                        .map(Provenance::operation)
+                        .collect::<Vec<_>>();
+                    (face.aabb(), face.interiors().count(), operations)
+                })
+                .collect::<Vec<_>>();
+            descriptions.sort();
+            descriptions
+        };
+
+        assert_eq!(describe(true), describe(false));
+    }
+
+    fn build(shape: &fj::Shape, cache: &mut BrepCache) -> FaceSet {
+        // Like the shape processor, use new services for every build.
+        let mut services = Services::new();
+
+        cache.begin_build();
+        shape.compute_brep(
+            &mut services.objects,
+            cache,
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        )
+    }
+
+    fn ids(faces: &FaceSet) -> BTreeSet<ObjectId> {
+        faces.into_iter().map(|face| face.id()).collect()
+    }
+
+    fn square() -> fj::Sketch {
+        [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].sketch()
+    }
+}
@@ -0,0 +1,218 @@
+use fj_interop::{
+    cancellation::CancellationToken,
+    debug::{DebugInfo, DebugLabel, FaceDebugInfo},
+    mesh::{Color, FaceId, Mesh},
+};
+use fj_kernel::{
+    algorithms::{
+        approx::shape::ShapeApprox, bounding_volume::BoundingVolume,
+        intersect::FaceFaceIntersection,
+    },
+    objects::{Face, FaceSet, Objects},
+    services::Service,
+    storage::Handle,
+};
+use fj_math::{Aabb, Point, Segment, Transform};
+
+use super::{cache::BrepCache, scale, transform, Shape};
+
+impl Shape for fj::WithDebug {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        // The debug geometry is added to `debug_info` separately, by
+        // `add_debug_geometry`. It doesn't need the boundary representation,
+        // and must be added, even if this shape is taken from the cache.
+        self.shape
+            .compute_brep(objects, cache, debug_info, cancellation)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        self.shape.bounding_volume()
+    }
+}
+
+/// Add the debug geometry within a shape to the debug info
+///
+/// Debug geometry is transformed along with the shapes that it is attached to.
+pub fn add_debug_geometry(shape: &fj::Shape, debug_info: &mut DebugInfo) {
+    add_transformed(shape, &Transform::identity(), debug_info);
+}
+
+fn add_transformed(
+    shape: &fj::Shape,
+    parent: &Transform,
+    debug_info: &mut DebugInfo,
+) {
+    match shape {
+        fj::Shape::Debug(shape) => {
+            for element in shape.geometry.elements() {
+                add_element(element, parent, debug_info);
+            }
+            add_transformed(&shape.shape, parent, debug_info);
+        }
+        fj::Shape::Group(group) => {
+            add_transformed(&group.a, parent, debug_info);
+            add_transformed(&group.b, parent, debug_info);
+        }
+        fj::Shape::Imprint(shape) => {
+            add_transformed(&shape.shape, parent, debug_info);
+        }
+        fj::Shape::Infill(shape) => {
+            add_transformed(shape.shape(), parent, debug_info);
+        }
+        fj::Shape::RemoveFaces(shape) => {
+            add_transformed(&shape.shape, parent, debug_info);
+        }
+        fj::Shape::Scale(shape) => {
+            let transform = *parent * scale::make_transform(shape);
+            add_transformed(&shape.shape, &transform, debug_info);
+        }
+        fj::Shape::Texture(shape) => {
+            add_transformed(shape.shape(), parent, debug_info);
+        }
+        fj::Shape::Transform(shape) => {
+            let transform = *parent * transform::make_transform(shape);
+            add_transformed(&shape.shape, &transform, debug_info);
+        }
+
+        // These are made from 2D shapes, images, or dimensions, which can't
+        // have debug geometry.
+        fj::Shape::Heightmap(_)
+        | fj::Shape::Rib(_)
+        | fj::Shape::Shape2d(_)
+        | fj::Shape::Sweep(_)
+        | fj::Shape::Thread(_) => {}
+    }
+}
+
+fn add_element(
+    element: &fj::DebugElement,
+    transform: &Transform,
+    debug_info: &mut DebugInfo,
+) {
+    let point = |point: [f64; 3]| transform.transform_point(&point.into());
+
+    match element {
+        fj::DebugElement::Point { position, color } => {
+            debug_info.points.push((point(*position), Color(color.0)));
+        }
+        fj::DebugElement::Line { points, color } => {
+            let segment = Segment::from_points(points.map(point));
+            debug_info.lines.push((segment, Color(color.0)));
+        }
+        fj::DebugElement::Label {
+            position,
+            text,
+            color,
+        } => {
+            debug_info.labels.push(DebugLabel {
+                position: point(*position),
+                text: text.to_string(),
+                color: Color(color.0),
+            });
+        }
+    }
+}
+
+/// Add the intermediate results of the kernel's algorithms to the debug info
+///
+/// Collects the approximation and triangulation of every face, as well as its
+/// intersections with all other faces. `mesh` is expected to be the result of
+/// triangulating `approx`, before any welding or simplification.
+///
+/// This is expensive, as every face is intersected with every other face that
+/// it could possibly intersect with.
+pub fn add_algorithm_debug_info(
+    faces: &[Handle<Face>],
+    approx: &ShapeApprox,
+    mesh: &Mesh<Point<3>>,
+    debug_info: &mut DebugInfo,
+) {
+    for face in approx.faces() {
+        let points = face.points().into_iter().map(|point| point.global_form);
+        face_debug_info(face.face, debug_info)
+            .approx_points
+            .extend(points);
+    }
+
+    for triangle in mesh.triangles() {
+        if let Some(face) = triangle.face {
+            face_debug_info(face, debug_info)
+                .triangles
+                .push(triangle.inner);
+        }
+    }
+
+    let aabbs = faces.iter().map(|face| face.aabb()).collect::<Vec<_>>();
+
+    for (i, a) in faces.iter().enumerate() {
+        for (j, b) in faces.iter().enumerate().skip(i + 1) {
+            let (Some(aabb_a), Some(aabb_b)) = (aabbs[i], aabbs[j]) else {
+                continue;
+            };
+            if !aabb_a.intersects(&aabb_b) {
+                continue;
+            }
+
+            let segments = intersection_segments([a, b]);
+            for face in [a, b] {
+                face_debug_info(face.id().into(), debug_info)
+                    .intersections
+                    .extend(segments.iter().copied());
+            }
+        }
+    }
+}
+
+fn face_debug_info(
+    face: FaceId,
+    debug_info: &mut DebugInfo,
+) -> &mut FaceDebugInfo {
+    debug_info.faces.entry(face).or_default()
+}
+
+/// Approximate the intersection of two faces with line segments
+///
+/// This is for display only, so the intersection is sampled at regular
+/// intervals, instead of respecting any tolerance.
+fn intersection_segments(faces: [&Handle<Face>; 2]) -> Vec<Segment<3>> {
+    const NUM_SEGMENTS: u32 = 32;
+
+    let [a, b] = faces;
+    let Some(intersection) = FaceFaceIntersection::compute([a, b]) else {
+        return Vec::new();
+    };
+
+    // Both curves represent the same global curve, so one is enough.
+    let [curve, _] = intersection.intersection_curves;
+    let surface = a.surface().geometry();
+
+    let mut segments = Vec::new();
+    for interval in intersection.intersection_intervals.intervals {
+        let points = (0..=NUM_SEGMENTS)
+            .map(|i| {
+                let t = f64::from(i) / f64::from(NUM_SEGMENTS);
+                let point =
+                    interval.start + (interval.end - interval.start) * t;
+                surface.point_from_surface_coords(
+                    curve.point_from_path_coords(point),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        segments.extend(
+            points
+                .windows(2)
+                .map(|points| Segment::from_points([points[0], points[1]])),
+        );
+    }
+
+    segments
+}
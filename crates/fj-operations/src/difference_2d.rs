@@ -1,6 +1,9 @@
 use std::ops::Deref;
 
-use fj_interop::{debug::DebugInfo, ext::ArrayExt, mesh::Color};
+use fj_interop::{
+    cancellation::CancellationToken, debug::DebugInfo, ext::ArrayExt,
+    mesh::Color,
+};
 use fj_kernel::{
     algorithms::reverse::Reverse,
     objects::{Face, Objects, Sketch},
@@ -9,7 +12,7 @@ use fj_kernel::{
 };
 use fj_math::Aabb;
 
-use super::Shape;
+use super::{cache::BrepCache, Shape};
 
 impl Shape for fj::Difference2d {
     type Brep = Sketch;
@@ -17,7 +20,9 @@ impl Shape for fj::Difference2d {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
         // This method assumes that `b` is fully contained within `a`:
         // https://github.com/hannobraun/Fornjot/issues/92
@@ -27,10 +32,9 @@ impl Shape for fj::Difference2d {
         let mut exteriors = Vec::new();
         let mut interiors = Vec::new();
 
-        let [a, b] = self
-            .shapes()
-            .each_ref_ext()
-            .map(|shape| shape.compute_brep(objects, debug_info));
+        let [a, b] = self.shapes().each_ref_ext().map(|shape| {
+            shape.compute_brep(objects, cache, debug_info, cancellation)
+        });
 
         if let Some(face) = a.faces().into_iter().next() {
             // If there's at least one face to subtract from, we can proceed.
@@ -44,12 +48,14 @@ impl Shape for fj::Difference2d {
                     "Trying to subtract faces with different surfaces.",
                 );
 
+                // The holes in `a` stay holes. They already have the winding
+                // that interior cycles require.
                 exteriors.push(face.exterior().clone());
-                for cycle in face.interiors() {
-                    interiors.push(cycle.clone().reverse(objects));
-                }
+                interiors.extend(face.interiors().cloned());
             }
 
+            let mut islands = Vec::new();
+
             for face in b.faces() {
                 assert_eq!(
                     surface,
@@ -58,6 +64,12 @@ impl Shape for fj::Difference2d {
                 );
 
                 interiors.push(face.exterior().clone().reverse(objects));
+
+                // Whatever is within a hole in `b` is not subtracted from `a`,
+                // which leaves it as a separate face.
+                for cycle in face.interiors() {
+                    islands.push(cycle.clone().reverse(objects));
+                }
             }
 
             // Faces only support one exterior, while the code here comes from
@@ -84,6 +96,16 @@ impl Shape for fj::Difference2d {
                 Some(Color(self.color())),
             );
             faces.push(face.insert(objects));
+
+            for exterior in islands {
+                let face = Face::new(
+                    surface.clone(),
+                    exterior,
+                    [],
+                    Some(Color(self.color())),
+                );
+                faces.push(face.insert(objects));
+            }
         }
 
         let difference = Sketch::new(faces).insert(objects);
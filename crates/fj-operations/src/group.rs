@@ -1,11 +1,11 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
 use fj_kernel::{
     objects::{FaceSet, Objects},
     services::Service,
 };
 use fj_math::Aabb;
 
-use super::Shape;
+use super::{cache::BrepCache, Shape};
 
 impl Shape for fj::Group {
     type Brep = FaceSet;
@@ -13,12 +13,18 @@ impl Shape for fj::Group {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
         let mut faces = FaceSet::new();
 
-        let a = self.a.compute_brep(objects, debug_info);
-        let b = self.b.compute_brep(objects, debug_info);
+        let a = self
+            .a
+            .compute_brep(objects, cache, debug_info, cancellation);
+        let b = self
+            .b
+            .compute_brep(objects, cache, debug_info, cancellation);
 
         faces.extend(a);
         faces.extend(b);
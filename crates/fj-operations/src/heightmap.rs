@@ -0,0 +1,117 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    objects::{FaceSet, Objects, Shell},
+    operations::BuildShell,
+    services::Service,
+};
+use fj_math::{Aabb, Point};
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Heightmap {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        _: &mut BrepCache,
+        _: &mut DebugInfo,
+        _: &CancellationToken,
+    ) -> Self::Brep {
+        let (points, triangles) = mesh(self);
+        Shell::from_triangles(points, triangles, objects)
+            .faces()
+            .clone()
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let [x, y] = self.size();
+        let max = self.samples().into_iter().fold(0., f64::max);
+
+        Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([x, y, self.base() + self.relief() * max]),
+        }
+    }
+}
+
+/// Compute the closed triangle mesh that bounds a heightmap
+///
+/// The top surface has one vertex per sample, and two triangles between each
+/// four neighboring samples. The bottom is a fan around its center, and the
+/// sides connect the outermost samples with the bottom. All triangles wind
+/// counter-clockwise, as seen from outside.
+fn mesh(heightmap: &fj::Heightmap) -> (Vec<[f64; 3]>, Vec<[usize; 3]>) {
+    let width = heightmap.width();
+    let height = heightmap.height();
+    let [size_x, size_y] = heightmap.size();
+
+    assert!(
+        size_x > 0. && size_y > 0. && heightmap.base() > 0.,
+        "Heightmap must have a positive size and base"
+    );
+
+    let mut points = Vec::new();
+    let mut triangles = Vec::new();
+
+    // The first row of samples is along the top edge of the image, so it ends
+    // up at the largest y-coordinate.
+    let xy = |row: usize, column: usize| {
+        let x = size_x * column as f64 / (width - 1) as f64;
+        let y = size_y * (height - 1 - row) as f64 / (height - 1) as f64;
+        [x, y]
+    };
+
+    for (i, sample) in heightmap.samples().into_iter().enumerate() {
+        let [x, y] = xy(i / width, i % width);
+        let z = heightmap.base() + heightmap.relief() * sample;
+        points.push([x, y, z]);
+    }
+
+    for row in 0..height - 1 {
+        for column in 0..width - 1 {
+            let top_left = row * width + column;
+            let top_right = top_left + 1;
+            let bottom_left = top_left + width;
+            let bottom_right = bottom_left + 1;
+
+            triangles.push([bottom_left, bottom_right, top_right]);
+            triangles.push([bottom_left, top_right, top_left]);
+        }
+    }
+
+    // The outermost samples, counter-clockwise as seen from above, starting
+    // at the origin.
+    let boundary = (0..width - 1)
+        .map(|column| (height - 1, column))
+        .chain((1..height).rev().map(|row| (row, width - 1)))
+        .chain((1..width).rev().map(|column| (0, column)))
+        .chain((0..height - 1).map(|row| (row, 0)))
+        .collect::<Vec<_>>();
+
+    let first_bottom = points.len();
+    for &(row, column) in &boundary {
+        let [x, y] = xy(row, column);
+        points.push([x, y, 0.]);
+    }
+
+    let center = points.len();
+    points.push([size_x / 2., size_y / 2., 0.]);
+
+    for i in 0..boundary.len() {
+        let j = (i + 1) % boundary.len();
+
+        let (top_i, top_j) = {
+            let [(row_i, column_i), (row_j, column_j)] =
+                [boundary[i], boundary[j]];
+            (row_i * width + column_i, row_j * width + column_j)
+        };
+        let (bottom_i, bottom_j) = (first_bottom + i, first_bottom + j);
+
+        triangles.push([bottom_i, bottom_j, top_j]);
+        triangles.push([bottom_i, top_j, top_i]);
+        triangles.push([center, bottom_j, bottom_i]);
+    }
+
+    (points, triangles)
+}
@@ -0,0 +1,85 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    algorithms::imprint::Imprint,
+    objects::{FaceSet, Objects, Shell},
+    operations::Insert,
+    services::Service,
+};
+use fj_math::Aabb;
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Imprint {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        let faces =
+            self.shape
+                .compute_brep(objects, cache, debug_info, cancellation);
+        let tool =
+            self.tool
+                .compute_brep(objects, cache, debug_info, cancellation);
+
+        let shell = Shell::new(faces).insert(objects);
+        let tool = Shell::new(tool).insert(objects);
+        shell
+            .imprint(&tool, objects)
+            .unwrap_or_else(|err| panic!("Failed to imprint shape: {err}"))
+            .faces()
+            .clone()
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // Imprinting splits faces, but doesn't change the shape.
+        self.shape.bounding_volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::services::Services;
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn imprint_box_resting_on_plate() {
+        let plate = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]]
+            .sketch()
+            .sweep([0., 0., 1.]);
+        let tool = [[1., 1.], [2., 1.], [2., 2.], [1., 2.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .translate([0., 0., 1.]);
+        let plate = fj::Imprint {
+            shape: plate.into(),
+            tool: tool.into(),
+        };
+
+        let mut services = Services::new();
+        let faces = plate.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+
+        // The top face of the plate is split into the contact area, and the
+        // area around it.
+        let with_hole = (&faces)
+            .into_iter()
+            .filter(|face| face.interiors().count() == 1)
+            .count();
+        assert_eq!(with_hole, 1);
+        assert_eq!(faces.into_iter().count(), 7);
+
+        services.drop_and_validate().unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use fj_interop::{
+    cancellation::CancellationToken, debug::DebugInfo, mesh::Color,
+};
+use fj_kernel::{
+    algorithms::lattice::{infill, Lattice},
+    objects::{FaceSet, Objects, Shell},
+    operations::BuildShell,
+    services::Service,
+};
+use fj_math::{Aabb, Scalar};
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Infill {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        let faces =
+            self.shape()
+                .compute_brep(objects, cache, debug_info, cancellation);
+
+        let lattice = match self.lattice() {
+            fj::Lattice::Grid {
+                cell_size,
+                thickness,
+            } => Lattice::Grid {
+                cell_size: Scalar::from(cell_size),
+                thickness: Scalar::from(thickness),
+            },
+            fj::Lattice::Gyroid {
+                cell_size,
+                thickness,
+            } => Lattice::Gyroid {
+                cell_size: Scalar::from(cell_size),
+                thickness: Scalar::from(thickness),
+            },
+        };
+
+        let mesh =
+            infill(&faces, &lattice, self.resolution(), Color::default());
+
+        let indices = mesh.indices().collect::<Vec<_>>();
+        let triangles = indices.chunks(3).map(|triangle| {
+            [triangle[0], triangle[1], triangle[2]].map(|index| index as usize)
+        });
+
+        Shell::from_triangles(mesh.vertices(), triangles, objects)
+            .faces()
+            .clone()
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // The lattice is clipped by the shape.
+        self.shape().bounding_volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::{
+        algorithms::bounding_volume::BoundingVolume, services::Services,
+    };
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn infill_cube() {
+        let cube = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.]);
+
+        let lattice = fj::Lattice::Grid {
+            cell_size: 0.5,
+            thickness: 0.1,
+        };
+        let infill = fj::Infill::new(cube, lattice, 4).unwrap();
+
+        let mut services = Services::new();
+        let faces = infill.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+        assert!((&faces).into_iter().next().is_some());
+
+        let aabb = faces.aabb().unwrap();
+        let epsilon = 1e-9;
+        for c in aabb.min.coords.components {
+            assert!(c.into_f64() > -epsilon);
+        }
+        for c in aabb.max.coords.components {
+            assert!(c.into_f64() < 1. + epsilon);
+        }
+    }
+}
@@ -16,17 +16,29 @@
 
 #![warn(missing_docs)]
 
+pub mod cache;
 pub mod shape_processor;
 
+mod debug;
 mod difference_2d;
 mod group;
+mod heightmap;
+mod imprint;
+mod infill;
+mod remove_faces;
+mod rib;
+mod scale;
+mod selection;
 mod sketch;
 mod sweep;
+mod texture;
+mod thread;
 mod transform;
 
-use fj_interop::debug::DebugInfo;
+use cache::BrepCache;
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
 use fj_kernel::{
-    objects::{FaceSet, Objects, Sketch},
+    objects::{FaceSet, Objects, Provenance, Sketch},
     services::Service,
 };
 use fj_math::Aabb;
@@ -37,10 +49,20 @@ pub trait Shape {
     type Brep;
 
     /// Compute the boundary representation of the shape
+    ///
+    /// Shapes that are unchanged since the last build are taken from `cache`,
+    /// which includes all objects they are made of. Debug information is not
+    /// generated again for those.
+    ///
+    /// Once `cancellation` has been cancelled, the remaining shapes are
+    /// skipped, and the returned boundary representation is incomplete. The
+    /// caller must check for that, and discard the result.
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep;
 
     /// Access the axis-aligned bounding box of a shape
@@ -56,31 +78,116 @@ impl Shape for fj::Shape {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
-        match self {
-            Self::Shape2d(shape) => {
-                shape.compute_brep(objects, debug_info).faces().clone()
+        if let Some(brep) = cache.get_shape(self) {
+            return brep;
+        }
+        if cancellation.is_cancelled() {
+            return FaceSet::new();
+        }
+
+        let brep = match self {
+            Self::Debug(shape) => {
+                shape.compute_brep(objects, cache, debug_info, cancellation)
             }
-            Self::Group(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sweep(shape) => shape
-                .compute_brep(objects, debug_info)
-                .shells()
-                .map(|shell| shell.faces().clone())
-                .reduce(|mut a, b| {
-                    a.extend(b);
-                    a
+            Self::Scale(shape) => objects.with_provenance(
+                Provenance::new("scale", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Shape2d(shape) => shape
+                .compute_brep(objects, cache, debug_info, cancellation)
+                .faces()
+                .clone(),
+            Self::Group(shape) => objects.with_provenance(
+                Provenance::new("group", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Heightmap(shape) => objects.with_provenance(
+                Provenance::new("heightmap", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Imprint(shape) => objects.with_provenance(
+                Provenance::new("imprint", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Infill(shape) => objects.with_provenance(
+                Provenance::new("infill", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::RemoveFaces(shape) => objects.with_provenance(
+                Provenance::new("remove faces", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Rib(shape) => {
+                objects.with_provenance(Provenance::new("rib", []), |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
                 })
-                .unwrap_or_default(),
-            Self::Transform(shape) => shape.compute_brep(objects, debug_info),
-        }
+            }
+            Self::Sweep(shape) => objects.with_provenance(
+                Provenance::new("sweep", []),
+                |objects| {
+                    let solid = shape.compute_brep(
+                        objects,
+                        cache,
+                        debug_info,
+                        cancellation,
+                    );
+                    sweep::faces(&solid)
+                },
+            ),
+            Self::Texture(shape) => objects.with_provenance(
+                Provenance::new("texture", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Thread(shape) => objects.with_provenance(
+                Provenance::new("thread", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Transform(shape) => objects.with_provenance(
+                Provenance::new("transform", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+        };
+
+        cache.insert_shape(self, brep.clone());
+        brep
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
+            Self::Debug(shape) => shape.bounding_volume(),
+            Self::Scale(shape) => shape.bounding_volume(),
             Self::Shape2d(shape) => shape.bounding_volume(),
             Self::Group(shape) => shape.bounding_volume(),
+            Self::Heightmap(shape) => shape.bounding_volume(),
+            Self::Imprint(shape) => shape.bounding_volume(),
+            Self::Infill(shape) => shape.bounding_volume(),
+            Self::RemoveFaces(shape) => shape.bounding_volume(),
+            Self::Rib(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
+            Self::Texture(shape) => shape.bounding_volume(),
+            Self::Thread(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
         }
     }
@@ -92,12 +199,34 @@ impl Shape for fj::Shape2d {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
-        match self {
-            Self::Difference(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sketch(shape) => shape.compute_brep(objects, debug_info),
+        if let Some(brep) = cache.get_shape_2d(self) {
+            return brep;
         }
+        if cancellation.is_cancelled() {
+            return Sketch::new([]);
+        }
+
+        let brep = match self {
+            Self::Difference(shape) => objects.with_provenance(
+                Provenance::new("difference", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+            Self::Sketch(shape) => objects.with_provenance(
+                Provenance::new("sketch", []),
+                |objects| {
+                    shape.compute_brep(objects, cache, debug_info, cancellation)
+                },
+            ),
+        };
+
+        cache.insert_shape_2d(self, brep.clone());
+        brep
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -0,0 +1,83 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    algorithms::remove_faces::RemoveFaces,
+    objects::{FaceSet, Objects, Shell},
+    operations::Insert,
+    services::Service,
+};
+use fj_math::Aabb;
+
+use super::{cache::BrepCache, selection::is_selected, Shape};
+
+impl Shape for fj::RemoveFaces {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        let faces =
+            self.shape
+                .compute_brep(objects, cache, debug_info, cancellation);
+
+        let removed = (&faces)
+            .into_iter()
+            .filter(|face| is_selected(&self.faces, face))
+            .cloned()
+            .collect::<Vec<_>>();
+        if removed.is_empty() {
+            return faces;
+        }
+
+        let shell = Shell::new(faces).insert(objects);
+        shell
+            .remove_faces(&removed, objects)
+            .unwrap_or_else(|err| panic!("Failed to remove faces: {err}"))
+            .faces()
+            .clone()
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // Healing the opening doesn't extend past the removed faces, so the
+        // shape doesn't get any larger.
+        self.shape.bounding_volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::services::Services;
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn remove_wall_of_hole() {
+        let plate = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]].sketch();
+        let hole = fj::Hole::new([2., 2.], 1.);
+        let plate = fj::RemoveFaces {
+            shape: plate.with_hole(&hole).sweep([0., 0., 1.]).into(),
+            faces: fj::FaceSelection::Curved,
+        };
+
+        let mut services = Services::new();
+        let faces = plate.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+
+        // What's left is a box, without the hole in its top and bottom.
+        for face in &faces {
+            assert_eq!(face.interiors().count(), 0);
+        }
+        assert_eq!(faces.into_iter().count(), 6);
+
+        services.drop_and_validate().unwrap();
+    }
+}
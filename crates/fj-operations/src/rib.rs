@@ -0,0 +1,230 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    algorithms::{
+        approx::Tolerance, bounding_volume::BoundingVolume,
+        triangulate::Triangulate,
+    },
+    objects::{FaceSet, Objects},
+    services::Service,
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+use itertools::Itertools;
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Rib {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        let height = match self.target() {
+            Some(target) => {
+                let target = target.compute_brep(
+                    objects,
+                    cache,
+                    debug_info,
+                    cancellation,
+                );
+                height_up_to(self, &target)
+            }
+            None => self.height(),
+        };
+
+        // The rib is just a sweep of its outline. Going through `fj::Shape`
+        // instead of `fj::Sweep` directly, records that in the provenance of
+        // the resulting objects.
+        fj::Shape::from(make_sweep(self, height)).compute_brep(
+            objects,
+            cache,
+            debug_info,
+            cancellation,
+        )
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // If the rib extends up to a target, it might end up lower than its
+        // maximum height. That's fine, as the bounding volume is allowed to be
+        // larger than the shape.
+        make_sweep(self, self.height()).bounding_volume()
+    }
+}
+
+pub(crate) fn make_sweep(rib: &fj::Rib, height: f64) -> fj::Sweep {
+    let sketch = fj::Sketch::from_points(outline(rib))
+        .expect("Outline of rib has at least four points");
+    fj::Sweep::from_path(sketch.into(), [0., 0., height])
+}
+
+/// Compute the height of a rib that extends up to the next faces of `target`
+///
+/// Casts rays along the z-axis, from points on the outline and profile of the
+/// rib, against a triangulation of `target`. The rib ends where the first of
+/// them hits, or at its maximum height, if none of them do.
+fn height_up_to(rib: &fj::Rib, target: &FaceSet) -> f64 {
+    let Some(aabb) = target.aabb() else {
+        return rib.height();
+    };
+
+    // Hits closer than the tolerance are considered to be on the faces that
+    // the rib starts from, not faces that it extends up to.
+    let min_extent = aabb
+        .size()
+        .components
+        .into_iter()
+        .filter(|extent| *extent > Scalar::ZERO)
+        .min()
+        .unwrap_or(Scalar::ONE);
+    let tolerance = Tolerance::from_scalar(min_extent / 1000.)
+        .expect("Smallest extent is positive");
+    let mesh = (target, tolerance).triangulate();
+
+    let direction = Vector::from([0., 0., 1.]);
+
+    samples(rib)
+        .into_iter()
+        .flat_map(|point| {
+            let origin = Point::from([point.u, point.v, Scalar::ZERO]);
+            mesh.triangles().filter_map(move |triangle| {
+                triangle.inner.cast_local_ray(
+                    origin,
+                    direction,
+                    rib.height(),
+                    true,
+                )
+            })
+        })
+        .filter(|distance| *distance > tolerance.length())
+        .min()
+        .map_or(rib.height(), Scalar::into_f64)
+}
+
+/// Compute the points of a rib that rays are cast from
+///
+/// Those are the points of its outline and profile, and points along their
+/// segments that are not further than the rib's thickness apart.
+fn samples(rib: &fj::Rib) -> Vec<Point<2>> {
+    let outline = outline(rib).into_iter().map(Point::from).collect_vec();
+    let profile = rib.profile().into_iter().map(Point::from).collect_vec();
+    let spacing = Scalar::from(rib.thickness());
+
+    let outline_segments = outline.iter().copied().circular_tuple_windows();
+    let profile_segments = profile.iter().copied().tuple_windows();
+
+    outline_segments
+        .chain(profile_segments)
+        .flat_map(|(a, b): (Point<2>, Point<2>)| {
+            let num_samples = ((b - a).magnitude() / spacing).ceil();
+            let num_samples = num_samples.into_f64().max(1.) as usize;
+
+            (0..=num_samples)
+                .map(move |i| a + (b - a) * (i as f64 / num_samples as f64))
+        })
+        .collect()
+}
+
+/// Compute the closed outline of a rib, by offsetting its profile to both sides
+///
+/// The outline winds counter-clockwise.
+fn outline(rib: &fj::Rib) -> Vec<[f64; 2]> {
+    let profile = rib.profile().into_iter().map(Point::from).collect_vec();
+    let half_thickness = Scalar::from(rib.thickness() / 2.);
+
+    let normals = profile
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| {
+            let direction = (*b - *a).normalize();
+            Vector::from([-direction.v, direction.u])
+        })
+        .collect_vec();
+
+    // Compute the offset of each point of the profile. At the ends, that's
+    // just the normal of the adjacent segment. In between, the offset needs to
+    // be extended along the bisector, for the sides of the rib to keep their
+    // distance to the profile.
+    let offsets = profile.iter().enumerate().map(|(i, _)| {
+        let prev = normals[i.saturating_sub(1)];
+        let next = normals[usize::min(i, normals.len() - 1)];
+
+        let bisector = prev + next;
+        assert_ne!(
+            bisector.magnitude(),
+            Scalar::ZERO,
+            "Rib profile must not reverse direction"
+        );
+        let bisector = bisector.normalize();
+
+        bisector * (half_thickness / bisector.dot(&prev))
+    });
+
+    let (left, right): (Vec<_>, Vec<_>) = profile
+        .iter()
+        .zip(offsets)
+        .map(|(&point, offset)| (point + offset, point - offset))
+        .unzip();
+
+    right
+        .into_iter()
+        .chain(left.into_iter().rev())
+        .map(|point| point.coords.components.map(Scalar::into_f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::{
+        algorithms::bounding_volume::BoundingVolume, services::Services,
+    };
+    use fj_math::Scalar;
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn rib_without_target() {
+        let rib = rib();
+        assert_eq!(top_of(&rib), Scalar::from(10.));
+    }
+
+    #[test]
+    fn rib_up_to_target() {
+        let rib = rib().up_to(plate_at(2.));
+        assert_eq!(top_of(&rib), Scalar::from(2.));
+    }
+
+    #[test]
+    fn rib_up_to_target_out_of_reach() {
+        let rib = rib().up_to(plate_at(20.));
+        assert_eq!(top_of(&rib), Scalar::from(10.));
+    }
+
+    fn rib() -> fj::Rib {
+        fj::Rib::from_profile(vec![[-1., 0.], [1., 0.]], 0.2, 10.).unwrap()
+    }
+
+    fn plate_at(z: f64) -> fj::Shape {
+        [[-5., -5.], [5., -5.], [5., 5.], [-5., 5.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .translate([0., 0., z])
+            .into()
+    }
+
+    fn top_of(rib: &fj::Rib) -> Scalar {
+        let mut services = Services::new();
+        let faces = rib.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+
+        faces.aabb().unwrap().max.z
+    }
+}
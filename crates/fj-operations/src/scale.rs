@@ -0,0 +1,33 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    algorithms::transform::TransformObject,
+    objects::{FaceSet, Objects},
+    services::Service,
+};
+use fj_math::{Aabb, Transform};
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Scale {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        self.shape
+            .compute_brep(objects, cache, debug_info, cancellation)
+            .transform(&make_transform(self), objects)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        make_transform(self).transform_aabb(&self.shape.bounding_volume())
+    }
+}
+
+pub(crate) fn make_transform(scale: &fj::Scale) -> Transform {
+    Transform::non_uniform_scale(scale.factors)
+}
@@ -0,0 +1,30 @@
+use fj_kernel::{
+    geometry::curve::GlobalPath,
+    objects::{Face, Handedness},
+};
+use fj_math::{Scalar, Vector};
+
+/// Determine whether a face is part of a selection
+pub(crate) fn is_selected(selection: &fj::FaceSelection, face: &Face) -> bool {
+    let surface = face.surface().geometry();
+    let is_planar = matches!(surface.u, GlobalPath::Line(_));
+
+    match selection {
+        fj::FaceSelection::All => true,
+        fj::FaceSelection::Curved => !is_planar,
+        fj::FaceSelection::Planar { normal } => {
+            let normal = Vector::from(*normal);
+            if !is_planar || normal.magnitude() == Scalar::ZERO {
+                return false;
+            }
+
+            let front = match face.coord_handedness() {
+                Handedness::RightHanded => surface.normal_at([0., 0.]),
+                Handedness::LeftHanded => -surface.normal_at([0., 0.]),
+            };
+
+            // Allow for some rounding errors in the normal of the surface.
+            front.dot(&normal.normalize()) > Scalar::ONE - Scalar::from(1e-9)
+        }
+    }
+}
@@ -1,28 +1,188 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
+use std::{
+    num::NonZeroUsize,
+    panic,
+    sync::{mpsc, Arc, Mutex},
+    thread::{self, ScopedJoinHandle},
+    time::Instant,
+};
+
+use fj_interop::{
+    analysis::MassProperties,
+    cancellation::{CancellationToken, Cancelled},
+    debug::DebugInfo,
+    mesh::Mesh,
+    processed_shape::{PartialShape, ProcessedShape},
+    progress::{Progress, Stage},
+};
 use fj_kernel::{
     algorithms::{
-        approx::{InvalidTolerance, Tolerance},
+        analysis::{curvature, dimensions, mass, overhang},
+        approx::{
+            face::check_distinct_points, shape::ShapeApprox, InvalidTolerance,
+            Tolerance,
+        },
+        bounding_volume::BoundingVolume,
+        simplify,
         triangulate::Triangulate,
     },
-    services::Services,
+    objects::Objects,
+    services::{Services, Validation},
     validate::ValidationError,
 };
-use fj_math::Scalar;
+use fj_math::{Aabb, Point, Scalar, Vector};
+use tracing::debug_span;
 
-use crate::Shape as _;
+use crate::{cache::BrepCache, debug, Shape as _};
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
+#[derive(Clone)]
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
     pub tolerance: Option<Tolerance>,
+
+    /// The maximum angle, in radians, that a segment of a circle may cover
+    ///
+    /// This overrides the angle of [`ShapeProcessor::tolerance`], and applies
+    /// to the default tolerance too. Circles are approximated with at least as
+    /// many segments as this requires, and possibly more, if the length
+    /// tolerance requires that.
+    pub angle_tolerance: Option<Scalar>,
+
+    /// How to simplify the triangle mesh, if at all
+    ///
+    /// The mesh is simplified before it is analyzed, so all results refer to
+    /// the simplified mesh.
+    pub simplification: Option<simplify::Target>,
+
+    /// Whether to collect the intermediate results of the kernel's algorithms
+    ///
+    /// If this is enabled, the approximation, triangulation, and intersections
+    /// of each face are added to [`DebugInfo::faces`]. This is expensive, and
+    /// only useful for debugging the kernel.
+    pub debug_algorithms: bool,
+
+    /// Whether to guarantee identical results for identical shapes
+    ///
+    /// With this enabled, processing the same shape with the same settings
+    /// always results in the same mesh, down to the order of its triangles,
+    /// and therefore in byte-identical exports. This means that
+    /// [`ShapeProcessor::cache`] isn't used, as objects that are reused from a
+    /// previous build can end up in a different order than new ones.
+    pub deterministic: bool,
+
+    /// The kernel objects and approximations of the previous build
+    ///
+    /// Clones of a shape processor share the same cache. It can only be used
+    /// by one build at a time. Builds that run concurrently to that go without.
+    pub cache: Arc<Mutex<BrepCache>>,
 }
 
 impl ShapeProcessor {
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
     pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
-        let aabb = shape.bounding_volume();
+        self.process_with_progress(shape, &CancellationToken::new(), |_| {})
+    }
+
+    /// Process an [`fj::Shape`] into [`ProcessedShape`], reporting progress
+    ///
+    /// `progress` is called whenever a step of approximation or triangulation
+    /// has been completed. Computing the boundary representation doesn't report
+    /// progress, as the number of steps isn't known in advance.
+    ///
+    /// `cancellation` is checked regularly while the boundary representation
+    /// is computed and validated, and during approximation and triangulation.
+    /// Once it has been cancelled, processing is aborted and
+    /// [`Error::Cancelled`] is returned.
+    pub fn process_with_progress(
+        &self,
+        shape: &fj::Shape,
+        cancellation: &CancellationToken,
+        progress: impl FnMut(Progress),
+    ) -> Result<ProcessedShape, Error> {
+        self.process_incrementally(shape, cancellation, progress, |_| {})
+    }
+
+    /// Process an [`fj::Shape`] into [`ProcessedShape`], reporting partial
+    /// results
+    ///
+    /// Works like [`ShapeProcessor::process_with_progress`]. In addition,
+    /// `partial` is called with the faces that have been triangulated so far,
+    /// a few times while triangulation is going on.
+    ///
+    /// Triangulation, and the analysis of the resulting mesh, make use of all
+    /// available threads.
+    pub fn process_incrementally(
+        &self,
+        shape: &fj::Shape,
+        cancellation: &CancellationToken,
+        mut progress: impl FnMut(Progress),
+        mut partial: impl FnMut(PartialShape),
+    ) -> Result<ProcessedShape, Error> {
+        let _span = debug_span!("process_shape").entered();
+        let start = Instant::now();
+
+        // Identical surfaces are deduplicated within a tolerance, which needs
+        // to grow with the size of the model.
+        let model_size = shape
+            .bounding_volume()
+            .size()
+            .components
+            .into_iter()
+            .max()
+            .unwrap_or(Scalar::ZERO);
+        let mut services = Services::from_states(
+            Objects::with_model_size(model_size),
+            Validation::with_cancellation(cancellation.clone()),
+        );
+        let mut debug_info = DebugInfo::new();
+
+        let mut cache_guard = if self.deterministic {
+            None
+        } else {
+            self.cache.try_lock().ok()
+        };
+        let mut no_cache = BrepCache::new();
+        let cache = match &mut cache_guard {
+            Some(cache) => &mut **cache,
+            None => &mut no_cache,
+        };
+
+        cache.begin_build();
+        let brep = {
+            let _span = debug_span!("compute_brep").entered();
+            shape.compute_brep(
+                &mut services.objects,
+                cache,
+                &mut debug_info,
+                cancellation,
+            )
+        };
+        if let Err(err) = cancellation.check() {
+            cache.abort_build();
+            return Err(err.into());
+        }
+        let validation = {
+            let _span = debug_span!("validate").entered();
+            services.drop_and_validate()
+        };
+        if let Err(errors) = validation {
+            cache.abort_build();
+            let err = errors
+                .into_iter()
+                .next()
+                .expect("Expected at least one validation error")
+                .err;
+            return Err(Error::ToShape(Box::new(err)));
+        }
+        cache.finish_build(&brep);
+        debug::add_debug_geometry(shape, &mut debug_info);
+
+        // The bounding volume is computed from the exact geometry of the
+        // boundary representation. The shape's own estimate is only needed,
+        // if there is no geometry.
+        let aabb = brep.aabb().unwrap_or_else(|| shape.bounding_volume());
 
         let tolerance = match self.tolerance {
             None => {
@@ -41,20 +201,224 @@ impl ShapeProcessor {
             }
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
+        let tolerance = match self.angle_tolerance {
+            Some(angle) => tolerance.with_angle(angle)?,
+            None => tolerance,
+        };
 
-        let mut services = Services::new();
-        let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&mut services.objects, &mut debug_info);
-        let mesh = (&shape, tolerance).triangulate();
+        let faces = brep.into_iter().collect::<Vec<_>>();
+        let num_faces = faces.len();
+        let exact = mass::is_polyhedral(&faces);
 
-        Ok(ProcessedShape {
+        // Faces that have been reused from the previous build still have the
+        // same edges, which means their approximations can be reused, too.
+        let approx_span = debug_span!("approximate").entered();
+        let mut approx = ShapeApprox::new(tolerance);
+        let mut edges = cache.take_edge_cache(tolerance);
+        for (i, face) in faces.iter().enumerate() {
+            progress(Progress::new(Stage::Approximation, i, num_faces));
+            cancellation.check()?;
+            approx.insert(face, &mut edges);
+        }
+        cache.return_edge_cache(tolerance, edges);
+        drop(cache_guard);
+        drop(approx_span);
+        progress(Progress::new(Stage::Approximation, num_faces, num_faces));
+        check_distinct_points(approx.faces());
+
+        let mesh = triangulate(
+            &approx,
             aabb,
+            cancellation,
+            &mut progress,
+            &mut partial,
+        )?;
+        if self.debug_algorithms {
+            debug::add_algorithm_debug_info(
+                &faces,
+                &approx,
+                &mesh,
+                &mut debug_info,
+            );
+        }
+
+        // Faces are triangulated separately. Weld their triangles together,
+        // to close any gaps between them.
+        let mesh = {
+            let _span = debug_span!("weld").entered();
+            mesh.weld(tolerance.length() / 100.)
+        };
+
+        Ok(self.analyze(
             mesh,
+            aabb,
+            exact,
+            tolerance.length(),
             debug_info,
-        })
+            start,
+        ))
+    }
+
+    /// Process a mesh that has been imported from a file
+    ///
+    /// The mesh is simplified and analyzed, like the triangle mesh of a shape.
+    /// Since the mesh is all there is to the imported shape, its mass
+    /// properties are exact.
+    pub fn process_mesh(&self, mesh: Mesh<Point<3>>) -> ProcessedShape {
+        let start = Instant::now();
+        let aabb = Aabb::<3>::from_points(mesh.vertices());
+        self.analyze(mesh, aabb, true, Scalar::ZERO, DebugInfo::new(), start)
     }
+
+    // Simplify the triangle mesh of a shape, if requested, and analyze it.
+    //
+    // `start` is when processing started, which is needed to tell how long it
+    // took.
+    fn analyze(
+        &self,
+        mut mesh: Mesh<Point<3>>,
+        aabb: Aabb<3>,
+        exact: bool,
+        tolerance: Scalar,
+        debug_info: DebugInfo,
+        start: Instant,
+    ) -> ProcessedShape {
+        let _span = debug_span!("analyze").entered();
+
+        if let Some(target) = self.simplification {
+            mesh = simplify::simplify(&mesh, target);
+        }
+        let mesh_defects = mesh.defects();
+
+        let (mass, dimensions, overhangs, curvature) = thread::scope(|scope| {
+            let mass = scope.spawn(|| mass::from_mesh(&mesh));
+            let dimensions = scope.spawn(|| dimensions::from_mesh(&mesh));
+            let overhangs = scope.spawn(|| {
+                overhang::from_mesh(
+                    &mesh,
+                    Vector::unit_z(),
+                    overhang::DEFAULT_THRESHOLD,
+                )
+            });
+            let curvature = curvature::from_mesh(&mesh);
+
+            (join(mass), join(dimensions), join(overhangs), curvature)
+        });
+        let mass_properties = MassProperties { exact, ..mass };
+
+        ProcessedShape {
+            aabb,
+            mesh,
+            tolerance,
+            mesh_defects,
+            mass_properties,
+            dimensions,
+            overhangs,
+            curvature,
+            debug_info,
+            processing_time: start.elapsed(),
+        }
+    }
+}
+
+/// Triangulate the approximated faces of a shape, using all available threads
+///
+/// The resulting mesh doesn't depend on the number of threads, or on the order
+/// in which the faces were triangulated.
+fn triangulate(
+    approx: &ShapeApprox,
+    aabb: Aabb<3>,
+    cancellation: &CancellationToken,
+    progress: &mut impl FnMut(Progress),
+    partial: &mut impl FnMut(PartialShape),
+) -> Result<Mesh<Point<3>>, Cancelled> {
+    let _span = debug_span!("triangulate").entered();
+
+    let num_faces = approx.num_faces();
+    let num_threads = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+        .min(num_faces.max(1));
+
+    // Faces are handed out one by one, so that threads that got small faces
+    // don't sit idle while the others are still busy.
+    let (work_tx, work_rx) = mpsc::channel();
+    for face in approx.faces().enumerate() {
+        work_tx.send(face).expect("Receiver has not been dropped");
+    }
+    drop(work_tx);
+    let work_rx = Mutex::new(work_rx);
+
+    let (result_tx, result_rx) = mpsc::channel();
+    let mut meshes = vec![None; num_faces];
+
+    progress(Progress::new(Stage::Triangulation, 0, num_faces));
+
+    thread::scope(|scope| {
+        for _ in 0..num_threads {
+            let result_tx = result_tx.clone();
+            let work_rx = &work_rx;
+
+            scope.spawn(move || loop {
+                let next = work_rx
+                    .lock()
+                    .expect("Work is only received while holding the lock")
+                    .try_recv();
+                let Ok((i, face)) = next else {
+                    break;
+                };
+                if cancellation.is_cancelled() {
+                    break;
+                }
+
+                let mut mesh = Mesh::new();
+                face.clone().triangulate_into_mesh(&mut mesh);
+
+                if result_tx.send((i, mesh)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(result_tx);
+
+        let partial_interval = (num_faces / NUM_PARTIAL_SHAPES).max(1);
+        let mut partial_mesh = Mesh::new();
+
+        for (completed, (i, mesh)) in result_rx.into_iter().enumerate() {
+            let completed = completed + 1;
+            progress(Progress::new(Stage::Triangulation, completed, num_faces));
+
+            partial_mesh.append(&mesh);
+            meshes[i] = Some(mesh);
+
+            if completed % partial_interval == 0 && completed < num_faces {
+                partial(PartialShape {
+                    aabb,
+                    mesh: partial_mesh.clone(),
+                });
+            }
+        }
+    });
+
+    cancellation.check()?;
+
+    let mut mesh = Mesh::new();
+    for face in meshes.into_iter().flatten() {
+        mesh.append(&face);
+    }
+
+    Ok(mesh)
 }
 
+fn join<T>(handle: ScopedJoinHandle<T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// The number of partial shapes reported during triangulation
+const NUM_PARTIAL_SHAPES: usize = 10;
+
 /// A shape processing error
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -65,4 +429,8 @@ pub enum Error {
     /// Model has zero size
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
+
+    /// Processing was cancelled
+    #[error("Processing was cancelled")]
+    Cancelled(#[from] Cancelled),
 }
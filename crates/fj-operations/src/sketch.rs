@@ -1,6 +1,8 @@
 use std::ops::Deref;
 
-use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_interop::{
+    cancellation::CancellationToken, debug::DebugInfo, mesh::Color,
+};
 use fj_kernel::{
     objects::{Cycle, Face, HalfEdge, Objects, Sketch},
     operations::{BuildCycle, BuildHalfEdge, Insert, UpdateCycle},
@@ -9,7 +11,7 @@ use fj_kernel::{
 use fj_math::{Aabb, Point};
 use itertools::Itertools;
 
-use super::Shape;
+use super::{cache::BrepCache, Shape};
 
 impl Shape for fj::Sketch {
     type Brep = Sketch;
@@ -17,7 +19,9 @@ impl Shape for fj::Sketch {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        _: &mut BrepCache,
         _: &mut DebugInfo,
+        _: &CancellationToken,
     ) -> Self::Brep {
         let surface = objects.surfaces.xy_plane();
 
@@ -107,34 +111,13 @@ impl Shape for fj::Sketch {
                     match segment.route {
                         fj::SketchSegmentRoute::Direct => (),
                         fj::SketchSegmentRoute::Arc { angle } => {
-                            use std::f64::consts::PI;
                             let arc = fj_math::Arc::from_endpoints_and_angle(
                                 start_point,
                                 segment.endpoint,
                                 fj_math::Scalar::from_f64(angle.rad()),
                             );
-                            for circle_min_max_angle in
-                                [0., PI / 2., PI, 3. * PI / 2.]
-                            {
-                                let mm_angle = fj_math::Scalar::from_f64(
-                                    circle_min_max_angle,
-                                );
-                                if arc.start_angle < mm_angle
-                                    && mm_angle < arc.end_angle
-                                {
-                                    points.push(
-                                        arc.center
-                                            + [
-                                                arc.radius
-                                                    * circle_min_max_angle
-                                                        .cos(),
-                                                arc.radius
-                                                    * circle_min_max_angle
-                                                        .sin(),
-                                            ],
-                                    );
-                                }
-                            }
+                            let aabb = arc.aabb();
+                            points.extend([aabb.min, aabb.max]);
                         }
                     }
                     points.push(Point::from(segment.endpoint));
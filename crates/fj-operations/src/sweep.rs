@@ -1,15 +1,15 @@
 use std::ops::Deref;
 
-use fj_interop::debug::DebugInfo;
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
 use fj_kernel::{
-    algorithms::sweep::Sweep,
-    objects::{Objects, Solid},
+    algorithms::{sweep::Sweep, transform::TransformObject},
+    objects::{FaceSet, Objects, Solid},
     operations::Insert,
     services::Service,
 };
-use fj_math::{Aabb, Vector};
+use fj_math::{Aabb, Transform, Vector};
 
-use super::Shape;
+use super::{cache::BrepCache, Shape};
 
 impl Shape for fj::Sweep {
     type Brep = Solid;
@@ -17,25 +17,57 @@ impl Shape for fj::Sweep {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
-        let sketch = self.shape().compute_brep(objects, debug_info);
+        // Patterns contain many copies of the same sweep. Copying the solid of
+        // an identical sweep creates new objects, just as sweeping again
+        // would, but is much cheaper.
+        if let Some(solid) = cache.get_sweep(self) {
+            return solid.transform(&Transform::identity(), objects);
+        }
+
+        let sketch =
+            self.shape()
+                .compute_brep(objects, cache, debug_info, cancellation);
+        let sketch = if self.start() == [0.; 3] {
+            sketch
+        } else {
+            sketch.translate(self.start(), objects)
+        };
         let sketch = sketch.insert(objects);
 
         let path = Vector::from(self.path());
 
-        let solid = sketch.sweep(path, objects);
-        solid.deref().clone()
+        let solid = sketch
+            .sweep(path, objects)
+            .expect("Sketches are defined on planes")
+            .deref()
+            .clone();
+        cache.insert_sweep(self, solid.clone());
+
+        solid
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        self.shape()
-            .bounding_volume()
-            .merged(&Aabb::<3>::from_points(
-                self.shape()
-                    .bounding_volume()
-                    .vertices()
-                    .map(|v| v + self.path()),
-            ))
+        let shape = self.shape().bounding_volume();
+        let start = Vector::from(self.start());
+        let end = start + Vector::from(self.path());
+
+        Aabb::<3>::from_points(shape.vertices().map(|v| v + start))
+            .merged(&Aabb::<3>::from_points(shape.vertices().map(|v| v + end)))
     }
 }
+
+/// Collect the faces of all shells of a solid
+pub(crate) fn faces(solid: &Solid) -> FaceSet {
+    solid
+        .shells()
+        .map(|shell| shell.faces().clone())
+        .reduce(|mut a, b| {
+            a.extend(b);
+            a
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,107 @@
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    algorithms::texture::{Pattern, Texture},
+    objects::{FaceSet, Objects},
+    operations::Insert,
+    services::Service,
+};
+use fj_math::{Aabb, Scalar};
+
+use super::{cache::BrepCache, selection::is_selected, Shape};
+
+impl Shape for fj::Texture {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        let faces =
+            self.shape()
+                .compute_brep(objects, cache, debug_info, cancellation);
+
+        let texture = Texture {
+            pattern: match self.pattern() {
+                fj::TexturePattern::Ribs => Pattern::Ribs,
+                fj::TexturePattern::Knurl => Pattern::Knurl,
+                fj::TexturePattern::Diamond => Pattern::Diamond,
+            },
+            pitch: Scalar::from(self.pitch()),
+            depth: Scalar::from(self.depth()),
+        };
+
+        faces
+            .into_iter()
+            .map(|face| {
+                if !is_selected(self.faces(), &face) {
+                    return face;
+                }
+
+                face.clone_object()
+                    .with_texture(Some(texture))
+                    .insert(objects)
+            })
+            .collect()
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // The texture is cut into the faces, so the shape doesn't get any
+        // larger.
+        self.shape().bounding_volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::services::Services;
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn texture_selected_faces() {
+        let cube: fj::Shape = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .into();
+
+        let num_textured = |faces: fj::FaceSelection| {
+            let texture = fj::Texture::new(
+                cube.clone(),
+                fj::TexturePattern::Knurl,
+                0.1,
+                0.01,
+            )
+            .unwrap()
+            .on(faces);
+
+            let mut services = Services::new();
+            let faces = texture.compute_brep(
+                &mut services.objects,
+                &mut BrepCache::new(),
+                &mut DebugInfo::new(),
+                &CancellationToken::new(),
+            );
+
+            faces
+                .into_iter()
+                .filter(|face| face.texture().is_some())
+                .count()
+        };
+
+        assert_eq!(num_textured(fj::FaceSelection::All), 6);
+        assert_eq!(num_textured(fj::FaceSelection::Curved), 0);
+        for normal in [[0., 0., 1.], [0., 0., -1.], [1., 0., 0.]] {
+            let faces = fj::FaceSelection::Planar { normal };
+            assert_eq!(num_textured(faces), 1);
+        }
+        let faces = fj::FaceSelection::Planar {
+            normal: [1., 1., 0.],
+        };
+        assert_eq!(num_textured(faces), 0);
+    }
+}
@@ -0,0 +1,256 @@
+use std::f64::consts::TAU;
+
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+use fj_kernel::{
+    objects::{FaceSet, Objects, Shell},
+    operations::BuildShell,
+    services::Service,
+};
+use fj_math::{Aabb, Point};
+
+use super::{cache::BrepCache, Shape};
+
+impl Shape for fj::Thread {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
+        debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
+    ) -> Self::Brep {
+        match self.representation {
+            fj::ThreadRepresentation::Cosmetic => {
+                // Going through `fj::Shape` instead of `fj::Sweep` directly,
+                // records the sweep in the provenance of the resulting objects.
+                fj::Shape::from(make_cylinder(self)).compute_brep(
+                    objects,
+                    cache,
+                    debug_info,
+                    cancellation,
+                )
+            }
+            fj::ThreadRepresentation::Modeled => {
+                let (points, triangles) = mesh(self);
+                Shell::from_triangles(points, triangles, objects)
+                    .faces()
+                    .clone()
+            }
+        }
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let radius = self.nominal_diameter / 2.;
+
+        Aabb {
+            min: Point::from([-radius, -radius, 0.]),
+            max: Point::from([radius, radius, self.length]),
+        }
+    }
+}
+
+/// Create the cylinder that is the cosmetic representation of a thread
+pub(crate) fn make_cylinder(thread: &fj::Thread) -> fj::Sweep {
+    let circle = fj::Circle::from_radius(thread.nominal_diameter / 2.);
+    fj::Sweep::from_path(
+        fj::Sketch::from_circle(circle).into(),
+        [0., 0., thread.length],
+    )
+}
+
+/// The number of segments that each turn of a modeled thread is made of
+const SEGMENTS_PER_TURN: usize = 32;
+
+/// The breakpoints of the basic profile, as fractions of the pitch
+///
+/// The profile starts with the crest, which is an eighth of the pitch wide.
+/// The flanks are at 60° to each other, and the root is a quarter of the pitch
+/// wide.
+const PROFILE: [f64; 4] = [0., 1. / 8., 7. / 16., 11. / 16.];
+
+/// Compute the closed triangle mesh that bounds a modeled thread
+///
+/// Each segment of a turn is bounded by a column of points, at the
+/// breakpoints of the profile and at both ends of the thread. The profile is
+/// offset along the z-axis from one column to the next, by a fraction of the
+/// pitch. Neighboring columns are connected by triangles, and the ends are
+/// fans around the axis. All triangles wind counter-clockwise, as seen from
+/// outside.
+fn mesh(thread: &fj::Thread) -> (Vec<[f64; 3]>, Vec<[usize; 3]>) {
+    let pitch = thread.pitch;
+    let length = thread.length;
+
+    assert!(
+        pitch > 0. && length > 0. && thread.minor_diameter() > 0.,
+        "Thread must have a positive pitch, length, and minor diameter"
+    );
+
+    // Breakpoints that are closer to the ends than that are left out, to
+    // prevent tiny triangles.
+    let margin = pitch / 1000.;
+
+    let mut points = Vec::new();
+    let mut columns = Vec::new();
+
+    for i in 0..SEGMENTS_PER_TURN {
+        let fraction = i as f64 / SEGMENTS_PER_TURN as f64;
+        let [x, y] = {
+            let angle = TAU * fraction;
+            [angle.cos(), angle.sin()]
+        };
+        let offset = pitch * fraction;
+
+        let mut heights = vec![0.];
+        for turn in -1.. {
+            let start = offset + pitch * turn as f64;
+            if start >= length {
+                break;
+            }
+
+            heights.extend(
+                PROFILE
+                    .into_iter()
+                    .map(|breakpoint| start + pitch * breakpoint)
+                    .filter(|&z| z > margin && z < length - margin),
+            );
+        }
+        heights.push(length);
+
+        let column = heights
+            .into_iter()
+            .map(|z| {
+                let r = radius(thread, z - offset);
+                points.push([x * r, y * r, z]);
+                (points.len() - 1, z)
+            })
+            .collect::<Vec<_>>();
+        columns.push(column);
+    }
+
+    let mut triangles = Vec::new();
+
+    // The profile is offset upwards from one column to the next, so the
+    // points of neighboring columns alternate along the z-axis. Advancing
+    // along both columns in that order connects them with a strip of
+    // triangles.
+    for i in 0..SEGMENTS_PER_TURN {
+        let a = &columns[i];
+        let b = &columns[(i + 1) % SEGMENTS_PER_TURN];
+
+        let (mut j, mut k) = (0, 0);
+        while j + 1 < a.len() || k + 1 < b.len() {
+            let advance_a = k + 1 == b.len()
+                || (j + 1 < a.len() && a[j + 1].1 <= b[k + 1].1);
+
+            if advance_a {
+                triangles.push([a[j].0, b[k].0, a[j + 1].0]);
+                j += 1;
+            } else {
+                triangles.push([a[j].0, b[k].0, b[k + 1].0]);
+                k += 1;
+            }
+        }
+    }
+
+    let bottom = points.len();
+    points.push([0., 0., 0.]);
+    let top = points.len();
+    points.push([0., 0., length]);
+
+    for i in 0..SEGMENTS_PER_TURN {
+        let a = &columns[i];
+        let b = &columns[(i + 1) % SEGMENTS_PER_TURN];
+
+        let (a_bottom, b_bottom) = (a[0].0, b[0].0);
+        let (a_top, b_top) = (a[a.len() - 1].0, b[b.len() - 1].0);
+
+        triangles.push([bottom, b_bottom, a_bottom]);
+        triangles.push([top, a_top, b_top]);
+    }
+
+    (points, triangles)
+}
+
+/// Compute the radius of the basic profile
+///
+/// `z` is the position along the profile, relative to the start of a crest.
+fn radius(thread: &fj::Thread, z: f64) -> f64 {
+    let major = thread.nominal_diameter / 2.;
+    let minor = thread.minor_diameter() / 2.;
+
+    let [crest_end, flank_end, root_end] = [PROFILE[1], PROFILE[2], PROFILE[3]];
+    let flank_width = flank_end - crest_end;
+
+    let z = (z / thread.pitch).rem_euclid(1.);
+
+    if z < crest_end {
+        major
+    } else if z < flank_end {
+        major - (major - minor) * (z - crest_end) / flank_width
+    } else if z < root_end {
+        minor
+    } else {
+        minor + (major - minor) * (z - root_end) / flank_width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
+    use fj_kernel::{
+        algorithms::bounding_volume::BoundingVolume, services::Services,
+    };
+    use fj_math::Scalar;
+
+    use crate::{cache::BrepCache, Shape};
+
+    #[test]
+    fn modeled_mesh_is_closed() {
+        let thread = fj::Thread::metric(fj::MetricSize::M3, 2.2).modeled();
+        let (points, triangles) = super::mesh(&thread);
+
+        // Every edge is traversed once in either direction.
+        let mut edges = BTreeMap::new();
+        for [a, b, c] in triangles {
+            for edge in [[a, b], [b, c], [c, a]] {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        for (&[a, b], &count) in &edges {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&[b, a]), Some(&1));
+        }
+
+        let max_radius = thread.nominal_diameter / 2. + 1e-12;
+        let min_radius = thread.minor_diameter() / 2. - 1e-12;
+        for [x, y, z] in points {
+            let radius = f64::hypot(x, y);
+            if radius > 0. {
+                assert!(radius <= max_radius && radius >= min_radius);
+            }
+            assert!((0. ..=thread.length).contains(&z));
+        }
+    }
+
+    #[test]
+    fn modeled_thread() {
+        let thread = fj::Thread::metric(fj::MetricSize::M3, 2.2).modeled();
+
+        let mut services = Services::new();
+        let faces = thread.compute_brep(
+            &mut services.objects,
+            &mut BrepCache::new(),
+            &mut DebugInfo::new(),
+            &CancellationToken::new(),
+        );
+
+        let aabb = faces.aabb().unwrap();
+        assert_eq!(aabb.min.z, Scalar::ZERO);
+        assert_eq!(aabb.max.z, Scalar::from(thread.length));
+        assert!(aabb.max.x <= Scalar::from(thread.nominal_diameter / 2.));
+        assert!(aabb.max.x > Scalar::from(thread.minor_diameter() / 2.));
+    }
+}
@@ -1,12 +1,12 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{cancellation::CancellationToken, debug::DebugInfo};
 use fj_kernel::{
     algorithms::transform::TransformObject,
     objects::{FaceSet, Objects},
     services::Service,
 };
-use fj_math::{Aabb, Transform, Vector};
+use fj_math::{Aabb, Transform};
 
-use super::Shape;
+use super::{cache::BrepCache, Shape};
 
 impl Shape for fj::Transform {
     type Brep = FaceSet;
@@ -14,10 +14,12 @@ impl Shape for fj::Transform {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &mut BrepCache,
         debug_info: &mut DebugInfo,
+        cancellation: &CancellationToken,
     ) -> Self::Brep {
         self.shape
-            .compute_brep(objects, debug_info)
+            .compute_brep(objects, cache, debug_info, cancellation)
             .transform(&make_transform(self), objects)
     }
 
@@ -26,8 +28,7 @@ impl Shape for fj::Transform {
     }
 }
 
-fn make_transform(transform: &fj::Transform) -> Transform {
-    let axis = Vector::from(transform.axis).normalize();
+pub(crate) fn make_transform(transform: &fj::Transform) -> Transform {
     Transform::translation(transform.offset)
-        * Transform::rotation(axis * transform.angle.rad())
+        * Transform::from_axis_angle(transform.axis, transform.angle.rad())
 }
@@ -0,0 +1,206 @@
+//! # Fornjot Python Bindings
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library makes the shape-building API of the [`fj`] crate available to
+//! Python, as the `fornjot` module. Shapes that are defined in Python are
+//! processed by the Fornjot kernel, and can be exported to files. Build it with
+//! [maturin]:
+//!
+//! ``` sh
+//! maturin develop --manifest-path crates/fj-python/Cargo.toml
+//! ```
+//!
+//! Then use it from Python:
+//!
+//! ``` python
+//! import fornjot as fj
+//!
+//! outer = fj.Sketch.from_points([(-1, -1), (1, -1), (1, 1), (-1, 1)])
+//! inner = fj.Sketch.circle(0.5)
+//!
+//! spacer = outer.difference(inner).sweep((0, 0, 0.5))
+//! spacer.export("spacer.stl")
+//! ```
+//!
+//! [Fornjot]: https://www.fornjot.app/
+//! [maturin]: https://www.maturin.rs/
+
+#![warn(missing_docs)]
+
+use std::path::PathBuf;
+
+use fornjot::Tolerance;
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+};
+
+/// A 2-dimensional shape, that can be swept into a 3-dimensional one
+#[pyclass]
+#[derive(Clone)]
+pub struct Sketch {
+    shape: fj::Shape2d,
+}
+
+#[pymethods]
+impl Sketch {
+    /// Create a sketch from a closed polygon, given by its corners
+    #[staticmethod]
+    fn from_points(points: Vec<(f64, f64)>) -> PyResult<Self> {
+        if points.len() < 3 {
+            return Err(PyValueError::new_err(
+                "A sketch needs at least three points",
+            ));
+        }
+
+        let points = points.into_iter().map(|(x, y)| [x, y]).collect();
+        let sketch = fj::Sketch::from_points(points)
+            .expect("Checked that there are points");
+
+        Ok(Self {
+            shape: sketch.into(),
+        })
+    }
+
+    /// Create a sketch from a circle around the origin
+    #[staticmethod]
+    fn circle(radius: f64) -> PyResult<Self> {
+        if radius.is_nan() || radius <= 0. {
+            return Err(PyValueError::new_err("Radius must be positive"));
+        }
+
+        let circle = fj::Circle::from_radius(radius);
+        Ok(Self {
+            shape: fj::Sketch::from_circle(circle).into(),
+        })
+    }
+
+    /// Subtract another sketch from this one
+    fn difference(&self, other: &Self) -> Self {
+        let shapes = [self.shape.clone(), other.shape.clone()];
+        Self {
+            shape: fj::Difference2d::from_shapes(shapes).into(),
+        }
+    }
+
+    /// Sweep the sketch along a straight path, creating a 3D shape
+    ///
+    /// If `symmetric` is set, half of the path extends to each side of the
+    /// sketch.
+    #[pyo3(signature = (path, symmetric = false))]
+    fn sweep(&self, path: (f64, f64, f64), symmetric: bool) -> Shape {
+        let path = [path.0, path.1, path.2];
+        let sweep = if symmetric {
+            fj::Sweep::symmetric(self.shape.clone(), path)
+        } else {
+            fj::Sweep::from_path(self.shape.clone(), path)
+        };
+
+        Shape {
+            shape: sweep.into(),
+        }
+    }
+}
+
+/// A 3-dimensional shape
+#[pyclass]
+#[derive(Clone)]
+pub struct Shape {
+    shape: fj::Shape,
+}
+
+#[pymethods]
+impl Shape {
+    /// Combine this shape with another one
+    ///
+    /// The shapes must not overlap.
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            shape: fj::Group {
+                a: self.shape.clone(),
+                b: other.shape.clone(),
+            }
+            .into(),
+        }
+    }
+
+    /// Move the shape by the given offset
+    fn translate(&self, offset: (f64, f64, f64)) -> Self {
+        self.transform([1., 0., 0.], 0., [offset.0, offset.1, offset.2])
+    }
+
+    /// Rotate the shape around an axis through the origin
+    ///
+    /// The angle is given in radians.
+    fn rotate(&self, axis: (f64, f64, f64), angle: f64) -> Self {
+        self.transform([axis.0, axis.1, axis.2], angle, [0.; 3])
+    }
+
+    /// Process the shape, and export the resulting mesh to a file
+    ///
+    /// The format is determined from the file extension of `path`. The mesh
+    /// approximates the shape within `tolerance`. If it is `None`, a tolerance
+    /// is derived from the size of the shape.
+    ///
+    /// The kernel does its work without holding the GIL, so other Python
+    /// threads can run in the meantime.
+    #[pyo3(signature = (path, tolerance = None))]
+    fn export(
+        &self,
+        py: Python<'_>,
+        path: PathBuf,
+        tolerance: Option<f64>,
+    ) -> PyResult<()> {
+        let tolerance = tolerance
+            .map(Tolerance::from_scalar)
+            .transpose()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        let shape = self.shape.clone();
+        py.allow_threads(|| {
+            fornjot::process_and_export(shape, tolerance, &path)
+        })
+        .map_err(|err| PyRuntimeError::new_err(error_chain(&err)))?;
+
+        Ok(())
+    }
+}
+
+impl Shape {
+    fn transform(&self, axis: [f64; 3], angle: f64, offset: [f64; 3]) -> Self {
+        Self {
+            shape: fj::Transform {
+                shape: self.shape.clone(),
+                axis,
+                angle: fj::Angle::from_rad(angle),
+                offset,
+            }
+            .into(),
+        }
+    }
+}
+
+/// Build Fornjot models in Python
+#[pymodule]
+#[pyo3(name = "fornjot")]
+fn python_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<Sketch>()?;
+    module.add_class::<Shape>()?;
+    Ok(())
+}
+
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+
+    message
+}
@@ -0,0 +1,155 @@
+//! Translation of ANSI escape codes into formatted text
+//!
+//! Cargo and the compiler color their output using escape codes. Only those
+//! that select colors are translated. Any others are removed.
+
+use egui::{text::LayoutJob, Color32, FontId, TextFormat};
+
+/// Lay out text that contains ANSI escape codes
+///
+/// Text that has no color selected is shown in `default_color`.
+pub(crate) fn layout_job(
+    text: &str,
+    font: FontId,
+    default_color: Color32,
+) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let mut style = Style::default();
+
+    let mut rest = text;
+    while let Some(start) = rest.find('\x1b') {
+        append(&mut job, &rest[..start], &style, &font, default_color);
+        rest = &rest[start + 1..];
+
+        // Only Control Sequence Introducer (CSI) sequences are expected. They
+        // end with a byte in the range `@` to `~`.
+        let Some(sequence) = rest.strip_prefix('[') else {
+            continue;
+        };
+        let Some(end) = sequence.find(|c| ('@'..='~').contains(&c)) else {
+            rest = "";
+            break;
+        };
+
+        // Select Graphic Rendition (SGR) sequences are the ones that set
+        // colors.
+        if sequence[end..].starts_with('m') {
+            style.apply(&sequence[..end]);
+        }
+
+        rest = &sequence[end + 1..];
+    }
+    append(&mut job, rest, &style, &font, default_color);
+
+    job
+}
+
+fn append(
+    job: &mut LayoutJob,
+    text: &str,
+    style: &Style,
+    font: &FontId,
+    default_color: Color32,
+) {
+    if text.is_empty() {
+        return;
+    }
+
+    let format = TextFormat {
+        font_id: font.clone(),
+        color: style.color().unwrap_or(default_color),
+        ..TextFormat::default()
+    };
+    job.append(text, 0., format);
+}
+
+#[derive(Default)]
+struct Style {
+    color: Option<Color>,
+    bold: bool,
+}
+
+impl Style {
+    fn apply(&mut self, parameters: &str) {
+        // An empty parameter means the same as 0, which resets the style.
+        let mut parameters = parameters
+            .split(';')
+            .map(|parameter| parameter.parse::<u8>().unwrap_or(0));
+
+        while let Some(parameter) = parameters.next() {
+            match parameter {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                30..=37 => self.color = Some(Color::Indexed(parameter - 30)),
+                39 => self.color = None,
+                90..=97 => {
+                    self.color = Some(Color::Indexed(parameter - 90 + 8));
+                }
+                38 => match parameters.next() {
+                    Some(5) => {
+                        self.color = parameters.next().map(Color::Indexed);
+                    }
+                    Some(2) => {
+                        let mut next = || parameters.next().unwrap_or(0);
+                        let [r, g, b] = [next(), next(), next()];
+                        self.color =
+                            Some(Color::Rgb(Color32::from_rgb(r, g, b)));
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn color(&self) -> Option<Color32> {
+        match self.color? {
+            // Terminals traditionally show bold text in bright colors.
+            Color::Indexed(index) if self.bold && index < 8 => {
+                Color::Indexed(index + 8).to_color32()
+            }
+            color => color.to_color32(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Color {
+    Indexed(u8),
+    Rgb(Color32),
+}
+
+impl Color {
+    /// The standard and bright colors, as seen on a dark background
+    const PALETTE: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 49, 49],
+        [13, 188, 121],
+        [229, 229, 16],
+        [36, 114, 200],
+        [188, 63, 188],
+        [17, 168, 205],
+        [229, 229, 229],
+        [102, 102, 102],
+        [241, 76, 76],
+        [35, 209, 139],
+        [245, 245, 67],
+        [59, 142, 234],
+        [214, 112, 214],
+        [41, 184, 219],
+        [255, 255, 255],
+    ];
+
+    fn to_color32(self) -> Option<Color32> {
+        match self {
+            Self::Indexed(index) => {
+                // Colors beyond the first 16 are rarely used by the compiler.
+                // Show them in the default color.
+                let [r, g, b] = *Self::PALETTE.get(usize::from(index))?;
+                Some(Color32::from_rgb(r, g, b))
+            }
+            Self::Rgb(color) => Some(color),
+        }
+    }
+}
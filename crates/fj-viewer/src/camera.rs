@@ -116,6 +116,28 @@ impl Camera {
         Some(FocusPoint(origin + dir * min_t?))
     }
 
+    /// Save the pose of the camera under a name
+    pub fn bookmark(&self, name: impl Into<String>) -> CameraBookmark {
+        let data = |transform: &Transform| {
+            transform
+                .data()
+                .try_into()
+                .expect("Transform matrix has 16 elements")
+        };
+
+        CameraBookmark {
+            name: name.into(),
+            rotation: data(&self.rotation),
+            translation: data(&self.translation),
+        }
+    }
+
+    /// Move the camera back into a pose that has been saved before
+    pub fn jump_to(&mut self, bookmark: &CameraBookmark) {
+        self.rotation = Transform::from_data(bookmark.rotation);
+        self.translation = Transform::from_data(bookmark.translation);
+    }
+
     /// Access the transform from camera to model space.
     pub fn camera_to_model(&self) -> Transform {
         // Using a mutable variable cleanly takes care of any type inference
@@ -228,6 +250,22 @@ impl Default for Camera {
     }
 }
 
+/// A named pose of the camera, that can be returned to later
+///
+/// The transforms are stored as plain matrices in column-major order, so
+/// bookmarks can easily be saved. See [`Camera::bookmark`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraBookmark {
+    /// The name of the bookmark
+    pub name: String,
+
+    /// The rotational part of the camera's transform
+    pub rotation: [f64; 16],
+
+    /// The locational part of the camera's transform
+    pub translation: [f64; 16],
+}
+
 /// The point around which camera movement happens.
 ///
 /// This will be the point on the model that the cursor is currently pointing at if such a point exists,
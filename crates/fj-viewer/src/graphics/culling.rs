@@ -0,0 +1,127 @@
+use std::ops::Range;
+
+use fj_math::Aabb;
+
+use super::transform::Transform;
+
+/// A part of the model that is not connected to any other part
+///
+/// Bodies are drawn separately, which allows bodies that are not visible to
+/// be skipped.
+#[derive(Clone, Debug)]
+pub struct Body {
+    /// The range of indices that make up the body
+    pub indices: Range<u32>,
+
+    /// The bounding box of the body
+    pub aabb: Aabb<3>,
+
+    /// Whether the triangles of the body are transparent
+    ///
+    /// The transparent triangles of a body are kept separate from its opaque
+    /// ones, as they need to be drawn after all opaque triangles.
+    pub transparent: bool,
+}
+
+/// The ranges of indices that need to be drawn
+#[derive(Debug, Default)]
+pub struct VisibleIndices {
+    /// The ranges of opaque triangles
+    pub opaque: Vec<Range<u32>>,
+
+    /// The ranges of transparent triangles, ordered from back to front
+    pub transparent: Vec<Range<u32>>,
+}
+
+impl VisibleIndices {
+    /// Iterate over all ranges, opaque and transparent
+    pub fn all(&self) -> impl Iterator<Item = &Range<u32>> {
+        self.opaque.iter().chain(&self.transparent)
+    }
+}
+
+/// Compute the ranges of indices that need to be drawn
+///
+/// Bodies outside of the view frustum are left out. Ranges of neighboring
+/// bodies are merged, to keep the number of draw calls low.
+///
+/// Transparent bodies are sorted from back to front, so they are blended
+/// correctly with each other. This only works between bodies. Triangles within
+/// a body are not sorted.
+///
+/// If there are no bodies, everything is drawn as opaque.
+pub fn visible_indices(
+    bodies: &[Body],
+    num_indices: u32,
+    transform: &Transform,
+) -> VisibleIndices {
+    if bodies.is_empty() {
+        return VisibleIndices {
+            opaque: vec![0..num_indices],
+            transparent: Vec::new(),
+        };
+    }
+
+    let visible = bodies
+        .iter()
+        .filter(|body| is_in_frustum(&body.aabb, transform));
+    let (opaque, mut transparent): (Vec<_>, Vec<_>) =
+        visible.partition(|body| !body.transparent);
+
+    transparent.sort_by(|a, b| {
+        let a = depth(&a.aabb, transform);
+        let b = depth(&b.aabb, transform);
+        b.total_cmp(&a)
+    });
+
+    VisibleIndices {
+        opaque: merge_ranges(opaque),
+        transparent: merge_ranges(transparent),
+    }
+}
+
+fn merge_ranges<'r>(
+    bodies: impl IntoIterator<Item = &'r Body>,
+) -> Vec<Range<u32>> {
+    let mut ranges: Vec<Range<u32>> = Vec::new();
+
+    for body in bodies {
+        match ranges.last_mut() {
+            Some(range) if range.end == body.indices.start => {
+                range.end = body.indices.end;
+            }
+            _ => ranges.push(body.indices.clone()),
+        }
+    }
+
+    ranges
+}
+
+/// The distance of a bounding box from the camera, in clip space
+fn depth(aabb: &Aabb<3>, transform: &Transform) -> f32 {
+    let [_, _, _, w] = transform.to_clip_space(aabb.center());
+    w
+}
+
+/// Check whether a bounding box might be within the view frustum
+///
+/// The box is only considered to be outside, if all of its corners are
+/// outside of the same clipping plane. This is conservative: Some boxes that
+/// are not actually visible are considered to be visible.
+fn is_in_frustum(aabb: &Aabb<3>, transform: &Transform) -> bool {
+    let corners = aabb.vertices().map(|point| transform.to_clip_space(point));
+
+    let outside =
+        |is_outside: fn(&[f32; 4]) -> bool| corners.iter().all(is_outside);
+
+    let planes: [fn(&[f32; 4]) -> bool; 6] = [
+        |[x, _, _, w]| *x < -w,
+        |[x, _, _, w]| *x > *w,
+        |[_, y, _, w]| *y < -w,
+        |[_, y, _, w]| *y > *w,
+        |[_, _, z, w]| *z < -w,
+        |[_, _, z, w]| *z > *w,
+    ];
+
+    !planes.into_iter().any(outside)
+}
@@ -9,6 +9,40 @@ pub struct DrawConfig {
 
     /// Toggle for displaying model debug information
     pub draw_debug: bool,
+
+    /// Toggle for displaying transparent colors as transparent
+    ///
+    /// If this is disabled, transparent faces are displayed as opaque.
+    pub draw_transparency: bool,
+
+    /// Toggle for displaying the axes
+    ///
+    /// If this is enabled, a labeled triad in the corner of the screen shows
+    /// the orientation of the axes, and a marker shows the world origin.
+    pub draw_axes: bool,
+
+    /// Toggle for displaying the reference grid
+    ///
+    /// The grid lies on the xy-plane, and its spacing depends on the size of
+    /// the model and the units it declares.
+    pub draw_grid: bool,
+
+    /// Toggle for displaying performance statistics
+    ///
+    /// If this is enabled, an overlay shows how long frames take to draw, how
+    /// many draw calls they need, how much GPU memory the geometry takes up,
+    /// and how long it took to process the model.
+    pub draw_performance: bool,
+
+    /// Toggle for debugging the kernel's algorithms
+    ///
+    /// If this is enabled, the intermediate results of the kernel's algorithms
+    /// are collected while processing the model. Those of the selected face
+    /// are displayed along with the other debug information.
+    pub debug_algorithms: bool,
+
+    /// How the shaded model is colored
+    pub coloring: Coloring,
 }
 
 impl Default for DrawConfig {
@@ -17,6 +51,59 @@ impl Default for DrawConfig {
             draw_model: true,
             draw_mesh: false,
             draw_debug: false,
+            draw_transparency: true,
+            draw_axes: true,
+            draw_grid: false,
+            draw_performance: false,
+            debug_algorithms: false,
+            coloring: Coloring::default(),
+        }
+    }
+}
+
+/// How the shaded model is colored
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Coloring {
+    /// Use the colors defined by the model
+    #[default]
+    Model,
+
+    /// Color the model according to its overhangs
+    Overhangs,
+
+    /// Color the model according to its Gaussian curvature
+    GaussianCurvature,
+
+    /// Color the model according to its mean curvature
+    MeanCurvature,
+}
+
+impl Coloring {
+    /// All ways of coloring the model
+    pub const ALL: [Self; 4] = [
+        Self::Model,
+        Self::Overhangs,
+        Self::GaussianCurvature,
+        Self::MeanCurvature,
+    ];
+
+    /// Access the way of coloring that comes after this one
+    pub fn next(self) -> Self {
+        match self {
+            Self::Model => Self::Overhangs,
+            Self::Overhangs => Self::GaussianCurvature,
+            Self::GaussianCurvature => Self::MeanCurvature,
+            Self::MeanCurvature => Self::Model,
+        }
+    }
+
+    /// Access a human-readable description
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Model => "Model colors",
+            Self::Overhangs => "Overhangs",
+            Self::GaussianCurvature => "Gaussian curvature",
+            Self::MeanCurvature => "Mean curvature",
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use super::{
     geometries::{Geometries, Geometry},
     pipelines::{Pipeline, Pipelines},
@@ -5,6 +7,8 @@ use super::{
 
 pub struct Drawables<'r> {
     pub model: Drawable<'r>,
+    pub model_transparent: Drawable<'r>,
+    pub model_opaque: Drawable<'r>,
     pub mesh: Drawable<'r>,
     pub lines: Drawable<'r>,
 }
@@ -12,10 +16,20 @@ pub struct Drawables<'r> {
 impl<'r> Drawables<'r> {
     pub fn new(geometries: &'r Geometries, pipelines: &'r Pipelines) -> Self {
         let model = Drawable::new(&geometries.mesh, &pipelines.model);
+        let model_transparent =
+            Drawable::new(&geometries.mesh, &pipelines.model_transparent);
+        let model_opaque =
+            Drawable::new(&geometries.mesh, &pipelines.model_opaque);
         let mesh = Drawable::new(&geometries.mesh, &pipelines.mesh);
         let lines = Drawable::new(&geometries.lines, &pipelines.lines);
 
-        Self { model, mesh, lines }
+        Self {
+            model,
+            model_transparent,
+            model_opaque,
+            mesh,
+            lines,
+        }
     }
 }
 
@@ -29,17 +43,30 @@ impl<'a> Drawable<'a> {
         Self { geometry, pipeline }
     }
 
-    pub fn draw<'b>(&self, render_pass: &mut wgpu::RenderPass<'b>)
+    /// Draw the given ranges of indices
+    ///
+    /// Returns the number of draw calls that this took.
+    pub fn draw<'b>(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'b>,
+        ranges: impl IntoIterator<Item = &Range<u32>>,
+    ) -> usize
     where
         'a: 'b,
     {
         render_pass.set_pipeline(&self.pipeline.0);
-        render_pass.set_vertex_buffer(0, self.geometry.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(0, self.geometry.vertex_buffer.slice());
         render_pass.set_index_buffer(
-            self.geometry.index_buffer.slice(..),
+            self.geometry.index_buffer.slice(),
             wgpu::IndexFormat::Uint32,
         );
 
-        render_pass.draw_indexed(0..self.geometry.num_indices, 0, 0..1);
+        let mut draw_calls = 0;
+        for range in ranges {
+            render_pass.draw_indexed(range.clone(), 0, 0..1);
+            draw_calls += 1;
+        }
+
+        draw_calls
     }
 }
@@ -1,8 +1,13 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, mem::size_of};
 
+use bytemuck::Pod;
 use wgpu::util::DeviceExt;
 
-use super::vertices::{Vertex, Vertices};
+use super::{
+    culling::{self, Body, VisibleIndices},
+    transform::Transform,
+    vertices::{Vertex, Vertices},
+};
 
 #[derive(Debug)]
 pub struct Geometries {
@@ -16,46 +21,166 @@ impl Geometries {
         mesh: &Vertices,
         debug_info: &Vertices,
     ) -> Self {
-        let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
-        let lines =
-            Geometry::new(device, debug_info.vertices(), debug_info.indices());
+        let mesh = Geometry::new(device, mesh);
+        let lines = Geometry::new(device, debug_info);
 
         Self { mesh, lines }
     }
+
+    /// Update the geometries, uploading only the parts that have changed
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mesh: &Vertices,
+        debug_info: &Vertices,
+    ) {
+        self.mesh.update(device, queue, mesh);
+        self.lines.update(device, queue, debug_info);
+    }
+
+    /// Compute the size of the GPU buffers that hold the geometries, in bytes
+    pub fn buffer_size(&self) -> u64 {
+        self.mesh.buffer_size() + self.lines.buffer_size()
+    }
 }
 
 #[derive(Debug)]
 pub struct Geometry {
-    pub vertex_buffer: wgpu::Buffer,
-    pub index_buffer: wgpu::Buffer,
+    pub vertex_buffer: Buffer<Vertex>,
+    pub index_buffer: Buffer<u32>,
     pub num_indices: u32,
+    pub bodies: Vec<Body>,
 }
 
 impl Geometry {
-    fn new(
-        device: &wgpu::Device,
-        vertices: &[Vertex],
-        indices: &[u32],
-    ) -> Self {
+    fn new(device: &wgpu::Device, vertices: &Vertices) -> Self {
         Self {
-            vertex_buffer: device.create_buffer_init(
-                &wgpu::util::BufferInitDescriptor {
-                    label: None,
-                    contents: bytemuck::cast_slice(vertices),
-                    usage: wgpu::BufferUsages::VERTEX,
-                },
+            vertex_buffer: Buffer::new(
+                device,
+                vertices.vertices(),
+                wgpu::BufferUsages::VERTEX,
             ),
-            index_buffer: device.create_buffer_init(
-                &wgpu::util::BufferInitDescriptor {
-                    label: None,
-                    contents: bytemuck::cast_slice(indices),
-                    usage: wgpu::BufferUsages::INDEX,
-                },
+            index_buffer: Buffer::new(
+                device,
+                vertices.indices(),
+                wgpu::BufferUsages::INDEX,
             ),
-            num_indices: indices
-                .len()
-                .try_into()
-                .expect("`usize` couldn't be cast to `u32`"),
+            num_indices: num_indices(vertices.indices()),
+            bodies: vertices.bodies().to_vec(),
+        }
+    }
+
+    fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &Vertices,
+    ) {
+        self.vertex_buffer
+            .update(device, queue, vertices.vertices());
+        self.index_buffer.update(device, queue, vertices.indices());
+        self.num_indices = num_indices(vertices.indices());
+        self.bodies = vertices.bodies().to_vec();
+    }
+
+    /// Compute the ranges of indices that are visible with the given transform
+    pub fn visible_indices(&self, transform: &Transform) -> VisibleIndices {
+        culling::visible_indices(&self.bodies, self.num_indices, transform)
+    }
+
+    /// Compute the size of the GPU buffers that hold the geometry, in bytes
+    pub fn buffer_size(&self) -> u64 {
+        self.vertex_buffer.size() + self.index_buffer.size()
+    }
+}
+
+/// A GPU buffer that remembers its contents
+///
+/// The contents are split into ranges of [`Buffer::RANGE_LEN`] elements. On
+/// update, only the ranges that have changed are written. When a model is
+/// reloaded, the parts of it that haven't changed usually end up at the same
+/// place in the buffer, so most of it doesn't need to be uploaded again.
+#[derive(Debug)]
+pub struct Buffer<T> {
+    buffer: wgpu::Buffer,
+    contents: Vec<T>,
+    usage: wgpu::BufferUsages,
+}
+
+impl<T> Buffer<T>
+where
+    T: Pod + PartialEq,
+{
+    const RANGE_LEN: usize = 4096;
+
+    pub fn new(
+        device: &wgpu::Device,
+        contents: &[T],
+        usage: wgpu::BufferUsages,
+    ) -> Self {
+        let buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(contents),
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+            });
+
+        Self {
+            buffer,
+            contents: contents.to_vec(),
+            usage,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        contents: &[T],
+    ) {
+        let capacity = self.buffer.size() as usize / size_of::<T>();
+        if contents.len() > capacity {
+            *self = Self::new(device, contents, self.usage);
+            return;
         }
+
+        for (i, range) in contents.chunks(Self::RANGE_LEN).enumerate() {
+            let start = i * Self::RANGE_LEN;
+            let end = start + range.len();
+
+            if self.contents.get(start..end) == Some(range) {
+                continue;
+            }
+
+            let offset = (start * size_of::<T>()) as wgpu::BufferAddress;
+            queue.write_buffer(
+                &self.buffer,
+                offset,
+                bytemuck::cast_slice(range),
+            );
+        }
+
+        self.contents.clear();
+        self.contents.extend_from_slice(contents);
     }
+
+    pub fn slice(&self) -> wgpu::BufferSlice {
+        self.buffer.slice(..)
+    }
+
+    /// Access the size of the GPU buffer, in bytes
+    ///
+    /// This can be larger than the contents, as the buffer is only replaced,
+    /// if the contents don't fit anymore.
+    pub fn size(&self) -> u64 {
+        self.buffer.size()
+    }
+}
+
+fn num_indices(indices: &[u32]) -> u32 {
+    indices
+        .len()
+        .try_into()
+        .expect("`usize` couldn't be cast to `u32`")
 }
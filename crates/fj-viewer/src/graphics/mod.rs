@@ -1,10 +1,12 @@
 //! Rendering primitives, routines, and structures.
 
+mod culling;
 mod draw_config;
 mod drawables;
 mod geometries;
 mod model;
 mod navigation_cube;
+mod picking;
 mod pipelines;
 mod renderer;
 mod shaders;
@@ -14,8 +16,12 @@ mod uniforms;
 mod vertices;
 
 pub use self::{
-    draw_config::DrawConfig,
-    renderer::{DrawError, Renderer, RendererInitError},
+    draw_config::{Coloring, DrawConfig},
+    renderer::{DrawError, RenderStats, Renderer, RendererInitError},
+};
+
+pub(crate) use self::{
+    picking::PickVertices, transform::Transform, vertices::Vertices,
 };
 
 pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
@@ -0,0 +1,336 @@
+use std::{borrow::Cow, mem::size_of, num::NonZeroU32, sync::mpsc};
+
+use bytemuck::{Pod, Zeroable};
+use fj_interop::mesh::{FaceId, Mesh};
+use fj_math::Point;
+
+use super::{geometries::Buffer, DEPTH_FORMAT};
+
+/// Renders the IDs of faces into a texture, to find the face under the cursor
+///
+/// Every pixel of the texture contains the ID of the face that is visible at
+/// that pixel. Picking a face only requires reading back a single pixel,
+/// regardless of how many triangles the model has.
+#[derive(Debug)]
+pub struct Picking {
+    pipeline: wgpu::RenderPipeline,
+    vertices: Buffer<PickVertex>,
+    num_vertices: u32,
+
+    size: [u32; 2],
+    id_view: wgpu::TextureView,
+    id_texture: wgpu::Texture,
+    depth_view: wgpu::TextureView,
+    readback: wgpu::Buffer,
+}
+
+impl Picking {
+    pub fn new(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        size: [u32; 2],
+    ) -> Self {
+        let pipeline = create_pipeline(device, bind_group_layout);
+        let vertices = Buffer::new(device, &[], wgpu::BufferUsages::VERTEX);
+        let (id_texture, id_view, depth_view) = create_textures(device, size);
+
+        // Copies from textures to buffers require rows to be aligned, even if
+        // only one pixel is copied.
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: u64::from(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            vertices,
+            num_vertices: 0,
+            size,
+            id_view,
+            id_texture,
+            depth_view,
+            readback,
+        }
+    }
+
+    pub fn update_geometry(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &PickVertices,
+    ) {
+        self.vertices.update(device, queue, &vertices.0);
+        self.num_vertices = vertices
+            .0
+            .len()
+            .try_into()
+            .expect("`usize` couldn't be cast to `u32`");
+    }
+
+    pub fn handle_resize(&mut self, device: &wgpu::Device, size: [u32; 2]) {
+        let (id_texture, id_view, depth_view) = create_textures(device, size);
+
+        self.size = size;
+        self.id_texture = id_texture;
+        self.id_view = id_view;
+        self.depth_view = depth_view;
+    }
+
+    /// Find the face that is visible at the given pixel
+    ///
+    /// Blocks until the GPU has rendered the pixel. Returns `None`, if there is
+    /// no face at the pixel, or if the result could not be read back.
+    pub fn pick(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        bind_group: &wgpu::BindGroup,
+        [x, y]: [u32; 2],
+    ) -> Option<FaceId> {
+        let [width, height] = self.size;
+        if x >= width || y >= height {
+            return None;
+        }
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: None,
+            });
+
+        // Need this block here, as a render pass only takes effect once it's
+        // dropped.
+        {
+            let mut render_pass =
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view: &self.id_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(
+                                    wgpu::Color::TRANSPARENT,
+                                ),
+                                store: true,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: Some(
+                        wgpu::RenderPassDepthStencilAttachment {
+                            view: &self.depth_view,
+                            depth_ops: Some(wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(1.0),
+                                store: false,
+                            }),
+                            stencil_ops: None,
+                        },
+                    ),
+                });
+
+            // Only the one pixel is needed, so there's no reason to rasterize
+            // anything else.
+            render_pass.set_scissor_rect(x, y, 1, 1);
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertices.slice());
+            render_pass.draw(0..self.num_vertices, 0..1);
+        }
+
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.id_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &self.readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(
+                        wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
+                    ),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = self.readback.slice(..size_of::<[u32; 4]>() as u64);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            // The receiver is only dropped after the result has been
+            // received, or waiting for it has failed.
+            let _ = tx.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+
+        // On the web, polling doesn't block, so the result might not be
+        // available yet.
+        let mapped = rx.try_recv().ok()?;
+        let face = mapped.ok().map(|()| {
+            bytemuck::pod_read_unaligned::<[u32; 4]>(&slice.get_mapped_range())
+        });
+        self.readback.unmap();
+
+        let [low, mid, high, is_face] = face?;
+        (is_face != 0).then_some(FaceId(
+            u128::from(high) << 64 | u128::from(mid) << 32 | u128::from(low),
+        ))
+    }
+}
+
+/// The vertices that the picking pass is rendered from
+///
+/// Every triangle has its own vertices, as vertices can't be shared between
+/// triangles of different faces.
+#[derive(Debug)]
+pub struct PickVertices(Vec<PickVertex>);
+
+impl From<&Mesh<Point<3>>> for PickVertices {
+    fn from(mesh: &Mesh<Point<3>>) -> Self {
+        let vertices = mesh
+            .triangles()
+            .flat_map(|triangle| {
+                let face = match triangle.face {
+                    Some(FaceId(id)) => {
+                        assert!(id >> 96 == 0, "Face ID exceeds 96 bits");
+                        [id as u32, (id >> 32) as u32, (id >> 64) as u32, 1]
+                    }
+                    None => [0; 4],
+                };
+
+                triangle.inner.points().map(|point| PickVertex {
+                    position: point.coords.components.map(|s| s.into_f32()),
+                    face,
+                })
+            })
+            .collect();
+
+        Self(vertices)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PickVertex {
+    position: [f32; 3],
+
+    /// The face ID, from its lowest 32 bits to its highest, and whether there
+    /// is a face
+    face: [u32; 4],
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!(
+            "picking.wgsl"
+        ))),
+    });
+
+    let layout =
+        device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: "vertex",
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: size_of::<PickVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![
+                    0 => Float32x3,
+                    1 => Uint32x4,
+                ],
+            }],
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        // Face IDs can't be blended, so there is no multisampling.
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: "frag_picking",
+            targets: &[Some(wgpu::ColorTargetState {
+                format: ID_FORMAT,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview: None,
+    })
+}
+
+fn create_textures(
+    device: &wgpu::Device,
+    [width, height]: [u32; 2],
+) -> (wgpu::Texture, wgpu::TextureView, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+
+    let id_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: ID_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let id_view =
+        id_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let depth_view =
+        depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    (id_texture, id_view, depth_view)
+}
+
+const ID_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Uint;
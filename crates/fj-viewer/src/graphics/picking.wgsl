@@ -0,0 +1,37 @@
+struct Uniforms {
+    transform: mat4x4<f32>,
+    transform_normals: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) face: vec4<u32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) face: vec4<u32>,
+};
+
+struct FragmentOutput {
+    @location(0) face: vec4<u32>,
+}
+
+@vertex
+fn vertex(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = uniforms.transform * vec4<f32>(in.position, 1.0);
+    out.face = in.face;
+
+    return out;
+}
+
+@fragment
+fn frag_picking(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+    out.face = in.face;
+    return out;
+}
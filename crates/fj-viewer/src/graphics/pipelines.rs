@@ -9,6 +9,8 @@ use super::{
 #[derive(Debug)]
 pub struct Pipelines {
     pub model: Pipeline,
+    pub model_transparent: Pipeline,
+    pub model_opaque: Pipeline,
     pub mesh: Pipeline,
     pub lines: Pipeline,
 }
@@ -36,6 +38,25 @@ impl Pipelines {
                 wgpu::PrimitiveTopology::TriangleList,
                 wgpu::PolygonMode::Fill,
                 color_format,
+                false,
+            ),
+            model_transparent: Pipeline::new(
+                device,
+                &pipeline_layout,
+                shaders.model(),
+                wgpu::PrimitiveTopology::TriangleList,
+                wgpu::PolygonMode::Fill,
+                color_format,
+                true,
+            ),
+            model_opaque: Pipeline::new(
+                device,
+                &pipeline_layout,
+                shaders.model_opaque(),
+                wgpu::PrimitiveTopology::TriangleList,
+                wgpu::PolygonMode::Fill,
+                color_format,
+                false,
             ),
             mesh: Pipeline::new(
                 device,
@@ -44,6 +65,7 @@ impl Pipelines {
                 wgpu::PrimitiveTopology::TriangleList,
                 wgpu::PolygonMode::Line,
                 color_format,
+                false,
             ),
             lines: Pipeline::new(
                 device,
@@ -52,6 +74,7 @@ impl Pipelines {
                 wgpu::PrimitiveTopology::LineList,
                 wgpu::PolygonMode::Line,
                 color_format,
+                false,
             ),
         }
     }
@@ -68,7 +91,12 @@ impl Pipeline {
         topology: wgpu::PrimitiveTopology,
         polygon_mode: wgpu::PolygonMode,
         color_format: wgpu::TextureFormat,
+        transparent: bool,
     ) -> Self {
+        // Transparent triangles are drawn after all opaque ones. They must not
+        // hide what's behind them, so they don't write to the depth buffer.
+        // Alpha to coverage would turn their transparency into a dither
+        // pattern, so it is left out too.
         let pipeline =
             device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: None,
@@ -97,7 +125,7 @@ impl Pipeline {
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
                     format: DEPTH_FORMAT,
-                    depth_write_enabled: true,
+                    depth_write_enabled: !transparent,
                     depth_compare: wgpu::CompareFunction::LessEqual,
                     stencil: wgpu::StencilState {
                         front: wgpu::StencilFaceState::IGNORE,
@@ -110,7 +138,7 @@ impl Pipeline {
                 multisample: wgpu::MultisampleState {
                     count: SAMPLE_COUNT,
                     mask: !0,
-                    alpha_to_coverage_enabled: true,
+                    alpha_to_coverage_enabled: !transparent,
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: shader.module,
@@ -4,17 +4,25 @@ use thiserror::Error;
 use tracing::debug;
 use wgpu::util::DeviceExt as _;
 
+use fj_interop::mesh::FaceId;
+
 use crate::{
     camera::Camera,
     gui::Gui,
-    screen::{Screen, ScreenSize},
+    screen::{NormalizedScreenPosition, Screen, ScreenSize},
 };
 
 use super::{
-    draw_config::DrawConfig, drawables::Drawables, geometries::Geometries,
-    navigation_cube::NavigationCubeRenderer, pipelines::Pipelines,
-    transform::Transform, uniforms::Uniforms, vertices::Vertices, DEPTH_FORMAT,
-    SAMPLE_COUNT,
+    draw_config::DrawConfig,
+    drawables::Drawables,
+    geometries::Geometries,
+    navigation_cube::NavigationCubeRenderer,
+    picking::{PickVertices, Picking},
+    pipelines::Pipelines,
+    transform::Transform,
+    uniforms::Uniforms,
+    vertices::Vertices,
+    DEPTH_FORMAT, SAMPLE_COUNT,
 };
 
 /// Graphics rendering state and target abstraction
@@ -34,8 +42,11 @@ pub struct Renderer {
 
     geometries: Geometries,
     pipelines: Pipelines,
+    picking: Picking,
 
     navigation_cube_renderer: NavigationCubeRenderer,
+
+    stats: RenderStats,
 }
 
 impl Renderer {
@@ -189,6 +200,8 @@ impl Renderer {
             Geometries::new(&device, &Vertices::empty(), &Vertices::empty());
         let pipelines =
             Pipelines::new(&device, &bind_group_layout, color_format);
+        let picking =
+            Picking::new(&device, &bind_group_layout, [width, height]);
 
         let navigation_cube_renderer =
             NavigationCubeRenderer::new(&device, &queue, &surface_config);
@@ -208,8 +221,11 @@ impl Renderer {
 
             geometries,
             pipelines,
+            picking,
 
             navigation_cube_renderer,
+
+            stats: RenderStats::default(),
         })
     }
 
@@ -218,8 +234,52 @@ impl Renderer {
     }
 
     /// Updates the geometry of the model being rendered.
-    pub fn update_geometry(&mut self, mesh: Vertices, lines: Vertices) {
-        self.geometries = Geometries::new(&self.device, &mesh, &lines);
+    ///
+    /// Only the parts of the geometry that have changed since the last update
+    /// are uploaded to the GPU.
+    pub fn update_geometry(
+        &mut self,
+        mesh: Vertices,
+        lines: Vertices,
+        faces: PickVertices,
+    ) {
+        self.geometries
+            .update(&self.device, &self.queue, &mesh, &lines);
+        self.picking
+            .update_geometry(&self.device, &self.queue, &faces);
+    }
+
+    /// Find the face that is visible at the given cursor position
+    ///
+    /// This renders the IDs of the faces visible to the camera, but only reads
+    /// back the pixel under the cursor. Returns `None`, if there is no face
+    /// under the cursor.
+    pub fn pick(
+        &self,
+        camera: &Camera,
+        cursor: NormalizedScreenPosition,
+    ) -> Option<FaceId> {
+        self.write_uniforms(camera);
+
+        let [width, height] =
+            [self.surface_config.width, self.surface_config.height]
+                .map(f64::from);
+        let aspect_ratio = width / height;
+
+        // Normalized screen positions have the aspect ratio baked into their
+        // y coordinate.
+        let x = (cursor.x + 1.) / 2. * width;
+        let y = (1. - cursor.y * aspect_ratio) / 2. * height;
+        if x < 0. || y < 0. {
+            return None;
+        }
+
+        self.picking.pick(
+            &self.device,
+            &self.queue,
+            &self.bind_group,
+            [x as u32, y as u32],
+        )
     }
 
     /// Resizes the render surface.
@@ -236,6 +296,8 @@ impl Renderer {
             Self::create_frame_buffer(&self.device, &self.surface_config);
         self.depth_view =
             Self::create_depth_buffer(&self.device, &self.surface_config);
+        self.picking
+            .handle_resize(&self.device, [size.width, size.height]);
     }
 
     /// Draws the renderer, camera, and config state to the window.
@@ -246,18 +308,13 @@ impl Renderer {
         scale_factor: f32,
         gui: &mut Gui,
     ) -> Result<(), DrawError> {
-        let aspect_ratio = f64::from(self.surface_config.width)
-            / f64::from(self.surface_config.height);
-        let uniforms = Uniforms {
-            transform: Transform::for_vertices(camera, aspect_ratio),
-            transform_normals: Transform::for_normals(camera),
-        };
+        let aspect_ratio = self.write_uniforms(camera);
 
-        self.queue.write_buffer(
-            &self.uniform_buffer,
-            0,
-            bytemuck::cast_slice(&[uniforms]),
-        );
+        // Bodies outside of the view are skipped, which matters for large
+        // assemblies, of which only a few parts are visible when zoomed in.
+        let transform = Transform::for_vertices(camera, aspect_ratio);
+        let mesh_ranges = self.geometries.mesh.visible_indices(&transform);
+        let lines_ranges = self.geometries.lines.visible_indices(&transform);
 
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
@@ -295,6 +352,8 @@ impl Renderer {
             &screen_descriptor,
         );
 
+        let mut draw_calls = 0;
+
         // Need this block here, as a render pass only takes effect once it's
         // dropped.
         {
@@ -328,15 +387,30 @@ impl Renderer {
             let drawables = Drawables::new(&self.geometries, &self.pipelines);
 
             if config.draw_model {
-                drawables.model.draw(&mut render_pass);
+                if config.draw_transparency {
+                    draw_calls += drawables
+                        .model
+                        .draw(&mut render_pass, &mesh_ranges.opaque);
+                    draw_calls += drawables
+                        .model_transparent
+                        .draw(&mut render_pass, &mesh_ranges.transparent);
+                } else {
+                    draw_calls += drawables
+                        .model_opaque
+                        .draw(&mut render_pass, mesh_ranges.all());
+                }
             }
 
             if self.is_line_drawing_available() {
                 if config.draw_mesh {
-                    drawables.mesh.draw(&mut render_pass);
+                    draw_calls += drawables
+                        .mesh
+                        .draw(&mut render_pass, mesh_ranges.all());
                 }
                 if config.draw_debug {
-                    drawables.lines.draw(&mut render_pass);
+                    draw_calls += drawables
+                        .lines
+                        .draw(&mut render_pass, lines_ranges.all());
                 }
             }
 
@@ -354,6 +428,11 @@ impl Renderer {
         let command_buffer = encoder.finish();
         self.queue.submit(Some(command_buffer));
 
+        self.stats = RenderStats {
+            draw_calls,
+            buffer_size: self.geometries.buffer_size(),
+        };
+
         debug!("Presenting...");
         surface_texture.present();
 
@@ -361,6 +440,23 @@ impl Renderer {
         Ok(())
     }
 
+    fn write_uniforms(&self, camera: &Camera) -> f64 {
+        let aspect_ratio = f64::from(self.surface_config.width)
+            / f64::from(self.surface_config.height);
+        let uniforms = Uniforms {
+            transform: Transform::for_vertices(camera, aspect_ratio),
+            transform_normals: Transform::for_normals(camera),
+        };
+
+        self.queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[uniforms]),
+        );
+
+        aspect_ratio
+    }
+
     fn create_frame_buffer(
         device: &wgpu::Device,
         surface_config: &wgpu::SurfaceConfiguration,
@@ -404,12 +500,29 @@ impl Renderer {
         texture.create_view(&wgpu::TextureViewDescriptor::default())
     }
 
+    /// Access statistics about the last frame that has been drawn
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
     /// Returns true if the renderer's adapter can draw lines
     pub fn is_line_drawing_available(&self) -> bool {
         self.features.contains(wgpu::Features::POLYGON_MODE_LINE)
     }
 }
 
+/// Statistics about a frame that the renderer has drawn
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderStats {
+    /// The number of draw calls for the model and its debug information
+    ///
+    /// Draw calls for the GUI and the navigation cube are not included.
+    pub draw_calls: usize,
+
+    /// The size of the GPU buffers that hold the geometry, in bytes
+    pub buffer_size: u64,
+}
+
 /// Error describing the set of render surface initialization errors
 #[derive(Error, Debug)]
 pub enum RendererInitError {
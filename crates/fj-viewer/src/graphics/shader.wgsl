@@ -35,17 +35,31 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 const pi: f32 = 3.14159265359;
 
-@fragment
-fn frag_model(in: VertexOutput) -> FragmentOutput {
+fn shading(normal: vec3<f32>) -> f32 {
     let light = vec3<f32>(0.0, 0.0, -1.0);
 
-    let angle = acos(dot(light, -in.normal));
+    let angle = acos(dot(light, -normal));
     let f_angle = angle / (pi * 0.75);
 
-    let f_normal = max(1.0 - f_angle, 0.0);
+    return max(1.0 - f_angle, 0.0);
+}
+
+@fragment
+fn frag_model(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+    out.color = vec4<f32>(in.color.rgb * shading(in.normal), in.color.a);
+
+    return out;
+}
+
+// Renders transparent colors as if they were opaque.
+@fragment
+fn frag_model_opaque(in: VertexOutput) -> FragmentOutput {
+    // Undo the premultiplication of the vertex shader.
+    let rgb = in.color.rgb / max(in.color.a, 1.0 / 255.0);
 
     var out: FragmentOutput;
-    out.color = vec4<f32>(in.color.rgb * f_normal, in.color.a);
+    out.color = vec4<f32>(rgb * shading(in.normal), 1.0);
 
     return out;
 }
@@ -22,6 +22,13 @@ impl Shaders {
         }
     }
 
+    pub fn model_opaque(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            frag_entry: "frag_model_opaque",
+        }
+    }
+
     pub fn mesh(&self) -> Shader {
         Shader {
             module: &self.0,
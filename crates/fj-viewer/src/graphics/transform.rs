@@ -1,4 +1,5 @@
 use bytemuck::{Pod, Zeroable};
+use fj_math::Point;
 
 use crate::camera::Camera;
 
@@ -28,6 +29,19 @@ impl Transform {
         Self(transform.map(|scalar| scalar.into_f32()))
     }
 
+    /// Transform a point into clip space
+    ///
+    /// Only valid for transforms computed by [`Transform::for_vertices`].
+    pub fn to_clip_space(&self, point: Point<3>) -> [f32; 4] {
+        let m = self.0;
+        let [x, y, z] = point.coords.components.map(|s| s.into_f32());
+
+        // The matrix is stored in column-major order.
+        [0, 1, 2, 3].map(|row| {
+            m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row]
+        })
+    }
+
     /// Compute transform used for normals
     ///
     /// This method is only relevant for the graphics code. The returned
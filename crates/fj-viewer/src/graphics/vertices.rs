@@ -1,14 +1,20 @@
+use std::collections::BTreeMap;
+
 use bytemuck::{Pod, Zeroable};
 use fj_interop::{
-    debug::DebugInfo,
-    mesh::{Index, Mesh},
+    analysis::{Curvature, Overhangs, TriangleOverhang, VertexCurvature},
+    debug::{DebugInfo, FaceDebugInfo},
+    mesh::{Color, Index, Mesh, DEFAULT_CREASE_ANGLE},
 };
-use fj_math::{Point, Vector};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use super::culling::Body;
 
 #[derive(Debug)]
 pub struct Vertices {
     vertices: Vec<Vertex>,
     indices: Vec<Index>,
+    bodies: Vec<Body>,
 }
 
 impl Vertices {
@@ -16,6 +22,7 @@ impl Vertices {
         Self {
             vertices: Vec::new(),
             indices: Vec::new(),
+            bodies: Vec::new(),
         }
     }
 
@@ -27,6 +34,13 @@ impl Vertices {
         self.indices.as_slice()
     }
 
+    /// Access the bodies that make up the vertices
+    ///
+    /// This is empty for vertices that don't come from a mesh.
+    pub fn bodies(&self) -> &[Body] {
+        self.bodies.as_slice()
+    }
+
     pub fn push_line(
         &mut self,
         line: [Point<3>; 2],
@@ -72,19 +86,130 @@ impl Vertices {
     }
 }
 
-impl From<&Mesh<fj_math::Point<3>>> for Vertices {
-    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+impl Vertices {
+    /// Create vertices for a mesh, colored according to its overhangs
+    ///
+    /// Triangles that don't need support are gray. Triangles that do are
+    /// colored from yellow to red, depending on how steep they are.
+    pub fn from_overhangs(
+        mesh: &Mesh<fj_math::Point<3>>,
+        overhangs: &Overhangs,
+    ) -> Self {
+        let triangles = mesh
+            .triangles()
+            .zip(&overhangs.triangles)
+            .zip(mesh.normals(DEFAULT_CREASE_ANGLE))
+            .map(|((triangle, overhang), normals)| {
+                let color = overhang_color(overhang, overhangs.threshold);
+                (triangle.inner, [color; 3], normals)
+            });
+
+        Self::from_triangles(triangles)
+    }
+
+    /// Create vertices for a mesh, colored according to its curvature
+    ///
+    /// `value` selects the kind of curvature. Negative values are blue,
+    /// positive ones are red. Flat regions are white.
+    pub fn from_curvature(
+        mesh: &Mesh<fj_math::Point<3>>,
+        curvature: &Curvature,
+        value: impl Fn(&VertexCurvature) -> Scalar,
+    ) -> Self {
+        let values = curvature.vertices.iter().map(value).collect::<Vec<_>>();
+
+        // Sharp edges and corners have extreme curvature values. Scale the
+        // colors to most vertices instead, so they don't wash out the rest.
+        let mut magnitudes =
+            values.iter().map(|value| value.abs()).collect::<Vec<_>>();
+        magnitudes.sort();
+        let scale = magnitudes
+            .get(magnitudes.len() * 95 / 100)
+            .copied()
+            .filter(|scale| *scale > Scalar::ZERO)
+            .unwrap_or(Scalar::ONE);
+
+        let indices = mesh.indices().collect::<Vec<_>>();
+        let triangles = mesh
+            .triangles()
+            .zip(indices.chunks(3))
+            .zip(mesh.normals(DEFAULT_CREASE_ANGLE))
+            .map(|((triangle, indices), normals)| {
+                let colors = [0, 1, 2].map(|i| {
+                    let value = values
+                        .get(indices[i] as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    curvature_color(value / scale)
+                });
+                (triangle.inner, colors, normals)
+            });
+
+        Self::from_triangles(triangles)
+    }
+
+    fn from_triangles(
+        triangles: impl Iterator<
+            Item = (fj_math::Triangle<3>, [Color; 3], [Vector<3>; 3]),
+        >,
+    ) -> Self {
+        let triangles = triangles.collect::<Vec<_>>();
+
         let mut m = Mesh::new();
+        let mut bodies = Vec::new();
+        let mut num_indices = 0;
+
+        let groups = group_into_bodies(&triangles);
+        let is_transparent = |i: &usize| {
+            let (_, colors, _) = &triangles[*i];
+            !colors.iter().all(Color::is_opaque)
+        };
+
+        // Triangles are sorted by body, so each body is a contiguous range of
+        // indices, which can be drawn separately. Transparent triangles come
+        // after all opaque ones, as they need to be drawn last.
+        let bodies_by_transparency =
+            [false, true].into_iter().flat_map(|transparent| {
+                groups.iter().map(move |group| {
+                    let body = group
+                        .iter()
+                        .copied()
+                        .filter(|i| is_transparent(i) == transparent)
+                        .collect::<Vec<_>>();
+                    (body, transparent)
+                })
+            });
 
-        for triangle in mesh.triangles() {
-            let [a, b, c] = triangle.inner.points();
+        for (body, transparent) in bodies_by_transparency {
+            let start = num_indices;
+            let mut aabb: Option<Aabb<3>> = None;
 
-            let normal = (b - a).cross(&(c - a)).normalize();
-            let color = triangle.color;
+            for i in body {
+                let (triangle, colors, normals) = triangles[i];
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
+                let corners =
+                    triangle.points().into_iter().zip(colors).zip(normals);
+                for ((point, color), normal) in corners {
+                    m.push_vertex((point, normal, color));
+                    num_indices += 1;
+
+                    aabb = Some(match aabb {
+                        Some(aabb) => aabb.include_point(&point),
+                        None => Aabb {
+                            min: point,
+                            max: point,
+                        },
+                    });
+                }
+            }
+
+            if let Some(aabb) = aabb {
+                bodies.push(Body {
+                    indices: start..num_indices,
+                    aabb,
+                    transparent,
+                });
+            }
         }
 
         let vertices = m
@@ -92,13 +217,66 @@ impl From<&Mesh<fj_math::Point<3>>> for Vertices {
             .map(|(vertex, normal, color)| Vertex {
                 position: vertex.into(),
                 normal: normal.into(),
-                color: color.0.map(|v| f32::from(v) / 255.0),
+                color: color_to_f32(color),
             })
             .collect();
 
         let indices = m.indices().collect();
 
-        Self { vertices, indices }
+        Self {
+            vertices,
+            indices,
+            bodies,
+        }
+    }
+}
+
+/// Group triangles into bodies
+///
+/// Triangles that share a vertex position are part of the same body. Returns
+/// the indices of the triangles of each body.
+fn group_into_bodies(
+    triangles: &[(fj_math::Triangle<3>, [Color; 3], [Vector<3>; 3])],
+) -> Vec<Vec<usize>> {
+    fn find(parents: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[i] = root;
+        root
+    }
+
+    let mut parents = (0..triangles.len()).collect::<Vec<_>>();
+    let mut triangle_at_point = BTreeMap::new();
+
+    for (i, (triangle, _, _)) in triangles.iter().enumerate() {
+        for point in triangle.points() {
+            let j = *triangle_at_point.entry(point).or_insert(i);
+
+            let a = find(&mut parents, i);
+            let b = find(&mut parents, j);
+            parents[a] = b;
+        }
+    }
+
+    let mut bodies = BTreeMap::<_, Vec<_>>::new();
+    for i in 0..triangles.len() {
+        bodies.entry(find(&mut parents, i)).or_default().push(i);
+    }
+
+    bodies.into_values().collect()
+}
+
+impl From<&Mesh<fj_math::Point<3>>> for Vertices {
+    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+        Self::from_triangles(
+            mesh.triangles()
+                .zip(mesh.normals(DEFAULT_CREASE_ANGLE))
+                .map(|(triangle, normals)| {
+                    (triangle.inner, [triangle.color; 3], normals)
+                }),
+        )
     }
 }
 
@@ -128,10 +306,45 @@ impl From<&DebugInfo> for Vertices {
             }
         }
 
+        for (point, color) in &debug_info.points {
+            self_.push_cross(*point, [0.; 3], color_to_f32(*color));
+        }
+        for (segment, color) in &debug_info.lines {
+            self_.push_line(segment.points(), [0.; 3], color_to_f32(*color));
+        }
+
         self_
     }
 }
 
+impl Vertices {
+    /// Add the intermediate results of the kernel's algorithms for a face
+    ///
+    /// Triangle edges are gray, approximation points are blue, and the
+    /// intersections with other faces are magenta.
+    pub fn push_face_debug_info(&mut self, face_debug_info: &FaceDebugInfo) {
+        let normal = [0.; 3];
+
+        let gray = [0.5, 0.5, 0.5, 1.];
+        let blue = [0., 0., 1., 1.];
+        let magenta = [1., 0., 1., 1.];
+
+        for triangle in &face_debug_info.triangles {
+            let [a, b, c] = triangle.points();
+
+            for line in [[a, b], [b, c], [c, a]] {
+                self.push_line(line, normal, gray);
+            }
+        }
+        for &point in &face_debug_info.approx_points {
+            self.push_cross(point, normal, blue);
+        }
+        for segment in &face_debug_info.intersections {
+            self.push_line(segment.points(), normal, magenta);
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct Vertex {
@@ -139,3 +352,43 @@ pub struct Vertex {
     pub normal: [f32; 3],
     pub color: [f32; 4],
 }
+
+fn color_to_f32(color: Color) -> [f32; 4] {
+    color.0.map(|v| f32::from(v) / 255.0)
+}
+
+fn overhang_color(overhang: &TriangleOverhang, threshold: Scalar) -> Color {
+    if !overhang.needs_support {
+        return Color([200, 200, 200, 255]);
+    }
+
+    let steepness =
+        (overhang.angle - threshold) / (Scalar::PI / 2. - threshold);
+    let steepness = steepness.into_f64().clamp(0., 1.);
+
+    let yellow = [255., 220., 0.];
+    let red = [220., 0., 0.];
+
+    let mut color = [255; 4];
+    for (channel, (yellow, red)) in
+        color.iter_mut().zip(yellow.into_iter().zip(red))
+    {
+        *channel = (yellow + (red - yellow) * steepness) as u8;
+    }
+
+    Color(color)
+}
+
+fn curvature_color(value: Scalar) -> Color {
+    let value = value.into_f64().clamp(-1., 1.);
+
+    // Fade from white to red for positive values, to blue for negative ones.
+    let fade = (255. * (1. - value.abs())) as u8;
+    let color = if value >= 0. {
+        [255, fade, fade, 255]
+    } else {
+        [fade, fade, 255, 255]
+    };
+
+    Color(color)
+}
@@ -14,7 +14,7 @@
 //!
 //! <https://github.com/gfx-rs/wgpu/issues/1492>
 
-use std::path::PathBuf;
+use std::{collections::BTreeSet, path::PathBuf, time::Duration};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::env::current_dir;
@@ -22,11 +22,19 @@ use std::env::current_dir;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 
-use fj_math::{Aabb, Scalar};
+use chrono::{DateTime, Local};
+use fj_interop::{
+    assembly::Part,
+    processed_shape::ProcessedShape,
+    status::{Level, Source, StatusMessage},
+    units::Units,
+};
+use fj_math::{Scalar, Tolerance};
 
 use crate::{
-    graphics::{DrawConfig, DEPTH_FORMAT, SAMPLE_COUNT},
-    StatusReport,
+    ansi,
+    graphics::{Coloring, DrawConfig, DEPTH_FORMAT, SAMPLE_COUNT},
+    RenderStats, StatusReport,
 };
 
 /// The GUI
@@ -35,6 +43,20 @@ pub struct Gui {
     renderer: egui_wgpu::Renderer,
     options: Options,
     egui_output: Option<egui::FullOutput>,
+    labels: Vec<ScreenLabel>,
+    axes: Option<ScreenAxes>,
+    grid: Option<ScreenGrid>,
+    units: Option<Units>,
+    precision: usize,
+    bookmarks: Vec<String>,
+    new_bookmark_name: String,
+    bookmark_requests: Vec<BookmarkRequest>,
+    parts: Vec<Part>,
+    export: ExportDialog,
+    export_request: Option<ExportRequest>,
+    toast: Option<StatusMessage>,
+    performance: Option<PerformanceStats>,
+    compiler_output: Option<egui::text::LayoutJob>,
 }
 
 impl Gui {
@@ -73,6 +95,20 @@ impl Gui {
             renderer,
             options: Options::default(),
             egui_output: None,
+            labels: Vec::new(),
+            axes: None,
+            grid: None,
+            units: None,
+            precision: 3,
+            bookmarks: Vec::new(),
+            new_bookmark_name: String::new(),
+            bookmark_requests: Vec::new(),
+            parts: Vec::new(),
+            export: ExportDialog::default(),
+            export_request: None,
+            toast: None,
+            performance: None,
+            compiler_output: None,
         }
     }
 
@@ -81,22 +117,129 @@ impl Gui {
         &self.context
     }
 
+    /// Set the labels that are drawn over the model
+    ///
+    /// They stay until new labels are set.
+    pub(crate) fn set_labels(&mut self, labels: Vec<ScreenLabel>) {
+        self.labels = labels;
+    }
+
+    /// Set the axes that are drawn over the model
+    ///
+    /// They stay until new axes are set. Nothing is drawn, if they are `None`.
+    pub(crate) fn set_axes(&mut self, axes: Option<ScreenAxes>) {
+        self.axes = axes;
+    }
+
+    /// Set the reference grid that is drawn over the model
+    ///
+    /// It stays until a new grid is set. Nothing is drawn, if it is `None`.
+    pub(crate) fn set_grid(&mut self, grid: Option<ScreenGrid>) {
+        self.grid = grid;
+    }
+
+    /// Set the performance statistics that are shown in an overlay
+    ///
+    /// They stay until new statistics are set. Nothing is shown, if they are
+    /// `None`.
+    pub(crate) fn set_performance(
+        &mut self,
+        performance: Option<PerformanceStats>,
+    ) {
+        self.performance = performance;
+    }
+
+    /// Set the units that lengths are displayed in
+    pub(crate) fn set_units(&mut self, units: Option<Units>) {
+        self.units = units;
+    }
+
+    /// Set the names of the camera bookmarks that are listed
+    ///
+    /// They stay until new names are set.
+    pub(crate) fn set_bookmarks(&mut self, names: Vec<String>) {
+        self.bookmarks = names;
+    }
+
+    /// Take the changes to the camera bookmarks that the user has requested
+    pub(crate) fn take_bookmark_requests(&mut self) -> Vec<BookmarkRequest> {
+        std::mem::take(&mut self.bookmark_requests)
+    }
+
+    /// Set the parts of the assembly that are listed
+    ///
+    /// The user can move and hide them. Use [`Gui::parts`] to access the
+    /// result.
+    pub(crate) fn set_parts(&mut self, parts: Vec<Part>) {
+        self.parts = parts;
+    }
+
+    /// Access the parts of the assembly, as the user has placed them
+    pub(crate) fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    /// Take the export that the user has requested, if any
+    pub(crate) fn take_export_request(&mut self) -> Option<ExportRequest> {
+        self.export_request.take()
+    }
+
+    /// Show a status message briefly, at the bottom of the screen
+    ///
+    /// Replaces any message that is still being shown.
+    pub(crate) fn show_toast(&mut self, message: StatusMessage) {
+        self.toast = Some(message);
+    }
+
+    /// Set the output of the compiler, after compiling the model has failed
+    ///
+    /// The output is shown until it is set to `None`, or the user closes it.
+    /// ANSI escape codes in the output are translated into colors.
+    pub(crate) fn set_compiler_output(&mut self, output: Option<&str>) {
+        self.compiler_output = output.map(|output| {
+            ansi::layout_job(
+                output,
+                egui::FontId::monospace(12.0),
+                egui::Color32::LIGHT_GRAY,
+            )
+        });
+    }
+
     pub(crate) fn update(
         &mut self,
         pixels_per_point: f32,
         egui_input: egui::RawInput,
         config: &mut DrawConfig,
-        aabb: &Aabb<3>,
+        shape: Option<&ProcessedShape>,
         line_drawing_available: bool,
-        state: GuiState,
+        mut state: GuiState,
     ) -> Option<PathBuf> {
         self.context.set_pixels_per_point(pixels_per_point);
         self.context.begin_frame(egui_input);
 
+        let p = self.precision;
+        let symbol = self
+            .units
+            .map(|units| format!(" {}", units.symbol()))
+            .unwrap_or_default();
+
         let bounding_box_size = {
-            let [x, y, z] = aabb.size().components.map(Scalar::into_f32);
-            format!("Model bounding box size:\n{x:0.1} {y:0.1} {z:0.1}")
+            let aabb = shape.map(|shape| shape.aabb).unwrap_or_default();
+            let [x, y, z] = aabb.size().components.map(Scalar::into_f64);
+            format!("Model bounding box size:\n{x:.p$} {y:.p$} {z:.p$}{symbol}")
         };
+        let dimensions = shape.map(|shape| format!("{:.p$}", shape.dimensions));
+        let mass_properties =
+            shape.map(|shape| format!("{:.p$}", shape.mass_properties));
+        let grid = self.grid.as_ref().map(|grid| {
+            let mut readout = format!("Grid spacing: {}{symbol}", grid.spacing);
+            if let Some([x, y]) = grid.cursor {
+                readout.push_str(&format!(
+                    "\nCursor on grid: {x:.p$} {y:.p$}{symbol}"
+                ));
+            }
+            readout
+        });
 
         egui::SidePanel::left("fj-left-panel").show(&self.context, |ui| {
             ui.add_space(16.0);
@@ -114,12 +257,140 @@ impl Gui {
                     .on_disabled_hover_text(
                         "Rendering device does not have line rendering feature support"
                     );
+                ui.checkbox(&mut config.draw_transparency, "Render transparency")
+                    .on_hover_text_at_pointer("Toggle with 5");
+                ui.checkbox(&mut config.draw_axes, "Render axes")
+                    .on_hover_text_at_pointer("Toggle with 7");
+                ui.checkbox(&mut config.draw_grid, "Render grid")
+                    .on_hover_text_at_pointer("Toggle with 8");
+                ui.checkbox(&mut config.draw_performance, "Show performance")
+                    .on_hover_text_at_pointer("Toggle with 9");
+                ui.add_enabled(line_drawing_available, egui::Checkbox::new(&mut config.debug_algorithms, "Debug algorithms"))
+                    .on_hover_text_at_pointer(
+                        "Toggle with 6. Select a face with the middle mouse button."
+                    )
+                    .on_disabled_hover_text(
+                        "Rendering device does not have line rendering feature support"
+                    );
+                egui::ComboBox::from_label("Coloring")
+                    .selected_text(config.coloring.description())
+                    .show_ui(ui, |ui| {
+                        for coloring in Coloring::ALL {
+                            ui.selectable_value(
+                                &mut config.coloring,
+                                coloring,
+                                coloring.description(),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text_at_pointer("Cycle with 4");
+                if ui
+                    .add_enabled(
+                        state.model_available,
+                        egui::Button::new("Export…"),
+                    )
+                    .clicked()
+                {
+                    self.export.open = true;
+                }
+                ui.add_space(16.0);
+                match self.units {
+                    Some(units) => ui.label(format!("Units: {units}")),
+                    None => ui.label("Units: not declared by model"),
+                };
+                ui.add(
+                    egui::Slider::new(&mut self.precision, 0..=6)
+                        .text("Decimal places"),
+                );
                 ui.add_space(16.0);
                 ui.strong(bounding_box_size);
+                if let Some(dimensions) = dimensions {
+                    ui.label(dimensions);
+                }
+                if let Some(mass_properties) = mass_properties {
+                    ui.add_space(16.0);
+                    ui.label(mass_properties);
+                }
+                if let Some(grid) = grid {
+                    ui.add_space(16.0);
+                    ui.label(grid);
+                }
             });
 
             ui.add_space(16.0);
 
+            ui.group(|ui| {
+                ui.strong("Camera bookmarks");
+                for (i, name) in self.bookmarks.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let jump = ui.button(name);
+                        let jump = if i < 9 {
+                            jump.on_hover_text_at_pointer(format!(
+                                "Jump with F{}",
+                                i + 1
+                            ))
+                        } else {
+                            jump
+                        };
+                        if jump.clicked() {
+                            self.bookmark_requests
+                                .push(BookmarkRequest::Jump(i));
+                        }
+
+                        if ui
+                            .small_button("x")
+                            .on_hover_text_at_pointer("Remove bookmark")
+                            .clicked()
+                        {
+                            self.bookmark_requests
+                                .push(BookmarkRequest::Remove(i));
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.new_bookmark_name);
+
+                    let name = self.new_bookmark_name.trim();
+                    if ui
+                        .add_enabled(
+                            !name.is_empty(),
+                            egui::Button::new("Save view"),
+                        )
+                        .clicked()
+                    {
+                        self.bookmark_requests
+                            .push(BookmarkRequest::Save(name.to_string()));
+                        self.new_bookmark_name.clear();
+                    }
+                });
+            });
+
+            ui.add_space(16.0);
+
+            if !self.parts.is_empty() {
+                ui.group(|ui| {
+                    ui.strong("Parts");
+                    for part in &mut self.parts {
+                        ui.checkbox(&mut part.visible, &part.name);
+                        ui.horizontal(|ui| {
+                            for (axis, offset) in
+                                ["x", "y", "z"].into_iter().zip(&mut part.offset)
+                            {
+                                ui.add_enabled(
+                                    part.visible,
+                                    egui::DragValue::new(offset)
+                                        .prefix(format!("{axis}: "))
+                                        .speed(0.1),
+                                );
+                            }
+                        });
+                    }
+                });
+
+                ui.add_space(16.0);
+            }
+
             {
                 ui.group(|ui| {
                     ui.checkbox(
@@ -237,21 +508,239 @@ impl Gui {
             ui.add_space(16.0);
         });
 
+        let painter = self.context.layer_painter(egui::LayerId::background());
+        if let Some(grid) = &self.grid {
+            let stroke = egui::Stroke::new(1.0, egui::Color32::LIGHT_GRAY);
+            for &line in &grid.lines {
+                painter.line_segment(line, stroke);
+            }
+        }
+        for label in &self.labels {
+            painter.text(
+                label.position,
+                egui::Align2::LEFT_BOTTOM,
+                &label.text,
+                egui::FontId::monospace(14.0),
+                label.color,
+            );
+        }
+        if let Some(axes) = &self.axes {
+            axes.draw(&painter, self.context.available_rect());
+        }
+
+        let log_filter = &mut self.options.log_filter;
         egui::Window::new("Status")
             .min_width(400.0)
             .min_height(200.0)
             .collapsible(true)
             .resizable(true)
             .show(&self.context, |ui| {
-                egui::ScrollArea::both().show(ui, |ui| {
-                    ui.add(egui::Label::new(
-                        egui::RichText::new(state.status.status())
-                            .monospace()
-                            .color(egui::Color32::WHITE),
-                    ))
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source("log-level")
+                        .selected_text(log_filter.min_level.description())
+                        .show_ui(ui, |ui| {
+                            for level in Level::ALL {
+                                ui.selectable_value(
+                                    &mut log_filter.min_level,
+                                    level,
+                                    level.description(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text_at_pointer(
+                            "Least important level shown",
+                        );
+
+                    for source in Source::ALL {
+                        let mut shown = log_filter.sources.contains(&source);
+                        if ui
+                            .checkbox(&mut shown, source.description())
+                            .changed()
+                        {
+                            if shown {
+                                log_filter.sources.insert(source);
+                            } else {
+                                log_filter.sources.remove(&source);
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut state.status.echo, "Echo to terminal")
+                        .on_hover_text_at_pointer("Also log to the terminal");
+                    if ui.button("Clear").clicked() {
+                        state.status.clear_messages();
+                    }
                 });
+
+                if let Some(progress) = state.status.progress() {
+                    ui.add(egui::ProgressBar::new(progress.fraction()).text(
+                        format!(
+                            "{} ({}/{})",
+                            progress.stage.description(),
+                            progress.completed,
+                            progress.total,
+                        ),
+                    ));
+                }
+
+                egui::ScrollArea::both()
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        let messages = state
+                            .status
+                            .messages()
+                            .filter(|message| log_filter.shows(message));
+
+                        for message in messages {
+                            let time = DateTime::<Local>::from(message.time)
+                                .format("[%H:%M:%S.%3f]");
+                            let color = match message.level {
+                                Level::Info => egui::Color32::WHITE,
+                                Level::Warn => egui::Color32::YELLOW,
+                                Level::Error => egui::Color32::LIGHT_RED,
+                            };
+
+                            ui.add(egui::Label::new(
+                                egui::RichText::new(format!(
+                                    "{time} {}: {}",
+                                    message.source.description(),
+                                    message.text,
+                                ))
+                                .monospace()
+                                .color(color),
+                            ));
+                        }
+                    });
             });
 
+        let mut open = self.export.open;
+        let mut requested = false;
+        egui::Window::new("Export")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(&self.context, |ui| {
+                let export = &mut self.export;
+                let (name, extension) = ExportDialog::FORMATS[export.format];
+
+                egui::ComboBox::from_label("Format")
+                    .selected_text(name)
+                    .show_ui(ui, |ui| {
+                        for (i, (name, _)) in
+                            ExportDialog::FORMATS.into_iter().enumerate()
+                        {
+                            ui.selectable_value(&mut export.format, i, name);
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut export.custom_tolerance, "Tolerance");
+                    ui.add_enabled(
+                        export.custom_tolerance,
+                        egui::DragValue::new(&mut export.tolerance)
+                            .clamp_range(0.0001..=f64::MAX)
+                            .speed(0.001),
+                    );
+                })
+                .response
+                .on_hover_text_at_pointer(
+                    "Use the tolerance of the displayed model, unless checked",
+                );
+                egui::ComboBox::from_label("Up axis")
+                    .selected_text(match export.up_axis {
+                        None => "Format default",
+                        Some(UpAxis::Y) => "Y",
+                        Some(UpAxis::Z) => "Z",
+                    })
+                    .show_ui(ui, |ui| {
+                        for (up_axis, name) in [
+                            (None, "Format default"),
+                            (Some(UpAxis::Y), "Y"),
+                            (Some(UpAxis::Z), "Z"),
+                        ] {
+                            ui.selectable_value(
+                                &mut export.up_axis,
+                                up_axis,
+                                name,
+                            );
+                        }
+                    });
+
+                if ui.button("Save…").clicked() {
+                    if let Some(path) = show_save_dialog(name, extension) {
+                        let tolerance = if export.custom_tolerance {
+                            Tolerance::from_scalar(export.tolerance).ok()
+                        } else {
+                            None
+                        };
+
+                        self.export_request = Some(ExportRequest {
+                            path,
+                            tolerance,
+                            up_axis: export.up_axis,
+                        });
+                        requested = true;
+                    }
+                }
+            });
+        self.export.open = open && !requested;
+
+        if let Some(compiler_output) = &self.compiler_output {
+            let mut open = true;
+            egui::Window::new("Compiler output")
+                .open(&mut open)
+                .default_size([640., 400.])
+                .resizable(true)
+                .show(&self.context, |ui| {
+                    ui.label(
+                        "Compiling the model failed. The model will be \
+                        updated, once it compiles again.",
+                    );
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        ui.add(
+                            egui::Label::new(compiler_output.clone())
+                                .wrap(false),
+                        );
+                    });
+                });
+
+            if !open {
+                self.compiler_output = None;
+            }
+        }
+
+        if let Some(performance) = &self.performance {
+            egui::Window::new("Performance")
+                .anchor(egui::Align2::RIGHT_TOP, [-16., 16.])
+                .resizable(false)
+                .show(&self.context, |ui| performance.show(ui));
+        }
+
+        if let Some(toast) = &self.toast {
+            const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+            if toast.time.elapsed().unwrap_or_default() < TOAST_DURATION {
+                let color = match toast.level {
+                    Level::Info => egui::Color32::WHITE,
+                    Level::Warn => egui::Color32::YELLOW,
+                    Level::Error => egui::Color32::LIGHT_RED,
+                };
+
+                egui::Area::new("toast")
+                    .anchor(egui::Align2::CENTER_BOTTOM, [0., -16.])
+                    .show(&self.context, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(
+                                egui::RichText::new(&toast.text).color(color),
+                            );
+                        });
+                    });
+            } else {
+                self.toast = None;
+            }
+        }
+
         let mut new_model_path = None;
 
         if !state.model_available {
@@ -340,6 +829,19 @@ fn show_file_dialog() -> Option<PathBuf> {
     todo!("Picking folders does not work on wasm32")
 }
 
+fn show_save_dialog(name: &str, extension: &str) -> Option<PathBuf> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return FileDialog::new()
+        .set_directory(current_dir().unwrap_or_else(|_| PathBuf::from("/")))
+        .set_file_name(&format!("model.{extension}"))
+        .add_filter(name, &[extension])
+        .save_file()
+        .map(|path| path.with_extension(extension));
+
+    #[cfg(target_arch = "wasm32")]
+    todo!("Saving files does not work on wasm32")
+}
+
 impl std::fmt::Debug for Gui {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Gui {}")
@@ -353,12 +855,299 @@ pub struct Options {
     pub show_debug_text_example: bool,
     pub show_settings_ui: bool,
     pub show_inspection_ui: bool,
+    pub log_filter: LogFilter,
+}
+
+/// Decides which status messages are shown
+pub struct LogFilter {
+    /// The least important level that is shown
+    pub min_level: Level,
+
+    /// The sources whose messages are shown
+    pub sources: BTreeSet<Source>,
+}
+
+impl LogFilter {
+    fn shows(&self, message: &StatusMessage) -> bool {
+        message.level >= self.min_level
+            && self.sources.contains(&message.source)
+    }
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            min_level: Level::Info,
+            sources: Source::ALL.into_iter().collect(),
+        }
+    }
+}
+
+/// A text label, at a position on the screen
+pub(crate) struct ScreenLabel {
+    /// The position of the label, in points
+    pub position: egui::Pos2,
+
+    /// The text of the label
+    pub text: String,
+
+    /// The color of the label
+    pub color: egui::Color32,
+}
+
+/// The axes, as seen from the camera
+pub(crate) struct ScreenAxes {
+    /// The directions of the x, y, and z axes, in camera space
+    pub directions: [[f32; 3]; 3],
+
+    /// The position of the world origin on the screen, in points
+    ///
+    /// This is `None`, if the origin is behind the camera.
+    pub origin: Option<egui::Pos2>,
+}
+
+impl ScreenAxes {
+    const LABELS: [&'static str; 3] = ["X", "Y", "Z"];
+    const COLORS: [egui::Color32; 3] = [
+        egui::Color32::from_rgb(220, 50, 50),
+        egui::Color32::from_rgb(50, 170, 50),
+        egui::Color32::from_rgb(50, 90, 220),
+    ];
+
+    /// The length of the axes in the triad, in points
+    const LENGTH: f32 = 40.0;
+
+    fn draw(&self, painter: &egui::Painter, area: egui::Rect) {
+        if let Some(origin) = self.origin {
+            let stroke = egui::Stroke::new(1.5, egui::Color32::DARK_GRAY);
+            let size = 6.0;
+
+            painter.circle_stroke(origin, size, stroke);
+            painter.line_segment(
+                [origin - egui::vec2(size, 0.), origin + egui::vec2(size, 0.)],
+                stroke,
+            );
+            painter.line_segment(
+                [origin - egui::vec2(0., size), origin + egui::vec2(0., size)],
+                stroke,
+            );
+        }
+
+        let center =
+            area.right_bottom() - egui::vec2(Self::LENGTH, Self::LENGTH) * 1.5;
+
+        // Draw the axes that point away from the camera first, so the ones
+        // pointing towards it end up on top.
+        let mut axes = [0, 1, 2];
+        axes.sort_by(|&a, &b| {
+            self.directions[a][2].total_cmp(&self.directions[b][2])
+        });
+
+        for i in axes {
+            let [x, y, _] = self.directions[i];
+
+            // The y-axis of camera space points up, while that of the screen
+            // points down.
+            let tip = center + egui::vec2(x, -y) * Self::LENGTH;
+
+            painter.line_segment(
+                [center, tip],
+                egui::Stroke::new(2.0, Self::COLORS[i]),
+            );
+            painter.text(
+                center + egui::vec2(x, -y) * Self::LENGTH * 1.25,
+                egui::Align2::CENTER_CENTER,
+                Self::LABELS[i],
+                egui::FontId::proportional(14.0),
+                Self::COLORS[i],
+            );
+        }
+    }
+}
+
+/// The reference grid, as seen from the camera
+pub(crate) struct ScreenGrid {
+    /// The lines of the grid on the screen, in points
+    pub lines: Vec<[egui::Pos2; 2]>,
+
+    /// The distance between neighboring grid lines, in model units
+    pub spacing: f64,
+
+    /// The point on the grid that the cursor points to, if any
+    ///
+    /// The point is snapped to the nearest intersection of grid lines.
+    pub cursor: Option<[f64; 2]>,
+}
+
+/// Statistics about the performance of the viewer
+pub(crate) struct PerformanceStats {
+    /// The times between the most recent frames, oldest first
+    ///
+    /// Holds at most [`PerformanceStats::NUM_FRAMES`] times.
+    pub frame_times: Vec<Duration>,
+
+    /// Statistics about the last frame that the renderer has drawn
+    pub render: RenderStats,
+
+    /// How long it took to process the displayed shape, if any
+    pub processing_time: Option<Duration>,
+}
+
+impl PerformanceStats {
+    /// The number of frames that are shown in the frame time graph
+    pub const NUM_FRAMES: usize = 120;
+
+    /// The size of the frame time graph, in points
+    const GRAPH_SIZE: [f32; 2] = [240., 60.];
+
+    fn show(&self, ui: &mut egui::Ui) {
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.;
+
+        if let Some(&last) = self.frame_times.last() {
+            let max = self.frame_times.iter().max().copied().unwrap_or(last);
+            let average = self.frame_times.iter().sum::<Duration>()
+                / self.frame_times.len() as u32;
+
+            ui.label(format!(
+                "Frame time: {:.1} ms (average {:.1} ms, max {:.1} ms)",
+                millis(last),
+                millis(average),
+                millis(max),
+            ));
+        }
+        self.draw_graph(ui);
+
+        ui.label(format!("Draw calls: {}", self.render.draw_calls));
+        ui.label(format!(
+            "Geometry buffers: {:.2} MiB",
+            self.render.buffer_size as f64 / (1024. * 1024.)
+        ));
+        match self.processing_time {
+            Some(processing_time) => ui.label(format!(
+                "Last processing: {:.0} ms",
+                millis(processing_time)
+            )),
+            None => ui.label("Last processing: -"),
+        };
+    }
+
+    fn draw_graph(&self, ui: &mut egui::Ui) {
+        let [width, height] = Self::GRAPH_SIZE;
+        let (response, painter) = ui
+            .allocate_painter(egui::vec2(width, height), egui::Sense::hover());
+        let rect = response.rect;
+
+        painter.rect_filled(rect, 0., ui.visuals().extreme_bg_color);
+
+        // The graph shows at least two frames at 60 fps, so a smooth frame
+        // rate shows up as a flat line in the middle, instead of noise.
+        let target = Duration::from_secs_f64(1. / 60.);
+        let scale = self
+            .frame_times
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or_default()
+            .max(target * 2)
+            .as_secs_f32();
+        let y = |duration: Duration| {
+            rect.bottom() - duration.as_secs_f32() / scale * height
+        };
+
+        painter.line_segment(
+            [
+                egui::pos2(rect.left(), y(target)),
+                egui::pos2(rect.right(), y(target)),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::DARK_GRAY),
+        );
+
+        let step = width / (Self::NUM_FRAMES - 1) as f32;
+        let points = self
+            .frame_times
+            .iter()
+            .enumerate()
+            .map(|(i, &duration)| {
+                egui::pos2(rect.left() + i as f32 * step, y(duration))
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, egui::Color32::LIGHT_BLUE),
+        ));
+    }
+}
+
+/// The settings of the export dialog
+struct ExportDialog {
+    open: bool,
+    format: usize,
+    custom_tolerance: bool,
+    tolerance: f64,
+    up_axis: Option<UpAxis>,
+}
+
+impl ExportDialog {
+    /// The names and file extensions of the formats that can be exported
+    const FORMATS: [(&'static str, &'static str); 3] =
+        [("3MF", "3mf"), ("STL", "stl"), ("OBJ", "obj")];
+}
+
+impl Default for ExportDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            format: 0,
+            custom_tolerance: false,
+            tolerance: 0.01,
+            up_axis: None,
+        }
+    }
+}
+
+/// An export of the model, that the user has requested
+#[derive(Debug)]
+pub struct ExportRequest {
+    /// The path of the file that the model is exported to
+    pub path: PathBuf,
+
+    /// The tolerance that the model is approximated with
+    ///
+    /// If this is `None`, the tolerance of the displayed model is used.
+    pub tolerance: Option<Tolerance>,
+
+    /// The axis that points up in the exported file
+    ///
+    /// If this is `None`, the convention of the file format is used.
+    pub up_axis: Option<UpAxis>,
+}
+
+/// The axis that points up in an exported file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpAxis {
+    /// The y-axis points up
+    Y,
+
+    /// The z-axis points up
+    Z,
+}
+
+/// A change to the camera bookmarks, requested through the GUI
+pub(crate) enum BookmarkRequest {
+    /// Save the current camera pose under the given name
+    Save(String),
+
+    /// Move the camera to the bookmark with the given index
+    Jump(usize),
+
+    /// Remove the bookmark with the given index
+    Remove(usize),
 }
 
 /// The current status of the GUI
 pub struct GuiState<'a> {
     /// Reference to the status messages
-    pub status: &'a StatusReport,
+    pub status: &'a mut StatusReport,
 
     /// Indicates whether a model is currently available
     pub model_available: bool,
@@ -18,6 +18,11 @@ pub enum InputEvent {
         angle_y: f64,
     },
 
-    /// Move the view forwards and backwards
-    Zoom(f64),
+    /// Move the view forwards and backwards, toward the cursor
+    Zoom {
+        /// The amount to zoom, relative to the distance of the focus point
+        delta: f64,
+        /// The normalized position of the cursor
+        cursor: NormalizedScreenPosition,
+    },
 }
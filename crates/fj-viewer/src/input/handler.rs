@@ -1,15 +1,36 @@
+use std::time::Duration;
+
 use super::{movement::Movement, rotation::Rotation, zoom::Zoom, InputEvent};
-use crate::camera::{Camera, FocusPoint};
+use crate::{
+    camera::{Camera, FocusPoint},
+    screen::NormalizedScreenPosition,
+};
 
 /// Input handling abstraction
 ///
 /// Takes user input and applies them to application state.
+///
+/// If [`InputHandler::damping`] is set, rotation and movement continue for a
+/// while after the mouse has been released, slowing down gradually. Call
+/// [`InputHandler::update`] once per frame for that.
 #[derive(Default)]
-pub struct InputHandler;
+pub struct InputHandler {
+    /// How quickly the camera comes to rest after the mouse has been released
+    ///
+    /// The speed of the camera decreases by a factor of `e` per `1 / damping`
+    /// seconds. If this is `None`, the camera stops immediately.
+    pub damping: Option<f64>,
+
+    motion: Motion,
+    velocity: Motion,
+    cursor: Option<NormalizedScreenPosition>,
+    coasting: Option<FocusPoint>,
+}
 
 impl InputHandler {
     /// Handle an input event
     pub fn handle_event(
+        &mut self,
         event: InputEvent,
         focus_point: FocusPoint,
         camera: &mut Camera,
@@ -17,13 +38,111 @@ impl InputHandler {
         match event {
             InputEvent::Translation { previous, current } => {
                 Movement::apply(previous, current, focus_point, camera);
+
+                self.motion.translation[0] += current.x - previous.x;
+                self.motion.translation[1] += current.y - previous.y;
+                self.cursor = Some(current);
             }
             InputEvent::Rotation { angle_x, angle_y } => {
                 Rotation::apply(angle_x, angle_y, focus_point, camera);
+
+                self.motion.rotation[0] += angle_x;
+                self.motion.rotation[1] += angle_y;
             }
-            InputEvent::Zoom(zoom_delta) => {
-                Zoom::apply(zoom_delta, focus_point, camera);
+            InputEvent::Zoom { delta, cursor } => {
+                Zoom::apply(delta, cursor, focus_point, camera);
             }
         }
     }
+
+    /// Keep the camera moving, after the mouse has been released
+    ///
+    /// Does nothing, if damping is disabled, or if the camera wasn't moving
+    /// when the mouse was released.
+    pub fn start_coasting(&mut self, focus_point: FocusPoint) {
+        if self.damping.is_some() && !self.velocity.is_negligible() {
+            self.coasting = Some(focus_point);
+        }
+    }
+
+    /// Stop the camera, if it is still moving after the mouse was released
+    pub fn stop_coasting(&mut self) {
+        self.coasting = None;
+        self.velocity = Motion::default();
+    }
+
+    /// Update the camera, after `elapsed` time has passed since the last frame
+    ///
+    /// While the mouse is held, this measures how fast the camera is moving.
+    /// Once it has been released, the camera keeps moving at that speed, which
+    /// decreases according to [`InputHandler::damping`].
+    pub fn update(&mut self, elapsed: Duration, camera: &mut Camera) {
+        let dt = elapsed.as_secs_f64();
+        if dt <= 0. {
+            return;
+        }
+
+        let (Some(focus_point), Some(damping)) = (self.coasting, self.damping)
+        else {
+            // Average over two frames, to smooth out uneven event delivery.
+            let motion = std::mem::take(&mut self.motion);
+            self.velocity = self.velocity.lerp(&motion.scaled(1. / dt), 0.5);
+            return;
+        };
+
+        let [angle_x, angle_y] = self.velocity.rotation.map(|v| v * dt);
+        Rotation::apply(angle_x, angle_y, focus_point, camera);
+
+        if let Some(previous) = self.cursor {
+            let [dx, dy] = self.velocity.translation.map(|v| v * dt);
+            let current = NormalizedScreenPosition {
+                x: previous.x + dx,
+                y: previous.y + dy,
+            };
+            Movement::apply(previous, current, focus_point, camera);
+        }
+
+        self.velocity = self.velocity.scaled((-damping * dt).exp());
+        if self.velocity.is_negligible() {
+            self.stop_coasting();
+        }
+    }
+}
+
+/// Rotation and movement of the camera, either in total or per second
+#[derive(Clone, Copy, Debug, Default)]
+struct Motion {
+    rotation: [f64; 2],
+    translation: [f64; 2],
+}
+
+impl Motion {
+    fn scaled(&self, factor: f64) -> Self {
+        Self {
+            rotation: self.rotation.map(|v| v * factor),
+            translation: self.translation.map(|v| v * factor),
+        }
+    }
+
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let lerp = |[a, b]: [f64; 2], [c, d]: [f64; 2]| {
+            [a + (c - a) * t, b + (d - b) * t]
+        };
+
+        Self {
+            rotation: lerp(self.rotation, other.rotation),
+            translation: lerp(self.translation, other.translation),
+        }
+    }
+
+    fn is_negligible(&self) -> bool {
+        // Radians and normalized screen coordinates per second. Motion this
+        // slow is no longer noticeable.
+        const THRESHOLD: f64 = 1e-3;
+
+        self.rotation
+            .into_iter()
+            .chain(self.translation)
+            .all(|v| v.abs() < THRESHOLD)
+    }
 }
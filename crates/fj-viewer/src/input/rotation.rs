@@ -1,4 +1,4 @@
-use fj_math::{Transform, Vector};
+use fj_math::{Quaternion, Transform};
 
 use crate::camera::{Camera, FocusPoint};
 
@@ -14,12 +14,13 @@ impl Rotation {
         let rotate_around = Transform::translation(focus_point.0.coords);
 
         // the model rotates not the camera, so invert the transform
-        let camera_rotation = camera.rotation.inverse();
-        let right_vector = right_vector(&camera_rotation);
-        let up_vector = up_vector(&camera_rotation);
+        let (_, camera_rotation, _) = camera.rotation.inverse().decompose();
+        let [right_vector, up_vector, _] = camera_rotation.basis();
 
-        let rotation = Transform::rotation(right_vector * angle_x)
-            * Transform::rotation(up_vector * angle_y);
+        let rotation = Transform::from_rotation(
+            Quaternion::from_axis_angle(right_vector, angle_x)
+                * Quaternion::from_axis_angle(up_vector, angle_y),
+        );
 
         let transform = camera.camera_to_model()
             * rotate_around
@@ -30,13 +31,3 @@ impl Rotation {
         camera.translation = transform.extract_translation();
     }
 }
-
-fn up_vector(rotation: &Transform) -> Vector<3> {
-    let d = rotation.data();
-    Vector::from_components_f64([d[4], d[5], d[6]])
-}
-
-fn right_vector(rotation: &Transform) -> Vector<3> {
-    let d = rotation.data();
-    Vector::from_components_f64([d[0], d[1], d[2]])
-}
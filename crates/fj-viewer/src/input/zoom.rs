@@ -1,18 +1,31 @@
-use fj_math::{Transform, Vector};
+use fj_math::Transform;
 
-use crate::camera::{Camera, FocusPoint};
+use crate::{
+    camera::{Camera, FocusPoint},
+    screen::NormalizedScreenPosition,
+};
 
 pub struct Zoom;
 
 impl Zoom {
     pub fn apply(
         zoom_delta: f64,
+        cursor: NormalizedScreenPosition,
         focus_point: FocusPoint,
         camera: &mut Camera,
     ) {
+        // Move along the ray through the cursor, so the point under it stays
+        // where it is on the screen. If the cursor is over the model, the focus
+        // point is on that ray, and the camera moves straight toward it.
+        let cursor = camera.cursor_to_model_space(cursor);
+        let direction = (cursor - camera.position()).normalize();
+
         let distance = (focus_point.0 - camera.position()).magnitude();
-        let displacement = zoom_delta * distance.into_f64();
-        camera.translation = camera.translation
-            * Transform::translation(Vector::from([0.0, 0.0, displacement]));
+        let displacement = direction * zoom_delta * distance.into_f64();
+        let offset = camera.camera_to_model().transform_vector(&displacement);
+
+        // The model moves, not the camera, so the offset needs to be inverted.
+        camera.translation =
+            camera.translation * Transform::translation(-offset);
     }
 }
@@ -14,20 +14,25 @@
 
 #![warn(missing_docs)]
 
+mod ansi;
 mod assets;
 mod camera;
 mod graphics;
 mod gui;
 mod input;
+mod lod;
 mod screen;
 mod status_report;
 mod viewer;
 
 pub use self::{
-    camera::Camera,
-    graphics::{DrawConfig, Renderer, RendererInitError},
-    gui::{Gui, GuiState},
+    camera::{Camera, CameraBookmark},
+    graphics::{
+        Coloring, DrawConfig, RenderStats, Renderer, RendererInitError,
+    },
+    gui::{ExportRequest, Gui, GuiState, UpAxis},
     input::{InputEvent, InputHandler},
+    lod::LevelOfDetail,
     screen::{NormalizedScreenPosition, Screen, ScreenSize},
     status_report::StatusReport,
     viewer::Viewer,
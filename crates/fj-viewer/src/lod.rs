@@ -0,0 +1,104 @@
+//! Level of detail
+//!
+//! See [`LevelOfDetail`].
+
+use fj_math::{Aabb, Scalar, Tolerance};
+
+use crate::{Camera, ScreenSize};
+
+/// Chooses the tolerance of the model's approximation, based on the camera
+///
+/// A model that is far away covers few pixels on the screen, and a coarse
+/// approximation of it looks the same as a fine one. A model that is zoomed in
+/// on needs a fine approximation, to avoid visible facets.
+///
+/// The tolerance is chosen such that the approximation deviates from the model
+/// by about [`LevelOfDetail::PIXELS`] on screen. Tolerance values are rounded
+/// to powers of two, and only change once they are off by more than a factor
+/// of two. This keeps small camera movements from causing the model to be
+/// processed again.
+#[derive(Debug)]
+pub struct LevelOfDetail {
+    /// Whether the tolerance is chosen automatically
+    ///
+    /// Should be disabled, if the user has chosen a tolerance.
+    pub enabled: bool,
+
+    level: Option<i32>,
+}
+
+impl LevelOfDetail {
+    /// The deviation of the approximation on screen, in pixels
+    pub const PIXELS: f64 = 0.5;
+
+    /// Construct a new instance of `LevelOfDetail`
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            level: None,
+        }
+    }
+
+    /// Compute a new tolerance, if the current one is no longer suitable
+    ///
+    /// Returns `None`, if the current tolerance can be kept.
+    pub fn update(
+        &mut self,
+        camera: &Camera,
+        aabb: &Aabb<3>,
+        screen_size: ScreenSize,
+    ) -> Option<Tolerance> {
+        if !self.enabled {
+            return None;
+        }
+
+        let min_extent = aabb
+            .size()
+            .components
+            .into_iter()
+            .filter(|extent| *extent > Scalar::ZERO)
+            .min()?
+            .into_f64();
+
+        // The shape processor derives its default tolerance from the model,
+        // in the same way. That's the tolerance the first approximation was
+        // computed with.
+        let current = *self
+            .level
+            .get_or_insert_with(|| (min_extent / 1000.).log2().round() as i32);
+
+        // Size of a pixel in model space, at the distance of the closest point
+        // of the model's bounding box.
+        let radius = (aabb.max - aabb.min).magnitude().into_f64() / 2.;
+        let distance =
+            (camera.position() - aabb.center()).magnitude().into_f64();
+        let distance = (distance - radius).max(min_extent / 10.);
+        let pixel = distance * 2. * (camera.field_of_view_in_x() / 2.).tan()
+            / f64::from(screen_size.width.max(1));
+
+        let target = (pixel * Self::PIXELS)
+            .clamp(min_extent / MAX_DETAIL, min_extent / MIN_DETAIL)
+            .log2();
+
+        if (target - f64::from(current)).abs() <= 1. {
+            return None;
+        }
+
+        let level = target.round() as i32;
+        self.level = Some(level);
+
+        Tolerance::from_scalar(2f64.powi(level)).ok()
+    }
+}
+
+impl Default for LevelOfDetail {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The finest tolerance, as a fraction of the model's smallest extent
+const MAX_DETAIL: f64 = 10_000.;
+
+/// The coarsest tolerance, as a fraction of the model's smallest extent
+const MIN_DETAIL: f64 = 100.;
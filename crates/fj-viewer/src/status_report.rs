@@ -2,52 +2,79 @@
 
 use std::collections::VecDeque;
 
-use chrono::Local;
+use fj_interop::{
+    progress::Progress,
+    status::{Level, StatusMessage},
+};
 
 /// Struct to store and update status messages
 #[derive(Default)]
 pub struct StatusReport {
-    status: VecDeque<String>,
+    messages: VecDeque<StatusMessage>,
+    progress: Option<Progress>,
+
+    /// Whether messages are also logged via `tracing`
+    ///
+    /// This makes them show up in the terminal, if logging is enabled there.
+    pub echo: bool,
 }
 
 impl StatusReport {
-    /// Create a new `StatusReport` instance with a blank status
+    /// The maximum number of messages that are kept
+    ///
+    /// Once there are more, the oldest ones are removed.
+    pub const MAX_MESSAGES: usize = 1000;
+
+    /// Create a new `StatusReport` instance without any messages
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Update the status
-    pub fn update_status(&mut self, status: &str) {
-        let date = {
-            let date = Local::now();
-            format!("{}", date.format("[%H:%M:%S.%3f]"))
-        };
-        let empty_space = " ".repeat(date.chars().count());
-
-        let mut rendered = String::new();
-        for (i, line) in status.lines().enumerate() {
-            let prefix = if i == 0 { &date } else { &empty_space };
-            rendered.push_str(&format!("\n{prefix} {line}"));
+    /// Add a status message
+    pub fn add(&mut self, message: StatusMessage) {
+        if self.echo {
+            echo(&message);
         }
 
-        self.status.push_back(rendered);
-        if self.status.len() > 5 {
-            for _ in 0..(self.status.len() - 5) {
-                self.status.pop_front();
-            }
+        self.messages.push_back(message);
+        while self.messages.len() > Self::MAX_MESSAGES {
+            self.messages.pop_front();
         }
     }
 
-    /// Get current status
-    pub fn status(&self) -> String {
-        self.status
-            .iter()
-            .map(std::string::ToString::to_string)
-            .collect::<String>()
+    /// Iterate over the status messages, from oldest to newest
+    pub fn messages(&self) -> impl Iterator<Item = &StatusMessage> {
+        self.messages.iter()
+    }
+
+    /// Remove all status messages
+    pub fn clear_messages(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Update the progress of the current operation
+    pub fn update_progress(&mut self, progress: Progress) {
+        self.progress = Some(progress);
     }
 
-    /// Reset status
-    pub fn clear_status(&mut self) {
-        self.status.clear();
+    /// Get the progress of the current operation, if any is going on
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress
+    }
+
+    /// Reset progress, after the current operation has finished
+    pub fn clear_progress(&mut self) {
+        self.progress = None;
+    }
+}
+
+fn echo(message: &StatusMessage) {
+    let source = message.source.description();
+    let text = &message.text;
+
+    match message.level {
+        Level::Info => tracing::info!(source, "{text}"),
+        Level::Warn => tracing::warn!(source, "{text}"),
+        Level::Error => tracing::error!(source, "{text}"),
     }
 }
@@ -1,13 +1,29 @@
-use std::path::PathBuf;
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use fj_interop::processed_shape::ProcessedShape;
-use fj_math::Aabb;
-use tracing::warn;
+use fj_interop::{
+    assembly::Part,
+    mesh::FaceId,
+    processed_shape::{PartialShape, ProcessedShape},
+    status::StatusMessage,
+    units::Units,
+};
+use fj_math::{Aabb, Point, Tolerance};
+use tracing::{debug_span, warn};
 
 use crate::{
-    camera::FocusPoint, gui::Gui, Camera, DrawConfig, GuiState, InputEvent,
-    InputHandler, NormalizedScreenPosition, Renderer, RendererInitError,
-    Screen, ScreenSize,
+    camera::FocusPoint,
+    graphics::{Transform, Vertices},
+    gui::{
+        BookmarkRequest, Gui, PerformanceStats, ScreenAxes, ScreenGrid,
+        ScreenLabel,
+    },
+    Camera, CameraBookmark, Coloring, DrawConfig, ExportRequest, GuiState,
+    InputEvent, InputHandler, LevelOfDetail, NormalizedScreenPosition,
+    Renderer, RendererInitError, Screen, ScreenSize,
 };
 
 /// The Fornjot model viewer
@@ -15,6 +31,18 @@ pub struct Viewer {
     /// The camera
     pub camera: Camera,
 
+    /// The saved poses of the camera
+    ///
+    /// Use [`Viewer::changed_bookmarks`] to find out, when these need to be
+    /// saved.
+    pub bookmarks: Vec<CameraBookmark>,
+
+    /// The parts of the model, if it is an assembly
+    ///
+    /// Use [`Viewer::changed_parts`] to find out, when the model needs to be
+    /// processed again.
+    pub parts: Vec<Part>,
+
     /// The cursor
     pub cursor: Option<NormalizedScreenPosition>,
 
@@ -30,11 +58,45 @@ pub struct Viewer {
     /// The input handler
     pub input_handler: InputHandler,
 
+    /// The level of detail that the model is approximated with
+    pub level_of_detail: LevelOfDetail,
+
     /// The renderer
     pub renderer: Renderer,
 
     /// The shape
     pub shape: Option<ProcessedShape>,
+
+    /// The shape that is currently being processed, if any
+    ///
+    /// Displayed instead of `shape`, until processing has finished.
+    pub partial_shape: Option<PartialShape>,
+
+    /// The selected face
+    ///
+    /// If algorithm debugging is enabled, the intermediate results of the
+    /// kernel's algorithms are displayed for this face.
+    pub selected_face: Option<FaceId>,
+
+    /// The units that the model's coordinates are given in, if known
+    pub units: Option<Units>,
+
+    screen_size: ScreenSize,
+
+    // Whether the host has last been asked to collect algorithm debug info.
+    debug_algorithms_requested: bool,
+
+    // Whether the bookmarks have changed since they were last saved.
+    bookmarks_changed: bool,
+
+    // Whether the user has moved or hidden parts since this was last checked.
+    parts_changed: bool,
+
+    // The times between the most recent frames, for the performance overlay.
+    frame_times: VecDeque<Duration>,
+
+    // When the last frame has been drawn.
+    last_draw: Option<Instant>,
 }
 
 impl Viewer {
@@ -45,13 +107,25 @@ impl Viewer {
 
         Ok(Self {
             camera: Camera::default(),
+            bookmarks: Vec::new(),
+            parts: Vec::new(),
             cursor: None,
             draw_config: DrawConfig::default(),
             focus_point: None,
             gui,
             input_handler: InputHandler::default(),
+            level_of_detail: LevelOfDetail::new(),
             renderer,
             shape: None,
+            partial_shape: None,
+            selected_face: None,
+            units: None,
+            screen_size: screen.size(),
+            debug_algorithms_requested: false,
+            bookmarks_changed: false,
+            parts_changed: false,
+            frame_times: VecDeque::new(),
+            last_draw: None,
         })
     }
 
@@ -74,31 +148,204 @@ impl Viewer {
         }
     }
 
+    /// Toggle the "draw transparency" setting
+    pub fn toggle_draw_transparency(&mut self) {
+        self.draw_config.draw_transparency =
+            !self.draw_config.draw_transparency;
+    }
+
+    /// Toggle the "draw axes" setting
+    pub fn toggle_draw_axes(&mut self) {
+        self.draw_config.draw_axes = !self.draw_config.draw_axes;
+    }
+
+    /// Toggle the "draw grid" setting
+    pub fn toggle_draw_grid(&mut self) {
+        self.draw_config.draw_grid = !self.draw_config.draw_grid;
+    }
+
+    /// Toggle the "draw performance" setting
+    pub fn toggle_draw_performance(&mut self) {
+        self.draw_config.draw_performance = !self.draw_config.draw_performance;
+    }
+
+    /// Toggle the "debug algorithms" setting
+    pub fn toggle_debug_algorithms(&mut self) {
+        if self.renderer.is_line_drawing_available() {
+            self.draw_config.debug_algorithms =
+                !self.draw_config.debug_algorithms;
+            self.update_geometry();
+        }
+    }
+
+    /// Switch to the next way of coloring the model
+    pub fn cycle_coloring(&mut self) {
+        self.draw_config.coloring = self.draw_config.coloring.next();
+        self.update_geometry();
+    }
+
+    /// Save the current pose of the camera as a bookmark
+    ///
+    /// Replaces any bookmark with the same name.
+    pub fn save_bookmark(&mut self, name: impl Into<String>) {
+        let bookmark = self.camera.bookmark(name);
+
+        match self
+            .bookmarks
+            .iter_mut()
+            .find(|existing| existing.name == bookmark.name)
+        {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+        self.bookmarks_changed = true;
+    }
+
+    /// Move the camera to the bookmark with the given index, if it exists
+    pub fn jump_to_bookmark(&mut self, index: usize) {
+        if let Some(bookmark) = self.bookmarks.get(index) {
+            self.camera.jump_to(bookmark);
+        }
+    }
+
+    /// Remove the bookmark with the given index, if it exists
+    pub fn remove_bookmark(&mut self, index: usize) {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+            self.bookmarks_changed = true;
+        }
+    }
+
+    /// Check whether the bookmarks need to be saved
+    ///
+    /// Returns the bookmarks, if they have changed since this method was last
+    /// called.
+    pub fn changed_bookmarks(&mut self) -> Option<&[CameraBookmark]> {
+        if !self.bookmarks_changed {
+            return None;
+        }
+
+        self.bookmarks_changed = false;
+        Some(&self.bookmarks)
+    }
+
+    /// Check whether the user has moved or hidden parts of the model
+    ///
+    /// Returns the parts, if they have changed since this method was last
+    /// called, `None` otherwise.
+    pub fn changed_parts(&mut self) -> Option<&[Part]> {
+        if !self.parts_changed {
+            return None;
+        }
+
+        self.parts_changed = false;
+        Some(&self.parts)
+    }
+
     /// Handle the shape being updated
     pub fn handle_shape_update(&mut self, shape: ProcessedShape) {
-        self.renderer
-            .update_geometry((&shape.mesh).into(), (&shape.debug_info).into());
+        let aabb = shape.aabb;
+        let is_first = self.shape.is_none() && self.partial_shape.is_none();
+        self.partial_shape = None;
+
+        if self.shape.replace(shape).is_none() && is_first {
+            self.camera.init_planes(&aabb);
+        }
+
+        self.update_geometry();
+    }
 
+    /// Handle part of a shape being processed
+    pub fn handle_partial_shape_update(&mut self, shape: PartialShape) {
         let aabb = shape.aabb;
-        if self.shape.replace(shape).is_none() {
+        let is_first = self.shape.is_none() && self.partial_shape.is_none();
+        self.partial_shape = Some(shape);
+
+        if is_first {
             self.camera.init_planes(&aabb);
         }
+
+        self.update_geometry();
+    }
+
+    /// Discard the partial shape, if processing failed
+    ///
+    /// Shows the last shape that was processed successfully again.
+    pub fn discard_partial_shape(&mut self) {
+        if self.partial_shape.take().is_some() {
+            self.update_geometry();
+        }
+    }
+
+    /// Find the face under the cursor
+    ///
+    /// Returns `None`, if the cursor is not over the window, or if there is no
+    /// face under it.
+    pub fn pick_face(&self) -> Option<FaceId> {
+        self.renderer.pick(&self.camera, self.cursor?)
+    }
+
+    /// Select the face under the cursor
+    ///
+    /// Removes the selection, if there is no face under the cursor.
+    pub fn select_face(&mut self) {
+        self.selected_face = self.pick_face();
+        self.update_geometry();
     }
 
     /// Handle an input event
     pub fn handle_input_event(&mut self, event: InputEvent) {
         if let Some(focus_point) = self.focus_point {
-            InputHandler::handle_event(event, focus_point, &mut self.camera);
+            self.input_handler.handle_event(
+                event,
+                focus_point,
+                &mut self.camera,
+            );
         }
     }
 
     /// Handle the screen being resized
     pub fn handle_screen_resize(&mut self, screen_size: ScreenSize) {
         self.renderer.handle_resize(screen_size);
+        self.screen_size = screen_size;
+    }
+
+    /// Compute the tolerance that the model should be approximated with
+    ///
+    /// Returns `None`, if the tolerance of the current approximation is still
+    /// suitable for the camera. See [`LevelOfDetail`].
+    pub fn requested_tolerance(&mut self) -> Option<Tolerance> {
+        // Wait for processing to finish, before requesting more of it.
+        if self.partial_shape.is_some() {
+            return None;
+        }
+
+        let shape = self.shape.as_ref()?;
+        self.level_of_detail
+            .update(&self.camera, &shape.aabb, self.screen_size)
+    }
+
+    /// Check whether algorithm debug info needs to be collected
+    ///
+    /// Returns `Some`, if the "debug algorithms" setting has changed since this
+    /// method was last called. The model then needs to be processed again,
+    /// with algorithm debug info collected or not, as indicated by the value.
+    pub fn requested_debug_algorithms(&mut self) -> Option<bool> {
+        let enabled = self.draw_config.debug_algorithms;
+        if enabled == self.debug_algorithms_requested {
+            return None;
+        }
+
+        self.debug_algorithms_requested = enabled;
+        Some(enabled)
     }
 
     /// Compute and store a focus point, unless one is already stored
+    ///
+    /// Stops any camera motion that continues after the mouse was released.
     pub fn add_focus_point(&mut self) {
+        self.input_handler.stop_coasting();
+
         // Don't recompute the focus point unnecessarily.
         if let Some(shape) = &self.shape {
             if self.focus_point.is_none() {
@@ -109,8 +356,37 @@ impl Viewer {
     }
 
     /// Remove the stored focus point
+    ///
+    /// If inertia is enabled, the camera keeps moving around the focus point
+    /// for a while. See [`InputHandler::damping`].
     pub fn remove_focus_point(&mut self) {
-        self.focus_point = None;
+        if let Some(focus_point) = self.focus_point.take() {
+            self.input_handler.start_coasting(focus_point);
+        }
+    }
+
+    /// Take the export of the model that the user has requested, if any
+    pub fn requested_export(&mut self) -> Option<ExportRequest> {
+        self.gui.take_export_request()
+    }
+
+    /// Show the output of the compiler, after compiling the model has failed
+    ///
+    /// Pass `None` to hide the output again, once compiling has succeeded.
+    pub fn set_compiler_output(&mut self, output: Option<&str>) {
+        self.gui.set_compiler_output(output);
+    }
+
+    /// Show a status message briefly, on top of the model
+    pub fn show_toast(&mut self, message: StatusMessage) {
+        self.gui.show_toast(message);
+    }
+
+    /// Update the camera, after `elapsed` time has passed since the last frame
+    ///
+    /// Call this once per frame, before [`Viewer::draw`].
+    pub fn update_camera(&mut self, elapsed: Duration) {
+        self.input_handler.update(elapsed, &mut self.camera);
     }
 
     /// Draw the graphics
@@ -120,23 +396,78 @@ impl Viewer {
         egui_input: egui::RawInput,
         gui_state: GuiState,
     ) -> Option<PathBuf> {
+        let _span = debug_span!("draw").entered();
+
+        let now = Instant::now();
+        if let Some(last_draw) = self.last_draw.replace(now) {
+            if self.frame_times.len() == PerformanceStats::NUM_FRAMES {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(now - last_draw);
+        }
+
         let aabb = self
-            .shape
+            .partial_shape
             .as_ref()
             .map(|shape| shape.aabb)
+            .or_else(|| self.shape.as_ref().map(|shape| shape.aabb))
             .unwrap_or_else(Aabb::default);
 
         self.camera.update_planes(&aabb);
+        self.gui.set_labels(self.debug_labels(pixels_per_point));
+        self.gui.set_axes(self.screen_axes(pixels_per_point));
+        self.gui.set_grid(self.screen_grid(&aabb, pixels_per_point));
+        self.gui.set_units(self.units);
+        self.gui.set_bookmarks(
+            self.bookmarks
+                .iter()
+                .map(|bookmark| bookmark.name.clone())
+                .collect(),
+        );
+        self.gui.set_parts(self.parts.clone());
+        self.gui
+            .set_performance(self.draw_config.draw_performance.then(|| {
+                PerformanceStats {
+                    frame_times: self.frame_times.iter().copied().collect(),
+                    render: self.renderer.stats(),
+                    processing_time: self
+                        .shape
+                        .as_ref()
+                        .map(|shape| shape.processing_time),
+                }
+            }));
 
+        let coloring = self.draw_config.coloring;
+        let debug_algorithms = self.draw_config.debug_algorithms;
         let new_model_path = self.gui.update(
             pixels_per_point,
             egui_input,
             &mut self.draw_config,
-            &aabb,
+            self.shape.as_ref(),
             self.renderer.is_line_drawing_available(),
             gui_state,
         );
 
+        for request in self.gui.take_bookmark_requests() {
+            match request {
+                BookmarkRequest::Save(name) => self.save_bookmark(name),
+                BookmarkRequest::Jump(index) => self.jump_to_bookmark(index),
+                BookmarkRequest::Remove(index) => self.remove_bookmark(index),
+            }
+        }
+
+        if self.gui.parts() != self.parts {
+            self.parts = self.gui.parts().to_vec();
+            self.parts_changed = true;
+        }
+
+        // The GUI might have changed the settings.
+        if self.draw_config.coloring != coloring
+            || self.draw_config.debug_algorithms != debug_algorithms
+        {
+            self.update_geometry();
+        }
+
         if let Err(err) = self.renderer.draw(
             &self.camera,
             &self.draw_config,
@@ -148,4 +479,191 @@ impl Viewer {
 
         new_model_path
     }
+
+    // Project the labels of the debug info onto the screen.
+    fn debug_labels(&self, pixels_per_point: f32) -> Vec<ScreenLabel> {
+        let Some(shape) = &self.shape else {
+            return Vec::new();
+        };
+        if !self.draw_config.draw_debug {
+            return Vec::new();
+        }
+
+        shape
+            .debug_info
+            .labels
+            .iter()
+            .filter_map(|label| {
+                let position =
+                    self.project_to_screen(label.position, pixels_per_point)?;
+                let [r, g, b, a] = label.color.0;
+
+                Some(ScreenLabel {
+                    position,
+                    text: label.text.clone(),
+                    color: egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+                })
+            })
+            .collect()
+    }
+
+    // Compute where the axes are pointing, as seen from the camera.
+    fn screen_axes(&self, pixels_per_point: f32) -> Option<ScreenAxes> {
+        if !self.draw_config.draw_axes {
+            return None;
+        }
+
+        let directions =
+            [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]].map(|axis| {
+                let direction =
+                    self.camera.rotation.transform_vector(&axis.into());
+                direction.components.map(|s| s.into_f32())
+            });
+        let origin = self.project_to_screen(Point::origin(), pixels_per_point);
+
+        Some(ScreenAxes { directions, origin })
+    }
+
+    // Compute the reference grid on the xy-plane, covering the model, and the
+    // point on it that the cursor points to.
+    fn screen_grid(
+        &self,
+        aabb: &Aabb<3>,
+        pixels_per_point: f32,
+    ) -> Option<ScreenGrid> {
+        if !self.draw_config.draw_grid {
+            return None;
+        }
+
+        // Without declared units, the grid is divided like a metric one.
+        let units = self.units.unwrap_or(Units::Millimeters);
+        let [min_x, min_y, _] =
+            aabb.min.coords.components.map(|s| s.into_f64());
+        let [max_x, max_y, _] =
+            aabb.max.coords.components.map(|s| s.into_f64());
+        let spacing =
+            units.grid_spacing(f64::max(max_x - min_x, max_y - min_y));
+
+        // Indices of the outermost grid lines. There's always one line beyond
+        // the model, and the grid always includes the origin.
+        let [min_x, min_y] =
+            [min_x, min_y].map(|c| ((c / spacing).floor() as i64 - 1).min(-1));
+        let [max_x, max_y] =
+            [max_x, max_y].map(|c| ((c / spacing).ceil() as i64 + 1).max(1));
+
+        let mut lines = Vec::new();
+        let mut push_line = |a: [i64; 2], b: [i64; 2]| {
+            let [a, b] = [a, b].map(|[x, y]| {
+                let [x, y] = [x, y].map(|c| c as f64 * spacing);
+                self.project_to_screen(
+                    Point::from([x, y, 0.]),
+                    pixels_per_point,
+                )
+            });
+            if let (Some(a), Some(b)) = (a, b) {
+                lines.push([a, b]);
+            }
+        };
+
+        for x in min_x..=max_x {
+            push_line([x, min_y], [x, max_y]);
+        }
+        for y in min_y..=max_y {
+            push_line([min_x, y], [max_x, y]);
+        }
+
+        let cursor = self.cursor.and_then(|cursor| {
+            let origin = self.camera.position();
+            let direction = self.camera.cursor_to_model_space(cursor) - origin;
+
+            let [ox, oy, oz] = origin.coords.components.map(|s| s.into_f64());
+            let [dx, dy, dz] = direction.components.map(|s| s.into_f64());
+
+            // Intersect the ray through the cursor with the xy-plane.
+            let t = -oz / dz;
+            if !t.is_finite() || t <= 0. {
+                return None;
+            }
+
+            Some(
+                [ox + dx * t, oy + dy * t]
+                    .map(|c| (c / spacing).round() * spacing),
+            )
+        });
+
+        Some(ScreenGrid {
+            lines,
+            spacing,
+            cursor,
+        })
+    }
+
+    // Project a point in model space onto the screen, in points. Returns
+    // `None`, if the point is behind the camera.
+    fn project_to_screen(
+        &self,
+        point: Point<3>,
+        pixels_per_point: f32,
+    ) -> Option<egui::Pos2> {
+        let [width, height] = self.screen_size.as_f64();
+        let transform = Transform::for_vertices(&self.camera, width / height);
+
+        let [x, y, _, w] = transform.to_clip_space(point);
+        if w <= 0. {
+            return None;
+        }
+
+        let [x, y] = [x / w, y / w];
+        Some(egui::pos2(
+            (x + 1.) / 2. * width as f32 / pixels_per_point,
+            (1. - y) / 2. * height as f32 / pixels_per_point,
+        ))
+    }
+
+    fn update_geometry(&mut self) {
+        // The analysis of a mesh is only available once processing has
+        // finished, so a partial shape can only be shown in its own colors.
+        if let Some(shape) = &self.partial_shape {
+            self.renderer.update_geometry(
+                (&shape.mesh).into(),
+                Vertices::empty(),
+                (&shape.mesh).into(),
+            );
+            return;
+        }
+
+        let Some(shape) = &self.shape else {
+            return;
+        };
+
+        let mesh = match self.draw_config.coloring {
+            Coloring::Model => (&shape.mesh).into(),
+            Coloring::Overhangs => {
+                Vertices::from_overhangs(&shape.mesh, &shape.overhangs)
+            }
+            Coloring::GaussianCurvature => {
+                Vertices::from_curvature(&shape.mesh, &shape.curvature, |v| {
+                    v.gaussian
+                })
+            }
+            Coloring::MeanCurvature => {
+                Vertices::from_curvature(&shape.mesh, &shape.curvature, |v| {
+                    v.mean
+                })
+            }
+        };
+
+        let mut debug_info = Vertices::from(&shape.debug_info);
+        if self.draw_config.debug_algorithms {
+            let face_debug_info = self
+                .selected_face
+                .and_then(|face| shape.debug_info.faces.get(&face));
+            if let Some(face_debug_info) = face_debug_info {
+                debug_info.push_face_debug_info(face_debug_info);
+            }
+        }
+
+        self.renderer
+            .update_geometry(mesh, debug_info, (&shape.mesh).into());
+    }
 }
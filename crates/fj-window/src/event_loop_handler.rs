@@ -1,8 +1,12 @@
+use std::{path::PathBuf, time::Instant};
+
+use fj_export::{CoordinateSystem, ExportOptions, Format};
 use fj_host::{Host, Model, ModelEvent, Parameters};
+use fj_interop::status::{Source, StatusMessage};
 use fj_operations::shape_processor;
 use fj_viewer::{
-    GuiState, InputEvent, NormalizedScreenPosition, Screen, ScreenSize,
-    StatusReport, Viewer,
+    CameraBookmark, ExportRequest, GuiState, InputEvent,
+    NormalizedScreenPosition, Screen, ScreenSize, StatusReport, UpAxis, Viewer,
 };
 use winit::{
     dpi::PhysicalPosition,
@@ -30,6 +34,12 @@ pub struct EventLoopHandler {
     /// <https://github.com/rust-windowing/winit/issues/2094>
     pub new_size: Option<ScreenSize>,
     pub stop_drawing: bool,
+
+    /// The time at which the last frame was drawn
+    pub last_frame: Instant,
+
+    /// Called with all camera bookmarks, whenever they have changed
+    pub save_bookmarks: Box<dyn FnMut(&[CameraBookmark])>,
 }
 
 impl EventLoopHandler {
@@ -58,6 +68,19 @@ impl EventLoopHandler {
             }
         }
 
+        // Zooming moves toward the point under the cursor, so a focus point is
+        // needed for that, even if no mouse button is held.
+        let is_zoom = matches!(
+            event,
+            Event::WindowEvent {
+                event: WindowEvent::MouseWheel { .. },
+                ..
+            }
+        );
+        if is_zoom {
+            self.viewer.add_focus_point();
+        }
+
         let input_event = input_event(
             &event,
             &self.window,
@@ -69,32 +92,76 @@ impl EventLoopHandler {
             self.viewer.handle_input_event(input_event);
         }
 
+        // Unless a mouse button is held, the next zoom must compute a new focus
+        // point, as the cursor might have moved over a different part of the
+        // model.
+        if is_zoom && self.held_mouse_button.is_none() {
+            self.viewer.remove_focus_point();
+        }
+
         // fj-window events
         match event {
             Event::UserEvent(event) => match event {
                 ModelEvent::StartWatching => {
-                    self.status
-                        .update_status("New model loaded. Evaluating model...");
+                    self.status.add(StatusMessage::info(
+                        Source::Host,
+                        "New model loaded. Evaluating model...",
+                    ));
                 }
                 ModelEvent::ChangeDetected => {
-                    self.status.update_status(
+                    self.status.clear_progress();
+                    self.status.add(StatusMessage::info(
+                        Source::Host,
                         "Change in model detected. Evaluating model...",
-                    );
+                    ));
                 }
                 ModelEvent::Evaluated => {
-                    self.status
-                        .update_status("Model evaluated. Processing model...");
+                    self.viewer.set_compiler_output(None);
+                    self.status.add(StatusMessage::info(
+                        Source::Host,
+                        "Model evaluated. Processing model...",
+                    ));
+                }
+                ModelEvent::Progress(progress) => {
+                    self.status.update_progress(progress);
+                }
+                ModelEvent::PartialShape(shape) => {
+                    self.viewer.handle_partial_shape_update(shape);
                 }
                 ModelEvent::ProcessedShape(shape) => {
+                    self.status.clear_progress();
                     self.viewer.handle_shape_update(shape);
-                    self.status.update_status("Model processed.");
+                    self.status.add(StatusMessage::info(
+                        Source::Operations,
+                        "Model processed.",
+                    ));
                 }
 
                 ModelEvent::Error(err) => {
+                    self.status.clear_progress();
+                    self.viewer.discard_partial_shape();
+                    if let fj_host::Error::Compile { output } = &err {
+                        self.viewer.set_compiler_output(Some(output));
+                    }
                     return Err(Box::new(err).into());
                 }
-                ModelEvent::Warning(warning) => {
-                    self.status.update_status(&warning);
+                ModelEvent::Status(message) => {
+                    self.status.add(message);
+                }
+                ModelEvent::Exported(path) => {
+                    let message = StatusMessage::info(
+                        Source::Export,
+                        format!("Exported model to `{}`.", path.display()),
+                    );
+                    self.viewer.show_toast(message.clone());
+                    self.status.add(message);
+                }
+                ModelEvent::ExportFailed(err) => {
+                    self.viewer.show_toast(StatusMessage::error(
+                        Source::Export,
+                        "Export failed. See status for details.",
+                    ));
+                    return Err(Box::new(err).into());
                 }
             },
             Event::WindowEvent {
@@ -126,8 +193,39 @@ impl EventLoopHandler {
                 VirtualKeyCode::Key3 => {
                     self.viewer.toggle_draw_debug();
                 }
+                VirtualKeyCode::Key4 => {
+                    self.viewer.cycle_coloring();
+                }
+                VirtualKeyCode::Key5 => {
+                    self.viewer.toggle_draw_transparency();
+                }
+                VirtualKeyCode::Key6 => {
+                    self.viewer.toggle_debug_algorithms();
+                }
+                VirtualKeyCode::Key7 => {
+                    self.viewer.toggle_draw_axes();
+                }
+                VirtualKeyCode::Key8 => {
+                    self.viewer.toggle_draw_grid();
+                }
+                VirtualKeyCode::Key9 => {
+                    self.viewer.toggle_draw_performance();
+                }
+                VirtualKeyCode::F1 => self.viewer.jump_to_bookmark(0),
+                VirtualKeyCode::F2 => self.viewer.jump_to_bookmark(1),
+                VirtualKeyCode::F3 => self.viewer.jump_to_bookmark(2),
+                VirtualKeyCode::F4 => self.viewer.jump_to_bookmark(3),
+                VirtualKeyCode::F5 => self.viewer.jump_to_bookmark(4),
+                VirtualKeyCode::F6 => self.viewer.jump_to_bookmark(5),
+                VirtualKeyCode::F7 => self.viewer.jump_to_bookmark(6),
+                VirtualKeyCode::F8 => self.viewer.jump_to_bookmark(7),
+                VirtualKeyCode::F9 => self.viewer.jump_to_bookmark(8),
                 _ => {}
             },
+            Event::WindowEvent {
+                event: WindowEvent::DroppedFile(path),
+                ..
+            } => self.load_dropped_file(path)?,
             Event::WindowEvent {
                 event: WindowEvent::Resized(size),
                 ..
@@ -144,20 +242,26 @@ impl EventLoopHandler {
                 ElementState::Pressed => {
                     self.held_mouse_button = Some(button);
                     self.viewer.add_focus_point();
+
+                    if button == MouseButton::Middle {
+                        self.viewer.select_face();
+                    }
                 }
                 ElementState::Released => {
                     self.held_mouse_button = None;
                     self.viewer.remove_focus_point();
                 }
             },
-            Event::WindowEvent {
-                event: WindowEvent::MouseWheel { .. },
-                ..
-            } => self.viewer.add_focus_point(),
             Event::MainEventsCleared => {
                 self.window.window().request_redraw();
             }
             Event::RedrawRequested(_) => {
+                let _span = tracing::debug_span!("frame").entered();
+
+                let now = Instant::now();
+                self.viewer.update_camera(now - self.last_frame);
+                self.last_frame = now;
+
                 // Only do a screen resize once per frame. This protects against
                 // spurious resize events that cause issues with the renderer.
                 if let Some(size) = self.new_size.take() {
@@ -178,7 +282,7 @@ impl EventLoopHandler {
                         .take_egui_input(self.window.window());
 
                     let gui_state = GuiState {
-                        status: &self.status,
+                        status: &mut self.status,
                         model_available: self.host.is_model_loaded(),
                     };
                     let new_model_path = self.viewer.draw(
@@ -186,9 +290,42 @@ impl EventLoopHandler {
                         egui_input,
                         gui_state,
                     );
+                    if let Some(tolerance) = self.viewer.requested_tolerance() {
+                        self.host.set_tolerance(tolerance);
+                    }
+                    if let Some(enabled) =
+                        self.viewer.requested_debug_algorithms()
+                    {
+                        self.host.set_debug_algorithms(enabled);
+                    }
+                    if let Some(bookmarks) = self.viewer.changed_bookmarks() {
+                        (self.save_bookmarks)(bookmarks);
+                    }
+                    if let Some(parts) = self.viewer.changed_parts() {
+                        self.host.set_parts(parts.to_vec());
+                    }
+                    if let Some(export) = self.viewer.requested_export() {
+                        self.status.add(StatusMessage::info(
+                            Source::Export,
+                            format!(
+                                "Exporting model to `{}`...",
+                                export.path.display()
+                            ),
+                        ));
+                        let options = export_options(&export);
+                        self.host.export(
+                            export.path,
+                            export.tolerance,
+                            options,
+                        );
+                    }
+
                     if let Some(model_path) = new_model_path {
-                        let model = Model::new(model_path, Parameters::empty())
-                            .map_err(Box::new)?;
+                        let model =
+                            Model::from_path(model_path, Parameters::empty())
+                                .map_err(Box::new)?;
+                        self.viewer.units = model.units();
+                        self.viewer.parts = model.parts();
                         self.host.load_model(model);
                     }
                 }
@@ -198,6 +335,29 @@ impl EventLoopHandler {
 
         Ok(())
     }
+
+    // Load a file that has been dropped onto the window. Meshes are imported,
+    // anything else is expected to be a model.
+    fn load_dropped_file(&mut self, path: PathBuf) -> Result<(), Error> {
+        if let Ok(Format::Stl | Format::Obj) = Format::from_path(&path) {
+            self.status.add(StatusMessage::info(
+                Source::Host,
+                format!("Importing mesh `{}`...", path.display()),
+            ));
+            self.viewer.units = None;
+            self.viewer.parts.clear();
+            self.host.load_mesh(path);
+            return Ok(());
+        }
+
+        let model =
+            Model::from_path(path, Parameters::empty()).map_err(Box::new)?;
+        self.viewer.units = model.units();
+        self.viewer.parts = model.parts();
+        self.host.load_model(model);
+
+        Ok(())
+    }
 }
 
 fn input_event<T>(
@@ -256,12 +416,40 @@ fn input_event<T>(
 
             let delta = if invert_zoom { -delta } else { delta };
 
-            Some(InputEvent::Zoom(delta))
+            // Without a known cursor position, zoom toward the center of the
+            // screen.
+            let cursor = previous_cursor
+                .unwrap_or(NormalizedScreenPosition { x: 0., y: 0. });
+
+            Some(InputEvent::Zoom { delta, cursor })
         }
         _ => None,
     }
 }
 
+/// Determine the options for an export that was requested through the GUI
+///
+/// Anything that hasn't been specified defaults to the convention of the
+/// format, like it does for exports from the command line.
+fn export_options(export: &ExportRequest) -> ExportOptions {
+    // If the format is unknown, exporting fails anyway.
+    let coordinate_system =
+        Format::from_path(&export.path).ok().map(|format| {
+            let default = format.default_coordinate_system();
+
+            CoordinateSystem {
+                up: match export.up_axis {
+                    Some(UpAxis::Y) => fj_export::UpAxis::Y,
+                    Some(UpAxis::Z) => fj_export::UpAxis::Z,
+                    None => default.up,
+                },
+                handedness: default.handedness,
+            }
+        });
+
+    ExportOptions { coordinate_system }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Host error")]
@@ -271,6 +459,29 @@ pub enum Error {
     ShapeProcessor(#[from] Box<shape_processor::Error>),
 }
 
+impl Error {
+    /// The component that the error originated from
+    pub fn origin(&self) -> Source {
+        let err = match self {
+            Self::Host(err) => match err.as_ref() {
+                fj_host::Error::ShapeProcessor(err) => err,
+                fj_host::Error::Export(_) | fj_host::Error::NothingToExport => {
+                    return Source::Export
+                }
+                _ => return Source::Host,
+            },
+            Self::ShapeProcessor(err) => err.as_ref(),
+        };
+
+        match err {
+            // Converting the model's shape into kernel objects validates them.
+            // Errors there are detected by the kernel.
+            shape_processor::Error::ToShape(_) => Source::Kernel,
+            _ => Source::Operations,
+        }
+    }
+}
+
 /// Affects the speed of zoom movement given a scroll wheel input in lines.
 ///
 /// Smaller values will move the camera less with the same input.
@@ -7,14 +7,16 @@ use std::{
     error,
     fmt::{self, Write},
     thread,
+    time::Instant,
 };
 
 use fj_host::{Host, Model, ModelEvent};
+use fj_interop::status::StatusMessage;
 use fj_operations::shape_processor::ShapeProcessor;
-use fj_viewer::{RendererInitError, StatusReport, Viewer};
+use fj_viewer::{CameraBookmark, RendererInitError, StatusReport, Viewer};
 use futures::executor::block_on;
 use tracing::trace;
-use winit::event_loop::EventLoopBuilder;
+use winit::{event::Event, event_loop::EventLoopBuilder};
 
 use crate::{
     event_loop_handler::{self, EventLoopHandler},
@@ -22,14 +24,33 @@ use crate::{
 };
 
 /// Initializes a model viewer for a given model and enters its process loop.
+///
+/// The viewer starts out with the given camera bookmarks. Whenever the user
+/// changes them, `save_bookmarks` is called with all bookmarks.
+///
+/// If `camera_damping` is set, the camera keeps moving for a while after the
+/// mouse has been released. See [`fj_viewer::InputHandler::damping`].
+///
+/// This function doesn't return, once the window has been opened. `on_exit` is
+/// called when the window is closed, right before the process exits.
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     model: Option<Model>,
     shape_processor: ShapeProcessor,
     invert_zoom: bool,
+    camera_damping: Option<f64>,
+    bookmarks: Vec<CameraBookmark>,
+    save_bookmarks: impl FnMut(&[CameraBookmark]) + 'static,
+    on_exit: impl FnOnce() + 'static,
 ) -> Result<(), Error> {
     let event_loop = EventLoopBuilder::<ModelEvent>::with_user_event().build();
     let window = Window::new(&event_loop)?;
-    let viewer = block_on(Viewer::new(&window))?;
+    let mut viewer = block_on(Viewer::new(&window))?;
+
+    // A tolerance that the user has chosen must not be overridden.
+    viewer.level_of_detail.enabled = shape_processor.tolerance.is_none();
+    viewer.bookmarks = bookmarks;
+    viewer.input_handler.damping = camera_damping;
 
     let egui_winit_state = egui_winit::State::new(&event_loop);
 
@@ -50,6 +71,8 @@ pub fn run(
     let mut host = Host::new(shape_processor, model_event_tx);
 
     if let Some(model) = model {
+        viewer.units = model.units();
+        viewer.parts = model.parts();
         host.load_model(model);
     }
 
@@ -63,11 +86,21 @@ pub fn run(
         held_mouse_button: None,
         new_size: None,
         stop_drawing: false,
+        last_frame: Instant::now(),
+        save_bookmarks: Box::new(save_bookmarks),
     };
 
+    let mut on_exit = Some(on_exit);
+
     event_loop.run(move |event, _, control_flow| {
         trace!("Handling event: {:?}", event);
 
+        if let Event::LoopDestroyed = event {
+            if let Some(on_exit) = on_exit.take() {
+                on_exit();
+            }
+        }
+
         if let Err(err) = handler.handle_event(event, control_flow) {
             handle_error(err, &mut handler.status)
                 .expect("Expected error handling not to fail");
@@ -95,7 +128,7 @@ fn handle_error(
         current_err = err;
     }
 
-    status.update_status(&msg);
+    status.add(StatusMessage::error(err.origin(), msg));
 
     Ok(())
 }
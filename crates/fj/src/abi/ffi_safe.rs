@@ -212,6 +212,29 @@ impl Deref for String {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for String {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for String {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Ok(std::string::String::deserialize(deserializer)?.into())
+    }
+}
+
 /// A version of `Result` that is `#[repr(C)]`.
 #[must_use]
 #[repr(C)]
@@ -42,6 +42,8 @@ pub mod ffi_safe;
 mod host;
 mod metadata;
 mod model;
+#[cfg(feature = "serde")]
+pub mod wasm;
 
 use backtrace::Backtrace;
 use std::{any::Any, fmt::Display, panic, sync::Mutex};
@@ -99,6 +101,46 @@ macro_rules! register_model {
                 Err(e) => $crate::abi::InitResult::Err(e.into()),
             }
         }
+
+        // Models that are compiled to WebAssembly are loaded through a
+        // different interface. See `fj::abi::wasm`.
+        $crate::__register_wasm_model!();
+    };
+}
+
+// Whether `fj::abi::wasm` is available depends on the features of `fj`, not
+// those of the model that expands `register_model!`. So the check for the
+// feature needs to happen here, when `fj` is compiled.
+
+#[doc(hidden)]
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! __register_wasm_model {
+    () => {
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        extern "C" fn fj_wasm_alloc(len: u32) -> *mut u8 {
+            $crate::abi::wasm::alloc(len)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        unsafe extern "C" fn fj_wasm_shape(ptr: *mut u8, len: u32) -> u64 {
+            $crate::abi::wasm::shape(fj_model_init, ptr, len)
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+macro_rules! __register_wasm_model {
+    () => {
+        #[cfg(target_arch = "wasm32")]
+        compile_error!(
+            "Models that are compiled to WebAssembly require the `serde` \
+            feature of `fj`"
+        );
     };
 }
 
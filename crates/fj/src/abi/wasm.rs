@@ -0,0 +1,151 @@
+//! The interface between models compiled to WebAssembly and the host
+//!
+//! Models compiled to WebAssembly can't exchange Rust values with the host, as
+//! they live in a separate address space. Instead, the host writes the
+//! serialized model arguments into the module's memory, calls
+//! [`SHAPE_FUNCTION_NAME`], and reads back a serialized [`Response`].
+//!
+//! All values are serialized as JSON.
+
+#[cfg(target_arch = "wasm32")]
+use std::collections::HashMap;
+
+/// The name of the function that allocates memory in a WebAssembly module
+///
+/// The host uses this function to allocate the memory that it writes the
+/// model arguments into.
+pub const ALLOC_FUNCTION_NAME: &str = "fj_wasm_alloc";
+
+/// The name of the function that computes the shape of a WebAssembly model
+///
+/// The function expects a pointer to and the length of the serialized model
+/// arguments. It returns pointer and length of the serialized [`Response`],
+/// packed into a single `u64` (see [`pack`]).
+pub const SHAPE_FUNCTION_NAME: &str = "fj_wasm_shape";
+
+/// The response of a WebAssembly model to a request for its shape
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub enum Response {
+    /// The shape of the model
+    Shape(crate::Shape),
+
+    /// Initializing the model failed
+    InitializeModel(String),
+
+    /// No model was registered during initialization
+    NoModelRegistered,
+
+    /// Computing the shape failed
+    ComputeShape(String),
+}
+
+/// Pack a pointer and a length into a single `u64`
+pub fn pack(ptr: u32, len: u32) -> u64 {
+    ((ptr as u64) << 32) | len as u64
+}
+
+/// Unpack a pointer and a length, that were packed using [`pack`]
+pub fn unpack(packed: u64) -> (u32, u32) {
+    ((packed >> 32) as u32, packed as u32)
+}
+
+/// Allocate a buffer of the given length, and leak it
+///
+/// Called by the function generated by [`crate::register_model`].
+#[cfg(target_arch = "wasm32")]
+pub fn alloc(len: u32) -> *mut u8 {
+    let mut buffer = Vec::<u8>::with_capacity(len as usize);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr
+}
+
+/// Initialize a model and compute its shape
+///
+/// Called by the function generated by [`crate::register_model`].
+///
+/// # Safety
+///
+/// `ptr` must point to `len` bytes that were allocated by [`alloc`]. The
+/// buffer is consumed by this function.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn shape(init: super::InitFunction, ptr: *mut u8, len: u32) -> u64 {
+    let len = len as usize;
+    let arguments = Vec::from_raw_parts(ptr, len, len);
+
+    let response = compute_shape(init, &arguments);
+
+    let mut response = serde_json::to_vec(&response)
+        .expect("Failed to serialize shape")
+        .into_boxed_slice();
+    let packed = pack(response.as_mut_ptr() as u32, response.len() as u32);
+
+    // The host is done with the response, once it has read it. The memory
+    // goes away together with the module instance.
+    std::mem::forget(response);
+
+    packed
+}
+
+#[cfg(target_arch = "wasm32")]
+unsafe fn compute_shape(
+    init: super::InitFunction,
+    arguments: &[u8],
+) -> Response {
+    let arguments = match serde_json::from_slice(arguments) {
+        Ok(arguments) => arguments,
+        Err(err) => return Response::InitializeModel(err.to_string()),
+    };
+
+    let mut host = Host {
+        arguments,
+        model: None,
+    };
+
+    if let super::ffi_safe::Result::Err(err) =
+        init(&mut super::Host::from(&mut host))
+    {
+        let err: crate::models::Error = err.into();
+        return Response::InitializeModel(err.to_string());
+    }
+
+    let Some(model) = host.model.take() else {
+        return Response::NoModelRegistered;
+    };
+
+    match model.shape(&host) {
+        Ok(shape) => Response::Shape(shape),
+        Err(err) => Response::ComputeShape(err.to_string()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct Host {
+    arguments: HashMap<String, String>,
+    model: Option<Box<dyn crate::models::Model>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl crate::models::Host for Host {
+    fn register_boxed_model(&mut self, model: Box<dyn crate::models::Model>) {
+        self.model = Some(model);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl crate::models::Context for Host {
+    fn get_argument(&self, name: &str) -> Option<&str> {
+        self.arguments.get(name).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pack, unpack};
+
+    #[test]
+    fn pack_and_unpack() {
+        let (ptr, len) = (0x1234_5678, 0x9abc_def0);
+        assert_eq!(unpack(pack(ptr, len)), (ptr, len));
+    }
+}
@@ -0,0 +1,196 @@
+use std::{fmt, str::FromStr};
+
+/// A color in RGBA
+///
+/// Colors can be created from arrays of channels, from one of the named
+/// constants, or from a hex string:
+///
+/// ``` rust
+/// use fj::Color;
+///
+/// let red = Color::RED;
+/// let orange: Color = "#ff8000".parse().unwrap();
+/// let translucent_blue = Color::BLUE.with_alpha(128);
+/// # assert_eq!(orange, Color::rgb(255, 128, 0));
+/// # assert_eq!(translucent_blue.alpha(), 128);
+/// ```
+///
+/// An alpha of `255` is fully opaque, an alpha of `0` fully transparent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Color(pub [u8; 4]);
+
+impl Color {
+    /// Black
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+
+    /// White
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+
+    /// Gray
+    pub const GRAY: Self = Self::rgb(128, 128, 128);
+
+    /// Red
+    ///
+    /// This is the default color of sketches.
+    pub const RED: Self = Self::rgb(255, 0, 0);
+
+    /// Green
+    pub const GREEN: Self = Self::rgb(0, 255, 0);
+
+    /// Blue
+    pub const BLUE: Self = Self::rgb(0, 0, 255);
+
+    /// Yellow
+    pub const YELLOW: Self = Self::rgb(255, 255, 0);
+
+    /// Cyan
+    pub const CYAN: Self = Self::rgb(0, 255, 255);
+
+    /// Magenta
+    pub const MAGENTA: Self = Self::rgb(255, 0, 255);
+
+    /// Orange
+    pub const ORANGE: Self = Self::rgb(255, 128, 0);
+
+    /// Create an opaque color from its red, green, and blue channels
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self([r, g, b, 255])
+    }
+
+    /// Create a color from its red, green, blue, and alpha channels
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self([r, g, b, a])
+    }
+
+    /// Parse a color from a hex string
+    ///
+    /// Accepts `RGB`, `RGBA`, `RRGGBB`, and `RRGGBBAA`, optionally prefixed
+    /// with `#`. Colors without alpha are opaque.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let error = || ParseColorError(hex.to_owned());
+
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        let channel = |digits: &str| {
+            let value = u8::from_str_radix(digits, 16).map_err(|_| error())?;
+
+            // A single digit stands for a repeated one, so `f` means `ff`.
+            Ok(if digits.len() == 1 { value * 17 } else { value })
+        };
+
+        let width = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return Err(error()),
+        };
+
+        let mut color = Self::WHITE.0;
+        for (i, c) in color.iter_mut().enumerate() {
+            if let Some(digits) = digits.get(i * width..(i + 1) * width) {
+                *c = channel(digits)?;
+            }
+        }
+
+        Ok(Self(color))
+    }
+
+    /// Return a copy of the color, with the given alpha
+    pub const fn with_alpha(self, alpha: u8) -> Self {
+        let [r, g, b, _] = self.0;
+        Self([r, g, b, alpha])
+    }
+
+    /// Access the alpha channel of the color
+    pub const fn alpha(&self) -> u8 {
+        self.0[3]
+    }
+
+    /// Indicate whether the color is fully opaque
+    pub const fn is_opaque(&self) -> bool {
+        self.alpha() == 255
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::RED
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+impl From<[u8; 4]> for Color {
+    fn from(rgba: [u8; 4]) -> Self {
+        Self(rgba)
+    }
+}
+
+impl From<[u8; 3]> for Color {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Self::rgb(r, g, b)
+    }
+}
+
+impl From<Color> for [u8; 4] {
+    fn from(color: Color) -> Self {
+        color.0
+    }
+}
+
+/// Error parsing a [`Color`] from a hex string
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` is not a valid hex color", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn from_hex() {
+        assert_eq!(Color::from_hex("#f80"), Ok(Color::rgb(255, 136, 0)));
+        assert_eq!(Color::from_hex("f808"), Ok(Color::rgba(255, 136, 0, 136)));
+        assert_eq!(Color::from_hex("#FF8000"), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!(
+            Color::from_hex("ff800080"),
+            Ok(Color::rgba(255, 128, 0, 128)),
+        );
+    }
+
+    #[test]
+    fn from_hex_invalid() {
+        for hex in [
+            "",
+            "#",
+            "##f80",
+            "f8",
+            "f8000",
+            "ff80000",
+            "ff8000800",
+            "#gg8000",
+            "+f80",
+            "ff 800",
+            "fö80",
+        ] {
+            assert!(Color::from_hex(hex).is_err(), "{hex:?}");
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{abi::ffi_safe, Color, Shape};
+
+/// A shape, with debug geometry attached to it
+///
+/// Debug geometry is not part of the model. It is only displayed by the
+/// viewer, if rendering of debug information is enabled. This can help with
+/// developing complicated models, by showing intermediate values, like points
+/// that a sketch is computed from.
+///
+/// Debug geometry is defined in the coordinate system of the shape it is
+/// attached to. If the shape is transformed, so is its debug geometry.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]).unwrap();
+/// use fj::{syntax::*, Color, DebugElement, DebugGeometry};
+///
+/// let debug = DebugGeometry::from_elements(vec![
+///     DebugElement::point([0., 0., 0.], Color::BLUE),
+///     DebugElement::line([[0., 0., 0.], [1., 1., 0.]], Color::GREEN),
+///     DebugElement::label([1., 1., 0.], "corner", Color::BLACK),
+/// ]);
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let shape = shape.with_debug(debug);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct WithDebug {
+    /// The shape that the debug geometry is attached to
+    pub shape: Shape,
+
+    /// The debug geometry
+    pub geometry: DebugGeometry,
+}
+
+impl From<WithDebug> for Shape {
+    fn from(shape: WithDebug) -> Self {
+        Self::Debug(Box::new(shape))
+    }
+}
+
+/// Geometry that is displayed for debugging, but is not part of the model
+///
+/// See [`WithDebug`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct DebugGeometry {
+    elements: ffi_safe::Vec<DebugElement>,
+}
+
+impl DebugGeometry {
+    /// Create debug geometry from a list of elements
+    pub fn from_elements(elements: Vec<DebugElement>) -> Self {
+        Self {
+            elements: elements.into(),
+        }
+    }
+
+    /// Access the elements of the debug geometry
+    pub fn elements(&self) -> &[DebugElement] {
+        &self.elements
+    }
+}
+
+/// An element of [`DebugGeometry`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum DebugElement {
+    /// A point
+    Point {
+        /// The position of the point
+        position: [f64; 3],
+
+        /// The color of the point
+        color: Color,
+    },
+
+    /// A line segment
+    Line {
+        /// The start and end points of the line segment
+        points: [[f64; 3]; 2],
+
+        /// The color of the line segment
+        color: Color,
+    },
+
+    /// A text label
+    Label {
+        /// The position that the label is attached to
+        position: [f64; 3],
+
+        /// The text of the label
+        text: ffi_safe::String,
+
+        /// The color of the text
+        color: Color,
+    },
+}
+
+impl DebugElement {
+    /// Create a point
+    pub fn point(position: [f64; 3], color: impl Into<Color>) -> Self {
+        Self::Point {
+            position,
+            color: color.into(),
+        }
+    }
+
+    /// Create a line segment
+    pub fn line(points: [[f64; 3]; 2], color: impl Into<Color>) -> Self {
+        Self::Line {
+            points,
+            color: color.into(),
+        }
+    }
+
+    /// Create a text label
+    pub fn label(
+        position: [f64; 3],
+        text: impl Into<String>,
+        color: impl Into<Color>,
+    ) -> Self {
+        Self::Label {
+            position,
+            text: text.into().into(),
+            color: color.into(),
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Import of 2D profiles from DXF files
+//!
+//! Profiles that have been drawn in another CAD application can be converted
+//! into sketches, which can then be swept like any other 2D shape:
+//!
+//! ``` rust
+//! # let dxf = "0\nSECTION\n2\nENTITIES\n0\nCIRCLE\n10\n0\n20\n0\n40\n1\n0\nENDSEC\n0\nEOF\n";
+//! use fj::syntax::*;
+//!
+//! // `dxf` could come from `include_str!("profile.dxf")`
+//! for profile in fj::dxf::profiles(dxf).unwrap() {
+//!     let solid = profile.sweep([0., 0., 1.]);
+//! }
+//! ```
+//!
+//! Only the ASCII variant of DXF is supported. Of the entities in the
+//! `ENTITIES` section, `LINE`, `ARC`, `CIRCLE`, and `LWPOLYLINE` are read. All
+//! other entities are ignored, as are the z-coordinates of the entities that
+//! are read.
+//!
+//! Lines, arcs, and polylines are joined at their endpoints, to form closed
+//! cycles. A cycle that lies within another cycle is a hole in that cycle,
+//! unless it also lies within a hole, which makes it a profile of its own.
+
+use std::fmt;
+
+use crate::{
+    profile::{self, distance, FillRule, Segment, TOLERANCE},
+    Shape2d,
+};
+
+/// Read the profiles from a DXF file
+///
+/// Each of the returned shapes is an outer cycle, with all of its holes cut
+/// out of it.
+pub fn profiles(dxf: &str) -> Result<Vec<Shape2d>, DxfError> {
+    let segments = read_entities(dxf)?;
+    let cycles = join_segments(segments)?;
+    Ok(profile::profiles(cycles, FillRule::EvenOdd))
+}
+
+/// Error reading profiles from a DXF file
+#[derive(Clone, Debug, PartialEq)]
+pub enum DxfError {
+    /// The file ended in the middle of a group code/value pair
+    UnexpectedEnd,
+
+    /// A group code or value could not be parsed
+    InvalidValue {
+        /// The line the value is on, starting at 1
+        line: usize,
+    },
+
+    /// A required value of an entity is missing
+    MissingValue {
+        /// The type of the entity
+        entity: String,
+
+        /// The group code of the missing value
+        code: i32,
+    },
+
+    /// A chain of segments doesn't form a closed cycle
+    OpenProfile {
+        /// The point at which the chain ends
+        end: [f64; 2],
+    },
+}
+
+impl fmt::Display for DxfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "DXF file ended unexpectedly"),
+            Self::InvalidValue { line } => {
+                write!(f, "Invalid value in DXF file on line {line}")
+            }
+            Self::MissingValue { entity, code } => {
+                write!(f, "`{entity}` entity is missing group code {code}")
+            }
+            Self::OpenProfile { end: [x, y] } => {
+                write!(f, "Profile is not closed; open end at ({x}, {y})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DxfError {}
+
+fn read_entities(dxf: &str) -> Result<Vec<Segment>, DxfError> {
+    let mut lines = dxf.lines().enumerate();
+    let mut pairs = Vec::new();
+
+    while let Some((i, code)) = lines.next() {
+        let code = code
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| DxfError::InvalidValue { line: i + 1 })?;
+        let (_, value) = lines.next().ok_or(DxfError::UnexpectedEnd)?;
+
+        pairs.push((i + 2, code, value.trim()));
+    }
+
+    let mut segments = Vec::new();
+    let mut in_entities = false;
+
+    // Every entity starts with a pair of group code 0, which also ends the
+    // previous one. Whatever comes before the first entity is skipped.
+    let names = pairs
+        .iter()
+        .filter(|&&(_, code, _)| code == 0)
+        .map(|&(_, _, value)| value);
+    let groups = pairs.split(|&(_, code, _)| code == 0).skip(1);
+
+    for (name, groups) in names.zip(groups) {
+        match name {
+            "SECTION" => {
+                in_entities = groups
+                    .iter()
+                    .any(|&(_, code, value)| code == 2 && value == "ENTITIES");
+            }
+            "ENDSEC" => in_entities = false,
+            _ if in_entities => {
+                let entity = Entity { name, groups };
+                let start = segments.len();
+
+                entity.read(&mut segments)?;
+
+                if entity.is_mirrored()? {
+                    for segment in &mut segments[start..] {
+                        *segment = segment.mirror(|[x, y]| [-x, y]);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(segments)
+}
+
+struct Entity<'r> {
+    name: &'r str,
+    groups: &'r [(usize, i32, &'r str)],
+}
+
+impl Entity<'_> {
+    fn read(&self, segments: &mut Vec<Segment>) -> Result<(), DxfError> {
+        match self.name {
+            "LINE" => {
+                let start = [self.value(10)?, self.value(20)?];
+                let end = [self.value(11)?, self.value(21)?];
+
+                segments.push(Segment::line(start, end));
+            }
+            "ARC" => {
+                let center = [self.value(10)?, self.value(20)?];
+                let radius = self.value(40)?;
+                let start = self.value(50)?;
+                let mut end = self.value(51)?;
+
+                // Arcs always go counter-clockwise from start to end.
+                while end <= start {
+                    end += 360.;
+                }
+
+                let point = |angle: f64| {
+                    let angle = angle.to_radians();
+                    [
+                        center[0] + radius * angle.cos(),
+                        center[1] + radius * angle.sin(),
+                    ]
+                };
+
+                segments.push(Segment {
+                    start: point(start),
+                    end: point(end),
+                    angle: (end - start).to_radians(),
+                });
+            }
+            "CIRCLE" => {
+                let [x, y] = [self.value(10)?, self.value(20)?];
+                let radius = self.value(40)?;
+
+                let a = [x + radius, y];
+                let b = [x - radius, y];
+                let angle = std::f64::consts::PI;
+
+                segments.push(Segment {
+                    start: a,
+                    end: b,
+                    angle,
+                });
+                segments.push(Segment {
+                    start: b,
+                    end: a,
+                    angle,
+                });
+            }
+            "LWPOLYLINE" => {
+                let flags = self.optional(70)?.unwrap_or(0.) as i32;
+                let closed = flags & 1 == 1;
+
+                // The bulge of a vertex applies to the segment that starts
+                // there. It's the tangent of a quarter of the arc's angle.
+                let mut vertices: Vec<([f64; 2], f64)> = Vec::new();
+                for &(line, code, value) in self.groups {
+                    let value = || {
+                        value
+                            .parse::<f64>()
+                            .map_err(|_| DxfError::InvalidValue { line })
+                    };
+
+                    match code {
+                        10 => vertices.push(([value()?, 0.], 0.)),
+                        20 => {
+                            if let Some((vertex, _)) = vertices.last_mut() {
+                                vertex[1] = value()?;
+                            }
+                        }
+                        42 => {
+                            if let Some((_, bulge)) = vertices.last_mut() {
+                                *bulge = value()?;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                let num_segments = if closed {
+                    vertices.len()
+                } else {
+                    vertices.len().saturating_sub(1)
+                };
+
+                for i in 0..num_segments {
+                    let (start, bulge) = vertices[i];
+                    let (end, _) = vertices[(i + 1) % vertices.len()];
+
+                    segments.push(Segment {
+                        start,
+                        end,
+                        angle: bulge.atan() * 4.,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Whether the entity's coordinate system is mirrored
+    ///
+    /// Entities are defined in a coordinate system that depends on their
+    /// extrusion direction. For 2D drawings, the only other direction that
+    /// occurs is the negative z-axis, which mirrors the x-axis.
+    fn is_mirrored(&self) -> Result<bool, DxfError> {
+        Ok(self.optional(230)?.map(|z| z < 0.).unwrap_or(false))
+    }
+
+    fn value(&self, code: i32) -> Result<f64, DxfError> {
+        self.optional(code)?.ok_or_else(|| DxfError::MissingValue {
+            entity: self.name.to_owned(),
+            code,
+        })
+    }
+
+    fn optional(&self, code: i32) -> Result<Option<f64>, DxfError> {
+        let Some(&(line, _, value)) =
+            self.groups.iter().find(|&&(_, c, _)| c == code)
+        else {
+            return Ok(None);
+        };
+
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| DxfError::InvalidValue { line })
+    }
+}
+
+fn join_segments(
+    mut segments: Vec<Segment>,
+) -> Result<Vec<Vec<Segment>>, DxfError> {
+    segments.retain(|segment| segment.length() > TOLERANCE);
+
+    let mut cycles = Vec::new();
+
+    while !segments.is_empty() {
+        let first = segments.remove(0);
+        let mut cycle = vec![first];
+
+        loop {
+            let end = cycle[cycle.len() - 1].end;
+            if distance(end, first.start) <= TOLERANCE {
+                break;
+            }
+
+            let next = segments.iter().position(|segment| {
+                distance(segment.start, end) <= TOLERANCE
+                    || distance(segment.end, end) <= TOLERANCE
+            });
+            let Some(next) = next else {
+                return Err(DxfError::OpenProfile { end });
+            };
+
+            let next = segments.remove(next);
+            if distance(next.start, end) <= TOLERANCE {
+                cycle.push(next);
+            } else {
+                cycle.push(next.reverse());
+            }
+        }
+
+        cycles.push(cycle);
+    }
+
+    Ok(cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Shape2d, SketchSegmentRoute};
+
+    use super::{profiles, DxfError};
+
+    #[test]
+    fn profile_with_hole() -> Result<(), DxfError> {
+        let dxf = dxf(&[
+            // A square, made of a closed polyline.
+            &[
+                "LWPOLYLINE",
+                "90",
+                "4",
+                "70",
+                "1",
+                "10",
+                "0",
+                "20",
+                "0",
+                "10",
+                "4",
+                "20",
+                "0",
+                "10",
+                "4",
+                "20",
+                "4",
+                "10",
+                "0",
+                "20",
+                "4",
+            ],
+            // A hole in the square.
+            &["CIRCLE", "10", "2", "20", "2", "40", "1"],
+            // A separate profile, made of a line and an arc.
+            &["LINE", "10", "10", "20", "0", "11", "12", "21", "0"],
+            &[
+                "ARC", "10", "11", "20", "0", "40", "1", "50", "0", "51", "180",
+            ],
+        ]);
+
+        let profiles = profiles(&dxf)?;
+        assert_eq!(profiles.len(), 2);
+
+        let Shape2d::Difference(difference) = &profiles[0] else {
+            panic!("Expected square with hole");
+        };
+        let [Shape2d::Sketch(square), Shape2d::Sketch(hole)] =
+            difference.shapes()
+        else {
+            panic!("Expected square and hole to be sketches");
+        };
+        assert_eq!(segments(square).len(), 4);
+        assert_eq!(segments(hole).len(), 2);
+
+        let Shape2d::Sketch(half_disc) = &profiles[1] else {
+            panic!("Expected half disc without holes");
+        };
+        let segments = segments(half_disc);
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().any(|segment| matches!(
+            segment.route,
+            SketchSegmentRoute::Arc { angle } if angle.rad() > 0.
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_profile() {
+        let dxf = dxf(&[&["LINE", "10", "0", "20", "0", "11", "1", "21", "0"]]);
+        assert_eq!(
+            profiles(&dxf),
+            Err(DxfError::OpenProfile { end: [1., 0.] })
+        );
+    }
+
+    fn dxf(entities: &[&[&str]]) -> String {
+        let mut lines = vec!["0", "SECTION", "2", "ENTITIES"];
+        for entity in entities {
+            lines.push("0");
+            lines.extend(entity.iter());
+        }
+        lines.extend(["0", "ENDSEC", "0", "EOF"]);
+
+        lines.join("\n")
+    }
+
+    fn segments(sketch: &crate::Sketch) -> Vec<crate::SketchSegment> {
+        match sketch.chain() {
+            crate::Chain::PolyChain(chain) => chain.to_segments(),
+            crate::Chain::Circle(_) => panic!("Expected polygonal chain"),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+
+use crate::{abi::ffi_safe, Shape};
+
+/// A relief, made from a grayscale image
+///
+/// The relief stands on the x-y plane. It has a flat bottom and a top surface
+/// that is displaced by the brightness of the image: Each pixel is a sample of
+/// the top surface, which is `base` high where the image is black, and
+/// `base + relief` high where it is white.
+///
+/// The image covers `size` in the x-y plane, with its first row along the top
+/// edge, at the largest y-coordinate. The centers of the outermost pixels are
+/// on the edges of the relief.
+///
+/// This can be used for lithophanes, which are thin where they should let
+/// light through (see [`Heightmap::inverted`]), or for terrain models.
+///
+/// # Examples
+///
+/// ``` rust
+/// // A 3x2 image, as it would be decoded from an image file
+/// let pixels = vec![0, 128, 255, 255, 128, 0];
+///
+/// let heightmap = fj::Heightmap::from_luma8(3, pixels)
+///     .unwrap()
+///     .with_size([30., 20.])
+///     .with_base(1.)
+///     .with_relief(2.);
+/// let shape: fj::Shape = heightmap.into();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Heightmap {
+    width: usize,
+    samples: ffi_safe::Vec<f64>,
+    size: [f64; 2],
+    base: f64,
+    relief: f64,
+}
+
+impl Heightmap {
+    /// Create a heightmap from 8-bit grayscale pixels, stored row by row
+    ///
+    /// The heightmap is one unit high, plus one unit of relief, and covers one
+    /// unit per pixel, until changed using the other methods.
+    ///
+    /// Returns `None`, if the image is less than two pixels wide or high, or
+    /// if the number of pixels is not a multiple of `width`.
+    pub fn from_luma8(width: usize, pixels: Vec<u8>) -> Option<Self> {
+        let samples = pixels
+            .into_iter()
+            .map(|pixel| f64::from(pixel) / f64::from(u8::MAX))
+            .collect();
+        Self::from_samples(width, samples)
+    }
+
+    /// Create a heightmap from samples between `0.` and `1.`, stored row by row
+    ///
+    /// See [`Heightmap::from_luma8`] for details.
+    pub fn from_samples(width: usize, samples: Vec<f64>) -> Option<Self> {
+        if width < 2 || samples.len() % width != 0 || samples.len() / width < 2
+        {
+            return None;
+        }
+
+        let samples = samples
+            .into_iter()
+            .map(|sample| sample.clamp(0., 1.))
+            .collect::<Vec<_>>();
+        let height = samples.len() / width;
+
+        Some(Self {
+            width,
+            samples: samples.into(),
+            size: [(width - 1) as f64, (height - 1) as f64],
+            base: 1.,
+            relief: 1.,
+        })
+    }
+
+    /// Create a heightmap from a PGM (portable graymap) image
+    ///
+    /// Both the ASCII (`P2`) and binary (`P5`) variants are supported.
+    pub fn from_pgm(pgm: &[u8]) -> Result<Self, ParsePgmError> {
+        let mut tokens = PgmTokens { pgm, position: 0 };
+
+        let binary = match tokens.next() {
+            Some(b"P2") => false,
+            Some(b"P5") => true,
+            _ => return Err(ParsePgmError::InvalidHeader),
+        };
+
+        let mut header = [0; 3];
+        for value in &mut header {
+            *value = tokens
+                .next()
+                .and_then(|token| std::str::from_utf8(token).ok())
+                .and_then(|token| token.parse().ok())
+                .ok_or(ParsePgmError::InvalidHeader)?;
+        }
+        let [width, height, max] = header;
+        if max == 0 || max > usize::from(u16::MAX) {
+            return Err(ParsePgmError::InvalidHeader);
+        }
+
+        let num_samples = width * height;
+        let mut samples = Vec::with_capacity(num_samples);
+
+        if binary {
+            // A single whitespace character separates the header from the
+            // binary data.
+            let data = tokens.pgm.get(tokens.position + 1..).unwrap_or(&[]);
+            let bytes_per_sample = if max > 255 { 2 } else { 1 };
+
+            let data = data
+                .get(..num_samples * bytes_per_sample)
+                .ok_or(ParsePgmError::NotEnoughData)?;
+            for sample in data.chunks(bytes_per_sample) {
+                let value = sample
+                    .iter()
+                    .fold(0, |value, &byte| value << 8 | usize::from(byte));
+                samples.push(value as f64 / max as f64);
+            }
+        } else {
+            for _ in 0..num_samples {
+                let token =
+                    tokens.next().ok_or(ParsePgmError::NotEnoughData)?;
+                let value = std::str::from_utf8(token)
+                    .ok()
+                    .and_then(|token| token.parse::<usize>().ok())
+                    .ok_or(ParsePgmError::InvalidSample)?;
+
+                samples.push(value as f64 / max as f64);
+            }
+        }
+
+        Self::from_samples(width, samples).ok_or(ParsePgmError::TooSmall)
+    }
+
+    /// Set the size of the heightmap in the x-y plane
+    pub fn with_size(mut self, size: [f64; 2]) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the thickness below the displaced top surface
+    pub fn with_base(mut self, base: f64) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Set the height difference between black and white pixels
+    pub fn with_relief(mut self, relief: f64) -> Self {
+        self.relief = relief;
+        self
+    }
+
+    /// Invert the heightmap, making dark pixels high and bright pixels low
+    ///
+    /// This is what lithophanes need, as their thin parts let more light
+    /// through.
+    pub fn inverted(mut self) -> Self {
+        self.samples = self
+            .samples()
+            .into_iter()
+            .map(|sample| 1. - sample)
+            .collect::<Vec<_>>()
+            .into();
+        self
+    }
+
+    /// Access the number of samples per row
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Access the number of rows
+    pub fn height(&self) -> usize {
+        self.samples.len() / self.width
+    }
+
+    /// Access the samples, row by row
+    pub fn samples(&self) -> Vec<f64> {
+        self.samples.clone().into()
+    }
+
+    /// Access the size of the heightmap in the x-y plane
+    pub fn size(&self) -> [f64; 2] {
+        self.size
+    }
+
+    /// Access the thickness below the displaced top surface
+    pub fn base(&self) -> f64 {
+        self.base
+    }
+
+    /// Access the height difference between black and white pixels
+    pub fn relief(&self) -> f64 {
+        self.relief
+    }
+}
+
+impl From<Heightmap> for Shape {
+    fn from(shape: Heightmap) -> Self {
+        Self::Heightmap(shape)
+    }
+}
+
+/// Error parsing a [`Heightmap`] from a PGM image
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParsePgmError {
+    /// The header is not that of a grayscale PGM image
+    InvalidHeader,
+
+    /// The image contains fewer samples than its size requires
+    NotEnoughData,
+
+    /// A sample of an ASCII image is not a number
+    InvalidSample,
+
+    /// The image is less than two pixels wide or high
+    TooSmall,
+}
+
+impl fmt::Display for ParsePgmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Self::InvalidHeader => "Invalid PGM header",
+            Self::NotEnoughData => "PGM image is missing data",
+            Self::InvalidSample => "Invalid sample in PGM image",
+            Self::TooSmall => "PGM image is too small for a heightmap",
+        };
+
+        write!(f, "{message}")
+    }
+}
+
+impl std::error::Error for ParsePgmError {}
+
+/// Splits a PGM image into whitespace-separated tokens, skipping comments
+struct PgmTokens<'r> {
+    pgm: &'r [u8],
+    position: usize,
+}
+
+impl PgmTokens<'_> {
+    fn current(&self) -> Option<u8> {
+        self.pgm.get(self.position).copied()
+    }
+}
+
+impl<'r> Iterator for PgmTokens<'r> {
+    type Item = &'r [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current()? {
+                b'#' => {
+                    while !matches!(self.current(), Some(b'\n') | None) {
+                        self.position += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+
+        let start = self.position;
+        while matches!(self.current(), Some(c) if !c.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+
+        Some(&self.pgm[start..self.position])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Heightmap, ParsePgmError};
+
+    #[test]
+    fn ascii_pgm() -> Result<(), ParsePgmError> {
+        let pgm = b"P2\n# A comment\n3 2\n4\n0 1 2\n4 3 2\n";
+        let heightmap = Heightmap::from_pgm(pgm)?;
+
+        assert_eq!(heightmap.width(), 3);
+        assert_eq!(heightmap.height(), 2);
+        assert_eq!(heightmap.samples(), [0., 0.25, 0.5, 1., 0.75, 0.5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_pgm() -> Result<(), ParsePgmError> {
+        let mut pgm = b"P5 2 2 255\n".to_vec();
+        pgm.extend([0, 255, 255, 0]);
+
+        let heightmap = Heightmap::from_pgm(&pgm)?;
+        assert_eq!(heightmap.samples(), [0., 1., 1., 0.]);
+
+        let mut pgm = b"P5 2 2 65535\n".to_vec();
+        pgm.extend([0, 0, 255, 255, 255, 255, 0, 0]);
+
+        let heightmap = Heightmap::from_pgm(&pgm)?;
+        assert_eq!(heightmap.samples(), [0., 1., 1., 0.]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pgm() {
+        assert_eq!(
+            Heightmap::from_pgm(b"P6 2 2 255\n"),
+            Err(ParsePgmError::InvalidHeader)
+        );
+        assert_eq!(
+            Heightmap::from_pgm(b"P2 2 2 255\n0 1 2"),
+            Err(ParsePgmError::NotEnoughData)
+        );
+        assert_eq!(
+            Heightmap::from_pgm(b"P2 2 2 255\n0 1 x 3"),
+            Err(ParsePgmError::InvalidSample)
+        );
+        assert_eq!(
+            Heightmap::from_pgm(b"P2 1 2 255\n0 1"),
+            Err(ParsePgmError::TooSmall)
+        );
+    }
+}
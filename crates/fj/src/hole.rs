@@ -0,0 +1,349 @@
+use crate::{Angle, Sketch, SketchSegment, SketchSegmentRoute};
+
+/// A hole, to be cut into a 2-dimensional shape
+///
+/// Holes are defined in the x-y plane, like sketches. Cutting a hole into a
+/// shape that is then swept results in a hole through the swept solid.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let plate = fj::Sketch::from_points(vec![[0., 0.], [20., 0.], [20., 20.], [0., 20.]]).unwrap();
+/// use fj::syntax::*;
+///
+/// let hole = fj::Hole::counterbore(fj::MetricSize::M4, [10., 10.]);
+/// let plate = plate.with_hole(&hole).sweep([0., 0., 5.]);
+/// ```
+///
+/// # Limitations
+///
+/// Only the bore of the hole is cut, and it always goes through the swept
+/// solid. The kernel doesn't support boolean operations between 3D shapes yet,
+/// which would be required for cutting a blind hole, the recess of a
+/// counterbore or countersink, or the thread of a threaded hole.
+///
+/// The [`HoleDepth`] and [`HoleStyle`] are still recorded, for example to be
+/// exported as metadata, but they don't affect the geometry.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Hole {
+    /// The position of the center of the hole in the x-y plane
+    pub position: [f64; 2],
+
+    /// The diameter of the hole's bore
+    pub diameter: f64,
+
+    /// The depth of the hole's bore
+    pub depth: HoleDepth,
+
+    /// The style of the hole
+    pub style: HoleStyle,
+}
+
+impl Hole {
+    /// Create a plain hole
+    pub fn new(position: [f64; 2], diameter: f64) -> Self {
+        Self {
+            position,
+            diameter,
+            depth: HoleDepth::Through,
+            style: HoleStyle::Plain,
+        }
+    }
+
+    /// Make this a blind hole with the given depth
+    pub fn with_depth(mut self, depth: f64) -> Self {
+        self.depth = HoleDepth::Blind(depth);
+        self
+    }
+
+    /// Create a clearance hole for a screw of the given size
+    ///
+    /// Uses the medium series of ISO 273.
+    pub fn clearance(size: MetricSize, position: [f64; 2]) -> Self {
+        Self::new(position, size.clearance_diameter())
+    }
+
+    /// Create a counterbored clearance hole for a socket head cap screw
+    ///
+    /// The counterbore fits the head of an ISO 4762 screw of the given size.
+    pub fn counterbore(size: MetricSize, position: [f64; 2]) -> Self {
+        Self {
+            style: HoleStyle::Counterbore {
+                diameter: size.counterbore_diameter(),
+                depth: size.nominal_diameter(),
+            },
+            ..Self::clearance(size, position)
+        }
+    }
+
+    /// Create a countersunk clearance hole for a flat head screw
+    ///
+    /// The countersink fits the head of an ISO 10642 screw of the given size.
+    pub fn countersink(size: MetricSize, position: [f64; 2]) -> Self {
+        Self {
+            style: HoleStyle::Countersink {
+                diameter: size.countersink_diameter(),
+                angle: Angle::from_deg(90.),
+            },
+            ..Self::clearance(size, position)
+        }
+    }
+
+    /// Create a hole that is tapped with a coarse thread of the given size
+    ///
+    /// The bore has the diameter of the tap drill.
+    pub fn threaded(size: MetricSize, position: [f64; 2]) -> Self {
+        let pitch = size.coarse_pitch();
+
+        Self {
+            style: HoleStyle::Threaded {
+                nominal_diameter: size.nominal_diameter(),
+                pitch,
+            },
+            ..Self::new(position, size.nominal_diameter() - pitch)
+        }
+    }
+
+    /// Create a sketch of the hole's bore
+    pub fn to_sketch(&self) -> Sketch {
+        let [x, y] = self.position;
+        let radius = self.diameter / 2.;
+
+        let half_circle = SketchSegmentRoute::Arc {
+            angle: Angle::from_deg(180.),
+        };
+        let segments = vec![
+            SketchSegment {
+                endpoint: [x - radius, y],
+                route: half_circle.clone(),
+            },
+            SketchSegment {
+                endpoint: [x + radius, y],
+                route: half_circle,
+            },
+        ];
+
+        Sketch::from_segments(segments)
+            .expect("Segments of a hole are not empty")
+    }
+}
+
+/// The depth of a [`Hole`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum HoleDepth {
+    /// The hole goes through the whole shape
+    Through,
+
+    /// The hole ends at the given depth
+    Blind(f64),
+}
+
+/// The style of a [`Hole`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum HoleStyle {
+    /// A plain cylindrical hole
+    Plain,
+
+    /// A hole with a cylindrical recess for the head of a screw
+    Counterbore {
+        /// The diameter of the recess
+        diameter: f64,
+
+        /// The depth of the recess
+        depth: f64,
+    },
+
+    /// A hole with a conical recess for the head of a screw
+    Countersink {
+        /// The diameter of the recess at the surface
+        diameter: f64,
+
+        /// The included angle of the cone
+        angle: Angle,
+    },
+
+    /// A hole with an internal thread
+    Threaded {
+        /// The nominal diameter of the thread
+        nominal_diameter: f64,
+
+        /// The pitch of the thread
+        pitch: f64,
+    },
+}
+
+/// An ISO metric screw size, used for [`Hole`] presets
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum MetricSize {
+    /// M3
+    M3,
+
+    /// M4
+    M4,
+
+    /// M5
+    M5,
+
+    /// M6
+    M6,
+
+    /// M8
+    M8,
+
+    /// M10
+    M10,
+
+    /// M12
+    M12,
+}
+
+impl MetricSize {
+    /// Access the nominal diameter
+    pub fn nominal_diameter(&self) -> f64 {
+        self.values()[0]
+    }
+
+    /// Access the pitch of the coarse thread
+    pub fn coarse_pitch(&self) -> f64 {
+        self.values()[1]
+    }
+
+    /// Access the diameter of a clearance hole (ISO 273, medium)
+    pub fn clearance_diameter(&self) -> f64 {
+        self.values()[2]
+    }
+
+    /// Access the diameter of a counterbore for an ISO 4762 screw head
+    pub fn counterbore_diameter(&self) -> f64 {
+        self.values()[3]
+    }
+
+    /// Access the diameter of a countersink for an ISO 10642 screw head
+    pub fn countersink_diameter(&self) -> f64 {
+        self.values()[4]
+    }
+
+    fn values(&self) -> [f64; 5] {
+        match self {
+            Self::M3 => [3., 0.5, 3.4, 6.5, 6.72],
+            Self::M4 => [4., 0.7, 4.5, 8., 8.96],
+            Self::M5 => [5., 0.8, 5.5, 10., 11.2],
+            Self::M6 => [6., 1., 6.6, 11., 13.44],
+            Self::M8 => [8., 1.25, 9., 15., 17.92],
+            Self::M10 => [10., 1.5, 11., 18., 22.4],
+            Self::M12 => [12., 1.75, 13.5, 20., 26.88],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Angle, Chain, Hole, HoleDepth, HoleStyle, MetricSize, SketchSegment,
+        SketchSegmentRoute,
+    };
+
+    #[test]
+    fn metric_presets() {
+        let size = MetricSize::M6;
+
+        let clearance = Hole::clearance(size, [1., 2.]);
+        assert_eq!(clearance.position, [1., 2.]);
+        assert_eq!(clearance.diameter, 6.6);
+        assert_eq!(clearance.depth, HoleDepth::Through);
+        assert_eq!(clearance.style, HoleStyle::Plain);
+
+        let counterbore = Hole::counterbore(size, [1., 2.]);
+        assert_eq!(counterbore.diameter, 6.6);
+        assert_eq!(
+            counterbore.style,
+            HoleStyle::Counterbore {
+                diameter: 11.,
+                depth: 6.,
+            }
+        );
+
+        let countersink = Hole::countersink(size, [1., 2.]);
+        assert_eq!(countersink.diameter, 6.6);
+        assert_eq!(
+            countersink.style,
+            HoleStyle::Countersink {
+                diameter: 13.44,
+                angle: Angle::from_deg(90.),
+            }
+        );
+
+        let threaded = Hole::threaded(size, [1., 2.]);
+        assert_eq!(threaded.diameter, 5.);
+        assert_eq!(
+            threaded.style,
+            HoleStyle::Threaded {
+                nominal_diameter: 6.,
+                pitch: 1.,
+            }
+        );
+
+        let blind = threaded.with_depth(10.);
+        assert_eq!(blind.depth, HoleDepth::Blind(10.));
+    }
+
+    #[test]
+    fn metric_sizes_increase() {
+        let sizes = [
+            MetricSize::M3,
+            MetricSize::M4,
+            MetricSize::M5,
+            MetricSize::M6,
+            MetricSize::M8,
+            MetricSize::M10,
+            MetricSize::M12,
+        ];
+
+        for size in sizes {
+            assert!(size.clearance_diameter() > size.nominal_diameter());
+            assert!(size.counterbore_diameter() > size.clearance_diameter());
+            assert!(size.countersink_diameter() > size.clearance_diameter());
+            assert!(size.coarse_pitch() < size.nominal_diameter());
+        }
+
+        for [smaller, larger] in sizes.windows(2).map(|w| [w[0], w[1]]) {
+            assert!(smaller.nominal_diameter() < larger.nominal_diameter());
+            assert!(smaller.coarse_pitch() < larger.coarse_pitch());
+        }
+    }
+
+    #[test]
+    fn to_sketch() {
+        let sketch = Hole::new([1., 2.], 4.).to_sketch();
+
+        let Chain::PolyChain(chain) = sketch.chain() else {
+            panic!("Expected hole to be sketched as a polygonal chain");
+        };
+        let half_circle = SketchSegmentRoute::Arc {
+            angle: Angle::from_deg(180.),
+        };
+        assert_eq!(
+            chain.to_segments(),
+            vec![
+                SketchSegment {
+                    endpoint: [-1., 2.],
+                    route: half_circle.clone(),
+                },
+                SketchSegment {
+                    endpoint: [3., 2.],
+                    route: half_circle,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::Shape;
+
+/// A 3-dimensional shape with the edges of another imprinted onto its faces
+///
+/// The faces of the shape are split along the outline of the faces of the
+/// tool that rest on them. This doesn't change the shape geometrically, but
+/// makes the contact area available as faces of its own, for example to mesh
+/// an assembly, or to select an area for printing. The tool itself is not part
+/// of the result.
+///
+/// # Examples
+///
+/// ``` rust
+/// use fj::syntax::*;
+///
+/// let base = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]]
+///     .sketch()
+///     .sweep([0., 0., 1.]);
+/// let tool = [[1., 1.], [2., 1.], [2., 2.], [1., 2.]]
+///     .sketch()
+///     .sweep([0., 0., 1.])
+///     .translate([0., 0., 1.]);
+///
+/// let base = fj::Imprint {
+///     shape: base.into(),
+///     tool: tool.into(),
+/// };
+/// ```
+///
+/// # Limitations
+///
+/// Only faces of the tool that lie within the plane of a planar face of the
+/// shape are imprinted. If the tool overlaps the shape, instead of resting on
+/// it, processing the shape fails.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Imprint {
+    /// The shape whose faces are split
+    pub shape: Shape,
+
+    /// The shape whose edges are imprinted
+    pub tool: Shape,
+}
+
+impl From<Imprint> for Shape {
+    fn from(shape: Imprint) -> Self {
+        Self::Imprint(Box::new(shape))
+    }
+}
@@ -0,0 +1,115 @@
+use crate::Shape;
+
+/// A lattice that fills the interior of a 3-dimensional shape
+///
+/// Only the lattice is part of the result, clipped by the boundary of the
+/// shape. Group it with a hollow version of the shape, to create a part with a
+/// lightweight interior.
+///
+/// # Examples
+///
+/// ``` rust
+/// use fj::syntax::*;
+///
+/// let cube = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]]
+///     .sketch()
+///     .sweep([0., 0., 2.]);
+///
+/// let lattice = fj::Lattice::Gyroid {
+///     cell_size: 0.5,
+///     thickness: 0.05,
+/// };
+/// let infill = fj::Infill::new(cube, lattice, 8).unwrap();
+/// let shape: fj::Shape = infill.into();
+/// ```
+///
+/// # Limitations
+///
+/// The lattice is sampled on a grid, and the result is only as accurate as
+/// that sampling. It consists of many small triangles, which makes it
+/// expensive to process.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Infill {
+    shape: Shape,
+    lattice: Lattice,
+    resolution: u32,
+}
+
+impl Infill {
+    /// Fill a shape with a lattice
+    ///
+    /// The lattice is sampled `resolution` times along each axis of a lattice
+    /// cell. Returns `None`, if `resolution` is zero, or if the cell size or
+    /// thickness of the lattice are not positive.
+    pub fn new(
+        shape: impl Into<Shape>,
+        lattice: Lattice,
+        resolution: u32,
+    ) -> Option<Self> {
+        let (Lattice::Grid {
+            cell_size,
+            thickness,
+        }
+        | Lattice::Gyroid {
+            cell_size,
+            thickness,
+        }) = lattice;
+
+        if !(cell_size > 0. && thickness > 0. && resolution > 0) {
+            return None;
+        }
+
+        Some(Self {
+            shape: shape.into(),
+            lattice,
+            resolution,
+        })
+    }
+
+    /// Access the shape that is filled
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the lattice that fills the shape
+    pub fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+
+    /// Access the number of samples along each axis of a lattice cell
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+}
+
+impl From<Infill> for Shape {
+    fn from(shape: Infill) -> Self {
+        Self::Infill(Box::new(shape))
+    }
+}
+
+/// The lattice of an [`Infill`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum Lattice {
+    /// A grid of struts along the x, y, and z axes
+    Grid {
+        /// The distance between neighboring struts
+        cell_size: f64,
+
+        /// The thickness of the struts
+        thickness: f64,
+    },
+
+    /// A gyroid, a triply periodic minimal surface, thickened into a sheet
+    Gyroid {
+        /// The size of the cell after which the gyroid repeats
+        cell_size: f64,
+
+        /// The thickness of the sheet
+        thickness: f64,
+    },
+}
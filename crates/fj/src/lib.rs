@@ -13,6 +13,13 @@
 //! need the Fornjot application. Please refer to the [Fornjot repository] for
 //! usage examples.
 //!
+//! ## Features
+//!
+//! - `serde`: Implements `Serialize` and `Deserialize` for [`Shape`] and all
+//!   the types it is made of, as well as for model metadata. This allows shapes
+//!   to be cached, compared between model versions, or created by tools that
+//!   are not written in Rust.
+//!
 //! [Fornjot]: https://www.fornjot.app/
 //! [Fornjot repository]: https://github.com/hannobraun/Fornjot
 
@@ -23,15 +30,46 @@ pub mod syntax;
 #[doc(hidden)]
 pub mod abi;
 mod angle;
+mod color;
+mod debug;
+pub mod dxf;
 mod group;
+mod heightmap;
+mod hole;
+mod imprint;
+mod infill;
 pub mod models;
+mod profile;
+mod remove_faces;
+mod rib;
+mod scale;
+mod selection;
 mod shape_2d;
+pub mod svg;
 mod sweep;
+mod texture;
+mod thread;
 mod transform;
 pub mod version;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    color::{Color, ParseColorError},
+    debug::{DebugElement, DebugGeometry, WithDebug},
+    group::Group,
+    heightmap::{Heightmap, ParsePgmError},
+    hole::{Hole, HoleDepth, HoleStyle, MetricSize},
+    imprint::Imprint,
+    infill::{Infill, Lattice},
+    remove_faces::RemoveFaces,
+    rib::Rib,
+    scale::Scale,
+    selection::FaceSelection,
+    shape_2d::*,
+    sweep::Sweep,
+    texture::{Texture, TexturePattern},
+    thread::{Thread, ThreadRepresentation},
+    transform::Transform,
 };
 pub use fj_proc::*;
 
@@ -40,15 +78,100 @@ pub use fj_proc::*;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum Shape {
+    /// A 3-dimensional shape with debug geometry attached
+    Debug(Box<WithDebug>),
+
     /// A group of two 3-dimensional shapes
     Group(Box<Group>),
 
+    /// A relief, made from a grayscale image
+    Heightmap(Heightmap),
+
+    /// A 3-dimensional shape with the edges of another imprinted onto it
+    Imprint(Box<Imprint>),
+
+    /// A lattice that fills the interior of a 3-dimensional shape
+    Infill(Box<Infill>),
+
+    /// A 3-dimensional shape with some of its faces removed
+    RemoveFaces(Box<RemoveFaces>),
+
+    /// A rib, made by thickening an open profile
+    Rib(Rib),
+
+    /// A scaled 3-dimensional shape
+    Scale(Box<Scale>),
+
     /// A 2D shape
     Shape2d(Shape2d),
 
     /// A sweep of 2-dimensional shape along the z-axis
     Sweep(Sweep),
 
+    /// A 3-dimensional shape with a texture applied to some of its faces
+    Texture(Box<Texture>),
+
+    /// An ISO metric thread
+    Thread(Thread),
+
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
 }
+
+#[cfg(feature = "serde")]
+impl Shape {
+    /// Serialize the shape to JSON
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserialize a shape from JSON
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{
+        syntax::*, Angle, Circle, Shape, Shape2d, SketchSegment,
+        SketchSegmentRoute,
+    };
+
+    #[test]
+    fn serialize_and_deserialize_shape() -> Result<(), serde_json::Error> {
+        let outer = crate::Sketch::from_circle(Circle::from_radius(1.))
+            .with_color([255, 0, 0, 255]);
+        let inner = crate::Sketch::from_segments(vec![
+            SketchSegment {
+                endpoint: [0., 0.],
+                route: SketchSegmentRoute::Direct,
+            },
+            SketchSegment {
+                endpoint: [0.5, 0.],
+                route: SketchSegmentRoute::Arc {
+                    angle: Angle::from_deg(90.),
+                },
+            },
+            SketchSegment {
+                endpoint: [0., 0.5],
+                route: SketchSegmentRoute::Direct,
+            },
+        ])
+        .unwrap();
+
+        let difference: Shape2d = outer.difference(&inner).into();
+        let sweep = difference.sweep([0., 0., 1.]);
+        let group = sweep.group(&[[0., 0.], [1., 0.], [0., 1.]].sketch());
+        let shape: Shape = group
+            .rotate([0., 0., 1.], Angle::from_rev(0.25))
+            .translate([1., 2., 3.])
+            .scale([1., 2., 3.])
+            .into();
+
+        let json = shape.to_json()?;
+        assert_eq!(Shape::from_json(&json)?, shape);
+
+        Ok(())
+    }
+}
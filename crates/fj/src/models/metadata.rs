@@ -1,6 +1,7 @@
 /// Information about a particular module that can be used by the host for
 /// things like introspection and search.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Metadata {
     /// A short, human-friendly name used to identify this module.
     pub name: String,
@@ -123,6 +124,7 @@ impl Metadata {
 
 /// Metadata about a [`crate::models::Model`].
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModelMetadata {
     /// A short, human-friendly name used to identify this model.
     pub name: String,
@@ -176,6 +178,7 @@ impl ModelMetadata {
 
 /// Metadata describing a model's argument.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArgumentMetadata {
     /// The name used to refer to this argument.
     pub name: String,
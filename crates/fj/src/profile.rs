@@ -0,0 +1,251 @@
+//! Conversion of imported 2D geometry into sketches
+//!
+//! Shared by the importers of the various file formats.
+
+use crate::{
+    Angle, Difference2d, Shape2d, Sketch, SketchSegment, SketchSegmentRoute,
+};
+
+/// The distance within which points are considered equal
+pub const TOLERANCE: f64 = 1e-6;
+
+/// The number of points per arc, when approximating cycles as polygons
+const POINTS_PER_ARC: u32 = 16;
+
+/// A line or circular arc
+#[derive(Clone, Copy, Debug)]
+pub struct Segment {
+    pub start: [f64; 2],
+    pub end: [f64; 2],
+
+    /// The angle of the arc in radians, or zero for a line
+    ///
+    /// Positive angles are counter-clockwise.
+    pub angle: f64,
+}
+
+impl Segment {
+    pub fn line(start: [f64; 2], end: [f64; 2]) -> Self {
+        Self {
+            start,
+            end,
+            angle: 0.,
+        }
+    }
+
+    pub fn length(&self) -> f64 {
+        distance(self.start, self.end)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self {
+            start: self.end,
+            end: self.start,
+            angle: -self.angle,
+        }
+    }
+
+    /// Mirror the segment, using the given function to mirror its points
+    pub fn mirror(self, mirror: impl Fn([f64; 2]) -> [f64; 2]) -> Self {
+        Self {
+            start: mirror(self.start),
+            end: mirror(self.end),
+            angle: -self.angle,
+        }
+    }
+
+    /// Approximate the segment, excluding its end point
+    fn approx(&self, points: &mut Vec<[f64; 2]>) {
+        if self.angle == 0. {
+            points.push(self.start);
+            return;
+        }
+
+        let [sx, sy] = self.start;
+        let [ex, ey] = self.end;
+
+        let [dx, dy] = [ex - sx, ey - sy];
+        let half_chord = (dx * dx + dy * dy).sqrt() / 2.;
+
+        // The center is to the left of the chord for counter-clockwise arcs
+        // of less than half a circle, and moves to the right for larger ones.
+        let offset = half_chord / (self.angle / 2.).tan();
+        let [nx, ny] = [-dy / (half_chord * 2.), dx / (half_chord * 2.)];
+        let center =
+            [(sx + ex) / 2. + nx * offset, (sy + ey) / 2. + ny * offset];
+
+        let radius = half_chord / (self.angle.abs() / 2.).sin();
+        let start_angle = (sy - center[1]).atan2(sx - center[0]);
+
+        for i in 0..POINTS_PER_ARC {
+            let t = f64::from(i) / f64::from(POINTS_PER_ARC);
+            let angle = start_angle + self.angle * t;
+            points.push([
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]);
+        }
+    }
+}
+
+/// The rule that decides which regions enclosed by cycles are filled
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FillRule {
+    /// A region is filled, if it is enclosed by an odd number of cycles
+    EvenOdd,
+
+    /// A region is filled, if the cycles that enclose it don't cancel out
+    ///
+    /// Cycles cancel each other out, if they wind around the region in
+    /// opposite directions.
+    NonZero,
+}
+
+impl FillRule {
+    fn is_filled(&self, winding: i32, enclosing: i32) -> bool {
+        match self {
+            Self::EvenOdd => enclosing % 2 == 1,
+            Self::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Convert closed cycles into sketches, with holes where they aren't filled
+///
+/// Cycles that aren't the boundary between a filled and an unfilled region
+/// don't contribute to the result.
+pub fn profiles(
+    cycles: Vec<Vec<Segment>>,
+    fill_rule: FillRule,
+) -> Vec<Shape2d> {
+    let cycles = cycles
+        .into_iter()
+        .filter_map(|segments| {
+            let mut polygon = Vec::new();
+            for segment in &segments {
+                segment.approx(&mut polygon);
+            }
+
+            let area = signed_area(&polygon);
+            (area.abs() > TOLERANCE * TOLERANCE)
+                .then_some((segments, polygon, area))
+        })
+        .collect::<Vec<_>>();
+
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+
+    for (i, (segments, polygon, area)) in cycles.iter().enumerate() {
+        let point = polygon[0];
+
+        let mut winding = 0;
+        let mut enclosing = 0;
+        for (j, (_, other, other_area)) in cycles.iter().enumerate() {
+            if i != j && contains(other, point) {
+                winding += other_area.signum() as i32;
+                enclosing += 1;
+            }
+        }
+
+        let outside = fill_rule.is_filled(winding, enclosing);
+        let inside =
+            fill_rule.is_filled(winding + area.signum() as i32, enclosing + 1);
+
+        // All cycles are made counter-clockwise. Holes are subtracted, which
+        // reverses them as required.
+        let segments = if *area < 0. {
+            segments
+                .iter()
+                .rev()
+                .map(|segment| segment.reverse())
+                .collect()
+        } else {
+            segments.clone()
+        };
+
+        let cycle = (segments, polygon, area.abs());
+        match (inside, outside) {
+            (true, false) => outers.push(cycle),
+            (false, true) => holes.push(cycle),
+            _ => {}
+        }
+    }
+
+    let sketch = |segments: &[Segment]| -> Shape2d {
+        let segments = segments
+            .iter()
+            .map(|segment| SketchSegment {
+                endpoint: segment.end,
+                route: if segment.angle == 0. {
+                    SketchSegmentRoute::Direct
+                } else {
+                    SketchSegmentRoute::Arc {
+                        angle: Angle::from_rad(segment.angle),
+                    }
+                },
+            })
+            .collect();
+
+        Sketch::from_segments(segments)
+            .expect("Cycles are not empty")
+            .into()
+    };
+
+    let mut profiles = outers
+        .iter()
+        .map(|(segments, _, _)| sketch(segments))
+        .collect::<Vec<_>>();
+
+    for (segments, polygon, area) in holes {
+        // A hole belongs to the smallest outer cycle that contains it.
+        let parent = outers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, outer, outer_area))| {
+                *outer_area > area && contains(outer, polygon[0])
+            })
+            .min_by(|(_, (_, _, a)), (_, (_, _, b))| a.total_cmp(b))
+            .map(|(i, _)| i);
+
+        if let Some(i) = parent {
+            let profile = profiles[i].clone();
+            profiles[i] =
+                Difference2d::from_shapes([profile, sketch(&segments)]).into();
+        }
+    }
+
+    profiles
+}
+
+pub fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+fn signed_area(polygon: &[[f64; 2]]) -> f64 {
+    let mut area = 0.;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+
+    area / 2.
+}
+
+fn contains(polygon: &[[f64; 2]], [x, y]: [f64; 2]) -> bool {
+    let mut inside = false;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        if (a[1] > y) != (b[1] > y) {
+            let intersection =
+                a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+            if x < intersection {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
@@ -0,0 +1,45 @@
+use crate::{FaceSelection, Shape};
+
+/// A 3-dimensional shape with some of its faces removed
+///
+/// The opening that this leaves is healed, which can be used to defeature a
+/// model, for example by removing the wall of a hole.
+///
+/// # Examples
+///
+/// ``` rust
+/// use fj::syntax::*;
+///
+/// let plate = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]].sketch();
+/// let hole = fj::Hole::new([2., 2.], 1.);
+/// let plate = plate.with_hole(&hole).sweep([0., 0., 1.]);
+///
+/// // The plate without the hole
+/// let plate = fj::RemoveFaces {
+///     shape: plate.into(),
+///     faces: fj::FaceSelection::Curved,
+/// };
+/// ```
+///
+/// # Limitations
+///
+/// Holes in the remaining faces that were only bordered by removed faces are
+/// closed, and any other openings are closed with a flat face. If an opening
+/// is not bounded by straight edges that lie in a plane, it can't be healed,
+/// and processing the shape fails.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct RemoveFaces {
+    /// The shape that faces are removed from
+    pub shape: Shape,
+
+    /// The faces that are removed
+    pub faces: FaceSelection,
+}
+
+impl From<RemoveFaces> for Shape {
+    fn from(shape: RemoveFaces) -> Self {
+        Self::RemoveFaces(Box::new(shape))
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{abi::ffi_safe, Shape};
+
+/// A rib, made by thickening an open profile
+///
+/// The profile is an open polygonal chain in the x-y plane. The rib is centered
+/// on that chain, `thickness` wide, and extends `height` along the z-axis.
+///
+/// If a target shape is set using [`Rib::up_to`], the rib only extends up to
+/// the next faces of that shape above the profile, but never further than
+/// `height`.
+///
+/// # Examples
+///
+/// ``` rust
+/// let rib = fj::Rib::from_profile(vec![[0., 0.], [2., 0.], [2., 1.]], 0.2, 1.)
+///     .unwrap();
+/// let shape: fj::Shape = rib.into();
+/// ```
+///
+/// # Limitations
+///
+/// The top of the rib is flat. If the faces above the rib are not parallel to
+/// the x-y plane, the rib extends up to the lowest point where it meets them.
+///
+/// The rib is not merged with the target shape, as the kernel doesn't support
+/// boolean operations between 3D shapes yet. Combine them using a
+/// [`crate::Group`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Rib {
+    profile: ffi_safe::Vec<[f64; 2]>,
+    thickness: f64,
+    height: f64,
+    target: Option<Box<Shape>>,
+}
+
+impl Rib {
+    /// Create a rib from an open profile
+    ///
+    /// Returns `None`, if the profile has less than two points, or if
+    /// `thickness` or `height` are not positive.
+    pub fn from_profile(
+        profile: Vec<[f64; 2]>,
+        thickness: f64,
+        height: f64,
+    ) -> Option<Self> {
+        if profile.len() < 2 || thickness <= 0. || height <= 0. {
+            return None;
+        }
+
+        Some(Self {
+            profile: profile.into(),
+            thickness,
+            height,
+            target: None,
+        })
+    }
+
+    /// Extend the rib up to the next faces of `target`
+    pub fn up_to(mut self, target: impl Into<Shape>) -> Self {
+        self.target = Some(Box::new(target.into()));
+        self
+    }
+
+    /// Access the profile of the rib
+    pub fn profile(&self) -> Vec<[f64; 2]> {
+        self.profile.clone().into()
+    }
+
+    /// Access the thickness of the rib
+    pub fn thickness(&self) -> f64 {
+        self.thickness
+    }
+
+    /// Access the height of the rib
+    ///
+    /// If the rib extends up to a target shape, this is its maximum height.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Access the shape that the rib extends up to, if any
+    pub fn target(&self) -> Option<&Shape> {
+        self.target.as_deref()
+    }
+}
+
+impl From<Rib> for Shape {
+    fn from(shape: Rib) -> Self {
+        Self::Rib(shape)
+    }
+}
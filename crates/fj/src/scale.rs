@@ -0,0 +1,39 @@
+use crate::Shape;
+
+/// A scaled 3-dimensional shape
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]).unwrap();
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let scaled = shape.scale([2., 1., 1.]);
+/// ```
+///
+/// The scaling can be non-uniform, meaning each axis can have a different
+/// factor. Circles in the shape can become ellipses as a result.
+///
+/// # Limitations
+///
+/// All factors must be positive. Mirroring a shape using negative factors is
+/// not supported.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Scale {
+    /// The shape being scaled
+    pub shape: Shape,
+
+    /// The scaling factors along the x, y, and z axes
+    pub factors: [f64; 3],
+}
+
+impl From<Scale> for Shape {
+    fn from(shape: Scale) -> Self {
+        Self::Scale(Box::new(shape))
+    }
+}
@@ -0,0 +1,20 @@
+/// A selection of the faces of a shape
+///
+/// Used by operations that only affect some faces of a shape, like
+/// [`crate::Texture`] and [`crate::RemoveFaces`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum FaceSelection {
+    /// All faces
+    All,
+
+    /// All faces that are not flat, like the side of a cylinder
+    Curved,
+
+    /// All flat faces whose front side points in the given direction
+    Planar {
+        /// The direction that the faces point in
+        normal: [f64; 3],
+    },
+}
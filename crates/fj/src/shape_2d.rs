@@ -1,4 +1,4 @@
-use crate::{abi::ffi_safe, Angle, Shape};
+use crate::{abi::ffi_safe, Angle, Color, Shape};
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug, PartialEq)]
@@ -109,7 +109,7 @@ impl Sketch {
         } else {
             Some(Self {
                 chain: Chain::PolyChain(PolyChain::from_segments(segments)),
-                color: [255, 0, 0, 255],
+                color: Color::default().into(),
             })
         }
     }
@@ -122,7 +122,7 @@ impl Sketch {
         } else {
             Some(Self {
                 chain: Chain::PolyChain(PolyChain::from_points(points)),
-                color: [255, 0, 0, 255],
+                color: Color::default().into(),
             })
         }
     }
@@ -131,13 +131,16 @@ impl Sketch {
     pub fn from_circle(circle: Circle) -> Self {
         Self {
             chain: Chain::Circle(circle),
-            color: [255, 0, 0, 255],
+            color: Color::default().into(),
         }
     }
 
-    /// Set the rendering color of the sketch in RGBA
-    pub fn with_color(mut self, color: [u8; 4]) -> Self {
-        self.color = color;
+    /// Set the rendering color of the sketch
+    ///
+    /// Accepts a [`Color`], or an array of RGBA or RGB channels. Colors that
+    /// are not fully opaque are rendered as transparent.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into().into();
         self
     }
 
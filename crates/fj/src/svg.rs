@@ -0,0 +1,569 @@
+//! Import of 2D profiles from SVG files
+//!
+//! Logos and other artwork can be converted into sketches, which can then be
+//! swept, to emboss them onto a part:
+//!
+//! ``` rust
+//! # let svg = r#"<svg><path d="M 0 0 H 10 V 10 H 0 Z" /></svg>"#;
+//! use fj::syntax::*;
+//!
+//! // `svg` could come from `include_str!("logo.svg")`
+//! for profile in fj::svg::profiles(svg).unwrap() {
+//!     let relief = profile.sweep([0., 0., 1.]);
+//! }
+//! ```
+//!
+//! Only the `d` attribute of `path` elements is read. All path commands are
+//! supported. Lines and circular arcs are converted as they are, while
+//! elliptical arcs and Bézier curves are approximated with straight lines.
+//! Whether a subpath is a hole is decided by the `fill-rule` of its path.
+//!
+//! Coordinates are used as they are, except for the y-axis, which points down
+//! in SVG and is flipped, so the profiles aren't mirrored. Transforms, view
+//! boxes, and units are not taken into account.
+
+use std::{f64::consts::PI, fmt};
+
+use crate::{
+    profile::{self, distance, FillRule, Segment, TOLERANCE},
+    Shape2d,
+};
+
+/// Read the profiles from an SVG file
+///
+/// Each of the returned shapes is an outer cycle, with all of its holes cut
+/// out of it.
+pub fn profiles(svg: &str) -> Result<Vec<Shape2d>, SvgError> {
+    let mut profiles = Vec::new();
+
+    for (path, attributes) in path_elements(svg).enumerate() {
+        let Some(data) = attribute(attributes, "d") else {
+            continue;
+        };
+
+        let cycles = PathData::new(data)
+            .parse()
+            .map_err(|position| SvgError::InvalidPathData { path, position })?
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|segment| segment.mirror(|[x, y]| [x, -y]))
+                    .collect()
+            })
+            .collect();
+
+        profiles.extend(profile::profiles(cycles, fill_rule(attributes)));
+    }
+
+    Ok(profiles)
+}
+
+/// Error reading profiles from an SVG file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SvgError {
+    /// The path data of a `path` element could not be parsed
+    InvalidPathData {
+        /// The index of the `path` element, in order of appearance
+        path: usize,
+
+        /// The position within the path data, in bytes
+        position: usize,
+    },
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPathData { path, position } => write!(
+                f,
+                "Invalid data of SVG path {path}, at position {position}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// The number of lines that a Bézier curve is approximated with
+const LINES_PER_CURVE: u32 = 16;
+
+/// Iterate over the attributes of all `path` elements
+fn path_elements(svg: &str) -> impl Iterator<Item = &str> {
+    svg.split('<').filter_map(|tag| {
+        let attributes = tag.strip_prefix("path")?;
+        if !attributes.starts_with(|c: char| c.is_whitespace() || c == '/') {
+            return None;
+        }
+
+        let end = attributes.find('>').unwrap_or(attributes.len());
+        Some(attributes[..end].trim_end_matches('/'))
+    })
+}
+
+fn attribute<'r>(attributes: &'r str, name: &str) -> Option<&'r str> {
+    let mut rest = attributes;
+
+    loop {
+        rest = rest.trim_start();
+        let (key, value) = rest.split_once('=')?;
+        let value = value.trim_start();
+
+        let quote = value.chars().next()?;
+        let (value, remaining) = value[quote.len_utf8()..].split_once(quote)?;
+
+        if key.trim() == name {
+            return Some(value);
+        }
+
+        rest = remaining;
+    }
+}
+
+fn fill_rule(attributes: &str) -> FillRule {
+    let style = attribute(attributes, "style").and_then(|style| {
+        style.split(';').find_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            (property.trim() == "fill-rule").then_some(value.trim())
+        })
+    });
+
+    match style.or_else(|| attribute(attributes, "fill-rule")) {
+        Some("evenodd") => FillRule::EvenOdd,
+        _ => FillRule::NonZero,
+    }
+}
+
+struct PathData<'r> {
+    data: &'r [u8],
+    position: usize,
+
+    cycles: Vec<Vec<Segment>>,
+    segments: Vec<Segment>,
+
+    start: [f64; 2],
+    current: [f64; 2],
+
+    /// The last control point of the previous command, if it was a curve
+    ///
+    /// Smooth curves reflect this, to compute their first control point.
+    control: Option<(u8, [f64; 2])>,
+}
+
+impl<'r> PathData<'r> {
+    fn new(data: &'r str) -> Self {
+        Self {
+            data: data.as_bytes(),
+            position: 0,
+            cycles: Vec::new(),
+            segments: Vec::new(),
+            start: [0., 0.],
+            current: [0., 0.],
+            control: None,
+        }
+    }
+
+    /// Parse the path data into cycles
+    ///
+    /// Returns the position of the error, if the path data is invalid.
+    fn parse(mut self) -> Result<Vec<Vec<Segment>>, usize> {
+        let mut command = None;
+
+        loop {
+            self.skip_separators();
+            let Some(&next) = self.data.get(self.position) else {
+                break;
+            };
+
+            if next.is_ascii_alphabetic() {
+                command = Some(next);
+                self.position += 1;
+            }
+
+            let Some(c) = command else {
+                return Err(self.position);
+            };
+            let relative = c.is_ascii_lowercase();
+            let absolute = c.to_ascii_uppercase();
+            let control = self.control.take();
+
+            match absolute {
+                b'M' => {
+                    let point = self.point(relative)?;
+                    self.close();
+                    self.start = point;
+                    self.current = point;
+
+                    // Further coordinates are implicit line commands.
+                    command = Some(if relative { b'l' } else { b'L' });
+                }
+                b'L' => {
+                    let point = self.point(relative)?;
+                    self.line_to(point);
+                }
+                b'H' => {
+                    let mut x = self.number()?;
+                    if relative {
+                        x += self.current[0];
+                    }
+                    self.line_to([x, self.current[1]]);
+                }
+                b'V' => {
+                    let mut y = self.number()?;
+                    if relative {
+                        y += self.current[1];
+                    }
+                    self.line_to([self.current[0], y]);
+                }
+                b'C' | b'S' => {
+                    let a = if absolute == b'C' {
+                        self.point(relative)?
+                    } else {
+                        self.reflect(control, b'C')
+                    };
+                    let b = self.point(relative)?;
+                    let end = self.point(relative)?;
+
+                    self.curve_to(
+                        |t| {
+                            let s = 1. - t;
+                            [
+                                s * s * s,
+                                3. * s * s * t,
+                                3. * s * t * t,
+                                t * t * t,
+                            ]
+                        },
+                        [a, b, end],
+                    );
+                    self.control = Some((b'C', b));
+                }
+                b'Q' | b'T' => {
+                    let a = if absolute == b'Q' {
+                        self.point(relative)?
+                    } else {
+                        self.reflect(control, b'Q')
+                    };
+                    let end = self.point(relative)?;
+
+                    // The curve has a single control point. Its weight is split
+                    // between both control points that are passed.
+                    self.curve_to(
+                        |t| {
+                            let s = 1. - t;
+                            [s * s, s * t, s * t, t * t]
+                        },
+                        [a, a, end],
+                    );
+                    self.control = Some((b'Q', a));
+                }
+                b'A' => {
+                    let radii = [self.number()?, self.number()?];
+                    let rotation = self.number()?;
+                    let large_arc = self.flag()?;
+                    let sweep = self.flag()?;
+                    let end = self.point(relative)?;
+
+                    self.arc_to(radii, rotation, large_arc, sweep, end);
+                }
+                b'Z' => {
+                    self.close();
+                    self.current = self.start;
+
+                    // Close commands don't take any coordinates.
+                    command = None;
+                }
+                _ => return Err(self.position - 1),
+            }
+        }
+
+        self.close();
+        Ok(self.cycles)
+    }
+
+    fn line_to(&mut self, end: [f64; 2]) {
+        let segment = Segment::line(self.current, end);
+        if segment.length() > TOLERANCE {
+            self.segments.push(segment);
+        }
+
+        self.current = end;
+    }
+
+    /// Approximate a Bézier curve from the current point
+    ///
+    /// `weights` computes the weights of the current point and the three
+    /// points that are passed, for a curve parameter between 0 and 1.
+    fn curve_to(
+        &mut self,
+        weights: impl Fn(f64) -> [f64; 4],
+        points: [[f64; 2]; 3],
+    ) {
+        let [a, b, c] = points;
+        let start = self.current;
+
+        for i in 1..=LINES_PER_CURVE {
+            let t = f64::from(i) / f64::from(LINES_PER_CURVE);
+            let [w0, w1, w2, w3] = weights(t);
+
+            self.line_to([
+                w0 * start[0] + w1 * a[0] + w2 * b[0] + w3 * c[0],
+                w0 * start[1] + w1 * a[1] + w2 * b[1] + w3 * c[1],
+            ]);
+        }
+    }
+
+    /// Add an elliptical arc from the current point
+    ///
+    /// Follows the conversion from endpoint to center parameterization from
+    /// the appendix of the SVG specification.
+    fn arc_to(
+        &mut self,
+        radii: [f64; 2],
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: [f64; 2],
+    ) {
+        let start = self.current;
+        let [mut rx, mut ry] = radii.map(f64::abs);
+
+        if distance(start, end) <= TOLERANCE {
+            return;
+        }
+        if rx <= TOLERANCE || ry <= TOLERANCE {
+            self.line_to(end);
+            return;
+        }
+
+        let (sin, cos) = rotation.to_radians().sin_cos();
+
+        let [dx, dy] = [(start[0] - end[0]) / 2., (start[1] - end[1]) / 2.];
+        let [x1, y1] = [cos * dx + sin * dy, -sin * dx + cos * dy];
+
+        // Radii that are too small to reach the end point are scaled up.
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1. {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator =
+            rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0.).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+
+        let [cx1, cy1] =
+            [coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx];
+        let center = [
+            cos * cx1 - sin * cy1 + (start[0] + end[0]) / 2.,
+            sin * cx1 + cos * cy1 + (start[1] + end[1]) / 2.,
+        ];
+
+        let angle = |[ux, uy]: [f64; 2], [vx, vy]: [f64; 2]| {
+            (ux * vy - uy * vx).atan2(ux * vx + uy * vy)
+        };
+        let u = [(x1 - cx1) / rx, (y1 - cy1) / ry];
+        let v = [(-x1 - cx1) / rx, (-y1 - cy1) / ry];
+
+        let start_angle = angle([1., 0.], u);
+        let mut sweep_angle = angle(u, v);
+        if sweep && sweep_angle < 0. {
+            sweep_angle += 2. * PI;
+        }
+        if !sweep && sweep_angle > 0. {
+            sweep_angle -= 2. * PI;
+        }
+
+        if (rx - ry).abs() <= TOLERANCE {
+            self.segments.push(Segment {
+                start,
+                end,
+                angle: sweep_angle,
+            });
+            self.current = end;
+            return;
+        }
+
+        let num_lines = (f64::from(LINES_PER_CURVE) * sweep_angle.abs() / PI)
+            .ceil()
+            .max(1.) as u32;
+
+        for i in 1..num_lines {
+            let t = f64::from(i) / f64::from(num_lines);
+            let (sin_t, cos_t) = (start_angle + sweep_angle * t).sin_cos();
+            let [x, y] = [rx * cos_t, ry * sin_t];
+
+            self.line_to([
+                cos * x - sin * y + center[0],
+                sin * x + cos * y + center[1],
+            ]);
+        }
+        self.line_to(end);
+    }
+
+    /// Finish the current subpath, closing it if necessary
+    fn close(&mut self) {
+        if self.segments.is_empty() {
+            return;
+        }
+
+        self.line_to(self.start);
+        self.cycles.push(std::mem::take(&mut self.segments));
+    }
+
+    /// Reflect the previous control point, for smooth curves
+    fn reflect(&self, control: Option<(u8, [f64; 2])>, kind: u8) -> [f64; 2] {
+        let [x, y] = self.current;
+
+        match control {
+            Some((previous, [cx, cy])) if previous == kind => {
+                [2. * x - cx, 2. * y - cy]
+            }
+            _ => self.current,
+        }
+    }
+
+    fn point(&mut self, relative: bool) -> Result<[f64; 2], usize> {
+        let mut point = [self.number()?, self.number()?];
+        if relative {
+            point[0] += self.current[0];
+            point[1] += self.current[1];
+        }
+
+        Ok(point)
+    }
+
+    fn flag(&mut self) -> Result<bool, usize> {
+        self.skip_separators();
+
+        let flag = match self.data.get(self.position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.position),
+        };
+        self.position += 1;
+
+        Ok(flag)
+    }
+
+    fn number(&mut self) -> Result<f64, usize> {
+        self.skip_separators();
+
+        let start = self.position;
+        let digits = |data: &[u8], mut position: usize| {
+            while matches!(data.get(position), Some(c) if c.is_ascii_digit()) {
+                position += 1;
+            }
+            position
+        };
+
+        let mut end = start;
+        if matches!(self.data.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        end = digits(self.data, end);
+        if self.data.get(end) == Some(&b'.') {
+            end = digits(self.data, end + 1);
+        }
+        if matches!(self.data.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+            if matches!(self.data.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+            if matches!(self.data.get(exponent), Some(c) if c.is_ascii_digit())
+            {
+                end = digits(self.data, exponent);
+            }
+        }
+
+        let number = std::str::from_utf8(&self.data[start..end])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(start)?;
+        self.position = end;
+
+        Ok(number)
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r' | b',') =
+            self.data.get(self.position)
+        {
+            self.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chain, Shape2d, SketchSegment, SketchSegmentRoute};
+
+    use super::{profiles, SvgError};
+
+    #[test]
+    fn holes_from_fill_rule() -> Result<(), SvgError> {
+        let squares = "M 0 0 H 10 V 10 H 0 Z M 2 2 h 6 v 6 h -6 z";
+
+        let even_odd = profiles(&svg(squares, "evenodd"))?;
+        assert_eq!(even_odd.len(), 1);
+        assert!(matches!(even_odd[0], Shape2d::Difference(_)));
+
+        // Both squares wind in the same direction, so the inner one doesn't
+        // make a difference.
+        let non_zero = profiles(&svg(squares, "nonzero"))?;
+        assert_eq!(non_zero.len(), 1);
+        assert!(matches!(non_zero[0], Shape2d::Sketch(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn arcs_and_curves() -> Result<(), SvgError> {
+        let half_disc = profiles(&svg("M0,0A5,5 0 0,1 10,0z", "nonzero"))?;
+        let half_disc = segments(&half_disc[0]);
+        assert_eq!(half_disc.len(), 2);
+        assert!(half_disc.iter().any(|segment| matches!(
+            segment.route,
+            SketchSegmentRoute::Arc { angle }
+                if (angle.rad().abs() - std::f64::consts::PI).abs() < 1e-9
+        )));
+
+        let curve = profiles(&svg("M0 0 C 0 5 10 5 10 0 Z", "nonzero"))?;
+        assert_eq!(segments(&curve[0]).len(), 17);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_path_data() {
+        assert_eq!(
+            profiles(&svg("M 0 0 L 1 x", "nonzero")),
+            Err(SvgError::InvalidPathData {
+                path: 0,
+                position: 10
+            })
+        );
+    }
+
+    fn svg(path: &str, fill_rule: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path fill-rule="{fill_rule}" d="{path}" />
+            </svg>"#
+        )
+    }
+
+    fn segments(shape: &Shape2d) -> Vec<SketchSegment> {
+        let Shape2d::Sketch(sketch) = shape else {
+            panic!("Expected sketch");
+        };
+        match sketch.chain() {
+            Chain::PolyChain(chain) => chain.to_segments(),
+            Chain::Circle(_) => panic!("Expected polygonal chain"),
+        }
+    }
+}
@@ -12,6 +12,9 @@ use crate::{Shape, Shape2d};
 ///
 /// // `shape` can be anything that converts to `fj::Shape2d`
 /// let group = shape.sweep([0., 0., 1.]);
+///
+/// // Sweep half the distance to each side of the shape
+/// let symmetric = shape.sweep_symmetric([0., 0., 1.]);
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -22,12 +25,53 @@ pub struct Sweep {
 
     /// The length and direction of the sweep
     path: [f64; 3],
+
+    /// The offset from the shape to the start of the sweep
+    #[cfg_attr(feature = "serde", serde(default))]
+    start: [f64; 3],
 }
 
 impl Sweep {
     /// Create a `Sweep` along a straight path
     pub fn from_path(shape: Shape2d, path: [f64; 3]) -> Self {
-        Self { shape, path }
+        Self {
+            shape,
+            path,
+            start: [0.; 3],
+        }
+    }
+
+    /// Create a `Sweep` that extends equally to both sides of the shape
+    ///
+    /// The sweep covers the length of `path` in total, half of it in the
+    /// direction of `path`, the other half in the opposite direction.
+    pub fn symmetric(shape: Shape2d, path: [f64; 3]) -> Self {
+        Self {
+            shape,
+            path,
+            start: path.map(|component| -component / 2.),
+        }
+    }
+
+    /// Create a `Sweep` that extends to both sides of the shape
+    ///
+    /// `forward` and `backward` are the paths from the shape to either end of
+    /// the sweep. They are expected to point in opposite directions.
+    pub fn two_sided(
+        shape: Shape2d,
+        forward: [f64; 3],
+        backward: [f64; 3],
+    ) -> Self {
+        let mut path = forward;
+        for (path, backward) in path.iter_mut().zip(backward) {
+            *path -= backward;
+        }
+
+        Self {
+            shape,
+            path,
+            start: backward,
+        }
     }
 
     /// Access the shape being swept
@@ -39,6 +83,13 @@ impl Sweep {
     pub fn path(&self) -> [f64; 3] {
         self.path
     }
+
+    /// Access the offset from the shape to the start of the sweep
+    ///
+    /// This is zero, unless the sweep extends to both sides of the shape.
+    pub fn start(&self) -> [f64; 3] {
+        self.start
+    }
 }
 
 impl From<Sweep> for Shape {
@@ -53,6 +53,44 @@ where
     }
 }
 
+/// Convenient syntax to cut an [`fj::Hole`] into a 2D shape
+///
+/// [`fj::Hole`]: crate::Hole
+pub trait Hole {
+    /// Create a difference between `self` and the bore of `hole`
+    fn with_hole(&self, hole: &crate::Hole) -> crate::Difference2d;
+}
+
+impl<T> Hole for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn with_hole(&self, hole: &crate::Hole) -> crate::Difference2d {
+        let a = self.clone().into();
+        let b = hole.to_sketch().into();
+
+        crate::Difference2d::from_shapes([a, b])
+    }
+}
+
+/// Convenient syntax to create an [`fj::Scale`]
+///
+/// [`fj::Scale`]: crate::Scale
+pub trait Scale {
+    /// Scale `self` by a separate factor along each axis
+    fn scale(&self, factors: [f64; 3]) -> crate::Scale;
+}
+
+impl<T> Scale for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn scale(&self, factors: [f64; 3]) -> crate::Scale {
+        let shape = self.clone().into();
+        crate::Scale { shape, factors }
+    }
+}
+
 /// Convenient syntax to create an [`fj::Sketch`]
 ///
 /// [`fj::Sketch`]: crate::Sketch
@@ -79,6 +117,13 @@ where
 pub trait Sweep {
     /// Sweep `self` along a straight path
     fn sweep(&self, path: [f64; 3]) -> crate::Sweep;
+
+    /// Sweep `self` along a straight path, half of it to each side
+    ///
+    /// See [`fj::Sweep::symmetric`].
+    ///
+    /// [`fj::Sweep::symmetric`]: crate::Sweep::symmetric
+    fn sweep_symmetric(&self, path: [f64; 3]) -> crate::Sweep;
 }
 
 impl<T> Sweep for T
@@ -89,6 +134,11 @@ where
         let shape = self.clone().into();
         crate::Sweep::from_path(shape, path)
     }
+
+    fn sweep_symmetric(&self, path: [f64; 3]) -> crate::Sweep {
+        let shape = self.clone().into();
+        crate::Sweep::symmetric(shape, path)
+    }
 }
 
 /// Convenient syntax to create an [`fj::Transform`]
@@ -131,3 +181,21 @@ where
         }
     }
 }
+
+/// Convenient syntax to create an [`fj::WithDebug`]
+///
+/// [`fj::WithDebug`]: crate::WithDebug
+pub trait WithDebug {
+    /// Attach debug geometry to `self`
+    fn with_debug(&self, geometry: crate::DebugGeometry) -> crate::WithDebug;
+}
+
+impl<T> WithDebug for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn with_debug(&self, geometry: crate::DebugGeometry) -> crate::WithDebug {
+        let shape = self.clone().into();
+        crate::WithDebug { shape, geometry }
+    }
+}
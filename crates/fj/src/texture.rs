@@ -0,0 +1,117 @@
+use crate::{FaceSelection, Shape};
+
+/// A 3-dimensional shape with a texture applied to some of its faces
+///
+/// Textures are repeating displacement patterns, like knurling. They are a
+/// purely cosmetic feature of the triangle mesh, and don't change the shape
+/// itself. This makes it practical to add grip surfaces to a model that would
+/// otherwise require hundreds of features.
+///
+/// # Examples
+///
+/// ``` rust
+/// let circle = fj::Sketch::from_circle(fj::Circle::from_radius(1.));
+/// let cylinder = fj::Sweep::from_path(circle.into(), [0., 0., 2.]);
+///
+/// let pattern = fj::TexturePattern::Diamond;
+/// let knurled = fj::Texture::new(cylinder, pattern, 0.2, 0.05)
+///     .unwrap()
+///     .on(fj::FaceSelection::Curved);
+/// let shape: fj::Shape = knurled.into();
+/// ```
+///
+/// # Limitations
+///
+/// The pattern is laid out in the coordinates of each face's surface, scaled
+/// to model units. It works best on planes and cylinders, where those
+/// coordinates are not distorted.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Texture {
+    shape: Shape,
+    pattern: TexturePattern,
+    pitch: f64,
+    depth: f64,
+    faces: FaceSelection,
+}
+
+impl Texture {
+    /// Apply a texture to all faces of a shape
+    ///
+    /// The pattern repeats after `pitch`, and is cut `depth` deep into the
+    /// faces. Returns `None`, if `pitch` is not positive, or if `depth` is
+    /// negative.
+    pub fn new(
+        shape: impl Into<Shape>,
+        pattern: TexturePattern,
+        pitch: f64,
+        depth: f64,
+    ) -> Option<Self> {
+        if !(pitch > 0. && depth >= 0.) {
+            return None;
+        }
+
+        Some(Self {
+            shape: shape.into(),
+            pattern,
+            pitch,
+            depth,
+            faces: FaceSelection::All,
+        })
+    }
+
+    /// Only apply the texture to the selected faces
+    pub fn on(mut self, faces: FaceSelection) -> Self {
+        self.faces = faces;
+        self
+    }
+
+    /// Access the shape that the texture is applied to
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the pattern of the texture
+    pub fn pattern(&self) -> TexturePattern {
+        self.pattern
+    }
+
+    /// Access the distance after which the pattern repeats
+    pub fn pitch(&self) -> f64 {
+        self.pitch
+    }
+
+    /// Access the depth of the pattern
+    pub fn depth(&self) -> f64 {
+        self.depth
+    }
+
+    /// Access the selection of faces that the texture is applied to
+    pub fn faces(&self) -> &FaceSelection {
+        &self.faces
+    }
+}
+
+impl From<Texture> for Shape {
+    fn from(shape: Texture) -> Self {
+        Self::Texture(Box::new(shape))
+    }
+}
+
+/// The pattern of a [`Texture`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum TexturePattern {
+    /// Grooves that run along the v-axis of each face's surface
+    ///
+    /// On the curved face of a cylinder, this is a straight knurl.
+    Ribs,
+
+    /// Grooves that run along both axes of each face's surface
+    Knurl,
+
+    /// Grooves that run diagonally to the axes of each face's surface
+    Diamond,
+}
@@ -0,0 +1,146 @@
+use crate::{MetricSize, Shape};
+
+/// An external ISO metric thread
+///
+/// Provides the dimensions of the basic profile of the thread, as defined by
+/// ISO 68-1. As a shape, the thread starts at the origin and extends along
+/// the z-axis. It is either represented by a cylinder, or modeled in full. See
+/// [`ThreadRepresentation`].
+///
+/// # Examples
+///
+/// ``` rust
+/// let thread = fj::Thread::metric(fj::MetricSize::M6, 20.);
+/// assert_eq!(thread.pitch, 1.);
+///
+/// // A cylinder with the nominal diameter of the thread
+/// let bolt: fj::Shape = thread.clone().into();
+///
+/// // The actual thread
+/// let bolt: fj::Shape = thread.modeled().into();
+/// ```
+///
+/// # Limitations
+///
+/// The kernel doesn't support helical surfaces yet. The modeled thread is
+/// made of flat triangles that approximate them, which makes it much more
+/// expensive than the cosmetic representation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Thread {
+    /// The nominal (major) diameter of the thread
+    pub nominal_diameter: f64,
+
+    /// The pitch of the thread
+    pub pitch: f64,
+
+    /// The length of the thread along the z-axis
+    pub length: f64,
+
+    /// How the thread is represented, if it is used as a shape
+    pub representation: ThreadRepresentation,
+}
+
+impl Thread {
+    /// Create a coarse thread of the given size
+    ///
+    /// The thread has the cosmetic representation.
+    pub fn metric(size: MetricSize, length: f64) -> Self {
+        Self {
+            nominal_diameter: size.nominal_diameter(),
+            pitch: size.coarse_pitch(),
+            length,
+            representation: ThreadRepresentation::Cosmetic,
+        }
+    }
+
+    /// Compute the height of the fundamental triangle of the thread profile
+    pub fn fundamental_height(&self) -> f64 {
+        3f64.sqrt() / 2. * self.pitch
+    }
+
+    /// Compute the pitch diameter of the thread
+    pub fn pitch_diameter(&self) -> f64 {
+        self.nominal_diameter - self.fundamental_height() * 3. / 4.
+    }
+
+    /// Compute the minor diameter of the basic profile
+    ///
+    /// This is the minor diameter of an internal thread. External threads are
+    /// usually cut slightly deeper.
+    pub fn minor_diameter(&self) -> f64 {
+        self.nominal_diameter - self.fundamental_height() * 5. / 4.
+    }
+
+    /// Represent the thread by a cylinder
+    ///
+    /// See [`ThreadRepresentation::Cosmetic`].
+    pub fn cosmetic(self) -> Self {
+        Self {
+            representation: ThreadRepresentation::Cosmetic,
+            ..self
+        }
+    }
+
+    /// Model the thread in full
+    ///
+    /// See [`ThreadRepresentation::Modeled`].
+    pub fn modeled(self) -> Self {
+        Self {
+            representation: ThreadRepresentation::Modeled,
+            ..self
+        }
+    }
+}
+
+impl From<Thread> for Shape {
+    fn from(shape: Thread) -> Self {
+        Self::Thread(shape)
+    }
+}
+
+/// How a [`Thread`] is represented, if it is used as a shape
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum ThreadRepresentation {
+    /// A cylinder with the nominal diameter of the thread
+    ///
+    /// The dimensions of the thread are still part of the shape, for example
+    /// to be exported as metadata.
+    Cosmetic,
+
+    /// The thread with its basic profile, running along a right-hand helix
+    Modeled,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MetricSize;
+
+    use super::Thread;
+
+    #[test]
+    fn iso_68_1_dimensions() {
+        // Reference values from ISO 724, which lists the basic dimensions of
+        // the profile defined by ISO 68-1.
+        let dimensions = [
+            (MetricSize::M3, 0.5, 2.675, 2.459),
+            (MetricSize::M6, 1., 5.350, 4.917),
+            (MetricSize::M10, 1.5, 9.026, 8.376),
+            (MetricSize::M12, 1.75, 10.863, 10.106),
+        ];
+
+        for (size, pitch, pitch_diameter, minor_diameter) in dimensions {
+            let thread = Thread::metric(size, 10.);
+
+            assert_eq!(thread.pitch, pitch);
+            assert!((thread.pitch_diameter() - pitch_diameter).abs() < 1e-3);
+            assert!((thread.minor_diameter() - minor_diameter).abs() < 1e-3);
+        }
+
+        let thread = Thread::metric(MetricSize::M6, 10.);
+        assert!((thread.fundamental_height() - 0.866025).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,116 @@
+//! # Fornjot
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library is the entry point for using Fornjot from other Rust programs.
+//! It turns shapes, defined using the [`fj`] crate, into triangle meshes, and
+//! exports those to files. No window is opened, and nothing is watched for
+//! changes.
+//!
+//! ``` no_run
+//! use fornjot::fj::syntax::*;
+//!
+//! let shape = [[0., 0.], [1., 0.], [0., 1.]].sketch().sweep([0., 0., 1.]);
+//!
+//! let processed = fornjot::process(shape, None)?;
+//! fornjot::export(&processed.mesh, "prism.stl".as_ref())?;
+//! # Ok::<(), fornjot::Error>(())
+//! ```
+//!
+//! For more control over processing, use [`ShapeProcessor`] directly.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+#![warn(missing_docs)]
+
+use std::path::Path;
+
+pub use fj;
+pub use fj_export::{
+    export, export_with_options, import, CoordinateSystem, ExportOptions,
+    Format, Handedness, UpAxis,
+};
+pub use fj_interop::{
+    analysis::ShapeDiff, mesh::Mesh, processed_shape::ProcessedShape,
+};
+pub use fj_math::{Point, Tolerance};
+pub use fj_operations::shape_processor::ShapeProcessor;
+
+/// Process a shape into a triangle mesh, and analyze it
+///
+/// The mesh approximates the shape within the given tolerance. If it is
+/// `None`, a tolerance is derived from the size of the shape.
+///
+/// The shape is validated while it is processed. An error is returned, if it
+/// isn't valid.
+///
+/// Processing is deterministic: The same shape and tolerance always result in
+/// the same mesh, and therefore in byte-identical exports.
+pub fn process(
+    shape: impl Into<fj::Shape>,
+    tolerance: Option<Tolerance>,
+) -> Result<ProcessedShape, Error> {
+    let shape_processor = ShapeProcessor {
+        tolerance,
+        angle_tolerance: None,
+        simplification: None,
+        debug_algorithms: false,
+        deterministic: true,
+        cache: Default::default(),
+    };
+
+    let processed = shape_processor.process(&shape.into())?;
+    Ok(processed)
+}
+
+/// Process a shape, and export the resulting mesh to a file
+///
+/// Combines [`process`] and [`export`]. The format is determined from the file
+/// extension of `path`. The processed shape is returned, so its analysis can
+/// be used too.
+pub fn process_and_export(
+    shape: impl Into<fj::Shape>,
+    tolerance: Option<Tolerance>,
+    path: &Path,
+) -> Result<ProcessedShape, Error> {
+    // Fail early, if the file format is not supported. No need to process the
+    // shape in that case.
+    Format::from_path(path)?;
+
+    let processed = process(shape, tolerance)?;
+    export(&processed.mesh, path)?;
+
+    Ok(processed)
+}
+
+/// Compare two processed shapes
+///
+/// Both meshes approximate their shape within some tolerance, so the shapes
+/// are considered to be the same, where their meshes are no further apart than
+/// the sum of those tolerances.
+///
+/// To compare a shape to a mesh that has been exported before, [`import`] the
+/// mesh and process it using [`ShapeProcessor::process_mesh`].
+///
+/// Returns `None`, if either shape is empty.
+pub fn compare(a: &ProcessedShape, b: &ProcessedShape) -> Option<ShapeDiff> {
+    fj_kernel::algorithms::analysis::diff::compare(
+        &a.mesh,
+        &b.mesh,
+        a.tolerance + b.tolerance,
+    )
+}
+
+/// An error processing or exporting a shape
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The shape could not be processed
+    #[error("Error processing shape")]
+    Processing(#[from] fj_operations::shape_processor::Error),
+
+    /// The mesh could not be exported
+    #[error("Error exporting mesh")]
+    Export(#[from] fj_export::Error),
+}
@@ -0,0 +1,167 @@
+use benchmarks::SIZES;
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+};
+use fj_kernel::{
+    algorithms::{
+        approx::Tolerance, imprint::Imprint, intersect::FaceFaceIntersection,
+        sweep::Sweep, triangulate::Triangulate,
+    },
+    builder::{CycleBuilder, FaceBuilder},
+    operations::Insert,
+    services::Services,
+    validate::Validate,
+};
+
+fn sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sweep");
+
+    for size in SIZES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |b, &size| {
+                b.iter_batched(
+                    || {
+                        let mut services = Services::new();
+                        let face =
+                            benchmarks::polygon(size, &mut services.objects);
+                        (services, face)
+                    },
+                    |(mut services, face)| {
+                        let shell =
+                            face.sweep([0., 0., 1.], &mut services.objects);
+
+                        // Dropping the services is not part of the benchmark.
+                        (services, shell)
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn triangulate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("triangulate");
+
+    for size in SIZES {
+        let mut services = Services::new();
+        let shell = benchmarks::prism(size, &mut services.objects);
+        let tolerance =
+            Tolerance::from_scalar(0.001).expect("Tolerance is positive");
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |b, _| {
+                b.iter(|| (&*shell, tolerance).triangulate());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate");
+
+    for size in SIZES {
+        let mut services = Services::new();
+        let shell = benchmarks::prism(size, &mut services.objects);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |b, _| {
+                b.iter(|| {
+                    let mut errors = Vec::new();
+                    shell.validate(&mut errors);
+                    errors
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn intersect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intersect");
+
+    for size in SIZES {
+        let mut services = Services::new();
+        let shell = benchmarks::prism(size, &mut services.objects);
+
+        // A face that cuts through the whole prism, along its axis.
+        let cutter = FaceBuilder::new(services.objects.surfaces.xz_plane())
+            .with_exterior(CycleBuilder::polygon(
+                [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+                &mut services.objects,
+            ))
+            .build(&mut services.objects);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |b, _| {
+                b.iter(|| {
+                    shell
+                        .faces()
+                        .into_iter()
+                        .filter_map(|face| {
+                            FaceFaceIntersection::compute([&**face, &cutter])
+                        })
+                        .count()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn imprint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("imprint");
+
+    for size in SIZES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |b, &size| {
+                b.iter_batched(
+                    || {
+                        let mut services = Services::new();
+                        let shell =
+                            benchmarks::prism(size, &mut services.objects);
+
+                        // A small triangle on the bottom of the prism.
+                        let tool = FaceBuilder::new(
+                            services.objects.surfaces.xy_plane(),
+                        )
+                        .with_exterior(CycleBuilder::polygon(
+                            [[-0.1, -0.1], [-0.1, 0.1], [0.1, 0.]],
+                            &mut services.objects,
+                        ))
+                        .build(&mut services.objects)
+                        .insert(&mut services.objects);
+
+                        (services, shell, tool)
+                    },
+                    |(mut services, shell, tool)| {
+                        let shell = shell.imprint(&tool, &mut services.objects);
+                        (services, shell)
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(kernel, sweep, triangulate, validate, intersect, imprint);
+criterion_main!(kernel);
@@ -0,0 +1,52 @@
+//! Parametric test models for benchmarking the kernel
+//!
+//! All models are regular prisms or faces, whose number of sides can be chosen
+//! freely. That makes it easy to see how an algorithm scales with the number
+//! of faces and edges.
+
+use std::f64::consts::TAU;
+
+use fj_kernel::{
+    algorithms::sweep::Sweep,
+    builder::{CycleBuilder, FaceBuilder},
+    objects::{Face, Objects, Shell},
+    operations::Insert,
+    services::Service,
+    storage::Handle,
+};
+use fj_math::Point;
+
+/// The model sizes that benchmarks are run with
+///
+/// Each size is the number of sides of the model.
+pub const SIZES: [usize; 4] = [8, 32, 128, 512];
+
+/// Create a regular polygon in the xy-plane
+///
+/// The polygon has a radius of `1`, and is centered on the origin.
+pub fn polygon(
+    num_sides: usize,
+    objects: &mut Service<Objects>,
+) -> Handle<Face> {
+    let points = (0..num_sides)
+        .map(|i| {
+            let angle = TAU * i as f64 / num_sides as f64;
+            Point::from([angle.cos(), angle.sin()])
+        })
+        .collect::<Vec<_>>();
+
+    FaceBuilder::new(objects.surfaces.xy_plane())
+        .with_exterior(CycleBuilder::polygon(points, objects))
+        .build(objects)
+        .insert(objects)
+}
+
+/// Create a regular prism, by sweeping a polygon along the z-axis
+///
+/// See [`polygon`]. The resulting shell has `num_sides + 2` faces.
+pub fn prism(
+    num_sides: usize,
+    objects: &mut Service<Objects>,
+) -> Handle<Shell> {
+    polygon(num_sides, objects).sweep([0., 0., 1.], objects)
+}